@@ -0,0 +1,228 @@
+//! Benchmarks for the hot paths of report generation.
+//!
+//! Run with `cargo bench`. All inputs come from a deterministic synthetic
+//! dataset (10k activity events across 50 repositories) so results are
+//! comparable between runs and machines.
+//!
+//! Performance budget (release build, 10k events):
+//!
+//! | Stage                  | Budget        |
+//! |------------------------|---------------|
+//! | Event filtering        | < 1ms         |
+//! | Grouping by repository | < 25ms        |
+//! | Prompt assembly        | < 10ms        |
+//! | Cache key generation   | < 5µs per key |
+//!
+//! A change that pushes a stage over its budget should come with a
+//! justification in the PR description.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use gh_report::activity::filter_events;
+use gh_report::cache::{generate_cache_key, CacheKeyBuilder};
+use gh_report::claude::prompts::summarize_activities_prompt;
+use gh_report::github::{
+    ActivityEvent, ActivityRepo, Author, CommentCount, Issue, IssueState, Label,
+};
+use gh_report::report::group_activities_by_repo;
+use jiff::{Timestamp, ToSpan};
+use std::collections::BTreeMap;
+
+const EVENT_COUNT: usize = 10_000;
+const REPO_COUNT: usize = 50;
+
+/// The moment the synthetic activity is dated back from
+const DATASET_NOW: &str = "2024-03-15T12:00:00Z";
+
+/// Deterministic dataset generator for benchmarks
+///
+/// Uses a small LCG instead of a random number crate so the dataset is
+/// identical on every run.
+struct Synthetic {
+    state: u64,
+    now: Timestamp,
+}
+
+impl Synthetic {
+    fn new(seed: u64) -> Self {
+        Synthetic {
+            state: seed,
+            now: DATASET_NOW.parse().unwrap(),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.state >> 33
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.next() as usize % items.len()]
+    }
+
+    fn repo_name(&mut self) -> String {
+        format!(
+            "org{}/repo{}",
+            self.next() % 10,
+            self.next() as usize % REPO_COUNT
+        )
+    }
+
+    fn timestamp(&mut self) -> Timestamp {
+        let hours_ago = (self.next() % (24 * 14)) as i64;
+        self.now - hours_ago.hours()
+    }
+
+    fn events(&mut self, count: usize) -> Vec<ActivityEvent> {
+        const TYPES: &[&str] = &[
+            "IssueCommentEvent",
+            "PullRequestEvent",
+            "IssuesEvent",
+            "PullRequestReviewCommentEvent",
+            "PullRequestReviewEvent",
+            "PushEvent",
+            "WatchEvent",
+            "CreateEvent",
+        ];
+        const ACTIONS: &[&str] = &["opened", "closed", "created", "labeled", "unlabeled"];
+
+        (0..count)
+            .map(|i| {
+                let repo = self.repo_name();
+                let number = self.next() % 5000;
+                ActivityEvent {
+                    id: i.to_string(),
+                    event_type: self.pick(TYPES).to_string(),
+                    actor: Author {
+                        login: format!("user{}", self.next() % 200),
+                        user_type: Some("User".to_string()),
+                    },
+                    repo: ActivityRepo {
+                        id: self.next(),
+                        url: format!("https://api.github.com/repos/{}", repo),
+                        name: repo,
+                    },
+                    payload: serde_json::json!({
+                        "action": self.pick(ACTIONS),
+                        "issue": { "number": number, "title": format!("Issue {}", number) },
+                    }),
+                    created_at: self.timestamp(),
+                    is_public: true,
+                }
+            })
+            .collect()
+    }
+
+    fn issues(&mut self, count: usize) -> Vec<Issue> {
+        const STATES: &[&str] = &["open", "closed", "merged"];
+
+        (0..count)
+            .map(|_| {
+                let repo = self.repo_name();
+                let number = (self.next() % 5000) as u32;
+                let is_pull_request = self.next() & 1 == 0;
+                let state = match self.pick(STATES) {
+                    "open" => IssueState::Open,
+                    "merged" if is_pull_request => IssueState::Merged,
+                    _ => IssueState::Closed,
+                };
+                let kind = if is_pull_request { "pull" } else { "issues" };
+                Issue {
+                    number,
+                    title: format!("Synthetic issue {} in {}", number, repo),
+                    body: Some("Lorem ipsum dolor sit amet. ".repeat(20)),
+                    state,
+                    author: Author {
+                        login: format!("user{}", self.next() % 200),
+                        user_type: None,
+                    },
+                    created_at: self.timestamp(),
+                    updated_at: self.timestamp(),
+                    labels: vec![Label {
+                        name: "bug".to_string(),
+                        color: None,
+                        description: None,
+                    }],
+                    url: format!("https://github.com/{}/{}/{}", repo, kind, number),
+                    comments: CommentCount {
+                        total_count: (self.next() % 30) as u32,
+                    },
+                    is_pull_request,
+                    assignees: vec![],
+                    requested_reviewers: vec![],
+                    milestone: None,
+                    author_association: None,
+                }
+            })
+            .collect()
+    }
+}
+
+fn bench_filter_events(c: &mut Criterion) {
+    let events = Synthetic::new(1).events(EVENT_COUNT);
+
+    c.bench_function("filter_events/10k", |b| {
+        b.iter(|| filter_events(black_box(&events), None, None))
+    });
+}
+
+fn bench_group_by_repo(c: &mut Criterion) {
+    let issues = Synthetic::new(2).issues(EVENT_COUNT);
+
+    c.bench_function("group_activities_by_repo/10k", |b| {
+        b.iter_batched(
+            || issues.clone(),
+            group_activities_by_repo,
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_prompt_assembly(c: &mut Criterion) {
+    let activities = group_activities_by_repo(Synthetic::new(3).issues(EVENT_COUNT));
+    let repo_contexts: BTreeMap<String, String> = (0..REPO_COUNT)
+        .step_by(5)
+        .map(|i| (format!("org0/repo{}", i), format!("Context for repo{}", i)))
+        .collect();
+
+    c.bench_function("summarize_activities_prompt/10k", |b| {
+        b.iter(|| {
+            summarize_activities_prompt(
+                black_box(&activities),
+                Some("Focus on regressions"),
+                &repo_contexts,
+            )
+        })
+    });
+}
+
+fn bench_cache_keys(c: &mut Criterion) {
+    c.bench_function("generate_cache_key/simple", |b| {
+        b.iter(|| generate_cache_key(black_box(&["github", "tokio-rs_tokio", "7"])))
+    });
+
+    c.bench_function("generate_cache_key/hashed", |b| {
+        b.iter(|| generate_cache_key(black_box(&["github", "tokio-rs/tokio", "since 7 days"])))
+    });
+
+    c.bench_function("cache_key_builder", |b| {
+        b.iter(|| {
+            CacheKeyBuilder::new()
+                .with_namespace("claude")
+                .add(black_box("claude-3-5-sonnet-20241022"))
+                .add(black_box("prompt body"))
+                .build()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_filter_events,
+    bench_group_by_repo,
+    bench_prompt_assembly,
+    bench_cache_keys
+);
+criterion_main!(benches);
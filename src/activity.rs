@@ -0,0 +1,743 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use tracing::info_span;
+
+use crate::github::{ActivityEvent, Author};
+use crate::schema::SCHEMA_VERSION;
+
+/// Event types included in activity views when no explicit filter is given
+pub const DEFAULT_EVENT_TYPES: &[&str] = &[
+    "IssueCommentEvent",
+    "PullRequestEvent",
+    "IssuesEvent",
+    "PullRequestReviewCommentEvent",
+    "PullRequestReviewEvent",
+    "DiscussionEvent",
+    "DiscussionCommentEvent",
+];
+
+/// Output format of the `activity` command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ActivityFormat {
+    /// Human-readable summary
+    #[default]
+    Text,
+    /// The grouped events as structured JSON, for scripting
+    Json,
+}
+
+/// Activity events grouped by date, repository, and issue/PR
+#[derive(Debug, Serialize)]
+pub struct ActivitySummary {
+    /// See [`crate::schema`] for the compatibility guarantees
+    pub schema_version: u32,
+    pub total_events: usize,
+    /// Events dropped by the type filters and ignored authors
+    pub filtered_out: usize,
+    /// Events by or about items of ignored authors, like bots, by login
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub ignored_authors: BTreeMap<String, usize>,
+    /// Most recent day first
+    pub days: Vec<DayActivity>,
+    /// Number of events per event type, most frequent first
+    pub event_types: Vec<EventTypeCount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayActivity {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub event_count: usize,
+    pub repos: Vec<RepoEvents>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepoEvents {
+    /// `owner/repo`
+    pub name: String,
+    pub items: Vec<ItemActivity>,
+    /// Events that don't belong to an issue or PR, like pushes and releases
+    pub other_events: Vec<OtherEvent>,
+}
+
+/// What happened on one issue or PR during a day
+#[derive(Debug, Serialize)]
+pub struct ItemActivity {
+    pub number: u64,
+    pub is_pull_request: bool,
+    pub title: Option<String>,
+    pub actions: Vec<ActionActors>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActionActors {
+    /// What was done, e.g. `opened` or `review commented`
+    pub action: String,
+    /// Logins of who did it, sorted
+    pub actors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OtherEvent {
+    pub event_type: String,
+    pub actor: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventTypeCount {
+    pub event_type: String,
+    pub count: usize,
+}
+
+/// Filter activity events down to the ones worth reporting on
+///
+/// When `include_types` is `None`, [`DEFAULT_EVENT_TYPES`] is used. Label
+/// churn on issues (`labeled` / `unlabeled`) is always dropped.
+pub fn filter_events<'a>(
+    events: &'a [ActivityEvent],
+    include_types: Option<&[String]>,
+    exclude_types: Option<&[String]>,
+) -> Vec<&'a ActivityEvent> {
+    let _span = info_span!("filter").entered();
+    events
+        .iter()
+        .filter(|event| {
+            // First check include types (default to user's preferred list if not specified)
+            let included = match include_types {
+                Some(types) => types.contains(&event.event_type),
+                None => DEFAULT_EVENT_TYPES.contains(&event.event_type.as_str()),
+            };
+            if !included {
+                return false;
+            }
+
+            // Check exclude types
+            if let Some(excluded) = exclude_types {
+                if excluded.contains(&event.event_type) {
+                    return false;
+                }
+            }
+
+            // Special filtering for IssuesEvent - exclude 'labeled' actions
+            if event.event_type == "IssuesEvent" {
+                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                    if action == "labeled" || action == "unlabeled" {
+                        return false;
+                    }
+                }
+            }
+
+            true
+        })
+        .collect()
+}
+
+/// Drop events performed by ignored authors, or about issues and PRs they opened
+///
+/// Returns the kept events and the number of dropped events per author.
+pub fn drop_ignored_authors(
+    events: Vec<&ActivityEvent>,
+    ignores: impl Fn(&Author) -> bool,
+) -> (Vec<&ActivityEvent>, BTreeMap<String, usize>) {
+    let mut ignored = BTreeMap::new();
+    let kept = events
+        .into_iter()
+        .filter(|event| {
+            let author = std::iter::once(event.actor.clone())
+                .chain(item_author(event))
+                .find(|author| ignores(author));
+            match author {
+                Some(author) => {
+                    *ignored.entry(author.login).or_default() += 1;
+                    false
+                }
+                None => true,
+            }
+        })
+        .collect();
+    (kept, ignored)
+}
+
+/// Author of the issue or PR an event is about, from its payload
+fn item_author(event: &ActivityEvent) -> Option<Author> {
+    let item = event
+        .payload
+        .get("pull_request")
+        .or_else(|| event.payload.get("issue"))?;
+    serde_json::from_value(item.get("user")?.clone()).ok()
+}
+
+/// Group filtered events by date → repo → issue/PR
+///
+/// `total` is the number of events before filtering, used to report how many
+/// were filtered out.
+pub fn summarize_activity(events: &[&ActivityEvent], total: usize) -> ActivitySummary {
+    let mut events_by_date: BTreeMap<String, BTreeMap<String, IssueEvents>> = BTreeMap::new();
+    let mut event_type_counts: HashMap<&str, usize> = HashMap::new();
+
+    for event in events {
+        let date_key = event.created_at.strftime("%Y-%m-%d").to_string();
+        events_by_date
+            .entry(date_key)
+            .or_default()
+            .entry(event.repo.name.clone())
+            .or_default()
+            .entry(extract_issue_key(event))
+            .or_default()
+            .push(event);
+        *event_type_counts.entry(&event.event_type).or_insert(0) += 1;
+    }
+
+    let days = events_by_date
+        .into_iter()
+        .rev()
+        .map(|(date, repos_events)| {
+            let event_count = repos_events
+                .values()
+                .flat_map(|issues_events| issues_events.values())
+                .map(Vec::len)
+                .sum();
+            let repos = repos_events
+                .into_iter()
+                .map(|(name, issues_events)| {
+                    let mut items = Vec::new();
+                    let mut other_events = Vec::new();
+                    for (issue_key, issue_events) in issues_events {
+                        match issue_key {
+                            Some(key) => items.push(ItemActivity {
+                                number: key.issue_number,
+                                is_pull_request: key.is_pr,
+                                // Take the title from the first event that has one
+                                title: issue_events
+                                    .iter()
+                                    .find_map(|event| extract_title_from_event(event)),
+                                actions: group_events_by_action(&issue_events),
+                            }),
+                            None => {
+                                other_events.extend(issue_events.iter().map(|event| OtherEvent {
+                                    event_type: event.event_type.clone(),
+                                    actor: event.actor.login.clone(),
+                                    description: format_activity_event(event),
+                                }))
+                            }
+                        }
+                    }
+                    RepoEvents {
+                        name,
+                        items,
+                        other_events,
+                    }
+                })
+                .collect();
+
+            DayActivity {
+                date,
+                event_count,
+                repos,
+            }
+        })
+        .collect();
+
+    let mut event_types: Vec<EventTypeCount> = event_type_counts
+        .into_iter()
+        .map(|(event_type, count)| EventTypeCount {
+            event_type: event_type.to_string(),
+            count,
+        })
+        .collect();
+    event_types.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.event_type.cmp(&b.event_type))
+    });
+
+    ActivitySummary {
+        schema_version: SCHEMA_VERSION,
+        total_events: events.len(),
+        filtered_out: total - events.len(),
+        ignored_authors: BTreeMap::new(),
+        days,
+        event_types,
+    }
+}
+
+/// Unique `(repo, number)` references to the issues and PRs the events are about
+pub fn issue_references(events: &[&ActivityEvent]) -> Vec<(String, u32)> {
+    let refs: BTreeSet<(String, u32)> = events
+        .iter()
+        .filter_map(|event| {
+            let number = match event.event_type.as_str() {
+                "PullRequestEvent" | "PullRequestReviewCommentEvent" | "PullRequestReviewEvent" => {
+                    event.payload.get("pull_request")
+                }
+                _ => event.payload.get("issue"),
+            }
+            .and_then(|item| item.get("number"))
+            .and_then(|n| n.as_u64())?;
+            Some((event.repo.name.clone(), number as u32))
+        })
+        .collect();
+    refs.into_iter().collect()
+}
+
+fn extract_issue_key(event: &ActivityEvent) -> Option<IssueKey> {
+    match event.event_type.as_str() {
+        "PullRequestEvent" => event
+            .payload
+            .get("pull_request")
+            .and_then(|pr| pr.get("number"))
+            .and_then(|n| n.as_u64())
+            .map(|pr_number| IssueKey {
+                issue_number: pr_number,
+                is_pr: true,
+            }),
+        "IssuesEvent" | "IssueCommentEvent" => {
+            if let Some(issue_number) = event
+                .payload
+                .get("issue")
+                .and_then(|issue| issue.get("number"))
+                .and_then(|n| n.as_u64())
+            {
+                // Check if this is actually a PR (issues API includes PRs)
+                let is_pr = event
+                    .payload
+                    .get("issue")
+                    .and_then(|issue| issue.get("pull_request"))
+                    .is_some();
+
+                Some(IssueKey {
+                    issue_number,
+                    is_pr,
+                })
+            } else {
+                None
+            }
+        }
+        "PullRequestReviewCommentEvent" => event
+            .payload
+            .get("pull_request")
+            .and_then(|pr| pr.get("number"))
+            .and_then(|n| n.as_u64())
+            .map(|pr_number| IssueKey {
+                issue_number: pr_number,
+                is_pr: true,
+            }),
+        _ => None,
+    }
+}
+
+/// Events for a single repository, keyed by the issue/PR they belong to
+type IssueEvents<'a> = BTreeMap<Option<IssueKey>, Vec<&'a ActivityEvent>>;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct IssueKey {
+    issue_number: u64,
+    is_pr: bool,
+}
+
+fn format_activity_event(event: &ActivityEvent) -> String {
+    let actor = &event.actor.login;
+
+    match event.event_type.as_str() {
+        "PushEvent" => {
+            if let Some(commits) = event.payload.get("commits").and_then(|c| c.as_array()) {
+                format!("@{} pushed {} commit(s)", actor, commits.len())
+            } else {
+                format!("@{} pushed commits", actor)
+            }
+        }
+        "PullRequestEvent" => {
+            if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                if let Some(pr_number) = event
+                    .payload
+                    .get("pull_request")
+                    .and_then(|pr| pr.get("number"))
+                    .and_then(|n| n.as_u64())
+                {
+                    format!("@{} {} PR #{}", actor, action, pr_number)
+                } else {
+                    format!("@{} {} pull request", actor, action)
+                }
+            } else {
+                format!("@{} pull request activity", actor)
+            }
+        }
+        "IssuesEvent" => {
+            if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                if let Some(issue_number) = event
+                    .payload
+                    .get("issue")
+                    .and_then(|issue| issue.get("number"))
+                    .and_then(|n| n.as_u64())
+                {
+                    format!("@{} {} issue #{}", actor, action, issue_number)
+                } else {
+                    format!("@{} {} issue", actor, action)
+                }
+            } else {
+                format!("@{} issue activity", actor)
+            }
+        }
+        "IssueCommentEvent" => {
+            if let Some(issue_number) = event
+                .payload
+                .get("issue")
+                .and_then(|issue| issue.get("number"))
+                .and_then(|n| n.as_u64())
+            {
+                format!("@{} commented on issue #{}", actor, issue_number)
+            } else {
+                format!("@{} commented on issue", actor)
+            }
+        }
+        "PullRequestReviewEvent" => {
+            if let Some(pr_number) = event
+                .payload
+                .get("pull_request")
+                .and_then(|pr| pr.get("number"))
+                .and_then(|n| n.as_u64())
+            {
+                format!("@{} reviewed PR #{}", actor, pr_number)
+            } else {
+                format!("@{} reviewed pull request", actor)
+            }
+        }
+        "PullRequestReviewCommentEvent" => {
+            if let Some(pr_number) = event
+                .payload
+                .get("pull_request")
+                .and_then(|pr| pr.get("number"))
+                .and_then(|n| n.as_u64())
+            {
+                format!("@{} commented on PR #{}", actor, pr_number)
+            } else {
+                format!("@{} commented on pull request", actor)
+            }
+        }
+        "CreateEvent" => {
+            if let Some(ref_type) = event.payload.get("ref_type").and_then(|r| r.as_str()) {
+                format!("@{} created {}", actor, ref_type)
+            } else {
+                format!("@{} created resource", actor)
+            }
+        }
+        "DeleteEvent" => {
+            if let Some(ref_type) = event.payload.get("ref_type").and_then(|r| r.as_str()) {
+                format!("@{} deleted {}", actor, ref_type)
+            } else {
+                format!("@{} deleted resource", actor)
+            }
+        }
+        "ForkEvent" => format!("@{} forked repository", actor),
+        "WatchEvent" => format!("@{} starred repository", actor),
+        "ReleaseEvent" => {
+            if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                format!("@{} {} release", actor, action)
+            } else {
+                format!("@{} release activity", actor)
+            }
+        }
+        "DiscussionEvent" | "DiscussionCommentEvent" => {
+            let number = event
+                .payload
+                .get("discussion")
+                .and_then(|discussion| discussion.get("number"))
+                .and_then(|n| n.as_u64());
+            match (event.event_type.as_str(), number) {
+                ("DiscussionCommentEvent", Some(number)) => {
+                    format!("@{} commented on discussion #{}", actor, number)
+                }
+                ("DiscussionCommentEvent", None) => format!("@{} commented on discussion", actor),
+                (_, Some(number)) => {
+                    let action = event
+                        .payload
+                        .get("action")
+                        .and_then(|a| a.as_str())
+                        .unwrap_or("updated");
+                    format!("@{} {} discussion #{}", actor, action, number)
+                }
+                (_, None) => format!("@{} discussion activity", actor),
+            }
+        }
+        _ => format!("@{} {} event", actor, event.event_type),
+    }
+}
+
+/// Extract title from an event payload for issues or PRs
+pub(crate) fn extract_title_from_event(event: &ActivityEvent) -> Option<String> {
+    match event.event_type.as_str() {
+        "PullRequestEvent" => event
+            .payload
+            .get("pull_request")
+            .and_then(|pr| pr.get("title"))
+            .and_then(|t| t.as_str())
+            .map(|s| s.to_string()),
+        "IssuesEvent" | "IssueCommentEvent" => event
+            .payload
+            .get("issue")
+            .and_then(|issue| issue.get("title"))
+            .and_then(|t| t.as_str())
+            .map(|s| s.to_string()),
+        "PullRequestReviewCommentEvent" | "PullRequestReviewEvent" => event
+            .payload
+            .get("pull_request")
+            .and_then(|pr| pr.get("title"))
+            .and_then(|t| t.as_str())
+            .map(|s| s.to_string()),
+        "DiscussionEvent" | "DiscussionCommentEvent" => event
+            .payload
+            .get("discussion")
+            .and_then(|discussion| discussion.get("title"))
+            .and_then(|t| t.as_str())
+            .map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+/// Group events by action and collect actors for each action
+fn group_events_by_action(events: &[&ActivityEvent]) -> Vec<ActionActors> {
+    let mut action_actors: HashMap<String, BTreeSet<String>> = HashMap::new();
+
+    for event in events {
+        let action_text = match event.event_type.as_str() {
+            "PullRequestEvent" => {
+                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                    match action {
+                        "opened" => "opened".to_string(),
+                        "closed" => "closed".to_string(),
+                        "reopened" => "reopened".to_string(),
+                        "ready_for_review" => "ready for review".to_string(),
+                        "converted_to_draft" => "converted to draft".to_string(),
+                        _ => action.to_string(),
+                    }
+                } else {
+                    "updated".to_string()
+                }
+            }
+            "IssuesEvent" => {
+                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                    match action {
+                        "opened" => "opened".to_string(),
+                        "closed" => "closed".to_string(),
+                        "reopened" => "reopened".to_string(),
+                        _ => action.to_string(),
+                    }
+                } else {
+                    "updated".to_string()
+                }
+            }
+            "IssueCommentEvent" => "commented".to_string(),
+            "PullRequestReviewEvent" => {
+                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
+                    match action {
+                        "submitted" => "reviewed".to_string(),
+                        _ => "review activity".to_string(),
+                    }
+                } else {
+                    "reviewed".to_string()
+                }
+            }
+            "PullRequestReviewCommentEvent" => "review commented".to_string(),
+            _ => event.event_type.clone(),
+        };
+
+        action_actors
+            .entry(action_text)
+            .or_default()
+            .insert(event.actor.login.clone());
+    }
+
+    let mut result: Vec<ActionActors> = action_actors
+        .into_iter()
+        .map(|(action, actors)| ActionActors {
+            action,
+            actors: actors.into_iter().collect(),
+        })
+        .collect();
+
+    // Sort actions by a reasonable order
+    result.sort_by(|a, b| {
+        let order_a = action_priority(&a.action);
+        let order_b = action_priority(&b.action);
+        order_a.cmp(&order_b).then_with(|| a.action.cmp(&b.action))
+    });
+
+    result
+}
+
+/// Get priority order for actions (lower number = higher priority)
+fn action_priority(action: &str) -> u8 {
+    match action {
+        "opened" => 1,
+        "closed" => 2,
+        "reopened" => 3,
+        "reviewed" => 4,
+        "commented" => 5,
+        "review commented" => 6,
+        _ => 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{ActivityRepo, Author};
+    use jiff::Timestamp;
+
+    fn event(event_type: &str, action: &str) -> ActivityEvent {
+        ActivityEvent {
+            id: "1".to_string(),
+            event_type: event_type.to_string(),
+            actor: Author {
+                login: "alice".to_string(),
+                user_type: None,
+            },
+            repo: ActivityRepo {
+                id: 1,
+                name: "owner/repo".to_string(),
+                url: "https://api.github.com/repos/owner/repo".to_string(),
+            },
+            payload: serde_json::json!({ "action": action }),
+            created_at: Timestamp::now(),
+            is_public: true,
+        }
+    }
+
+    fn issue_event(event_type: &str, action: &str, actor: &str, number: u64) -> ActivityEvent {
+        let mut event = event(event_type, action);
+        event.actor.login = actor.to_string();
+        event.created_at = "2024-03-06T12:00:00Z".parse().unwrap();
+        event.payload = serde_json::json!({
+            "action": action,
+            "issue": { "number": number, "title": "Runtime hangs" },
+        });
+        event
+    }
+
+    #[test]
+    fn test_action_priority() {
+        assert!(action_priority("opened") < action_priority("closed"));
+        assert!(action_priority("closed") < action_priority("commented"));
+        assert!(action_priority("reviewed") < action_priority("unknown"));
+    }
+
+    #[test]
+    fn test_summarize_activity() {
+        let mut push = event("PushEvent", "");
+        push.created_at = "2024-03-05T08:00:00Z".parse().unwrap();
+        let events = [
+            issue_event("IssuesEvent", "opened", "bob", 7),
+            issue_event("IssueCommentEvent", "created", "carol", 7),
+            issue_event("IssueCommentEvent", "created", "alice", 7),
+            push,
+        ];
+        let refs: Vec<&ActivityEvent> = events.iter().collect();
+
+        let summary = summarize_activity(&refs, 6);
+        assert_eq!(summary.total_events, 4);
+        assert_eq!(summary.filtered_out, 2);
+        // Most recent day first
+        let dates: Vec<&str> = summary.days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2024-03-06", "2024-03-05"]);
+        assert_eq!(summary.days[0].event_count, 3);
+
+        let item = &summary.days[0].repos[0].items[0];
+        assert_eq!(item.number, 7);
+        assert_eq!(item.title.as_deref(), Some("Runtime hangs"));
+        assert_eq!(item.actions[0].action, "opened");
+        assert_eq!(item.actions[1].action, "commented");
+        assert_eq!(item.actions[1].actors, ["alice", "carol"]);
+
+        let other = &summary.days[1].repos[0].other_events[0];
+        assert_eq!(other.description, "@alice pushed commits");
+        assert_eq!(summary.event_types[0].event_type, "IssueCommentEvent");
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["days"][0]["repos"][0]["name"], "owner/repo");
+        assert_eq!(
+            json["days"][0]["repos"][0]["items"][0]["is_pull_request"],
+            false
+        );
+    }
+
+    #[test]
+    fn test_issue_references() {
+        let mut review = event("PullRequestReviewEvent", "created");
+        review.payload = serde_json::json!({ "pull_request": { "number": 7 } });
+        let events = [
+            issue_event("IssueCommentEvent", "created", "alice", 12),
+            review,
+            issue_event("IssuesEvent", "closed", "bob", 12),
+            event("PushEvent", ""),
+        ];
+        let events: Vec<&ActivityEvent> = events.iter().collect();
+
+        assert_eq!(
+            issue_references(&events),
+            [
+                ("owner/repo".to_string(), 7),
+                ("owner/repo".to_string(), 12)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drop_ignored_authors() {
+        let mut bot_pr = event("PullRequestEvent", "closed");
+        bot_pr.payload = serde_json::json!({
+            "action": "closed",
+            "pull_request": { "number": 3, "user": { "login": "renovate[bot]", "type": "Bot" } },
+        });
+        let mut ci = issue_event("IssueCommentEvent", "created", "ci-bot", 12);
+        ci.actor.user_type = Some("Bot".to_string());
+        let events = [
+            issue_event("IssueCommentEvent", "created", "alice", 12),
+            bot_pr,
+            ci,
+            issue_event("IssuesEvent", "opened", "dependabot[bot]", 13),
+        ];
+        let events: Vec<&ActivityEvent> = events.iter().collect();
+
+        let (kept, ignored) = drop_ignored_authors(events, Author::is_bot);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].actor.login, "alice");
+        assert_eq!(
+            ignored,
+            BTreeMap::from([
+                ("ci-bot".to_string(), 1),
+                ("dependabot[bot]".to_string(), 1),
+                ("renovate[bot]".to_string(), 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_filter_events_defaults() {
+        let events = vec![
+            event("IssuesEvent", "opened"),
+            event("IssuesEvent", "labeled"),
+            event("WatchEvent", "started"),
+            event("PullRequestEvent", "opened"),
+        ];
+
+        let filtered = filter_events(&events, None, None);
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|e| e.payload["action"] == "opened"));
+    }
+
+    #[test]
+    fn test_filter_events_include_exclude() {
+        let events = vec![
+            event("WatchEvent", "started"),
+            event("PullRequestEvent", "opened"),
+        ];
+
+        let include = vec!["WatchEvent".to_string(), "PullRequestEvent".to_string()];
+        let exclude = vec!["PullRequestEvent".to_string()];
+        let filtered = filter_events(&events, Some(&include), Some(&exclude));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].event_type, "WatchEvent");
+    }
+}
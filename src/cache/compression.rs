@@ -56,7 +56,7 @@ mod tests {
         let original = b"Hello, world! This is a test string that should compress well.";
 
         let compressed = compress_data(original).unwrap();
-        assert!(compressed.len() > 0);
+        assert!(!compressed.is_empty());
 
         let decompressed = decompress_data(&compressed).unwrap();
         assert_eq!(decompressed, original);
@@ -50,6 +50,7 @@ impl CacheKeyBuilder {
     }
 
     /// Add a component to the key
+    #[allow(clippy::should_implement_trait)]
     pub fn add(mut self, component: impl Into<String>) -> Self {
         self.components.push(component.into());
         self
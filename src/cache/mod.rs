@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
 use jiff::Timestamp;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use std::time::SystemTime;
+use tracing::{debug, info, warn};
+
+use crate::github::{ActivityFeed, Comment, Issue};
 
 mod compression;
 mod key_gen;
@@ -11,13 +15,44 @@ mod storage;
 
 pub use compression::{compress_data, decompress_data};
 pub use key_gen::{generate_cache_key, CacheKeyBuilder};
-pub use storage::{CacheEntry, CacheStorage};
+pub use storage::{CacheEntry, CacheStorage, CACHE_SCHEMA_VERSION, ENTRY_HEADER_LEN};
+
+/// Issue contexts outlive regular cache entries so threads can be followed
+/// across consecutive reports
+const ISSUE_CONTEXT_TTL_HOURS: u32 = 14 * 24;
+
+/// Activity feeds are reused for a short while only, as new events keep coming in
+const DEFAULT_ACTIVITY_TTL_MINUTES: u32 = 15;
+
+/// Subdirectories with entries that expire and can be evicted
+const ENTRY_DIRS: [&str; 3] = ["github", "claude", "contexts"];
+
+/// Running totals of evicted entries, in the cache directory
+const EVICTIONS_FILE: &str = "evictions.json";
+
+/// Where cached data is stored without `cache.cache_dir`
+///
+/// `$XDG_CACHE_HOME/gh-report` when it is set, on every platform, otherwise
+/// the platform's cache directory. See [`crate::Config::cache_dir`].
+pub fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("gh-report")
+}
 
 /// Main cache manager
 pub struct CacheManager {
     cache_dir: PathBuf,
     ttl_hours: u32,
     compression_enabled: bool,
+    activity_ttl_minutes: u32,
+    /// Size the cache is evicted down to, in bytes
+    max_size_bytes: Option<u64>,
+    /// Serve entries however old they are
+    offline: bool,
 }
 
 impl CacheManager {
@@ -27,9 +62,34 @@ impl CacheManager {
             cache_dir,
             ttl_hours,
             compression_enabled,
+            activity_ttl_minutes: DEFAULT_ACTIVITY_TTL_MINUTES,
+            max_size_bytes: None,
+            offline: false,
         }
     }
 
+    /// Keep the cache under `mb` megabytes with [`Self::evict_to_max_size`] (0 for no limit)
+    pub fn with_max_size_mb(mut self, mb: u64) -> Self {
+        self.max_size_bytes = (mb > 0).then(|| mb * 1024 * 1024);
+        self
+    }
+
+    /// Reuse cached activity feeds for `minutes` instead of the default 15
+    pub fn with_activity_ttl_minutes(mut self, minutes: u32) -> Self {
+        self.activity_ttl_minutes = minutes;
+        self
+    }
+
+    /// Keep serving expired entries, for runs that can't fetch anything anew
+    pub fn with_offline(mut self) -> Self {
+        self.offline = true;
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
     /// Initialize cache directory structure
     pub fn initialize(&self) -> Result<()> {
         // Create cache subdirectories
@@ -57,6 +117,27 @@ impl CacheManager {
         self.cache_data(&path, data)
     }
 
+    /// Get a cached activity feed, if it was fetched within the activity TTL
+    pub fn get_activity_feed(&self, key: &str) -> Result<Option<ActivityFeed>> {
+        let path = self.cache_dir.join("github").join(format!("{}.cache", key));
+        let max_age = std::time::Duration::from_secs(u64::from(self.activity_ttl_minutes) * 60);
+        let result = self.read_cached_data(&path, max_age);
+        crate::stats::record_cache_lookup(matches!(result, Ok(Some(_))));
+        match result? {
+            Some(data) => serde_json::from_slice(&data)
+                .map(Some)
+                .context("Failed to deserialize cached activity feed"),
+            None => Ok(None),
+        }
+    }
+
+    /// Cache an activity feed
+    pub fn cache_activity_feed(&self, key: &str, feed: &ActivityFeed) -> Result<()> {
+        let path = self.cache_dir.join("github").join(format!("{}.cache", key));
+        let data = serde_json::to_vec(feed).context("Failed to serialize activity feed")?;
+        self.cache_data(&path, &data)
+    }
+
     /// Get cached Claude response
     pub fn get_claude_response(&self, key: &str) -> Result<Option<String>> {
         let path = self.cache_dir.join("claude").join(format!("{}.cache", key));
@@ -91,7 +172,11 @@ impl CacheManager {
                 serde_json::from_slice(&data).context("Failed to deserialize issue context")?;
 
             // Check if context is still valid
-            if self.is_valid_timestamp(context.cached_at) {
+            let age_hours = Timestamp::now()
+                .duration_since(context.cached_at)
+                .as_hours();
+            if age_hours < i64::from(ISSUE_CONTEXT_TTL_HOURS.max(self.ttl_hours)) {
+                mark_used(&path);
                 Ok(Some(context))
             } else {
                 // Context expired, remove it
@@ -119,12 +204,84 @@ impl CacheManager {
         let data =
             serde_json::to_vec_pretty(context).context("Failed to serialize issue context")?;
 
-        fs::write(&path, data)
+        self.write_file(&path, &data)
             .with_context(|| format!("Failed to write context cache: {:?}", path))?;
 
         Ok(())
     }
 
+    /// Get the last `summarize` result for an issue/PR
+    ///
+    /// Checkpoints don't expire by age: callers compare `updated_at` with the
+    /// issue to tell whether the summary is still current.
+    pub fn get_summary_checkpoint(
+        &self,
+        repo: &str,
+        issue_number: u32,
+    ) -> Result<Option<SummaryCheckpoint>> {
+        let path = self.summary_checkpoint_path(repo, issue_number);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = fs::read(&path)
+            .with_context(|| format!("Failed to read summary checkpoint: {:?}", path))?;
+        let checkpoint =
+            serde_json::from_slice(&data).context("Failed to deserialize summary checkpoint")?;
+        mark_used(&path);
+        Ok(Some(checkpoint))
+    }
+
+    /// Cache a `summarize` result, replacing the previous one for the issue/PR
+    pub fn cache_summary_checkpoint(&self, checkpoint: &SummaryCheckpoint) -> Result<()> {
+        let path = self.summary_checkpoint_path(&checkpoint.repo, checkpoint.issue_number);
+        let data = serde_json::to_vec_pretty(checkpoint)
+            .context("Failed to serialize summary checkpoint")?;
+
+        self.write_file(&path, &data)
+            .with_context(|| format!("Failed to write summary checkpoint: {:?}", path))?;
+
+        Ok(())
+    }
+
+    fn summary_checkpoint_path(&self, repo: &str, issue_number: u32) -> PathBuf {
+        self.cache_dir.join("contexts").join(format!(
+            "summary_{}_{}.json",
+            repo.replace('/', "_"),
+            issue_number
+        ))
+    }
+
+    /// Get the data fetched by an interrupted report, if it is within the TTL
+    pub fn get_partial_run(&self) -> Result<Option<PartialRun>> {
+        let max_age = std::time::Duration::from_secs(u64::from(self.ttl_hours) * 3600);
+        match self.read_cached_data(&self.partial_run_path(), max_age)? {
+            Some(data) => serde_json::from_slice(&data)
+                .map(Some)
+                .context("Failed to deserialize interrupted report"),
+            None => Ok(None),
+        }
+    }
+
+    /// Save the data a report fetched so far, for `report --resume`
+    pub fn cache_partial_run(&self, run: &PartialRun) -> Result<()> {
+        let data = serde_json::to_vec(run).context("Failed to serialize interrupted report")?;
+        self.cache_data(&self.partial_run_path(), &data)
+    }
+
+    /// Forget the fetched data once the report completed
+    pub fn clear_partial_run(&self) -> Result<()> {
+        let path = self.partial_run_path();
+        if path.exists() {
+            fs::remove_file(&path).with_context(|| format!("Failed to remove {:?}", path))?;
+        }
+        Ok(())
+    }
+
+    fn partial_run_path(&self) -> PathBuf {
+        self.cache_dir.join("temp").join("partial_run.cache")
+    }
+
     /// Clear all cache
     pub fn clear_all(&self) -> Result<()> {
         info!("Clearing all cache at {:?}", self.cache_dir);
@@ -144,11 +301,16 @@ impl CacheManager {
     pub fn clear_expired(&self) -> Result<usize> {
         let mut removed = 0;
 
-        for subdir in &["github", "claude", "contexts"] {
+        for subdir in &ENTRY_DIRS {
             let dir = self.cache_dir.join(subdir);
             if !dir.exists() {
                 continue;
             }
+            let ttl_hours = match *subdir {
+                "contexts" => ISSUE_CONTEXT_TTL_HOURS.max(self.ttl_hours),
+                _ => self.ttl_hours,
+            };
+            let max_age = std::time::Duration::from_secs((ttl_hours as u64) * 3600);
 
             for entry in fs::read_dir(&dir)? {
                 let entry = entry?;
@@ -158,9 +320,6 @@ impl CacheManager {
                     if let Ok(metadata) = entry.metadata() {
                         if let Ok(modified) = metadata.modified() {
                             let age = modified.elapsed().unwrap_or_default();
-                            let max_age =
-                                std::time::Duration::from_secs((self.ttl_hours as u64) * 3600);
-
                             if age > max_age {
                                 debug!("Removing expired cache: {:?}", path);
                                 let _ = fs::remove_file(&path);
@@ -179,11 +338,84 @@ impl CacheManager {
         Ok(removed)
     }
 
+    /// Remove the least recently used entries until the cache fits in `cache.max_size_mb`
+    ///
+    /// An entry was last used when it was last read or written. The entries
+    /// evicted are added to the totals shown by [`Self::get_stats`].
+    pub fn evict_to_max_size(&self) -> Result<Eviction> {
+        let Some(max_size) = self.max_size_bytes else {
+            return Ok(Eviction::default());
+        };
+
+        let mut entries = Vec::new();
+        for subdir in ENTRY_DIRS {
+            let dir = self.cache_dir.join(subdir);
+            if !dir.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.is_file() {
+                    entries.push((last_used(&metadata), metadata.len(), entry.path()));
+                }
+            }
+        }
+
+        let mut size: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        let mut eviction = Eviction::default();
+        if size <= max_size {
+            return Ok(eviction);
+        }
+        entries.sort();
+        for (_, len, path) in entries {
+            if size <= max_size {
+                break;
+            }
+            debug!("Evicting cache entry: {:?}", path);
+            if fs::remove_file(&path).is_ok() {
+                size -= len;
+                eviction.entries += 1;
+                eviction.size += len;
+            }
+        }
+
+        info!(
+            "Evicted {} cache entries ({}) to stay under {}",
+            eviction.entries,
+            format_size(eviction.size),
+            format_size(max_size)
+        );
+        let mut totals = self.eviction_totals();
+        totals.entries += eviction.entries;
+        totals.size += eviction.size;
+        match serde_json::to_vec(&totals) {
+            Ok(data) => {
+                if let Err(e) = self.write_file(&self.cache_dir.join(EVICTIONS_FILE), &data) {
+                    warn!("Failed to record cache evictions: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to record cache evictions: {}", e),
+        }
+        Ok(eviction)
+    }
+
+    /// Entries evicted since the cache was last cleared
+    fn eviction_totals(&self) -> Eviction {
+        fs::read(self.cache_dir.join(EVICTIONS_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
     /// Get cache statistics
     pub fn get_stats(&self) -> Result<CacheStats> {
         let mut stats = CacheStats::default();
+        let evicted = self.eviction_totals();
+        stats.evicted_entries = evicted.entries;
+        stats.evicted_size = evicted.size;
 
-        for subdir in &["github", "claude", "contexts"] {
+        for subdir in &ENTRY_DIRS {
             let dir = self.cache_dir.join(subdir);
             if !dir.exists() {
                 continue;
@@ -197,10 +429,19 @@ impl CacheManager {
                     stats.total_entries += 1;
                     stats.total_size += metadata.len();
 
-                    match subdir.as_ref() {
-                        "github" => stats.github_entries += 1,
-                        "claude" => stats.claude_entries += 1,
-                        "contexts" => stats.context_entries += 1,
+                    match *subdir {
+                        "github" => {
+                            stats.github_entries += 1;
+                            stats.github_size += metadata.len();
+                        }
+                        "claude" => {
+                            stats.claude_entries += 1;
+                            stats.claude_size += metadata.len();
+                        }
+                        "contexts" => {
+                            stats.context_entries += 1;
+                            stats.context_size += metadata.len();
+                        }
                         _ => {}
                     }
                 }
@@ -213,6 +454,17 @@ impl CacheManager {
     // Helper methods
 
     fn get_cached_data(&self, path: &Path) -> Result<Option<Vec<u8>>> {
+        let max_age = std::time::Duration::from_secs((self.ttl_hours as u64) * 3600);
+        let result = self.read_cached_data(path, max_age);
+        crate::stats::record_cache_lookup(matches!(result, Ok(Some(_))));
+        result
+    }
+
+    fn read_cached_data(
+        &self,
+        path: &Path,
+        max_age: std::time::Duration,
+    ) -> Result<Option<Vec<u8>>> {
         if !path.exists() {
             return Ok(None);
         }
@@ -221,21 +473,33 @@ impl CacheManager {
         let metadata = fs::metadata(path)?;
         if let Ok(modified) = metadata.modified() {
             let age = modified.elapsed().unwrap_or_default();
-            let max_age = std::time::Duration::from_secs((self.ttl_hours as u64) * 3600);
 
-            if age > max_age {
+            if age > max_age && !self.offline {
                 debug!("Cache expired: {:?}", path);
                 let _ = fs::remove_file(path);
                 return Ok(None);
             }
         }
 
-        let data = fs::read(path).with_context(|| format!("Failed to read cache: {:?}", path))?;
+        let bytes = fs::read(path).with_context(|| format!("Failed to read cache: {:?}", path))?;
 
-        if self.compression_enabled {
-            decompress_data(&data).map(Some)
+        // Entries of an older layout, or cut short, are dropped like expired ones
+        let entry = match CacheEntry::decode(&entry_key(path), &bytes) {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!("Dropping unusable cache entry {:?}: {}", path, e);
+                let _ = fs::remove_file(path);
+                return Ok(None);
+            }
+        };
+
+        mark_used(path);
+
+        // As written, whatever `compression_enabled` is now
+        if entry.metadata.compressed {
+            decompress_data(&entry.data).map(Some)
         } else {
-            Ok(Some(data))
+            Ok(Some(entry.data))
         }
     }
 
@@ -245,21 +509,53 @@ impl CacheManager {
         } else {
             data.to_vec()
         };
+        let entry = CacheEntry::new(entry_key(path), data_to_store)
+            .with_compression(self.compression_enabled);
 
-        fs::write(path, data_to_store)
+        self.write_file(path, &entry.encode())
             .with_context(|| format!("Failed to write cache: {:?}", path))?;
 
         debug!("Cached data to {:?}", path);
         Ok(())
     }
 
-    fn is_valid_timestamp(&self, timestamp: Timestamp) -> bool {
-        let now = Timestamp::now();
-        let age_hours = ((now - timestamp).get_hours() as u32).max(0);
-        age_hours < self.ttl_hours
+    /// Write a cache file under the cache lock, so concurrent runs don't interleave writes
+    fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
+        crate::lock::with_lock(&self.cache_dir.join(".lock"), || {
+            fs::write(path, data)?;
+            Ok(())
+        })
+    }
+}
+
+/// When an entry was last read or written, for eviction
+fn last_used(metadata: &fs::Metadata) -> SystemTime {
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    metadata
+        .accessed()
+        .map_or(modified, |accessed| accessed.max(modified))
+}
+
+/// Record a read of the entry at `path` in its access time
+///
+/// The access time is set explicitly, as file systems mounted with
+/// `noatime` or `relatime` don't keep it current. The modification time,
+/// which entries expire by, is left alone.
+fn mark_used(path: &Path) {
+    let result = fs::File::open(path)
+        .and_then(|file| file.set_times(fs::FileTimes::new().set_accessed(SystemTime::now())));
+    if let Err(e) = result {
+        debug!("Failed to mark {:?} as used: {}", path, e);
     }
 }
 
+/// Key of the cache entry stored at `path`
+fn entry_key(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
 /// Cached issue context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueContext {
@@ -271,6 +567,69 @@ pub struct IssueContext {
     pub cached_at: Timestamp,
 }
 
+/// A summary produced by `summarize`, with what it was generated from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryCheckpoint {
+    pub issue_number: u32,
+    pub repo: String,
+    /// `updated_at` of the issue/PR when it was summarized
+    pub updated_at: Timestamp,
+    pub model: String,
+    pub include_recommendations: bool,
+    /// Whether the summary was grounded in the PR's patches (`--with-diff`)
+    #[serde(default)]
+    pub with_diff: bool,
+    /// `claude.output_language` the summary was written in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub summary: String,
+    pub cached_at: Timestamp,
+}
+
+impl SummaryCheckpoint {
+    /// Whether the summary still describes the issue as of `updated_at`,
+    /// generated the same way
+    pub fn is_current(
+        &self,
+        updated_at: Timestamp,
+        model: &str,
+        include_recommendations: bool,
+    ) -> bool {
+        self.updated_at == updated_at
+            && self.model == model
+            && self.include_recommendations == include_recommendations
+    }
+}
+
+/// What a report fetched before it was interrupted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialRun {
+    /// Which report this is (organization, users, repositories), as it can
+    /// only be resumed by the same kind of report
+    pub scope: String,
+    /// Start of the report window
+    pub since: Timestamp,
+    pub started_at: Timestamp,
+    /// Issues and PRs per repository, for reports that discover repositories
+    #[serde(default)]
+    pub repos: BTreeMap<String, Vec<Issue>>,
+    /// Issues and PRs with their comments, for reports from the activity feed
+    #[serde(default)]
+    pub items: Vec<(Issue, Vec<Comment>)>,
+}
+
+impl PartialRun {
+    pub fn new(scope: String, since: Timestamp) -> Self {
+        PartialRun {
+            scope,
+            since,
+            started_at: Timestamp::now(),
+            repos: BTreeMap::new(),
+            items: Vec::new(),
+        }
+    }
+}
+
 /// Cache statistics
 #[derive(Debug, Default)]
 pub struct CacheStats {
@@ -279,21 +638,40 @@ pub struct CacheStats {
     pub github_entries: usize,
     pub claude_entries: usize,
     pub context_entries: usize,
+    pub github_size: u64,
+    pub claude_size: u64,
+    pub context_size: u64,
+    /// Entries evicted to stay under `cache.max_size_mb` since the cache was cleared
+    pub evicted_entries: usize,
+    pub evicted_size: u64,
+}
+
+/// Entries removed by [`CacheManager::evict_to_max_size`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eviction {
+    pub entries: usize,
+    /// Bytes freed
+    pub size: u64,
 }
 
 impl CacheStats {
     /// Get human-readable size
     pub fn size_human(&self) -> String {
-        let size = self.total_size as f64;
-        if size < 1024.0 {
-            format!("{} B", size)
-        } else if size < 1024.0 * 1024.0 {
-            format!("{:.2} KB", size / 1024.0)
-        } else if size < 1024.0 * 1024.0 * 1024.0 {
-            format!("{:.2} MB", size / (1024.0 * 1024.0))
-        } else {
-            format!("{:.2} GB", size / (1024.0 * 1024.0 * 1024.0))
-        }
+        format_size(self.total_size)
+    }
+}
+
+/// Human-readable size of `bytes`
+pub fn format_size(bytes: u64) -> String {
+    let size = bytes as f64;
+    if size < 1024.0 {
+        format!("{} B", size)
+    } else if size < 1024.0 * 1024.0 {
+        format!("{:.2} KB", size / 1024.0)
+    } else if size < 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.2} MB", size / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} GB", size / (1024.0 * 1024.0 * 1024.0))
     }
 }
 
@@ -329,6 +707,28 @@ mod tests {
         assert_eq!(retrieved, Some(data.to_vec()));
     }
 
+    #[test]
+    fn test_partial_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = CacheManager::new(temp_dir.path().to_path_buf(), 24, true);
+        manager.initialize().unwrap();
+        assert!(manager.get_partial_run().unwrap().is_none());
+
+        let mut run = PartialRun::new("org=tokio-rs".to_string(), Timestamp::now());
+        run.repos.insert(
+            "tokio-rs/tokio".to_string(),
+            vec![crate::test_utils::create_test_issue(1, "Panic", false)],
+        );
+        manager.cache_partial_run(&run).unwrap();
+
+        let cached = manager.get_partial_run().unwrap().unwrap();
+        assert_eq!(cached.scope, "org=tokio-rs");
+        assert_eq!(cached.repos["tokio-rs/tokio"][0].number, 1);
+
+        manager.clear_partial_run().unwrap();
+        assert!(manager.get_partial_run().unwrap().is_none());
+    }
+
     #[test]
     fn test_cache_stats() {
         let temp_dir = TempDir::new().unwrap();
@@ -343,5 +743,150 @@ mod tests {
         assert_eq!(stats.total_entries, 2);
         assert_eq!(stats.github_entries, 1);
         assert_eq!(stats.claude_entries, 1);
+        assert_eq!(stats.github_size, (ENTRY_HEADER_LEN + 5) as u64);
+        assert_eq!(stats.github_size + stats.claude_size, stats.total_size);
+    }
+
+    #[test]
+    fn test_clear_expired_keeps_contexts_longer() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        manager.initialize().unwrap();
+
+        manager.cache_github_response("old", b"data").unwrap();
+        let context = IssueContext {
+            issue_number: 1,
+            repo: "tokio-rs/tokio".to_string(),
+            summary: "Summary".to_string(),
+            key_points: Vec::new(),
+            last_processed_comment_id: None,
+            cached_at: Timestamp::now(),
+        };
+        manager
+            .cache_issue_context("tokio-rs/tokio", 1, &context)
+            .unwrap();
+
+        // Two days old: past the regular TTL, within the context TTL
+        let two_days_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(48 * 3600);
+        for dir in ["github", "contexts"] {
+            for entry in fs::read_dir(temp_dir.path().join(dir)).unwrap() {
+                let file = fs::File::options()
+                    .write(true)
+                    .open(entry.unwrap().path())
+                    .unwrap();
+                file.set_modified(two_days_ago).unwrap();
+            }
+        }
+
+        assert_eq!(manager.clear_expired().unwrap(), 1);
+        let stats = manager.get_stats().unwrap();
+        assert_eq!((stats.github_entries, stats.context_entries), (0, 1));
+    }
+
+    #[test]
+    fn test_offline_reads_expired_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = CacheManager::new(temp_dir.path().to_path_buf(), 1, false);
+        manager.initialize().unwrap();
+        manager.cache_github_response("old", b"data").unwrap();
+
+        let two_hours_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 3600);
+        for entry in fs::read_dir(temp_dir.path().join("github")).unwrap() {
+            let file = fs::File::options()
+                .write(true)
+                .open(entry.unwrap().path())
+                .unwrap();
+            file.set_modified(two_hours_ago).unwrap();
+        }
+
+        let offline = CacheManager::new(temp_dir.path().to_path_buf(), 1, false).with_offline();
+        assert_eq!(
+            offline.get_github_response("old").unwrap(),
+            Some(b"data".to_vec())
+        );
+        assert_eq!(manager.get_github_response("old").unwrap(), None);
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let temp_dir = TempDir::new().unwrap();
+        let entry_size = (ENTRY_HEADER_LEN + 1000) as u64;
+        let mut manager = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        manager.initialize().unwrap();
+        // No limit set
+        assert_eq!(manager.evict_to_max_size().unwrap(), Eviction::default());
+
+        let hours_ago =
+            |hours: u64| SystemTime::now() - std::time::Duration::from_secs(hours * 3600);
+        for (key, hours) in [("a", 3), ("b", 2), ("c", 1)] {
+            manager.cache_github_response(key, &[0; 1000]).unwrap();
+            let path = temp_dir
+                .path()
+                .join("github")
+                .join(format!("{}.cache", key));
+            let file = fs::File::options().write(true).open(path).unwrap();
+            file.set_times(
+                fs::FileTimes::new()
+                    .set_modified(hours_ago(hours))
+                    .set_accessed(hours_ago(hours)),
+            )
+            .unwrap();
+        }
+        // Reading the oldest entry makes it the most recently used one
+        assert!(manager.get_github_response("a").unwrap().is_some());
+
+        manager.max_size_bytes = Some(2 * entry_size);
+        let eviction = manager.evict_to_max_size().unwrap();
+        assert_eq!(
+            eviction,
+            Eviction {
+                entries: 1,
+                size: entry_size
+            }
+        );
+        assert!(manager.get_github_response("b").unwrap().is_none());
+        assert!(manager.get_github_response("a").unwrap().is_some());
+
+        // Within the limit, nothing more goes
+        assert_eq!(manager.evict_to_max_size().unwrap(), Eviction::default());
+        let stats = manager.get_stats().unwrap();
+        assert_eq!(stats.github_entries, 2);
+        assert_eq!((stats.evicted_entries, stats.evicted_size), (1, entry_size));
+    }
+
+    #[test]
+    fn test_unusable_entries_are_dropped() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = CacheManager::new(temp_dir.path().to_path_buf(), 24, true);
+        manager.initialize().unwrap();
+
+        // Written before entries had a header
+        let path = temp_dir.path().join("github").join("old.cache");
+        fs::write(&path, compress_data(b"data").unwrap()).unwrap();
+        assert_eq!(manager.get_github_response("old").unwrap(), None);
+        assert!(!path.exists());
+
+        // Cut short while writing
+        manager
+            .cache_claude_response("summary", "A summary")
+            .unwrap();
+        let path = temp_dir.path().join("claude").join("summary.cache");
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 2]).unwrap();
+        assert_eq!(manager.get_claude_response("summary").unwrap(), None);
+        assert!(!path.exists());
+
+        // Compressed entries stay readable with compression turned off
+        manager
+            .cache_claude_response("summary", "A summary")
+            .unwrap();
+        let uncompressed = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        assert_eq!(
+            uncompressed
+                .get_claude_response("summary")
+                .unwrap()
+                .as_deref(),
+            Some("A summary")
+        );
     }
 }
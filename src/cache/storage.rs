@@ -1,6 +1,7 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use jiff::Timestamp;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::PathBuf;
 use tracing::debug;
@@ -118,6 +119,19 @@ impl CacheStorage {
     }
 }
 
+/// Leading bytes of every cache file written through [`CacheEntry::encode`]
+const ENTRY_MAGIC: &[u8; 4] = b"GHRC";
+
+/// Version of the cache file layout; files of any other version are dropped
+pub const CACHE_SCHEMA_VERSION: u8 = 1;
+
+/// Bytes before the data of a cache file: magic, version, flags, creation
+/// time, and the SHA-256 of the data
+pub const ENTRY_HEADER_LEN: usize = 4 + 1 + 1 + 8 + 32;
+
+/// Flag of entries whose data is gzip-compressed
+const FLAG_COMPRESSED: u8 = 1;
+
 /// Cache entry with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheEntry {
@@ -175,6 +189,56 @@ impl CacheEntry {
             false
         }
     }
+
+    /// The entry as written to a cache file, with a header in front of the data
+    ///
+    /// The header holds the magic bytes, [`CACHE_SCHEMA_VERSION`], whether the
+    /// data is compressed, the creation time, and a checksum of the data.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(ENTRY_HEADER_LEN + self.data.len());
+        bytes.extend_from_slice(ENTRY_MAGIC);
+        bytes.push(CACHE_SCHEMA_VERSION);
+        bytes.push(if self.metadata.compressed {
+            FLAG_COMPRESSED
+        } else {
+            0
+        });
+        bytes.extend_from_slice(&self.created_at.as_second().to_be_bytes());
+        bytes.extend_from_slice(&Sha256::digest(&self.data));
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    /// Read an entry written by [`Self::encode`]
+    ///
+    /// Fails for files of another format or version, and for data that
+    /// doesn't match its checksum, like a file cut short by a crash.
+    pub fn decode(key: &str, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < ENTRY_HEADER_LEN || &bytes[..4] != ENTRY_MAGIC {
+            bail!("not a cache entry");
+        }
+        let version = bytes[4];
+        if version != CACHE_SCHEMA_VERSION {
+            bail!(
+                "cache entry version {} (expected {})",
+                version,
+                CACHE_SCHEMA_VERSION
+            );
+        }
+        let created_at = i64::from_be_bytes(bytes[6..14].try_into()?);
+        let checksum = &bytes[14..ENTRY_HEADER_LEN];
+        let data = &bytes[ENTRY_HEADER_LEN..];
+        if Sha256::digest(data).as_slice() != checksum {
+            bail!("cache entry checksum mismatch");
+        }
+
+        let mut entry = CacheEntry::new(key.to_string(), data.to_vec())
+            .with_compression(bytes[5] & FLAG_COMPRESSED != 0);
+        entry.created_at = Timestamp::from_second(created_at)
+            .map_err(|e| anyhow!("invalid cache entry time: {}", e))?;
+        entry.metadata.checksum = Some(format!("{:x}", Sha256::digest(data)));
+        Ok(entry)
+    }
 }
 
 #[cfg(test)]
@@ -234,4 +298,31 @@ mod tests {
         let expired_entry = CacheEntry::new("key".to_string(), vec![]).with_expiration(past);
         assert!(expired_entry.is_expired());
     }
+
+    #[test]
+    fn test_cache_entry_envelope() {
+        let entry = CacheEntry::new("key".to_string(), b"payload".to_vec()).with_compression(true);
+        let bytes = entry.encode();
+        assert_eq!(bytes.len(), ENTRY_HEADER_LEN + 7);
+
+        let decoded = CacheEntry::decode("key", &bytes).unwrap();
+        assert_eq!(decoded.data, b"payload");
+        assert!(decoded.metadata.compressed);
+        assert_eq!(decoded.created_at.as_second(), entry.created_at.as_second());
+        assert!(decoded.metadata.checksum.is_some());
+
+        // Cut short, as by a crash while writing
+        let err = CacheEntry::decode("key", &bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err.to_string(), "cache entry checksum mismatch");
+        // Written before entries had a header
+        let err = CacheEntry::decode("key", b"{\"old\": true}").unwrap_err();
+        assert_eq!(err.to_string(), "not a cache entry");
+        // Written by a later version
+        let mut newer = bytes.clone();
+        newer[4] = CACHE_SCHEMA_VERSION + 1;
+        assert!(CacheEntry::decode("key", &newer)
+            .unwrap_err()
+            .to_string()
+            .starts_with("cache entry version"));
+    }
 }
@@ -0,0 +1,445 @@
+use super::{ClaudeCLI, ClaudeClient, MessagesRequest, MessagesResponse, OpenAiClient};
+use crate::config::{Config, LlmProvider};
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tracing::{info, info_span, warn};
+
+/// A provider that prompts are sent to
+///
+/// The report generator and the summarizer only talk to this trait. Which
+/// implementation they get is decided by `[llm] provider` in the config.
+/// Backends are shared between threads for requests that are sent in parallel.
+pub trait LlmBackend: Send + Sync {
+    /// Short name of the provider, for logs
+    fn name(&self) -> &'static str;
+
+    /// Where prompts are sent, for messages to the user, e.g. `the Anthropic API`
+    fn destination(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// Send a request to the provider
+    fn send(&self, request: MessagesRequest) -> Result<MessagesResponse>;
+
+    /// Send a request, passing the text of the response to `on_text` as it arrives
+    ///
+    /// Providers that can't stream pass the whole text once the response is in.
+    fn send_streaming(
+        &self,
+        request: MessagesRequest,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        let response = self.send(request)?;
+        on_text(&response.get_text());
+        Ok(response)
+    }
+
+    /// Why requests moved to another provider during the run, if they did
+    fn fallback_reason(&self) -> Option<String> {
+        None
+    }
+
+    /// Send a messages request, recording it in the usage statistics
+    fn messages(&self, request: MessagesRequest) -> Result<MessagesResponse> {
+        let _span = info_span!("claude", backend = self.name(), model = %request.model).entered();
+        crate::stats::record_claude_call();
+        let result = self.send(request);
+        record_result(&result);
+        result
+    }
+
+    /// Stream a messages request, recording it in the usage statistics
+    fn messages_streaming(
+        &self,
+        request: MessagesRequest,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        let _span = info_span!("claude", backend = self.name(), model = %request.model).entered();
+        crate::stats::record_claude_call();
+        let result = self.send_streaming(request, on_text);
+        record_result(&result);
+        result
+    }
+}
+
+/// Backend of offline runs, which only get AI output from the cache
+///
+/// Every request fails without being sent, so callers fall back as if the
+/// provider were down.
+pub struct OfflineBackend;
+
+impl LlmBackend for OfflineBackend {
+    fn name(&self) -> &'static str {
+        "offline"
+    }
+
+    fn send(&self, _request: MessagesRequest) -> Result<MessagesResponse> {
+        Err(anyhow!("not in the cache (offline)"))
+    }
+
+    // Nothing is sent, so nothing is counted in the usage statistics
+    fn messages(&self, request: MessagesRequest) -> Result<MessagesResponse> {
+        self.send(request)
+    }
+
+    fn messages_streaming(
+        &self,
+        request: MessagesRequest,
+        _on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        self.send(request)
+    }
+}
+
+/// The Claude CLI, falling back to the Claude API when it fails mid-run
+///
+/// Used by `provider = "auto"` when both are set up. Once the CLI fails,
+/// the rest of the run goes to the API, and the reason is kept for the
+/// report footer.
+pub struct FallbackBackend {
+    primary: Box<dyn LlmBackend>,
+    fallback: Box<dyn LlmBackend>,
+    failed_over: AtomicBool,
+    reason: Mutex<Option<String>>,
+}
+
+impl FallbackBackend {
+    pub fn new(primary: Box<dyn LlmBackend>, fallback: Box<dyn LlmBackend>) -> Self {
+        FallbackBackend {
+            primary,
+            fallback,
+            failed_over: AtomicBool::new(false),
+            reason: Mutex::new(None),
+        }
+    }
+
+    fn active(&self) -> &dyn LlmBackend {
+        if self.failed_over.load(Ordering::Relaxed) {
+            self.fallback.as_ref()
+        } else {
+            self.primary.as_ref()
+        }
+    }
+
+    /// Move the rest of the run to the fallback, keeping the first reason
+    fn fail_over(&self, error: &anyhow::Error) {
+        warn!(
+            "{} failed, falling back to {}: {}",
+            self.primary.name(),
+            self.fallback.name(),
+            error
+        );
+        let mut reason = self.reason.lock().unwrap();
+        if reason.is_none() {
+            *reason = Some(format!("{} failed: {}", self.primary.name(), error));
+        }
+        self.failed_over.store(true, Ordering::Relaxed);
+    }
+}
+
+impl LlmBackend for FallbackBackend {
+    fn name(&self) -> &'static str {
+        self.active().name()
+    }
+
+    fn destination(&self) -> String {
+        format!(
+            "{} (or {} if it fails)",
+            self.primary.destination(),
+            self.fallback.destination()
+        )
+    }
+
+    fn send(&self, request: MessagesRequest) -> Result<MessagesResponse> {
+        if !self.failed_over.load(Ordering::Relaxed) {
+            match self.primary.send(request.clone()) {
+                Ok(response) => return Ok(response),
+                Err(e) => self.fail_over(&e),
+            }
+        }
+        self.fallback.send(request)
+    }
+
+    fn send_streaming(
+        &self,
+        request: MessagesRequest,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        if !self.failed_over.load(Ordering::Relaxed) {
+            // The CLI hands over the text only once it is done, so nothing
+            // was passed to `on_text` when it fails
+            match self.primary.send_streaming(request.clone(), on_text) {
+                Ok(response) => return Ok(response),
+                Err(e) => self.fail_over(&e),
+            }
+        }
+        self.fallback.send_streaming(request, on_text)
+    }
+
+    fn fallback_reason(&self) -> Option<String> {
+        self.reason.lock().unwrap().clone()
+    }
+}
+
+/// Whether a provider can be used, as shown by `gh-report models`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendProbe {
+    pub name: &'static str,
+    pub available: bool,
+    /// Version, endpoint, or what is missing
+    pub detail: String,
+}
+
+/// Check which providers are set up, without sending any prompt
+pub fn probe_backends(config: &Config) -> Vec<BackendProbe> {
+    let cli = match ClaudeCLI::version() {
+        Some(version) => BackendProbe {
+            name: "claude-cli",
+            available: true,
+            detail: version,
+        },
+        None => BackendProbe {
+            name: "claude-cli",
+            available: ClaudeCLI::is_available(),
+            detail: "`claude` not found or not working".to_string(),
+        },
+    };
+    let api_key = std::env::var("ANTHROPIC_API_KEY").is_ok_and(|key| !key.is_empty());
+    let api = BackendProbe {
+        name: "anthropic",
+        available: api_key,
+        detail: if api_key {
+            "ANTHROPIC_API_KEY is set".to_string()
+        } else {
+            "ANTHROPIC_API_KEY not set".to_string()
+        },
+    };
+    let mut probes = vec![cli, api];
+    if config.llm_provider() == LlmProvider::Openai {
+        let api_key = std::env::var(&config.llm.api_key_env).is_ok();
+        probes.push(BackendProbe {
+            name: "openai",
+            available: api_key,
+            detail: if api_key {
+                format!("{} is set", config.llm.api_key_env)
+            } else {
+                format!("{} not set", config.llm.api_key_env)
+            },
+        });
+    }
+    probes
+}
+
+/// Count a response in the cost ledger, or its error in the usage statistics
+fn record_result(result: &Result<MessagesResponse>) {
+    match result {
+        Ok(response) => crate::costs::record_usage(
+            &response.model,
+            response.usage.input_tokens,
+            response.usage.output_tokens,
+        ),
+        Err(e) => crate::stats::record_error(&e.to_string()),
+    }
+}
+
+/// Create the configured backend, or `None` when it is not available
+pub fn backend_from_config(config: &Config) -> Result<Option<Box<dyn LlmBackend>>> {
+    match config.llm_provider() {
+        LlmProvider::Anthropic => Ok(anthropic_backend()),
+        LlmProvider::ClaudeCli => {
+            if ClaudeCLI::is_available() {
+                Ok(claude_cli_backend(config))
+            } else {
+                info!("Claude CLI not available");
+                Ok(None)
+            }
+        }
+        LlmProvider::Openai => match OpenAiClient::new(&config.llm) {
+            Ok(Some(client)) => {
+                info!("Using OpenAI-compatible backend at {}", client.base_url());
+                Ok(Some(Box::new(client)))
+            }
+            Ok(None) => {
+                info!(
+                    "{} not set, OpenAI-compatible backend unavailable",
+                    config.llm.api_key_env
+                );
+                Ok(None)
+            }
+            Err(e) => {
+                warn!("Failed to initialize OpenAI-compatible client: {}", e);
+                Ok(None)
+            }
+        },
+        LlmProvider::Auto => {
+            // Try CLI first, then API, which also takes over when the CLI fails
+            let cli = if ClaudeCLI::is_available() {
+                claude_cli_backend(config)
+            } else {
+                None
+            };
+            let backend = match (cli, anthropic_backend()) {
+                (Some(cli), Some(api)) => {
+                    info!("Claude API available as fallback for the Claude CLI");
+                    Some(Box::new(FallbackBackend::new(cli, api)) as Box<dyn LlmBackend>)
+                }
+                (Some(cli), None) => Some(cli),
+                (None, api) => api,
+            };
+            if backend.is_none() {
+                info!("No LLM backend available (Claude CLI not installed, API key not set)");
+            }
+            Ok(backend)
+        }
+    }
+}
+
+fn anthropic_backend() -> Option<Box<dyn LlmBackend>> {
+    if std::env::var("ANTHROPIC_API_KEY").is_err() {
+        info!("ANTHROPIC_API_KEY not set, Claude API unavailable");
+        return None;
+    }
+    match ClaudeClient::new() {
+        Ok(client) => {
+            info!("Using Claude API backend");
+            Some(Box::new(client))
+        }
+        Err(e) => {
+            warn!("Failed to initialize Claude API client: {}", e);
+            None
+        }
+    }
+}
+
+fn claude_cli_backend(config: &Config) -> Option<Box<dyn LlmBackend>> {
+    let model = config.claude.resolve_model(&config.claude.primary_model);
+    match ClaudeCLI::new(model) {
+        Ok(client) => {
+            info!("Using Claude CLI backend");
+            Some(Box::new(client))
+        }
+        Err(e) => {
+            warn!("Failed to initialize Claude CLI: {}", e);
+            None
+        }
+    }
+}
+
+impl LlmBackend for ClaudeClient {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn destination(&self) -> String {
+        "the Anthropic API".to_string()
+    }
+
+    fn send(&self, request: MessagesRequest) -> Result<MessagesResponse> {
+        ClaudeClient::messages(self, request)
+    }
+
+    fn send_streaming(
+        &self,
+        request: MessagesRequest,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        ClaudeClient::messages_streaming(self, request, on_text)
+    }
+}
+
+impl LlmBackend for ClaudeCLI {
+    fn name(&self) -> &'static str {
+        "claude-cli"
+    }
+
+    fn destination(&self) -> String {
+        "Claude through the `claude` CLI".to_string()
+    }
+
+    fn send(&self, request: MessagesRequest) -> Result<MessagesResponse> {
+        // Convert MessagesRequest to CLI format
+        let prompt = request
+            .messages
+            .iter()
+            .map(|m| m.content.clone())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let system = request.system.as_deref();
+
+        // Send to CLI
+        let response_text = self.send_message(&prompt, system)?;
+
+        // Convert response to MessagesResponse format
+        Ok(MessagesResponse {
+            id: "cli_response".to_string(),
+            content: vec![crate::claude::Content::Text {
+                text: response_text,
+            }],
+            model: request.model,
+            stop_reason: Some("end_turn".to_string()),
+            usage: crate::claude::Usage {
+                // Estimate tokens for CLI (rough approximation)
+                input_tokens: (prompt.len() / 4) as u32,
+                output_tokens: 100, // Default estimate
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::{Message, MockClaude};
+    use crate::test_utils::text_response;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    /// A provider that is down, counting the requests it gets
+    struct FailingBackend(Arc<AtomicUsize>);
+
+    impl LlmBackend for FailingBackend {
+        fn name(&self) -> &'static str {
+            "claude-cli"
+        }
+
+        fn send(&self, _request: MessagesRequest) -> Result<MessagesResponse> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Err(anyhow!("claude CLI exited with status 1"))
+        }
+    }
+
+    fn request() -> MessagesRequest {
+        MessagesRequest::new(
+            "claude-sonnet-4-5".to_string(),
+            vec![Message::user("Summarize".to_string())],
+        )
+    }
+
+    #[test]
+    fn test_fallback_backend_switches_once() {
+        let cli_calls = Arc::new(AtomicUsize::new(0));
+        let backend = FallbackBackend::new(
+            Box::new(FailingBackend(cli_calls.clone())),
+            Box::new(ClaudeClient::Mock(
+                MockClaude::new()
+                    .with_response(text_response("first"))
+                    .with_response(text_response("second")),
+            )),
+        );
+        assert_eq!(backend.name(), "claude-cli");
+        assert_eq!(backend.fallback_reason(), None);
+
+        assert_eq!(backend.send(request()).unwrap().get_text(), "first");
+        assert_eq!(backend.name(), "anthropic");
+        assert_eq!(
+            backend.fallback_reason().unwrap(),
+            "claude-cli failed: claude CLI exited with status 1"
+        );
+
+        // The CLI is not tried again
+        assert_eq!(backend.send(request()).unwrap().get_text(), "second");
+        assert_eq!(cli_calls.load(Ordering::Relaxed), 1);
+    }
+}
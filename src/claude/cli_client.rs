@@ -186,6 +186,18 @@ impl ClaudeCLI {
         false
     }
 
+    /// Version reported by `claude --version`, if it runs
+    pub fn version() -> Option<String> {
+        let output = Command::new(Self::get_claude_path().ok()?)
+            .arg("--version")
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     /// Get the path to the claude binary
     fn get_claude_path() -> Result<String> {
         // First try which
@@ -1,16 +1,23 @@
 use anyhow::{anyhow, Context, Result};
 use reqwest::blocking::{Client as HttpClient, Response};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde::Deserialize;
 use serde_json;
+use std::io::{BufRead, BufReader};
 use std::time::Duration;
 
 use crate::claude::{
-    get_api_key, resolve_model_alias, ErrorResponse, MessagesRequest, MessagesResponse,
+    get_api_key, resolve_model_alias, ApiError, Content, ErrorResponse, MessagesRequest,
+    MessagesResponse, ModelInfo, ModelList,
 };
 
 const API_URL: &str = "https://api.anthropic.com/v1/messages";
+const MODELS_URL: &str = "https://api.anthropic.com/v1/models";
 const API_VERSION: &str = "2023-06-01";
 
+/// Timeout of streamed requests, which stay open while the response is written
+const STREAM_TIMEOUT: Duration = Duration::from_secs(600);
+
 /// Claude client abstraction
 pub enum ClaudeClient {
     Real(RealClaude),
@@ -51,6 +58,34 @@ impl ClaudeClient {
             ClaudeClient::Mock(client) => client.messages(request),
         }
     }
+
+    /// Send a messages request, passing the text to `on_text` as it is generated
+    ///
+    /// The mock client passes the whole text of its response at once.
+    pub fn messages_streaming(
+        &self,
+        request: MessagesRequest,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        match self {
+            ClaudeClient::Real(client) => client.messages_streaming(request, on_text),
+            #[cfg(test)]
+            ClaudeClient::Mock(client) => {
+                let response = client.messages(request)?;
+                on_text(&response.get_text());
+                Ok(response)
+            }
+        }
+    }
+
+    /// List the models available to the API key
+    pub fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        match self {
+            ClaudeClient::Real(client) => client.list_models(),
+            #[cfg(test)]
+            ClaudeClient::Mock(client) => Ok(client.models.clone()),
+        }
+    }
 }
 
 /// Real Claude API client
@@ -114,6 +149,44 @@ impl RealClaude {
         self.handle_response(response)
     }
 
+    /// Send a messages request as a stream of server-sent events
+    ///
+    /// Each piece of text is passed to `on_text` as it arrives, and the whole
+    /// response is returned once the stream ends.
+    pub fn messages_streaming(
+        &self,
+        mut request: MessagesRequest,
+        on_text: &mut dyn FnMut(&str),
+    ) -> Result<MessagesResponse> {
+        request.model = resolve_model_alias(&request.model);
+        request.stream = Some(true);
+
+        let headers = self.build_headers()?;
+        let body = serde_json::to_string(&request).context("Failed to serialize request")?;
+
+        let response = self
+            .client
+            .post(API_URL)
+            .headers(headers)
+            .timeout(STREAM_TIMEOUT)
+            .body(body)
+            .send()
+            .context("Failed to send request to Claude API")?;
+        if !response.status().is_success() {
+            return self.handle_response(response);
+        }
+
+        let mut stream = StreamAccumulator::default();
+        for line in BufReader::new(response).lines() {
+            let line = line.context("Failed to read response stream")?;
+            // Only `data` lines matter, as each one names its event type
+            if let Some(data) = line.strip_prefix("data:") {
+                stream.push(data.trim(), on_text)?;
+            }
+        }
+        stream.finish()
+    }
+
     /// Send a messages request with retries
     pub fn messages_with_retry(
         &self,
@@ -157,6 +230,42 @@ impl RealClaude {
         Err(last_error.unwrap_or_else(|| anyhow!("Failed after {} retries", max_retries)))
     }
 
+    /// List the models available to the API key, following pagination
+    pub fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let mut models = Vec::new();
+        let mut after_id: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(MODELS_URL)
+                .headers(self.build_headers()?)
+                .query(&[("limit", "100")]);
+            if let Some(after_id) = &after_id {
+                request = request.query(&[("after_id", after_id)]);
+            }
+
+            let response = request
+                .send()
+                .context("Failed to send request to Claude API")?;
+            let status = response.status();
+            let body = response.text().context("Failed to read response body")?;
+            if !status.is_success() {
+                return Err(anyhow!("Claude API error ({}): {}", status, body));
+            }
+
+            let page: ModelList =
+                serde_json::from_str(&body).context("Failed to parse models list")?;
+            models.extend(page.data);
+            match page.last_id {
+                Some(last_id) if page.has_more => after_id = Some(last_id),
+                _ => break,
+            }
+        }
+
+        Ok(models)
+    }
+
     /// Handle API response
     fn handle_response(&self, response: Response) -> Result<MessagesResponse> {
         let status = response.status();
@@ -179,11 +288,109 @@ impl RealClaude {
     }
 }
 
+/// Builds a response from the events of a streamed one
+#[derive(Default)]
+struct StreamAccumulator {
+    response: Option<MessagesResponse>,
+    text: String,
+}
+
+/// The events of a streamed response that make up the message
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: MessagesResponse,
+    },
+    ContentBlockDelta {
+        delta: ContentDelta,
+    },
+    MessageDelta {
+        delta: MessageDelta,
+        usage: OutputUsage,
+    },
+    Error {
+        error: ApiError,
+    },
+    /// `ping`, `content_block_start`, `content_block_stop`, and `message_stop`
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentDelta {
+    TextDelta {
+        text: String,
+    },
+    /// Deltas of tool use and thinking blocks, which aren't requested
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDelta {
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputUsage {
+    output_tokens: u32,
+}
+
+impl StreamAccumulator {
+    /// Add the event of a `data` line, passing any new text to `on_text`
+    fn push(&mut self, data: &str, on_text: &mut dyn FnMut(&str)) -> Result<()> {
+        let event: StreamEvent =
+            serde_json::from_str(data).context("Failed to parse streamed event")?;
+        match event {
+            StreamEvent::MessageStart { message } => self.response = Some(message),
+            StreamEvent::ContentBlockDelta {
+                delta: ContentDelta::TextDelta { text },
+            } => {
+                on_text(&text);
+                self.text.push_str(&text);
+            }
+            StreamEvent::MessageDelta { delta, usage } => {
+                if let Some(response) = &mut self.response {
+                    response.stop_reason = delta.stop_reason;
+                    response.usage.output_tokens = usage.output_tokens;
+                }
+            }
+            StreamEvent::Error { error } => {
+                return Err(anyhow!(
+                    "Claude API error ({}): {}",
+                    error.error_type,
+                    error.message
+                ));
+            }
+            StreamEvent::ContentBlockDelta { .. } | StreamEvent::Other => {}
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<MessagesResponse> {
+        let mut response = self
+            .response
+            .ok_or_else(|| anyhow!("Claude API stream ended without a message"))?;
+        response.content = vec![Content::Text { text: self.text }];
+        Ok(response)
+    }
+}
+
 /// Mock Claude client for testing
 #[cfg(test)]
 pub struct MockClaude {
     pub responses: Vec<MessagesResponse>,
-    pub call_count: std::cell::RefCell<usize>,
+    pub models: Vec<ModelInfo>,
+    pub call_count: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(test)]
+impl Default for MockClaude {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -191,7 +398,8 @@ impl MockClaude {
     pub fn new() -> Self {
         MockClaude {
             responses: vec![],
-            call_count: std::cell::RefCell::new(0),
+            models: vec![],
+            call_count: std::sync::atomic::AtomicUsize::new(0),
         }
     }
 
@@ -201,9 +409,9 @@ impl MockClaude {
     }
 
     pub fn messages(&self, _request: MessagesRequest) -> Result<MessagesResponse> {
-        let mut count = self.call_count.borrow_mut();
-        let index = *count;
-        *count += 1;
+        let index = self
+            .call_count
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         self.responses
             .get(index)
@@ -242,22 +450,12 @@ impl Clone for crate::claude::Content {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::claude::{Content, Message, Usage};
+    use crate::claude::Message;
+    use crate::test_utils::text_response;
 
     #[test]
     fn test_mock_claude_client() {
-        let mock_response = MessagesResponse {
-            id: "msg_123".to_string(),
-            content: vec![Content::Text {
-                text: "Test response".to_string(),
-            }],
-            model: "claude-3-5-sonnet-20241022".to_string(),
-            stop_reason: Some("end_turn".to_string()),
-            usage: Usage {
-                input_tokens: 10,
-                output_tokens: 5,
-            },
-        };
+        let mock_response = text_response("Test response");
 
         let client = MockClaude::new().with_response(mock_response.clone());
 
@@ -268,8 +466,50 @@ mod tests {
 
         let response = client.messages(request).unwrap();
         assert_eq!(response.get_text(), "Test response");
-        assert_eq!(response.usage.input_tokens, 10);
-        assert_eq!(response.usage.output_tokens, 5);
+        assert_eq!(response.usage.input_tokens, 100);
+        assert_eq!(response.usage.output_tokens, 10);
+    }
+
+    #[test]
+    fn test_stream_accumulator() {
+        let events = [
+            r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-5","stop_reason":null,"usage":{"input_tokens":25,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"ping"}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Summary:"}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"\nA deadlock."}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":12}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+        let mut stream = StreamAccumulator::default();
+        let mut pieces = Vec::new();
+        for data in events {
+            stream
+                .push(data, &mut |text| pieces.push(text.to_string()))
+                .unwrap();
+        }
+        assert_eq!(pieces, ["Summary:", "\nA deadlock."]);
+
+        let response = stream.finish().unwrap();
+        assert_eq!(response.get_text(), "Summary:\nA deadlock.");
+        assert_eq!(response.stop_reason.as_deref(), Some("end_turn"));
+        assert_eq!(
+            (response.usage.input_tokens, response.usage.output_tokens),
+            (25, 12)
+        );
+
+        let mut stream = StreamAccumulator::default();
+        let error = stream
+            .push(
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+                &mut |_| {},
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Claude API error (overloaded_error): Overloaded"
+        );
     }
 
     #[test]
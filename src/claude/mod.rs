@@ -1,28 +1,79 @@
 use anyhow::{Context, Result};
+use std::collections::BTreeMap;
 use std::env;
 
-mod claude_interface;
+mod backend;
 mod cli_client;
 mod client;
 mod models;
+mod openai_client;
+mod pricing;
+mod privacy;
 pub mod prompts;
 
-pub use claude_interface::ClaudeInterface;
+pub use backend::{
+    backend_from_config, probe_backends, BackendProbe, FallbackBackend, LlmBackend, OfflineBackend,
+};
 pub use cli_client::ClaudeCLI;
 pub use client::*;
 pub use models::*;
+pub use openai_client::{OpenAiClient, DEFAULT_OPENAI_BASE_URL};
+pub use pricing::{ModelPrice, PricingTable, PRICING_TABLE_VERSION};
+pub use privacy::{confirm_new_repos, confirm_private_repos, find_private_repos};
 
 #[cfg(test)]
 pub use client::MockClaude;
 
+/// Built-in model aliases, overridable with `[claude.aliases]` in the config
+pub const BUILTIN_MODEL_ALIASES: &[(&str, &str)] = &[
+    ("sonnet", "claude-3-5-sonnet-20241022"),
+    ("sonnet-3.5", "claude-3-5-sonnet-20241022"),
+    ("haiku", "claude-3-5-haiku-20241022"),
+    ("haiku-3.5", "claude-3-5-haiku-20241022"),
+    ("opus", "claude-3-opus-20240229"),
+    ("opus-3", "claude-3-opus-20240229"),
+];
+
+/// Context windows in tokens, keyed by model ID prefix
+pub const MODEL_CONTEXT_WINDOWS: &[(&str, u32)] = &[
+    ("claude-", 200_000),
+    ("gpt-3.5-turbo", 16_385),
+    ("gpt-4", 8_192),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4o", 128_000),
+    ("gpt-4.1", 1_047_576),
+];
+
+/// Context window assumed for models missing from the table, like local ones
+pub const DEFAULT_CONTEXT_WINDOW: u32 = 32_000;
+
+/// Context window of a model in tokens
+///
+/// `overrides` from `[claude.context_windows]` match like the built-in
+/// entries, by longest prefix, and win on equally long prefixes.
+pub fn context_window(model: &str, overrides: &BTreeMap<String, u32>) -> u32 {
+    overrides
+        .iter()
+        .map(|(id, tokens)| (id.as_str(), *tokens, true))
+        .chain(
+            MODEL_CONTEXT_WINDOWS
+                .iter()
+                .map(|(id, tokens)| (*id, *tokens, false)),
+        )
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, is_override)| (prefix.len(), *is_override))
+        .map(|(_, tokens, _)| tokens)
+        .unwrap_or(DEFAULT_CONTEXT_WINDOW)
+}
+
 /// Resolve model alias to full model name
 pub fn resolve_model_alias(alias: &str) -> String {
-    match alias.to_lowercase().as_str() {
-        "sonnet" | "sonnet-3.5" => "claude-3-5-sonnet-20241022".to_string(),
-        "haiku" | "haiku-3.5" => "claude-3-5-haiku-20241022".to_string(),
-        "opus" | "opus-3" => "claude-3-opus-20240229".to_string(),
-        _ => alias.to_string(), // Return as-is if not an alias
-    }
+    let lower = alias.to_lowercase();
+    BUILTIN_MODEL_ALIASES
+        .iter()
+        .find(|(name, _)| *name == lower)
+        .map(|(_, model)| model.to_string())
+        .unwrap_or_else(|| alias.to_string()) // Return as-is if not an alias
 }
 
 /// Get API key from environment
@@ -30,20 +81,9 @@ pub fn get_api_key() -> Result<String> {
     env::var("ANTHROPIC_API_KEY").context("ANTHROPIC_API_KEY environment variable not set")
 }
 
-/// Estimate cost for a request in dollars
+/// Estimate cost for a request in dollars, using the built-in prices
 pub fn estimate_cost(model: &str, input_tokens: u32, output_tokens: u32) -> f32 {
-    // Pricing as of late 2024 (per 1M tokens)
-    let (input_price, output_price) = match model {
-        m if m.contains("sonnet") => (3.0, 15.0),
-        m if m.contains("haiku") => (0.25, 1.25),
-        m if m.contains("opus") => (15.0, 75.0),
-        _ => (3.0, 15.0), // Default to Sonnet pricing
-    };
-
-    let input_cost = (input_tokens as f32 / 1_000_000.0) * input_price;
-    let output_cost = (output_tokens as f32 / 1_000_000.0) * output_price;
-
-    input_cost + output_cost
+    PricingTable::builtin().estimate_cost(model, input_tokens, output_tokens)
 }
 
 /// Estimate token count for text (rough approximation)
@@ -72,7 +112,27 @@ mod tests {
 
         // Test Haiku pricing
         let cost = estimate_cost("claude-3-5-haiku-20241022", 1000, 500);
-        assert!((cost - 0.00088).abs() < 0.00001);
+        assert!((cost - 0.0028).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_context_window() {
+        let none = BTreeMap::new();
+        assert_eq!(context_window("claude-3-5-haiku-20241022", &none), 200_000);
+        assert_eq!(context_window("gpt-4o-mini", &none), 128_000);
+        assert_eq!(context_window("gpt-4-0613", &none), 8_192);
+        assert_eq!(context_window("llama3.1:8b", &none), DEFAULT_CONTEXT_WINDOW);
+
+        let overrides = BTreeMap::from([
+            ("llama3".to_string(), 128_000),
+            ("claude-".to_string(), 100_000),
+        ]);
+        assert_eq!(context_window("llama3.1:8b", &overrides), 128_000);
+        assert_eq!(
+            context_window("claude-3-opus-20240229", &overrides),
+            100_000
+        );
+        assert_eq!(context_window("gpt-4o", &overrides), 128_000);
     }
 
     #[test]
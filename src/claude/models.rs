@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Request to Claude Messages API
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MessagesRequest {
     pub model: String,
     pub max_tokens: u32,
@@ -10,6 +10,9 @@ pub struct MessagesRequest {
     pub system: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+    /// Whether the response is sent as server-sent events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
 }
 
 impl MessagesRequest {
@@ -20,6 +23,7 @@ impl MessagesRequest {
             messages,
             system: None,
             temperature: None,
+            stream: None,
         }
     }
 
@@ -85,8 +89,8 @@ impl MessagesResponse {
     pub fn get_text(&self) -> String {
         self.content
             .iter()
-            .filter_map(|c| match c {
-                Content::Text { text } => Some(text.clone()),
+            .map(|c| match c {
+                Content::Text { text } => text.clone(),
             })
             .collect::<Vec<_>>()
             .join("")
@@ -107,6 +111,25 @@ pub struct Usage {
     pub output_tokens: u32,
 }
 
+/// A model available through the API
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelInfo {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// A page of the models list
+#[derive(Debug, Deserialize)]
+pub struct ModelList {
+    pub data: Vec<ModelInfo>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub last_id: Option<String>,
+}
+
 /// Error response from API
 #[derive(Debug, Deserialize)]
 pub struct ErrorResponse {
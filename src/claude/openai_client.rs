@@ -0,0 +1,269 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client as HttpClient;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::backend::LlmBackend;
+use crate::claude::{Content, MessageRole, MessagesRequest, MessagesResponse, Usage};
+use crate::config::LlmConfig;
+
+/// Base URL used when `[llm] base_url` is not set
+pub const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Client for OpenAI-compatible chat completion endpoints
+///
+/// Works with OpenAI itself and with servers that mimic its API, such as
+/// Ollama, vLLM, or LiteLLM.
+pub struct OpenAiClient {
+    client: HttpClient,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl OpenAiClient {
+    /// Create a client from the `[llm]` config
+    ///
+    /// Returns `None` when talking to OpenAI without an API key. Custom
+    /// endpoints may not need one, so for those the key is optional.
+    pub fn new(config: &LlmConfig) -> Result<Option<Self>> {
+        let api_key = std::env::var(&config.api_key_env)
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+        if api_key.is_none() && config.base_url.is_none() {
+            return Ok(None);
+        }
+
+        let client = HttpClient::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .context("Failed to create HTTP client")?;
+        let base_url = config
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_OPENAI_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Some(OpenAiClient {
+            client,
+            base_url,
+            api_key,
+        }))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+impl LlmBackend for OpenAiClient {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn destination(&self) -> String {
+        format!("the OpenAI-compatible API at {}", self.base_url)
+    }
+
+    fn send(&self, request: MessagesRequest) -> Result<MessagesResponse> {
+        let mut http_request = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(&ChatRequest::from(&request));
+        if let Some(api_key) = &self.api_key {
+            http_request = http_request.bearer_auth(api_key);
+        }
+
+        let response = http_request
+            .send()
+            .context("Failed to send request to the OpenAI-compatible API")?;
+        let status = response.status();
+        let body = response.text().context("Failed to read response body")?;
+        if !status.is_success() {
+            return Err(anyhow!(
+                "OpenAI-compatible API error ({}): {}",
+                status,
+                body
+            ));
+        }
+
+        let response: ChatResponse =
+            serde_json::from_str(&body).context("Failed to parse chat completion response")?;
+        response.into_messages_response(request.model)
+    }
+}
+
+/// Chat completion request, with the system prompt as the first message
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+impl<'a> From<&'a MessagesRequest> for ChatRequest<'a> {
+    fn from(request: &'a MessagesRequest) -> Self {
+        let system = request.system.as_deref().map(|content| ChatMessage {
+            role: "system",
+            content,
+        });
+        let messages = request.messages.iter().map(|message| ChatMessage {
+            role: match message.role {
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+            },
+            content: &message.content,
+        });
+
+        ChatRequest {
+            model: &request.model,
+            messages: system.into_iter().chain(messages).collect(),
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    model: Option<String>,
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoiceMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl ChatResponse {
+    fn into_messages_response(self, requested_model: String) -> Result<MessagesResponse> {
+        let choice = self
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Chat completion response has no choices"))?;
+        let usage = self.usage.unwrap_or(ChatUsage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+        });
+
+        Ok(MessagesResponse {
+            id: self.id,
+            content: vec![Content::Text {
+                text: choice.message.content.unwrap_or_default(),
+            }],
+            model: self.model.unwrap_or(requested_model),
+            // Map to the Anthropic stop reasons the rest of the code knows
+            stop_reason: choice.finish_reason.map(|reason| match reason.as_str() {
+                "stop" => "end_turn".to_string(),
+                "length" => "max_tokens".to_string(),
+                _ => reason,
+            }),
+            usage: Usage {
+                input_tokens: usage.prompt_tokens,
+                output_tokens: usage.completion_tokens,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::Message;
+
+    #[test]
+    fn test_chat_request_puts_system_prompt_first() {
+        let request = MessagesRequest::new(
+            "gpt-4o-mini".to_string(),
+            vec![
+                Message::user("Summarize this".to_string()),
+                Message::assistant("Sure".to_string()),
+            ],
+        )
+        .with_system("You are a maintainer".to_string())
+        .with_max_tokens(512);
+
+        let body = serde_json::to_value(ChatRequest::from(&request)).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "model": "gpt-4o-mini",
+                "messages": [
+                    { "role": "system", "content": "You are a maintainer" },
+                    { "role": "user", "content": "Summarize this" },
+                    { "role": "assistant", "content": "Sure" },
+                ],
+                "max_tokens": 512,
+            })
+        );
+    }
+
+    #[test]
+    fn test_chat_response_conversion() {
+        let response: ChatResponse = serde_json::from_str(
+            r#"{
+                "id": "chatcmpl-1",
+                "model": "gpt-4o-mini-2024-07-18",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Two PRs need review." },
+                    "finish_reason": "length"
+                }],
+                "usage": { "prompt_tokens": 120, "completion_tokens": 8, "total_tokens": 128 }
+            }"#,
+        )
+        .unwrap();
+
+        let response = response
+            .into_messages_response("gpt-4o-mini".to_string())
+            .unwrap();
+        assert_eq!(response.get_text(), "Two PRs need review.");
+        assert_eq!(response.model, "gpt-4o-mini-2024-07-18");
+        assert_eq!(response.stop_reason.as_deref(), Some("max_tokens"));
+        assert_eq!(response.usage.input_tokens, 120);
+        assert_eq!(response.usage.output_tokens, 8);
+    }
+
+    #[test]
+    fn test_client_needs_key_only_for_openai() {
+        let config = LlmConfig {
+            api_key_env: "GH_REPORT_TEST_UNSET_KEY".to_string(),
+            ..LlmConfig::default()
+        };
+        assert!(OpenAiClient::new(&config).unwrap().is_none());
+
+        let config = LlmConfig {
+            base_url: Some("http://localhost:11434/v1/".to_string()),
+            ..config
+        };
+        let client = OpenAiClient::new(&config).unwrap().unwrap();
+        assert_eq!(client.base_url(), "http://localhost:11434/v1");
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use tracing::warn;
+
+/// Date the built-in prices were last checked against the published pricing
+pub const PRICING_TABLE_VERSION: &str = "2025-11";
+
+/// Price used for models missing from the table (Sonnet-class)
+const FALLBACK_PRICE: ModelPrice = ModelPrice::new(3.0, 15.0);
+
+/// Built-in prices, keyed by model ID prefix so dated snapshots match
+const BUILTIN_PRICES: &[(&str, ModelPrice)] = &[
+    ("claude-opus-4-5", ModelPrice::new(5.0, 25.0)),
+    ("claude-opus-4", ModelPrice::new(15.0, 75.0)),
+    ("claude-sonnet-4", ModelPrice::new(3.0, 15.0)),
+    ("claude-haiku-4-5", ModelPrice::new(1.0, 5.0)),
+    ("claude-3-7-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-5-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-5-haiku", ModelPrice::new(0.80, 4.0)),
+    ("claude-3-opus", ModelPrice::new(15.0, 75.0)),
+    ("claude-3-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-haiku", ModelPrice::new(0.25, 1.25)),
+];
+
+/// Models already warned about, so each unknown model is reported once
+static WARNED_MODELS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Prices of a model in dollars per 1M tokens
+///
+/// Cache prices default to the usual multipliers of the input price when
+/// omitted from the config.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(from = "ModelPriceConfig")]
+pub struct ModelPrice {
+    pub input: f32,
+    pub output: f32,
+    /// Writing to the prompt cache
+    pub cache_write: f32,
+    /// Reading from the prompt cache
+    pub cache_read: f32,
+}
+
+#[derive(Deserialize)]
+struct ModelPriceConfig {
+    input: f32,
+    output: f32,
+    cache_write: Option<f32>,
+    cache_read: Option<f32>,
+}
+
+impl From<ModelPriceConfig> for ModelPrice {
+    fn from(config: ModelPriceConfig) -> Self {
+        let price = ModelPrice::new(config.input, config.output);
+        ModelPrice {
+            cache_write: config.cache_write.unwrap_or(price.cache_write),
+            cache_read: config.cache_read.unwrap_or(price.cache_read),
+            ..price
+        }
+    }
+}
+
+impl ModelPrice {
+    /// Price with the usual cache multipliers (1.25x write, 0.1x read)
+    pub const fn new(input: f32, output: f32) -> Self {
+        ModelPrice {
+            input,
+            output,
+            cache_write: input * 1.25,
+            cache_read: input * 0.1,
+        }
+    }
+
+    /// Cost in dollars of a request
+    pub fn cost(&self, input_tokens: u32, output_tokens: u32) -> f32 {
+        (input_tokens as f32 / 1_000_000.0) * self.input
+            + (output_tokens as f32 / 1_000_000.0) * self.output
+    }
+}
+
+/// Model prices, with overrides from `[claude.pricing]` taking precedence
+#[derive(Debug, Clone, Copy)]
+pub struct PricingTable<'a> {
+    overrides: Option<&'a BTreeMap<String, ModelPrice>>,
+}
+
+impl<'a> PricingTable<'a> {
+    /// The built-in prices only
+    pub fn builtin() -> Self {
+        PricingTable { overrides: None }
+    }
+
+    /// The built-in prices with per-model overrides
+    pub fn with_overrides(overrides: &'a BTreeMap<String, ModelPrice>) -> Self {
+        PricingTable {
+            overrides: Some(overrides),
+        }
+    }
+
+    /// Price of a model, or `None` when it is not in the table
+    ///
+    /// Overrides match on exact ID first, then by longest prefix, like the
+    /// built-in entries.
+    pub fn lookup(&self, model: &str) -> Option<ModelPrice> {
+        let overrides = self.overrides.into_iter().flatten();
+        if let Some((_, price)) = overrides.clone().find(|(id, _)| id.as_str() == model) {
+            return Some(*price);
+        }
+
+        // On equally long prefixes, overrides win over built-in entries
+        overrides
+            .map(|(id, price)| (id.as_str(), *price, true))
+            .chain(
+                BUILTIN_PRICES
+                    .iter()
+                    .map(|(id, price)| (*id, *price, false)),
+            )
+            .filter(|(prefix, _, _)| model.starts_with(prefix))
+            .max_by_key(|(prefix, _, is_override)| (prefix.len(), *is_override))
+            .map(|(_, price, _)| price)
+    }
+
+    /// Price of a model, falling back to Sonnet-class pricing with a warning
+    pub fn price(&self, model: &str) -> ModelPrice {
+        self.lookup(model).unwrap_or_else(|| {
+            warn_unknown_model(model);
+            FALLBACK_PRICE
+        })
+    }
+
+    /// Estimate the cost of a request in dollars
+    pub fn estimate_cost(&self, model: &str, input_tokens: u32, output_tokens: u32) -> f32 {
+        self.price(model).cost(input_tokens, output_tokens)
+    }
+}
+
+fn warn_unknown_model(model: &str) {
+    let mut warned = WARNED_MODELS.lock().unwrap_or_else(|e| e.into_inner());
+    if warned
+        .get_or_insert_with(HashSet::new)
+        .insert(model.to_string())
+    {
+        warn!(
+            "No pricing known for model {} (table version {}), estimating with Sonnet prices. \
+             Add it under [claude.pricing] for accurate costs.",
+            model, PRICING_TABLE_VERSION
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_lookup() {
+        let table = PricingTable::builtin();
+
+        let price = table.lookup("claude-opus-4-5-20251101").unwrap();
+        assert_eq!((price.input, price.output), (5.0, 25.0));
+        // The longest prefix wins over the opus-4 family entry
+        let price = table.lookup("claude-opus-4-1-20250805").unwrap();
+        assert_eq!((price.input, price.output), (15.0, 75.0));
+        assert!((price.cache_read - 1.5).abs() < 0.0001);
+
+        assert_eq!(table.lookup("gpt-4o"), None);
+        assert_eq!(table.price("gpt-4o"), FALLBACK_PRICE);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("claude-3-5-haiku".to_string(), ModelPrice::new(1.0, 5.0));
+        overrides.insert("my-finetune".to_string(), ModelPrice::new(2.0, 8.0));
+        let table = PricingTable::with_overrides(&overrides);
+
+        let cost = table.estimate_cost("claude-3-5-haiku-20241022", 1_000_000, 1_000_000);
+        assert!((cost - 6.0).abs() < 0.0001);
+        assert_eq!(table.lookup("my-finetune").unwrap().output, 8.0);
+        // Other models keep their built-in prices
+        assert_eq!(table.lookup("claude-3-opus-20240229").unwrap().input, 15.0);
+    }
+}
@@ -0,0 +1,331 @@
+//! Guard against sending private repository content to the LLM provider
+//! without the user agreeing to it, and against repositories creeping into
+//! AI summaries unnoticed
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, Write};
+use tracing::warn;
+
+use crate::cache::{generate_cache_key, CacheManager};
+use crate::config::ClaudeConfig;
+use crate::github::GitHubClient;
+use crate::state::RepoTrust;
+
+/// Repositories among `repos` that are private
+///
+/// Visibility is kept in `cache` for its TTL, so repeated runs don't look
+/// every repository up again. Repositories whose visibility can't be
+/// determined are treated as private.
+pub fn find_private_repos<'r>(
+    github_client: &GitHubClient,
+    cache: Option<&CacheManager>,
+    repos: impl IntoIterator<Item = &'r str>,
+) -> Vec<String> {
+    repos
+        .into_iter()
+        .filter(|repo| is_private(github_client, cache, repo))
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_private(github_client: &GitHubClient, cache: Option<&CacheManager>, repo: &str) -> bool {
+    let key = generate_cache_key(&["visibility", repo]);
+    let cached = cache
+        .and_then(|cache| cache.get_github_response(&key).ok().flatten())
+        .and_then(|data| serde_json::from_slice::<bool>(&data).ok());
+    if let Some(is_private) = cached {
+        return is_private;
+    }
+
+    match github_client.fetch_repository(repo) {
+        Ok(info) => {
+            if let Some(cache) = cache {
+                let data = serde_json::to_vec(&info.is_private).unwrap_or_default();
+                if let Err(e) = cache.cache_github_response(&key, &data) {
+                    warn!("Failed to cache the visibility of {}: {}", repo, e);
+                }
+            }
+            info.is_private
+        }
+        Err(e) => {
+            warn!("Could not determine visibility of {}: {}", repo, e);
+            true
+        }
+    }
+}
+
+/// Whether content from the given private repositories may be sent to
+/// `destination`, the active LLM backend (see `LlmBackend::destination`)
+///
+/// Always allowed when `claude.allow_private_repos` is set. Otherwise the user
+/// is asked in an interactive terminal; without one this fails, so scheduled
+/// runs never send private data by accident.
+pub fn confirm_private_repos(
+    config: &ClaudeConfig,
+    destination: &str,
+    private_repos: &[String],
+) -> Result<bool> {
+    if config.allow_private_repos || private_repos.is_empty() {
+        return Ok(true);
+    }
+
+    let interactive = atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
+    ask(
+        destination,
+        private_repos,
+        interactive,
+        &mut std::io::stdin().lock(),
+        &mut std::io::stdout(),
+    )
+}
+
+fn ask(
+    destination: &str,
+    private_repos: &[String],
+    interactive: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<bool> {
+    if !interactive {
+        bail!(
+            "Refusing to send content from private repositories ({}) to {}. \
+             Set `allow_private_repos = true` under [claude] to allow it.",
+            private_repos.join(", "),
+            destination
+        );
+    }
+
+    writeln!(
+        output,
+        "⚠️  This report includes private repositories: {}",
+        private_repos.join(", ")
+    )?;
+    write!(output, "Send their content to {}? [y/N] ", destination)?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Repositories among `repos` to leave out of AI summaries
+///
+/// With `claude.confirm_new_repos`, the user is asked about each repository
+/// seen for the first time and the answer is recorded in `trust`. Without an
+/// interactive terminal, new repositories are left out of this run only.
+pub fn confirm_new_repos<'r>(
+    config: &ClaudeConfig,
+    trust: &mut RepoTrust,
+    repos: impl IntoIterator<Item = &'r str>,
+) -> Result<Vec<String>> {
+    if !config.confirm_new_repos {
+        return Ok(Vec::new());
+    }
+
+    let interactive = atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
+    ask_new_repos(
+        trust,
+        repos,
+        interactive,
+        &mut std::io::stdin().lock(),
+        &mut std::io::stdout(),
+    )
+}
+
+fn ask_new_repos<'r>(
+    trust: &mut RepoTrust,
+    repos: impl IntoIterator<Item = &'r str>,
+    interactive: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Vec<String>> {
+    let mut excluded = Vec::new();
+    for repo in repos {
+        let include = match trust.repos.get(repo) {
+            Some(&include) => include,
+            None if trust.include_new => true,
+            None if !interactive => {
+                warn!(
+                    "Leaving newly discovered repository {} out of AI summaries \
+                     until it is confirmed in an interactive run",
+                    repo
+                );
+                false
+            }
+            None => {
+                write!(
+                    output,
+                    "Include newly discovered repo {} in AI reports? [y/N/always] ",
+                    repo
+                )?;
+                output.flush()?;
+
+                let mut answer = String::new();
+                input
+                    .read_line(&mut answer)
+                    .context("Failed to read confirmation")?;
+                let include = match answer.trim().to_lowercase().as_str() {
+                    "y" | "yes" => true,
+                    "a" | "always" => {
+                        trust.include_new = true;
+                        true
+                    }
+                    _ => false,
+                };
+                trust.repos.insert(repo.to_string(), include);
+                include
+            }
+        };
+
+        if !include {
+            excluded.push(repo.to_string());
+        }
+    }
+    Ok(excluded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{MockGitHub, Repository};
+    use crate::test_utils::mock_repo;
+
+    fn repo(full_name: &str, is_private: bool) -> Repository {
+        Repository {
+            is_private,
+            ..mock_repo(full_name)
+        }
+    }
+
+    #[test]
+    fn test_find_private_repos() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![repo("acme/secret", true), repo("tokio-rs/tokio", false)];
+        let client = GitHubClient::Mock(mock);
+
+        let private = find_private_repos(
+            &client,
+            None,
+            ["acme/secret", "tokio-rs/tokio", "acme/gone"],
+        );
+        assert_eq!(private, vec!["acme/secret", "acme/gone"]);
+    }
+
+    #[test]
+    fn test_find_private_repos_cached() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 24, false);
+        cache.initialize().unwrap();
+
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![repo("acme/secret", true), repo("tokio-rs/tokio", false)];
+        let repos = ["acme/secret", "tokio-rs/tokio"];
+        let private = find_private_repos(&GitHubClient::Mock(mock), Some(&cache), repos);
+        assert_eq!(private, ["acme/secret"]);
+
+        // Later runs don't look the repositories up again
+        let private =
+            find_private_repos(&GitHubClient::Mock(MockGitHub::new()), Some(&cache), repos);
+        assert_eq!(private, ["acme/secret"]);
+    }
+
+    #[test]
+    fn test_ask() {
+        let repos = vec!["acme/secret".to_string()];
+        let mut output = Vec::new();
+
+        assert!(ask(
+            "the Anthropic API",
+            &repos,
+            true,
+            &mut "y\n".as_bytes(),
+            &mut output
+        )
+        .unwrap());
+        assert!(!ask(
+            "the Anthropic API",
+            &repos,
+            true,
+            &mut "\n".as_bytes(),
+            &mut output
+        )
+        .unwrap());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("acme/secret"));
+        assert!(output.contains("Send their content to the Anthropic API? [y/N]"));
+
+        let err = ask(
+            "the OpenAI-compatible API at http://localhost:11434/v1",
+            &repos,
+            false,
+            &mut "y\n".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("to the OpenAI-compatible API at http://localhost:11434/v1."));
+        assert!(err.to_string().contains("allow_private_repos"));
+    }
+
+    #[test]
+    fn test_ask_new_repos() {
+        let mut trust = RepoTrust::default();
+        trust.repos.insert("tokio-rs/tokio".to_string(), true);
+        let mut output = Vec::new();
+
+        let repos = ["tokio-rs/tokio", "acme/secret", "acme/tools"];
+        let excluded = ask_new_repos(
+            &mut trust,
+            repos,
+            true,
+            &mut "n\ny\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(excluded, ["acme/secret"]);
+        assert_eq!(trust.repos.get("acme/secret"), Some(&false));
+        assert_eq!(trust.repos.get("acme/tools"), Some(&true));
+        // Only new repositories are asked about
+        let output = String::from_utf8(output).unwrap();
+        assert!(!output.contains("tokio-rs/tokio"));
+        assert!(output.contains("acme/secret in AI reports? [y/N/always]"));
+
+        // Remembered answers are not asked again
+        let excluded =
+            ask_new_repos(&mut trust, repos, true, &mut "".as_bytes(), &mut Vec::new()).unwrap();
+        assert_eq!(excluded, ["acme/secret"]);
+    }
+
+    #[test]
+    fn test_ask_new_repos_always() {
+        let mut trust = RepoTrust::default();
+        let repos = ["acme/one", "acme/two"];
+        let excluded = ask_new_repos(
+            &mut trust,
+            repos,
+            true,
+            &mut "always\n".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert!(excluded.is_empty());
+        assert!(trust.include_new);
+        assert!(!trust.repos.contains_key("acme/two"));
+
+        // Without a terminal, new repositories are left out but not remembered
+        let mut trust = RepoTrust::default();
+        let excluded = ask_new_repos(
+            &mut trust,
+            repos,
+            false,
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(excluded, repos);
+        assert!(trust.is_empty());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::github::RepoActivity;
+use super::estimate_tokens;
+use crate::github::{
+    Comment, Discussion, Issue, IssueState, Label, PrDiff, Release, RepoActivity, ReviewThread,
+};
 use std::collections::BTreeMap;
 
 /// Generate a system prompt for GitHub activity summarization
@@ -17,10 +20,24 @@ Be concise but comprehensive. Use bullet points for clarity.
 Prioritize information based on urgency and importance."#.to_string()
 }
 
+/// Format repository-specific context for inclusion in a prompt
+pub fn repo_context_section(repo_name: &str, context: &str) -> String {
+    format!(
+        "Repository Context ({}):\n{}\n\n",
+        repo_name,
+        context.trim()
+    )
+}
+
 /// Generate a prompt for summarizing repository activities
+///
+/// `repo_contexts` maps repositories to their context (see
+/// `Config::repo_contexts`), which is only included in the section for the
+/// matching repository.
 pub fn summarize_activities_prompt(
     activities: &BTreeMap<String, RepoActivity>,
     context: Option<&str>,
+    repo_contexts: &BTreeMap<String, String>,
 ) -> String {
     let mut prompt = String::new();
 
@@ -35,6 +52,10 @@ pub fn summarize_activities_prompt(
     for (repo_name, activity) in activities {
         prompt.push_str(&format!("## Repository: {}\n\n", repo_name));
 
+        if let Some(repo_context) = repo_contexts.get(repo_name) {
+            prompt.push_str(&repo_context_section(repo_name, repo_context));
+        }
+
         if !activity.new_prs.is_empty() {
             prompt.push_str(&format!(
                 "### New Pull Requests ({})\n",
@@ -50,6 +71,7 @@ pub fn summarize_activities_prompt(
                     "- [PR #{}]({}): {} (State: {}, by [@{}](https://github.com/{}))\n",
                     pr.number, pr.url, pr.title, state_str, pr.author.login, pr.author.login
                 ));
+                prompt.push_str(&assignment_details(pr));
                 if let Some(body) = &pr.body {
                     if !body.is_empty() && body.len() < 200 {
                         prompt.push_str(&format!("  {}\n", body.replace('\n', " ")));
@@ -74,6 +96,7 @@ pub fn summarize_activities_prompt(
                     "- [PR #{}]({}): {} (State: {}, comments: {})\n",
                     pr.number, pr.url, pr.title, state_str, pr.comments.total_count
                 ));
+                prompt.push_str(&assignment_details(pr));
             }
             prompt.push('\n');
         }
@@ -88,8 +111,14 @@ pub fn summarize_activities_prompt(
                 };
                 prompt.push_str(&format!(
                     "- [Issue #{}]({}): {} (State: {}, by [@{}](https://github.com/{}))\n",
-                    issue.number, issue.url, issue.title, state_str, issue.author.login, issue.author.login
+                    issue.number,
+                    issue.url,
+                    issue.title,
+                    state_str,
+                    issue.author.login,
+                    issue.author.login
                 ));
+                prompt.push_str(&assignment_details(issue));
                 // Add labels if present
                 if !issue.labels.is_empty() {
                     let labels: Vec<String> = issue.labels.iter().map(|l| l.name.clone()).collect();
@@ -114,6 +143,7 @@ pub fn summarize_activities_prompt(
                     "- [Issue #{}]({}): {} (State: {}, comments: {})\n",
                     issue.number, issue.url, issue.title, state_str, issue.comments.total_count
                 ));
+                prompt.push_str(&assignment_details(issue));
             }
             prompt.push('\n');
         }
@@ -136,6 +166,328 @@ pub fn summarize_activities_prompt(
     prompt
 }
 
+/// Split `activities` into chunks whose summary prompts fit in `budget` tokens
+///
+/// Repositories are packed together in order while they fit. A repository
+/// too large on its own is halved, item by item, until its parts fit or are
+/// down to a single item.
+pub fn chunk_activities(
+    activities: &BTreeMap<String, RepoActivity>,
+    repo_contexts: &BTreeMap<String, String>,
+    budget: u32,
+) -> Vec<BTreeMap<String, RepoActivity>> {
+    let fits = |chunk: &BTreeMap<String, RepoActivity>| {
+        estimate_tokens(&summarize_activities_prompt(chunk, None, repo_contexts)) <= budget
+    };
+
+    let mut parts = Vec::new();
+    for (repo, activity) in activities {
+        split_to_fit(repo, activity.clone(), &fits, &mut parts);
+    }
+
+    let mut chunks = Vec::new();
+    let mut current: BTreeMap<String, RepoActivity> = BTreeMap::new();
+    for (repo, activity) in parts {
+        // Parts of the same repository go to separate requests
+        if !current.is_empty() && !current.contains_key(&repo) {
+            let mut candidate = current.clone();
+            candidate.insert(repo.clone(), activity.clone());
+            if fits(&candidate) {
+                current = candidate;
+                continue;
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        current.insert(repo, activity);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn split_to_fit(
+    repo: &str,
+    activity: RepoActivity,
+    fits: &impl Fn(&BTreeMap<String, RepoActivity>) -> bool,
+    parts: &mut Vec<(String, RepoActivity)>,
+) {
+    let single = BTreeMap::from([(repo.to_string(), activity)]);
+    if fits(&single) || item_count(&single[repo]) < 2 {
+        parts.extend(single);
+        return;
+    }
+    let (first, second) = halve(single.into_values().next().unwrap_or_default());
+    split_to_fit(repo, first, fits, parts);
+    split_to_fit(repo, second, fits, parts);
+}
+
+fn item_count(activity: &RepoActivity) -> usize {
+    activity.new_issues.len()
+        + activity.new_prs.len()
+        + activity.updated_issues.len()
+        + activity.updated_prs.len()
+        + activity.merged_prs.len()
+        + activity.closed_issues.len()
+        + activity.new_comments.len()
+}
+
+/// Split the items of `activity` in two halves, both with the reviews
+fn halve(mut activity: RepoActivity) -> (RepoActivity, RepoActivity) {
+    fn move_tail<T>(from: &mut Vec<T>, to: &mut Vec<T>, remaining: &mut usize) {
+        let n = (*remaining).min(from.len());
+        *to = from.split_off(from.len() - n);
+        *remaining -= n;
+    }
+
+    let mut second = RepoActivity {
+        reviews: activity.reviews.clone(),
+        head_shas: activity.head_shas.clone(),
+        ..RepoActivity::default()
+    };
+    let mut remaining = item_count(&activity) / 2;
+    move_tail(
+        &mut activity.new_comments,
+        &mut second.new_comments,
+        &mut remaining,
+    );
+    move_tail(
+        &mut activity.closed_issues,
+        &mut second.closed_issues,
+        &mut remaining,
+    );
+    move_tail(
+        &mut activity.merged_prs,
+        &mut second.merged_prs,
+        &mut remaining,
+    );
+    move_tail(
+        &mut activity.updated_prs,
+        &mut second.updated_prs,
+        &mut remaining,
+    );
+    move_tail(
+        &mut activity.updated_issues,
+        &mut second.updated_issues,
+        &mut remaining,
+    );
+    move_tail(&mut activity.new_prs, &mut second.new_prs, &mut remaining);
+    move_tail(
+        &mut activity.new_issues,
+        &mut second.new_issues,
+        &mut remaining,
+    );
+    (activity, second)
+}
+
+/// Generate a prompt combining the activity summaries of single repositories
+/// into the summary of a report
+pub fn combine_summaries_prompt(summaries: &[(&str, String)], context: Option<&str>) -> String {
+    let mut prompt = String::new();
+
+    if let Some(ctx) = context {
+        prompt.push_str("User Context:\n");
+        prompt.push_str(ctx);
+        prompt.push_str("\n\n");
+    }
+
+    prompt.push_str("These are summaries of the recent GitHub activity in each repository:\n\n");
+    for (repo_name, summary) in summaries {
+        prompt.push_str(&format!(
+            "## Repository: {}\n\n{}\n\n",
+            repo_name,
+            summary.trim()
+        ));
+    }
+
+    prompt.push_str("Combine them into one summary that:\n");
+    prompt.push_str(
+        "1. Highlights the most important items that need attention, across all repositories\n",
+    );
+    prompt.push_str("2. Groups related activities together\n");
+    prompt.push_str("3. Identifies any blocking issues or urgent matters\n");
+    prompt.push_str("4. Keep the action items of the repository summaries, most urgent first\n");
+    prompt.push_str("5. Celebrates completed work (Merged PRs, Closed issues) separately\n");
+    prompt.push_str("6. Keep it concise - focus on what matters most\n");
+    prompt.push_str(
+        "7. Keep the markdown links to issues, PRs, and users from the repository summaries\n",
+    );
+
+    prompt
+}
+
+/// `  Assignees: ...` and `  Milestone: ...` lines of an item, when it has them
+fn assignment_details(issue: &Issue) -> String {
+    let mut details = String::new();
+    if !issue.assignees.is_empty() {
+        let assignees: Vec<String> = issue
+            .assignees
+            .iter()
+            .map(|a| format!("@{}", a.login))
+            .collect();
+        details.push_str(&format!("  Assignees: {}\n", assignees.join(", ")));
+    }
+    if let Some(milestone) = &issue.milestone {
+        match milestone.due_on {
+            Some(due_on) => details.push_str(&format!(
+                "  Milestone: {} (due {})\n",
+                milestone.title,
+                due_on.strftime("%Y-%m-%d")
+            )),
+            None => details.push_str(&format!("  Milestone: {}\n", milestone.title)),
+        }
+    }
+    details
+}
+
+/// Instructions turning a summary prompt into a weekly or monthly rollup
+///
+/// `flavor` is `weekly` or `monthly` and `period` is `week` or `month`.
+/// `counts` lists the items opened, closed, and merged in the period, with
+/// those of the previous period when there was a report of it.
+pub fn rollup_instructions(flavor: &str, period: &str, counts: &str) -> String {
+    format!(
+        r#"
+
+This is a {flavor} rollup for someone catching up on the whole {period}. Instead of going item by item, write a higher-level rollup with these sections:
+1. Themes: the few threads of work that defined the {period}, each with the issues and PRs behind it
+2. Trends: how the {period} compares to the last one, going by the counts below, and what is speeding up or stalling
+3. Completed vs. planned: what was finished (Merged PRs, Closed issues) against what is still open in milestones, assignments, and the goals in the user context
+4. Next {period}: the few things that most need attention
+Mention single issues and PRs only as examples of a theme, keeping their markdown links.
+
+Counts of this {period}:
+{counts}"#
+    )
+}
+
+/// Files listed per pull request in `pr_changes_section`
+const MAX_CHANGED_FILES: usize = 8;
+
+/// Describe the size and most-changed files of pull requests, as context for
+/// the activity summary
+pub fn pr_changes_section(changes: &[(&str, &Issue, &PrDiff)]) -> String {
+    let mut section = String::from("Pull request changes:\n");
+    for (repo, pr, diff) in changes {
+        section.push_str(&format!(
+            "- [PR #{}]({}) in {}: +{}/-{} in {} files\n",
+            pr.number, pr.url, repo, diff.total_additions, diff.total_deletions, diff.total_files
+        ));
+        let mut files: Vec<_> = diff.files.iter().collect();
+        files.sort_by_key(|file| std::cmp::Reverse(file.changes));
+        for file in files.iter().take(MAX_CHANGED_FILES) {
+            section.push_str(&format!(
+                "  - {} ({}, +{}/-{})\n",
+                file.filename, file.status, file.additions, file.deletions
+            ));
+        }
+        if files.len() > MAX_CHANGED_FILES {
+            section.push_str(&format!(
+                "  - and {} more files\n",
+                files.len() - MAX_CHANGED_FILES
+            ));
+        }
+    }
+    section
+}
+
+/// Patch tokens `summarize --with-diff` uses when `intelligence.diff_patch_tokens` is 0
+pub const DEFAULT_DIFF_PATCH_TOKENS: u32 = 4000;
+
+/// The patches of a pull request's most-changed files, within about `max_tokens`
+///
+/// The last file that fits is cut at a line boundary. Files without a patch,
+/// such as binary files, are skipped.
+pub fn diff_patches_section(diff: &PrDiff, max_tokens: u32) -> String {
+    let mut files: Vec<_> = diff.files.iter().filter(|f| f.patch.is_some()).collect();
+    files.sort_by_key(|file| std::cmp::Reverse(file.changes));
+
+    let mut section = String::new();
+    let mut remaining = max_tokens;
+    for file in files {
+        let header = format!(
+            "{} ({}, +{}/-{}):\n```diff\n",
+            file.filename, file.status, file.additions, file.deletions
+        );
+        let overhead = estimate_tokens(&header) + estimate_tokens("```\n");
+        if remaining <= overhead {
+            break;
+        }
+        let patch = file.patch.as_deref().unwrap_or_default();
+        let budget = remaining - overhead;
+        let (patch, truncated) = if estimate_tokens(patch) <= budget {
+            (patch, false)
+        } else {
+            // About four characters per token, as in `estimate_tokens`
+            let mut end = (budget as usize * 4).min(patch.len());
+            while !patch.is_char_boundary(end) {
+                end -= 1;
+            }
+            (&patch[..patch[..end].rfind('\n').unwrap_or(0)], true)
+        };
+        if patch.is_empty() {
+            break;
+        }
+
+        let mut block = header;
+        block.push_str(patch.trim_end_matches('\n'));
+        block.push('\n');
+        if truncated {
+            block.push_str("… (truncated)\n");
+        }
+        block.push_str("```\n");
+        remaining = remaining.saturating_sub(estimate_tokens(&block));
+        section.push_str(&block);
+        if truncated {
+            break;
+        }
+    }
+    section
+}
+
+/// Names of common languages by ISO 639-1 code, for `claude.output_language`
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("uk", "Ukrainian"),
+    ("zh", "Chinese"),
+];
+
+/// Ask for the answer to `prompt` in `language`, a code like `de` or a name
+///
+/// Prompts are left alone without a language, or for English.
+pub fn with_output_language(mut prompt: String, language: Option<&str>) -> String {
+    let Some(language) = language.map(str::trim).filter(|l| !l.is_empty()) else {
+        return prompt;
+    };
+    let code = language.to_lowercase();
+    let code = code.split(['-', '_']).next().unwrap_or_default();
+    if code == "en" || language.eq_ignore_ascii_case("english") {
+        return prompt;
+    }
+    let name = LANGUAGE_NAMES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map_or(language, |(_, name)| name);
+    prompt.push_str(&format!(
+        "\n\nWrite your answer in {}. Keep code snippets, identifiers, usernames, \
+         and markdown links exactly as they are.",
+        name
+    ));
+    prompt
+}
+
 /// Generate a prompt for creating a short title
 pub fn generate_title_prompt(summary: &str) -> String {
     format!(
@@ -148,6 +500,243 @@ Provide only the title, no additional text or punctuation."#,
     )
 }
 
+/// Generate a prompt for a short summary of one organization's activity
+pub fn summarize_org_prompt(org: &str, repos: &[(&str, &RepoActivity)]) -> String {
+    let mut prompt = format!(
+        "Here is the recent GitHub activity in the repositories of the {} organization:\n\n",
+        org
+    );
+
+    for (repo_name, activity) in repos {
+        prompt.push_str(&format!("{}:\n", repo_name));
+        for (kind, issues) in [
+            ("merged PR", &activity.merged_prs),
+            ("closed issue", &activity.closed_issues),
+            ("new PR", &activity.new_prs),
+            ("updated PR", &activity.updated_prs),
+            ("new issue", &activity.new_issues),
+            ("updated issue", &activity.updated_issues),
+        ] {
+            for issue in issues {
+                prompt.push_str(&format!("- {} #{}: {}\n", kind, issue.number, issue.title));
+            }
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str(
+        "In at most two sentences, summarize what is going on across this organization: the main themes and anything that needs attention. Provide only the summary, with no preamble.",
+    );
+    prompt
+}
+
+/// Generate a prompt asking how the period's activity moved each goal forward
+pub fn goals_check_in_prompt(
+    goals: &[String],
+    activities: &BTreeMap<String, RepoActivity>,
+) -> String {
+    let mut prompt = String::from("These are my goals for the week:\n\n");
+    for (i, goal) in goals.iter().enumerate() {
+        prompt.push_str(&format!("{}. {}\n", i + 1, goal));
+    }
+
+    prompt.push_str("\nHere is the GitHub activity of the report period:\n\n");
+    for (repo_name, activity) in activities {
+        prompt.push_str(&format!("{}:\n", repo_name));
+        for (kind, issues) in [
+            ("merged PR", &activity.merged_prs),
+            ("closed issue", &activity.closed_issues),
+            ("new PR", &activity.new_prs),
+            ("updated PR", &activity.updated_prs),
+            ("new issue", &activity.new_issues),
+            ("updated issue", &activity.updated_issues),
+        ] {
+            for issue in issues {
+                prompt.push_str(&format!(
+                    "- {} [#{}]({}): {}\n",
+                    kind, issue.number, issue.url, issue.title
+                ));
+            }
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str(
+        r#"For each goal, judge the progress this activity shows. Respond with exactly one line per goal, in this format:
+<goal number>. <STATUS>: <one or two sentences on what moved the goal forward or held it back>
+
+STATUS is one of DONE, ON_TRACK, AT_RISK, NO_PROGRESS, or UNCLEAR (when the activity says nothing about the goal). Link the issues and PRs you mention in markdown, like [#123](URL). Provide only these lines."#,
+    );
+    prompt
+}
+
+/// Comments at the end of a thread shown to Claude when drafting a nudge
+const NUDGE_CONTEXT_COMMENTS: usize = 3;
+
+/// Generate a prompt for a short reply to a thread that has been waiting on the user
+pub fn nudge_reply_prompt(
+    repo: &str,
+    issue: &Issue,
+    comments: &[Comment],
+    user: &str,
+    idle_days: i64,
+) -> String {
+    let kind = if issue.is_pull_request {
+        "pull request"
+    } else {
+        "issue"
+    };
+    let mut prompt = format!(
+        "I am @{}. This {} in {} has been waiting on my review or reply for {} days.\n\n#{}: {}\n",
+        user, kind, repo, idle_days, issue.number, issue.title
+    );
+    if let Some(body) = issue.body.as_deref().filter(|b| !b.trim().is_empty()) {
+        let body: String = body.chars().take(1000).collect();
+        prompt.push_str(&format!("\n{}\n", body.trim()));
+    }
+
+    let skip = comments.len().saturating_sub(NUDGE_CONTEXT_COMMENTS);
+    if !comments[skip..].is_empty() {
+        prompt.push_str("\nThe latest comments:\n");
+        for comment in &comments[skip..] {
+            let body: String = comment.body.chars().take(1000).collect();
+            prompt.push_str(&format!("\n@{}: {}\n", comment.author.login, body.trim()));
+        }
+    }
+
+    prompt.push_str(
+        "\nDraft a short, friendly reply I can post to move this forward: acknowledge the wait, and say what happens next or ask what is still needed. Don't promise dates. Provide only the reply text.",
+    );
+    prompt
+}
+
+/// Latest comments of each thread shown to Claude in an escalation check
+const ESCALATION_CONTEXT_COMMENTS: usize = 8;
+
+/// Generate a prompt asking which threads are growing frustrated or hostile
+pub fn escalation_check_prompt(threads: &[(&str, &Issue, &[Comment])]) -> String {
+    let mut prompt =
+        String::from("Here are recent comment threads from GitHub issues and pull requests:\n");
+    for (i, (repo, issue, comments)) in threads.iter().enumerate() {
+        prompt.push_str(&format!(
+            "\n{}. {}#{}: {}\n",
+            i + 1,
+            repo,
+            issue.number,
+            issue.title
+        ));
+        let skip = comments.len().saturating_sub(ESCALATION_CONTEXT_COMMENTS);
+        for comment in &comments[skip..] {
+            let body: String = comment.body.chars().take(500).collect();
+            prompt.push_str(&format!("\n@{}: {}\n", comment.author.login, body.trim()));
+        }
+    }
+
+    prompt.push_str(
+        r#"
+For each thread, judge whether frustration or conflict is rising and a calming reply from a maintainer would help. Respond with exactly one line per thread, in this format:
+<thread number>. <LEVEL>: <one sentence on who is frustrated or in conflict, and about what>
+
+LEVEL is CALM (a normal or friendly discussion, including blunt technical disagreement), TENSE (someone is growing frustrated or impatient), or HEATED (an argument, personal remarks, or hostility). Provide only these lines."#,
+    );
+    prompt
+}
+
+/// Longest release notes, in characters, sent for a release summary
+const MAX_RELEASE_NOTES_CHARS: usize = 3000;
+
+/// Generate a prompt asking for a one-line summary of each release's notes
+pub fn release_summaries_prompt(releases: &[(&str, &Release)]) -> String {
+    let mut prompt = String::from("These releases were published recently:\n\n");
+    for (i, (repo, release)) in releases.iter().enumerate() {
+        prompt.push_str(&format!(
+            "{}. {} {} ({})\n",
+            i + 1,
+            repo,
+            release.title(),
+            release.tag_name
+        ));
+        let notes: String = release
+            .body
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .take(MAX_RELEASE_NOTES_CHARS)
+            .collect();
+        prompt.push_str(&format!("{}\n\n", notes.trim()));
+    }
+
+    prompt.push_str(
+        r#"Summarize the notes of each release in one short sentence, naming the most notable changes. Respond with exactly one line per release, in this format:
+<release number>. <summary>
+
+Provide only these lines."#,
+    );
+    prompt
+}
+
+/// Longest excerpt of a discussion or comment body sent to Claude
+const MAX_DISCUSSION_BODY_CHARS: usize = 1500;
+
+/// Generate a prompt asking for a one-line summary of each discussion thread
+pub fn discussion_summaries_prompt(discussions: &[(&str, &Discussion)]) -> String {
+    let excerpt = |text: &str| -> String {
+        text.trim()
+            .chars()
+            .take(MAX_DISCUSSION_BODY_CHARS)
+            .collect()
+    };
+    let mut prompt = String::from("These GitHub Discussions threads were active recently:\n\n");
+    for (i, (repo, discussion)) in discussions.iter().enumerate() {
+        prompt.push_str(&format!(
+            "{}. {} #{} {} ({}, started by @{}, {} comments{})\n",
+            i + 1,
+            repo,
+            discussion.number,
+            discussion.title,
+            discussion.category,
+            discussion.author,
+            discussion.comment_count,
+            if discussion.is_answered {
+                ", answered"
+            } else {
+                ""
+            }
+        ));
+        prompt.push_str(&format!("{}\n", excerpt(&discussion.body)));
+        for comment in &discussion.comments {
+            prompt.push_str(&format!(
+                "  @{}: {}\n",
+                comment.author,
+                excerpt(&comment.body)
+            ));
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str(
+        r#"Summarize each thread in one short sentence: what is being discussed and where the conversation stands. Respond with exactly one line per discussion, in this format:
+<discussion number>. <summary>
+
+Provide only these lines."#,
+    );
+    prompt
+}
+
+/// Generate a prompt asking for the probable cause of a CI failure
+pub fn explain_ci_failure_prompt(workflow: &str, pr_title: &str, log_excerpt: &str) -> String {
+    format!(
+        r#"The "{}" GitHub Actions workflow failed on the pull request "{}". Here is the relevant part of the failed job's log:
+
+```
+{}
+```
+
+In one sentence of at most 25 words, explain the most probable cause of the failure. Provide only the sentence, with no preamble."#,
+        workflow, pr_title, log_excerpt
+    )
+}
+
 /// Generate a prompt for summarizing issue/PR context
 pub fn summarize_context_prompt(
     issue_title: &str,
@@ -184,14 +773,43 @@ Description:
     prompt
 }
 
+/// Generate a prompt for updating a stored thread summary with new comments
+pub fn thread_update_prompt(
+    issue_title: &str,
+    previous_summary: &str,
+    comments: &[String],
+) -> String {
+    let mut prompt = format!(
+        r#"You previously summarized the GitHub issue/PR "{}" as:
+
+{}
+
+New comments since then:
+
+"#,
+        issue_title, previous_summary
+    );
+
+    for (i, comment) in comments.iter().enumerate() {
+        prompt.push_str(&format!("Comment {}:\n{}\n\n", i + 1, comment));
+    }
+
+    prompt.push_str(
+        r#"Respond in exactly this format:
+CHANGED: <one sentence on what changed since the previous summary: decisions, new findings, status changes>
+SUMMARY: <the updated summary, 2-4 sentences>"#,
+    );
+
+    prompt
+}
+
 /// Generate a prompt for filtering activities by importance
 pub fn filter_activities_prompt(activities_summary: &str, watch_rules: &[String]) -> String {
-    let mut prompt = format!(
-        r#"Given these watch rules for what's important:
+    let mut prompt = r#"Given these watch rules for what's important:
 
 Watch Rules:
 "#
-    );
+    .to_string();
 
     for rule in watch_rules {
         prompt.push_str(&format!("- {}\n", rule));
@@ -213,7 +831,93 @@ For each item, briefly explain which rule it matches and why it matters."#,
     prompt
 }
 
+/// Longest issue description, in characters, sent for triage
+const MAX_TRIAGE_BODY_CHARS: usize = 2000;
+
+/// Generate a prompt asking for labels, duplicates, and a first response for
+/// each issue to triage
+///
+/// `open_issues` are the other open issues of the repository, which Claude
+/// may point to as duplicates.
+pub fn triage_issues_prompt(
+    repo_name: &str,
+    labels: &[Label],
+    issues: &[&Issue],
+    open_issues: &[&Issue],
+) -> String {
+    let mut prompt = format!(
+        "I maintain {} and am triaging its new and unlabeled issues.\n\nLabels of the repository:\n",
+        repo_name
+    );
+    for label in labels {
+        match label
+            .description
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+        {
+            Some(description) => {
+                prompt.push_str(&format!("- {}: {}\n", label.name, description.trim()))
+            }
+            None => prompt.push_str(&format!("- {}\n", label.name)),
+        }
+    }
+
+    prompt.push_str("\nOther open issues:\n");
+    for issue in open_issues {
+        prompt.push_str(&format!("- #{}: {}\n", issue.number, issue.title));
+    }
+
+    prompt.push_str("\nIssues to triage:\n\n");
+    for issue in issues {
+        let body: String = issue
+            .body
+            .as_deref()
+            .unwrap_or("No description provided.")
+            .chars()
+            .take(MAX_TRIAGE_BODY_CHARS)
+            .collect();
+        prompt.push_str(&format!(
+            "### #{}: {}\nOpened by @{}\n\n{}\n\n",
+            issue.number,
+            issue.title,
+            issue.author.login,
+            body.trim()
+        ));
+    }
+
+    prompt.push_str(
+        r#"For each issue to triage, respond with a block in exactly this format:
+#<issue number>
+LABELS: <comma-separated labels from the list above, or none>
+DUPLICATES: <comma-separated numbers of other open issues it duplicates, like #12, or none>
+RESPONSE: <a short, friendly first response to the author: thank them, ask for what is missing to act on it, or point to the duplicate>
+
+Only use labels from the list, and only name a duplicate when it reports the same problem. Provide only these blocks."#,
+    );
+    prompt
+}
+
+/// The comments of a thread for a maintainer prompt, noting how many were left out
+fn discussion_section(comments: &[(String, String)], omitted: usize) -> String {
+    let mut section = String::new();
+    if comments.is_empty() {
+        return section;
+    }
+    section.push_str("**Discussion:**\n");
+    for (i, (author, body)) in comments.iter().enumerate() {
+        section.push_str(&format!("Comment {} by @{}:\n{}\n\n", i + 1, author, body));
+    }
+    if omitted > 0 {
+        section.push_str(&format!(
+            "({} more comments of this thread are not shown.)\n\n",
+            omitted
+        ));
+    }
+    section
+}
+
 /// Generate a maintainer-focused prompt for summarizing a specific issue/PR
+#[allow(clippy::too_many_arguments)]
 pub fn summarize_issue_for_maintainer(
     issue_title: &str,
     issue_body: &str,
@@ -222,6 +926,7 @@ pub fn summarize_issue_for_maintainer(
     issue_labels: &[String],
     issue_url: &str,
     comments: &[(String, String)], // (author, body) pairs
+    omitted_comments: usize,
     include_recommendations: bool,
 ) -> String {
     let mut prompt = format!(
@@ -250,12 +955,7 @@ pub fn summarize_issue_for_maintainer(
         issue_body
     );
 
-    if !comments.is_empty() {
-        prompt.push_str("**Discussion:**\n");
-        for (i, (author, body)) in comments.iter().enumerate() {
-            prompt.push_str(&format!("Comment {} by @{}:\n{}\n\n", i + 1, author, body));
-        }
-    }
+    prompt.push_str(&discussion_section(comments, omitted_comments));
 
     if include_recommendations {
         prompt.push_str(r#"
@@ -322,6 +1022,111 @@ Latest developments in chronological order.
     prompt
 }
 
+/// An issue or PR summarized together with others
+pub struct ComparedItem<'a> {
+    pub repo: &'a str,
+    pub issue: &'a Issue,
+    pub comments: &'a [Comment],
+    /// Changed files of a PR, as for a single summary
+    pub diff_summary: Option<&'a str>,
+}
+
+/// Longest excerpt of a description or comment of a compared item
+const MAX_COMPARED_BODY_CHARS: usize = 3000;
+
+/// Most recent comments of each compared item sent to Claude
+const MAX_COMPARED_COMMENTS: usize = 10;
+
+/// Generate a prompt asking how several issues and PRs relate to each other
+pub fn compare_items_prompt(items: &[ComparedItem], include_recommendations: bool) -> String {
+    let excerpt = |text: &str, max: usize| -> String { text.trim().chars().take(max).collect() };
+    let mut prompt = format!(
+        "You are helping a project maintainer understand how {} related GitHub issues and PRs fit together.\n\n",
+        items.len()
+    );
+    for item in items {
+        let issue = item.issue;
+        prompt.push_str(&format!(
+            "### {} {}#{}: {}\n- State: {}\n- Author: @{}\n- URL: {}\n",
+            if issue.is_pull_request { "PR" } else { "Issue" },
+            item.repo,
+            issue.number,
+            issue.title,
+            match issue.state {
+                IssueState::Open => "open",
+                IssueState::Closed => "closed",
+                IssueState::Merged => "merged",
+            },
+            issue.author.login,
+            issue.url
+        ));
+        if let Some(diff_summary) = item.diff_summary {
+            prompt.push_str(&format!("- Changes: {}\n", diff_summary));
+        }
+        prompt.push_str(&format!(
+            "\n{}\n\n",
+            excerpt(
+                issue.body.as_deref().unwrap_or("No description provided."),
+                MAX_COMPARED_BODY_CHARS
+            )
+        ));
+        let skipped = item.comments.len().saturating_sub(MAX_COMPARED_COMMENTS);
+        for comment in &item.comments[skipped..] {
+            prompt.push_str(&format!(
+                "Comment by @{}:\n{}\n\n",
+                comment.author.login,
+                excerpt(&comment.body, MAX_COMPARED_BODY_CHARS)
+            ));
+        }
+    }
+
+    prompt.push_str(
+        r#"**Provide the following analysis:**
+
+## Overview
+One or two sentences on what each item is about.
+
+## Overlaps
+Where the items address the same problem or touch the same code.
+
+## Conflicts
+Changes or proposals that contradict each other or would conflict when merged.
+
+## Sequencing
+The order in which the items should land or be resolved, and what depends on what.
+"#,
+    );
+    if include_recommendations {
+        prompt.push_str(
+            r#"
+## Recommendations
+Concrete next steps for the maintainer, such as which item to close as a duplicate or which to merge first.
+"#,
+        );
+    }
+    prompt.push_str(
+        r#"
+**Format as markdown with these headings. Always refer to an item with a clickable link to its URL, like [owner/repo#123](URL), and make users clickable: [@username](https://github.com/username)**"#,
+    );
+    prompt
+}
+
+/// Filename of a comparison of several issues and PRs, e.g.
+/// `tokio-12-14-axum-3-comparison.md`
+pub fn generate_comparison_filename(items: &[(String, u32)]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut previous_repo = None;
+    for (repo_name, number) in items {
+        if previous_repo != Some(repo_name) {
+            let repo = repo_name.split('/').nth(1).unwrap_or(repo_name);
+            parts.push(repo.to_string());
+            previous_repo = Some(repo_name);
+        }
+        parts.push(number.to_string());
+    }
+    format!("{}-comparison.md", parts.join("-"))
+}
+
 /// Generate a filename-safe version of an issue title
 pub fn generate_issue_filename(repo_name: &str, issue_number: u32, title: &str) -> String {
     // Extract just the repo name (not owner/repo)
@@ -351,6 +1156,7 @@ pub fn generate_issue_filename(repo_name: &str, issue_number: u32, title: &str)
 }
 
 /// Generate a specialized prompt for Claude Code review of a PR
+#[allow(clippy::too_many_arguments)]
 pub fn review_pr_for_maintainer(
     pr_title: &str,
     pr_body: &str,
@@ -359,7 +1165,9 @@ pub fn review_pr_for_maintainer(
     pr_labels: &[String],
     pr_url: &str,
     comments: &[(String, String)], // (author, body) pairs
-    diff_summary: &str,            // Summary of file changes
+    omitted_comments: usize,
+    diff_summary: &str, // Summary of file changes
+    review_threads: &[ReviewThread],
     include_recommendations: bool,
 ) -> String {
     let mut prompt = format!(
@@ -392,11 +1200,10 @@ pub fn review_pr_for_maintainer(
         diff_summary
     );
 
-    if !comments.is_empty() {
-        prompt.push_str("**Discussion:**\n");
-        for (i, (author, body)) in comments.iter().enumerate() {
-            prompt.push_str(&format!("Comment {} by @{}:\n{}\n\n", i + 1, author, body));
-        }
+    prompt.push_str(&discussion_section(comments, omitted_comments));
+
+    if !review_threads.is_empty() {
+        prompt.push_str(&review_threads_section(review_threads));
     }
 
     if include_recommendations {
@@ -465,13 +1272,72 @@ Key technical points discussed in the PR comments.
         );
     }
 
+    if review_threads.iter().any(|t| !t.is_resolved) {
+        prompt.push_str(
+            r#"
+
+Also include a section **## Unresolved Review Threads** listing every unresolved review thread with its file location, the topic in a few words, and what is still needed to resolve it. Put long-running threads first; they are what blocks merging."#,
+        );
+    }
+
     prompt
 }
 
+/// Review threads of a PR for a prompt, with unresolved threads spelled out
+fn review_threads_section(threads: &[ReviewThread]) -> String {
+    let unresolved: Vec<&ReviewThread> = threads.iter().filter(|t| !t.is_resolved).collect();
+    let long_resolved = threads
+        .iter()
+        .filter(|t| t.is_resolved && t.is_long_running())
+        .count();
+
+    let mut section = format!(
+        "**Review Threads:** {} total, {} resolved, {} unresolved\n",
+        threads.len(),
+        threads.len() - unresolved.len(),
+        unresolved.len()
+    );
+    if long_resolved > 0 {
+        section.push_str(&format!(
+            "{} long-running threads have been resolved.\n",
+            long_resolved
+        ));
+    }
+
+    if !unresolved.is_empty() {
+        section.push_str("\nUnresolved threads:\n");
+        for thread in unresolved {
+            let mut notes = vec![format!("{} comments", thread.comment_count)];
+            if thread.is_long_running() {
+                notes.push("long-running".to_string());
+            }
+            if thread.is_outdated {
+                notes.push("outdated".to_string());
+            }
+            section.push_str(&format!(
+                "- {} ({}), between @{}\n",
+                thread.location(),
+                notes.join(", "),
+                thread.participants().join(", @")
+            ));
+            for comment in &thread.comments {
+                section.push_str(&format!(
+                    "  > @{}: {}\n",
+                    comment.author,
+                    comment.body.trim()
+                ));
+            }
+        }
+    }
+    section.push('\n');
+    section
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::github::{Author, CommentCount, Issue, IssueState};
+    use crate::github::{Author, CommentCount, IssueState, PrFileChange};
+    use crate::test_utils::create_test_issue;
     use jiff::Timestamp;
 
     #[test]
@@ -501,16 +1367,202 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 0 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         });
 
         activities.insert("test/repo".to_string(), repo_activity);
 
-        let prompt = summarize_activities_prompt(&activities, Some("Focus on bug fixes"));
+        let prompt =
+            summarize_activities_prompt(&activities, Some("Focus on bug fixes"), &BTreeMap::new());
 
         assert!(prompt.contains("User Context:"));
         assert!(prompt.contains("Focus on bug fixes"));
         assert!(prompt.contains("Repository: test/repo"));
         assert!(prompt.contains("[Issue #42]"));
+        assert!(!prompt.contains("Repository Context"));
+    }
+
+    #[test]
+    fn test_summarize_activities_prompt_assignment() {
+        let mut issue = create_test_issue(7, "Flaky timer test", false);
+        issue.assignees = vec![Author {
+            login: "alice".to_string(),
+            user_type: None,
+        }];
+        issue.milestone = Some(crate::github::Milestone {
+            title: "v1.38".to_string(),
+            due_on: Some("2024-05-01T00:00:00Z".parse().unwrap()),
+        });
+        let mut repo_activity = RepoActivity::default();
+        repo_activity.updated_issues.push(issue);
+        let activities = BTreeMap::from([("tokio-rs/tokio".to_string(), repo_activity)]);
+
+        let prompt = summarize_activities_prompt(&activities, None, &BTreeMap::new());
+        assert!(prompt
+            .contains("comments: 0)\n  Assignees: @alice\n  Milestone: v1.38 (due 2024-05-01)\n"));
+    }
+
+    #[test]
+    fn test_summarize_activities_prompt_repo_context() {
+        let issue = |url: &str| Issue {
+            number: 1,
+            title: "Scheduler hang".to_string(),
+            body: None,
+            state: IssueState::Open,
+            author: Author {
+                login: "testuser".to_string(),
+                user_type: None,
+            },
+            created_at: Timestamp::now(),
+            updated_at: Timestamp::now(),
+            labels: vec![],
+            url: url.to_string(),
+            comments: CommentCount { total_count: 0 },
+            is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
+        };
+
+        let mut activities = BTreeMap::new();
+        let mut tokio = RepoActivity::default();
+        tokio
+            .new_issues
+            .push(issue("https://github.com/tokio-rs/tokio/issues/1"));
+        activities.insert("tokio-rs/tokio".to_string(), tokio);
+        let mut mio = RepoActivity::default();
+        mio.new_issues
+            .push(issue("https://github.com/tokio-rs/mio/issues/1"));
+        activities.insert("tokio-rs/mio".to_string(), mio);
+
+        let repo_contexts = BTreeMap::from([(
+            "tokio-rs/tokio".to_string(),
+            "Scheduler changes are highest priority".to_string(),
+        )]);
+
+        let prompt = summarize_activities_prompt(&activities, None, &repo_contexts);
+
+        assert_eq!(prompt.matches("Repository Context").count(), 1);
+        let tokio_section = prompt
+            .split("## Repository: tokio-rs/tokio")
+            .nth(1)
+            .unwrap();
+        assert!(tokio_section.starts_with(
+            "\n\nRepository Context (tokio-rs/tokio):\nScheduler changes are highest priority"
+        ));
+    }
+
+    #[test]
+    fn test_triage_issues_prompt() {
+        let labels = [
+            Label {
+                name: "bug".to_string(),
+                color: None,
+                description: Some("Something isn't working".to_string()),
+            },
+            Label {
+                name: "A-io".to_string(),
+                color: None,
+                description: None,
+            },
+        ];
+        let new = create_test_issue(12, "Panic on shutdown", false);
+        let old = create_test_issue(7, "Runtime panics when dropped", false);
+
+        let prompt = triage_issues_prompt("tokio-rs/tokio", &labels, &[&new], &[&old]);
+        assert!(prompt.contains("- bug: Something isn't working\n- A-io\n"));
+        assert!(prompt.contains("Other open issues:\n- #7: Runtime panics when dropped\n"));
+        assert!(prompt.contains(
+            "### #12: Panic on shutdown\nOpened by @testuser\n\nBody of Panic on shutdown\n"
+        ));
+        assert!(prompt.contains("DUPLICATES:"));
+    }
+
+    #[test]
+    fn test_combine_summaries_prompt() {
+        let prompt = combine_summaries_prompt(
+            &[
+                ("tokio-rs/axum", "Routing fixes.\n".to_string()),
+                ("tokio-rs/tokio", "A scheduler hang.".to_string()),
+            ],
+            Some("Focus on bug fixes"),
+        );
+        assert!(prompt.starts_with("User Context:\nFocus on bug fixes\n\n"));
+        assert!(prompt.contains(
+            "## Repository: tokio-rs/axum\n\nRouting fixes.\n\n## Repository: tokio-rs/tokio\n\nA scheduler hang.\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_chunk_activities() {
+        let activity = |first: u32, count: u32| RepoActivity {
+            new_issues: (first..first + count)
+                .map(|n| create_test_issue(n, &format!("Issue number {}", n), false))
+                .collect(),
+            ..RepoActivity::default()
+        };
+        let activities = BTreeMap::from([
+            ("tokio-rs/axum".to_string(), activity(1, 2)),
+            ("tokio-rs/mio".to_string(), activity(10, 1)),
+            ("tokio-rs/tokio".to_string(), activity(100, 40)),
+        ]);
+        let contexts = BTreeMap::new();
+        let prompt_tokens = |chunk: &BTreeMap<String, RepoActivity>| {
+            estimate_tokens(&summarize_activities_prompt(chunk, None, &contexts))
+        };
+
+        // Everything fits in one request
+        let budget = prompt_tokens(&activities);
+        assert_eq!(chunk_activities(&activities, &contexts, budget).len(), 1);
+
+        // Small repositories share a request, tokio is split in parts
+        let small: BTreeMap<_, _> = activities
+            .iter()
+            .filter(|(repo, _)| *repo != "tokio-rs/tokio")
+            .map(|(repo, activity)| (repo.clone(), activity.clone()))
+            .collect();
+        let budget = prompt_tokens(&small) + 10;
+        let chunks = chunk_activities(&activities, &contexts, budget);
+        assert!(chunks.len() > 2);
+        assert_eq!(
+            chunks[0].keys().collect::<Vec<_>>(),
+            ["tokio-rs/axum", "tokio-rs/mio"]
+        );
+        assert!(chunks.iter().all(|chunk| prompt_tokens(chunk) <= budget));
+        let tokio_issues: usize = chunks
+            .iter()
+            .filter_map(|chunk| chunk.get("tokio-rs/tokio"))
+            .map(|activity| activity.new_issues.len())
+            .sum();
+        assert_eq!(tokio_issues, 40);
+
+        // A single item too large for the budget is sent on its own
+        let chunks = chunk_activities(&activities, &contexts, 1);
+        assert_eq!(chunks.len(), 43);
+    }
+
+    #[test]
+    fn test_with_output_language() {
+        let prompt = || "Summarize this.".to_string();
+        assert_eq!(with_output_language(prompt(), None), "Summarize this.");
+        assert_eq!(
+            with_output_language(prompt(), Some("en")),
+            "Summarize this."
+        );
+        assert_eq!(
+            with_output_language(prompt(), Some("en-GB")),
+            "Summarize this."
+        );
+
+        let german = with_output_language(prompt(), Some("de"));
+        assert!(german.starts_with("Summarize this.\n\nWrite your answer in German."));
+        assert!(german.contains("markdown links exactly as they are"));
+        assert!(with_output_language(prompt(), Some("pt_BR")).contains("in Portuguese."));
+        assert!(with_output_language(prompt(), Some("Esperanto")).contains("in Esperanto."));
     }
 
     #[test]
@@ -522,6 +1574,135 @@ mod tests {
         assert!(prompt.contains("8 words or fewer"));
     }
 
+    #[test]
+    fn test_thread_update_prompt() {
+        let prompt = thread_update_prompt(
+            "Runtime hangs on shutdown",
+            "Shutdown hangs with pending blocking tasks.",
+            &["Bisected to #6100".to_string()],
+        );
+
+        assert!(prompt.contains("\"Runtime hangs on shutdown\""));
+        assert!(prompt.contains("Comment 1:\nBisected to #6100"));
+        assert!(prompt.contains("CHANGED:"));
+        assert!(prompt.contains("SUMMARY:"));
+    }
+
+    #[test]
+    fn test_summarize_org_prompt() {
+        let mut activity = RepoActivity::default();
+        activity
+            .merged_prs
+            .push(create_test_issue(12, "Add poll_read_ready", true));
+        activity
+            .new_issues
+            .push(create_test_issue(40, "Runtime hangs on shutdown", false));
+
+        let prompt = summarize_org_prompt("tokio-rs", &[("tokio-rs/tokio", &activity)]);
+        assert!(prompt.contains("repositories of the tokio-rs organization"));
+        assert!(prompt.contains("tokio-rs/tokio:\n- merged PR #12: Add poll_read_ready\n"));
+        assert!(prompt.contains("- new issue #40: Runtime hangs on shutdown"));
+        assert!(prompt.contains("at most two sentences"));
+    }
+
+    #[test]
+    fn test_pr_changes_section() {
+        let file = |filename: &str, additions: u32, deletions: u32| PrFileChange {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions,
+            deletions,
+            changes: additions + deletions,
+            patch: None,
+        };
+        let pr = create_test_issue(12, "io_uring driver", true);
+        let diff = PrDiff {
+            files: vec![file("README.md", 2, 0), file("src/io/uring.rs", 300, 12)],
+            total_additions: 302,
+            total_deletions: 12,
+            total_files: 2,
+        };
+
+        let section = pr_changes_section(&[("tokio-rs/tokio", &pr, &diff)]);
+        assert_eq!(
+            section,
+            "Pull request changes:\n\
+             - [PR #12](https://github.com/test/repo/pull/12) in tokio-rs/tokio: +302/-12 in 2 files\n\
+             \x20 - src/io/uring.rs (modified, +300/-12)\n\
+             \x20 - README.md (modified, +2/-0)\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_patches_section() {
+        let file = |filename: &str, changes: u32, patch: Option<&str>| PrFileChange {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: changes,
+            deletions: 0,
+            changes,
+            patch: patch.map(str::to_string),
+        };
+        let long_patch = format!("@@ -1,0 +1,40 @@\n{}", "+let x = 1;\n".repeat(40));
+        let diff = PrDiff {
+            files: vec![
+                file("README.md", 1, Some("@@ -1 +1 @@\n-old\n+new")),
+                file("logo.png", 0, None),
+                file("src/io/uring.rs", 40, Some(&long_patch)),
+            ],
+            total_additions: 41,
+            total_deletions: 0,
+            total_files: 3,
+        };
+
+        let all = diff_patches_section(&diff, 10_000);
+        assert!(all.starts_with("src/io/uring.rs (modified, +40/-0):\n```diff\n@@ -1,0 +1,40 @@\n"));
+        assert!(
+            all.ends_with("README.md (modified, +1/-0):\n```diff\n@@ -1 +1 @@\n-old\n+new\n```\n")
+        );
+        assert!(!all.contains("logo.png"));
+
+        let cut = diff_patches_section(&diff, 60);
+        assert!(cut.ends_with("+let x = 1;\n… (truncated)\n```\n"));
+        assert!(!cut.contains("README.md"));
+        assert!(estimate_tokens(&cut) <= 65);
+    }
+
+    #[test]
+    fn test_rollup_instructions() {
+        let instructions = rollup_instructions("weekly", "week", "- Opened: 12 (last week: 9)\n");
+        assert!(instructions
+            .contains("This is a weekly rollup for someone catching up on the whole week."));
+        assert!(instructions.contains("3. Completed vs. planned: "));
+        assert!(instructions.ends_with("Counts of this week:\n- Opened: 12 (last week: 9)\n"));
+    }
+
+    #[test]
+    fn test_goals_check_in_prompt() {
+        let mut activity = RepoActivity::default();
+        activity
+            .merged_prs
+            .push(create_test_issue(12, "io_uring driver", true));
+        let activities = BTreeMap::from([("tokio-rs/tokio".to_string(), activity)]);
+
+        let prompt = goals_check_in_prompt(&["Merge the io_uring PR".to_string()], &activities);
+        assert!(prompt.contains("1. Merge the io_uring PR\n"));
+        assert!(prompt.contains(
+            "- merged PR [#12](https://github.com/test/repo/pull/12): io_uring driver\n"
+        ));
+        assert!(prompt.contains("<goal number>. <STATUS>:"));
+    }
+
+    #[test]
+    fn test_explain_ci_failure_prompt() {
+        let prompt =
+            explain_ci_failure_prompt("CI", "Add io_uring", "error[E0308]: mismatched types");
+
+        assert!(prompt.contains("\"CI\" GitHub Actions workflow"));
+        assert!(prompt.contains("error[E0308]"));
+        assert!(prompt.contains("one sentence"));
+    }
+
     #[test]
     fn test_summarize_issue_for_maintainer_with_recommendations() {
         let prompt = summarize_issue_for_maintainer(
@@ -529,9 +1710,9 @@ mod tests {
             "Detailed description of the memory leak...",
             "open",
             "user123",
-            &vec!["bug".to_string(), "critical".to_string()],
+            &["bug".to_string(), "critical".to_string()],
             "https://github.com/owner/repo/issues/123",
-            &vec![
+            &[
                 (
                     "reviewer1".to_string(),
                     "I can reproduce this issue".to_string(),
@@ -541,6 +1722,7 @@ mod tests {
                     "Let's prioritize this fix".to_string(),
                 ),
             ],
+            12,
             true,
         );
 
@@ -551,6 +1733,8 @@ mod tests {
         assert!(prompt.contains("Recommendations"));
         assert!(prompt.contains("@reviewer1"));
         assert!(prompt.contains("I can reproduce this issue"));
+        assert!(prompt
+            .contains("Comment 2 by @maintainer:\nLet's prioritize this fix\n\n(12 more comments"));
     }
 
     #[test]
@@ -560,20 +1744,61 @@ mod tests {
             "Description of the feature...",
             "open",
             "contributor",
-            &vec![],
+            &[],
             "https://github.com/owner/repo/issues/456",
-            &vec![],
+            &[],
+            0,
             false,
         );
 
         assert!(prompt.contains("Feature request: Add new API"));
         assert!(prompt.contains("@contributor"));
         assert!(prompt.contains("Labels: none"));
+        assert!(!prompt.contains("**Discussion:**"));
         assert!(prompt.contains("Current Status"));
         assert!(!prompt.contains("Required Action"));
         assert!(!prompt.contains("Recommendations"));
     }
 
+    #[test]
+    fn test_compare_items_prompt() {
+        let mut pr = create_test_issue(14, "Use io_uring for files", true);
+        pr.body = Some("Replaces the blocking pool.".to_string());
+        let issue = create_test_issue(12, "File reads block the runtime", false);
+        let items = [
+            ComparedItem {
+                repo: "tokio-rs/tokio",
+                issue: &issue,
+                comments: &[],
+                diff_summary: None,
+            },
+            ComparedItem {
+                repo: "tokio-rs/tokio",
+                issue: &pr,
+                comments: &[],
+                diff_summary: Some("Modified 3 files."),
+            },
+        ];
+
+        let prompt = compare_items_prompt(&items, false);
+        assert!(prompt.contains("fit together"));
+        assert!(prompt.contains(
+            "### PR tokio-rs/tokio#14: Use io_uring for files\n- State: open\n- Author: @testuser\n"
+        ));
+        assert!(prompt.contains("- Changes: Modified 3 files.\n\nReplaces the blocking pool.\n"));
+        assert!(prompt.contains("## Sequencing"));
+        assert!(!prompt.contains("## Recommendations"));
+
+        assert_eq!(
+            generate_comparison_filename(&[
+                ("tokio-rs/tokio".to_string(), 12),
+                ("tokio-rs/tokio".to_string(), 14),
+                ("tokio-rs/axum".to_string(), 3),
+            ]),
+            "tokio-12-14-axum-3-comparison.md"
+        );
+    }
+
     #[test]
     fn test_generate_issue_filename() {
         // Test basic functionality
@@ -607,13 +1832,15 @@ mod tests {
             "This PR introduces async/await syntax support with full backwards compatibility.",
             "open",
             "contributor123",
-            &vec!["enhancement".to_string(), "breaking-change".to_string()],
+            &["enhancement".to_string(), "breaking-change".to_string()],
             "https://github.com/owner/repo/pull/456",
-            &vec![
+            &[
                 ("reviewer1".to_string(), "The implementation looks solid".to_string()),
                 ("maintainer".to_string(), "Let's ensure all tests pass".to_string()),
             ],
+            0,
             "Modified 15 files: 8 Rust files, 4 test files, 3 documentation files. Added 342 lines, removed 89 lines.",
+            &[],
             true,
         );
 
@@ -635,10 +1862,12 @@ mod tests {
             "Simple typo fix in README.md",
             "merged",
             "docs-contributor",
-            &vec![],
+            &[],
             "https://github.com/owner/repo/pull/789",
-            &vec![],
+            &[],
+            0,
             "Modified 1 file: README.md. Added 1 line, removed 1 line.",
+            &[],
             false,
         );
 
@@ -649,5 +1878,57 @@ mod tests {
         assert!(prompt.contains("Technical Assessment"));
         assert!(!prompt.contains("Required Actions"));
         assert!(!prompt.contains("Recommendations"));
+        assert!(!prompt.contains("Unresolved Review Threads"));
+    }
+
+    #[test]
+    fn test_review_pr_with_review_threads() {
+        use crate::github::ReviewThreadComment;
+
+        let thread = |path: &str, is_resolved, comment_count| ReviewThread {
+            path: path.to_string(),
+            line: Some(10),
+            is_resolved,
+            is_outdated: false,
+            comment_count,
+            comments: vec![
+                ReviewThreadComment {
+                    author: "reviewer".to_string(),
+                    body: "This allocates on every poll".to_string(),
+                },
+                ReviewThreadComment {
+                    author: "contributor".to_string(),
+                    body: "It's needed for the waker".to_string(),
+                },
+            ],
+        };
+        let threads = [
+            thread("src/io.rs", false, 6),
+            thread("src/net.rs", true, 4),
+            thread("README.md", true, 1),
+        ];
+
+        let prompt = review_pr_for_maintainer(
+            "Add poll_read_ready",
+            "",
+            "open",
+            "contributor",
+            &[],
+            "https://github.com/owner/repo/pull/1",
+            &[],
+            0,
+            "Modified 2 files.",
+            &threads,
+            false,
+        );
+
+        assert!(prompt.contains("3 total, 2 resolved, 1 unresolved"));
+        assert!(prompt.contains("1 long-running threads have been resolved"));
+        assert!(prompt.contains(
+            "- src/io.rs:10 (6 comments, long-running), between @reviewer, @contributor"
+        ));
+        assert!(prompt.contains("> @reviewer: This allocates on every poll"));
+        assert!(!prompt.contains("src/net.rs:10"));
+        assert!(prompt.contains("## Unresolved Review Threads"));
     }
 }
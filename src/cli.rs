@@ -1,6 +1,11 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand};
+use clap_complete::Shell;
 use std::path::PathBuf;
 
+use crate::activity::ActivityFormat;
+use crate::config::ReportFormat;
+use crate::report::ReportFlavor;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "gh-report",
@@ -16,26 +21,64 @@ pub struct Cli {
     #[arg(short, long, env = "GH_REPORT_CONFIG")]
     pub config: Option<PathBuf>,
 
+    /// Use the configuration of a named profile (~/.config/gh-report/profiles/NAME.toml)
+    #[arg(
+        long,
+        value_name = "NAME",
+        env = "GH_REPORT_PROFILE",
+        conflicts_with = "config"
+    )]
+    pub profile: Option<String>,
+
     /// Override the state file location
     #[arg(long)]
     pub state: Option<PathBuf>,
 
+    /// Run even if another gh-report instance holds the state file lock
+    #[arg(long, global = true)]
+    pub force: bool,
+
     /// Verbosity level (can be repeated)
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
+
+    /// Print how long each phase of the run took (fetching, filtering, Claude, rendering)
+    #[arg(long, global = true)]
+    pub profile_run: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Generate activity report
     Report {
-        /// Time period to look back (e.g., 3d, 12h, 2w)
-        #[arg(long, default_value = "7d")]
+        /// Time period to look back (e.g., 3d, 12h, 2w), or where to start (2024-05-01, yesterday, last-week)
+        #[arg(
+            long,
+            default_value = "7d",
+            default_value_if("flavor", "monthly", "30d")
+        )]
         since: String,
 
-        /// Override the output file location  
+        /// End of the report window, a date that is included (2024-05-07) or a keyword (yesterday)
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+
+        /// Start exactly where the previous report ended (falls back to --since on the first run)
+        #[arg(long, conflicts_with_all = ["since", "until"])]
+        since_last: bool,
+
+        /// Write the report here instead of the report directory (repeatable)
+        ///
+        /// Paths without an extension are directories and get the configured file name.
+        /// `-` prints the report to stdout, and status messages are left out.
         #[arg(short, long)]
-        output: Option<PathBuf>,
+        output: Vec<PathBuf>,
+
+        /// Add the report to the end of an existing file of the same name instead of replacing it
+        ///
+        /// Overrides `settings.collision_strategy` for this run.
+        #[arg(long)]
+        append: bool,
 
         /// Preview what would be fetched without generating report
         #[arg(long)]
@@ -52,6 +95,96 @@ pub enum Commands {
         /// Clear all cached data before running
         #[arg(long)]
         clear_cache: bool,
+
+        /// Connect report items with branches and unpushed work in a local clone
+        #[arg(long, value_name = "PATH")]
+        with_git: Option<PathBuf>,
+
+        /// Dig into failing GitHub Actions runs of PRs in the report and explain the probable cause
+        #[arg(long)]
+        ci_logs: bool,
+
+        /// Override the primary model for this run (e.g. opus, sonnet, or a full model name)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Override the secondary model for this run (e.g. haiku)
+        #[arg(long, value_name = "MODEL")]
+        secondary_model: Option<String>,
+
+        /// Write the summary and title in this language (e.g. de), overriding `claude.output_language`
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+
+        /// Output format of the report (defaults to `report.format` in the config)
+        #[arg(long, value_enum)]
+        format: Option<ReportFormat>,
+
+        /// Write a weekly or monthly rollup of themes and trends, compared to the previous period
+        ///
+        /// Monthly rollups look back 30 days unless --since is given.
+        #[arg(long, value_enum, default_value_t)]
+        flavor: ReportFlavor,
+
+        /// Build the report from a snapshot saved with `activity --save-snapshot` instead of fetching
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["since", "until", "since_last", "dry_run", "estimate_cost", "ci_logs"]
+        )]
+        from_snapshot: Option<PathBuf>,
+
+        /// Build the report from webhook events stored with `ingest` instead of polling the events API
+        #[arg(
+            long,
+            conflicts_with_all = ["from_snapshot", "org", "users", "dry_run", "estimate_cost", "resume"]
+        )]
+        from_events: bool,
+
+        /// Build the report from cached GitHub data and AI summaries only, never running gh or Claude
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "from_snapshot", "from_events", "dry_run", "estimate_cost", "resume",
+                "no_cache", "clear_cache", "email"
+            ]
+        )]
+        offline: bool,
+
+        /// Report on all repositories of an organization instead of your own activity
+        #[arg(long, value_name = "ORG", conflicts_with = "from_snapshot")]
+        org: Option<String>,
+
+        /// Report on what these GitHub users did instead of your own activity (repeatable)
+        #[arg(long = "user", value_name = "LOGIN", conflicts_with_all = ["from_snapshot", "org"])]
+        users: Vec<String>,
+
+        /// Only report on these repositories, e.g. `tokio-rs/tokio,carllerche/*` (replaces `report.include_repos`)
+        #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+        repos: Vec<String>,
+
+        /// Email the saved report to this address (repeatable), using `[delivery.email]`
+        #[arg(long, value_name = "ADDRESS", conflicts_with = "dry_run")]
+        email: Vec<String>,
+
+        /// Open the saved report in the default viewer (or `$EDITOR`)
+        #[arg(long)]
+        open: bool,
+
+        /// List every item, ignoring `report.min_priority_score`
+        #[arg(long)]
+        full: bool,
+
+        /// Keep only the N highest-priority issues and PRs (replaces `settings.max_issues_per_report`)
+        #[arg(long, value_name = "N")]
+        max_items: Option<usize>,
+
+        /// Pick up an interrupted report, skipping what it already fetched
+        #[arg(
+            long,
+            conflicts_with_all = ["since", "until", "since_last", "from_snapshot", "dry_run"]
+        )]
+        resume: bool,
     },
 
     /// Analyze GitHub activity and generate initial configuration
@@ -68,26 +201,120 @@ pub enum Commands {
     /// Rebuild state file from existing reports
     RebuildState,
 
-    /// Summarize a specific GitHub issue or PR
+    /// Summarize a specific GitHub issue or PR, or how several relate to each other
     Summarize {
-        /// Issue or PR reference (URL or shorthand like "owner/repo#123")
-        target: String,
+        /// Issue or PR references (URL or shorthand like "owner/repo#123");
+        /// several are summarized together in one file
+        #[arg(required = true)]
+        targets: Vec<String>,
 
-        /// Custom output file path
+        /// Custom output file path, or `-` for stdout
         #[arg(short, long)]
         output: Option<PathBuf>,
 
         /// Skip AI recommendations and focus on facts only
         #[arg(long)]
         no_recommendations: bool,
+
+        /// Regenerate the summary even if the issue is unchanged since the last one
+        #[arg(long)]
+        refresh: bool,
+
+        /// Print the summary to stdout as Claude writes it
+        #[arg(long)]
+        stream: bool,
+
+        /// Open the saved summary in the default viewer (or `$EDITOR`)
+        #[arg(long)]
+        open: bool,
+
+        /// Show Claude the patches of a PR's most-changed files, not just file counts
+        #[arg(long)]
+        with_diff: bool,
+
+        /// Override the primary model for this run (e.g. opus, sonnet, or a full model name)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Override the secondary model for this run (e.g. haiku)
+        #[arg(long, value_name = "MODEL")]
+        secondary_model: Option<String>,
+
+        /// Write the summary in this language (e.g. de), overriding `claude.output_language`
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+    },
+
+    /// Suggest labels, duplicates, and first responses for new and unlabeled issues
+    Triage {
+        /// Repository to triage, as `owner/repo`
+        repo: String,
+
+        /// Also triage labeled issues opened within this period (e.g., 3d, 2w)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Triage at most this many issues, the newest ones
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+
+        /// Write the worksheet here instead of the current directory
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Add the suggested labels to the issues with `gh issue edit`
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Show GitHub Projects boards by status, marking items with recent activity
+    Projects {
+        /// Board to show, e.g. https://github.com/orgs/tokio-rs/projects/5 (repeatable; defaults to `report.projects`)
+        #[arg(long = "project-url", value_name = "URL")]
+        project_urls: Vec<String>,
+
+        /// Time period of activity to mark items by (e.g., 3d, 12h, 2w)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Write the status here instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Show how the last report scored and ranked an issue or PR
+    Explain {
+        /// Issue or PR reference (URL or shorthand like "owner/repo#123")
+        target: String,
+    },
+
+    /// Leave an issue or PR out of every report until it is unmuted
+    Mute {
+        /// Issue or PR reference (URL or shorthand like "owner/repo#123")
+        #[arg(required_unless_present = "list", conflicts_with = "list")]
+        target: Option<String>,
+
+        /// List the muted issues and PRs instead
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Bring a muted issue or PR back into reports
+    Unmute {
+        /// Issue or PR reference (URL or shorthand like "owner/repo#123")
+        target: String,
     },
 
     /// List repositories with recent activity (preview for init)
     ListRepos {
-        /// Time period to look back (e.g., 30d, 4w, 720h)
+        /// Time period to look back (e.g., 30d, 4w, 720h), or where to start (2024-05-01, last-month)
         #[arg(long, default_value = "30d")]
         since: String,
 
+        /// End of the window, a date that is included (2024-05-31) or a keyword (yesterday)
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+
         /// Save the list to a file
         #[arg(short, long)]
         output: Option<PathBuf>,
@@ -95,10 +322,14 @@ pub enum Commands {
 
     /// Show your GitHub activity feed
     Activity {
-        /// Time period to look back (e.g., 7d, 12h, 2w)
+        /// Time period to look back (e.g., 7d, 12h, 2w), or where to start (2024-05-01, yesterday, last-week)
         #[arg(long, default_value = "7d")]
         since: String,
 
+        /// End of the window, a date that is included (2024-05-07) or a keyword (yesterday)
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+
         /// Include only these event types (comma-separated)
         /// Examples: IssueCommentEvent,PullRequestEvent,IssuesEvent
         #[arg(long, value_delimiter = ',')]
@@ -109,10 +340,138 @@ pub enum Commands {
         #[arg(long, value_delimiter = ',')]
         exclude_types: Option<Vec<String>>,
 
+        /// Output format: readable text or JSON for scripting
+        #[arg(long, value_enum, default_value_t = ActivityFormat::Text)]
+        format: ActivityFormat,
+
         /// Save the activity to a file
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Also save the events with the issues and PRs they refer to, for `report --from-snapshot`
+        #[arg(long, value_name = "FILE")]
+        save_snapshot: Option<PathBuf>,
     },
+
+    /// Store GitHub webhook events (JSON lines) for `report --from-events`
+    Ingest {
+        /// Read the events from standard input
+        #[arg(long, required_unless_present = "files")]
+        stdin: bool,
+
+        /// Read the events from these files
+        #[arg(value_name = "FILE", conflicts_with = "stdin")]
+        files: Vec<PathBuf>,
+    },
+
+    /// Keep running and regenerate the report on a schedule
+    Watch {
+        /// How often to check for new activity (e.g., 30m, 6h, 1d)
+        #[arg(long, default_value = "6h")]
+        every: String,
+    },
+
+    /// List Claude models with their aliases and pricing
+    Models,
+
+    /// Show what LLM calls cost per day, week, and month
+    Costs,
+
+    /// Check gh, the LLM providers, the config, and the directories gh-report writes to
+    Doctor,
+
+    /// Show locally recorded statistics
+    #[command(group(ArgGroup::new("view").required(true).args(["tool", "since"])))]
+    Stats {
+        /// Run durations, API calls, and errors of gh-report itself
+        #[arg(long)]
+        tool: bool,
+
+        /// Heatmap and top lists of your activity feed over this period (e.g. 90d, 12w)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Number of repositories and issues listed with --since
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Inspect or clean up the local cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Manage configuration profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+
+    /// Print a shell completion script, which also completes `summarize` targets
+    Completions {
+        /// Shell to generate the script for
+        #[arg(value_enum, required_unless_present = "complete_target")]
+        shell: Option<Shell>,
+
+        /// Print recently seen targets starting with PREFIX (used by the scripts)
+        #[arg(long, value_name = "PREFIX", hide = true, conflicts_with = "shell")]
+        complete_target: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAction {
+    /// Show entry counts and sizes per cache type
+    Stats,
+    /// Remove all cached data
+    Clear,
+    /// Remove only expired entries
+    Prune,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ProfileAction {
+    /// List the existing profiles
+    List,
+    /// Create a profile with its own state file and report directory
+    Create {
+        /// Name of the profile
+        name: String,
+    },
+    /// Show where a profile keeps its files and which repositories it covers
+    Show {
+        /// Name of the profile, the one selected with --profile by default
+        name: Option<String>,
+    },
+}
+
+impl Commands {
+    /// Short name of the command, used when recording usage statistics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Commands::Report { .. } => "report",
+            Commands::Init { .. } => "init",
+            Commands::RebuildState => "rebuild-state",
+            Commands::Summarize { .. } => "summarize",
+            Commands::Triage { .. } => "triage",
+            Commands::Projects { .. } => "projects",
+            Commands::Explain { .. } => "explain",
+            Commands::Mute { .. } => "mute",
+            Commands::Unmute { .. } => "unmute",
+            Commands::ListRepos { .. } => "list-repos",
+            Commands::Activity { .. } => "activity",
+            Commands::Ingest { .. } => "ingest",
+            Commands::Watch { .. } => "watch",
+            Commands::Models => "models",
+            Commands::Costs => "costs",
+            Commands::Doctor => "doctor",
+            Commands::Stats { .. } => "stats",
+            Commands::Cache { .. } => "cache",
+            Commands::Profile { .. } => "profile",
+            Commands::Completions { .. } => "completions",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -170,6 +529,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_report_resume() {
+        let cli = Cli::parse_from(["gh-report", "report", "--resume"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report { resume: true, .. })
+        ));
+        assert!(Cli::try_parse_from(["gh-report", "report", "--resume", "--since", "3d"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_report_max_items() {
+        let cli = Cli::parse_from(["gh-report", "report", "--max-items", "25"]);
+        match cli.command {
+            Some(Commands::Report { max_items, .. }) => assert_eq!(max_items, Some(25)),
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_report_append() {
+        let cli = Cli::parse_from(["gh-report", "report", "--append", "--since-last"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report {
+                append: true,
+                since_last: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_force() {
+        let cli = Cli::parse_from(["gh-report", "report", "--force"]);
+        assert!(cli.force);
+        assert!(!Cli::parse_from(["gh-report", "report"]).force);
+    }
+
     #[test]
     fn test_cli_parsing_report_with_output() {
         let args = vec!["gh-report", "report", "--output", "/tmp/custom-report.md"];
@@ -177,12 +575,533 @@ mod tests {
 
         match cli.command {
             Some(Commands::Report { output, .. }) => {
-                assert_eq!(output, Some(PathBuf::from("/tmp/custom-report.md")));
+                assert_eq!(output, [PathBuf::from("/tmp/custom-report.md")]);
             }
             _ => panic!("Expected Report command"),
         }
     }
 
+    #[test]
+    fn test_cli_parsing_report_format() {
+        let cli = Cli::parse_from(["gh-report", "report", "--format", "html"]);
+        match cli.command {
+            Some(Commands::Report { format, .. }) => {
+                assert_eq!(format, Some(ReportFormat::Html));
+            }
+            _ => panic!("Expected Report command"),
+        }
+
+        let cli = Cli::parse_from(["gh-report", "report"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report { format: None, .. })
+        ));
+        let cli = Cli::parse_from(["gh-report", "report", "--format", "json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report {
+                format: Some(ReportFormat::Json),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["gh-report", "report", "--format", "pdf"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_report_flavor() {
+        let cli = Cli::parse_from(["gh-report", "report"]);
+        match cli.command {
+            Some(Commands::Report { flavor, since, .. }) => {
+                assert_eq!(flavor, ReportFlavor::Daily);
+                assert_eq!(since, "7d");
+            }
+            _ => panic!("Expected Report command"),
+        }
+
+        let cli = Cli::parse_from(["gh-report", "report", "--flavor", "monthly"]);
+        match cli.command {
+            Some(Commands::Report { flavor, since, .. }) => {
+                assert_eq!(flavor, ReportFlavor::Monthly);
+                assert_eq!(since, "30d");
+            }
+            _ => panic!("Expected Report command"),
+        }
+
+        let cli = Cli::parse_from([
+            "gh-report",
+            "report",
+            "--flavor",
+            "weekly",
+            "--since",
+            "last-week",
+        ]);
+        match cli.command {
+            Some(Commands::Report { flavor, since, .. }) => {
+                assert_eq!(flavor, ReportFlavor::Weekly);
+                assert_eq!(since, "last-week");
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_team_report() {
+        let cli = Cli::parse_from(["gh-report", "report", "--user", "alice", "--user", "bob"]);
+        match cli.command {
+            Some(Commands::Report { users, .. }) => assert_eq!(users, ["alice", "bob"]),
+            _ => panic!("Expected Report command"),
+        }
+        assert!(Cli::try_parse_from([
+            "gh-report",
+            "report",
+            "--user",
+            "alice",
+            "--org",
+            "tokio-rs"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_triage() {
+        let cli = Cli::parse_from(["gh-report", "triage", "tokio-rs/tokio", "--apply"]);
+        match cli.command {
+            Some(Commands::Triage {
+                repo,
+                since,
+                limit,
+                output,
+                apply,
+            }) => {
+                assert_eq!(repo, "tokio-rs/tokio");
+                assert_eq!(since, "7d");
+                assert_eq!(limit, 20);
+                assert!(output.is_none());
+                assert!(apply);
+            }
+            _ => panic!("Expected Triage command"),
+        }
+        assert!(Cli::try_parse_from(["gh-report", "triage"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_projects() {
+        let cli = Cli::parse_from([
+            "gh-report",
+            "projects",
+            "--project-url",
+            "https://github.com/orgs/tokio-rs/projects/5",
+            "--project-url",
+            "https://github.com/users/carllerche/projects/2",
+        ]);
+        match cli.command {
+            Some(Commands::Projects {
+                project_urls,
+                since,
+                output,
+            }) => {
+                assert_eq!(project_urls.len(), 2);
+                assert_eq!(since, "7d");
+                assert!(output.is_none());
+            }
+            _ => panic!("Expected Projects command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_explain() {
+        let cli = Cli::parse_from(["gh-report", "explain", "tokio-rs/tokio#123"]);
+        match cli.command {
+            Some(Commands::Explain { target }) => assert_eq!(target, "tokio-rs/tokio#123"),
+            _ => panic!("Expected Explain command"),
+        }
+        assert!(Cli::try_parse_from(["gh-report", "explain"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_mute() {
+        let cli = Cli::parse_from(["gh-report", "mute", "tokio-rs/tokio#123"]);
+        match cli.command {
+            Some(Commands::Mute { target, list }) => {
+                assert_eq!(target.as_deref(), Some("tokio-rs/tokio#123"));
+                assert!(!list);
+            }
+            _ => panic!("Expected Mute command"),
+        }
+        let cli = Cli::parse_from(["gh-report", "mute", "--list"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Mute {
+                target: None,
+                list: true
+            })
+        ));
+        assert!(Cli::try_parse_from(["gh-report", "mute"]).is_err());
+        assert!(Cli::try_parse_from(["gh-report", "mute", "tokio-rs/tokio#1", "--list"]).is_err());
+
+        let cli = Cli::parse_from(["gh-report", "unmute", "tokio-rs/tokio#123"]);
+        match cli.command {
+            Some(Commands::Unmute { target }) => assert_eq!(target, "tokio-rs/tokio#123"),
+            _ => panic!("Expected Unmute command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_report_repos() {
+        let cli = Cli::parse_from([
+            "gh-report",
+            "report",
+            "--repos",
+            "tokio-rs/tokio,carllerche/*",
+        ]);
+        match cli.command {
+            Some(Commands::Report { repos, .. }) => {
+                assert_eq!(repos, ["tokio-rs/tokio", "carllerche/*"]);
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_snapshots() {
+        let cli = Cli::parse_from(["gh-report", "report", "--from-snapshot", "snap.json"]);
+        match cli.command {
+            Some(Commands::Report { from_snapshot, .. }) => {
+                assert_eq!(from_snapshot, Some(PathBuf::from("snap.json")));
+            }
+            _ => panic!("Expected Report command"),
+        }
+        assert!(Cli::try_parse_from([
+            "gh-report",
+            "report",
+            "--from-snapshot",
+            "snap.json",
+            "--since",
+            "3d"
+        ])
+        .is_err());
+
+        let cli = Cli::parse_from(["gh-report", "report", "--from-events"]);
+        match cli.command {
+            Some(Commands::Report { from_events, .. }) => assert!(from_events),
+            _ => panic!("Expected Report command"),
+        }
+        assert!(Cli::try_parse_from([
+            "gh-report",
+            "report",
+            "--from-events",
+            "--from-snapshot",
+            "snap.json"
+        ])
+        .is_err());
+
+        let cli = Cli::parse_from(["gh-report", "ingest", "--stdin"]);
+        match cli.command {
+            Some(Commands::Ingest { stdin, files }) => {
+                assert!(stdin);
+                assert!(files.is_empty());
+            }
+            _ => panic!("Expected Ingest command"),
+        }
+        assert!(Cli::try_parse_from(["gh-report", "ingest"]).is_err());
+
+        let cli = Cli::parse_from(["gh-report", "report", "--offline", "--since", "3d"]);
+        match cli.command {
+            Some(Commands::Report { offline, .. }) => assert!(offline),
+            _ => panic!("Expected Report command"),
+        }
+        assert!(Cli::try_parse_from(["gh-report", "report", "--offline", "--no-cache"]).is_err());
+
+        let cli = Cli::parse_from(["gh-report", "activity", "--save-snapshot", "snap.json"]);
+        match cli.command {
+            Some(Commands::Activity { save_snapshot, .. }) => {
+                assert_eq!(save_snapshot, Some(PathBuf::from("snap.json")));
+            }
+            _ => panic!("Expected Activity command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_report_org() {
+        let cli = Cli::parse_from(["gh-report", "report", "--org", "tokio-rs"]);
+        match cli.command {
+            Some(Commands::Report { org, .. }) => assert_eq!(org.as_deref(), Some("tokio-rs")),
+            _ => panic!("Expected Report command"),
+        }
+        assert!(Cli::try_parse_from([
+            "gh-report",
+            "report",
+            "--org",
+            "tokio-rs",
+            "--from-snapshot",
+            "snap.json"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_report_email() {
+        let cli = Cli::parse_from([
+            "gh-report",
+            "report",
+            "--email",
+            "me@example.com",
+            "--email",
+            "team@example.com",
+        ]);
+        match cli.command {
+            Some(Commands::Report { email, .. }) => {
+                assert_eq!(email, ["me@example.com", "team@example.com"])
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_report_with_git() {
+        let args = vec!["gh-report", "report", "--with-git", "~/src/tokio"];
+        let cli = Cli::parse_from(args);
+
+        match cli.command {
+            Some(Commands::Report { with_git, .. }) => {
+                assert_eq!(with_git, Some(PathBuf::from("~/src/tokio")));
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_report_ci_logs() {
+        let cli = Cli::parse_from(["gh-report", "report", "--ci-logs"]);
+
+        match cli.command {
+            Some(Commands::Report { ci_logs, .. }) => assert!(ci_logs),
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_model_overrides() {
+        let cli = Cli::parse_from(["gh-report", "report", "--model", "opus"]);
+        match cli.command {
+            Some(Commands::Report {
+                model,
+                secondary_model,
+                ..
+            }) => {
+                assert_eq!(model.as_deref(), Some("opus"));
+                assert_eq!(secondary_model, None);
+            }
+            _ => panic!("Expected Report command"),
+        }
+
+        let cli = Cli::parse_from([
+            "gh-report",
+            "summarize",
+            "tokio-rs/tokio#123",
+            "--secondary-model",
+            "haiku",
+        ]);
+        match cli.command {
+            Some(Commands::Summarize {
+                model,
+                secondary_model,
+                ..
+            }) => {
+                assert_eq!(model, None);
+                assert_eq!(secondary_model.as_deref(), Some("haiku"));
+            }
+            _ => panic!("Expected Summarize command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_language() {
+        let cli = Cli::parse_from(["gh-report", "report", "--language", "de"]);
+        match cli.command {
+            Some(Commands::Report { language, .. }) => assert_eq!(language.as_deref(), Some("de")),
+            _ => panic!("Expected Report command"),
+        }
+        let cli = Cli::parse_from([
+            "gh-report",
+            "summarize",
+            "tokio-rs/tokio#1",
+            "--language",
+            "fr",
+        ]);
+        match cli.command {
+            Some(Commands::Summarize { language, .. }) => {
+                assert_eq!(language.as_deref(), Some("fr"))
+            }
+            _ => panic!("Expected Summarize command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_watch() {
+        let cli = Cli::parse_from(["gh-report", "watch", "--every", "30m"]);
+        match cli.command {
+            Some(Commands::Watch { every }) => assert_eq!(every, "30m"),
+            _ => panic!("Expected Watch command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_models() {
+        let cli = Cli::parse_from(["gh-report", "models"]);
+        assert!(matches!(cli.command, Some(Commands::Models)));
+    }
+
+    #[test]
+    fn test_cli_parsing_doctor() {
+        let cli = Cli::parse_from(["gh-report", "doctor"]);
+        assert!(matches!(cli.command, Some(Commands::Doctor)));
+        assert_eq!(cli.command.unwrap().name(), "doctor");
+    }
+
+    #[test]
+    fn test_cli_parsing_costs() {
+        let cli = Cli::parse_from(["gh-report", "costs"]);
+        assert!(matches!(cli.command, Some(Commands::Costs)));
+    }
+
+    #[test]
+    fn test_cli_parsing_since_last() {
+        let cli = Cli::parse_from(["gh-report", "report", "--since-last"]);
+        match cli.command {
+            Some(Commands::Report { since_last, .. }) => assert!(since_last),
+            _ => panic!("Expected Report command"),
+        }
+
+        let result = Cli::try_parse_from(["gh-report", "report", "--since-last", "--since", "3d"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_until() {
+        let cli = Cli::parse_from([
+            "gh-report",
+            "report",
+            "--since",
+            "2024-05-01",
+            "--until",
+            "2024-05-07",
+        ]);
+        match cli.command {
+            Some(Commands::Report { since, until, .. }) => {
+                assert_eq!(since, "2024-05-01");
+                assert_eq!(until.as_deref(), Some("2024-05-07"));
+            }
+            _ => panic!("Expected Report command"),
+        }
+
+        let cli = Cli::parse_from(["gh-report", "activity", "--since", "last-week"]);
+        match cli.command {
+            Some(Commands::Activity { since, until, .. }) => {
+                assert_eq!(since, "last-week");
+                assert!(until.is_none());
+            }
+            _ => panic!("Expected Activity command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "gh-report",
+            "report",
+            "--since-last",
+            "--until",
+            "yesterday",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_stats() {
+        let cli = Cli::parse_from(["gh-report", "stats", "--tool"]);
+        match cli.command {
+            Some(Commands::Stats { tool, .. }) => assert!(tool),
+            _ => panic!("Expected Stats command"),
+        }
+
+        let cli = Cli::parse_from(["gh-report", "stats", "--since", "90d", "--top", "5"]);
+        match cli.command {
+            Some(Commands::Stats { tool, since, top }) => {
+                assert!(!tool);
+                assert_eq!(since.as_deref(), Some("90d"));
+                assert_eq!(top, 5);
+            }
+            _ => panic!("Expected Stats command"),
+        }
+        assert!(Cli::try_parse_from(["gh-report", "stats", "--tool", "--since", "90d"]).is_err());
+
+        // A view has to be selected
+        assert!(Cli::try_parse_from(["gh-report", "stats"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_cache() {
+        let cli = Cli::parse_from(["gh-report", "cache", "prune"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Cache {
+                action: CacheAction::Prune
+            })
+        ));
+
+        // An action has to be given
+        assert!(Cli::try_parse_from(["gh-report", "cache"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_profile() {
+        let cli = Cli::parse_from(["gh-report", "--profile", "work", "report"]);
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+
+        let cli = Cli::parse_from(["gh-report", "profile", "create", "oss"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Profile {
+                action: ProfileAction::Create { ref name }
+            }) if name == "oss"
+        ));
+        let cli = Cli::parse_from(["gh-report", "profile", "show"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Profile {
+                action: ProfileAction::Show { name: None }
+            })
+        ));
+
+        assert!(
+            Cli::try_parse_from(["gh-report", "--profile", "work", "--config", "c.toml"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_completions() {
+        let cli = Cli::parse_from(["gh-report", "completions", "zsh"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Completions {
+                shell: Some(Shell::Zsh),
+                complete_target: None,
+            })
+        ));
+
+        let cli = Cli::parse_from(["gh-report", "completions", "--complete-target", ""]);
+        match cli.command {
+            Some(Commands::Completions {
+                shell,
+                complete_target,
+            }) => {
+                assert_eq!(shell, None);
+                assert_eq!(complete_target.as_deref(), Some(""));
+            }
+            _ => panic!("Expected Completions command"),
+        }
+
+        assert!(Cli::try_parse_from(["gh-report", "completions"]).is_err());
+    }
+
     #[test]
     fn test_cli_parsing_config_path() {
         let args = vec!["gh-report", "--config", "/path/to/config.toml"];
@@ -209,11 +1128,12 @@ mod tests {
 
         match cli.command {
             Some(Commands::Summarize {
-                target,
+                targets,
                 output,
                 no_recommendations,
+                ..
             }) => {
-                assert_eq!(target, "tokio-rs/tokio#123");
+                assert_eq!(targets, ["tokio-rs/tokio#123"]);
                 assert!(output.is_none());
                 assert!(!no_recommendations);
             }
@@ -221,6 +1141,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_summarize_several() {
+        let cli = Cli::parse_from([
+            "gh-report",
+            "summarize",
+            "tokio-rs/tokio#12",
+            "tokio-rs/tokio#14",
+            "--no-recommendations",
+        ]);
+        match cli.command {
+            Some(Commands::Summarize { targets, .. }) => {
+                assert_eq!(targets, ["tokio-rs/tokio#12", "tokio-rs/tokio#14"]);
+            }
+            _ => panic!("Expected Summarize command"),
+        }
+        assert!(Cli::try_parse_from(["gh-report", "summarize"]).is_err());
+    }
+
     #[test]
     fn test_cli_parsing_summarize_with_options() {
         let args = vec![
@@ -230,18 +1168,31 @@ mod tests {
             "--output",
             "/tmp/summary.md",
             "--no-recommendations",
+            "--refresh",
+            "--stream",
+            "--open",
+            "--with-diff",
         ];
         let cli = Cli::parse_from(args);
 
         match cli.command {
             Some(Commands::Summarize {
-                target,
+                targets,
                 output,
                 no_recommendations,
+                refresh,
+                stream,
+                open,
+                with_diff,
+                ..
             }) => {
-                assert_eq!(target, "https://github.com/rust-lang/rust/issues/123");
+                assert_eq!(targets, ["https://github.com/rust-lang/rust/issues/123"]);
                 assert_eq!(output, Some(PathBuf::from("/tmp/summary.md")));
                 assert!(no_recommendations);
+                assert!(refresh);
+                assert!(stream);
+                assert!(open);
+                assert!(with_diff);
             }
             _ => panic!("Expected Summarize command"),
         }
@@ -253,8 +1204,13 @@ mod tests {
         let cli = Cli::parse_from(args);
 
         match cli.command {
-            Some(Commands::ListRepos { since, output }) => {
+            Some(Commands::ListRepos {
+                since,
+                until,
+                output,
+            }) => {
                 assert_eq!(since, "30d"); // default value
+                assert!(until.is_none());
                 assert!(output.is_none());
             }
             _ => panic!("Expected ListRepos command"),
@@ -267,7 +1223,7 @@ mod tests {
         let cli = Cli::parse_from(args);
 
         match cli.command {
-            Some(Commands::ListRepos { since, output }) => {
+            Some(Commands::ListRepos { since, output, .. }) => {
                 assert_eq!(since, "14d");
                 assert!(output.is_none());
             }
@@ -286,6 +1242,7 @@ mod tests {
                 include_types,
                 exclude_types,
                 output,
+                ..
             }) => {
                 assert_eq!(since, "7d"); // default value
                 assert!(include_types.is_none());
@@ -296,6 +1253,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_activity_format() {
+        let cli = Cli::parse_from(["gh-report", "activity"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Activity {
+                format: ActivityFormat::Text,
+                ..
+            })
+        ));
+
+        let cli = Cli::parse_from(["gh-report", "activity", "--format", "json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Activity {
+                format: ActivityFormat::Json,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_cli_parsing_activity_with_since() {
         let args = vec!["gh-report", "activity", "--since", "14d"];
@@ -307,6 +1285,7 @@ mod tests {
                 include_types,
                 exclude_types,
                 output,
+                ..
             }) => {
                 assert_eq!(since, "14d");
                 assert!(include_types.is_none());
@@ -0,0 +1,184 @@
+//! Shell completion scripts, with completion of `summarize` targets
+//!
+//! The static part of each script is generated by clap. For bash, zsh, and
+//! fish a hook is appended that asks `gh-report completions
+//! --complete-target` for `owner/repo#number` candidates. Those come from
+//! the issues and PRs recorded in the state file, so completing a target
+//! never waits on GitHub.
+
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+use crate::cli::Cli;
+use crate::state::State;
+
+const BIN_NAME: &str = "gh-report";
+
+/// Completes `summarize` targets, falling back to the clap completions
+const BASH_HOOK: &str = r#"
+_gh_report_with_targets() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        if [[ "${COMP_WORDS[i]}" == "summarize" && "$cur" != -* && "$prev" != -* ]]; then
+            local IFS=$'\n'
+            COMPREPLY=($(gh-report completions --complete-target "$cur" 2>/dev/null | cut -f1))
+            if [[ ${#COMPREPLY[@]} -gt 0 ]]; then
+                [[ "${COMPREPLY[0]}" == *'#' ]] && compopt -o nospace
+                return 0
+            fi
+            break
+        fi
+    done
+    _gh__report "$@"
+}
+complete -F _gh_report_with_targets -o bashdefault -o default gh-report
+"#;
+
+const ZSH_HOOK: &str = r#"
+_gh_report_with_targets() {
+    if (( CURRENT > 2 && ${words[(I)summarize]} )) && [[ $PREFIX != -* ]]; then
+        local -a repos items
+        local target
+        for target in ${(f)"$(gh-report completions --complete-target "$PREFIX" 2>/dev/null)"}; do
+            target=${target%%$'\t'*}
+            if [[ $target == *'#' ]]; then
+                repos+=($target)
+            else
+                items+=($target)
+            fi
+        done
+        if (( $#repos + $#items )); then
+            (( $#repos )) && compadd -S '' -- $repos
+            (( $#items )) && compadd -- $items
+            return 0
+        fi
+    fi
+    _gh-report "$@"
+}
+compdef _gh_report_with_targets gh-report
+"#;
+
+/// Fish shows the tab-separated titles as descriptions
+const FISH_HOOK: &str = r#"
+complete -c gh-report -n '__fish_seen_subcommand_from summarize; and not string match -q -- "-*" (commandline -ct)' -f -a '(gh-report completions --complete-target (commandline -ct) 2>/dev/null)'
+"#;
+
+/// Write the completion script for `shell`
+pub fn write_script(shell: Shell, out: &mut dyn Write) -> Result<()> {
+    let mut command = Cli::command();
+    clap_complete::generate(shell, &mut command, BIN_NAME, out);
+
+    let hook = match shell {
+        Shell::Bash => Some(BASH_HOOK),
+        Shell::Zsh => Some(ZSH_HOOK),
+        Shell::Fish => Some(FISH_HOOK),
+        _ => None,
+    };
+    if let Some(hook) = hook {
+        out.write_all(hook.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Candidates for a `summarize` target starting with `prefix`, with a description
+///
+/// Before the `#`, completes `owner/repo#` from recently seen repositories,
+/// then those configured under `[repos]`. After it, completes the numbers
+/// of recently seen issues and PRs in that repository, described by title.
+pub fn target_candidates<'a>(
+    state: &'a State,
+    configured_repos: impl IntoIterator<Item = &'a str>,
+    prefix: &str,
+) -> Vec<(String, String)> {
+    if let Some((repo, number)) = prefix.split_once('#') {
+        return state
+            .recent_items
+            .iter()
+            .filter(|item| item.repo.eq_ignore_ascii_case(repo))
+            .filter(|item| item.number.to_string().starts_with(number))
+            .map(|item| (item.reference(), item.title.clone()))
+            .collect();
+    }
+
+    let prefix = prefix.to_lowercase();
+    let mut repos: Vec<&str> = Vec::new();
+    for repo in state.recent_repos().into_iter().chain(configured_repos) {
+        if repo.to_lowercase().starts_with(&prefix) && !repos.contains(&repo) {
+            repos.push(repo);
+        }
+    }
+    repos
+        .into_iter()
+        .map(|repo| (format!("{}#", repo), String::new()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::RecentItem;
+    use jiff::Timestamp;
+
+    fn state() -> State {
+        let mut state = State::default();
+        state.record_items(
+            [
+                ("tokio-rs/tokio", 6234, "Breaking change proposal"),
+                ("tokio-rs/axum", 12, "Routing"),
+                ("tokio-rs/tokio", 6301, "Runtime panic"),
+            ]
+            .map(|(repo, number, title)| RecentItem {
+                repo: repo.to_string(),
+                number,
+                title: title.to_string(),
+                seen_at: Timestamp::UNIX_EPOCH,
+            }),
+        );
+        state
+    }
+
+    #[test]
+    fn test_repo_candidates() {
+        let candidates = target_candidates(&state(), ["tokio-rs/mio", "rust-lang/rust"], "tok");
+        let values: Vec<&str> = candidates.iter().map(|(v, _)| v.as_str()).collect();
+        assert_eq!(
+            values,
+            ["tokio-rs/tokio#", "tokio-rs/axum#", "tokio-rs/mio#"]
+        );
+
+        assert_eq!(target_candidates(&state(), [], "").len(), 2);
+    }
+
+    #[test]
+    fn test_item_candidates() {
+        let candidates = target_candidates(&state(), [], "tokio-rs/tokio#62");
+        assert_eq!(
+            candidates,
+            [(
+                "tokio-rs/tokio#6234".to_string(),
+                "Breaking change proposal".to_string()
+            )]
+        );
+        assert_eq!(target_candidates(&state(), [], "tokio-rs/tokio#").len(), 2);
+        assert!(target_candidates(&state(), [], "tokio-rs/mio#").is_empty());
+    }
+
+    #[test]
+    fn test_scripts_include_target_hook() {
+        let mut script = Vec::new();
+        write_script(Shell::Bash, &mut script).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("_gh__report()"));
+        assert!(script.contains("complete -F _gh_report_with_targets"));
+
+        let mut script = Vec::new();
+        write_script(Shell::PowerShell, &mut script).unwrap();
+        assert!(!String::from_utf8(script)
+            .unwrap()
+            .contains("_gh_report_with_targets"));
+    }
+}
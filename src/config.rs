@@ -1,15 +1,44 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::cache::default_cache_dir;
+use crate::claude::{
+    context_window, resolve_model_alias, ModelPrice, PricingTable, BUILTIN_MODEL_ALIASES,
+};
+use crate::github::Author;
+use crate::intelligence::parse_goal_list;
+use crate::report::Locale;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Config {
     pub settings: Settings,
     pub claude: ClaudeConfig,
+    /// Which LLM provider prompts are sent to
+    #[serde(default)]
+    pub llm: LlmConfig,
+    /// How gh is called
+    #[serde(default)]
+    pub github: GitHubConfig,
     #[serde(default)]
     pub report: ReportConfig,
     #[serde(default)]
     pub cache: CacheConfig,
+    #[serde(default)]
+    pub intelligence: IntelligenceConfig,
+    /// Where reports are sent after saving
+    #[serde(default)]
+    pub delivery: DeliveryConfig,
+    /// External commands run before and after reports
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// Per-repository settings, keyed by `owner/repo`
+    #[serde(default, rename = "repo", skip_serializing_if = "BTreeMap::is_empty")]
+    pub repos: BTreeMap<String, RepoConfig>,
+    /// Patterns that flag and boost items, keyed by the name of the rule
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub watch_rules: BTreeMap<String, Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -19,14 +48,53 @@ pub struct Settings {
     pub state_file: PathBuf,
     #[serde(default = "default_file_name_format")]
     pub file_name_format: String,
+    /// What a report does to an existing file of the same name
+    #[serde(default)]
+    pub collision_strategy: CollisionStrategy,
     #[serde(default = "default_max_lookback_days")]
     pub max_lookback_days: u32,
     #[serde(default = "default_max_issues")]
     pub max_issues_per_report: usize,
     #[serde(default = "default_max_comments")]
     pub max_comments_per_report: usize,
+    /// Comments of a single issue or PR sent to Claude (0 disables the limit)
+    #[serde(default)]
+    pub max_comments_per_issue: usize,
+    /// Which comments of a longer thread are kept
+    #[serde(default)]
+    pub comment_window: CommentWindow,
+    /// Number of issues and PRs fetched over REST at the same time, for those
+    /// a GraphQL batch couldn't return
+    #[serde(default = "default_fetch_concurrency")]
+    pub fetch_concurrency: usize,
     #[serde(default = "default_inactive_threshold")]
     pub inactive_repo_threshold_days: u32,
+    /// Days without a response before an item counts as waiting on its author (0 disables)
+    #[serde(default = "default_waiting_on_author_days")]
+    pub waiting_on_author_days: u32,
+    /// Days an item can wait on your review or reply before it is listed (0 disables)
+    #[serde(default = "default_waiting_on_you_days")]
+    pub waiting_on_you_days: u32,
+    /// Have Claude draft a nudge reply for items waiting on you
+    #[serde(default)]
+    pub draft_nudges: bool,
+    /// Issues and PRs with any of these labels are left out of reports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_labels: Vec<String>,
+    /// Users and teams (`@org/slug`) whose mentions get their own report section
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_mentions: Vec<String>,
+    /// Record local-only usage statistics (run durations, API calls, errors)
+    #[serde(default)]
+    pub usage_stats: bool,
+    #[serde(default = "default_stats_file")]
+    pub stats_file: PathBuf,
+    /// JSON lines file `gh-report ingest` stores webhook events in
+    #[serde(default = "default_event_store")]
+    pub event_store: PathBuf,
+    /// GitHub Enterprise Server host for gh to talk to instead of github.com
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_host: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -45,12 +113,363 @@ pub struct ClaudeConfig {
     pub cache_ttl_hours: u32,
     #[serde(default = "default_claude_backend")]
     pub backend: ClaudeBackend,
+    /// Model aliases, merged over the built-in ones (`opus = "claude-..."`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// Send content from private repositories to the API without asking
+    #[serde(default)]
+    pub allow_private_repos: bool,
+    /// Ask before including a repository in AI summaries for the first time
+    #[serde(default)]
+    pub confirm_new_repos: bool,
+    /// Per-model prices overriding the built-in table, keyed by model ID or prefix
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pricing: BTreeMap<String, ModelPrice>,
+    /// Monthly LLM spend in dollars above which runs warn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_budget: Option<f32>,
+    /// How the activity summary of a report is put together
+    #[serde(default)]
+    pub summarization_strategy: SummarizationStrategy,
+    /// Per-model context windows in tokens overriding the built-in table,
+    /// keyed by model ID or prefix
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context_windows: BTreeMap<String, u32>,
+    /// Language of summaries, titles, and action items, as a code like `de` or a name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_language: Option<String>,
+}
+
+/// What to do when a report is saved over an existing file, like a second
+/// run on the same day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionStrategy {
+    /// Replace the file
+    #[default]
+    Overwrite,
+    /// Add the new report to the end of a markdown file, under a timestamped heading
+    Append,
+    /// Keep the file and number the new one: `2024-01-11 (2).md`
+    NewFile,
+}
+
+/// Which comments of a thread are kept under `settings.max_comments_per_issue`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentWindow {
+    /// The most recent comments
+    #[default]
+    Latest,
+    /// The opening comments and the most recent ones, half each
+    FirstAndLatest,
+    /// The latest comment and those most likely to matter to a summary:
+    /// long ones, code, decisions and questions, skipping bots
+    AiRelevant,
+}
+
+impl CommentWindow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentWindow::Latest => "latest",
+            CommentWindow::FirstAndLatest => "first_and_latest",
+            CommentWindow::AiRelevant => "ai_relevant",
+        }
+    }
+}
+
+/// How the activity summary of a report is put together
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummarizationStrategy {
+    /// One prompt with the activity of all repositories, for the primary model
+    #[default]
+    Single,
+    /// A summary per repository from the secondary model, in parallel, which
+    /// the primary model combines
+    MapReduce,
+}
+
+impl ClaudeConfig {
+    /// Resolve a model alias, preferring aliases from the config
+    pub fn resolve_model(&self, alias: &str) -> String {
+        let lower = alias.to_lowercase();
+        self.aliases
+            .iter()
+            .find(|(name, _)| name.to_lowercase() == lower)
+            .map(|(_, model)| resolve_model_alias(model))
+            .unwrap_or_else(|| resolve_model_alias(alias))
+    }
+
+    /// Model prices, including overrides from the config
+    pub fn pricing(&self) -> PricingTable<'_> {
+        PricingTable::with_overrides(&self.pricing)
+    }
+
+    /// Context window of a model in tokens, including overrides from the config
+    pub fn context_window(&self, model: &str) -> u32 {
+        context_window(model, &self.context_windows)
+    }
+
+    /// All known aliases with the models they resolve to
+    pub fn model_aliases(&self) -> BTreeMap<String, String> {
+        let mut aliases: BTreeMap<String, String> = BUILTIN_MODEL_ALIASES
+            .iter()
+            .map(|(name, model)| (name.to_string(), model.to_string()))
+            .collect();
+        for name in self.aliases.keys() {
+            aliases.insert(name.to_lowercase(), self.resolve_model(name));
+        }
+        aliases
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LlmConfig {
+    /// Provider to send prompts to; follows `claude.backend` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<LlmProvider>,
+    /// Base URL of an OpenAI-compatible API, e.g. `http://localhost:11434/v1`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Environment variable holding the key for the OpenAI-compatible API
+    #[serde(default = "default_llm_api_key_env")]
+    pub api_key_env: String,
+}
+
+/// LLM provider selected with `[llm] provider`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LlmProvider {
+    /// Claude CLI when installed, the Anthropic API otherwise
+    Auto,
+    /// The Anthropic API, with `ANTHROPIC_API_KEY`
+    Anthropic,
+    /// The `claude` command-line tool
+    ClaudeCli,
+    /// An OpenAI-compatible chat completions endpoint
+    Openai,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ReportConfig {
-    #[serde(default = "default_template")]
-    pub template: String,
+    /// minijinja template laying out markdown reports (a built-in layout when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Write a `deadlines.ics` calendar file next to each saved report
+    #[serde(default)]
+    pub deadlines_ics: bool,
+    /// Format of saved reports
+    #[serde(default)]
+    pub format: ReportFormat,
+    /// Locale for dates and numbers in reports, e.g. `de-DE` (ISO dates when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// How the activity section is organized
+    #[serde(default)]
+    pub group_by: GroupBy,
+    /// Add review requests and mentions from GitHub notifications that are
+    /// missing from the activity feed
+    #[serde(default = "default_notifications")]
+    pub notifications: bool,
+    /// Mark open PRs with the state of their checks, calling out failing ones
+    #[serde(default = "default_ci_status")]
+    pub ci_status: bool,
+    /// List open milestones of the reported repositories due within this many
+    /// days under "Upcoming deadlines" (0 turns the section off)
+    #[serde(default = "default_upcoming_days")]
+    pub upcoming_days: u32,
+    /// List the open PRs of repositories you can push to by requested
+    /// reviewer, with how long each has waited
+    #[serde(default = "default_reviewer_workload")]
+    pub reviewer_workload: bool,
+    /// Priority score below which items are collapsed into an appendix (0 lists everything)
+    ///
+    /// Collapsed items are also left out of AI prompts. `report --full` lists everything.
+    #[serde(default)]
+    pub min_priority_score: u32,
+    /// Report on every repository of this organization instead of your own activity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    /// Report on what these GitHub users did, with a breakdown per person,
+    /// instead of your own activity
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<String>,
+    /// More places each report is written to, besides `settings.report_dir`
+    ///
+    /// Paths without an extension are directories and get the configured file name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<PathBuf>,
+    /// Goals Claude checks the report's activity against, e.g. "Merge the io_uring PR"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<String>,
+    /// Markdown file with more goals, one list item each
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goals_file: Option<PathBuf>,
+    /// Only report on repositories matching one of these patterns, e.g. `carllerche/*`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_repos: Vec<String>,
+    /// Leave out repositories matching one of these patterns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_repos: Vec<String>,
+    /// Leave out issues and PRs with any of these labels, in every repository
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_labels: Vec<String>,
+    /// Only report on issues and PRs with at least one of these labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require_labels: Vec<String>,
+    /// Leave out issues and PRs opened by authors matching one of these patterns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_authors: Vec<String>,
+    /// Leave out issues and PRs opened by bot accounts
+    #[serde(default = "default_ignore_bots")]
+    pub ignore_bots: bool,
+    /// GitHub Projects boards whose status gets a report section, by URL
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<String>,
+    /// Single select field of the boards that items are grouped by
+    #[serde(default = "default_project_status_field")]
+    pub project_status_field: String,
+    /// Built-in sections that can be turned off
+    #[serde(default)]
+    pub sections: ReportSections,
+}
+
+impl ReportConfig {
+    /// The configured locale, falling back to the default formats
+    pub fn locale(&self) -> Locale {
+        Locale::from_config(self.locale.as_deref())
+    }
+
+    /// The goals from `goals` followed by those in `goals_file`
+    pub fn goals(&self) -> Result<Vec<String>> {
+        let mut goals = self.goals.clone();
+        if let Some(path) = &self.goals_file {
+            let markdown = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read goals from {:?}", path))?;
+            goals.extend(parse_goal_list(&markdown));
+        }
+        Ok(goals)
+    }
+
+    /// Whether `repo` passes `include_repos` and `exclude_repos`
+    ///
+    /// An empty `include_repos` includes every repository.
+    pub fn includes_repo(&self, repo: &str) -> bool {
+        let matches = |pattern: &String| glob_matches(pattern, repo);
+        (self.include_repos.is_empty() || self.include_repos.iter().any(matches))
+            && !self.exclude_repos.iter().any(matches)
+    }
+
+    /// Whether items by `author` are left out, by `ignore_authors` or `ignore_bots`
+    pub fn ignores_author(&self, author: &Author) -> bool {
+        (self.ignore_bots && author.is_bot())
+            || self
+                .ignore_authors
+                .iter()
+                .any(|pattern| glob_matches(pattern, &author.login))
+    }
+}
+
+/// Case-insensitive match of `text` against a pattern where `*` matches any run of characters
+pub(crate) fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let mut parts = pattern.split('*');
+    // Without a `*`, the only part has to be the whole text
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Built-in report sections, each on unless turned off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReportSections {
+    /// Items that need a response from you
+    pub action_items: bool,
+    /// Claude's highlights of the period; when off, no summary is requested
+    pub ai_summary: bool,
+    /// The highest-scoring issues and PRs
+    pub prioritized: bool,
+    /// Every item, by repository (or organization or milestone, per `group_by`)
+    pub by_repository: bool,
+    /// Counts of new, updated, merged, and closed items
+    pub statistics: bool,
+}
+
+impl Default for ReportSections {
+    fn default() -> Self {
+        ReportSections {
+            action_items: true,
+            ai_summary: true,
+            prioritized: true,
+            by_repository: true,
+            statistics: true,
+        }
+    }
+}
+
+/// Grouping of repository sections in reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// One section per repository
+    #[default]
+    Repo,
+    /// Repository sections nested under their organization, with subtotals
+    Org,
+    /// One section per milestone over all repositories, soonest due first
+    Milestone,
+}
+
+/// Output format of saved reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    /// A standalone HTML page, ready to publish as is
+    Html,
+    /// The analysis behind a report, for dashboards and scripts
+    Json,
+}
+
+impl ReportFormat {
+    /// File extension of reports in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GitHubConfig {
+    /// Times a gh call is tried before giving up on rate limits and server
+    /// errors (1 disables retries)
+    #[serde(default = "default_github_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each one after it
+    #[serde(default = "default_github_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    /// API requests left below which optional fetches, like PR diffs, are skipped
+    #[serde(default = "default_min_remaining_requests")]
+    pub min_remaining_requests: u32,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -61,8 +480,168 @@ pub struct CacheConfig {
     pub ttl_hours: u32,
     #[serde(default = "default_compression_enabled")]
     pub compression_enabled: bool,
+    /// Where cached data is stored, instead of a directory under the XDG cache directory
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_dir: Option<PathBuf>,
+    /// Minutes a fetched activity feed is reused for (0 always fetches it)
+    #[serde(default = "default_activity_ttl_minutes")]
+    pub activity_ttl_minutes: u32,
+    /// Size the cache is kept under after each report, evicting the least
+    /// recently used entries first (0 lets it grow until entries expire)
+    #[serde(default = "default_cache_max_size_mb")]
+    pub max_size_mb: u64,
+    /// Subdirectory of the default cache directory for this config file,
+    /// so profiles and other configs don't share a cache
+    #[serde(skip)]
+    pub namespace: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IntelligenceConfig {
+    /// Priority score below which items are left out of AI prompts (0 sends everything)
+    ///
+    /// Left-out items are still listed in the report.
+    #[serde(default)]
+    pub min_score_for_ai: u32,
+    /// How many of the top prioritized open PRs have their diff described to
+    /// Claude (0 fetches no diffs)
+    #[serde(default = "default_diff_prefetch")]
+    pub diff_prefetch: usize,
+    /// Diffs fetched at the same time
+    #[serde(default = "default_diff_concurrency")]
+    pub diff_concurrency: usize,
+    /// Patch text kept per PR, in bytes; files past it keep only their line counts
+    #[serde(default = "default_max_diff_bytes")]
+    pub max_diff_bytes: usize,
+    /// Tokens of patch hunks from the most-changed files shown with each
+    /// prefetched diff (0 shows file names and line counts only)
+    ///
+    /// Also the budget of `summarize --with-diff`, which uses 4000 when this is 0.
+    #[serde(default)]
+    pub diff_patch_tokens: u32,
+    /// How comment threads are checked for rising frustration or conflict
+    #[serde(default)]
+    pub escalation: EscalationDetection,
+}
+
+/// How threads needing de-escalation are found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationDetection {
+    /// Threads aren't checked
+    #[default]
+    Off,
+    /// Heated wording and shouting in the comments
+    Keywords,
+    /// The secondary model reads the busiest threads, falling back to
+    /// keywords for threads kept out of AI prompts
+    Claude,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DeliveryConfig {
+    /// SMTP settings for `report --email`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailConfig>,
+    /// Where to post each generated report in Slack
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack: Option<SlackConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HooksConfig {
+    /// Commands run before a report is generated, whose output is added to
+    /// the report and to Claude's context
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_report: Vec<String>,
+    /// Commands run for each saved report, e.g. `./publish.sh {report_path}`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_report: Vec<String>,
+    /// Seconds a hook may run before it is stopped and the run fails
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmailConfig {
+    /// Sender address, e.g. `gh-report <reports@example.com>`
+    pub from: String,
+    pub smtp_host: String,
+    /// Defaults to the usual port for `security`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp_port: Option<u16>,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Login for the SMTP server; the password is read from `password_env`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default = "default_smtp_password_env")]
+    pub password_env: String,
+}
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS (port 587)
+    #[default]
+    Starttls,
+    /// TLS from the start (port 465)
+    Tls,
+    /// No encryption, for local relays only (port 25)
+    None,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SlackConfig {
+    /// Incoming webhook to post to when `channel` is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Channel to post to with a bot token, which lets long reports
+    /// continue in a thread
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Environment variable with the bot token, used with `channel`
+    #[serde(default = "default_slack_token_env")]
+    pub token_env: String,
+    #[serde(default)]
+    pub content: SlackContent,
+}
+
+/// Which part of the report is posted to Slack
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackContent {
+    /// Highlights and the activity counts
+    #[default]
+    Summary,
+    /// Only the action items
+    ActionItems,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RepoConfig {
+    /// Extra prompt context injected only for items from this repository
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Free-form notes about your role in the repository, added after `context`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Replaces `settings.exclude_labels` for this repository
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_labels: Option<Vec<String>>,
+}
+
+impl RepoConfig {
+    /// The configured `context` and `notes`, one per line, ignoring blank values
+    pub fn prompt_context(&self) -> Option<String> {
+        let lines: Vec<&str> = [&self.context, &self.notes]
+            .into_iter()
+            .filter_map(|text| text.as_deref().map(str::trim))
+            .filter(|text| !text.is_empty())
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -99,27 +678,182 @@ impl Config {
         // Expand home directory in paths
         config.settings.report_dir = expand_tilde(&config.settings.report_dir)?;
         config.settings.state_file = expand_tilde(&config.settings.state_file)?;
+        config.settings.stats_file = expand_tilde(&config.settings.stats_file)?;
+        config.settings.event_store = expand_tilde(&config.settings.event_store)?;
+        if let Some(cache_dir) = &config.cache.cache_dir {
+            config.cache.cache_dir = Some(expand_tilde(cache_dir)?);
+        }
+        config.cache.namespace = cache_namespace(&config_path);
+        if let Some(goals_file) = &config.report.goals_file {
+            config.report.goals_file = Some(expand_tilde(goals_file)?);
+        }
+        // Accept the host as a URL too, like `https://github.example.com/`
+        config.settings.github_host = config.settings.github_host.map(|host| {
+            let host = host.trim();
+            let host = host
+                .strip_prefix("https://")
+                .or_else(|| host.strip_prefix("http://"))
+                .unwrap_or(host);
+            host.trim_end_matches('/').to_string()
+        });
 
         Ok(config)
     }
 
+    /// Directory of the cache
+    ///
+    /// `cache.cache_dir` when set, otherwise `gh-report` under the XDG cache
+    /// directory, in a subdirectory for profiles and other config files.
+    pub fn cache_dir(&self) -> PathBuf {
+        match (&self.cache.cache_dir, &self.cache.namespace) {
+            (Some(dir), _) => dir.clone(),
+            (None, Some(namespace)) => default_cache_dir().join(namespace),
+            (None, None) => default_cache_dir(),
+        }
+    }
+
+    /// Override the configured models for a single invocation
+    ///
+    /// Aliases like `opus` are resolved to full model names.
+    pub fn override_models(&mut self, primary: Option<&str>, secondary: Option<&str>) {
+        if let Some(model) = primary {
+            self.claude.primary_model = self.claude.resolve_model(model);
+        }
+        if let Some(model) = secondary {
+            self.claude.secondary_model = self.claude.resolve_model(model);
+        }
+    }
+
+    /// The LLM provider to use, derived from `claude.backend` unless `[llm]` sets one
+    pub fn llm_provider(&self) -> LlmProvider {
+        self.llm.provider.unwrap_or(match self.claude.backend {
+            ClaudeBackend::Api => LlmProvider::Anthropic,
+            ClaudeBackend::Cli => LlmProvider::ClaudeCli,
+            ClaudeBackend::Auto => LlmProvider::Auto,
+        })
+    }
+
+    /// Host of the GitHub instance the reports are about
+    pub fn github_host(&self) -> &str {
+        self.settings.github_host.as_deref().unwrap_or("github.com")
+    }
+
+    /// Get the custom prompt context configured for a repository
+    ///
+    /// Combines `context` and `notes` of `[repo."owner/repo"]`, skipping
+    /// blank values.
+    pub fn repo_context(&self, repo: &str) -> Option<String> {
+        self.repos.get(repo).and_then(RepoConfig::prompt_context)
+    }
+
+    /// Prompt context of every repository that has some, see [`Config::repo_context`]
+    pub fn repo_contexts(&self) -> BTreeMap<String, String> {
+        self.repos
+            .iter()
+            .filter_map(|(repo, config)| Some((repo.clone(), config.prompt_context()?)))
+            .collect()
+    }
+
+    /// Labels whose issues and PRs are left out of reports for a repository
+    ///
+    /// `report.exclude_labels` applies everywhere, on top of the repository's
+    /// own list or `settings.exclude_labels`.
+    pub fn exclude_labels(&self, repo: &str) -> Vec<&str> {
+        let labels = self
+            .repos
+            .get(repo)
+            .and_then(|r| r.exclude_labels.as_deref())
+            .unwrap_or(&self.settings.exclude_labels);
+        self.report
+            .exclude_labels
+            .iter()
+            .chain(labels)
+            .map(String::as_str)
+            .collect()
+    }
+
     /// Get the default configuration file path
     pub fn default_config_path() -> Result<PathBuf> {
         let home = dirs::home_dir().context("Could not determine home directory")?;
         Ok(home.join(".config").join("gh-report").join("config.toml"))
     }
 
+    /// Directory of the configuration files of named profiles
+    pub fn profiles_dir() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not determine home directory")?;
+        Ok(home.join(".config").join("gh-report").join("profiles"))
+    }
+
+    /// Configuration file of the profile `name`
+    pub fn profile_path(name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(anyhow!(
+                "Invalid profile name {:?}: use letters, digits, '-', and '_'",
+                name
+            ));
+        }
+        Ok(Self::profiles_dir()?.join(format!("{}.toml", name)))
+    }
+
+    /// Names of the profiles in `dir`, sorted
+    pub fn profiles_in(dir: &Path) -> Result<Vec<String>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// The configuration a new profile starts with
+    ///
+    /// Reports and state go to a directory of their own, so the profile's
+    /// `--since-last` windows and report history don't mix with other profiles.
+    pub fn for_profile(name: &str) -> Self {
+        let mut config = Config::default();
+        let dir = PathBuf::from("~/Github Reports").join(name);
+        config.settings.state_file = dir.join(".gh-report-state.json");
+        config.settings.report_dir = dir;
+        config
+    }
+
     /// Create a default configuration
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Config {
             settings: Settings {
                 report_dir: PathBuf::from("~/Github Reports"),
                 state_file: default_state_file(),
                 file_name_format: default_file_name_format(),
+                collision_strategy: CollisionStrategy::default(),
                 max_lookback_days: default_max_lookback_days(),
                 max_issues_per_report: default_max_issues(),
                 max_comments_per_report: default_max_comments(),
+                max_comments_per_issue: 0,
+                comment_window: CommentWindow::default(),
+                fetch_concurrency: default_fetch_concurrency(),
                 inactive_repo_threshold_days: default_inactive_threshold(),
+                waiting_on_author_days: default_waiting_on_author_days(),
+                waiting_on_you_days: default_waiting_on_you_days(),
+                draft_nudges: false,
+                exclude_labels: Vec::new(),
+                include_mentions: Vec::new(),
+                usage_stats: false,
+                stats_file: default_stats_file(),
+                event_store: default_event_store(),
+                github_host: None,
             },
             claude: ClaudeConfig {
                 api_key: None,
@@ -129,36 +863,107 @@ impl Config {
                 cache_responses: default_cache_responses(),
                 cache_ttl_hours: default_cache_ttl(),
                 backend: default_claude_backend(),
+                aliases: BTreeMap::new(),
+                allow_private_repos: false,
+                confirm_new_repos: false,
+                pricing: BTreeMap::new(),
+                monthly_budget: None,
+                summarization_strategy: SummarizationStrategy::default(),
+                context_windows: BTreeMap::new(),
+                output_language: None,
             },
+            llm: LlmConfig::default(),
+            github: GitHubConfig::default(),
             report: ReportConfig {
-                template: default_template(),
+                template: None,
+                deadlines_ics: false,
+                format: ReportFormat::default(),
+                locale: None,
+                group_by: GroupBy::default(),
+                notifications: default_notifications(),
+                ci_status: default_ci_status(),
+                upcoming_days: default_upcoming_days(),
+                reviewer_workload: default_reviewer_workload(),
+                min_priority_score: 0,
+                org: None,
+                users: Vec::new(),
+                outputs: Vec::new(),
+                goals: Vec::new(),
+                goals_file: None,
+                include_repos: Vec::new(),
+                exclude_repos: Vec::new(),
+                exclude_labels: Vec::new(),
+                require_labels: Vec::new(),
+                ignore_authors: Vec::new(),
+                ignore_bots: default_ignore_bots(),
+                projects: Vec::new(),
+                project_status_field: default_project_status_field(),
+                sections: ReportSections::default(),
             },
             cache: CacheConfig {
                 enabled: default_cache_enabled(),
                 ttl_hours: default_cache_ttl(),
                 compression_enabled: default_compression_enabled(),
                 cache_dir: None,
+                activity_ttl_minutes: default_activity_ttl_minutes(),
+                max_size_mb: default_cache_max_size_mb(),
+                namespace: None,
             },
+            intelligence: IntelligenceConfig::default(),
+            delivery: DeliveryConfig::default(),
+            hooks: HooksConfig::default(),
+            repos: BTreeMap::new(),
+            watch_rules: BTreeMap::new(),
         }
     }
 }
 
 /// Expand tilde in paths to home directory
-fn expand_tilde(path: &Path) -> Result<PathBuf> {
+pub fn expand_tilde(path: &Path) -> Result<PathBuf> {
     if let Some(s) = path.to_str() {
-        if s.starts_with("~/") {
+        if let Some(rest) = s.strip_prefix("~/") {
             let home = dirs::home_dir().context("Could not determine home directory")?;
-            return Ok(home.join(&s[2..]));
+            return Ok(home.join(rest));
         }
     }
     Ok(path.to_path_buf())
 }
 
 // Default value functions
+/// Cache subdirectory of a config file other than the default one
+///
+/// Profiles get `profiles/<name>`; any other file gets `configs/<name>-<hash>`,
+/// with a hash of its absolute path so files of the same name stay apart.
+fn cache_namespace(config_path: &Path) -> Option<PathBuf> {
+    if Config::default_config_path().ok().as_deref() == Some(config_path) {
+        return None;
+    }
+    let name = config_path.file_stem()?.to_string_lossy();
+    if Config::profiles_dir().ok().as_deref() == config_path.parent() {
+        return Some(Path::new("profiles").join(&*name));
+    }
+
+    use sha2::{Digest, Sha256};
+    let absolute = std::path::absolute(config_path).unwrap_or_else(|_| config_path.to_path_buf());
+    let hash = format!(
+        "{:x}",
+        Sha256::digest(absolute.to_string_lossy().as_bytes())
+    );
+    Some(Path::new("configs").join(format!("{}-{}", name, &hash[..8])))
+}
+
 fn default_state_file() -> PathBuf {
     PathBuf::from("~/Github Reports/.gh-report-state.json")
 }
 
+fn default_stats_file() -> PathBuf {
+    PathBuf::from("~/Github Reports/.gh-report-stats.jsonl")
+}
+
+fn default_event_store() -> PathBuf {
+    PathBuf::from("~/Github Reports/.gh-report-events.jsonl")
+}
+
 fn default_file_name_format() -> String {
     "{yyyy-mm-dd} - Github - {short-title}".to_string()
 }
@@ -175,10 +980,22 @@ fn default_max_comments() -> usize {
     500
 }
 
+fn default_fetch_concurrency() -> usize {
+    8
+}
+
 fn default_inactive_threshold() -> u32 {
     30
 }
 
+fn default_waiting_on_author_days() -> u32 {
+    7
+}
+
+fn default_waiting_on_you_days() -> u32 {
+    3
+}
+
 fn default_primary_model() -> String {
     "sonnet".to_string()
 }
@@ -195,30 +1012,64 @@ fn default_cache_ttl() -> u32 {
     24
 }
 
-fn default_template() -> String {
-    r#"# GitHub Activity Report - {date}
+fn default_github_max_attempts() -> u32 {
+    3
+}
+
+fn default_github_retry_delay_ms() -> u64 {
+    1000
+}
+
+fn default_min_remaining_requests() -> u32 {
+    500
+}
+
+fn default_diff_prefetch() -> usize {
+    5
+}
+
+fn default_diff_concurrency() -> usize {
+    4
+}
+
+fn default_max_diff_bytes() -> usize {
+    20_000
+}
 
-## 🚨 Action Required
-{action_required}
+fn default_smtp_password_env() -> String {
+    "GH_REPORT_SMTP_PASSWORD".to_string()
+}
 
-## 👀 Needs Attention
-{needs_attention}
+fn default_slack_token_env() -> String {
+    "GH_REPORT_SLACK_TOKEN".to_string()
+}
 
-## 📋 Key Changes and Proposals
-{key_changes}
+fn default_hook_timeout_secs() -> u64 {
+    60
+}
 
-## 💡 Suggested Actions
-{suggested_actions}
+fn default_notifications() -> bool {
+    true
+}
 
-## 📰 FYI
-{fyi}
+fn default_ignore_bots() -> bool {
+    true
+}
 
-## 📊 Repository Activity Changes
-{repo_changes}
+fn default_ci_status() -> bool {
+    true
+}
 
----
-*Report generated at {timestamp} | Est. cost: ${cost}*"#
-        .to_string()
+fn default_upcoming_days() -> u32 {
+    14
+}
+
+fn default_reviewer_workload() -> bool {
+    true
+}
+
+fn default_project_status_field() -> String {
+    "Status".to_string()
 }
 
 fn default_cache_enabled() -> bool {
@@ -229,20 +1080,98 @@ fn default_compression_enabled() -> bool {
     true
 }
 
+fn default_activity_ttl_minutes() -> u32 {
+    15
+}
+
+fn default_cache_max_size_mb() -> u64 {
+    500
+}
+
 fn default_claude_backend() -> ClaudeBackend {
     ClaudeBackend::Auto
 }
 
+fn default_llm_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
 // Default implementation for ReportConfig
 impl Default for ReportConfig {
     fn default() -> Self {
         ReportConfig {
-            template: default_template(),
+            template: None,
+            deadlines_ics: false,
+            format: ReportFormat::default(),
+            locale: None,
+            group_by: GroupBy::default(),
+            notifications: default_notifications(),
+            ci_status: default_ci_status(),
+            upcoming_days: default_upcoming_days(),
+            reviewer_workload: default_reviewer_workload(),
+            min_priority_score: 0,
+            org: None,
+            users: Vec::new(),
+            outputs: Vec::new(),
+            goals: Vec::new(),
+            goals_file: None,
+            include_repos: Vec::new(),
+            exclude_repos: Vec::new(),
+            exclude_labels: Vec::new(),
+            require_labels: Vec::new(),
+            ignore_authors: Vec::new(),
+            ignore_bots: default_ignore_bots(),
+            projects: Vec::new(),
+            project_status_field: default_project_status_field(),
+            sections: ReportSections::default(),
+        }
+    }
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        LlmConfig {
+            provider: None,
+            base_url: None,
+            api_key_env: default_llm_api_key_env(),
         }
     }
 }
 
 // Default implementation for CacheConfig
+impl Default for HooksConfig {
+    fn default() -> Self {
+        HooksConfig {
+            pre_report: Vec::new(),
+            post_report: Vec::new(),
+            timeout_secs: default_hook_timeout_secs(),
+        }
+    }
+}
+
+impl Default for GitHubConfig {
+    fn default() -> Self {
+        GitHubConfig {
+            max_attempts: default_github_max_attempts(),
+            retry_delay_ms: default_github_retry_delay_ms(),
+            min_remaining_requests: default_min_remaining_requests(),
+        }
+    }
+}
+
+impl Default for IntelligenceConfig {
+    fn default() -> Self {
+        IntelligenceConfig {
+            min_score_for_ai: 0,
+            diff_prefetch: default_diff_prefetch(),
+            diff_concurrency: default_diff_concurrency(),
+            max_diff_bytes: default_max_diff_bytes(),
+            diff_patch_tokens: 0,
+            escalation: EscalationDetection::default(),
+        }
+    }
+}
+
 impl Default for CacheConfig {
     fn default() -> Self {
         CacheConfig {
@@ -250,6 +1179,9 @@ impl Default for CacheConfig {
             ttl_hours: default_cache_ttl(),
             compression_enabled: default_compression_enabled(),
             cache_dir: None,
+            activity_ttl_minutes: default_activity_ttl_minutes(),
+            max_size_mb: default_cache_max_size_mb(),
+            namespace: None,
         }
     }
 }
@@ -265,7 +1197,10 @@ mod tests {
         assert_eq!(config.settings.max_lookback_days, 30);
         assert_eq!(config.settings.max_issues_per_report, 100);
         assert_eq!(config.settings.max_comments_per_report, 500);
+        assert_eq!(config.settings.max_comments_per_issue, 0);
+        assert_eq!(config.settings.comment_window, CommentWindow::Latest);
         assert_eq!(config.settings.inactive_repo_threshold_days, 30);
+        assert_eq!(config.settings.waiting_on_author_days, 7);
 
         assert_eq!(config.claude.primary_model, "sonnet");
         assert_eq!(config.claude.secondary_model, "haiku");
@@ -293,6 +1228,24 @@ mod tests {
         assert_eq!(expanded, absolute_path);
     }
 
+    #[test]
+    fn test_repo_filters() {
+        let report = ReportConfig {
+            include_repos: vec!["tokio-rs/tokio".to_string(), "carllerche/*".to_string()],
+            exclude_repos: vec!["*-archive".to_string()],
+            ..ReportConfig::default()
+        };
+        assert!(report.includes_repo("tokio-rs/tokio"));
+        assert!(report.includes_repo("Carllerche/gh-report"));
+        assert!(!report.includes_repo("tokio-rs/tokio-uring"));
+        assert!(!report.includes_repo("carllerche/h2-archive"));
+
+        assert!(ReportConfig::default().includes_repo("tokio-rs/mio"));
+        assert!(glob_matches("tokio-rs/*-*", "tokio-rs/tokio-util"));
+        assert!(!glob_matches("tokio-rs/*-*", "tokio-rs/tokio"));
+        assert!(!glob_matches("a*a", "a"));
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
@@ -310,6 +1263,224 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repo_context() {
+        let toml_str = r#"
+[settings]
+report_dir = "/tmp/reports"
+
+[claude]
+
+[repo."tokio-rs/tokio"]
+context = "This is the async runtime"
+notes = "I maintain the io driver; ignore docs-only PRs"
+
+[repo."tokio-rs/mio"]
+context = "  "
+
+[repo."tokio-rs/axum"]
+notes = "Only routing changes matter to me"
+
+[repo."tokio-rs/tracing"]
+exclude_labels = ["wontfix"]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(
+            config.repo_context("tokio-rs/tokio").as_deref(),
+            Some("This is the async runtime\nI maintain the io driver; ignore docs-only PRs")
+        );
+        assert_eq!(
+            config.repo_context("tokio-rs/axum").as_deref(),
+            Some("Only routing changes matter to me")
+        );
+        assert_eq!(config.repo_context("tokio-rs/mio"), None);
+        assert_eq!(config.repo_context("rust-lang/rust"), None);
+        assert_eq!(
+            config.repo_contexts().into_keys().collect::<Vec<_>>(),
+            ["tokio-rs/axum", "tokio-rs/tokio"]
+        );
+    }
+
+    #[test]
+    fn test_report_sections() {
+        let toml_str = r#"
+[settings]
+report_dir = "/tmp/reports"
+
+[claude]
+
+[report.sections]
+prioritized = false
+statistics = false
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let sections = config.report.sections;
+        assert!(sections.action_items);
+        assert!(sections.ai_summary);
+        assert!(!sections.prioritized);
+        assert!(sections.by_repository);
+        assert!(!sections.statistics);
+
+        assert_eq!(Config::default().report.sections, ReportSections::default());
+        assert!(ReportSections::default().statistics);
+    }
+
+    #[test]
+    fn test_override_models() {
+        let mut config = Config::default();
+        let secondary = config.claude.secondary_model.clone();
+
+        config.override_models(Some("opus"), None);
+        assert_eq!(config.claude.primary_model, "claude-3-opus-20240229");
+        assert_eq!(config.claude.secondary_model, secondary);
+
+        config.override_models(None, Some("claude-3-custom"));
+        assert_eq!(config.claude.secondary_model, "claude-3-custom");
+    }
+
+    #[test]
+    fn test_model_aliases_from_config() {
+        let toml_str = r#"
+[settings]
+report_dir = "/tmp/reports"
+
+[claude]
+
+[claude.aliases]
+Opus = "claude-opus-4-1"
+fast = "haiku"
+
+[claude.pricing."claude-opus-4-1"]
+input = 12.0
+output = 60.0
+cache_read = 1.0
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(config.claude.resolve_model("opus"), "claude-opus-4-1");
+        assert_eq!(
+            config.claude.resolve_model("fast"),
+            "claude-3-5-haiku-20241022"
+        );
+        assert_eq!(
+            config.claude.resolve_model("sonnet"),
+            "claude-3-5-sonnet-20241022"
+        );
+
+        let aliases = config.claude.model_aliases();
+        assert_eq!(aliases["opus"], "claude-opus-4-1");
+        assert_eq!(aliases["opus-3"], "claude-3-opus-20240229");
+        assert_eq!(aliases["fast"], "claude-3-5-haiku-20241022");
+
+        let price = config.claude.pricing().price("claude-opus-4-1");
+        assert_eq!((price.input, price.output), (12.0, 60.0));
+        assert_eq!((price.cache_write, price.cache_read), (15.0, 1.0));
+    }
+
+    #[test]
+    fn test_llm_provider() {
+        let mut config = Config::default();
+        assert_eq!(config.llm_provider(), LlmProvider::Auto);
+        config.claude.backend = ClaudeBackend::Cli;
+        assert_eq!(config.llm_provider(), LlmProvider::ClaudeCli);
+
+        let toml_str = r#"
+[settings]
+report_dir = "/tmp/reports"
+
+[claude]
+backend = "cli"
+primary_model = "llama3.1"
+
+[llm]
+provider = "openai"
+base_url = "http://localhost:11434/v1"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.llm_provider(), LlmProvider::Openai);
+        assert_eq!(config.llm.api_key_env, "OPENAI_API_KEY");
+        assert_eq!(
+            config.llm.base_url.as_deref(),
+            Some("http://localhost:11434/v1")
+        );
+    }
+
+    #[test]
+    fn test_exclude_labels() {
+        let toml_str = r#"
+[settings]
+report_dir = "/tmp/reports"
+exclude_labels = ["wontfix", "duplicate"]
+
+[claude]
+
+[repo."tokio-rs/tokio"]
+exclude_labels = ["dependencies"]
+
+[repo."tokio-rs/axum"]
+context = "Web framework"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.exclude_labels("tokio-rs/tokio"), ["dependencies"]);
+        assert_eq!(
+            config.exclude_labels("tokio-rs/axum"),
+            ["wontfix", "duplicate"]
+        );
+        assert_eq!(
+            config.exclude_labels("rust-lang/rust"),
+            ["wontfix", "duplicate"]
+        );
+    }
+
+    #[test]
+    fn test_report_label_filters() {
+        let toml_str = r#"
+[settings]
+report_dir = "/tmp/reports"
+exclude_labels = ["wontfix"]
+
+[report]
+exclude_labels = ["dependencies", "bot"]
+require_labels = ["bug"]
+
+[claude]
+
+[repo."tokio-rs/tokio"]
+exclude_labels = ["duplicate"]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.exclude_labels("tokio-rs/tokio"),
+            ["dependencies", "bot", "duplicate"]
+        );
+        assert_eq!(
+            config.exclude_labels("rust-lang/rust"),
+            ["dependencies", "bot", "wontfix"]
+        );
+        assert_eq!(config.report.require_labels, ["bug"]);
+    }
+
+    #[test]
+    fn test_ignores_author() {
+        let author = |login: &str, user_type: Option<&str>| Author {
+            login: login.to_string(),
+            user_type: user_type.map(str::to_string),
+        };
+        let mut report = ReportConfig {
+            ignore_authors: vec!["ci-*".to_string()],
+            ..ReportConfig::default()
+        };
+        assert!(report.ignores_author(&author("dependabot[bot]", None)));
+        assert!(report.ignores_author(&author("renovate", Some("Bot"))));
+        assert!(report.ignores_author(&author("CI-Runner", Some("User"))));
+        assert!(!report.ignores_author(&author("carllerche", Some("User"))));
+
+        report.ignore_bots = false;
+        assert!(!report.ignores_author(&author("dependabot[bot]", None)));
+        assert!(report.ignores_author(&author("ci-runner", None)));
+    }
+
     #[test]
     fn test_importance_ordering() {
         use Importance::*;
@@ -323,11 +1494,86 @@ mod tests {
         assert_eq!(importances, vec![Low, Medium, High, Critical]);
     }
 
+    #[test]
+    fn test_profiles() {
+        let path = Config::profile_path("work").unwrap();
+        assert!(path.ends_with(".config/gh-report/profiles/work.toml"));
+        assert!(Config::profile_path("../config").is_err());
+        assert!(Config::profile_path("").is_err());
+
+        let config = Config::for_profile("oss");
+        assert_eq!(
+            config.settings.report_dir,
+            Path::new("~/Github Reports/oss")
+        );
+        assert_eq!(
+            config.settings.state_file,
+            Path::new("~/Github Reports/oss/.gh-report-state.json")
+        );
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(Config::profiles_in(&temp_dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+        for file in ["work.toml", "oss.toml", "notes.md"] {
+            std::fs::write(temp_dir.path().join(file), "").unwrap();
+        }
+        assert_eq!(
+            Config::profiles_in(temp_dir.path()).unwrap(),
+            ["oss", "work"]
+        );
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let mut config = Config::default();
+        assert_eq!(config.cache_dir(), default_cache_dir());
+
+        config.cache.namespace = cache_namespace(&Config::profile_path("work").unwrap());
+        assert_eq!(
+            config.cache_dir(),
+            default_cache_dir().join("profiles/work")
+        );
+        assert_eq!(
+            cache_namespace(&Config::default_config_path().unwrap()),
+            None
+        );
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("team.toml");
+        let namespace = cache_namespace(&path).unwrap();
+        let name = namespace.strip_prefix("configs").unwrap().to_string_lossy();
+        assert!(name.starts_with("team-") && name.len() == "team-".len() + 8);
+        assert_ne!(
+            cache_namespace(&temp_dir.path().join("other/team.toml")),
+            Some(namespace)
+        );
+
+        std::fs::write(
+            &path,
+            r#"
+[settings]
+report_dir = "/tmp/reports"
+
+[claude]
+primary_model = "claude-3-5-sonnet-20241022"
+secondary_model = "claude-3-5-haiku-20241022"
+
+[cache]
+cache_dir = "~/.cache/work-reports"
+"#,
+        )
+        .unwrap();
+        let config = Config::load(Some(&path)).unwrap();
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(config.cache_dir(), home.join(".cache/work-reports"));
+    }
+
     #[test]
     fn test_default_config_path() {
         let path = Config::default_config_path().unwrap();
         let path_str = path.to_string_lossy();
-        
+
         // Should always use ~/.config/gh-report/config.toml on all platforms
         assert!(path_str.ends_with(".config/gh-report/config.toml"));
         assert!(path_str.contains(".config"));
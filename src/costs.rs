@@ -0,0 +1,360 @@
+//! Ledger of what gh-report spent on LLM calls
+//!
+//! Every response from an LLM backend is counted per model while the command
+//! runs. At the end of a run that made any calls, one [`CostEntry`] with the
+//! usage and its estimated cost is added to a JSON file next to the state
+//! file, which `gh-report costs` totals by day, week, and month.
+
+use anyhow::{Context, Result};
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, ToSpan};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::claude::PricingTable;
+
+static USAGE: Mutex<BTreeMap<String, ModelUsage>> = Mutex::new(BTreeMap::new());
+
+/// File name of the ledger, in the directory of the state file
+pub const LEDGER_FILE_NAME: &str = ".gh-report-costs.json";
+
+/// Share of the monthly budget above which runs warn that it is running out
+pub const BUDGET_WARNING_SHARE: f32 = 0.8;
+
+/// Days, weeks, and months listed by `gh-report costs`
+const DAYS_SHOWN: i64 = 7;
+const WEEKS_SHOWN: i64 = 4;
+const MONTHS_SHOWN: i64 = 6;
+
+/// Record the tokens of a response from `model`
+pub fn record_usage(model: &str, input_tokens: u32, output_tokens: u32) {
+    let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let usage = usage.entry(model.to_string()).or_default();
+    usage.calls += 1;
+    usage.input_tokens += u64::from(input_tokens);
+    usage.output_tokens += u64::from(output_tokens);
+}
+
+/// Tokens sent to and received from one model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct ModelUsage {
+    pub calls: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated cost in dollars
+    #[serde(default)]
+    pub cost: f32,
+}
+
+/// The LLM usage of one run
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CostEntry {
+    pub command: String,
+    pub recorded_at: Timestamp,
+    pub models: BTreeMap<String, ModelUsage>,
+    /// Estimated cost of all models in dollars
+    pub cost: f32,
+}
+
+impl CostEntry {
+    /// Take the usage recorded since the last capture, priced with `pricing`
+    ///
+    /// Returns `None` when no calls were made, so runs that didn't use an
+    /// LLM stay out of the ledger.
+    pub fn capture(command: &str, recorded_at: Timestamp, pricing: &PricingTable) -> Option<Self> {
+        let usage = std::mem::take(&mut *USAGE.lock().unwrap_or_else(|e| e.into_inner()));
+        if usage.is_empty() {
+            return None;
+        }
+        let models: BTreeMap<String, ModelUsage> = usage
+            .into_iter()
+            .map(|(model, usage)| {
+                let cost = pricing.estimate_cost(
+                    &model,
+                    u32::try_from(usage.input_tokens).unwrap_or(u32::MAX),
+                    u32::try_from(usage.output_tokens).unwrap_or(u32::MAX),
+                );
+                (model, ModelUsage { cost, ..usage })
+            })
+            .collect();
+        Some(CostEntry {
+            command: command.to_string(),
+            recorded_at,
+            cost: models.values().map(|usage| usage.cost).sum(),
+            models,
+        })
+    }
+}
+
+/// The ledger file
+pub struct CostLedger {
+    path: PathBuf,
+}
+
+impl CostLedger {
+    /// The ledger kept next to `state_file`
+    pub fn beside(state_file: &Path) -> Self {
+        CostLedger {
+            path: state_file.with_file_name(LEDGER_FILE_NAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All entries, oldest first
+    pub fn load(&self) -> Result<Vec<CostEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read cost ledger {:?}", self.path))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse cost ledger {:?}", self.path))
+    }
+
+    /// Add an entry, returning all entries including it
+    pub fn append(&self, entry: CostEntry) -> Result<Vec<CostEntry>> {
+        let mut entries = self.load()?;
+        entries.push(entry);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        let json =
+            serde_json::to_string_pretty(&entries).context("Failed to serialize cost ledger")?;
+        fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write cost ledger {:?}", self.path))?;
+        Ok(entries)
+    }
+}
+
+/// Total cost of the entries in the calendar month of `now`
+pub fn month_to_date(entries: &[CostEntry], now: Timestamp, tz: &TimeZone) -> f32 {
+    let month = now.to_zoned(tz.clone()).date().first_of_month();
+    entries
+        .iter()
+        .filter(|entry| local_date(entry, tz).first_of_month() == month)
+        .map(|entry| entry.cost)
+        .sum()
+}
+
+/// A warning when the month's costs are close to or above `budget`
+pub fn budget_warning(month_to_date: f32, budget: f32) -> Option<String> {
+    if month_to_date > budget {
+        Some(format!(
+            "LLM costs this month (${:.2}) are over the monthly budget of ${:.2}",
+            month_to_date, budget
+        ))
+    } else if month_to_date >= budget * BUDGET_WARNING_SHARE {
+        Some(format!(
+            "LLM costs this month (${:.2}) have used {:.0}% of the monthly budget of ${:.2}",
+            month_to_date,
+            month_to_date / budget * 100.0,
+            budget
+        ))
+    } else {
+        None
+    }
+}
+
+fn local_date(entry: &CostEntry, tz: &TimeZone) -> Date {
+    entry.recorded_at.to_zoned(tz.clone()).date()
+}
+
+/// Render daily, weekly, and monthly totals of the ledger
+pub fn render_costs(
+    entries: &[CostEntry],
+    now: Timestamp,
+    tz: &TimeZone,
+    budget: Option<f32>,
+) -> String {
+    let mut out = String::new();
+    let Some(first) = entries.iter().map(|entry| entry.recorded_at).min() else {
+        return "No LLM costs recorded yet.\n".to_string();
+    };
+    let _ = writeln!(
+        out,
+        "LLM costs: ${:.2} over {} runs since {}\n",
+        entries.iter().map(|entry| entry.cost).sum::<f32>(),
+        entries.len(),
+        first.to_zoned(tz.clone()).date()
+    );
+
+    let today = now.to_zoned(tz.clone()).date();
+    let this_week = today
+        .checked_sub(i64::from(today.weekday().to_monday_zero_offset()).days())
+        .unwrap_or(today);
+    let this_month = today.first_of_month();
+
+    let _ = writeln!(out, "Daily:");
+    for days_ago in (0..DAYS_SHOWN).rev() {
+        let Ok(day) = today.checked_sub(days_ago.days()) else {
+            continue;
+        };
+        let (cost, runs) = total(entries, tz, |date| date == day);
+        let _ = writeln!(
+            out,
+            "  {:<18} ${:>8.2}  {:>3} runs",
+            day.to_string(),
+            cost,
+            runs
+        );
+    }
+
+    let _ = writeln!(out, "\nWeekly:");
+    for weeks_ago in (0..WEEKS_SHOWN).rev() {
+        let Ok(start) = this_week.checked_sub((weeks_ago * 7).days()) else {
+            continue;
+        };
+        let Ok(end) = start.checked_add(7.days()) else {
+            continue;
+        };
+        let (cost, runs) = total(entries, tz, |date| date >= start && date < end);
+        let _ = writeln!(
+            out,
+            "  {:<18} ${:>8.2}  {:>3} runs",
+            format!("week of {}", start),
+            cost,
+            runs
+        );
+    }
+
+    let _ = writeln!(out, "\nMonthly:");
+    for months_ago in (0..MONTHS_SHOWN).rev() {
+        let Ok(month) = this_month.checked_sub(months_ago.months()) else {
+            continue;
+        };
+        let (cost, runs) = total(entries, tz, |date| date.first_of_month() == month);
+        let _ = writeln!(
+            out,
+            "  {:<18} ${:>8.2}  {:>3} runs",
+            month.strftime("%Y-%m").to_string(),
+            cost,
+            runs
+        );
+    }
+
+    let mut models: BTreeMap<&str, ModelUsage> = BTreeMap::new();
+    for entry in entries {
+        if local_date(entry, tz).first_of_month() != this_month {
+            continue;
+        }
+        for (model, usage) in &entry.models {
+            let total = models.entry(model).or_default();
+            total.calls += usage.calls;
+            total.input_tokens += usage.input_tokens;
+            total.output_tokens += usage.output_tokens;
+            total.cost += usage.cost;
+        }
+    }
+    if !models.is_empty() {
+        let _ = writeln!(out, "\nThis month by model:");
+        for (model, usage) in &models {
+            let _ = writeln!(
+                out,
+                "  {:<32} {:>5} calls  {:>10} in  {:>9} out  ${:>8.2}",
+                model, usage.calls, usage.input_tokens, usage.output_tokens, usage.cost
+            );
+        }
+    }
+
+    if let Some(budget) = budget {
+        let spent = month_to_date(entries, now, tz);
+        let _ = writeln!(
+            out,
+            "\nBudget: ${:.2} of ${:.2} spent this month ({:.0}%)",
+            spent,
+            budget,
+            spent / budget * 100.0
+        );
+        if let Some(warning) = budget_warning(spent, budget) {
+            let _ = writeln!(out, "⚠️  {}", warning);
+        }
+    }
+
+    out
+}
+
+/// Cost and number of the entries on dates matching `on`
+fn total(entries: &[CostEntry], tz: &TimeZone, on: impl Fn(Date) -> bool) -> (f32, usize) {
+    entries
+        .iter()
+        .filter(|entry| on(local_date(entry, tz)))
+        .fold((0.0, 0), |(cost, runs), entry| {
+            (cost + entry.cost, runs + 1)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(recorded_at: &str, model: &str, cost: f32) -> CostEntry {
+        CostEntry {
+            command: "report".to_string(),
+            recorded_at: recorded_at.parse().unwrap(),
+            models: BTreeMap::from([(
+                model.to_string(),
+                ModelUsage {
+                    calls: 2,
+                    input_tokens: 10_000,
+                    output_tokens: 1_000,
+                    cost,
+                },
+            )]),
+            cost,
+        }
+    }
+
+    #[test]
+    fn test_ledger_and_totals() {
+        let temp_dir = TempDir::new().unwrap();
+        let ledger = CostLedger::beside(&temp_dir.path().join(".gh-report-state.json"));
+        assert!(ledger.load().unwrap().is_empty());
+
+        ledger
+            .append(entry("2024-02-28T09:00:00Z", "claude-3-5-haiku", 1.0))
+            .unwrap();
+        ledger
+            .append(entry("2024-03-04T09:00:00Z", "claude-sonnet-4-5", 2.0))
+            .unwrap();
+        let entries = ledger
+            .append(entry("2024-03-06T09:00:00Z", "claude-sonnet-4-5", 6.5))
+            .unwrap();
+        assert_eq!(ledger.load().unwrap(), entries);
+
+        let now: Timestamp = "2024-03-06T12:00:00Z".parse().unwrap();
+        let tz = TimeZone::UTC;
+        assert_eq!(month_to_date(&entries, now, &tz), 8.5);
+
+        let output = render_costs(&entries, now, &tz, Some(10.0));
+        assert!(output.starts_with("LLM costs: $9.50 over 3 runs since 2024-02-28\n"));
+        assert!(output.contains("  2024-03-06         $    6.50    1 runs\n"));
+        // Monday March 4th starts the week
+        assert!(output.contains("  week of 2024-03-04 $    8.50    2 runs\n"));
+        assert!(output.contains("  week of 2024-02-26 $    1.00    1 runs\n"));
+        assert!(output.contains("  2024-02            $    1.00    1 runs\n"));
+        assert!(output.contains("  claude-sonnet-4-5                    4 calls"));
+        assert!(!output.contains("claude-3-5-haiku"));
+        assert!(output.contains("Budget: $8.50 of $10.00 spent this month (85%)\n"));
+        assert!(output.contains("have used 85% of the monthly budget"));
+    }
+
+    #[test]
+    fn test_budget_warning() {
+        assert_eq!(budget_warning(5.0, 10.0), None);
+        assert!(budget_warning(8.0, 10.0).unwrap().contains("used 80%"));
+        assert!(budget_warning(12.0, 10.0)
+            .unwrap()
+            .contains("over the monthly budget of $10.00"));
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::{bail, Context, Result};
+use lettre::message::{Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use tracing::info;
+
+use crate::config::{Config, EmailConfig, ReportFormat, SmtpSecurity};
+use crate::report::{escape_html, markdown_to_html, Locale, Report};
+
+/// Build the email for a report
+///
+/// Markdown reports are sent as plain text with an HTML rendering of it as
+/// the alternative most clients show; HTML reports are sent as they are.
+pub fn build_email(report: &Report, from: &str, to: &[String], locale: Locale) -> Result<Message> {
+    if to.is_empty() {
+        bail!("No recipients to email the report to");
+    }
+
+    let mut builder = Message::builder()
+        .from(parse_mailbox(from)?)
+        .subject(format!(
+            "{} - {}",
+            report.title,
+            locale.format_date(report.timestamp)
+        ));
+    for address in to {
+        builder = builder.to(parse_mailbox(address)?);
+    }
+
+    let message = match report.format {
+        ReportFormat::Markdown => builder.multipart(MultiPart::alternative_plain_html(
+            report.content.clone(),
+            html_page(&report.title, &report.content),
+        )),
+        ReportFormat::Html => builder.singlepart(SinglePart::html(report.content.clone())),
+        ReportFormat::Json => builder.singlepart(SinglePart::plain(report.content.clone())),
+    };
+    message.context("Failed to build the report email")
+}
+
+/// Email a saved report with the SMTP settings under `[delivery.email]`
+pub fn send_email(report: &Report, config: &Config, to: &[String]) -> Result<()> {
+    let settings = config
+        .delivery
+        .email
+        .as_ref()
+        .context("Emailing reports needs SMTP settings under [delivery.email]")?;
+    let message = build_email(report, &settings.from, to, config.report.locale())?;
+
+    info!(
+        "Sending the report to {} through {}",
+        to.join(", "),
+        settings.smtp_host
+    );
+    transport(settings)?
+        .send(&message)
+        .with_context(|| format!("Failed to send the report through {}", settings.smtp_host))?;
+    Ok(())
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox> {
+    address
+        .parse()
+        .with_context(|| format!("Invalid email address {:?}", address))
+}
+
+fn transport(settings: &EmailConfig) -> Result<SmtpTransport> {
+    let host = settings.smtp_host.as_str();
+    let mut builder = match settings.security {
+        SmtpSecurity::Starttls => SmtpTransport::starttls_relay(host)?,
+        SmtpSecurity::Tls => SmtpTransport::relay(host)?,
+        SmtpSecurity::None => SmtpTransport::builder_dangerous(host),
+    };
+    if let Some(port) = settings.smtp_port {
+        builder = builder.port(port);
+    }
+    if let Some(username) = &settings.username {
+        let password = std::env::var(&settings.password_env).with_context(|| {
+            format!(
+                "Set {} to the SMTP password for {}",
+                settings.password_env, username
+            )
+        })?;
+        builder = builder.credentials(Credentials::new(username.clone(), password));
+    }
+    Ok(builder.build())
+}
+
+/// A standalone HTML page for a markdown report
+fn html_page(title: &str, markdown: &str) -> String {
+    let body = markdown_to_html(markdown, false);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+         <body style=\"font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; \
+         line-height: 1.5; max-width: 52rem;\">\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{PeriodTotals, ReportDigest};
+    use std::collections::BTreeMap;
+
+    fn report(format: ReportFormat, content: &str) -> Report {
+        Report {
+            title: "GitHub Activity Report".to_string(),
+            content: content.to_string(),
+            timestamp: "2024-03-06T12:00:00Z".parse().unwrap(),
+            estimated_cost: 0.0,
+            deadlines: Vec::new(),
+            format,
+            items: Vec::new(),
+            seen_items: BTreeMap::new(),
+            repo_trust: None,
+            scores: None,
+            totals: PeriodTotals::default(),
+            digest: ReportDigest::default(),
+        }
+    }
+
+    #[test]
+    fn test_markdown_report_email() {
+        let report = report(
+            ReportFormat::Markdown,
+            "# GitHub Activity Report\n\n- **[tokio-rs/tokio]** PR [#1](https://github.com/tokio-rs/tokio/pull/1)\n",
+        );
+        let message = build_email(
+            &report,
+            "gh-report <reports@example.com>",
+            &["me@example.com".to_string()],
+            Locale::default(),
+        )
+        .unwrap();
+        let message = String::from_utf8(message.formatted()).unwrap();
+
+        assert!(message.contains("Subject: GitHub Activity Report - 2024-03-06"));
+        assert!(message.contains("To: me@example.com"));
+        assert!(message.contains("Content-Type: text/plain"));
+        assert!(message.contains("Content-Type: text/html"));
+
+        let page = html_page(&report.title, &report.content);
+        assert!(page.contains("<h1>GitHub Activity Report</h1>"));
+        assert!(page.contains("<a href=\"https://github.com/tokio-rs/tokio/pull/1\">#1</a>"));
+    }
+
+    #[test]
+    fn test_html_in_markdown_is_escaped() {
+        let page = html_page(
+            "Report",
+            "- Fix <img src=x onerror=alert(1)> in docs\n\n<script>alert(1)</script>\n",
+        );
+        assert!(page.contains("Fix &lt;img src=x onerror=alert(1)&gt; in docs"));
+        assert!(page.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!page.contains("<script>"));
+        assert!(!page.contains("<img"));
+    }
+
+    #[test]
+    fn test_email_errors() {
+        let report = report(ReportFormat::Html, "<html></html>");
+        let to = ["not an address".to_string()];
+        let error =
+            build_email(&report, "reports@example.com", &to, Locale::default()).unwrap_err();
+        assert!(error.to_string().contains("Invalid email address"));
+
+        let error =
+            send_email(&report, &Config::default(), &["me@example.com".to_string()]).unwrap_err();
+        assert!(error.to_string().contains("[delivery.email]"));
+    }
+}
@@ -0,0 +1,10 @@
+//! Delivery of saved reports to places other than the report directory
+//!
+//! Kept apart from report generation: delivery only sees the finished
+//! `Report`, after it has been written to disk.
+
+mod email;
+mod slack;
+
+pub use email::{build_email, send_email};
+pub use slack::{send_slack, slack_messages, SLACK_MESSAGE_LIMIT};
@@ -0,0 +1,389 @@
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::blocking::Client as HttpClient;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::info;
+
+use crate::config::{Config, SlackConfig, SlackContent};
+use crate::report::{Locale, Report};
+
+/// Longest message posted at once; Slack truncates long messages and
+/// recommends staying under 4,000 characters
+pub const SLACK_MESSAGE_LIMIT: usize = 4000;
+
+const POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+/// The Slack messages for a report, in posting order
+///
+/// The configured part of the report's digest is converted to Slack's mrkdwn
+/// and split into messages of at most `limit` characters. What is posted
+/// doesn't depend on how `report.template` or `report.format` lay out the
+/// report itself.
+pub fn slack_messages(
+    report: &Report,
+    content: SlackContent,
+    locale: Locale,
+    limit: usize,
+) -> Vec<String> {
+    let digest = &report.digest;
+    let mut text = format!("# {}\n", report.title);
+    match content {
+        SlackContent::Summary => {
+            if let Some(highlights) = &digest.highlights {
+                text.push_str(&format!("\n## Highlights\n\n{}\n", highlights.trim_end()));
+            }
+            if let Some(summary) = &digest.summary {
+                text.push_str("\n## Summary\n\n");
+                for (label, count) in summary.labeled() {
+                    text.push_str(&format!(
+                        "- **{}**: {}\n",
+                        label,
+                        locale.format_count(count as i64)
+                    ));
+                }
+            }
+            if digest.highlights.is_none() && digest.summary.is_none() {
+                text.push_str("\nNo activity to summarize.\n");
+            }
+        }
+        SlackContent::ActionItems if digest.action_items.is_empty() => {
+            text.push_str("\nNo action items in this report.\n");
+        }
+        SlackContent::ActionItems => {
+            text.push_str("\n## Action Items\n\n");
+            for (i, action) in digest.action_items.iter().enumerate() {
+                text.push_str(&format!(
+                    "{}. [{}] {} - {}\n",
+                    i + 1,
+                    action.urgency.label(),
+                    action.description,
+                    action.reason
+                ));
+                if let Some(excerpt) = &action.excerpt {
+                    text.push_str(&format!(
+                        "   > {} — [@{}]({})\n",
+                        excerpt.text, excerpt.author, excerpt.url
+                    ));
+                }
+            }
+        }
+    }
+
+    split_message(&to_mrkdwn(&text), limit)
+}
+
+/// Post a saved report with the settings under `[delivery.slack]`
+///
+/// With a `channel`, the first message is posted with the bot token and the
+/// rest as replies in its thread. With only a webhook, the messages are
+/// posted one after the other.
+pub fn send_slack(report: &Report, config: &Config) -> Result<()> {
+    let settings = config
+        .delivery
+        .slack
+        .as_ref()
+        .context("Posting reports to Slack needs settings under [delivery.slack]")?;
+    let messages = slack_messages(
+        report,
+        settings.content,
+        config.report.locale(),
+        SLACK_MESSAGE_LIMIT,
+    );
+
+    let client = HttpClient::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("Failed to create HTTP client")?;
+    match (&settings.channel, &settings.webhook_url) {
+        (Some(channel), _) => post_threaded(&client, settings, channel, &messages),
+        (None, Some(webhook_url)) => post_to_webhook(&client, webhook_url, &messages),
+        (None, None) => bail!("[delivery.slack] needs either webhook_url or channel"),
+    }
+}
+
+fn post_threaded(
+    client: &HttpClient,
+    settings: &SlackConfig,
+    channel: &str,
+    messages: &[String],
+) -> Result<()> {
+    let token = std::env::var(&settings.token_env).with_context(|| {
+        format!(
+            "Set {} to a Slack bot token to post to {}",
+            settings.token_env, channel
+        )
+    })?;
+
+    info!("Posting the report to Slack channel {}", channel);
+    let mut thread_ts: Option<String> = None;
+    for text in messages {
+        let mut body = serde_json::json!({
+            "channel": channel,
+            "text": text,
+            "unfurl_links": false,
+        });
+        if let Some(ts) = &thread_ts {
+            body["thread_ts"] = ts.clone().into();
+        }
+        let response: PostMessageResponse = client
+            .post(POST_MESSAGE_URL)
+            .bearer_auth(&token)
+            .json(&body)
+            .send()
+            .context("Failed to post the report to Slack")?
+            .json()
+            .context("Failed to parse the Slack response")?;
+        if !response.ok {
+            return Err(anyhow!(
+                "Slack refused the message: {}",
+                response
+                    .error
+                    .unwrap_or_else(|| "unknown error".to_string())
+            ));
+        }
+        if thread_ts.is_none() {
+            thread_ts = response.ts;
+        }
+    }
+    Ok(())
+}
+
+fn post_to_webhook(client: &HttpClient, webhook_url: &str, messages: &[String]) -> Result<()> {
+    info!("Posting the report to a Slack webhook");
+    for text in messages {
+        let response = client
+            .post(webhook_url)
+            .json(&serde_json::json!({ "text": text, "unfurl_links": false }))
+            .send()
+            .context("Failed to post the report to Slack")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().unwrap_or_default();
+            bail!("Slack webhook error ({}): {}", status, body);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct PostMessageResponse {
+    ok: bool,
+    #[serde(default)]
+    ts: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Convert the markdown of a report to Slack's mrkdwn
+fn to_mrkdwn(markdown: &str) -> String {
+    let mut output = String::new();
+    for line in markdown.lines() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let line = line.trim_start();
+        if let Some(quote) = line.strip_prefix("> ") {
+            // Slack only quotes at the start of a line
+            output.push_str("> ");
+            output.push_str(&inline_mrkdwn(quote));
+            output.push('\n');
+            continue;
+        }
+
+        output.push_str(indent);
+        if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim();
+            output.push_str(&format!("*{}*", inline_mrkdwn(heading).replace('*', "")));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            output.push_str("• ");
+            output.push_str(&inline_mrkdwn(item));
+        } else {
+            output.push_str(&inline_mrkdwn(line));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Escape Slack's control characters and convert bold text and links
+fn inline_mrkdwn(text: &str) -> String {
+    let text = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace("**", "*");
+
+    let mut output = String::new();
+    let mut rest = text.as_str();
+    while let Some(open) = rest.find('[') {
+        output.push_str(&rest[..open]);
+        let candidate = &rest[open..];
+        let link = candidate.find("](").and_then(|close| {
+            let label = &candidate[1..close];
+            let end = candidate[close..].find(')')? + close;
+            (!label.contains('[')).then(|| (label, &candidate[close + 2..end], end))
+        });
+        match link {
+            Some((label, url, end)) => {
+                output.push_str(&format!("<{}|{}>", url, label.replace('|', "/")));
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                output.push('[');
+                rest = &candidate[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Split `text` into messages of at most `limit` characters, at line breaks
+/// where possible
+fn split_message(text: &str, limit: usize) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current = String::new();
+    for line in text.trim_end().lines() {
+        let mut line = line.to_string();
+        while line.chars().count() > limit {
+            let cut: String = line.chars().take(limit).collect();
+            line = line[cut.len()..].to_string();
+            if !current.is_empty() {
+                messages.push(std::mem::take(&mut current));
+            }
+            messages.push(cut);
+        }
+        if !current.is_empty() && current.chars().count() + 1 + line.chars().count() > limit {
+            messages.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ReportFormat;
+    use crate::intelligence::{ActionItem, Excerpt, Urgency};
+    use crate::report::{ActivityCounts, PeriodTotals, ReportDigest};
+    use crate::test_utils::create_test_issue;
+    use std::collections::BTreeMap;
+
+    fn report(digest: ReportDigest) -> Report {
+        Report {
+            title: "GitHub Activity Report".to_string(),
+            content: "# Weekly notes\n".to_string(),
+            timestamp: "2024-03-06T12:00:00Z".parse().unwrap(),
+            estimated_cost: 0.0,
+            deadlines: Vec::new(),
+            format: ReportFormat::Markdown,
+            items: Vec::new(),
+            seen_items: BTreeMap::new(),
+            repo_trust: None,
+            scores: None,
+            totals: PeriodTotals::default(),
+            digest,
+        }
+    }
+
+    fn digest() -> ReportDigest {
+        let mut issue = create_test_issue(42, "Fix a race", true);
+        issue.url = "https://github.com/tokio-rs/tokio/pull/42".to_string();
+        ReportDigest {
+            highlights: Some("Two PRs & one issue need you.\n".to_string()),
+            summary: Some(ActivityCounts {
+                repositories: 1,
+                new_prs: 1_200,
+                ..ActivityCounts::default()
+            }),
+            action_items: vec![ActionItem {
+                description: "**[tokio-rs/tokio]** Review PR \
+                              [#42](https://github.com/tokio-rs/tokio/pull/42)"
+                    .to_string(),
+                issue,
+                repo: "tokio-rs/tokio".to_string(),
+                urgency: Urgency::High,
+                reason: "Fix a <race>".to_string(),
+                excerpt: Some(Excerpt {
+                    author: "alice".to_string(),
+                    text: "Could you take a look?".to_string(),
+                    url: "https://github.com/tokio-rs/tokio/pull/42#issuecomment-1".to_string(),
+                }),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_slack_messages() {
+        let report = report(digest());
+        let summary = slack_messages(
+            &report,
+            SlackContent::Summary,
+            Locale::default(),
+            SLACK_MESSAGE_LIMIT,
+        );
+        assert_eq!(
+            summary,
+            [
+                "*GitHub Activity Report*\n\n*Highlights*\n\nTwo PRs &amp; one issue need you.\n\n\
+                 *Summary*\n\n• *Repositories*: 1\n• *New Issues*: 0\n• *Updated Issues*: 0\n\
+                 • *New Pull Requests*: 1200\n• *Updated Pull Requests*: 0\n\
+                 • *Merged Pull Requests*: 0\n• *Closed Issues*: 0"
+            ]
+        );
+
+        let actions = slack_messages(
+            &report,
+            SlackContent::ActionItems,
+            Locale::default(),
+            SLACK_MESSAGE_LIMIT,
+        );
+        assert_eq!(
+            actions,
+            ["*GitHub Activity Report*\n\n*Action Items*\n\n\
+              1. [HIGH] *[tokio-rs/tokio]* Review PR <https://github.com/tokio-rs/tokio/pull/42|#42> - Fix a &lt;race&gt;\n\
+              > Could you take a look? — <https://github.com/tokio-rs/tokio/pull/42#issuecomment-1|@alice>"]
+        );
+
+        let empty = self::report(ReportDigest::default());
+        let actions = slack_messages(&empty, SlackContent::ActionItems, Locale::default(), 4000);
+        assert_eq!(
+            actions,
+            ["*GitHub Activity Report*\n\nNo action items in this report."]
+        );
+        let summary = slack_messages(&empty, SlackContent::Summary, Locale::default(), 4000);
+        assert_eq!(
+            summary,
+            ["*GitHub Activity Report*\n\nNo activity to summarize."]
+        );
+    }
+
+    #[test]
+    fn test_html_reports_post_their_digest() {
+        let mut report = report(digest());
+        report.format = ReportFormat::Html;
+        report.content = "<html></html>".to_string();
+        let messages = slack_messages(&report, SlackContent::Summary, Locale::default(), 4000);
+        assert!(messages[0].contains("Two PRs &amp; one issue need you."));
+    }
+
+    #[test]
+    fn test_long_reports_are_split() {
+        let messages = split_message("one\ntwo\nthree\nfour", 9);
+        assert_eq!(messages, ["one\ntwo", "three", "four"]);
+
+        let messages = split_message("abcdefghij\nk", 4);
+        assert_eq!(messages, ["abcd", "efgh", "ij\nk"]);
+    }
+
+    #[test]
+    fn test_slack_errors() {
+        let error = send_slack(&report(digest()), &Config::default()).unwrap_err();
+        assert!(error.to_string().contains("[delivery.slack]"));
+    }
+}
@@ -0,0 +1,335 @@
+//! Environment checks of `gh-report doctor`
+//!
+//! Each check passes, warns, or fails. Failures carry the hint of
+//! [`user_friendly_error`] for the error behind them, so the checklist
+//! suggests the same fixes as a failed run would.
+
+use anyhow::{anyhow, Result};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::claude::{probe_backends, ClaudeClient};
+use crate::config::{expand_tilde, Config, LlmProvider};
+use crate::error::user_friendly_error;
+use crate::github::check_gh_version;
+
+/// Outcome of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Something optional is missing, reports still work
+    Warn,
+    Fail,
+}
+
+/// One line of the checklist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// How to fix a warning or failure
+    pub hint: Option<String>,
+}
+
+impl Check {
+    pub fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Check {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    /// A failed check, explained the way errors of a run are
+    pub fn fail(name: impl Into<String>, error: &anyhow::Error) -> Self {
+        let user_error = user_friendly_error(error);
+        Check {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: format!("{:#}", error),
+            hint: user_error.suggestion().map(str::to_string),
+        }
+    }
+
+    /// Downgrade a failure to a warning, for checks of optional features
+    pub fn optional(mut self) -> Self {
+        if self.status == CheckStatus::Fail {
+            self.status = CheckStatus::Warn;
+        }
+        self
+    }
+}
+
+/// Where `doctor` looks for the config and state
+#[derive(Debug, Clone, Default)]
+pub struct DoctorOptions {
+    /// Config file, the default location when unset
+    pub config: Option<PathBuf>,
+    /// State file overriding `settings.state_file`
+    pub state: Option<PathBuf>,
+}
+
+/// Run every check, in the order they are listed
+pub fn run_checks(options: &DoctorOptions) -> Vec<Check> {
+    let (config, config_check) = match load_config(options.config.as_deref()) {
+        Ok((config, path)) => (
+            config,
+            Check::pass("Config file", path.display().to_string()),
+        ),
+        Err(e) => {
+            // The rest can still be checked with the defaults
+            let mut config = Config::default();
+            if let Ok(dir) = expand_tilde(&config.settings.report_dir) {
+                config.settings.report_dir = dir;
+            }
+            if let Ok(file) = expand_tilde(&config.settings.state_file) {
+                config.settings.state_file = file;
+            }
+            (config, Check::fail("Config file", &e))
+        }
+    };
+
+    let mut checks = vec![check_gh_installed()];
+    if checks[0].status == CheckStatus::Pass {
+        checks.push(check_gh_auth(config.settings.github_host.as_deref()));
+    }
+    checks.push(config_check);
+
+    checks.extend(check_llm(&config));
+
+    let state_file = options
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    checks.push(check_writable(
+        "Report directory",
+        &config.settings.report_dir,
+    ));
+    if let Some(dir) = state_file.parent() {
+        checks.push(check_writable("State directory", dir));
+    }
+    checks.push(check_writable("Cache directory", &config.cache_dir()).optional());
+    checks
+}
+
+fn load_config(path: Option<&Path>) -> Result<(Config, PathBuf)> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => Config::default_config_path()?,
+    };
+    Ok((Config::load(Some(&path))?, path))
+}
+
+fn check_gh_installed() -> Check {
+    match check_gh_version() {
+        Ok(version) => Check::pass("GitHub CLI", format!("gh {}", version)),
+        Err(e) => Check::fail("GitHub CLI", &e),
+    }
+}
+
+fn check_gh_auth(host: Option<&str>) -> Check {
+    let mut command = Command::new("gh");
+    command.args(["auth", "status"]);
+    if let Some(host) = host {
+        command.args(["--hostname", host]);
+    }
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(e) => return Check::fail("GitHub login", &anyhow!(e)),
+    };
+    if output.status.success() {
+        // `gh auth status` prints to stderr, or stdout in newer versions
+        let text = [output.stdout, output.stderr].concat();
+        let text = String::from_utf8_lossy(&text);
+        let account = text
+            .lines()
+            .find(|line| line.contains("Logged in to"))
+            .map(|line| line.trim().trim_start_matches('✓').trim().to_string())
+            .unwrap_or_else(|| "Logged in".to_string());
+        Check::pass("GitHub login", account)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Check::fail(
+            "GitHub login",
+            &anyhow!("not authenticated: {}", stderr.trim()),
+        )
+    }
+}
+
+/// The LLM providers that `[llm] provider` may use
+///
+/// Without any, reports are written without AI summaries, so these only warn.
+fn check_llm(config: &Config) -> Vec<Check> {
+    let provider = config.llm_provider();
+    let mut checks = Vec::new();
+
+    if matches!(provider, LlmProvider::Auto | LlmProvider::ClaudeCli) {
+        let cli = probe_backends(config)
+            .into_iter()
+            .find(|probe| probe.name == "claude-cli");
+        checks.push(match cli {
+            Some(probe) if probe.available => Check::pass("Claude CLI", probe.detail),
+            _ => Check {
+                name: "Claude CLI".to_string(),
+                status: CheckStatus::Warn,
+                detail: "`claude` not found".to_string(),
+                hint: Some(
+                    "Install Claude Code, or set ANTHROPIC_API_KEY to use the API".to_string(),
+                ),
+            },
+        });
+    }
+
+    if matches!(provider, LlmProvider::Auto | LlmProvider::Anthropic) {
+        checks.push(check_api_key().optional());
+    }
+
+    if provider == LlmProvider::Openai {
+        let probe = probe_backends(config)
+            .into_iter()
+            .find(|probe| probe.name == "openai");
+        checks.push(match probe {
+            Some(probe) if probe.available => Check::pass("OpenAI-compatible API", probe.detail),
+            Some(probe) => Check {
+                name: "OpenAI-compatible API".to_string(),
+                status: CheckStatus::Warn,
+                detail: probe.detail,
+                hint: Some(format!("Set {}", config.llm.api_key_env)),
+            },
+            None => Check::pass("OpenAI-compatible API", "configured"),
+        });
+    }
+    checks
+}
+
+/// Whether the API accepts the key, by listing the models, which costs nothing
+fn check_api_key() -> Check {
+    let result = ClaudeClient::new().and_then(|client| client.list_models());
+    match result {
+        Ok(models) => Check::pass(
+            "Anthropic API key",
+            format!("accepted, {} models available", models.len()),
+        ),
+        Err(e) => Check::fail("Anthropic API key", &e),
+    }
+}
+
+/// Whether files can be created in `dir`, which is created if missing
+pub fn check_writable(name: &str, dir: &Path) -> Check {
+    // The io error stays in the message, where `Permission denied` is recognized
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| {
+            let probe = dir.join(".gh-report-doctor");
+            std::fs::write(&probe, b"")?;
+            std::fs::remove_file(&probe)
+        })
+        .map_err(|e| anyhow!("Cannot write to {}: {}", dir.display(), e));
+    match result {
+        Ok(()) => Check::pass(name, dir.display().to_string()),
+        Err(e) => Check::fail(name, &e),
+    }
+}
+
+/// The checklist, with a hint under each warning and failure
+pub fn render_checks(checks: &[Check]) -> String {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut output = String::new();
+    for check in checks {
+        let mark = match check.status {
+            CheckStatus::Pass => "✅",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "❌",
+        };
+        let _ = writeln!(
+            output,
+            "{} {:<width$}  {}",
+            mark,
+            check.name,
+            check.detail,
+            width = width
+        );
+        if let Some(hint) = &check.hint {
+            let _ = writeln!(output, "   💡 {}", hint);
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    let warned = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Warn)
+        .count();
+    let _ = match (failed, warned) {
+        (0, 0) => writeln!(output, "\nEverything looks good."),
+        (0, _) => writeln!(
+            output,
+            "\nReports work; {} optional check{} need attention.",
+            warned,
+            if warned == 1 { "" } else { "s" }
+        ),
+        _ => writeln!(
+            output,
+            "\n{} check{} failed.",
+            failed,
+            if failed == 1 { "" } else { "s" }
+        ),
+    };
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("reports");
+        let check = check_writable("Report directory", &nested);
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert!(nested.is_dir());
+        assert_eq!(std::fs::read_dir(&nested).unwrap().count(), 0);
+
+        // A file where the directory should be
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let check = check_writable("State directory", &file.join("state"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("Cannot write to"));
+    }
+
+    #[test]
+    fn test_failed_check_reuses_suggestion() {
+        let check = Check::fail(
+            "GitHub login",
+            &anyhow!("not authenticated: run gh auth login"),
+        );
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(
+            check.hint.as_deref(),
+            Some("Run 'gh auth login' to authenticate")
+        );
+        assert_eq!(check.clone().optional().status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn test_render_checks() {
+        let checks = vec![
+            Check::pass("GitHub CLI", "gh 2.45.0"),
+            Check::fail("Config file", &anyhow!("Failed to read config from \"x\"")),
+        ];
+        assert_eq!(
+            render_checks(&checks),
+            "✅ GitHub CLI   gh 2.45.0\n\
+             ❌ Config file  Failed to read config from \"x\"\n   \
+             💡 Run 'gh-report init' to create a configuration\n\
+             \n1 check failed.\n"
+        );
+        assert!(render_checks(&checks[..1]).ends_with("\nEverything looks good.\n"));
+    }
+}
@@ -35,6 +35,11 @@ impl UserError {
         &self.message
     }
 
+    /// Get the suggestion for how to fix the error
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
     /// Format the error for display
     pub fn display(&self) {
         eprintln!("\n❌ Error: {}", self.message);
@@ -66,12 +71,21 @@ pub fn user_friendly_error(error: &anyhow::Error) -> UserError {
     let error_str = error.to_string();
 
     // GitHub CLI errors
-    if error_str.contains("gh: command not found") || error_str.contains("GitHub CLI not found") {
+    if error_str.contains("gh: command not found")
+        || error_str.contains("GitHub CLI not found")
+        || error_str.contains("Is GitHub CLI installed")
+    {
         return UserError::new("GitHub CLI is not installed")
             .with_details("The 'gh' command is required to fetch GitHub data")
             .with_suggestion("Install GitHub CLI from https://cli.github.com/");
     }
 
+    if error_str.contains("gh version") && error_str.contains("too old") {
+        return UserError::new("GitHub CLI is out of date")
+            .with_details(error_str)
+            .with_suggestion("Upgrade GitHub CLI from https://cli.github.com/");
+    }
+
     if error_str.contains("gh auth login") || error_str.contains("not authenticated") {
         return UserError::new("Not authenticated with GitHub")
             .with_details("You need to log in to GitHub CLI first")
@@ -85,7 +99,9 @@ pub fn user_friendly_error(error: &anyhow::Error) -> UserError {
             .with_suggestion("Set the ANTHROPIC_API_KEY environment variable");
     }
 
-    if error_str.contains("401") && error_str.contains("anthropic") {
+    if error_str.contains("401")
+        && (error_str.contains("anthropic") || error_str.contains("Claude API"))
+    {
         return UserError::new("Invalid Anthropic API key")
             .with_details("The provided API key was rejected by Claude's API")
             .with_suggestion("Check your ANTHROPIC_API_KEY is correct");
@@ -194,6 +210,15 @@ mod tests {
         assert!(user_error.suggestion.unwrap().contains("ANTHROPIC_API_KEY"));
     }
 
+    #[test]
+    fn test_gh_version_error_detection() {
+        let error = anyhow!("gh version 2.10.0 is too old. Minimum required: 2.20.0");
+        let user_error = user_friendly_error(&error);
+
+        assert_eq!(user_error.message, "GitHub CLI is out of date");
+        assert!(user_error.suggestion().unwrap().contains("Upgrade"));
+    }
+
     #[test]
     fn test_rate_limit_error_detection() {
         let error = anyhow!("rate limit exceeded");
@@ -0,0 +1,587 @@
+//! Webhook events stored locally, for event-driven reports
+//!
+//! `gh-report ingest` reads GitHub webhook deliveries as JSON lines and
+//! appends them to the event store (`settings.event_store`) in the shape of
+//! the events API. `report --from-events` then builds the report from the
+//! stored events and the issues, comments, and reviews their payloads carry,
+//! instead of polling `received_events`. With a webhook receiver already in
+//! place this gives realtime data that doesn't count against the rate limit.
+
+use anyhow::{bail, Context, Result};
+use jiff::{Timestamp, ToSpan};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::activity::{filter_events, issue_references};
+use crate::cache::generate_cache_key;
+use crate::github::{ActivityEvent, ActivityRepo, Author, Comment, Issue, RestIssue, Review};
+use crate::snapshot::{ActivitySnapshot, SnapshotItem, SNAPSHOT_VERSION};
+
+/// Webhook event names (the `X-GitHub-Event` header) and their events API type
+const EVENT_TYPES: &[(&str, &str)] = &[
+    ("issues", "IssuesEvent"),
+    ("issue_comment", "IssueCommentEvent"),
+    ("pull_request", "PullRequestEvent"),
+    ("pull_request_review", "PullRequestReviewEvent"),
+    (
+        "pull_request_review_comment",
+        "PullRequestReviewCommentEvent",
+    ),
+    ("discussion", "DiscussionEvent"),
+    ("discussion_comment", "DiscussionCommentEvent"),
+    ("release", "ReleaseEvent"),
+    ("push", "PushEvent"),
+    ("create", "CreateEvent"),
+    ("delete", "DeleteEvent"),
+    ("fork", "ForkEvent"),
+    ("watch", "WatchEvent"),
+    ("member", "MemberEvent"),
+    ("public", "PublicEvent"),
+    ("gollum", "GollumEvent"),
+];
+
+/// Payload fields holding when the event happened, most specific first
+const TIME_FIELDS: &[&str] = &[
+    "/review/submitted_at",
+    "/comment/updated_at",
+    "/release/published_at",
+    "/pull_request/updated_at",
+    "/issue/updated_at",
+    "/discussion/updated_at",
+    "/head_commit/timestamp",
+];
+
+/// Payload fields about the delivery rather than the event, not stored
+const DELIVERY_FIELDS: &[&str] = &[
+    "repository",
+    "sender",
+    "organization",
+    "installation",
+    "enterprise",
+];
+
+/// A webhook delivery as written by a receiver, with the headers that matter
+#[derive(Debug, Deserialize)]
+struct WebhookDelivery {
+    /// The `X-GitHub-Event` header
+    #[serde(default)]
+    event: Option<String>,
+    /// The `X-GitHub-Delivery` header
+    #[serde(default)]
+    delivery: Option<String>,
+    #[serde(default)]
+    received_at: Option<Timestamp>,
+    payload: Value,
+}
+
+/// Convert one line of webhook input into an activity event
+///
+/// A line is either `{"event": ..., "delivery": ..., "payload": {...}}` or a
+/// bare payload, whose event is then told from the objects it carries.
+/// Returns `None` for events reports have no use for, like `ping`.
+pub fn parse_delivery(line: &str, received_at: Timestamp) -> Result<Option<ActivityEvent>> {
+    let value: Value = serde_json::from_str(line).context("Invalid JSON")?;
+    let delivery = if value.get("payload").is_some() {
+        serde_json::from_value(value).context("Invalid webhook delivery")?
+    } else {
+        WebhookDelivery {
+            event: None,
+            delivery: None,
+            received_at: None,
+            payload: value,
+        }
+    };
+    let WebhookDelivery {
+        event,
+        delivery,
+        received_at: delivered_at,
+        mut payload,
+    } = delivery;
+
+    let Some(name) = event.as_deref().or_else(|| infer_event(&payload)) else {
+        bail!("Could not tell the webhook event from its payload");
+    };
+    let Some(event_type) = EVENT_TYPES
+        .iter()
+        .find(|(webhook, _)| *webhook == name)
+        .map(|(_, event_type)| *event_type)
+    else {
+        return Ok(None);
+    };
+
+    let Some(repository) = payload.get("repository") else {
+        bail!("The {} event has no repository", name);
+    };
+    let repo = ActivityRepo {
+        id: repository.get("id").and_then(Value::as_u64).unwrap_or(0),
+        name: repository
+            .get("full_name")
+            .and_then(Value::as_str)
+            .context("The repository of the event has no full_name")?
+            .to_string(),
+        url: repository
+            .get("url")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    };
+    let is_public = !repository
+        .get("private")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let actor: Author = payload
+        .get("sender")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .context("Invalid sender")?
+        .unwrap_or_else(|| Author {
+            login: "ghost".to_string(),
+            user_type: None,
+        });
+    let created_at = delivered_at
+        .or_else(|| {
+            TIME_FIELDS
+                .iter()
+                .filter_map(|field| payload.pointer(field)?.as_str()?.parse().ok())
+                .next()
+        })
+        .unwrap_or(received_at);
+    let id = match delivery {
+        Some(delivery) => delivery,
+        None => generate_cache_key(&["webhook", name, &payload.to_string()]),
+    };
+
+    if let Some(fields) = payload.as_object_mut() {
+        for field in DELIVERY_FIELDS {
+            fields.remove(*field);
+        }
+    }
+
+    Ok(Some(ActivityEvent {
+        id,
+        event_type: event_type.to_string(),
+        actor,
+        repo,
+        payload,
+        created_at,
+        is_public,
+    }))
+}
+
+/// The webhook event a bare payload belongs to
+fn infer_event(payload: &Value) -> Option<&'static str> {
+    let has = |key: &str| payload.get(key).is_some();
+    let name = if has("zen") {
+        "ping"
+    } else if has("review") && has("pull_request") {
+        "pull_request_review"
+    } else if has("comment") && has("pull_request") {
+        "pull_request_review_comment"
+    } else if has("comment") && has("issue") {
+        "issue_comment"
+    } else if has("comment") && has("discussion") {
+        "discussion_comment"
+    } else if has("pull_request") {
+        "pull_request"
+    } else if has("issue") {
+        "issues"
+    } else if has("discussion") {
+        "discussion"
+    } else if has("release") {
+        "release"
+    } else if has("forkee") {
+        "fork"
+    } else if has("commits") && has("ref") {
+        "push"
+    } else {
+        return None;
+    };
+    Some(name)
+}
+
+/// JSON lines file of ingested webhook events
+#[derive(Debug, Clone)]
+pub struct EventStore {
+    path: PathBuf,
+}
+
+impl EventStore {
+    pub fn new(path: PathBuf) -> Self {
+        EventStore { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append events to the store
+    pub fn append(&self, events: &[ActivityEvent]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(&serde_json::to_string(event).context("Failed to serialize event")?);
+            lines.push('\n');
+        }
+        crate::lock::with_lock(&self.lock_path(), || {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .with_context(|| format!("Failed to open event store {:?}", self.path))?;
+            file.write_all(lines.as_bytes())
+                .with_context(|| format!("Failed to write events to {:?}", self.path))
+        })
+    }
+
+    /// Load all events in time order, skipping lines that can't be parsed
+    ///
+    /// Redelivered webhooks are stored twice; only the first copy is kept.
+    pub fn load(&self) -> Result<Vec<ActivityEvent>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read events from {:?}", self.path))?;
+
+        let mut ids = BTreeSet::new();
+        let mut events: Vec<ActivityEvent> = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ActivityEvent>(line) {
+                Ok(event) if ids.insert(event.id.clone()) => events.push(event),
+                Ok(_) => {}
+                Err(e) => warn!("Skipping malformed event line {}: {}", i + 1, e),
+            }
+        }
+        events.sort_by_key(|event| event.created_at);
+
+        Ok(events)
+    }
+
+    /// Drop the events from before `cutoff`, returning how many were dropped
+    pub fn prune(&self, cutoff: Timestamp) -> Result<usize> {
+        crate::lock::with_lock(&self.lock_path(), || {
+            let events = self.load()?;
+            let kept: Vec<&ActivityEvent> =
+                events.iter().filter(|e| e.created_at >= cutoff).collect();
+            let dropped = events.len() - kept.len();
+            if dropped == 0 {
+                return Ok(0);
+            }
+
+            let mut lines = String::new();
+            for event in kept {
+                lines.push_str(&serde_json::to_string(event).context("Failed to serialize event")?);
+                lines.push('\n');
+            }
+            fs::write(&self.path, lines)
+                .with_context(|| format!("Failed to write events to {:?}", self.path))?;
+            Ok(dropped)
+        })
+    }
+
+    /// Build a snapshot of the `lookback_days` before `now` from the stored events
+    ///
+    /// Issues, PRs, comments, and reviews come from the event payloads, so
+    /// the report only knows the parts of a conversation the store received.
+    pub fn snapshot(
+        &self,
+        now: Timestamp,
+        lookback_days: u32,
+        user: Option<String>,
+    ) -> Result<ActivitySnapshot> {
+        let events = self.load()?;
+        Ok(snapshot_from_events(&events, now, lookback_days, user))
+    }
+
+    /// The lock file next to the store, e.g. `events.jsonl.lock`
+    fn lock_path(&self) -> PathBuf {
+        let mut path = self.path.as_os_str().to_owned();
+        path.push(".lock");
+        PathBuf::from(path)
+    }
+}
+
+type ItemKey = (String, u32);
+
+/// Bundle the events of the window with the latest state of the items they refer to
+fn snapshot_from_events(
+    events: &[ActivityEvent],
+    now: Timestamp,
+    lookback_days: u32,
+    user: Option<String>,
+) -> ActivitySnapshot {
+    let since = now - (i64::from(lookback_days) * 24).hours();
+
+    // Every stored event adds to what is known about its item, including
+    // those before the window
+    let mut issues: BTreeMap<ItemKey, Issue> = BTreeMap::new();
+    let mut comments: BTreeMap<ItemKey, BTreeMap<u64, Comment>> = BTreeMap::new();
+    let mut reviews: BTreeMap<ItemKey, BTreeMap<u64, Review>> = BTreeMap::new();
+    for event in events.iter().filter(|e| e.created_at <= now) {
+        let payload = &event.payload;
+        let (object, is_pr) = match payload.get("pull_request") {
+            Some(pr) => (pr, true),
+            None => match payload.get("issue") {
+                Some(issue) => (issue, false),
+                None => continue,
+            },
+        };
+        let Some(issue) = webhook_issue(object, is_pr) else {
+            warn!(
+                "Skipping an item of event {} that could not be read",
+                event.id
+            );
+            continue;
+        };
+        let key = (event.repo.name.clone(), issue.number);
+
+        let action = payload.get("action").and_then(Value::as_str);
+        match event.event_type.as_str() {
+            "IssueCommentEvent" => {
+                let comment = payload
+                    .get("comment")
+                    .cloned()
+                    .and_then(|c| serde_json::from_value::<Comment>(c).ok());
+                if let Some(comment) = comment {
+                    let thread = comments.entry(key.clone()).or_default();
+                    if action == Some("deleted") {
+                        thread.remove(&comment.id);
+                    } else {
+                        thread.insert(comment.id, comment);
+                    }
+                }
+            }
+            "PullRequestReviewEvent" => {
+                if let Some(review) = payload.get("review").and_then(webhook_review) {
+                    reviews
+                        .entry(key.clone())
+                        .or_default()
+                        .insert(review.id, review);
+                }
+            }
+            _ => {}
+        }
+        issues.insert(key, issue);
+    }
+
+    let window: Vec<ActivityEvent> = events
+        .iter()
+        .filter(|e| e.created_at >= since && e.created_at <= now)
+        .cloned()
+        .collect();
+    let items = issue_references(&filter_events(&window, None, None))
+        .into_iter()
+        .filter_map(|key| {
+            let issue = issues.remove(&key)?;
+            Some(SnapshotItem {
+                issue,
+                comments: comments
+                    .remove(&key)
+                    .map(|thread| thread.into_values().collect())
+                    .unwrap_or_default(),
+                reviews: reviews
+                    .remove(&key)
+                    .map(|reviews| reviews.into_values().collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    ActivitySnapshot {
+        version: SNAPSHOT_VERSION,
+        captured_at: now,
+        lookback_days,
+        user,
+        events: window,
+        items,
+        errors: Vec::new(),
+    }
+}
+
+/// Read the issue or pull request object of a webhook payload
+///
+/// Pull request objects lack the `pull_request` marker of the issues API,
+/// and in review events also the comment count and `merged`.
+fn webhook_issue(object: &Value, is_pr: bool) -> Option<Issue> {
+    let mut object = object.clone();
+    let fields = object.as_object_mut()?;
+    if is_pr {
+        fields
+            .entry("pull_request")
+            .or_insert_with(|| Value::Object(Default::default()));
+        let merged = fields.get("merged_at").is_some_and(|m| !m.is_null());
+        fields
+            .entry("merged")
+            .or_insert_with(|| Value::Bool(merged));
+    }
+    fields.entry("comments").or_insert_with(|| Value::from(0));
+    serde_json::from_value::<RestIssue>(object)
+        .ok()
+        .map(Into::into)
+}
+
+/// Read the review of a `pull_request_review` payload, whose state is lowercase
+fn webhook_review(review: &Value) -> Option<Review> {
+    let mut review = review.clone();
+    let state = review.get("state")?.as_str()?.to_uppercase();
+    review["state"] = Value::String(state);
+    serde_json::from_value(review).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn repository() -> Value {
+        json!({ "id": 7, "full_name": "owner/repo", "url": "https://api.github.com/repos/owner/repo", "private": false })
+    }
+
+    fn user(login: &str) -> Value {
+        json!({ "login": login, "type": "User" })
+    }
+
+    fn issue(number: u32, state: &str, updated_at: &str) -> Value {
+        json!({
+            "number": number,
+            "title": "Runtime hangs",
+            "body": "It hangs",
+            "state": state,
+            "user": user("bob"),
+            "created_at": "2024-03-01T10:00:00Z",
+            "updated_at": updated_at,
+            "labels": [],
+            "html_url": format!("https://github.com/owner/repo/issues/{}", number),
+            "comments": 1,
+        })
+    }
+
+    #[test]
+    fn test_parse_delivery() {
+        let now: Timestamp = "2024-03-10T00:00:00Z".parse().unwrap();
+
+        // With the headers of the delivery
+        let line = json!({
+            "event": "issue_comment",
+            "delivery": "72d3162e-cc78-11e3-81ab-4c9367dc0958",
+            "payload": {
+                "action": "created",
+                "issue": issue(12, "open", "2024-03-06T12:00:00Z"),
+                "comment": { "id": 1, "body": "Same here", "user": user("alice"),
+                             "created_at": "2024-03-06T12:00:00Z", "updated_at": "2024-03-06T12:00:00Z" },
+                "repository": repository(),
+                "sender": user("alice"),
+            }
+        });
+        let event = parse_delivery(&line.to_string(), now).unwrap().unwrap();
+        assert_eq!(event.id, "72d3162e-cc78-11e3-81ab-4c9367dc0958");
+        assert_eq!(event.event_type, "IssueCommentEvent");
+        assert_eq!(event.actor.login, "alice");
+        assert_eq!(event.repo.name, "owner/repo");
+        assert_eq!(event.created_at.to_string(), "2024-03-06T12:00:00Z");
+        assert!(event.is_public);
+        assert!(event.payload.get("repository").is_none());
+        assert_eq!(event.payload["issue"]["number"], 12);
+
+        // A bare payload, told apart by its objects
+        let line = json!({
+            "action": "submitted",
+            "review": { "id": 5, "user": user("carol"), "state": "approved",
+                        "submitted_at": "2024-03-07T09:00:00Z" },
+            "pull_request": { "number": 3 },
+            "repository": repository(),
+            "sender": user("carol"),
+        });
+        let event = parse_delivery(&line.to_string(), now).unwrap().unwrap();
+        assert_eq!(event.event_type, "PullRequestReviewEvent");
+        assert_eq!(event.created_at.to_string(), "2024-03-07T09:00:00Z");
+        let again = parse_delivery(&line.to_string(), now).unwrap().unwrap();
+        assert_eq!(event.id, again.id);
+
+        // Pings are acknowledged but not stored
+        let ping = json!({ "zen": "Keep it logically awesome.", "hook_id": 1 });
+        assert!(parse_delivery(&ping.to_string(), now).unwrap().is_none());
+
+        assert!(parse_delivery("not json", now).is_err());
+        assert!(parse_delivery(r#"{"something": "else"}"#, now).is_err());
+    }
+
+    #[test]
+    fn test_store_builds_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = EventStore::new(temp_dir.path().join("events.jsonl"));
+        let now: Timestamp = "2024-03-10T00:00:00Z".parse().unwrap();
+
+        let deliveries = [
+            // Long before the window; pruned
+            json!({ "event": "issues", "delivery": "d0", "payload": {
+                "action": "opened", "issue": issue(1, "open", "2024-01-01T00:00:00Z"),
+                "repository": repository(), "sender": user("bob") } }),
+            // Before the window, but the comment still belongs to #12
+            json!({ "event": "issue_comment", "delivery": "d1", "payload": {
+                "action": "created", "issue": issue(12, "open", "2024-03-01T12:00:00Z"),
+                "comment": { "id": 1, "body": "Same here", "user": user("alice"),
+                             "created_at": "2024-03-01T12:00:00Z", "updated_at": "2024-03-01T12:00:00Z" },
+                "repository": repository(), "sender": user("alice") } }),
+            json!({ "event": "issues", "delivery": "d2", "payload": {
+                "action": "closed", "issue": issue(12, "closed", "2024-03-08T12:00:00Z"),
+                "repository": repository(), "sender": user("bob") } }),
+            // Redelivered
+            json!({ "event": "issues", "delivery": "d2", "payload": {
+                "action": "closed", "issue": issue(12, "closed", "2024-03-08T12:00:00Z"),
+                "repository": repository(), "sender": user("bob") } }),
+            json!({ "event": "pull_request_review", "delivery": "d3", "payload": {
+                "action": "submitted",
+                "review": { "id": 5, "user": user("carol"), "state": "changes_requested",
+                            "submitted_at": "2024-03-09T09:00:00Z" },
+                "pull_request": {
+                    "number": 3, "title": "Fix hang", "body": null, "state": "open",
+                    "user": user("dave"), "created_at": "2024-03-05T10:00:00Z",
+                    "updated_at": "2024-03-09T09:00:00Z", "labels": [],
+                    "html_url": "https://github.com/owner/repo/pull/3", "merged_at": null },
+                "repository": repository(), "sender": user("carol") } }),
+        ];
+        for delivery in &deliveries {
+            let event = parse_delivery(&delivery.to_string(), now).unwrap().unwrap();
+            store.append(&[event]).unwrap();
+        }
+        assert_eq!(store.load().unwrap().len(), 4);
+        assert_eq!(store.prune(now - (30 * 24).hours()).unwrap(), 1);
+        assert_eq!(store.load().unwrap().len(), 3);
+
+        let snapshot = store.snapshot(now, 7, Some("me".to_string())).unwrap();
+        assert_eq!(snapshot.events.len(), 2);
+        assert_eq!(snapshot.captured_at, now);
+        assert_eq!(snapshot.user.as_deref(), Some("me"));
+
+        let pr = &snapshot.items[0];
+        assert_eq!(pr.issue.number, 3);
+        assert!(pr.issue.is_pull_request);
+        assert_eq!(pr.reviews.len(), 1);
+        assert_eq!(
+            pr.reviews[0].state,
+            crate::github::ReviewState::ChangesRequested
+        );
+
+        let issue = &snapshot.items[1];
+        assert_eq!(issue.issue.number, 12);
+        assert_eq!(issue.issue.state, crate::github::IssueState::Closed);
+        assert_eq!(issue.comments.len(), 1);
+        assert_eq!(issue.comments[0].author.login, "alice");
+    }
+}
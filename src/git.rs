@@ -0,0 +1,367 @@
+//! Inspection of a local git clone via the `git` CLI
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::github::RepoActivity;
+
+/// Prefixes commonly glued to issue numbers in branch names (`gh123`, `issue-45`)
+const BRANCH_NUMBER_PREFIXES: &[&str] = &["issues", "issue", "gh", "pr", "i"];
+
+/// Maximum number of unpushed commits inspected per branch
+const MAX_UNPUSHED_COMMITS: usize = 50;
+
+/// A local clone of a GitHub repository
+pub struct LocalRepo {
+    path: PathBuf,
+}
+
+/// A local branch and the work on it that only exists on this machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalBranch {
+    pub name: String,
+    pub upstream: Option<String>,
+    /// Commits not present on any remote
+    pub unpushed_commits: usize,
+    /// Issue/PR numbers referenced by the branch name or unpushed commits
+    pub issue_refs: Vec<u32>,
+}
+
+/// A local branch together with the report items it refers to
+#[derive(Debug, Clone)]
+pub struct LocalWork {
+    pub branch: LocalBranch,
+    pub items: Vec<LinkedItem>,
+}
+
+/// An issue or PR referenced from local work
+#[derive(Debug, Clone)]
+pub struct LinkedItem {
+    pub number: u32,
+    /// Title, when the item is part of the report
+    pub title: Option<String>,
+    pub url: String,
+}
+
+impl LocalRepo {
+    /// Open a local clone, failing if `path` is not inside a git work tree
+    pub fn open(path: &Path) -> Result<Self> {
+        let repo = LocalRepo {
+            path: path.to_path_buf(),
+        };
+        repo.git(&["rev-parse", "--is-inside-work-tree"])
+            .with_context(|| format!("{} is not a git repository", path.display()))?;
+        Ok(repo)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The `owner/repo` name of the repository this clone tracks on `host`
+    ///
+    /// Prefers the `upstream` remote (fork workflows) and falls back to `origin`.
+    pub fn github_repo(&self, host: &str) -> Option<String> {
+        ["upstream", "origin"].iter().find_map(|remote| {
+            let url = self.git(&["remote", "get-url", remote]).ok()?;
+            parse_github_remote(url.trim(), host)
+        })
+    }
+
+    /// Local branches that reference issues or carry unpushed commits
+    pub fn branches(&self) -> Result<Vec<LocalBranch>> {
+        let output = self.git(&[
+            "for-each-ref",
+            "--format=%(refname:short)%09%(upstream:short)",
+            "refs/heads",
+        ])?;
+
+        let mut branches = Vec::new();
+        for (name, upstream) in parse_branch_list(&output) {
+            let max_count = format!("--max-count={}", MAX_UNPUSHED_COMMITS);
+            let subjects = self.git(&[
+                "log",
+                "--format=%s",
+                &max_count,
+                &name,
+                "--not",
+                "--remotes",
+            ])?;
+            let subjects: Vec<&str> = subjects.lines().filter(|l| !l.is_empty()).collect();
+
+            let mut issue_refs = issue_numbers_in_branch(&name);
+            for subject in &subjects {
+                issue_refs.extend(issue_numbers_in_text(subject));
+            }
+            issue_refs.sort_unstable();
+            issue_refs.dedup();
+
+            if issue_refs.is_empty() && subjects.is_empty() {
+                continue;
+            }
+
+            branches.push(LocalBranch {
+                name,
+                upstream,
+                unpushed_commits: subjects.len(),
+                issue_refs,
+            });
+        }
+
+        Ok(branches)
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(args)
+            .output()
+            .context("Failed to run git. Is git installed?")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("git command failed: {}", stderr.trim()));
+        }
+
+        String::from_utf8(output.stdout).context("Invalid UTF-8 in git output")
+    }
+}
+
+/// Connect local branches to the items in a report
+///
+/// Items found in `activities` for `repo_name` carry their title; other
+/// referenced numbers still get a link on `host` so the thread is one click
+/// away.
+pub fn correlate_local_work(
+    repo_name: Option<&str>,
+    branches: Vec<LocalBranch>,
+    activities: &BTreeMap<String, RepoActivity>,
+    host: &str,
+) -> Vec<LocalWork> {
+    let activity = repo_name.and_then(|name| activities.get(name));
+
+    branches
+        .into_iter()
+        .map(|branch| {
+            let items = match repo_name {
+                Some(repo) => branch
+                    .issue_refs
+                    .iter()
+                    .map(|&number| {
+                        let found = activity.and_then(|a| {
+                            a.new_issues
+                                .iter()
+                                .chain(&a.updated_issues)
+                                .chain(&a.closed_issues)
+                                .chain(&a.new_prs)
+                                .chain(&a.updated_prs)
+                                .chain(&a.merged_prs)
+                                .find(|i| i.number == number)
+                        });
+                        LinkedItem {
+                            number,
+                            title: found.map(|i| i.title.clone()),
+                            url: found.map(|i| i.url.clone()).unwrap_or_else(|| {
+                                format!("https://{}/{}/issues/{}", host, repo, number)
+                            }),
+                        }
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+            LocalWork { branch, items }
+        })
+        .collect()
+}
+
+/// Parse `owner/repo` from a remote URL (https or ssh) of `host`
+fn parse_github_remote(url: &str, host: &str) -> Option<String> {
+    let rest = ["https://", "http://", "ssh://git@"]
+        .iter()
+        .find_map(|scheme| strip_host(url.strip_prefix(scheme)?, host)?.strip_prefix('/'))
+        .or_else(|| strip_host(url.strip_prefix("git@")?, host)?.strip_prefix(':'))?;
+    let rest = rest.trim_end_matches('/');
+    let rest = rest.strip_suffix(".git").unwrap_or(rest);
+
+    let mut parts = rest.split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    Some(format!("{}/{}", owner, repo))
+}
+
+/// `rest` after a leading `host`, which matches in any case
+fn strip_host<'a>(rest: &'a str, host: &str) -> Option<&'a str> {
+    let (prefix, rest) = rest.split_at_checked(host.len())?;
+    prefix.eq_ignore_ascii_case(host).then_some(rest)
+}
+
+/// Parse `git for-each-ref` output into (branch, upstream) pairs
+fn parse_branch_list(output: &str) -> Vec<(String, Option<String>)> {
+    output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.splitn(2, '\t');
+            let name = fields.next().unwrap_or_default().trim().to_string();
+            let upstream = fields
+                .next()
+                .map(str::trim)
+                .filter(|u| !u.is_empty())
+                .map(str::to_string);
+            (name, upstream)
+        })
+        .collect()
+}
+
+/// Issue numbers in a branch name such as `fix/1234-panic` or `gh-42`
+///
+/// Dots are not separators so version-like names (`release-1.2`) don't match.
+fn issue_numbers_in_branch(name: &str) -> Vec<u32> {
+    name.split(['/', '-', '_'])
+        .filter_map(|token| {
+            let token = token.to_lowercase();
+            let digits = BRANCH_NUMBER_PREFIXES
+                .iter()
+                .find_map(|p| token.strip_prefix(p))
+                .filter(|rest| !rest.is_empty())
+                .unwrap_or(&token);
+            if digits.chars().all(|c| c.is_ascii_digit()) {
+                digits.parse().ok().filter(|n| *n > 0)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// `#123` style references in free text, ignoring cross-repo `owner/repo#123`
+fn issue_numbers_in_text(text: &str) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    let bytes = text.as_bytes();
+
+    for (i, _) in text.match_indices('#') {
+        if i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'/') {
+            continue;
+        }
+        let digits: String = text[i + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if let Ok(number) = digits.parse::<u32>() {
+            if number > 0 {
+                numbers.push(number);
+            }
+        }
+    }
+
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+
+    #[test]
+    fn test_parse_github_remote() {
+        assert_eq!(
+            parse_github_remote("https://github.com/tokio-rs/tokio.git", "github.com"),
+            Some("tokio-rs/tokio".to_string())
+        );
+        assert_eq!(
+            parse_github_remote("git@github.com:tokio-rs/tokio.git", "github.com"),
+            Some("tokio-rs/tokio".to_string())
+        );
+        assert_eq!(
+            parse_github_remote("ssh://git@github.com/tokio-rs/mio", "github.com"),
+            Some("tokio-rs/mio".to_string())
+        );
+        assert_eq!(
+            parse_github_remote(
+                "git@GitHub.example.com:tokio-rs/tokio.git",
+                "github.example.com"
+            ),
+            Some("tokio-rs/tokio".to_string())
+        );
+        assert_eq!(
+            parse_github_remote(
+                "https://github.com/tokio-rs/tokio.git",
+                "github.example.com"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_github_remote("https://github.com.evil.com/a/b.git", "github.com"),
+            None
+        );
+        assert_eq!(
+            parse_github_remote("https://gitlab.com/a/b.git", "github.com"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_branch_list() {
+        let output = "main\torigin/main\nfix-123\t\n";
+        assert_eq!(
+            parse_branch_list(output),
+            vec![
+                ("main".to_string(), Some("origin/main".to_string())),
+                ("fix-123".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_issue_numbers_in_branch() {
+        assert_eq!(
+            issue_numbers_in_branch("fix/1234-scheduler-panic"),
+            vec![1234]
+        );
+        assert_eq!(issue_numbers_in_branch("gh-42"), vec![42]);
+        assert_eq!(issue_numbers_in_branch("issue123"), vec![123]);
+        assert_eq!(issue_numbers_in_branch("release-1.2"), Vec::<u32>::new());
+        assert_eq!(issue_numbers_in_branch("main"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_issue_numbers_in_text() {
+        assert_eq!(issue_numbers_in_text("Fix panic (#123)"), vec![123]);
+        assert_eq!(issue_numbers_in_text("Closes #1, refs #22"), vec![1, 22]);
+        assert_eq!(
+            issue_numbers_in_text("See tokio-rs/mio#55 and C#"),
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    fn test_correlate_local_work() {
+        let mut activity = RepoActivity::default();
+        activity
+            .updated_prs
+            .push(create_test_issue(12, "Scheduler fix", true));
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), activity);
+
+        let branches = vec![LocalBranch {
+            name: "fix-12".to_string(),
+            upstream: None,
+            unpushed_commits: 2,
+            issue_refs: vec![12, 99],
+        }];
+
+        let work = correlate_local_work(Some("test/repo"), branches, &activities, "github.com");
+        assert_eq!(work.len(), 1);
+        assert_eq!(work[0].items.len(), 2);
+        assert_eq!(work[0].items[0].title.as_deref(), Some("Scheduler fix"));
+        assert_eq!(work[0].items[0].url, "https://github.com/test/repo/pull/12");
+        assert_eq!(work[0].items[1].title, None);
+        assert_eq!(
+            work[0].items[1].url,
+            "https://github.com/test/repo/issues/99"
+        );
+    }
+}
@@ -1,11 +1,327 @@
+use crate::cache::{generate_cache_key, CacheManager};
+use crate::config::GitHubConfig;
 use crate::github::models::*;
+use crate::github::rate_limit::{RateLimit, RateLimiter};
+use crate::github::ProjectReference;
 use anyhow::{anyhow, Context, Result};
 use jiff::{Timestamp, ToSpan};
 use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info_span, warn};
+
+/// Messages gh prints when its token is missing, revoked, or expired
+const AUTH_FAILURE_MARKERS: &[&str] = &[
+    "http 401",
+    "bad credentials",
+    "gh auth login",
+    "not logged in",
+    "authentication required",
+    "token has expired",
+];
+
+/// Messages gh prints for failures that may pass when tried again
+const TRANSIENT_FAILURE_MARKERS: &[&str] = &[
+    "rate limit",
+    "abuse",
+    "http 500",
+    "http 502",
+    "http 503",
+    "http 504",
+    "bad gateway",
+    "service unavailable",
+    "gateway timeout",
+];
+
+/// Longest wait between two attempts, before jitter
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Review threads of a pull request, with the first comments of each
+const REVIEW_THREADS_QUERY: &str = "
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          isResolved
+          isOutdated
+          path
+          line
+          comments(first: 20) {
+            totalCount
+            nodes { author { login } body }
+          }
+        }
+      }
+    }
+  }
+}";
+
+/// The most recently updated discussions of a repository, with their latest comments
+const DISCUSSIONS_QUERY: &str = "
+query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    discussions(first: 50, orderBy: { field: UPDATED_AT, direction: DESC }) {
+      nodes {
+        number
+        title
+        body
+        url
+        author { login }
+        category { name }
+        isAnswered
+        createdAt
+        updatedAt
+        comments(last: 10) {
+          totalCount
+          nodes { author { login } body createdAt }
+        }
+      }
+    }
+  }
+}";
+
+/// The open pull requests of a repository with their review requests, and
+/// when each reviewer was last asked
+const REVIEW_QUEUE_QUERY: &str = "
+query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    viewerPermission
+    pullRequests(states: OPEN, first: 50, orderBy: { field: CREATED_AT, direction: ASC }) {
+      nodes {
+        number
+        title
+        url
+        author { login }
+        isDraft
+        createdAt
+        reviewRequests(first: 20) {
+          nodes {
+            requestedReviewer { ... on User { login } ... on Bot { login } ... on Team { combinedSlug } }
+          }
+        }
+        timelineItems(itemTypes: [REVIEW_REQUESTED_EVENT], last: 20) {
+          nodes {
+            ... on ReviewRequestedEvent {
+              createdAt
+              requestedReviewer { ... on User { login } ... on Bot { login } ... on Team { combinedSlug } }
+            }
+          }
+        }
+      }
+    }
+  }
+}";
+
+/// One page of a project board's items, with the options of its status field
+///
+/// `OWNER_TYPE` is replaced with `organization` or `user`.
+const PROJECT_QUERY: &str = "
+query($login: String!, $number: Int!, $field: String!, $cursor: String) {
+  owner: OWNER_TYPE(login: $login) {
+    projectV2(number: $number) {
+      title
+      url
+      field(name: $field) {
+        ... on ProjectV2SingleSelectField { options { name } }
+      }
+      items(first: 100, after: $cursor) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          fieldValueByName(name: $field) {
+            ... on ProjectV2ItemFieldSingleSelectValue { name }
+          }
+          content {
+            __typename
+            ... on Issue { number title url state repository { nameWithOwner } }
+            ... on PullRequest { number title url state repository { nameWithOwner } }
+            ... on DraftIssue { title }
+          }
+        }
+      }
+    }
+  }
+}";
+
+/// Most pages of items fetched per project board, 100 items each
+const MAX_PROJECT_PAGES: usize = 10;
+
+/// An issue or PR with its comments
+pub type IssueWithComments = (Issue, Vec<Comment>);
+
+/// Issues and PRs fetched by a single GraphQL query
+const ISSUE_BATCH_SIZE: usize = 50;
+
+/// Fields shared by issues and PRs in a batch query
+///
+/// Only the last 100 comments are included; longer threads are fetched over REST.
+const ISSUE_BATCH_FIELDS: &str = "
+      number title body state url createdAt updatedAt authorAssociation
+      author { login __typename }
+      labels(first: 50) { nodes { name color description } }
+      assignees(first: 20) { nodes { login } }
+      milestone { title dueOn }
+      comments(last: 100) {
+        totalCount
+        nodes { databaseId body author { login __typename } createdAt updatedAt }
+      }";
+
+/// Build a query fetching each of `refs` under the aliases `i0`, `i1`, ...
+fn issue_batch_query(refs: &[(String, u32)]) -> Result<String> {
+    let is_name = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    let mut query = String::from("query {");
+    for (i, (repo, number)) in refs.iter().enumerate() {
+        let (owner, name) = repo
+            .split_once('/')
+            .filter(|(owner, name)| is_name(owner) && is_name(name))
+            .ok_or_else(|| anyhow!("Invalid repository name: {}", repo))?;
+        query.push_str(&format!(
+            "
+  i{i}: repository(owner: \"{owner}\", name: \"{name}\") {{
+    issueOrPullRequest(number: {number}) {{
+      __typename
+      ... on Issue {{{ISSUE_BATCH_FIELDS}
+      }}
+      ... on PullRequest {{{ISSUE_BATCH_FIELDS}
+        reviewRequests(first: 20) {{ nodes {{ requestedReviewer {{ ... on User {{ login }} }} }} }}
+      }}
+    }}
+  }}"
+        ));
+    }
+    query.push_str("\n}");
+    Ok(query)
+}
+
+/// Error for GitHub rejecting the gh token
+///
+/// Once a call fails this way, later calls on the same client fail with it
+/// right away instead of running gh, so a run stops with one clear error
+/// rather than a warning per repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthExpired;
+
+impl fmt::Display for AuthExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GitHub authentication expired — run gh auth login")
+    }
+}
+
+impl std::error::Error for AuthExpired {}
+
+/// Whether an error, or one of its causes, is [`AuthExpired`]
+pub fn is_auth_expired(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<AuthExpired>())
+}
+
+/// Error for a gh call that an offline client refused to make
+///
+/// Reports handle it like any other failed fetch, leaving the data out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Offline;
+
+impl fmt::Display for Offline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not in the cache (offline)")
+    }
+}
+
+impl std::error::Error for Offline {}
+
+/// What a gh call fetches, with issue and PR numbers folded into `{n}`
+///
+/// Used to list what an offline run had to go without, e.g.
+/// `repos/tokio-rs/tokio/issues/{n}/comments`.
+fn describe_gh_call(args: &[&str]) -> String {
+    match args {
+        ["api", "graphql", ..] => "GraphQL queries".to_string(),
+        ["api", endpoint, ..] => {
+            let path = endpoint.split('?').next().unwrap_or(endpoint);
+            path.split('/')
+                .map(|segment| {
+                    if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                        "{n}"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        }
+        _ => format!("gh {}", args.join(" ")),
+    }
+}
+
+/// Whether gh's stderr says the token was rejected
+fn is_auth_failure(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    AUTH_FAILURE_MARKERS.iter().any(|m| stderr.contains(m))
+}
+
+/// Whether gh's stderr says the call hit a rate limit or a server error
+fn is_transient_failure(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    TRANSIENT_FAILURE_MARKERS.iter().any(|m| stderr.contains(m))
+}
+
+/// How gh calls are retried after a transient failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each one after it
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &GitHubConfig) -> Self {
+        RetryPolicy {
+            max_attempts: config.max_attempts.max(1),
+            base_delay: Duration::from_millis(config.retry_delay_ms),
+        }
+    }
+
+    /// Delay before retrying after failed attempt `attempt` (1-based)
+    ///
+    /// Half of the backoff is fixed and `jitter` (0.0 to 1.0) scales the
+    /// other half, so parallel fetches don't all retry at the same moment.
+    fn delay(&self, attempt: u32, jitter: f64) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(MAX_RETRY_DELAY);
+        backoff / 2 + (backoff / 2).mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::from_config(&GitHubConfig::default())
+    }
+}
+
+/// A value between 0.0 and 1.0 that differs from call to call
+fn jitter() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or_default();
+    f64::from(nanos) / 1e9
+}
 
 /// GitHub client abstraction
+// The mock only exists in tests, where its size does not matter
+#[cfg_attr(test, allow(clippy::large_enum_variant))]
 pub enum GitHubClient {
     Real(RealGitHub),
     #[cfg(test)]
@@ -18,6 +334,136 @@ impl GitHubClient {
         Ok(GitHubClient::Real(RealGitHub::new()?))
     }
 
+    /// A client that never runs gh, answering only from its activity cache
+    ///
+    /// Every other call fails with [`Offline`] and is listed by
+    /// [`GitHubClient::unavailable`].
+    pub fn offline() -> Self {
+        GitHubClient::Real(RealGitHub {
+            offline: true,
+            ..RealGitHub::with_gh_path(PathBuf::from("gh"))
+        })
+    }
+
+    /// Whether the client was created with [`GitHubClient::offline`]
+    pub fn is_offline(&self) -> bool {
+        match self {
+            GitHubClient::Real(client) => client.offline,
+            #[cfg(test)]
+            GitHubClient::Mock(_) => false,
+        }
+    }
+
+    /// Fail with [`Offline`] when offline, for gh calls made outside the client
+    ///
+    /// The call is listed by [`GitHubClient::unavailable`] like the client's own.
+    pub fn check_online(&self, args: &[&str]) -> Result<()> {
+        match self {
+            GitHubClient::Real(client) => client.check_online(args),
+            #[cfg(test)]
+            GitHubClient::Mock(_) => Ok(()),
+        }
+    }
+
+    /// What an offline client could not fetch, with the number of calls for each
+    pub fn unavailable(&self) -> Vec<(String, usize)> {
+        match self {
+            GitHubClient::Real(client) => client
+                .unavailable
+                .lock()
+                .map(|calls| calls.clone().into_iter().collect())
+                .unwrap_or_default(),
+            #[cfg(test)]
+            GitHubClient::Mock(_) => Vec::new(),
+        }
+    }
+
+    /// Retry gh calls that fail on rate limits or server errors with `retry`
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        match &mut self {
+            GitHubClient::Real(client) => client.retry = retry,
+            #[cfg(test)]
+            GitHubClient::Mock(_) => {}
+        }
+        self
+    }
+
+    /// Send gh calls to a GitHub Enterprise Server `host` instead of github.com
+    pub fn with_host(mut self, host: Option<&str>) -> Self {
+        match &mut self {
+            GitHubClient::Real(client) => client.host = host.map(str::to_string),
+            #[cfg(test)]
+            GitHubClient::Mock(_) => {}
+        }
+        self
+    }
+
+    /// Reuse activity feeds fetched within the TTL of `cache`
+    ///
+    /// Without a cache, every call pages through the events API again.
+    pub fn with_activity_cache(mut self, cache: Option<CacheManager>) -> Self {
+        match &mut self {
+            GitHubClient::Real(client) => client.activity_cache = cache,
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.activity_cache = cache,
+        }
+        self
+    }
+
+    fn activity_cache(&self) -> Option<&CacheManager> {
+        match self {
+            GitHubClient::Real(client) => client.activity_cache.as_ref(),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.activity_cache.as_ref(),
+        }
+    }
+
+    /// The host gh calls go to
+    fn host(&self) -> &str {
+        match self {
+            GitHubClient::Real(client) => client.host.as_deref().unwrap_or("github.com"),
+            #[cfg(test)]
+            GitHubClient::Mock(_) => "github.com",
+        }
+    }
+
+    /// Skip optional fetches once fewer than `threshold` API requests are left
+    pub fn with_rate_limit_threshold(mut self, threshold: u32) -> Self {
+        self.rate_limiter_mut().set_threshold(threshold);
+        self
+    }
+
+    fn rate_limiter(&self) -> &RateLimiter {
+        match self {
+            GitHubClient::Real(client) => &client.rate_limiter,
+            #[cfg(test)]
+            GitHubClient::Mock(client) => &client.rate_limiter,
+        }
+    }
+
+    fn rate_limiter_mut(&mut self) -> &mut RateLimiter {
+        match self {
+            GitHubClient::Real(client) => &mut client.rate_limiter,
+            #[cfg(test)]
+            GitHubClient::Mock(client) => &mut client.rate_limiter,
+        }
+    }
+
+    /// The current REST API quota, checked now when possible
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        match self {
+            GitHubClient::Real(client) => client.check_rate_limit(),
+            #[cfg(test)]
+            GitHubClient::Mock(_) => {}
+        }
+        self.rate_limiter().latest()
+    }
+
+    /// Whether the API quota leaves room for fetches the report can do without
+    pub fn allows_optional_fetches(&self) -> bool {
+        self.rate_limiter().allows_optional()
+    }
+
     /// Create a mock client for testing
     #[cfg(test)]
     pub fn mock() -> Self {
@@ -26,6 +472,7 @@ impl GitHubClient {
 
     /// Fetch issues and PRs for a repository
     pub fn fetch_issues(&self, repo: &str, since: Option<Timestamp>) -> Result<Vec<Issue>> {
+        let _span = info_span!("issue_fetch", repo).entered();
         match self {
             GitHubClient::Real(client) => client.fetch_issues(repo, since),
             #[cfg(test)]
@@ -40,6 +487,7 @@ impl GitHubClient {
         issue_number: u32,
         since: Option<Timestamp>,
     ) -> Result<Vec<Comment>> {
+        let _span = info_span!("issue_fetch", repo).entered();
         match self {
             GitHubClient::Real(client) => client.fetch_comments(repo, issue_number, since),
             #[cfg(test)]
@@ -56,22 +504,50 @@ impl GitHubClient {
         }
     }
 
-    /// Search for mentions of the current user
-    pub fn fetch_mentions(&self, since: Timestamp) -> Result<Vec<Issue>> {
+    /// Fetch all repositories of an organization
+    pub fn fetch_org_repositories(&self, org: &str) -> Result<Vec<Repository>> {
+        let _span = info_span!("activity_fetch").entered();
         match self {
-            GitHubClient::Real(client) => client.fetch_mentions(since),
+            GitHubClient::Real(client) => client.fetch_org_repositories(org),
             #[cfg(test)]
-            GitHubClient::Mock(client) => client.fetch_mentions(since),
+            GitHubClient::Mock(client) => client.fetch_org_repositories(org),
+        }
+    }
+
+    /// Search for issues and PRs updated since `since` that mention `target`,
+    /// a user (`carllerche`) or a team (`@tokio-rs/maintainers`)
+    pub fn fetch_mentions(&self, target: &str, since: Timestamp) -> Result<Vec<Issue>> {
+        let _span = info_span!("activity_fetch").entered();
+        match self {
+            GitHubClient::Real(client) => client.search_issues(&mention_query(target, since)),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_mentions(target, since),
         }
     }
 
     /// Get current authenticated user
+    ///
+    /// With an activity cache, the login is remembered there for offline runs.
     pub fn get_current_user(&self) -> Result<String> {
-        match self {
+        let key = generate_cache_key(&["current_user", self.host()]);
+        let cache = self.activity_cache();
+        if let (true, Some(cache)) = (self.is_offline(), cache) {
+            if let Ok(Some(login)) = cache.get_github_response(&key) {
+                return String::from_utf8(login).context("Invalid cached login");
+            }
+        }
+
+        let login = match self {
             GitHubClient::Real(client) => client.get_current_user(),
             #[cfg(test)]
             GitHubClient::Mock(client) => client.get_current_user(),
+        }?;
+        if let Some(cache) = cache {
+            if let Err(e) = cache.cache_github_response(&key, login.as_bytes()) {
+                warn!("Failed to cache the current user: {}", e);
+            }
         }
+        Ok(login)
     }
 
     /// Fetch a single issue or PR with its comments
@@ -80,6 +556,7 @@ impl GitHubClient {
         repo: &str,
         issue_number: u32,
     ) -> Result<(Issue, Vec<Comment>)> {
+        let _span = info_span!("issue_fetch", repo).entered();
         match self {
             GitHubClient::Real(client) => client.fetch_single_issue(repo, issue_number),
             #[cfg(test)]
@@ -87,8 +564,122 @@ impl GitHubClient {
         }
     }
 
+    /// Fetch the issues and PRs of a project board with their status
+    ///
+    /// `status_field` names the single select field the board's columns
+    /// are made of, usually "Status".
+    pub fn fetch_project(
+        &self,
+        project: &ProjectReference,
+        status_field: &str,
+    ) -> Result<ProjectBoard> {
+        let _span = info_span!("project_fetch", owner = %project.owner).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_project(project, status_field),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_project(project, status_field),
+        }
+    }
+
+    /// Fetch up to [`ISSUE_BATCH_SIZE`] issues or PRs with one GraphQL query
+    ///
+    /// Results are in the order of `refs`, with `None` for items the batch
+    /// couldn't return in full: missing ones, and threads longer than the
+    /// comments a batch includes.
+    pub fn fetch_issue_batch(
+        &self,
+        refs: &[(String, u32)],
+    ) -> Result<Vec<Option<IssueWithComments>>> {
+        let _span = info_span!("issue_batch_fetch", items = refs.len()).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_issue_batch(refs),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_issue_batch(refs),
+        }
+    }
+
+    /// Fetch several issues or PRs with their comments
+    ///
+    /// Items are fetched in GraphQL batches of [`ISSUE_BATCH_SIZE`]. Whatever
+    /// a batch doesn't return, including every item of a batch that fails, is
+    /// fetched over REST, `concurrency` at a time. Results are in the order of
+    /// `refs`.
+    pub fn fetch_single_issues(
+        &self,
+        refs: &[(String, u32)],
+        concurrency: usize,
+    ) -> Vec<Result<(Issue, Vec<Comment>)>> {
+        let mut fetched: Vec<Option<IssueWithComments>> = Vec::with_capacity(refs.len());
+        for chunk in refs.chunks(ISSUE_BATCH_SIZE) {
+            match self.fetch_issue_batch(chunk) {
+                Ok(batch) => fetched.extend(batch),
+                Err(e) => {
+                    if !is_auth_expired(&e) {
+                        warn!("GraphQL batch fetch failed, falling back to REST: {:#}", e);
+                    }
+                    fetched.extend(chunk.iter().map(|_| None));
+                }
+            }
+        }
+
+        let missing: Vec<(String, u32)> = refs
+            .iter()
+            .zip(&fetched)
+            .filter(|(_, item)| item.is_none())
+            .map(|(item_ref, _)| item_ref.clone())
+            .collect();
+        let mut rest = self.fetch_each_issue(&missing, concurrency).into_iter();
+
+        fetched
+            .into_iter()
+            .map(|item| match item {
+                Some(item) => Ok(item),
+                None => rest.next().expect("one REST result per missing item"),
+            })
+            .collect()
+    }
+
+    /// Fetch each issue or PR over REST, `concurrency` at a time
+    ///
+    /// Results are in the order of `refs`. Once authentication expires, the
+    /// fetches still queued fail right away instead of each calling gh.
+    fn fetch_each_issue(
+        &self,
+        refs: &[(String, u32)],
+        concurrency: usize,
+    ) -> Vec<Result<(Issue, Vec<Comment>)>> {
+        let next = AtomicUsize::new(0);
+        let workers = concurrency.clamp(1, refs.len().max(1));
+
+        let mut results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut fetched = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some((repo, number)) = refs.get(i) else {
+                                break;
+                            };
+                            fetched.push((i, self.fetch_single_issue(repo, *number)));
+                        }
+                        fetched
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("issue fetch worker panicked"))
+                .collect()
+        });
+
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
     /// Fetch PR diff/file changes for a pull request
     pub fn fetch_pr_diff(&self, repo: &str, pr_number: u32) -> Result<PrDiff> {
+        let _span = info_span!("issue_fetch", repo).entered();
         match self {
             GitHubClient::Real(client) => client.fetch_pr_diff(repo, pr_number),
             #[cfg(test)]
@@ -96,19 +687,255 @@ impl GitHubClient {
         }
     }
 
+    /// Fetch the commit a pull request currently proposes to merge
+    pub fn fetch_pr_head_sha(&self, repo: &str, pr_number: u32) -> Result<Option<String>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_pr_head_sha(repo, pr_number),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_pr_head_sha(repo, pr_number),
+        }
+    }
+
+    /// Fetch reviews for a pull request
+    pub fn fetch_pr_reviews(&self, repo: &str, pr_number: u32) -> Result<Vec<Review>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_pr_reviews(repo, pr_number),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_pr_reviews(repo, pr_number),
+        }
+    }
+
+    /// Fetch the inline review comments of a pull request
+    pub fn fetch_pr_review_comments(
+        &self,
+        repo: &str,
+        pr_number: u32,
+    ) -> Result<Vec<ReviewComment>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_pr_review_comments(repo, pr_number),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_pr_review_comments(repo, pr_number),
+        }
+    }
+
+    /// Fetch the review threads of a pull request, with whether each is resolved
+    pub fn fetch_review_threads(&self, repo: &str, pr_number: u32) -> Result<Vec<ReviewThread>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_review_threads(repo, pr_number),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_review_threads(repo, pr_number),
+        }
+    }
+
+    /// Fetch failed workflow runs for the head commit of a pull request
+    pub fn fetch_failed_runs(&self, repo: &str, pr_number: u32) -> Result<Vec<WorkflowRun>> {
+        let _span = info_span!("ci_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_failed_runs(repo, pr_number),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_failed_runs(repo, pr_number),
+        }
+    }
+
+    /// Fetch the check runs on the head commit of a pull request
+    pub fn fetch_pr_checks(&self, repo: &str, pr_number: u32) -> Result<Vec<CheckRun>> {
+        let _span = info_span!("ci_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_pr_checks(repo, pr_number),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_pr_checks(repo, pr_number),
+        }
+    }
+
+    /// Fetch the logs of the failed jobs in a workflow run
+    pub fn fetch_failed_run_log(&self, repo: &str, run_id: u64) -> Result<String> {
+        let _span = info_span!("ci_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_failed_run_log(repo, run_id),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_failed_run_log(repo, run_id),
+        }
+    }
+
+    /// Fetch a release by its id
+    pub fn fetch_release(&self, repo: &str, release_id: u64) -> Result<Release> {
+        let _span = info_span!("release_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_release(repo, release_id),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_release(repo, release_id),
+        }
+    }
+
+    /// Fetch the discussions of a repository updated since `since`
+    pub fn fetch_discussions(&self, repo: &str, since: Timestamp) -> Result<Vec<Discussion>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_discussions(repo, since),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_discussions(repo, since),
+        }
+    }
+
+    /// Fetch the open pull requests of a repository and the reviews requested on them
+    pub fn fetch_review_queue(&self, repo: &str) -> Result<ReviewQueue> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_review_queue(repo),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_review_queue(repo),
+        }
+    }
+
+    /// Fetch the open milestones of a repository, soonest due first
+    pub fn fetch_milestones(&self, repo: &str) -> Result<Vec<RepoMilestone>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_milestones(repo),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_milestones(repo),
+        }
+    }
+
+    /// Fetch the labels defined in a repository
+    pub fn fetch_labels(&self, repo: &str) -> Result<Vec<Label>> {
+        let _span = info_span!("issue_fetch", repo).entered();
+        match self {
+            GitHubClient::Real(client) => client.fetch_labels(repo),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_labels(repo),
+        }
+    }
+
+    /// Add labels to an issue or PR, keeping the ones it already has
+    pub fn add_labels(&self, repo: &str, number: u32, labels: &[String]) -> Result<()> {
+        match self {
+            GitHubClient::Real(client) => client.add_labels(repo, number, labels),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.add_labels(repo, number, labels),
+        }
+    }
+
     /// Fetch user's activity events
     pub fn fetch_activity(&self, days: u32) -> Result<Vec<ActivityEvent>> {
+        Ok(self.fetch_activity_feed(days)?.events)
+    }
+
+    /// Fetch user's activity events, noting whether the feed was cut short
+    pub fn fetch_activity_feed(&self, days: u32) -> Result<ActivityFeed> {
+        let _span = info_span!("activity_fetch").entered();
+        let fetch = || match self {
+            GitHubClient::Real(client) => client.fetch_activity_feed(days),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_activity_feed(days),
+        };
+        if self.activity_cache().is_none() {
+            return fetch();
+        }
+        let username = self.get_current_user()?;
+        self.cached_feed("received_events", &username, days, fetch)
+    }
+
+    /// Fetch the events performed by `login`, rather than those they receive
+    pub fn fetch_user_events(&self, login: &str, days: u32) -> Result<ActivityFeed> {
+        let _span = info_span!("activity_fetch").entered();
+        self.cached_feed("user_events", login, days, || match self {
+            GitHubClient::Real(client) => client.fetch_user_events(login, days),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.fetch_user_events(login, days),
+        })
+    }
+
+    /// A feed from the activity cache, or from `fetch` when it has none
+    /// within the TTL
+    ///
+    /// Feeds are keyed by host, user, and lookback, so a wider window is
+    /// never answered from a narrower one.
+    fn cached_feed(
+        &self,
+        kind: &str,
+        login: &str,
+        days: u32,
+        fetch: impl FnOnce() -> Result<ActivityFeed>,
+    ) -> Result<ActivityFeed> {
+        let Some(cache) = self.activity_cache() else {
+            return fetch();
+        };
+        let key = generate_cache_key(&[
+            "activity",
+            kind,
+            self.host(),
+            &login.to_lowercase(),
+            &days.to_string(),
+        ]);
+        match cache.get_activity_feed(&key) {
+            Ok(Some(feed)) => {
+                debug!("Using the cached activity feed of {}", login);
+                return Ok(feed);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to read the cached activity feed: {}", e),
+        }
+
+        let feed = fetch()?;
+        if let Err(e) = cache.cache_activity_feed(&key, &feed) {
+            warn!("Failed to cache the activity feed: {}", e);
+        }
+        Ok(feed)
+    }
+
+    /// Search issues and PRs with a GitHub search query
+    pub fn search_issues(&self, query: &str) -> Result<Vec<Issue>> {
+        let _span = info_span!("activity_fetch").entered();
+        match self {
+            GitHubClient::Real(client) => client.search_issues(query),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.search_issues(query),
+        }
+    }
+
+    /// Fetch notifications updated since `since`, read or unread
+    pub fn fetch_notifications(&self, since: Timestamp) -> Result<Vec<Notification>> {
+        let _span = info_span!("notification_fetch").entered();
         match self {
-            GitHubClient::Real(client) => client.fetch_activity(days),
+            GitHubClient::Real(client) => client.fetch_notifications(since),
             #[cfg(test)]
-            GitHubClient::Mock(client) => client.fetch_activity(days),
+            GitHubClient::Mock(client) => client.fetch_notifications(since),
         }
     }
+
+    /// Fail with [`AuthExpired`] if GitHub rejected the token during this run
+    pub fn check_auth(&self) -> Result<()> {
+        let expired = match self {
+            GitHubClient::Real(client) => client.auth_expired.load(Ordering::Relaxed),
+            #[cfg(test)]
+            GitHubClient::Mock(client) => client.auth_expired,
+        };
+        if expired {
+            return Err(AuthExpired.into());
+        }
+        Ok(())
+    }
 }
 
 /// Real GitHub client using gh CLI
 pub struct RealGitHub {
     gh_path: PathBuf,
+    /// Set on the first authentication failure
+    auth_expired: AtomicBool,
+    retry: RetryPolicy,
+    rate_limiter: RateLimiter,
+    /// GitHub Enterprise Server host, passed to gh as `GH_HOST`
+    host: Option<String>,
+    activity_cache: Option<CacheManager>,
+    /// Refuse every gh call, see [`GitHubClient::offline`]
+    offline: bool,
+    /// Calls refused while offline, by [`describe_gh_call`]
+    unavailable: Mutex<BTreeMap<String, usize>>,
 }
 
 impl RealGitHub {
@@ -120,25 +947,133 @@ impl RealGitHub {
         // Verify version
         crate::github::check_gh_version()?;
 
-        Ok(RealGitHub { gh_path })
+        Ok(RealGitHub::with_gh_path(gh_path))
+    }
+
+    fn with_gh_path(gh_path: PathBuf) -> Self {
+        RealGitHub {
+            gh_path,
+            auth_expired: AtomicBool::new(false),
+            retry: RetryPolicy::default(),
+            rate_limiter: RateLimiter::new(GitHubConfig::default().min_remaining_requests),
+            host: None,
+            activity_cache: None,
+            offline: false,
+            unavailable: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Refuse `args` when offline, recording what they would have fetched
+    fn check_online(&self, args: &[&str]) -> Result<()> {
+        if !self.offline {
+            return Ok(());
+        }
+        if let Ok(mut unavailable) = self.unavailable.lock() {
+            *unavailable.entry(describe_gh_call(args)).or_default() += 1;
+        }
+        Err(Offline.into())
+    }
+
+    /// A gh invocation, pointed at the configured host
+    fn gh(&self) -> Command {
+        let mut command = Command::new(&self.gh_path);
+        if let Some(host) = &self.host {
+            command.env("GH_HOST", host);
+        }
+        command
+    }
+
+    /// Run a gh command, detecting a rejected token
+    ///
+    /// Authentication failures are turned into [`AuthExpired`], and after
+    /// the first one gh isn't run again. Rate limits and server errors are
+    /// retried with exponential backoff, as set by the [`RetryPolicy`].
+    fn run_gh(&self, args: &[&str]) -> Result<Output> {
+        self.check_online(args)?;
+
+        let mut attempt = 1;
+        loop {
+            if self.auth_expired.load(Ordering::Relaxed) {
+                return Err(AuthExpired.into());
+            }
+
+            if self.rate_limiter.record_call() {
+                self.check_rate_limit();
+            }
+            crate::stats::record_github_call();
+            let output = self
+                .gh()
+                .args(args)
+                .output()
+                .context("Failed to execute gh command")?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if is_auth_failure(&stderr) {
+                    if !self.auth_expired.swap(true, Ordering::Relaxed) {
+                        crate::stats::record_error(&stderr);
+                        warn!("GitHub rejected the gh token: {}", stderr.trim());
+                    }
+                    return Err(AuthExpired.into());
+                }
+                if attempt < self.retry.max_attempts && is_transient_failure(&stderr) {
+                    let delay = self.retry.delay(attempt, jitter());
+                    warn!(
+                        "gh failed ({}), retrying in {:.1}s (attempt {}/{})",
+                        stderr.trim(),
+                        delay.as_secs_f64(),
+                        attempt + 1,
+                        self.retry.max_attempts
+                    );
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                    continue;
+                }
+            }
+
+            return Ok(output);
+        }
+    }
+
+    /// Read the API quota into the rate limiter
+    ///
+    /// `gh api rate_limit` doesn't count against the quota, so it bypasses
+    /// [`Self::run_gh`]. Failures only mean the quota stays unknown.
+    fn check_rate_limit(&self) {
+        let output = match self.gh().args(["api", "rate_limit"]).output() {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                debug!(
+                    "Failed to check the rate limit: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+                return;
+            }
+            Err(e) => {
+                debug!("Failed to check the rate limit: {}", e);
+                return;
+            }
+        };
+        match RateLimit::parse(&String::from_utf8_lossy(&output.stdout)) {
+            Ok(rate_limit) => self.rate_limiter.update(rate_limit),
+            Err(e) => debug!("Failed to parse the rate limit: {}", e),
+        }
     }
 
     /// Execute a gh command and parse JSON output
     fn execute_gh<T: DeserializeOwned>(&self, args: &[&str]) -> Result<T> {
-        let output = Command::new(&self.gh_path)
-            .args(args)
-            .output()
-            .context("Failed to execute gh command")?;
+        let output = self.run_gh(args)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
+            crate::stats::record_error(&stderr);
 
             // Check for specific error conditions
             if stderr.contains("404") || stderr.contains("not found") {
                 return Err(anyhow!("Resource not found"));
             }
-            if stderr.contains("401") || stderr.contains("403") {
-                return Err(anyhow!("Authentication failed. Run 'gh auth login'"));
+            if stderr.contains("403") {
+                return Err(anyhow!("Access denied: {}", stderr.trim()));
             }
 
             return Err(anyhow!("gh command failed: {}", stderr));
@@ -151,13 +1086,11 @@ impl RealGitHub {
 
     /// Execute gh and return raw string output
     fn execute_gh_raw(&self, args: &[&str]) -> Result<String> {
-        let output = Command::new(&self.gh_path)
-            .args(args)
-            .output()
-            .context("Failed to execute gh command")?;
+        let output = self.run_gh(args)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
+            crate::stats::record_error(&stderr);
             return Err(anyhow!("gh command failed: {}", stderr));
         }
 
@@ -170,7 +1103,7 @@ impl RealGitHub {
 
         // Build endpoint with query parameters
         let endpoint = if let Some(since_ts) = since {
-            format!("repos/{}/issues?since={}", repo, since_ts.to_string())
+            format!("repos/{}/issues?since={}", repo, since_ts)
         } else {
             format!("repos/{}/issues", repo)
         };
@@ -179,7 +1112,38 @@ impl RealGitHub {
 
         // Deserialize as RestIssue and convert to Issue
         let rest_issues: Vec<RestIssue> = self.execute_gh(&args)?;
-        Ok(rest_issues.into_iter().map(Into::into).collect())
+        let mut issues: Vec<Issue> = rest_issues.into_iter().map(Into::into).collect();
+
+        // Requested reviewers are only exposed by the pulls endpoint
+        let has_open_prs = issues
+            .iter()
+            .any(|i| i.is_pull_request && i.state == IssueState::Open);
+        if has_open_prs {
+            match self.fetch_open_pull_requests(repo) {
+                Ok(prs) => {
+                    for pr in prs {
+                        if let Some(issue) = issues
+                            .iter_mut()
+                            .find(|i| i.is_pull_request && i.number == pr.number)
+                        {
+                            issue.requested_reviewers =
+                                pr.requested_reviewers.into_iter().map(Into::into).collect();
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to fetch requested reviewers for {}: {}", repo, e),
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Fetch open pull requests for a repository
+    fn fetch_open_pull_requests(&self, repo: &str) -> Result<Vec<RestPullRequest>> {
+        let endpoint = format!("repos/{}/pulls?state=open&per_page=100", repo);
+        let args = vec!["api", &endpoint, "--paginate"];
+
+        self.execute_gh(&args)
     }
 
     /// Fetch comments for an issue/PR
@@ -193,9 +1157,7 @@ impl RealGitHub {
         let endpoint = if let Some(since_ts) = since {
             format!(
                 "repos/{}/issues/{}/comments?since={}",
-                repo,
-                issue_number,
-                since_ts.to_string()
+                repo, issue_number, since_ts
             )
         } else {
             format!("repos/{}/issues/{}/comments", repo, issue_number)
@@ -214,24 +1176,12 @@ impl RealGitHub {
         self.execute_gh(&args)
     }
 
-    /// Search for mentions of the current user
-    pub fn fetch_mentions(&self, since: Timestamp) -> Result<Vec<Issue>> {
-        let query = format!("involves:@me updated:>{}", since.strftime("%Y-%m-%d"));
-        // URL encode the query parameter
-        let encoded_query = query
-            .replace(" ", "%20")
-            .replace(":", "%3A")
-            .replace(">", "%3E");
-        let endpoint = format!("search/issues?q={}", encoded_query);
-        let args = vec!["api", &endpoint];
-
-        #[derive(serde::Deserialize)]
-        struct SearchResult {
-            items: Vec<Issue>,
-        }
+    /// Fetch all repositories of an organization
+    pub fn fetch_org_repositories(&self, org: &str) -> Result<Vec<Repository>> {
+        let endpoint = format!("orgs/{}/repos?per_page=100", org);
+        let args = vec!["api", &endpoint, "--paginate"];
 
-        let result: SearchResult = self.execute_gh(&args)?;
-        Ok(result.items)
+        self.execute_gh(&args)
     }
 
     /// Get current authenticated user
@@ -260,7 +1210,22 @@ impl RealGitHub {
         let issue_args = vec!["api", &issue_endpoint];
 
         let rest_issue: RestIssue = self.execute_gh(&issue_args)?;
-        let issue: Issue = rest_issue.into();
+        let mut issue: Issue = rest_issue.into();
+
+        // Requested reviewers are only exposed by the pulls endpoint
+        if issue.is_pull_request {
+            let pr_endpoint = format!("repos/{}/pulls/{}", repo, issue_number);
+            match self.execute_gh::<RestPullRequest>(&["api", &pr_endpoint]) {
+                Ok(pr) => {
+                    issue.requested_reviewers =
+                        pr.requested_reviewers.into_iter().map(Into::into).collect();
+                }
+                Err(e) => warn!(
+                    "Failed to fetch requested reviewers for {}#{}: {}",
+                    repo, issue_number, e
+                ),
+            }
+        }
 
         // Then fetch all comments
         let comments_endpoint = format!("repos/{}/issues/{}/comments", repo, issue_number);
@@ -292,8 +1257,281 @@ impl RealGitHub {
         })
     }
 
+    /// Fetch the commit a pull request currently proposes to merge
+    pub fn fetch_pr_head_sha(&self, repo: &str, pr_number: u32) -> Result<Option<String>> {
+        let endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
+        let pr: RestPullRequest = self.execute_gh(&["api", &endpoint])?;
+
+        Ok(pr.head.map(|head| head.sha))
+    }
+
+    /// Fetch reviews for a pull request
+    pub fn fetch_pr_reviews(&self, repo: &str, pr_number: u32) -> Result<Vec<Review>> {
+        let endpoint = format!("repos/{}/pulls/{}/reviews", repo, pr_number);
+        let args = vec!["api", &endpoint, "--paginate"];
+
+        self.execute_gh(&args)
+    }
+
+    /// Fetch the inline review comments of a pull request
+    pub fn fetch_pr_review_comments(
+        &self,
+        repo: &str,
+        pr_number: u32,
+    ) -> Result<Vec<ReviewComment>> {
+        let endpoint = format!("repos/{}/pulls/{}/comments", repo, pr_number);
+        let args = vec!["api", &endpoint, "--paginate"];
+
+        self.execute_gh(&args)
+    }
+
+    /// Fetch the review threads of a pull request
+    ///
+    /// Resolution status is only exposed by the GraphQL API.
+    pub fn fetch_review_threads(&self, repo: &str, pr_number: u32) -> Result<Vec<ReviewThread>> {
+        let (owner, name) = repo
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Invalid repository name: {}", repo))?;
+        let query = format!("query={}", REVIEW_THREADS_QUERY);
+        let owner = format!("owner={}", owner);
+        let name = format!("name={}", name);
+        let number = format!("number={}", pr_number);
+        let args = [
+            "api", "graphql", "-f", &query, "-f", &owner, "-f", &name, "-F", &number,
+        ];
+
+        let response: ReviewThreadsResponse = self.execute_gh(&args)?;
+        let pull_request = response
+            .data
+            .repository
+            .and_then(|r| r.pull_request)
+            .ok_or_else(|| anyhow!("Pull request {}#{} not found", repo, pr_number))?;
+
+        Ok(pull_request
+            .review_threads
+            .nodes
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Fetch failed workflow runs for the head commit of a pull request
+    pub fn fetch_failed_runs(&self, repo: &str, pr_number: u32) -> Result<Vec<WorkflowRun>> {
+        let pr_endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
+        let pr: RestPullRequest = self.execute_gh(&["api", &pr_endpoint])?;
+        let Some(head) = pr.head else {
+            return Ok(Vec::new());
+        };
+
+        let runs_endpoint = format!(
+            "repos/{}/actions/runs?head_sha={}&status=failure&per_page=20",
+            repo, head.sha
+        );
+        let runs: WorkflowRunList = self.execute_gh(&["api", &runs_endpoint])?;
+
+        Ok(runs.workflow_runs)
+    }
+
+    /// Fetch the check runs on the head commit of a pull request
+    pub fn fetch_pr_checks(&self, repo: &str, pr_number: u32) -> Result<Vec<CheckRun>> {
+        let Some(sha) = self.fetch_pr_head_sha(repo, pr_number)? else {
+            return Ok(Vec::new());
+        };
+        let endpoint = format!("repos/{}/commits/{}/check-runs?per_page=100", repo, sha);
+        let runs: CheckRunList = self.execute_gh(&["api", &endpoint])?;
+        Ok(runs.check_runs)
+    }
+
+    /// Fetch the logs of the failed jobs in a workflow run
+    pub fn fetch_failed_run_log(&self, repo: &str, run_id: u64) -> Result<String> {
+        let run_id = run_id.to_string();
+        self.execute_gh_raw(&["run", "view", &run_id, "--repo", repo, "--log-failed"])
+    }
+
+    /// Fetch a release by its id
+    pub fn fetch_release(&self, repo: &str, release_id: u64) -> Result<Release> {
+        let endpoint = format!("repos/{}/releases/{}", repo, release_id);
+        self.execute_gh(&["api", &endpoint])
+    }
+
+    /// Fetch a project board, following the pages of its items
+    pub fn fetch_project(
+        &self,
+        project: &ProjectReference,
+        status_field: &str,
+    ) -> Result<ProjectBoard> {
+        let owner_type = if project.is_org {
+            "organization"
+        } else {
+            "user"
+        };
+        let query = format!("query={}", PROJECT_QUERY.replace("OWNER_TYPE", owner_type));
+        let login = format!("login={}", project.owner);
+        let number = format!("number={}", project.number);
+        let field = format!("field={}", status_field);
+
+        let mut board: Option<ProjectBoard> = None;
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_PROJECT_PAGES {
+            let after = cursor.as_ref().map(|cursor| format!("cursor={}", cursor));
+            let mut args = vec![
+                "api", "graphql", "-f", &query, "-f", &login, "-F", &number, "-f", &field,
+            ];
+            if let Some(after) = &after {
+                args.extend(["-f", after.as_str()]);
+            }
+
+            let response: ProjectResponse = self.execute_gh(&args)?;
+            let project = response
+                .data
+                .owner
+                .and_then(|owner| owner.project_v2)
+                .ok_or_else(|| {
+                    anyhow!("Project {} of {} not found", project.number, project.owner)
+                })?;
+            let board = board.get_or_insert_with(|| ProjectBoard {
+                title: project.title,
+                url: project.url,
+                statuses: project
+                    .field
+                    .map(|field| field.options.into_iter().map(|o| o.name).collect())
+                    .unwrap_or_default(),
+                items: Vec::new(),
+            });
+            board.items.extend(
+                project
+                    .items
+                    .nodes
+                    .into_iter()
+                    .filter_map(GraphQlProjectItem::into_item),
+            );
+
+            let page_info = project.items.page_info;
+            match page_info.end_cursor {
+                Some(end_cursor) if page_info.has_next_page => cursor = Some(end_cursor),
+                _ => {
+                    cursor = None;
+                    break;
+                }
+            }
+        }
+
+        let board = board.expect("at least one page is fetched");
+        if cursor.is_some() {
+            warn!(
+                "Only the first {} items of project {} are shown",
+                board.items.len(),
+                board.url
+            );
+        }
+        Ok(board)
+    }
+
+    /// Fetch several issues or PRs with their latest comments in one GraphQL query
+    pub fn fetch_issue_batch(
+        &self,
+        refs: &[(String, u32)],
+    ) -> Result<Vec<Option<IssueWithComments>>> {
+        let query = format!("query={}", issue_batch_query(refs)?);
+        let mut response: IssueBatchResponse =
+            self.execute_gh(&["api", "graphql", "-f", &query])?;
+
+        Ok((0..refs.len())
+            .map(|i| {
+                let item = response
+                    .data
+                    .remove(&format!("i{}", i))
+                    .flatten()?
+                    .issue_or_pull_request?;
+                let complete = item.comments.nodes.len() >= item.comments.total_count as usize;
+                complete.then(|| item.into_issue())
+            })
+            .collect())
+    }
+
+    /// Fetch the discussions of a repository updated since `since`
+    ///
+    /// Discussions are only exposed by the GraphQL API, which returns the 50
+    /// most recently updated ones.
+    pub fn fetch_discussions(&self, repo: &str, since: Timestamp) -> Result<Vec<Discussion>> {
+        let (owner, name) = repo
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Invalid repository name: {}", repo))?;
+        let query = format!("query={}", DISCUSSIONS_QUERY);
+        let owner = format!("owner={}", owner);
+        let name = format!("name={}", name);
+        let args = ["api", "graphql", "-f", &query, "-f", &owner, "-f", &name];
+
+        let response: DiscussionsResponse = self.execute_gh(&args)?;
+        let repository = response
+            .data
+            .repository
+            .ok_or_else(|| anyhow!("Repository {} not found", repo))?;
+        Ok(repository
+            .discussions
+            .nodes
+            .into_iter()
+            .map(Discussion::from)
+            .filter(|discussion| discussion.updated_at >= since)
+            .collect())
+    }
+
+    /// Fetch the open pull requests of a repository and the reviews requested on them
+    ///
+    /// The GraphQL API returns the 50 oldest open pull requests, which are
+    /// the ones that have waited longest.
+    pub fn fetch_review_queue(&self, repo: &str) -> Result<ReviewQueue> {
+        let (owner, name) = repo
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Invalid repository name: {}", repo))?;
+        let query = format!("query={}", REVIEW_QUEUE_QUERY);
+        let owner = format!("owner={}", owner);
+        let name = format!("name={}", name);
+        let args = ["api", "graphql", "-f", &query, "-f", &owner, "-f", &name];
+
+        let response: ReviewQueueResponse = self.execute_gh(&args)?;
+        let repository = response
+            .data
+            .repository
+            .ok_or_else(|| anyhow!("Repository {} not found", repo))?;
+        Ok(repository.into())
+    }
+
+    /// Fetch the open milestones of a repository, soonest due first
+    ///
+    /// Milestones without a due date come last.
+    pub fn fetch_milestones(&self, repo: &str) -> Result<Vec<RepoMilestone>> {
+        let endpoint = format!(
+            "repos/{}/milestones?state=open&sort=due_on&direction=asc&per_page=100",
+            repo
+        );
+        self.execute_gh(&["api", &endpoint, "--paginate"])
+    }
+
+    /// Fetch the labels defined in a repository
+    pub fn fetch_labels(&self, repo: &str) -> Result<Vec<Label>> {
+        let endpoint = format!("repos/{}/labels?per_page=100", repo);
+        self.execute_gh(&["api", &endpoint, "--paginate"])
+    }
+
+    /// Add labels to an issue or PR with `gh issue edit`
+    pub fn add_labels(&self, repo: &str, number: u32, labels: &[String]) -> Result<()> {
+        let number = number.to_string();
+        let labels = labels.join(",");
+        self.execute_gh_raw(&[
+            "issue",
+            "edit",
+            &number,
+            "--repo",
+            repo,
+            "--add-label",
+            &labels,
+        ])?;
+        Ok(())
+    }
+
     /// Fetch user's activity events (received events for subscribed repos)
-    pub fn fetch_activity(&self, days: u32) -> Result<Vec<ActivityEvent>> {
+    pub fn fetch_activity_feed(&self, days: u32) -> Result<ActivityFeed> {
         // Get current username first
         let username = self.get_current_user()?;
 
@@ -304,13 +1542,51 @@ impl RealGitHub {
         let events: Vec<ActivityEvent> = self.execute_gh(&args)?;
 
         // Filter by date - only include events from the last N days
-        let cutoff = jiff::Timestamp::now() - (days as i64 * 24).hours();
-        let filtered_events: Vec<ActivityEvent> = events
-            .into_iter()
-            .filter(|event| event.created_at >= cutoff)
-            .collect();
+        let now = jiff::Timestamp::now();
+        let cutoff = now - (days as i64 * 24).hours();
+        Ok(ActivityFeed::new(events, cutoff, now))
+    }
 
-        Ok(filtered_events)
+    /// Fetch the events `login` performed, in public and in repositories the token can see
+    pub fn fetch_user_events(&self, login: &str, days: u32) -> Result<ActivityFeed> {
+        let endpoint = format!("/users/{}/events", login);
+        let events: Vec<ActivityEvent> = self.execute_gh(&["api", &endpoint, "--paginate"])?;
+
+        let now = jiff::Timestamp::now();
+        let cutoff = now - (days as i64 * 24).hours();
+        Ok(ActivityFeed::new(events, cutoff, now))
+    }
+
+    /// Search issues and PRs, up to the first 100 results
+    pub fn search_issues(&self, query: &str) -> Result<Vec<Issue>> {
+        use crate::github::models::RestIssue;
+
+        #[derive(serde::Deserialize)]
+        struct SearchResult {
+            items: Vec<RestIssue>,
+        }
+
+        let query = format!("q={}", query);
+        let args = vec![
+            "api",
+            "-X",
+            "GET",
+            "search/issues",
+            "-f",
+            &query,
+            "-f",
+            "per_page=100",
+        ];
+        let result: SearchResult = self.execute_gh(&args)?;
+        Ok(result.items.into_iter().map(Into::into).collect())
+    }
+
+    /// Fetch notifications updated since `since`, including read ones
+    pub fn fetch_notifications(&self, since: Timestamp) -> Result<Vec<Notification>> {
+        let endpoint = format!("notifications?all=true&since={}", since);
+        let args = vec!["api", &endpoint, "--paginate"];
+
+        self.execute_gh(&args)
     }
 }
 
@@ -347,6 +1623,22 @@ fn which_gh() -> Result<PathBuf> {
     ))
 }
 
+/// The issue search for items mentioning a user or, with a slash, a team
+fn mention_query(target: &str, since: Timestamp) -> String {
+    let target = target.trim_start_matches('@');
+    let qualifier = if target.contains('/') {
+        "team"
+    } else {
+        "mentions"
+    };
+    format!(
+        "{}:{} updated:>{}",
+        qualifier,
+        target,
+        since.strftime("%Y-%m-%d")
+    )
+}
+
 /// Mock GitHub client for testing
 #[cfg(test)]
 pub struct MockGitHub {
@@ -354,7 +1646,39 @@ pub struct MockGitHub {
     pub comments: Vec<Comment>,
     pub repositories: Vec<Repository>,
     pub current_user: String,
-    pub pr_diffs: Vec<(u32, PrDiff)>, // (pr_number, diff)
+    pub pr_diffs: Vec<(u32, PrDiff)>,        // (pr_number, diff)
+    pub head_shas: Vec<(u32, String)>,       // (pr_number, sha)
+    pub pr_reviews: Vec<(u32, Vec<Review>)>, // (pr_number, reviews)
+    pub pr_review_comments: Vec<(u32, Vec<ReviewComment>)>, // (pr_number, comments)
+    pub failed_runs: Vec<(u32, Vec<WorkflowRun>)>, // (pr_number, runs)
+    pub review_threads: Vec<(u32, Vec<ReviewThread>)>, // (pr_number, threads)
+    pub run_logs: Vec<(u64, String)>,        // (run_id, log)
+    pub pr_checks: Vec<(u32, Vec<CheckRun>)>, // (pr_number, check runs)
+    pub releases: Vec<Release>,
+    pub notifications: Vec<Notification>,
+    pub events: Vec<ActivityEvent>,
+    pub search_results: Vec<Issue>,
+    pub mentions: Vec<(String, Issue)>, // (mentioned user or team, issue)
+    pub labels: Vec<Label>,
+    pub discussions: Vec<(String, Discussion)>, // (repo, discussion)
+    pub milestones: Vec<(String, RepoMilestone)>, // (repo, milestone)
+    pub review_queues: Vec<(String, ReviewQueue)>, // (repo, queue)
+    pub projects: Vec<ProjectBoard>,
+    /// Labels added through `add_labels`, as (issue number, labels)
+    pub added_labels: std::sync::Mutex<Vec<(u32, Vec<String>)>>,
+    /// Fail every call as if the token had been revoked
+    pub auth_expired: bool,
+    /// Fail GraphQL batch fetches, leaving them to REST
+    pub graphql_unavailable: bool,
+    pub rate_limiter: RateLimiter,
+    pub activity_cache: Option<CacheManager>,
+}
+
+#[cfg(test)]
+impl Default for MockGitHub {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -366,10 +1690,35 @@ impl MockGitHub {
             repositories: vec![],
             current_user: "testuser".to_string(),
             pr_diffs: vec![],
+            head_shas: vec![],
+            pr_reviews: vec![],
+            pr_review_comments: vec![],
+            failed_runs: vec![],
+            review_threads: vec![],
+            run_logs: vec![],
+            pr_checks: vec![],
+            releases: vec![],
+            notifications: vec![],
+            events: vec![],
+            search_results: vec![],
+            mentions: vec![],
+            labels: vec![],
+            discussions: vec![],
+            milestones: vec![],
+            review_queues: vec![],
+            projects: vec![],
+            added_labels: Default::default(),
+            auth_expired: false,
+            graphql_unavailable: false,
+            rate_limiter: RateLimiter::new(GitHubConfig::default().min_remaining_requests),
+            activity_cache: None,
         }
     }
 
     pub fn fetch_issues(&self, _repo: &str, _since: Option<Timestamp>) -> Result<Vec<Issue>> {
+        if self.auth_expired {
+            return Err(AuthExpired.into());
+        }
         Ok(self.issues.clone())
     }
 
@@ -390,8 +1739,22 @@ impl MockGitHub {
             .ok_or_else(|| anyhow!("Repository not found"))
     }
 
-    pub fn fetch_mentions(&self, _since: Timestamp) -> Result<Vec<Issue>> {
-        Ok(self.issues.clone())
+    pub fn fetch_org_repositories(&self, org: &str) -> Result<Vec<Repository>> {
+        Ok(self
+            .repositories
+            .iter()
+            .filter(|r| r.owner.login.eq_ignore_ascii_case(org))
+            .cloned()
+            .collect())
+    }
+
+    pub fn fetch_mentions(&self, target: &str, _since: Timestamp) -> Result<Vec<Issue>> {
+        Ok(self
+            .mentions
+            .iter()
+            .filter(|(mentioned, _)| mentioned == target)
+            .map(|(_, issue)| issue.clone())
+            .collect())
     }
 
     pub fn get_current_user(&self) -> Result<String> {
@@ -415,6 +1778,22 @@ impl MockGitHub {
         Ok((issue, self.comments.clone()))
     }
 
+    pub fn fetch_issue_batch(
+        &self,
+        refs: &[(String, u32)],
+    ) -> Result<Vec<Option<IssueWithComments>>> {
+        if self.auth_expired {
+            return Err(AuthExpired.into());
+        }
+        if self.graphql_unavailable {
+            return Err(anyhow!("GraphQL is unavailable"));
+        }
+        Ok(refs
+            .iter()
+            .map(|(repo, number)| self.fetch_single_issue(repo, *number).ok())
+            .collect())
+    }
+
     pub fn fetch_pr_diff(&self, _repo: &str, pr_number: u32) -> Result<PrDiff> {
         // Find the PR diff by number
         self.pr_diffs
@@ -424,9 +1803,163 @@ impl MockGitHub {
             .ok_or_else(|| anyhow!("PR #{} diff not found", pr_number))
     }
 
-    pub fn fetch_activity(&self, _days: u32) -> Result<Vec<ActivityEvent>> {
-        // Return empty activity for mock
-        Ok(vec![])
+    pub fn fetch_pr_head_sha(&self, _repo: &str, pr_number: u32) -> Result<Option<String>> {
+        Ok(self
+            .head_shas
+            .iter()
+            .find(|(num, _)| *num == pr_number)
+            .map(|(_, sha)| sha.clone()))
+    }
+
+    pub fn fetch_pr_reviews(&self, _repo: &str, pr_number: u32) -> Result<Vec<Review>> {
+        Ok(self
+            .pr_reviews
+            .iter()
+            .find(|(num, _)| *num == pr_number)
+            .map(|(_, reviews)| reviews.clone())
+            .unwrap_or_default())
+    }
+
+    pub fn fetch_pr_review_comments(
+        &self,
+        _repo: &str,
+        pr_number: u32,
+    ) -> Result<Vec<ReviewComment>> {
+        Ok(self
+            .pr_review_comments
+            .iter()
+            .find(|(num, _)| *num == pr_number)
+            .map(|(_, comments)| comments.clone())
+            .unwrap_or_default())
+    }
+
+    pub fn fetch_review_threads(&self, _repo: &str, pr_number: u32) -> Result<Vec<ReviewThread>> {
+        Ok(self
+            .review_threads
+            .iter()
+            .find(|(num, _)| *num == pr_number)
+            .map(|(_, threads)| threads.clone())
+            .unwrap_or_default())
+    }
+
+    pub fn fetch_failed_runs(&self, _repo: &str, pr_number: u32) -> Result<Vec<WorkflowRun>> {
+        Ok(self
+            .failed_runs
+            .iter()
+            .find(|(num, _)| *num == pr_number)
+            .map(|(_, runs)| runs.clone())
+            .unwrap_or_default())
+    }
+
+    pub fn fetch_pr_checks(&self, _repo: &str, pr_number: u32) -> Result<Vec<CheckRun>> {
+        Ok(self
+            .pr_checks
+            .iter()
+            .find(|(num, _)| *num == pr_number)
+            .map(|(_, runs)| runs.clone())
+            .unwrap_or_default())
+    }
+
+    pub fn fetch_failed_run_log(&self, _repo: &str, run_id: u64) -> Result<String> {
+        self.run_logs
+            .iter()
+            .find(|(id, _)| *id == run_id)
+            .map(|(_, log)| log.clone())
+            .ok_or_else(|| anyhow!("Run {} log not found", run_id))
+    }
+
+    pub fn fetch_release(&self, _repo: &str, release_id: u64) -> Result<Release> {
+        self.releases
+            .iter()
+            .find(|release| release.id == release_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Release {} not found", release_id))
+    }
+
+    pub fn fetch_labels(&self, _repo: &str) -> Result<Vec<Label>> {
+        Ok(self.labels.clone())
+    }
+
+    pub fn fetch_discussions(&self, repo: &str, since: Timestamp) -> Result<Vec<Discussion>> {
+        Ok(self
+            .discussions
+            .iter()
+            .filter(|(name, discussion)| name == repo && discussion.updated_at >= since)
+            .map(|(_, discussion)| discussion.clone())
+            .collect())
+    }
+
+    pub fn fetch_review_queue(&self, repo: &str) -> Result<ReviewQueue> {
+        Ok(self
+            .review_queues
+            .iter()
+            .find(|(name, _)| name == repo)
+            .map(|(_, queue)| queue.clone())
+            .unwrap_or_default())
+    }
+
+    pub fn fetch_milestones(&self, repo: &str) -> Result<Vec<RepoMilestone>> {
+        Ok(self
+            .milestones
+            .iter()
+            .filter(|(name, _)| name == repo)
+            .map(|(_, milestone)| milestone.clone())
+            .collect())
+    }
+
+    pub fn fetch_project(
+        &self,
+        project: &ProjectReference,
+        _status_field: &str,
+    ) -> Result<ProjectBoard> {
+        let suffix = format!("/{}/projects/{}", project.owner, project.number);
+        self.projects
+            .iter()
+            .find(|board| board.url.ends_with(&suffix))
+            .cloned()
+            .ok_or_else(|| anyhow!("Project {} of {} not found", project.number, project.owner))
+    }
+
+    pub fn add_labels(&self, _repo: &str, number: u32, labels: &[String]) -> Result<()> {
+        self.added_labels
+            .lock()
+            .unwrap()
+            .push((number, labels.to_vec()));
+        Ok(())
+    }
+
+    pub fn fetch_activity_feed(&self, days: u32) -> Result<ActivityFeed> {
+        let now = Timestamp::now();
+        let cutoff = now - (days as i64 * 24).hours();
+        Ok(ActivityFeed::new(self.events.clone(), cutoff, now))
+    }
+
+    pub fn fetch_user_events(&self, login: &str, days: u32) -> Result<ActivityFeed> {
+        let now = Timestamp::now();
+        let cutoff = now - (days as i64 * 24).hours();
+        let events = self
+            .events
+            .iter()
+            .filter(|event| event.actor.login.eq_ignore_ascii_case(login))
+            .cloned()
+            .collect();
+        Ok(ActivityFeed::new(events, cutoff, now))
+    }
+
+    pub fn search_issues(&self, _query: &str) -> Result<Vec<Issue>> {
+        Ok(self.search_results.clone())
+    }
+
+    pub fn fetch_notifications(&self, since: Timestamp) -> Result<Vec<Notification>> {
+        if self.auth_expired {
+            return Err(AuthExpired.into());
+        }
+        Ok(self
+            .notifications
+            .iter()
+            .filter(|n| n.updated_at >= since)
+            .cloned()
+            .collect())
     }
 }
 
@@ -435,6 +1968,71 @@ mod tests {
     use super::*;
     use crate::github::IssueState;
 
+    #[test]
+    fn test_auth_failure_detection() {
+        assert!(is_auth_failure(
+            "gh: Bad credentials (HTTP 401)\nTry authenticating with:  gh auth login"
+        ));
+        assert!(is_auth_failure(
+            "You are not logged into any GitHub hosts. To log in, run: gh auth login"
+        ));
+        assert!(!is_auth_failure("gh: Not Found (HTTP 404)"));
+        assert!(!is_auth_failure(
+            "gh: Resource not accessible by integration (HTTP 403)"
+        ));
+
+        let error = anyhow::Error::from(AuthExpired).context("Failed to fetch activity");
+        assert!(is_auth_expired(&error));
+        assert!(!is_auth_expired(&anyhow!("Resource not found")));
+    }
+
+    #[test]
+    fn test_offline_client_refuses_gh_calls() {
+        assert_eq!(
+            describe_gh_call(&[
+                "api",
+                "repos/tokio-rs/tokio/issues/42/comments?per_page=100"
+            ]),
+            "repos/tokio-rs/tokio/issues/{n}/comments"
+        );
+        assert_eq!(
+            describe_gh_call(&["api", "graphql", "-f", "query=..."]),
+            "GraphQL queries"
+        );
+
+        let client = GitHubClient::offline();
+        assert!(client.is_offline());
+        let error = client.fetch_issues("tokio-rs/tokio", None).unwrap_err();
+        assert!(error.downcast_ref::<Offline>().is_some());
+        assert!(client.check_online(&["api", "search/issues?q=x"]).is_err());
+        let unavailable = client.unavailable();
+        assert!(!unavailable.is_empty());
+        assert!(unavailable.contains(&("search/issues".to_string(), 1)));
+    }
+
+    #[test]
+    fn test_transient_failures_are_retried() {
+        assert!(is_transient_failure(
+            "gh: You have exceeded a secondary rate limit. Please wait a few minutes (HTTP 403)"
+        ));
+        assert!(is_transient_failure("gh: Server Error (HTTP 502)"));
+        assert!(is_transient_failure("HTTP 503: Service Unavailable"));
+        assert!(!is_transient_failure("gh: Not Found (HTTP 404)"));
+        assert!(!is_transient_failure("gh: Bad credentials (HTTP 401)"));
+
+        let retry = RetryPolicy::from_config(&GitHubConfig {
+            max_attempts: 0,
+            retry_delay_ms: 1000,
+            ..GitHubConfig::default()
+        });
+        assert_eq!(retry.max_attempts, 1);
+        assert_eq!(retry.delay(1, 0.0), Duration::from_millis(500));
+        assert_eq!(retry.delay(1, 1.0), Duration::from_secs(1));
+        assert_eq!(retry.delay(3, 1.0), Duration::from_secs(4));
+        assert_eq!(retry.delay(20, 1.0), MAX_RETRY_DELAY);
+        assert!((0.0..1.0).contains(&jitter()));
+    }
+
     #[test]
     fn test_mock_github_client() {
         // Create mock client with test data
@@ -456,6 +2054,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 0 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         });
 
         // Create client
@@ -467,6 +2069,19 @@ mod tests {
         assert_eq!(issues[0].number, 42);
     }
 
+    #[test]
+    fn test_mention_query() {
+        let since: Timestamp = "2024-03-01T12:00:00Z".parse().unwrap();
+        assert_eq!(
+            mention_query("@carllerche", since),
+            "mentions:carllerche updated:>2024-03-01"
+        );
+        assert_eq!(
+            mention_query("@tokio-rs/maintainers", since),
+            "team:tokio-rs/maintainers updated:>2024-03-01"
+        );
+    }
+
     #[test]
     fn test_mock_current_user() {
         let mock = MockGitHub::new();
@@ -496,6 +2111,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/123".to_string(),
             comments: CommentCount { total_count: 2 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         });
 
         // Add test comments
@@ -547,4 +2166,93 @@ mod tests {
             .to_string()
             .contains("Issue #999 not found"));
     }
+
+    #[test]
+    fn test_fetch_single_issues_keeps_order() {
+        let mut mock = MockGitHub::new();
+        for number in 1..=20 {
+            mock.issues
+                .push(crate::test_utils::create_test_issue(number, "Item", false));
+        }
+        let client = GitHubClient::Mock(mock);
+
+        let refs: Vec<(String, u32)> = (1..=21)
+            .rev()
+            .map(|n| ("test/repo".to_string(), n))
+            .collect();
+        for concurrency in [0, 1, 4, 64] {
+            let results = client.fetch_single_issues(&refs, concurrency);
+            assert_eq!(results.len(), 21);
+            assert!(results[0].is_err());
+            let numbers: Vec<u32> = results[1..]
+                .iter()
+                .map(|result| result.as_ref().unwrap().0.number)
+                .collect();
+            assert_eq!(numbers, (1..=20).rev().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_fetch_single_issues_falls_back_to_rest() {
+        let mut mock = MockGitHub::new();
+        mock.graphql_unavailable = true;
+        mock.issues
+            .push(crate::test_utils::create_test_issue(7, "Item", false));
+        let client = GitHubClient::Mock(mock);
+
+        let refs = [("test/repo".to_string(), 8), ("test/repo".to_string(), 7)];
+        assert!(client.fetch_issue_batch(&refs).is_err());
+        let results = client.fetch_single_issues(&refs, 2);
+        assert!(results[0].is_err());
+        assert_eq!(results[1].as_ref().unwrap().0.number, 7);
+    }
+
+    #[test]
+    fn test_issue_batch_query() {
+        let refs: Vec<(String, u32)> = vec![
+            ("tokio-rs/tokio".to_string(), 42),
+            ("rust-lang/rust.vim".to_string(), 7),
+        ];
+        let query = issue_batch_query(&refs).unwrap();
+        assert!(query.contains(
+            "i0: repository(owner: \"tokio-rs\", name: \"tokio\") {\n    issueOrPullRequest(number: 42)"
+        ));
+        assert!(query.contains("i1: repository(owner: \"rust-lang\", name: \"rust.vim\")"));
+        assert_eq!(query.matches("reviewRequests").count(), 2);
+        assert_eq!(query.matches("comments(last: 100)").count(), 4);
+
+        let injected = [("tokio-rs/tokio\") { x }".to_string(), 1)];
+        assert!(issue_batch_query(&injected).is_err());
+        assert!(issue_batch_query(&[("tokio".to_string(), 1)]).is_err());
+    }
+
+    #[test]
+    fn test_activity_feed_is_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        cache.initialize().unwrap();
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssuesEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "opened", "issue": { "number": 1 } },
+            "created_at": (Timestamp::now() - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        let mut client = GitHubClient::Mock(mock).with_activity_cache(Some(cache));
+
+        assert_eq!(client.fetch_activity_feed(7).unwrap().events.len(), 1);
+        if let GitHubClient::Mock(mock) = &mut client {
+            mock.events.clear();
+        }
+        // Served from the cache, while another window is fetched again
+        assert_eq!(client.fetch_activity_feed(7).unwrap().events.len(), 1);
+        assert!(client.fetch_activity_feed(3).unwrap().events.is_empty());
+
+        let client = client.with_activity_cache(None);
+        assert!(client.fetch_activity_feed(7).unwrap().events.is_empty());
+    }
 }
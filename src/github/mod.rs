@@ -3,11 +3,16 @@ use std::process::Command;
 
 mod client;
 mod models;
+mod rate_limit;
 pub mod reference;
 
-pub use client::GitHubClient;
+pub use client::{is_auth_expired, AuthExpired, GitHubClient, Offline, RetryPolicy};
 pub use models::*;
-pub use reference::{parse_issue_reference, IssueReference};
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use reference::{
+    parse_issue_reference, parse_issue_reference_on, parse_project_url, repo_from_url,
+    IssueReference, ProjectReference,
+};
 
 #[cfg(test)]
 pub use client::MockGitHub;
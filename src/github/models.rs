@@ -1,5 +1,6 @@
-use jiff::Timestamp;
+use jiff::{Timestamp, ToSpan};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Represents a GitHub issue or pull request
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -18,20 +19,45 @@ pub struct Issue {
     pub comments: CommentCount,
     #[serde(rename = "isPullRequest")]
     pub is_pull_request: bool,
+    #[serde(default)]
+    pub assignees: Vec<Author>,
+    #[serde(default, rename = "requestedReviewers")]
+    pub requested_reviewers: Vec<Author>,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    /// The author's relation to the repository, e.g. `FIRST_TIME_CONTRIBUTOR`
+    #[serde(default, rename = "authorAssociation")]
+    pub author_association: Option<String>,
 }
 
 impl Issue {
+    /// Whether the given user is one of the assignees
+    pub fn is_assigned_to(&self, login: &str) -> bool {
+        self.assignees
+            .iter()
+            .any(|a| a.login.eq_ignore_ascii_case(login))
+    }
+
+    /// Whether a review has been requested from the given user
+    pub fn is_review_requested_from(&self, login: &str) -> bool {
+        self.requested_reviewers
+            .iter()
+            .any(|r| r.login.eq_ignore_ascii_case(login))
+    }
+
+    /// Whether this is the author's first contribution to the repository
+    pub fn is_first_time_contributor(&self) -> bool {
+        matches!(
+            self.author_association.as_deref(),
+            Some("FIRST_TIME_CONTRIBUTOR" | "FIRST_TIMER")
+        )
+    }
+
     /// Extract repository name from the issue URL
-    /// URL format: https://github.com/owner/repo/issues/123 or https://github.com/owner/repo/pull/123
+    /// URL format: https://github.com/owner/repo/issues/123 or https://github.com/owner/repo/pull/123,
+    /// or the same on a GitHub Enterprise Server
     pub fn repository_name(&self) -> Option<String> {
-        let url_without_prefix = self.url.strip_prefix("https://github.com/")?;
-        let parts: Vec<&str> = url_without_prefix.split('/').collect();
-
-        if parts.len() >= 2 {
-            Some(format!("{}/{}", parts[0], parts[1]))
-        } else {
-            None
-        }
+        super::repo_from_url(&self.url)
     }
 }
 
@@ -52,6 +78,13 @@ pub struct Author {
     pub user_type: Option<String>,
 }
 
+impl Author {
+    /// Whether this is a bot account, like `dependabot[bot]`
+    pub fn is_bot(&self) -> bool {
+        self.user_type.as_deref() == Some("Bot") || self.login.ends_with("[bot]")
+    }
+}
+
 /// Label on an issue/PR
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Label {
@@ -60,6 +93,14 @@ pub struct Label {
     pub description: Option<String>,
 }
 
+/// Milestone an issue or PR belongs to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Milestone {
+    pub title: String,
+    #[serde(default, rename = "dueOn", alias = "due_on")]
+    pub due_on: Option<Timestamp>,
+}
+
 /// Comment count information
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CommentCount {
@@ -72,26 +113,716 @@ pub struct CommentCount {
 pub struct Comment {
     pub id: u64,
     pub body: String,
+    // The REST API calls the author `user`
+    #[serde(alias = "user")]
     pub author: Author,
-    #[serde(rename = "createdAt")]
+    #[serde(rename = "createdAt", alias = "created_at")]
     pub created_at: Timestamp,
-    #[serde(rename = "updatedAt")]
+    #[serde(rename = "updatedAt", alias = "updated_at")]
     pub updated_at: Timestamp,
 }
 
+/// An inline comment on the diff of a pull request
+///
+/// Served by a different endpoint than issue comments. Replies point to the
+/// first comment of their thread through `in_reply_to_id`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewComment {
+    pub id: u64,
+    pub body: String,
+    pub user: Author,
+    pub path: String,
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub in_reply_to_id: Option<u64>,
+    pub html_url: String,
+    pub created_at: Timestamp,
+}
+
+impl ReviewComment {
+    /// Id of the first comment of the thread this comment belongs to
+    pub fn thread_id(&self) -> u64 {
+        self.in_reply_to_id.unwrap_or(self.id)
+    }
+}
+
+/// A pull request review
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Review {
+    pub id: u64,
+    pub user: Author,
+    pub state: ReviewState,
+    #[serde(default)]
+    pub submitted_at: Option<Timestamp>,
+    /// The commit the review was submitted on
+    #[serde(default)]
+    pub commit_id: Option<String>,
+}
+
+/// State of a pull request review
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+/// Number of comments from which a review thread counts as long-running
+pub const LONG_RUNNING_THREAD_COMMENTS: u32 = 4;
+
+/// A pull request review thread: comments on a line of the diff
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewThread {
+    pub path: String,
+    pub line: Option<u32>,
+    pub is_resolved: bool,
+    /// The code the thread is about has changed since
+    pub is_outdated: bool,
+    /// Number of comments, including those not in `comments`
+    pub comment_count: u32,
+    /// The first comments of the thread
+    pub comments: Vec<ReviewThreadComment>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewThreadComment {
+    pub author: String,
+    pub body: String,
+}
+
+impl ReviewThread {
+    /// Whether the thread went back and forth enough to deserve a closer look
+    pub fn is_long_running(&self) -> bool {
+        self.comment_count >= LONG_RUNNING_THREAD_COMMENTS
+    }
+
+    /// `path:line` the thread is attached to
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        }
+    }
+
+    /// First line of the opening comment, shortened
+    pub fn topic(&self) -> String {
+        let first_line = self
+            .comments
+            .first()
+            .and_then(|c| c.body.lines().find(|l| !l.trim().is_empty()))
+            .unwrap_or_default()
+            .trim();
+        if first_line.chars().count() <= 100 {
+            first_line.to_string()
+        } else {
+            let truncated: String = first_line.chars().take(97).collect();
+            format!("{}...", truncated.trim_end())
+        }
+    }
+
+    /// Authors of the thread's comments, in order of first comment
+    pub fn participants(&self) -> Vec<&str> {
+        let mut participants: Vec<&str> = Vec::new();
+        for comment in &self.comments {
+            if !participants.contains(&comment.author.as_str()) {
+                participants.push(&comment.author);
+            }
+        }
+        participants
+    }
+}
+
+/// GraphQL response with the review threads of a pull request
+#[derive(Debug, Deserialize)]
+pub struct ReviewThreadsResponse {
+    pub data: ReviewThreadsData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewThreadsData {
+    pub repository: Option<ReviewThreadsRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewThreadsRepository {
+    pub pull_request: Option<ReviewThreadsPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewThreadsPullRequest {
+    pub review_threads: GraphQlNodes<GraphQlReviewThread>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlNodes<T> {
+    #[serde(default)]
+    pub total_count: u32,
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlReviewThread {
+    pub is_resolved: bool,
+    pub is_outdated: bool,
+    pub path: String,
+    pub line: Option<u32>,
+    pub comments: GraphQlNodes<GraphQlReviewComment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlReviewComment {
+    /// `None` for deleted accounts
+    pub author: Option<GraphQlActor>,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlActor {
+    pub login: String,
+}
+
+impl From<GraphQlReviewThread> for ReviewThread {
+    fn from(thread: GraphQlReviewThread) -> Self {
+        ReviewThread {
+            path: thread.path,
+            line: thread.line,
+            is_resolved: thread.is_resolved,
+            is_outdated: thread.is_outdated,
+            comment_count: thread.comments.total_count,
+            comments: thread
+                .comments
+                .nodes
+                .into_iter()
+                .map(|c| ReviewThreadComment {
+                    author: c
+                        .author
+                        .map(|a| a.login)
+                        .unwrap_or_else(|| "ghost".to_string()),
+                    body: c.body,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A GitHub Discussions thread
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Discussion {
+    pub number: u32,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    /// `ghost` for deleted accounts
+    pub author: String,
+    pub category: String,
+    /// Whether an answer was marked, for Q&A categories
+    pub is_answered: bool,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+    /// Number of top-level comments, including those not in `comments`
+    pub comment_count: u32,
+    /// The latest comments, oldest first
+    pub comments: Vec<DiscussionComment>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DiscussionComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: Timestamp,
+}
+
+/// GraphQL response with the most recently updated discussions of a repository
+#[derive(Debug, Deserialize)]
+pub struct DiscussionsResponse {
+    pub data: DiscussionsData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscussionsData {
+    pub repository: Option<DiscussionsRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscussionsRepository {
+    pub discussions: GraphQlNodes<GraphQlDiscussion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlDiscussion {
+    pub number: u32,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    pub author: Option<GraphQlActor>,
+    pub category: GraphQlDiscussionCategory,
+    /// `null` outside of Q&A categories
+    pub is_answered: Option<bool>,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+    pub comments: GraphQlNodes<GraphQlDiscussionComment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlDiscussionCategory {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlDiscussionComment {
+    pub author: Option<GraphQlActor>,
+    pub body: String,
+    pub created_at: Timestamp,
+}
+
+impl From<GraphQlDiscussion> for Discussion {
+    fn from(discussion: GraphQlDiscussion) -> Self {
+        let login = |author: Option<GraphQlActor>| {
+            author
+                .map(|a| a.login)
+                .unwrap_or_else(|| "ghost".to_string())
+        };
+        Discussion {
+            number: discussion.number,
+            title: discussion.title,
+            body: discussion.body,
+            url: discussion.url,
+            author: login(discussion.author),
+            category: discussion.category.name,
+            is_answered: discussion.is_answered.unwrap_or(false),
+            created_at: discussion.created_at,
+            updated_at: discussion.updated_at,
+            comment_count: discussion.comments.total_count,
+            comments: discussion
+                .comments
+                .nodes
+                .into_iter()
+                .map(|c| DiscussionComment {
+                    author: login(c.author),
+                    body: c.body,
+                    created_at: c.created_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The open pull requests of a repository and the reviews requested on them
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReviewQueue {
+    /// Whether the user can push to the repository
+    pub can_write: bool,
+    pub pull_requests: Vec<PendingReviewPr>,
+}
+
+/// An open pull request with its pending review requests
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingReviewPr {
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    /// `ghost` for deleted accounts
+    pub author: String,
+    pub is_draft: bool,
+    pub created_at: Timestamp,
+    pub requests: Vec<ReviewRequest>,
+}
+
+/// A review requested from a user, or a team as `org/team`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewRequest {
+    pub reviewer: String,
+    /// When the review was last requested, the PR's creation when unknown
+    pub requested_at: Timestamp,
+}
+
+/// GraphQL response with the open pull requests of a repository
+#[derive(Debug, Deserialize)]
+pub struct ReviewQueueResponse {
+    pub data: ReviewQueueData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewQueueData {
+    pub repository: Option<ReviewQueueRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewQueueRepository {
+    /// `ADMIN`, `MAINTAIN`, `WRITE`, `TRIAGE` or `READ`
+    pub viewer_permission: Option<String>,
+    pub pull_requests: GraphQlNodes<GraphQlPendingReviewPr>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlPendingReviewPr {
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    pub author: Option<GraphQlActor>,
+    pub is_draft: bool,
+    pub created_at: Timestamp,
+    pub review_requests: GraphQlNodes<GraphQlReviewRequest>,
+    pub timeline_items: GraphQlNodes<GraphQlReviewRequestedEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlReviewRequestedEvent {
+    pub created_at: Timestamp,
+    pub requested_reviewer: Option<GraphQlRequestedReviewer>,
+}
+
+impl From<ReviewQueueRepository> for ReviewQueue {
+    fn from(repository: ReviewQueueRepository) -> Self {
+        let can_write = matches!(
+            repository.viewer_permission.as_deref(),
+            Some("ADMIN" | "MAINTAIN" | "WRITE")
+        );
+        let pull_requests = repository
+            .pull_requests
+            .nodes
+            .into_iter()
+            .map(|pr| {
+                // The latest request of each reviewer, oldest events first
+                let requested: Vec<(String, Timestamp)> = pr
+                    .timeline_items
+                    .nodes
+                    .into_iter()
+                    .filter_map(|event| Some((event.requested_reviewer?.name()?, event.created_at)))
+                    .collect();
+                let requests = pr
+                    .review_requests
+                    .nodes
+                    .into_iter()
+                    .filter_map(|request| request.requested_reviewer?.name())
+                    .map(|reviewer| ReviewRequest {
+                        requested_at: requested
+                            .iter()
+                            .rev()
+                            .find(|(name, _)| *name == reviewer)
+                            .map_or(pr.created_at, |(_, at)| *at),
+                        reviewer,
+                    })
+                    .collect();
+                PendingReviewPr {
+                    number: pr.number,
+                    title: pr.title,
+                    url: pr.url,
+                    author: pr
+                        .author
+                        .map(|a| a.login)
+                        .unwrap_or_else(|| "ghost".to_string()),
+                    is_draft: pr.is_draft,
+                    created_at: pr.created_at,
+                    requests,
+                }
+            })
+            .collect();
+        ReviewQueue {
+            can_write,
+            pull_requests,
+        }
+    }
+}
+
+/// GraphQL response with several issues and PRs, under the aliases `i0`, `i1`, ...
+#[derive(Debug, Deserialize)]
+pub struct IssueBatchResponse {
+    pub data: BTreeMap<String, Option<IssueBatchRepository>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueBatchRepository {
+    /// `None` when the number doesn't exist in the repository
+    pub issue_or_pull_request: Option<GraphQlIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlIssue {
+    #[serde(rename = "__typename")]
+    pub typename: String,
+    pub number: u32,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: IssueState,
+    /// `None` for deleted accounts
+    pub author: Option<GraphQlAuthor>,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+    pub url: String,
+    pub labels: GraphQlNodes<Label>,
+    pub assignees: GraphQlNodes<GraphQlActor>,
+    pub milestone: Option<Milestone>,
+    #[serde(default)]
+    pub author_association: Option<String>,
+    /// The most recent comments, oldest first
+    pub comments: GraphQlNodes<GraphQlComment>,
+    /// Only on pull requests
+    #[serde(default)]
+    pub review_requests: Option<GraphQlNodes<GraphQlReviewRequest>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlAuthor {
+    pub login: String,
+    #[serde(rename = "__typename")]
+    pub typename: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlComment {
+    pub database_id: u64,
+    pub body: String,
+    pub author: Option<GraphQlAuthor>,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlReviewRequest {
+    pub requested_reviewer: Option<GraphQlRequestedReviewer>,
+}
+
+/// A requested user; teams have no login
+#[derive(Debug, Deserialize)]
+pub struct GraphQlRequestedReviewer {
+    pub login: Option<String>,
+    /// `org/team` of teams, when the query asks for it
+    #[serde(default, rename = "combinedSlug")]
+    pub combined_slug: Option<String>,
+}
+
+impl GraphQlRequestedReviewer {
+    /// The user's login, or `org/team`
+    fn name(self) -> Option<String> {
+        self.login.or(self.combined_slug)
+    }
+}
+
+impl From<Option<GraphQlAuthor>> for Author {
+    fn from(author: Option<GraphQlAuthor>) -> Self {
+        match author {
+            Some(author) => Author {
+                login: author.login,
+                user_type: Some(author.typename),
+            },
+            None => Author {
+                login: "ghost".to_string(),
+                user_type: None,
+            },
+        }
+    }
+}
+
+impl GraphQlIssue {
+    /// The issue and its comments, as fetched from the REST API
+    pub fn into_issue(self) -> (Issue, Vec<Comment>) {
+        let login = |login: String| Author {
+            login,
+            user_type: None,
+        };
+        let issue = Issue {
+            number: self.number,
+            title: self.title,
+            body: self.body,
+            state: self.state,
+            author: self.author.into(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            labels: self.labels.nodes,
+            url: self.url,
+            comments: CommentCount {
+                total_count: self.comments.total_count,
+            },
+            is_pull_request: self.typename == "PullRequest",
+            assignees: self
+                .assignees
+                .nodes
+                .into_iter()
+                .map(|a| login(a.login))
+                .collect(),
+            requested_reviewers: self
+                .review_requests
+                .map(|requests| requests.nodes)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|r| r.requested_reviewer?.login)
+                .map(login)
+                .collect(),
+            milestone: self.milestone,
+            author_association: self.author_association,
+        };
+        let comments = self
+            .comments
+            .nodes
+            .into_iter()
+            .map(|c| Comment {
+                id: c.database_id,
+                body: c.body,
+                author: c.author.into(),
+                created_at: c.created_at,
+                updated_at: c.updated_at,
+            })
+            .collect();
+        (issue, comments)
+    }
+}
+
+/// A GitHub Projects (v2) board with its issues and PRs
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectBoard {
+    pub title: String,
+    pub url: String,
+    /// Options of the status field, in the order of the board's columns
+    pub statuses: Vec<String>,
+    pub items: Vec<ProjectItem>,
+}
+
+/// An issue or PR on a project board; draft items are left out
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectItem {
+    pub repo: String,
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    pub is_pull_request: bool,
+    pub state: IssueState,
+    /// Value of the status field, `None` when unset
+    pub status: Option<String>,
+}
+
+/// GraphQL response with one page of a project's items, under `owner`
+#[derive(Debug, Deserialize)]
+pub struct ProjectResponse {
+    pub data: ProjectData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectData {
+    pub owner: Option<ProjectOwnerNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectOwnerNode {
+    pub project_v2: Option<GraphQlProject>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlProject {
+    pub title: String,
+    pub url: String,
+    /// `None` when the board has no field of that name; empty for fields
+    /// other than single select ones
+    pub field: Option<GraphQlStatusField>,
+    pub items: GraphQlPage<GraphQlProjectItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlStatusField {
+    #[serde(default)]
+    pub options: Vec<GraphQlStatusOption>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlStatusOption {
+    pub name: String,
+}
+
+/// Nodes of a paginated GraphQL connection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlPage<T> {
+    pub page_info: GraphQlPageInfo,
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlPageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlProjectItem {
+    pub field_value_by_name: Option<GraphQlStatusValue>,
+    pub content: Option<GraphQlProjectContent>,
+}
+
+/// Value of the status field; other field types have no name
+#[derive(Debug, Deserialize)]
+pub struct GraphQlStatusValue {
+    pub name: Option<String>,
+}
+
+/// An issue, PR or draft issue on a board; drafts only have a title
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlProjectContent {
+    #[serde(rename = "__typename")]
+    pub typename: String,
+    pub number: Option<u32>,
+    pub title: String,
+    pub url: Option<String>,
+    pub state: Option<IssueState>,
+    pub repository: Option<GraphQlRepositoryName>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlRepositoryName {
+    pub name_with_owner: String,
+}
+
+impl GraphQlProjectItem {
+    /// The issue or PR of the item, `None` for drafts
+    pub fn into_item(self) -> Option<ProjectItem> {
+        let content = self.content?;
+        Some(ProjectItem {
+            repo: content.repository?.name_with_owner,
+            number: content.number?,
+            title: content.title,
+            url: content.url?,
+            is_pull_request: content.typename == "PullRequest",
+            state: content.state?,
+            status: self.field_value_by_name.and_then(|value| value.name),
+        })
+    }
+}
+
 /// Repository information
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Repository {
     pub name: String,
     pub owner: Owner,
-    #[serde(rename = "nameWithOwner")]
+    // The REST API uses snake_case names for these fields
+    #[serde(rename = "nameWithOwner", alias = "full_name")]
     pub full_name: String,
     pub description: Option<String>,
-    #[serde(rename = "isPrivate")]
+    #[serde(rename = "isPrivate", alias = "private")]
     pub is_private: bool,
-    #[serde(rename = "isArchived")]
+    #[serde(rename = "isArchived", alias = "archived")]
     pub is_archived: bool,
-    #[serde(rename = "pushedAt")]
+    #[serde(rename = "pushedAt", alias = "pushed_at")]
     pub pushed_at: Option<Timestamp>,
     #[serde(rename = "defaultBranchRef")]
     pub default_branch: Option<BranchRef>,
@@ -120,6 +851,58 @@ pub struct Notification {
     pub subject: NotificationSubject,
 }
 
+impl Notification {
+    /// Number of the issue or PR the notification is about
+    ///
+    /// `None` for other subjects, such as releases, discussions, or CI runs.
+    pub fn item_number(&self) -> Option<u32> {
+        if !matches!(self.subject.subject_type.as_str(), "Issue" | "PullRequest") {
+            return None;
+        }
+        self.subject
+            .url
+            .as_deref()?
+            .rsplit('/')
+            .next()?
+            .parse()
+            .ok()
+    }
+
+    pub fn is_pull_request(&self) -> bool {
+        self.subject.subject_type == "PullRequest"
+    }
+
+    /// Web URL of the issue or PR, on `host` (`settings.github_host`)
+    pub fn html_url(&self, host: &str) -> Option<String> {
+        let kind = if self.is_pull_request() {
+            "pull"
+        } else {
+            "issues"
+        };
+        Some(format!(
+            "https://{}/{}/{}/{}",
+            host,
+            self.repository.full_name,
+            kind,
+            self.item_number()?
+        ))
+    }
+
+    /// Why the notification was sent, in words
+    pub fn describe_reason(&self) -> &str {
+        match self.reason.as_str() {
+            "review_requested" => "review requested",
+            "mention" => "mentioned",
+            "team_mention" => "team mentioned",
+            "assign" => "assigned",
+            "author" => "author",
+            "comment" => "commented",
+            "subscribed" => "watching",
+            other => other,
+        }
+    }
+}
+
 /// Repository info in notification
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NotificationRepo {
@@ -156,7 +939,7 @@ pub enum RepoStatus {
 }
 
 /// Activity summary for a repository
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct RepoActivity {
     pub new_issues: Vec<Issue>,
     pub new_prs: Vec<Issue>,
@@ -165,6 +948,10 @@ pub struct RepoActivity {
     pub merged_prs: Vec<Issue>,
     pub closed_issues: Vec<Issue>,
     pub new_comments: Vec<(Issue, Vec<Comment>)>,
+    /// Reviews for pull requests, keyed by PR number
+    pub reviews: BTreeMap<u32, Vec<Review>>,
+    /// Head commits of pull requests with requested changes, keyed by PR number
+    pub head_shas: BTreeMap<u32, String>,
 }
 
 /// REST API Issue representation (for deserialization from gh api)
@@ -193,6 +980,167 @@ pub struct RestIssue {
     pub issue_dependencies_summary: Option<serde_json::Value>,
     #[serde(default)]
     pub state_reason: Option<String>,
+    #[serde(default)]
+    pub assignees: Vec<RestUser>,
+    // Only present on the pulls endpoint
+    #[serde(default)]
+    pub requested_reviewers: Vec<RestUser>,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    #[serde(default)]
+    pub author_association: Option<String>,
+}
+
+/// REST API pull request representation, used for PR-only fields
+#[derive(Debug, Clone, Deserialize)]
+pub struct RestPullRequest {
+    pub number: u32,
+    #[serde(default)]
+    pub requested_reviewers: Vec<RestUser>,
+    #[serde(default)]
+    pub head: Option<RestPullRequestHead>,
+}
+
+/// The branch and commit a pull request proposes to merge
+#[derive(Debug, Clone, Deserialize)]
+pub struct RestPullRequestHead {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub sha: String,
+}
+
+/// A GitHub Actions workflow run
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    /// Workflow name
+    pub name: Option<String>,
+    pub html_url: String,
+    pub head_sha: String,
+    pub conclusion: Option<String>,
+    pub created_at: Timestamp,
+}
+
+/// REST API list of workflow runs
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowRunList {
+    pub workflow_runs: Vec<WorkflowRun>,
+}
+
+/// A check run on a commit, as reported by GitHub Actions and other CI apps
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CheckRun {
+    pub id: u64,
+    pub name: String,
+    /// `queued`, `in_progress`, or `completed`
+    pub status: String,
+    /// Set once completed, e.g. `success`, `failure`, or `skipped`
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+}
+
+impl CheckRun {
+    fn failed(&self) -> bool {
+        matches!(
+            self.conclusion.as_deref(),
+            Some("failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure")
+        )
+    }
+}
+
+/// REST API list of check runs
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckRunList {
+    pub check_runs: Vec<CheckRun>,
+}
+
+/// Overall CI state of a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiState {
+    Passing,
+    Failing,
+    Pending,
+}
+
+impl CiState {
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            CiState::Passing => "✅",
+            CiState::Failing => "❌",
+            CiState::Pending => "🟡",
+        }
+    }
+}
+
+/// The CI state of a PR's head commit, from its check runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiStatus {
+    pub state: CiState,
+    /// Names of the failed check runs
+    pub failing: Vec<String>,
+}
+
+impl CiStatus {
+    /// Failing when any check failed, pending while any is still running,
+    /// and passing otherwise; `None` for commits without checks
+    pub fn of(runs: &[CheckRun]) -> Option<Self> {
+        if runs.is_empty() {
+            return None;
+        }
+        let failing: Vec<String> = runs
+            .iter()
+            .filter(|run| run.failed())
+            .map(|run| run.name.clone())
+            .collect();
+        let state = if !failing.is_empty() {
+            CiState::Failing
+        } else if runs.iter().any(|run| run.status != "completed") {
+            CiState::Pending
+        } else {
+            CiState::Passing
+        };
+        Some(CiStatus { state, failing })
+    }
+}
+
+/// A published release of a repository
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    /// Release title, which GitHub falls back to the tag for when empty
+    pub name: Option<String>,
+    /// Release notes in markdown
+    pub body: Option<String>,
+    pub html_url: String,
+    pub author: Option<Author>,
+    pub published_at: Option<Timestamp>,
+    #[serde(default)]
+    pub prerelease: bool,
+}
+
+impl Release {
+    /// The release title, or its tag when it has none
+    pub fn title(&self) -> &str {
+        self.name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.tag_name)
+    }
+}
+
+/// An open milestone of a repository, with its progress
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RepoMilestone {
+    pub number: u32,
+    pub title: String,
+    pub html_url: String,
+    pub due_on: Option<Timestamp>,
+    #[serde(default)]
+    pub open_issues: u32,
+    #[serde(default)]
+    pub closed_issues: u32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -202,6 +1150,15 @@ pub struct RestUser {
     pub user_type: Option<String>,
 }
 
+impl From<RestUser> for Author {
+    fn from(user: RestUser) -> Self {
+        Author {
+            login: user.login,
+            user_type: user.user_type,
+        }
+    }
+}
+
 impl From<RestIssue> for Issue {
     fn from(rest: RestIssue) -> Self {
         Issue {
@@ -220,10 +1177,7 @@ impl From<RestIssue> for Issue {
                 }
                 _ => IssueState::Closed,
             },
-            author: Author {
-                login: rest.user.login,
-                user_type: rest.user.user_type,
-            },
+            author: rest.user.into(),
             created_at: rest.created_at,
             updated_at: rest.updated_at,
             labels: rest.labels,
@@ -232,6 +1186,14 @@ impl From<RestIssue> for Issue {
                 total_count: rest.comments,
             },
             is_pull_request: rest.pull_request.is_some(),
+            assignees: rest.assignees.into_iter().map(Into::into).collect(),
+            requested_reviewers: rest
+                .requested_reviewers
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            milestone: rest.milestone,
+            author_association: rest.author_association,
         }
     }
 }
@@ -240,6 +1202,116 @@ impl From<RestIssue> for Issue {
 mod tests {
     use super::*;
 
+    fn event(created_at: Timestamp) -> ActivityEvent {
+        ActivityEvent {
+            id: "1".to_string(),
+            event_type: "IssueCommentEvent".to_string(),
+            actor: Author {
+                login: "alice".to_string(),
+                user_type: None,
+            },
+            repo: ActivityRepo {
+                id: 1,
+                name: "owner/repo".to_string(),
+                url: String::new(),
+            },
+            payload: serde_json::json!({}),
+            created_at,
+            is_public: true,
+        }
+    }
+
+    #[test]
+    fn test_activity_feed_truncation() {
+        let now: Timestamp = "2024-03-08T12:00:00Z".parse().unwrap();
+        let cutoff = now - 168.hours();
+        let recent = now - 24.hours();
+
+        // A short feed simply has no older events
+        let feed = ActivityFeed::new(vec![event(recent), event(now - 200.hours())], cutoff, now);
+        assert_eq!(feed.truncated_at, None);
+        assert_eq!(feed.events.len(), 1);
+
+        // A full feed that ends inside the window is missing the rest
+        let events = (0..EVENTS_API_LIMIT as i64)
+            .map(|i| event(recent - i.minutes()))
+            .collect();
+        let feed = ActivityFeed::new(events, cutoff, now);
+        assert_eq!(
+            feed.truncated_at,
+            Some(recent - (EVENTS_API_LIMIT as i64 - 1).minutes())
+        );
+        assert_eq!(feed.events.len(), EVENTS_API_LIMIT);
+
+        // GitHub keeps no events older than 30 days
+        let feed = ActivityFeed::new(vec![event(recent)], now - (60 * 24).hours(), now);
+        assert_eq!(feed.truncated_at, Some(recent));
+    }
+
+    #[test]
+    fn test_ci_status() {
+        let run = |status: &str, conclusion: Option<&str>| CheckRun {
+            id: 1,
+            name: "test".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            html_url: None,
+        };
+        assert_eq!(CiStatus::of(&[]), None);
+        let state = |runs: &[CheckRun]| CiStatus::of(runs).unwrap().state;
+        assert_eq!(
+            state(&[
+                run("completed", Some("success")),
+                run("completed", Some("skipped"))
+            ]),
+            CiState::Passing
+        );
+        assert_eq!(
+            state(&[run("completed", Some("success")), run("in_progress", None)]),
+            CiState::Pending
+        );
+        assert_eq!(
+            state(&[
+                run("in_progress", None),
+                run("completed", Some("timed_out"))
+            ]),
+            CiState::Failing
+        );
+    }
+
+    #[test]
+    fn test_notification_item() {
+        let notification: Notification = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "unread": true,
+            "reason": "review_requested",
+            "updated_at": "2024-03-06T12:00:00Z",
+            "repository": { "full_name": "tokio-rs/tokio" },
+            "subject": {
+                "title": "Add task hooks",
+                "type": "PullRequest",
+                "url": "https://api.github.com/repos/tokio-rs/tokio/pulls/6234"
+            }
+        }))
+        .unwrap();
+        assert_eq!(notification.item_number(), Some(6234));
+        assert_eq!(
+            notification.html_url("github.com").as_deref(),
+            Some("https://github.com/tokio-rs/tokio/pull/6234")
+        );
+        assert_eq!(
+            notification.html_url("github.example.com").as_deref(),
+            Some("https://github.example.com/tokio-rs/tokio/pull/6234")
+        );
+        assert_eq!(notification.describe_reason(), "review requested");
+
+        let mut release = notification.clone();
+        release.subject.subject_type = "Release".to_string();
+        release.subject.url =
+            Some("https://api.github.com/repos/tokio-rs/tokio/releases/1".to_string());
+        assert_eq!(release.item_number(), None);
+    }
+
     #[test]
     fn test_issue_serialization() {
         let issue = Issue {
@@ -261,6 +1333,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 5 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         };
 
         // Test serialization
@@ -274,6 +1350,37 @@ mod tests {
         assert_eq!(issue.title, issue2.title);
     }
 
+    #[test]
+    fn test_rest_issue_assignees() {
+        let json = r#"{
+            "number": 7,
+            "title": "Add feature",
+            "body": null,
+            "state": "open",
+            "user": {"login": "author", "type": "User"},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z",
+            "labels": [],
+            "html_url": "https://github.com/test/repo/pull/7",
+            "comments": 0,
+            "pull_request": {},
+            "assignees": [{"login": "alice", "type": "User"}],
+            "requested_reviewers": [{"login": "Bob", "type": "User"}],
+            "milestone": {"title": "v1.0", "due_on": "2024-02-01T08:00:00Z"}
+        }"#;
+
+        let rest: RestIssue = serde_json::from_str(json).unwrap();
+        let issue: Issue = rest.into();
+
+        assert!(issue.is_assigned_to("alice"));
+        assert!(!issue.is_assigned_to("bob"));
+        assert!(issue.is_review_requested_from("bob"));
+
+        let milestone = issue.milestone.unwrap();
+        assert_eq!(milestone.title, "v1.0");
+        assert!(milestone.due_on.is_some());
+    }
+
     #[test]
     fn test_issue_state() {
         let states = vec![IssueState::Open, IssueState::Closed, IssueState::Merged];
@@ -317,6 +1424,25 @@ mod tests {
         assert_eq!(comment.author.login, comment2.author.login);
     }
 
+    #[test]
+    fn test_rest_repository() {
+        let json = r#"{
+            "name": "secret",
+            "full_name": "acme/secret",
+            "owner": {"login": "acme"},
+            "description": null,
+            "private": true,
+            "archived": false,
+            "pushed_at": "2024-01-15T10:00:00Z",
+            "default_branch": "main"
+        }"#;
+
+        let repo: Repository = serde_json::from_str(json).unwrap();
+        assert_eq!(repo.full_name, "acme/secret");
+        assert!(repo.is_private);
+        assert!(repo.default_branch.is_none());
+    }
+
     #[test]
     fn test_repo_status() {
         assert_eq!(RepoStatus::Active, RepoStatus::Active);
@@ -327,6 +1453,173 @@ mod tests {
         let status: RepoStatus = serde_json::from_str(&json).unwrap();
         assert_eq!(status, RepoStatus::Inaccessible);
     }
+
+    #[test]
+    fn test_review_threads_response() {
+        let json = r#"{"data": {"repository": {"pullRequest": {"reviewThreads": {
+            "nodes": [{
+                "isResolved": false,
+                "isOutdated": false,
+                "path": "tokio/src/runtime/mod.rs",
+                "line": 42,
+                "comments": {"totalCount": 5, "nodes": [
+                    {"author": {"login": "reviewer"}, "body": "\nShould this use `spawn_blocking`?\nIt blocks."},
+                    {"author": null, "body": "Fixed"}
+                ]}
+            }]
+        }}}}}"#;
+
+        let response: ReviewThreadsResponse = serde_json::from_str(json).unwrap();
+        let thread: ReviewThread = response
+            .data
+            .repository
+            .and_then(|r| r.pull_request)
+            .unwrap()
+            .review_threads
+            .nodes
+            .remove(0)
+            .into();
+
+        assert!(!thread.is_resolved);
+        assert!(thread.is_long_running());
+        assert_eq!(thread.location(), "tokio/src/runtime/mod.rs:42");
+        assert_eq!(thread.topic(), "Should this use `spawn_blocking`?");
+        assert_eq!(thread.participants(), ["reviewer", "ghost"]);
+    }
+
+    #[test]
+    fn test_issue_batch_response() {
+        let json = r#"{"data": {
+            "i0": {"issueOrPullRequest": {
+                "__typename": "PullRequest",
+                "number": 42,
+                "title": "Add io_uring support",
+                "body": null,
+                "state": "MERGED",
+                "author": {"login": "carllerche", "__typename": "User"},
+                "createdAt": "2024-03-01T10:00:00Z",
+                "updatedAt": "2024-03-06T10:00:00Z",
+                "url": "https://github.com/tokio-rs/tokio/pull/42",
+                "labels": {"nodes": [{"name": "A-io", "color": "fbca04", "description": null}]},
+                "assignees": {"nodes": [{"login": "Darksonn"}]},
+                "milestone": {"title": "1.38", "dueOn": null},
+                "comments": {"totalCount": 31, "nodes": [{
+                    "databaseId": 1001,
+                    "body": "Looks good",
+                    "author": null,
+                    "createdAt": "2024-03-05T10:00:00Z",
+                    "updatedAt": "2024-03-05T10:00:00Z"
+                }]},
+                "reviewRequests": {"nodes": [
+                    {"requestedReviewer": {"login": "hawkw"}},
+                    {"requestedReviewer": {}}
+                ]}
+            }},
+            "i1": {"issueOrPullRequest": null}
+        }}"#;
+
+        let mut response: IssueBatchResponse = serde_json::from_str(json).unwrap();
+        assert!(response.data["i1"]
+            .as_ref()
+            .unwrap()
+            .issue_or_pull_request
+            .is_none());
+        let (issue, comments) = response
+            .data
+            .remove("i0")
+            .flatten()
+            .and_then(|r| r.issue_or_pull_request)
+            .unwrap()
+            .into_issue();
+
+        assert!(issue.is_pull_request);
+        assert_eq!(issue.state, IssueState::Merged);
+        assert_eq!(issue.author.user_type.as_deref(), Some("User"));
+        assert_eq!(issue.comments.total_count, 31);
+        assert!(issue.is_assigned_to("darksonn"));
+        assert!(issue.is_review_requested_from("hawkw"));
+        assert_eq!(issue.requested_reviewers.len(), 1);
+        assert_eq!(issue.repository_name().as_deref(), Some("tokio-rs/tokio"));
+        assert_eq!(
+            (comments[0].id, comments[0].author.login.as_str()),
+            (1001, "ghost")
+        );
+    }
+
+    #[test]
+    fn test_project_response() {
+        let json = r#"{"data": {"owner": {"projectV2": {
+            "title": "Tokio roadmap",
+            "url": "https://github.com/orgs/tokio-rs/projects/5",
+            "field": {"options": [{"name": "Todo"}, {"name": "In Progress"}, {"name": "Done"}]},
+            "items": {
+                "pageInfo": {"hasNextPage": true, "endCursor": "Y3Vyc29yOjEwMA=="},
+                "nodes": [
+                    {
+                        "fieldValueByName": {"name": "In Progress"},
+                        "content": {
+                            "__typename": "PullRequest",
+                            "number": 42,
+                            "title": "Add io_uring support",
+                            "url": "https://github.com/tokio-rs/tokio/pull/42",
+                            "state": "OPEN",
+                            "repository": {"nameWithOwner": "tokio-rs/tokio"}
+                        }
+                    },
+                    {"fieldValueByName": null, "content": {"__typename": "DraftIssue", "title": "Idea"}},
+                    {"fieldValueByName": {}, "content": null}
+                ]
+            }
+        }}}}"#;
+
+        let response: ProjectResponse = serde_json::from_str(json).unwrap();
+        let project = response.data.owner.unwrap().project_v2.unwrap();
+        assert_eq!(project.field.unwrap().options[1].name, "In Progress");
+        assert!(project.items.page_info.has_next_page);
+        let items: Vec<ProjectItem> = project
+            .items
+            .nodes
+            .into_iter()
+            .filter_map(GraphQlProjectItem::into_item)
+            .collect();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].repo, "tokio-rs/tokio");
+        assert!(items[0].is_pull_request);
+        assert_eq!(items[0].status.as_deref(), Some("In Progress"));
+    }
+
+    #[test]
+    fn test_discussions_response() {
+        let json = r#"{"data": {"repository": {"discussions": {"nodes": [{
+            "number": 42,
+            "title": "Roadmap for 2.0",
+            "body": "What should go into 2.0?",
+            "url": "https://github.com/tokio-rs/tokio/discussions/42",
+            "author": {"login": "carllerche"},
+            "category": {"name": "Ideas"},
+            "isAnswered": null,
+            "createdAt": "2024-03-01T10:00:00Z",
+            "updatedAt": "2024-03-06T10:00:00Z",
+            "comments": {"totalCount": 12, "nodes": [
+                {"author": null, "body": "io_uring", "createdAt": "2024-03-06T10:00:00Z"}
+            ]}
+        }]}}}}"#;
+
+        let response: DiscussionsResponse = serde_json::from_str(json).unwrap();
+        let discussion: Discussion = response
+            .data
+            .repository
+            .unwrap()
+            .discussions
+            .nodes
+            .remove(0)
+            .into();
+
+        assert_eq!(discussion.category, "Ideas");
+        assert!(!discussion.is_answered);
+        assert_eq!(discussion.comment_count, 12);
+        assert_eq!(discussion.comments[0].author, "ghost");
+    }
 }
 
 /// PR file change information
@@ -349,6 +1642,24 @@ pub struct PrDiff {
     pub total_files: u32,
 }
 
+impl PrDiff {
+    /// Drop patches once they add up to more than `max_patch_bytes`
+    ///
+    /// Every file keeps its line counts, so the totals stay right.
+    pub fn cap_patches(&mut self, max_patch_bytes: usize) {
+        let mut size = 0;
+        for file in &mut self.files {
+            let Some(patch) = &file.patch else {
+                continue;
+            };
+            size += patch.len();
+            if size > max_patch_bytes {
+                file.patch = None;
+            }
+        }
+    }
+}
+
 /// GitHub activity event
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ActivityEvent {
@@ -371,3 +1682,68 @@ pub struct ActivityRepo {
     pub name: String,
     pub url: String,
 }
+
+/// Most events the events API returns, however far back they go
+pub const EVENTS_API_LIMIT: usize = 300;
+
+/// Days of history the events API keeps
+pub const EVENTS_API_MAX_DAYS: i64 = 30;
+
+/// The activity feed events within a time window
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ActivityFeed {
+    pub events: Vec<ActivityEvent>,
+    /// Start of the requested window
+    pub cutoff: Timestamp,
+    /// Set when GitHub's limits cut the feed short; events before it are missing
+    pub truncated_at: Option<Timestamp>,
+}
+
+impl ActivityFeed {
+    /// Keep the events since `cutoff` and check whether the feed reached back that far
+    ///
+    /// The events API returns at most [`EVENTS_API_LIMIT`] events of the last
+    /// [`EVENTS_API_MAX_DAYS`] days. When either limit is hit before the
+    /// oldest returned event, the span between `cutoff` and that event is
+    /// missing.
+    pub fn new(events: Vec<ActivityEvent>, cutoff: Timestamp, now: Timestamp) -> Self {
+        let oldest = events.iter().map(|e| e.created_at).min();
+        let hit_limit =
+            events.len() >= EVENTS_API_LIMIT || cutoff < now - (EVENTS_API_MAX_DAYS * 24).hours();
+        let truncated_at = oldest.filter(|oldest| *oldest > cutoff && hit_limit);
+
+        ActivityFeed {
+            events: events
+                .into_iter()
+                .filter(|event| event.created_at >= cutoff)
+                .collect(),
+            cutoff,
+            truncated_at,
+        }
+    }
+
+    /// Combine several feeds of the same window, most recent event first
+    ///
+    /// Events in more than one feed are kept once. The combined feed is
+    /// missing events before the latest point any of the feeds was cut short.
+    pub fn merge(cutoff: Timestamp, feeds: impl IntoIterator<Item = ActivityFeed>) -> Self {
+        let mut events: Vec<ActivityEvent> = Vec::new();
+        let mut truncated_at = None;
+        let mut seen = std::collections::HashSet::new();
+        for feed in feeds {
+            truncated_at = truncated_at.max(feed.truncated_at);
+            events.extend(
+                feed.events
+                    .into_iter()
+                    .filter(|event| seen.insert(event.id.clone())),
+            );
+        }
+        events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+        ActivityFeed {
+            events,
+            cutoff,
+            truncated_at,
+        }
+    }
+}
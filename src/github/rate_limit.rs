@@ -0,0 +1,158 @@
+//! Awareness of the GitHub REST API quota
+//!
+//! The quota is read from `gh api rate_limit`, which doesn't count against
+//! it, before the first gh call and then every [`CHECK_EVERY_CALLS`] calls.
+//! Below the configured threshold, fetches the report can do without (such
+//! as PR diffs) are skipped so the quota is left for the ones it needs.
+
+use anyhow::{anyhow, Result};
+use jiff::Timestamp;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use tracing::warn;
+
+/// gh calls between two quota checks
+pub const CHECK_EVERY_CALLS: u32 = 50;
+
+/// The REST API quota at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    /// When the quota is refilled
+    pub reset: Timestamp,
+}
+
+impl RateLimit {
+    /// Parse the output of `gh api rate_limit`
+    pub fn parse(json: &str) -> Result<Self> {
+        let response: RateLimitResponse = serde_json::from_str(json)?;
+        let core = response.resources.core;
+        Ok(RateLimit {
+            limit: core.limit,
+            remaining: core.remaining,
+            reset: Timestamp::from_second(core.reset)
+                .map_err(|e| anyhow!("Invalid rate limit reset time: {}", e))?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    resources: RateLimitResources,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResources {
+    core: CoreRateLimit,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoreRateLimit {
+    limit: u32,
+    remaining: u32,
+    /// Unix time in seconds
+    reset: i64,
+}
+
+/// Tracks the latest known quota and decides when to check it again
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Remaining requests below which optional fetches are skipped
+    threshold: u32,
+    calls_since_check: AtomicU32,
+    latest: Mutex<Option<RateLimit>>,
+    warned: AtomicBool,
+}
+
+impl RateLimiter {
+    pub fn new(threshold: u32) -> Self {
+        RateLimiter {
+            threshold,
+            // Check before the first call
+            calls_since_check: AtomicU32::new(CHECK_EVERY_CALLS),
+            latest: Mutex::new(None),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: u32) {
+        self.threshold = threshold;
+    }
+
+    /// Count a gh call, returning whether the quota is due to be checked
+    pub fn record_call(&self) -> bool {
+        let calls = self.calls_since_check.fetch_add(1, Ordering::Relaxed);
+        if calls + 1 >= CHECK_EVERY_CALLS {
+            self.calls_since_check.store(0, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    /// Store a new reading, warning the first time it is below the threshold
+    pub fn update(&self, rate_limit: RateLimit) {
+        *self.latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(rate_limit);
+        if rate_limit.remaining < self.threshold && !self.warned.swap(true, Ordering::Relaxed) {
+            warn!(
+                "Only {} of {} GitHub API requests left until {}; skipping optional fetches such as PR diffs",
+                rate_limit.remaining, rate_limit.limit, rate_limit.reset
+            );
+        }
+    }
+
+    pub fn latest(&self) -> Option<RateLimit> {
+        *self.latest.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether there is quota to spare for fetches the report can do without
+    ///
+    /// Before the first reading, the quota is assumed to be fine.
+    pub fn allows_optional(&self) -> bool {
+        self.latest()
+            .is_none_or(|rate_limit| rate_limit.remaining >= self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let json = r#"{
+            "resources": {
+                "core": { "limit": 5000, "used": 4890, "remaining": 110, "reset": 1709722800 },
+                "search": { "limit": 30, "used": 0, "remaining": 30, "reset": 1709719260 }
+            },
+            "rate": { "limit": 5000, "used": 4890, "remaining": 110, "reset": 1709722800 }
+        }"#;
+        let rate_limit = RateLimit::parse(json).unwrap();
+        assert_eq!(rate_limit.remaining, 110);
+        assert_eq!(rate_limit.reset.to_string(), "2024-03-06T11:00:00Z");
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(500);
+        assert!(limiter.record_call());
+        assert!((1..CHECK_EVERY_CALLS).all(|_| !limiter.record_call()));
+        assert!(limiter.record_call());
+
+        assert!(limiter.allows_optional());
+        let reset = "2024-03-06T11:00:00Z".parse().unwrap();
+        limiter.update(RateLimit {
+            limit: 5000,
+            remaining: 499,
+            reset,
+        });
+        assert!(!limiter.allows_optional());
+        limiter.update(RateLimit {
+            limit: 5000,
+            remaining: 5000,
+            reset,
+        });
+        assert!(limiter.allows_optional());
+    }
+}
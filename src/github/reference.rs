@@ -11,6 +11,8 @@ pub struct IssueReference {
     pub number: u32,
     /// Whether this is a pull request (vs issue)
     pub is_pull_request: Option<bool>, // None means unknown from reference
+    /// Host named by a URL reference; `None` for shorthand, which is on the configured host
+    pub host: Option<String>,
 }
 
 impl IssueReference {
@@ -32,7 +34,8 @@ impl IssueReference {
             None => "issues", // Default to issues if unknown
         };
         format!(
-            "https://github.com/{}/{}/{}",
+            "https://{}/{}/{}/{}",
+            self.host.as_deref().unwrap_or("github.com"),
             self.repo_name(),
             item_type,
             self.number
@@ -40,12 +43,67 @@ impl IssueReference {
     }
 }
 
+/// A GitHub Projects (v2) board, e.g. `https://github.com/orgs/tokio-rs/projects/5`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectReference {
+    /// Whether the board belongs to an organization rather than a user
+    pub is_org: bool,
+    pub owner: String,
+    pub number: u32,
+}
+
+/// Parse the URL of an organization's or user's project board
+///
+/// Links to a view of the board (`.../projects/5/views/2`) are taken too.
+/// Any host is accepted, for boards on GitHub Enterprise Server.
+pub fn parse_project_url(url: &str) -> Result<ProjectReference> {
+    let invalid = || {
+        anyhow!(
+            "Invalid project URL: {}. Expected https://github.com/orgs/OWNER/projects/NUMBER \
+             or https://github.com/users/OWNER/projects/NUMBER",
+            url
+        )
+    };
+    let (_, path) = url
+        .trim()
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split_once('/'))
+        .ok_or_else(invalid)?;
+    let parts: Vec<&str> = path.split('/').collect();
+    let (kind, owner, number) = match parts.as_slice() {
+        [kind, owner, "projects", number, ..] => (*kind, *owner, *number),
+        _ => return Err(invalid()),
+    };
+    let is_org = match kind {
+        "orgs" => true,
+        "users" => false,
+        _ => return Err(invalid()),
+    };
+    let number = number.parse::<u32>().map_err(|_| invalid())?;
+    if owner.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(ProjectReference {
+        is_org,
+        owner: owner.to_string(),
+        number,
+    })
+}
+
 /// Parse various formats of GitHub issue/PR references
 pub fn parse_issue_reference(input: &str) -> Result<IssueReference> {
+    parse_issue_reference_on(input, "github.com")
+}
+
+/// Parse an issue/PR reference, taking URLs of github.com and of `host`
+///
+/// `host` is the GitHub Enterprise Server of `settings.github_host`.
+pub fn parse_issue_reference_on(input: &str, host: &str) -> Result<IssueReference> {
     let input = input.trim();
 
     // Try parsing as full URL first
-    if let Ok(reference) = parse_github_url(input) {
+    if let Ok(reference) = parse_github_url(input, host) {
         return Ok(reference);
     }
 
@@ -57,13 +115,42 @@ pub fn parse_issue_reference(input: &str) -> Result<IssueReference> {
     Err(anyhow!("Invalid issue reference format. Expected URL (https://github.com/owner/repo/issues/123) or shorthand (owner/repo#123)"))
 }
 
-/// Parse a full GitHub URL
-fn parse_github_url(url: &str) -> Result<IssueReference> {
-    if !url.starts_with("https://github.com/") {
-        return Err(anyhow!("Not a GitHub URL"));
+/// `owner/repo` of a github.com or GitHub Enterprise Server URL
+///
+/// Takes web URLs and REST API URLs (`https://api.github.com/repos/owner/repo`,
+/// `https://HOST/api/v3/repos/owner/repo`). Web URLs of hosts other than
+/// github.com are only taken when they point to an issue or PR, which is
+/// how the URLs gh returns from an enterprise server look.
+pub fn repo_from_url(url: &str) -> Option<String> {
+    let (host, path) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?
+        .split_once('/')?;
+    let api_path = if host.eq_ignore_ascii_case("api.github.com") {
+        path.strip_prefix("repos/")
+    } else {
+        path.strip_prefix("api/v3/repos/")
+    };
+
+    let mut parts = api_path.unwrap_or(path).split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    let is_item = matches!(parts.next(), Some("issues" | "pull"));
+    if api_path.is_none() && !host.eq_ignore_ascii_case("github.com") && !is_item {
+        return None;
     }
+    Some(format!("{}/{}", owner, repo))
+}
 
-    let path = url.strip_prefix("https://github.com/").unwrap();
+/// Parse a full GitHub URL, of github.com or `enterprise_host`
+fn parse_github_url(url: &str, enterprise_host: &str) -> Result<IssueReference> {
+    let (host, path) = url
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split_once('/'))
+        .filter(|(host, _)| {
+            host.eq_ignore_ascii_case("github.com") || host.eq_ignore_ascii_case(enterprise_host)
+        })
+        .ok_or_else(|| anyhow!("Not a GitHub URL"))?;
     let parts: Vec<&str> = path.split('/').collect();
 
     if parts.len() < 4 {
@@ -92,6 +179,7 @@ fn parse_github_url(url: &str) -> Result<IssueReference> {
         repo,
         number,
         is_pull_request,
+        host: Some(host.to_lowercase()),
     })
 }
 
@@ -133,6 +221,7 @@ fn parse_shorthand_reference(input: &str) -> Result<IssueReference> {
         repo,
         number,
         is_pull_request: None,
+        host: None,
     })
 }
 
@@ -140,6 +229,31 @@ fn parse_shorthand_reference(input: &str) -> Result<IssueReference> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_project_url() {
+        let reference = parse_project_url("https://github.com/orgs/tokio-rs/projects/5").unwrap();
+        assert_eq!(
+            reference,
+            ProjectReference {
+                is_org: true,
+                owner: "tokio-rs".to_string(),
+                number: 5,
+            }
+        );
+
+        let reference =
+            parse_project_url("https://github.com/users/carllerche/projects/2/views/1").unwrap();
+        assert!(!reference.is_org);
+        assert_eq!(
+            (reference.owner.as_str(), reference.number),
+            ("carllerche", 2)
+        );
+
+        assert!(parse_project_url("https://github.com/tokio-rs/tokio/projects/5").is_err());
+        assert!(parse_project_url("https://github.com/orgs/tokio-rs/projects/x").is_err());
+        assert!(parse_project_url("tokio-rs/5").is_err());
+    }
+
     #[test]
     fn test_parse_github_issue_url() {
         let url = "https://github.com/tokio-rs/tokio/issues/7546";
@@ -216,6 +330,47 @@ mod tests {
         assert!(parse_issue_reference("123").is_err());
     }
 
+    #[test]
+    fn test_enterprise_host() {
+        let url = "https://GitHub.Example.com/platform/api/pull/42";
+        assert!(parse_issue_reference(url).is_err());
+
+        let reference = parse_issue_reference_on(url, "github.example.com").unwrap();
+        assert_eq!(reference.repo_name(), "platform/api");
+        assert_eq!(reference.host.as_deref(), Some("github.example.com"));
+        assert_eq!(
+            reference.url(),
+            "https://github.example.com/platform/api/pull/42"
+        );
+        // github.com stays readable, so a mismatch can be reported
+        let reference = parse_issue_reference_on(
+            "https://github.com/tokio-rs/tokio/issues/1",
+            "github.example.com",
+        )
+        .unwrap();
+        assert_eq!(reference.host.as_deref(), Some("github.com"));
+        assert!(parse_issue_reference_on(
+            "https://gitlab.com/owner/repo/issues/123",
+            "github.example.com"
+        )
+        .is_err());
+
+        assert_eq!(
+            repo_from_url("https://github.example.com/platform/api/issues/7").as_deref(),
+            Some("platform/api")
+        );
+        assert_eq!(
+            repo_from_url("https://github.example.com/api/v3/repos/platform/api").as_deref(),
+            Some("platform/api")
+        );
+        assert_eq!(
+            repo_from_url("https://api.github.com/repos/tokio-rs/tokio").as_deref(),
+            Some("tokio-rs/tokio")
+        );
+        assert_eq!(repo_from_url("github.com/tokio-rs"), None);
+        assert_eq!(repo_from_url("https://example.com/foo/bar"), None);
+    }
+
     #[test]
     fn test_reference_url_generation() {
         // Issue reference (unknown type)
@@ -224,6 +379,7 @@ mod tests {
             repo: "repo".to_string(),
             number: 123,
             is_pull_request: None,
+            host: None,
         };
         assert_eq!(reference.url(), "https://github.com/owner/repo/issues/123");
 
@@ -233,6 +389,7 @@ mod tests {
             repo: "repo".to_string(),
             number: 123,
             is_pull_request: Some(false),
+            host: None,
         };
         assert_eq!(reference.url(), "https://github.com/owner/repo/issues/123");
 
@@ -242,6 +399,7 @@ mod tests {
             repo: "repo".to_string(),
             number: 123,
             is_pull_request: Some(true),
+            host: None,
         };
         assert_eq!(reference.url(), "https://github.com/owner/repo/pull/123");
     }
@@ -0,0 +1,275 @@
+//! External commands run before and after reports, from `[hooks]`
+//!
+//! A hook is a command line whose `{placeholder}`s are filled in after it is
+//! split into words, and which then runs without a shell, so a path with
+//! spaces stays one argument. A hook that fails or runs longer than
+//! `hooks.timeout_secs` fails the run, with the end of what it wrote to
+//! stderr.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::config::expand_tilde;
+
+/// How often a running hook is checked on
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lines of stderr shown when a hook fails
+const STDERR_LINES: usize = 20;
+
+/// When a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    /// Before the report is generated; what it prints goes into the report
+    PreReport,
+    /// After the report is saved, once for each file
+    PostReport,
+}
+
+impl HookKind {
+    /// The key of the hooks in `[hooks]`
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookKind::PreReport => "pre_report",
+            HookKind::PostReport => "post_report",
+        }
+    }
+}
+
+/// Values filled in for the `{placeholder}`s of hook arguments
+#[derive(Debug, Clone, Default)]
+pub struct HookVars {
+    vars: BTreeMap<&'static str, String>,
+}
+
+impl HookVars {
+    pub fn new() -> Self {
+        HookVars::default()
+    }
+
+    /// Set `{name}` to `value`
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.vars.insert(name, value.to_string());
+        self
+    }
+
+    /// Set `{name}` to a path
+    pub fn with_path(self, name: &'static str, path: &Path) -> Self {
+        self.with(name, path.display())
+    }
+
+    /// Fill in the placeholders of one argument
+    ///
+    /// `{{` and `}}` stand for literal braces. Unknown placeholders are an
+    /// error rather than passed on as they are.
+    fn expand(&self, arg: &str) -> Result<String> {
+        let mut expanded = String::with_capacity(arg.len());
+        let mut rest = arg;
+        while let Some(i) = rest.find(['{', '}']) {
+            expanded.push_str(&rest[..i]);
+            let brace = &rest[i..i + 1];
+            rest = &rest[i + 1..];
+            if let Some(after) = rest.strip_prefix(brace) {
+                expanded.push_str(brace);
+                rest = after;
+                continue;
+            }
+            if brace == "}" {
+                bail!("Unmatched `}}` in `{}`", arg);
+            }
+            let (name, after) = rest
+                .split_once('}')
+                .ok_or_else(|| anyhow!("Unclosed `{{` in `{}`", arg))?;
+            let value = self.vars.get(name).ok_or_else(|| {
+                let known: Vec<String> = self.vars.keys().map(|k| format!("{{{}}}", k)).collect();
+                anyhow!(
+                    "Unknown placeholder `{{{}}}`; available are {}",
+                    name,
+                    known.join(", ")
+                )
+            })?;
+            expanded.push_str(value);
+            rest = after;
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+
+    /// Split `command` into a program and its arguments, filling in placeholders
+    fn command_line(&self, command: &str) -> Result<Vec<String>> {
+        let words = shlex::split(command).ok_or_else(|| anyhow!("Unbalanced quotes"))?;
+        if words.is_empty() {
+            bail!("The command is empty");
+        }
+        words.iter().map(|word| self.expand(word)).collect()
+    }
+}
+
+/// What a hook printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutput {
+    pub command: String,
+    pub stdout: String,
+}
+
+/// Run the hooks of `kind` in order, stopping at the first that fails
+pub fn run_hooks(
+    kind: HookKind,
+    commands: &[String],
+    vars: &HookVars,
+    timeout: Duration,
+) -> Result<Vec<HookOutput>> {
+    commands
+        .iter()
+        .map(|command| {
+            let stdout = run_hook(command, vars, timeout)
+                .with_context(|| format!("The {} hook `{}` failed", kind.name(), command))?;
+            Ok(HookOutput {
+                command: command.clone(),
+                stdout,
+            })
+        })
+        .collect()
+}
+
+/// Run one hook, returning what it printed to stdout
+fn run_hook(command: &str, vars: &HookVars, timeout: Duration) -> Result<String> {
+    let words = vars.command_line(command)?;
+    let program = expand_tilde(Path::new(&words[0]))?;
+    info!("Running hook: {}", words.join(" "));
+
+    let mut child = Command::new(&program)
+        .args(&words[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start {}", program.display()))?;
+    // Read both pipes while waiting, so a chatty hook can't fill one and block
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for the hook")? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            // Kill errors mean it just exited, which is as good
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(
+                "Timed out after {}s and was stopped (see `hooks.timeout_secs`)",
+                timeout.as_secs()
+            );
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let collect = |reader: Option<thread::JoinHandle<String>>| {
+        reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default()
+    };
+    let stdout = collect(stdout);
+    let stderr = collect(stderr);
+    debug!("Hook exited with {}", status);
+    if !status.success() {
+        // Without a code, it was killed by a signal
+        let exit = match status.code() {
+            Some(code) => format!("Exited with code {}", code),
+            None => format!("Stopped ({})", status),
+        };
+        let lines: Vec<&str> = stderr.trim_end().lines().collect();
+        let tail = lines[lines.len().saturating_sub(STDERR_LINES)..].join("\n");
+        if tail.is_empty() {
+            bail!("{}", exit);
+        }
+        bail!("{}:\n{}", exit, tail);
+    }
+    Ok(stdout)
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_line() {
+        let vars = HookVars::new()
+            .with("report_path", "/tmp/My Reports/2024-05-01.md")
+            .with("title", "Weekly rollup");
+
+        assert_eq!(
+            vars.command_line("./publish.sh {report_path} --title='{title}' {{raw}}")
+                .unwrap(),
+            [
+                "./publish.sh",
+                "/tmp/My Reports/2024-05-01.md",
+                "--title=Weekly rollup",
+                "{raw}"
+            ]
+        );
+
+        let error = vars.command_line("upload {report}").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown placeholder `{report}`; available are {report_path}, {title}"
+        );
+        assert!(vars.command_line("upload {title").is_err());
+        assert!(vars.command_line("upload 'unbalanced").is_err());
+        assert!(vars.command_line("  ").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_hooks() {
+        let vars = HookVars::new().with("name", "world");
+        let timeout = Duration::from_secs(10);
+
+        let outputs = run_hooks(
+            HookKind::PreReport,
+            &["echo hello {name}".to_string()],
+            &vars,
+            timeout,
+        )
+        .unwrap();
+        assert_eq!(outputs[0].stdout, "hello world\n");
+
+        let error = run_hooks(
+            HookKind::PostReport,
+            &["sh -c 'echo broken >&2; exit 3'".to_string()],
+            &vars,
+            timeout,
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "The post_report hook `sh -c 'echo broken >&2; exit 3'` failed: \
+             Exited with code 3:\nbroken"
+        );
+
+        let error = run_hooks(
+            HookKind::PostReport,
+            &["sleep 5".to_string()],
+            &vars,
+            Duration::from_secs(1),
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", error).contains("Timed out after 1s and was stopped"));
+    }
+}
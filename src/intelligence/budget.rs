@@ -0,0 +1,184 @@
+//! Limits on how much of the activity goes into one report
+//!
+//! `settings.max_issues_per_report` and `settings.max_comments_per_report`
+//! bound both the report and the prompts built from it. What doesn't fit is
+//! recorded so the report can say what it left out.
+
+use crate::config::CommentWindow;
+use crate::github::{Comment, Issue};
+
+/// What a report left out to stay within its budget
+#[derive(Debug, Clone, Default)]
+pub struct Truncation {
+    /// Issues and PRs before any were left out
+    pub total_items: usize,
+    /// The `(repo, issue)` pairs left out, highest score first
+    pub skipped: Vec<(String, Issue)>,
+    /// Comments kept per item, when comments had to be capped
+    pub comments_per_item: Option<usize>,
+    /// Older comments left out, and on how many items
+    pub skipped_comments: usize,
+    pub trimmed_items: usize,
+    /// Comments kept per thread and how they were picked, when threads
+    /// were longer than `settings.max_comments_per_issue`
+    pub comment_window: Option<(usize, CommentWindow)>,
+    /// Comments left out of those threads, and how many threads that was
+    pub windowed_comments: usize,
+    pub windowed_items: usize,
+}
+
+impl Truncation {
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty() && self.skipped_comments == 0 && self.windowed_comments == 0
+    }
+}
+
+/// Words suggesting a comment records a decision or a change in status
+const DECISION_WORDS: &[&str] = &[
+    "decided",
+    "agreed",
+    "blocked",
+    "blocking",
+    "lgtm",
+    "approve",
+    "fixed",
+    "duplicate",
+    "regression",
+    "workaround",
+    "root cause",
+    "released",
+];
+
+/// At most `max` of a thread's `comments`, picked by `window`, in the order
+/// given; all of them when `max` is 0
+///
+/// `comments` are expected oldest first.
+pub fn window_comments(comments: &[Comment], max: usize, window: CommentWindow) -> Vec<&Comment> {
+    if max == 0 || comments.len() <= max {
+        return comments.iter().collect();
+    }
+
+    match window {
+        CommentWindow::Latest => comments[comments.len() - max..].iter().collect(),
+        CommentWindow::FirstAndLatest => {
+            let first = max / 2;
+            comments[..first]
+                .iter()
+                .chain(&comments[comments.len() - (max - first)..])
+                .collect()
+        }
+        CommentWindow::AiRelevant => {
+            // The latest comment always stays, as it says where things stand
+            let (latest, earlier) = comments.split_last().expect("more comments than max");
+            let mut ranked: Vec<usize> = (0..earlier.len()).collect();
+            ranked.sort_by_key(|&i| std::cmp::Reverse((relevance(&earlier[i]), i)));
+            let mut kept: Vec<usize> = ranked.into_iter().take(max - 1).collect();
+            kept.sort_unstable();
+            kept.into_iter()
+                .map(|i| &earlier[i])
+                .chain(std::iter::once(latest))
+                .collect()
+        }
+    }
+}
+
+/// How much a comment is likely to add to a summary of its thread
+fn relevance(comment: &Comment) -> usize {
+    if comment.author.is_bot() {
+        return 0;
+    }
+
+    let body = comment.body.to_lowercase();
+    let mut score = body.split_whitespace().count().min(100) / 5;
+    if body.contains("```") {
+        score += 5;
+    }
+    if DECISION_WORDS.iter().any(|word| body.contains(word)) {
+        score += 5;
+    }
+    if body.contains('?') {
+        score += 2;
+    }
+    score
+}
+
+/// The most comments every item can keep for `counts` to add up to at most
+/// `max`, or `None` when they already do (or `max` is 0)
+pub(super) fn comment_cap(counts: &[usize], max: usize) -> Option<usize> {
+    if max == 0 || counts.iter().sum::<usize>() <= max {
+        return None;
+    }
+
+    let mut counts = counts.to_vec();
+    counts.sort_unstable();
+    let mut remaining = max;
+    for (i, &count) in counts.iter().enumerate() {
+        let items_left = counts.len() - i;
+        if count * items_left > remaining {
+            return Some(remaining / items_left);
+        }
+        remaining -= count;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Author;
+    use jiff::Timestamp;
+
+    #[test]
+    fn test_comment_cap() {
+        assert_eq!(comment_cap(&[3, 4], 10), None);
+        assert_eq!(comment_cap(&[3, 4], 0), None);
+        // The short thread keeps all of its comments, the long ones share the rest
+        assert_eq!(comment_cap(&[2, 50, 30], 20), Some(9));
+        assert_eq!(comment_cap(&[10, 10], 5), Some(2));
+    }
+
+    #[test]
+    fn test_window_comments() {
+        let comment = |id: u64, login: &str, body: &str| Comment {
+            id,
+            body: body.to_string(),
+            author: Author {
+                login: login.to_string(),
+                user_type: None,
+            },
+            created_at: Timestamp::UNIX_EPOCH,
+            updated_at: Timestamp::UNIX_EPOCH,
+        };
+        let comments = vec![
+            comment(
+                1,
+                "alice",
+                "The runtime panics on shutdown, see the trace below",
+            ),
+            comment(2, "bob", "+1"),
+            comment(
+                3,
+                "ci[bot]",
+                "Build failed: this PR is blocked until CI is fixed",
+            ),
+            comment(4, "carol", "We agreed to revert the change for now"),
+            comment(5, "bob", "Same here"),
+            comment(6, "alice", "Thanks!"),
+        ];
+        let ids = |window: CommentWindow, max: usize| -> Vec<u64> {
+            window_comments(&comments, max, window)
+                .iter()
+                .map(|c| c.id)
+                .collect()
+        };
+
+        assert_eq!(ids(CommentWindow::Latest, 0), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(ids(CommentWindow::Latest, 10), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(ids(CommentWindow::Latest, 3), [4, 5, 6]);
+        assert_eq!(ids(CommentWindow::FirstAndLatest, 3), [1, 5, 6]);
+        assert_eq!(ids(CommentWindow::FirstAndLatest, 1), [6]);
+        // The decision outranks the bot, whose comment is skipped like noise
+        assert_eq!(ids(CommentWindow::AiRelevant, 3), [1, 4, 6]);
+        assert_eq!(ids(CommentWindow::AiRelevant, 1), [6]);
+    }
+}
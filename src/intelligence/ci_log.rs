@@ -0,0 +1,134 @@
+use crate::github::{Issue, WorkflowRun};
+
+/// Markers that usually sit at the root cause of a failed job
+const ERROR_MARKERS: &[&str] = &[
+    "error[",
+    "error:",
+    "panicked at",
+    "assertion failed",
+    "failed:",
+    "failures:",
+    "test result: failed",
+    "npm err!",
+    "traceback (most recent call last)",
+    "exception",
+];
+
+/// Lines kept before the first error marker
+const CONTEXT_BEFORE: usize = 3;
+
+/// Maximum number of lines in an extracted excerpt
+pub const MAX_EXCERPT_LINES: usize = 20;
+
+/// A failed workflow run on a pull request in the report
+#[derive(Debug, Clone)]
+pub struct CiFailure {
+    pub repo: String,
+    pub pr: Issue,
+    pub run: WorkflowRun,
+    /// The part of the job log around the first error
+    pub excerpt: Option<String>,
+    /// One-line explanation of the probable cause
+    pub explanation: Option<String>,
+}
+
+/// Extract the region of a failed job log that most likely shows the error
+///
+/// Expects the output of `gh run view --log-failed`, whose lines are prefixed
+/// with the job name, step name, and a timestamp. Starts a few lines before
+/// the first error marker; when there is none, falls back to the log tail,
+/// skipping the generic "Process completed with exit code" trailer.
+pub fn extract_error_region(log: &str) -> Option<String> {
+    let lines: Vec<String> = log
+        .lines()
+        .map(|line| strip_ansi(strip_log_prefix(line)))
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| !line.contains("Process completed with exit code"))
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    let start = lines
+        .iter()
+        .position(|line| {
+            let lower = line.to_lowercase();
+            ERROR_MARKERS.iter().any(|m| lower.contains(m))
+        })
+        .map(|i| i.saturating_sub(CONTEXT_BEFORE))
+        .unwrap_or_else(|| lines.len().saturating_sub(MAX_EXCERPT_LINES));
+
+    let excerpt: Vec<&str> = lines[start..]
+        .iter()
+        .take(MAX_EXCERPT_LINES)
+        .map(|l| l.trim_end())
+        .collect();
+    Some(excerpt.join("\n"))
+}
+
+/// Drop the `job<TAB>step<TAB>timestamp ` prefix of `gh run view --log` lines
+fn strip_log_prefix(line: &str) -> &str {
+    let mut fields = line.splitn(3, '\t');
+    let (Some(_job), Some(_step), Some(rest)) = (fields.next(), fields.next(), fields.next())
+    else {
+        return line;
+    };
+
+    match rest.split_once(' ') {
+        Some((timestamp, message)) if timestamp.parse::<jiff::Timestamp>().is_ok() => message,
+        _ => rest,
+    }
+}
+
+/// Remove ANSI color escape sequences
+fn strip_ansi(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip until the final byte of the CSI sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_error_region() {
+        let log = "\
+build\tRun cargo test\t2024-01-15T10:00:00.0000000Z    Compiling tokio v1.36.0
+build\tRun cargo test\t2024-01-15T10:00:01.0000000Z     Running unittests
+build\tRun cargo test\t2024-01-15T10:00:02.0000000Z test rt::shutdown ... ok
+build\tRun cargo test\t2024-01-15T10:00:03.0000000Z test rt::spawn ... FAILED
+build\tRun cargo test\t2024-01-15T10:00:04.0000000Z \u{1b}[31mthread 'rt::spawn' panicked at src/rt.rs:42:5\u{1b}[0m
+build\tRun cargo test\t2024-01-15T10:00:05.0000000Z ##[error]Process completed with exit code 101.";
+
+        let excerpt = extract_error_region(log).unwrap();
+        // Starts a few lines before the panic
+        assert_eq!(excerpt.lines().next().unwrap().trim(), "Running unittests");
+        assert!(excerpt.contains("thread 'rt::spawn' panicked at src/rt.rs:42:5"));
+        assert!(!excerpt.contains("\u{1b}"));
+        assert!(!excerpt.contains("2024-01-15T10"));
+        assert!(!excerpt.contains("exit code 101"));
+    }
+
+    #[test]
+    fn test_extract_error_region_without_markers() {
+        let log: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+        let excerpt = extract_error_region(&log).unwrap();
+        assert_eq!(excerpt.lines().count(), MAX_EXCERPT_LINES);
+        assert!(excerpt.ends_with("line 49"));
+
+        assert_eq!(extract_error_region(""), None);
+    }
+}
@@ -1,5 +1,9 @@
 use crate::config::Importance;
-use crate::intelligence::{ActionItem, PrioritizedIssue, Urgency};
+use crate::github::{Comment, Issue};
+use crate::intelligence::{ActionItem, Excerpt, PrioritizedIssue, Urgency};
+
+/// Longest excerpt quoted under an action item, in characters
+pub const EXCERPT_MAX_CHARS: usize = 200;
 
 /// Build simple context prompt for AI summarization
 pub fn build_context_prompt() -> String {
@@ -36,12 +40,13 @@ pub fn extract_action_items(prioritized_issues: &[PrioritizedIssue]) -> Vec<Acti
                 repo: issue.repo.clone(),
                 urgency,
                 reason,
+                excerpt: None,
             });
         }
     }
 
     // Sort by urgency (highest first)
-    action_items.sort_by(|a, b| b.urgency.cmp(&a.urgency));
+    action_items.sort_by_key(|a| std::cmp::Reverse(a.urgency));
 
     // Limit to top 10 action items
     action_items.truncate(10);
@@ -49,18 +54,102 @@ pub fn extract_action_items(prioritized_issues: &[PrioritizedIssue]) -> Vec<Acti
     action_items
 }
 
+/// Quote the latest comment of an item, or its description when no comment says anything
+pub fn find_excerpt(issue: &Issue, comments: &[Comment]) -> Option<Excerpt> {
+    let mut comments: Vec<&Comment> = comments.iter().collect();
+    comments.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+
+    let from_comment = comments.into_iter().find_map(|comment| {
+        Some(Excerpt {
+            author: comment.author.login.clone(),
+            text: sanitize_excerpt(&comment.body)?,
+            url: format!("{}#issuecomment-{}", issue.url, comment.id),
+        })
+    });
+    from_comment.or_else(|| {
+        Some(Excerpt {
+            author: issue.author.login.clone(),
+            text: sanitize_excerpt(issue.body.as_deref()?)?,
+            url: issue.url.clone(),
+        })
+    })
+}
+
+/// Reduce markdown to one line of plain prose, truncated to `EXCERPT_MAX_CHARS`
+///
+/// Returns `None` when nothing is left.
+fn sanitize_excerpt(body: &str) -> Option<String> {
+    let text = plain_text(body);
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= EXCERPT_MAX_CHARS {
+        return Some(text);
+    }
+
+    let truncated: String = text.chars().take(EXCERPT_MAX_CHARS).collect();
+    // Cut at a word boundary when there is one
+    let truncated = truncated
+        .rsplit_once(' ')
+        .map_or(truncated.as_str(), |(head, _)| head);
+    Some(format!("{}…", truncated.trim_end()))
+}
+
+/// The prose of a markdown comment on one line
+///
+/// Drops HTML comments (issue template hints), code blocks, and quoted
+/// replies, which say little out of context.
+pub(super) fn plain_text(body: &str) -> String {
+    let mut text = String::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("<!--") {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("-->")
+            .map_or("", |end| &rest[start + end + 3..]);
+    }
+    text.push_str(rest);
+
+    let mut in_code = false;
+    let mut words = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || line.starts_with('>') {
+            continue;
+        }
+        words.extend(
+            line.trim_start_matches('#')
+                .split_whitespace()
+                .map(|word| word.replace('`', "")),
+        );
+    }
+    words.join(" ")
+}
+
 /// Determine urgency level for an issue
 fn determine_urgency(issue: &PrioritizedIssue) -> Urgency {
+    urgency_with_reason(issue).0
+}
+
+/// The urgency of an item, with the rule that decided it
+pub(super) fn urgency_with_reason(issue: &PrioritizedIssue) -> (Urgency, &'static str) {
     // Check for critical indicators based on labels
     if issue.issue.labels.iter().any(|l| {
         let name = l.name.to_lowercase();
         name.contains("security") || name.contains("critical")
     }) {
-        return Urgency::Critical;
+        return (Urgency::Critical, "labeled security or critical");
     }
 
     if issue.importance == Importance::Critical && issue.score.total > 80 {
-        return Urgency::Critical;
+        return (
+            Urgency::Critical,
+            "critical repository and a score above 80",
+        );
     }
 
     // Check for high urgency based on labels and score
@@ -68,19 +157,19 @@ fn determine_urgency(issue: &PrioritizedIssue) -> Urgency {
         let name = l.name.to_lowercase();
         name.contains("breaking") || name.contains("bug") || name.contains("urgent")
     }) {
-        return Urgency::High;
+        return (Urgency::High, "labeled breaking, bug, or urgent");
     }
 
     if issue.score.total > 60 {
-        return Urgency::High;
+        return (Urgency::High, "score above 60");
     }
 
     // Check for medium urgency
     if issue.score.total > 30 {
-        return Urgency::Medium;
+        return (Urgency::Medium, "score above 30");
     }
 
-    Urgency::Low
+    (Urgency::Low, "score of 30 or less")
 }
 
 /// Generate action description for an issue with full context
@@ -224,6 +313,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 0 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         };
 
         let prioritized = vec![PrioritizedIssue {
@@ -236,6 +329,8 @@ mod tests {
                 activity_score: 0,
                 rule_match_score: 30,
                 label_score: 0,
+                involvement_score: 0,
+                newcomer_score: 0,
             },
             importance: Importance::High,
         }];
@@ -248,6 +343,45 @@ mod tests {
         assert!(actions[0].reason.contains("Security concern"));
     }
 
+    #[test]
+    fn test_find_excerpt() {
+        let mut issue = crate::test_utils::create_test_issue(7, "Panic on shutdown", false);
+        issue.body = Some(
+            "<!-- Describe the bug -->\n## Bug\nThe runtime panics when `shutdown` runs twice.\n\n```\nthread 'main' panicked\n```"
+                .to_string(),
+        );
+        let excerpt = find_excerpt(&issue, &[]).unwrap();
+        assert_eq!(
+            excerpt.text,
+            "Bug The runtime panics when shutdown runs twice."
+        );
+        assert_eq!(excerpt.url, issue.url);
+
+        let comment = |id, body: &str, at: &str| Comment {
+            id,
+            body: body.to_string(),
+            author: Author {
+                login: format!("user{}", id),
+                user_type: None,
+            },
+            created_at: at.parse().unwrap(),
+            updated_at: at.parse().unwrap(),
+        };
+        let comments = [
+            comment(1, "First look", "2024-03-01T00:00:00Z"),
+            comment(2, &"word ".repeat(100), "2024-03-02T00:00:00Z"),
+            comment(3, "> quoted only", "2024-03-03T00:00:00Z"),
+        ];
+        let excerpt = find_excerpt(&issue, &comments).unwrap();
+        assert_eq!(excerpt.author, "user2");
+        assert_eq!(excerpt.url, format!("{}#issuecomment-2", issue.url));
+        assert!(excerpt.text.ends_with("word…"));
+        assert!(excerpt.text.chars().count() <= EXCERPT_MAX_CHARS + 1);
+
+        issue.body = None;
+        assert!(find_excerpt(&issue, &comments[2..]).is_none());
+    }
+
     #[test]
     fn test_urgency_determination() {
         let issue = Issue {
@@ -265,6 +399,10 @@ mod tests {
             url: "https://github.com/test/repo/pull/100".to_string(),
             comments: CommentCount { total_count: 15 },
             is_pull_request: true,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         };
 
         let prioritized = PrioritizedIssue {
@@ -277,6 +415,8 @@ mod tests {
                 activity_score: 20,
                 rule_match_score: 0,
                 label_score: 10,
+                involvement_score: 0,
+                newcomer_score: 0,
             },
             importance: Importance::Medium,
         };
@@ -0,0 +1,399 @@
+use crate::github::{Issue, RepoActivity};
+use jiff::civil::{Date, Weekday};
+use jiff::tz::TimeZone;
+use jiff::ToSpan;
+use std::collections::{BTreeMap, HashSet};
+
+/// Words that mark a sentence as talking about a deadline or schedule
+const DEADLINE_KEYWORDS: &[&str] = &[
+    "deadline",
+    "due",
+    "release",
+    "cut",
+    "freeze",
+    "ship",
+    "launch",
+    "until",
+    "by",
+    "before",
+    "target",
+    "targeting",
+    "scheduled",
+    "planned",
+    "eta",
+    "cutoff",
+    "rc",
+];
+
+/// Words that turn a bare weekday ("by Friday") into a date
+const WEEKDAY_PREFIXES: &[&str] = &["on", "by", "this", "next", "until", "before", "due"];
+
+/// Maximum length of the sentence quoted in a deadline summary
+const MAX_SUMMARY_CHARS: usize = 80;
+
+/// Where a deadline was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlineSource {
+    /// The due date of a milestone
+    Milestone,
+    /// A date mentioned in an issue body or comment
+    Discussion,
+}
+
+/// A dated event worth putting on a calendar
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deadline {
+    pub date: Date,
+    pub summary: String,
+    pub description: String,
+    pub url: String,
+    pub source: DeadlineSource,
+}
+
+/// Collect upcoming deadlines from milestones and issue discussions
+///
+/// Dates before `today` are dropped. Relative mentions such as "by Friday"
+/// are resolved against the time the text was written, in `tz`.
+pub fn extract_deadlines(
+    activities: &BTreeMap<String, RepoActivity>,
+    today: Date,
+    tz: &TimeZone,
+) -> Vec<Deadline> {
+    let mut deadlines = Vec::new();
+    let mut seen_milestones = HashSet::new();
+    let mut seen_mentions = HashSet::new();
+
+    for (repo_name, activity) in activities {
+        let issues = activity
+            .new_issues
+            .iter()
+            .chain(&activity.updated_issues)
+            .chain(&activity.closed_issues)
+            .chain(&activity.new_prs)
+            .chain(&activity.updated_prs)
+            .chain(&activity.merged_prs);
+
+        for issue in issues {
+            if let Some(milestone) = &issue.milestone {
+                if let Some(due_on) = milestone.due_on {
+                    // GitHub stores due dates as a date at midnight Pacific,
+                    // so the UTC date is the one the user picked
+                    let date = due_on.to_zoned(TimeZone::UTC).date();
+                    if date >= today && seen_milestones.insert((repo_name, &milestone.title)) {
+                        deadlines.push(Deadline {
+                            date,
+                            summary: format!("{}: milestone {} due", repo_name, milestone.title),
+                            description: format!(
+                                "Milestone \"{}\" in {} is due.",
+                                milestone.title, repo_name
+                            ),
+                            url: format!("https://github.com/{}/milestones", repo_name),
+                            source: DeadlineSource::Milestone,
+                        });
+                    }
+                }
+            }
+
+            if let Some(body) = &issue.body {
+                let written = issue.created_at.to_zoned(tz.clone()).date();
+                push_mentions(
+                    &mut deadlines,
+                    &mut seen_mentions,
+                    repo_name,
+                    issue,
+                    body,
+                    written,
+                    today,
+                );
+            }
+        }
+
+        for (issue, comments) in &activity.new_comments {
+            for comment in comments {
+                let written = comment.created_at.to_zoned(tz.clone()).date();
+                push_mentions(
+                    &mut deadlines,
+                    &mut seen_mentions,
+                    repo_name,
+                    issue,
+                    &comment.body,
+                    written,
+                    today,
+                );
+            }
+        }
+    }
+
+    deadlines.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.summary.cmp(&b.summary)));
+    deadlines
+}
+
+/// Add a deadline for each dated sentence in `text`
+fn push_mentions(
+    deadlines: &mut Vec<Deadline>,
+    seen: &mut HashSet<(String, Date)>,
+    repo_name: &str,
+    issue: &Issue,
+    text: &str,
+    written: Date,
+    today: Date,
+) {
+    for sentence in text.split(['.', '!', '?', '\n']) {
+        let words = words(sentence);
+        if !words
+            .iter()
+            .any(|w| DEADLINE_KEYWORDS.contains(&w.as_str()))
+        {
+            continue;
+        }
+
+        for date in find_dates(&words, written) {
+            if date < today || !seen.insert((issue.url.clone(), date)) {
+                continue;
+            }
+            let sentence = sentence.trim();
+            deadlines.push(Deadline {
+                date,
+                summary: format!(
+                    "{}#{}: {}",
+                    repo_name,
+                    issue.number,
+                    truncate(sentence, MAX_SUMMARY_CHARS)
+                ),
+                description: format!("Mentioned in \"{}\": {}", issue.title, sentence),
+                url: issue.url.clone(),
+                source: DeadlineSource::Discussion,
+            });
+        }
+    }
+}
+
+/// Lowercased words of a sentence with surrounding punctuation removed
+fn words(sentence: &str) -> Vec<String> {
+    sentence
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric() && c != '-')
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Dates mentioned in a sentence, resolving partial dates against `reference`
+///
+/// Understands ISO dates (`2024-03-15`), month/day in either order with an
+/// optional year (`March 15`, `15th of Mar 2024`), `tomorrow`, and weekdays
+/// introduced by words like "by" or "next".
+fn find_dates(words: &[String], reference: Date) -> Vec<Date> {
+    let mut dates = Vec::new();
+
+    for (i, word) in words.iter().enumerate() {
+        let next = |offset: usize| words.get(i + offset).map(String::as_str);
+
+        let date = if let Some(date) = iso_date(word) {
+            Some(date)
+        } else if let Some(month) = month_number(word) {
+            next(1)
+                .and_then(day_number)
+                .and_then(|day| resolve_date(next(2).and_then(year_number), month, day, reference))
+        } else if let Some(day) = day_number(word) {
+            let offset = if next(1) == Some("of") { 2 } else { 1 };
+            next(offset).and_then(month_number).and_then(|month| {
+                resolve_date(
+                    next(offset + 1).and_then(year_number),
+                    month,
+                    day,
+                    reference,
+                )
+            })
+        } else if let Some(weekday) = weekday(word) {
+            let prefixed = i > 0 && WEEKDAY_PREFIXES.contains(&words[i - 1].as_str());
+            prefixed
+                .then(|| reference.nth_weekday(1, weekday).ok())
+                .flatten()
+        } else if word == "tomorrow" {
+            reference.tomorrow().ok()
+        } else {
+            None
+        };
+
+        if let Some(date) = date {
+            if !dates.contains(&date) {
+                dates.push(date);
+            }
+        }
+    }
+
+    dates
+}
+
+fn iso_date(word: &str) -> Option<Date> {
+    let bytes = word.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    word.parse().ok()
+}
+
+/// Build a date, picking the year when it was not written out
+///
+/// A month/day without a year means the next occurrence, unless it is only a
+/// few months back, in which case it most likely refers to the past.
+fn resolve_date(year: Option<i16>, month: i8, day: i8, reference: Date) -> Option<Date> {
+    if let Some(year) = year {
+        return Date::new(year, month, day).ok();
+    }
+
+    let date = Date::new(reference.year(), month, day).ok()?;
+    if date < reference - 183.days() {
+        Date::new(reference.year() + 1, month, day).ok()
+    } else {
+        Some(date)
+    }
+}
+
+fn month_number(word: &str) -> Option<i8> {
+    const MONTHS: &[&str] = &[
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    MONTHS
+        .iter()
+        .position(|m| {
+            *m == word
+                || (word.len() == 3 && m.starts_with(word))
+                || (word == "sept" && *m == "september")
+        })
+        .map(|i| i as i8 + 1)
+}
+
+fn day_number(word: &str) -> Option<i8> {
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .unwrap_or(word);
+    if digits.is_empty() || digits.len() > 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+fn year_number(word: &str) -> Option<i16> {
+    if word.len() != 4 || !word.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    word.parse().ok()
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "monday" => Some(Weekday::Monday),
+        "tuesday" => Some(Weekday::Tuesday),
+        "wednesday" => Some(Weekday::Wednesday),
+        "thursday" => Some(Weekday::Thursday),
+        "friday" => Some(Weekday::Friday),
+        "saturday" => Some(Weekday::Saturday),
+        "sunday" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let truncated: String = text.chars().take(max_chars - 3).collect();
+        format!("{}...", truncated.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Comment, Milestone};
+    use crate::test_utils::create_test_issue;
+    use jiff::Timestamp;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    fn dates_in(sentence: &str, reference: &str) -> Vec<Date> {
+        find_dates(&words(sentence), date(reference))
+    }
+
+    #[test]
+    fn test_find_dates() {
+        assert_eq!(
+            dates_in("Release planned for 2024-03-15", "2024-03-01"),
+            vec![date("2024-03-15")]
+        );
+        assert_eq!(
+            dates_in(
+                "Feature freeze on March 20th, then RC on 27 Mar",
+                "2024-03-01"
+            ),
+            vec![date("2024-03-20"), date("2024-03-27")]
+        );
+        assert_eq!(
+            dates_in("Target: 5th of January", "2024-12-10"),
+            vec![date("2025-01-05")]
+        );
+        // 2024-03-06 is a Wednesday
+        assert_eq!(
+            dates_in("RC cut on Friday", "2024-03-06"),
+            vec![date("2024-03-08")]
+        );
+        assert_eq!(dates_in("I tried it Friday", "2024-03-06"), vec![]);
+        assert_eq!(dates_in("Fixed in v1.2 by #123", "2024-03-06"), vec![]);
+    }
+
+    #[test]
+    fn test_extract_deadlines() {
+        let mut issue = create_test_issue(7, "Release 1.0", false);
+        issue.body = Some("Tracking issue. Nothing scheduled yet.".to_string());
+        issue.milestone = Some(Milestone {
+            title: "1.0".to_string(),
+            due_on: Some("2024-04-01T07:00:00Z".parse().unwrap()),
+        });
+        let written: Timestamp = "2024-03-06T12:00:00Z".parse().unwrap();
+        let comments = vec![Comment {
+            id: 1,
+            body: "Let's do the RC cut on Friday. I think 2024-01-01 was too early.".to_string(),
+            author: issue.author.clone(),
+            created_at: written,
+            updated_at: written,
+        }];
+
+        let mut activity = RepoActivity::default();
+        activity.updated_issues.push(issue.clone());
+        activity.new_comments.push((issue, comments));
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), activity);
+
+        let deadlines = extract_deadlines(&activities, date("2024-03-06"), &TimeZone::UTC);
+        assert_eq!(deadlines.len(), 2);
+
+        assert_eq!(deadlines[0].date, date("2024-03-08"));
+        assert_eq!(deadlines[0].source, DeadlineSource::Discussion);
+        assert_eq!(
+            deadlines[0].summary,
+            "test/repo#7: Let's do the RC cut on Friday"
+        );
+
+        assert_eq!(deadlines[1].date, date("2024-04-01"));
+        assert_eq!(deadlines[1].source, DeadlineSource::Milestone);
+    }
+}
@@ -0,0 +1,381 @@
+//! Comment threads with rising frustration or conflict
+//!
+//! Threads are either checked for heated wording with [`find_escalations`],
+//! or judged by Claude through `escalation_check_prompt`, whose answer
+//! [`parse_escalation_check`] reads.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::github::{Comment, Issue, RepoActivity};
+
+/// Most threads sent to Claude for an escalation check
+pub const MAX_ESCALATION_THREADS: usize = 10;
+
+/// New comments a thread needs before it is checked
+const MIN_THREAD_COMMENTS: usize = 3;
+
+/// Heated comments a thread needs before keywords flag it
+const MIN_HEATED_COMMENTS: usize = 2;
+
+/// Lowercase wording that reads as frustration or hostility
+const HEATED_PHRASES: &[&str] = &[
+    "unacceptable",
+    "ridiculous",
+    "absurd",
+    "waste of time",
+    "wasting my time",
+    "frustrated",
+    "frustrating",
+    "disappointed",
+    "useless",
+    "pathetic",
+    "are you kidding",
+    "this is a joke",
+    "wtf",
+    "nobody cares",
+    "no one cares",
+    "still not fixed",
+    "still broken",
+    "how hard can it be",
+    "you people",
+    "shut up",
+    "toxic",
+];
+
+/// How tense a thread has become
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EscalationLevel {
+    /// Someone is getting frustrated
+    Tense,
+    /// An argument, or frustration that keeps growing
+    Heated,
+}
+
+impl EscalationLevel {
+    fn parse(level: &str) -> Option<Self> {
+        match level.trim().to_uppercase().as_str() {
+            "TENSE" => Some(EscalationLevel::Tense),
+            "HEATED" => Some(EscalationLevel::Heated),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EscalationLevel::Tense => "🟠 Tense",
+            EscalationLevel::Heated => "🔴 Heated",
+        }
+    }
+}
+
+/// A thread that could use a calming reply
+#[derive(Debug, Clone, Serialize)]
+pub struct Escalation {
+    pub repo: String,
+    pub issue: Issue,
+    pub level: EscalationLevel,
+    /// Why the thread was flagged, in one sentence
+    pub reason: String,
+}
+
+/// The threads of `activities` with enough new comments to be checked,
+/// busiest first
+pub fn escalation_candidates(
+    activities: &BTreeMap<String, RepoActivity>,
+) -> Vec<(&str, &Issue, &[Comment])> {
+    let mut threads: Vec<(&str, &Issue, &[Comment])> = activities
+        .iter()
+        .flat_map(|(repo, activity)| {
+            activity
+                .new_comments
+                .iter()
+                .map(move |(issue, comments)| (repo.as_str(), issue, comments.as_slice()))
+        })
+        .filter(|(_, _, comments)| human_comments(comments).count() >= MIN_THREAD_COMMENTS)
+        .collect();
+    threads.sort_by_key(|(_, _, comments)| std::cmp::Reverse(comments.len()));
+    threads
+}
+
+/// Threads whose comments grow more heated, going by their wording
+///
+/// A thread is flagged when at least two comments read heated, one of the
+/// last three does, and the later half of the thread has at least as many as
+/// the earlier one. Heated comments from two or more people make it
+/// [`EscalationLevel::Heated`], as do three or more from anyone.
+pub fn find_escalations(activities: &BTreeMap<String, RepoActivity>) -> Vec<Escalation> {
+    let mut escalations: Vec<Escalation> = escalation_candidates(activities)
+        .into_iter()
+        .filter_map(|(repo, issue, comments)| {
+            let (level, reason) = detect_escalation(comments)?;
+            Some(Escalation {
+                repo: repo.to_string(),
+                issue: issue.clone(),
+                level,
+                reason,
+            })
+        })
+        .collect();
+    escalations.sort_by_key(|e| std::cmp::Reverse(e.level));
+    escalations
+}
+
+/// The level of a thread and why, when its comments read as escalating
+fn detect_escalation(comments: &[Comment]) -> Option<(EscalationLevel, String)> {
+    let mut comments: Vec<&Comment> = human_comments(comments).collect();
+    comments.sort_by_key(|c| c.created_at);
+
+    let heated: Vec<(usize, &Comment, Vec<&str>)> = comments
+        .iter()
+        .enumerate()
+        .filter_map(|(i, comment)| {
+            let cues = heat_cues(&comment.body);
+            (!cues.is_empty()).then_some((i, *comment, cues))
+        })
+        .collect();
+    let last = heated.last()?.0;
+    let later = heated
+        .iter()
+        .filter(|(i, _, _)| *i >= comments.len() / 2)
+        .count();
+    if heated.len() < MIN_HEATED_COMMENTS
+        || last + 3 < comments.len()
+        || later < heated.len() - later
+    {
+        return None;
+    }
+
+    let authors: BTreeSet<&str> = heated
+        .iter()
+        .map(|(_, comment, _)| comment.author.login.as_str())
+        .collect();
+    let mut cues: Vec<&str> = Vec::new();
+    for cue in heated.iter().flat_map(|(_, _, cues)| cues) {
+        if !cues.contains(cue) && cues.len() < 3 {
+            cues.push(cue);
+        }
+    }
+    let authors: Vec<String> = authors.iter().map(|a| format!("@{}", a)).collect();
+    let (level, who) = if authors.len() > 1 {
+        (
+            EscalationLevel::Heated,
+            format!("Heated exchange between {}", authors.join(" and ")),
+        )
+    } else {
+        let level = if heated.len() >= 3 {
+            EscalationLevel::Heated
+        } else {
+            EscalationLevel::Tense
+        };
+        (level, format!("{} is growing frustrated", authors[0]))
+    };
+    Some((
+        level,
+        format!(
+            "{} ({} heated comments: {})",
+            who,
+            heated.len(),
+            cues.join(", ")
+        ),
+    ))
+}
+
+/// What makes a comment read heated: quoted phrases, `shouting`, or
+/// `repeated exclamation marks`
+///
+/// Quotes and code blocks are skipped, so quoted messages and error output
+/// don't count.
+fn heat_cues(body: &str) -> Vec<&'static str> {
+    let mut in_code = false;
+    let mut prose = String::new();
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || line.trim_start().starts_with('>') {
+            continue;
+        }
+        prose.push_str(line);
+        prose.push('\n');
+    }
+
+    let lower = prose.to_lowercase();
+    let mut cues: Vec<&'static str> = HEATED_PHRASES
+        .iter()
+        .copied()
+        .filter(|phrase| lower.contains(phrase))
+        .collect();
+
+    let shouted = prose
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .fold((0, 0), |(run, longest), word| {
+            let loud = word.chars().filter(|c| c.is_alphabetic()).count() >= 2
+                && word.chars().all(|c| !c.is_lowercase());
+            let run = if loud { run + 1 } else { 0 };
+            (run, longest.max(run))
+        })
+        .1;
+    if shouted >= 3 {
+        cues.push("shouting");
+    }
+    if prose.contains("!!") || prose.contains("?!") {
+        cues.push("repeated exclamation marks");
+    }
+    cues
+}
+
+/// Comments not written by bots
+fn human_comments(comments: &[Comment]) -> impl Iterator<Item = &Comment> {
+    comments.iter().filter(|c| !c.author.is_bot())
+}
+
+/// Read Claude's judgment of `threads`
+///
+/// Expects one `<number>. <CALM|TENSE|HEATED>: <reason>` line per thread.
+/// Calm threads and lines that can't be read are left out.
+pub fn parse_escalation_check(
+    threads: &[(&str, &Issue, &[Comment])],
+    response: &str,
+) -> Vec<Escalation> {
+    let mut escalations: Vec<Escalation> = response
+        .lines()
+        .filter_map(|line| {
+            let (number, rest) = line.trim().split_once(". ")?;
+            let (repo, issue, _) = threads.get(number.parse::<usize>().ok()?.checked_sub(1)?)?;
+            let (level, reason) = rest.split_once(':')?;
+            let level = EscalationLevel::parse(level.trim_matches('*'))?;
+            Some(Escalation {
+                repo: repo.to_string(),
+                issue: (*issue).clone(),
+                level,
+                reason: reason.trim().to_string(),
+            })
+        })
+        .collect();
+    escalations.sort_by_key(|e| std::cmp::Reverse(e.level));
+    escalations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Author;
+    use crate::test_utils::create_test_issue;
+    use jiff::{Timestamp, ToSpan};
+
+    fn thread(comments: &[(&str, &str)]) -> Vec<Comment> {
+        let start = Timestamp::now() - 24.hours();
+        comments
+            .iter()
+            .enumerate()
+            .map(|(i, (login, body))| Comment {
+                id: i as u64,
+                body: body.to_string(),
+                author: Author {
+                    login: login.to_string(),
+                    user_type: None,
+                },
+                created_at: start + (i as i64).hours(),
+                updated_at: start + (i as i64).hours(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_escalations() {
+        let activity = RepoActivity {
+            new_comments: vec![
+                (
+                    create_test_issue(1, "Shutdown hangs", false),
+                    thread(&[
+                        ("alice", "Shutdown hangs on Linux."),
+                        ("bob", "Can you share a reproduction?"),
+                        ("alice", "This is still broken and frankly ridiculous."),
+                        (
+                            "bob",
+                            "Closing as you people never read the docs. Unacceptable tone.",
+                        ),
+                    ]),
+                ),
+                (
+                    create_test_issue(2, "Timer drift", false),
+                    thread(&[
+                        ("carol", "Timers drift by a few ms."),
+                        ("dave", "```\nERROR: TIMER DRIFT DETECTED!!\n```"),
+                        ("carol", "> this is ridiculous\nThanks, I'll try that."),
+                    ]),
+                ),
+                (
+                    create_test_issue(3, "Panic in poll", false),
+                    thread(&[
+                        ("erin", "Panic in poll."),
+                        ("frank", "Looking into it."),
+                        ("erin", "Any news? This is frustrating."),
+                        ("erin", "IT STILL PANICS ON EVERY RUN"),
+                    ]),
+                ),
+            ],
+            ..RepoActivity::default()
+        };
+        let activities = BTreeMap::from([("test/repo".to_string(), activity)]);
+
+        let escalations = find_escalations(&activities);
+        let flagged: Vec<(u32, EscalationLevel)> = escalations
+            .iter()
+            .map(|e| (e.issue.number, e.level))
+            .collect();
+        assert_eq!(
+            flagged,
+            [(1, EscalationLevel::Heated), (3, EscalationLevel::Tense)]
+        );
+        assert_eq!(
+            escalations[0].reason,
+            "Heated exchange between @alice and @bob (2 heated comments: ridiculous, still broken, unacceptable)"
+        );
+        assert_eq!(
+            escalations[1].reason,
+            "@erin is growing frustrated (2 heated comments: frustrating, shouting)"
+        );
+    }
+
+    #[test]
+    fn test_parse_escalation_check() {
+        let first = create_test_issue(1, "Shutdown hangs", false);
+        let second = create_test_issue(2, "Timer drift", false);
+        let third = create_test_issue(3, "Panic in poll", false);
+        let threads: Vec<(&str, &Issue, &[Comment])> = vec![
+            ("test/repo", &first, &[]),
+            ("test/repo", &second, &[]),
+            ("test/repo", &third, &[]),
+        ];
+        let response = "1. TENSE: The reporter is losing patience with the slow replies.\n\
+                        2. CALM: A friendly exchange.\n\
+                        3. **HEATED**: Two maintainers disagree sharply about the fix.\n\
+                        4. HEATED: No such thread.";
+
+        let escalations = parse_escalation_check(&threads, response);
+        let flagged: Vec<(u32, EscalationLevel, &str)> = escalations
+            .iter()
+            .map(|e| (e.issue.number, e.level, e.reason.as_str()))
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                (
+                    3,
+                    EscalationLevel::Heated,
+                    "Two maintainers disagree sharply about the fix."
+                ),
+                (
+                    1,
+                    EscalationLevel::Tense,
+                    "The reporter is losing patience with the slow replies."
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Why items ranked where they did in a report
+//!
+//! Every report records a [`ScoreExplanation`] per prioritized item in the
+//! state file, which `gh-report explain` prints back to show what went into
+//! an item's place in the report.
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::context::urgency_with_reason;
+use super::scoring::label_points;
+use super::{AnalysisResult, PrioritizedIssue, Urgency};
+use crate::config::Importance;
+
+/// The scored items of one report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunScores {
+    /// End of the report's window
+    pub generated_at: Timestamp,
+    /// Highest score first
+    pub items: Vec<ScoreExplanation>,
+}
+
+impl RunScores {
+    /// The explanation for `repo#number`, matching the repository case-insensitively
+    pub fn find(&self, repo: &str, number: u32) -> Option<&ScoreExplanation> {
+        self.items
+            .iter()
+            .find(|item| item.number == number && item.repo.eq_ignore_ascii_case(repo))
+    }
+}
+
+/// How one item was scored and ranked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    pub repo: String,
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    /// Position among the report's prioritized items, starting at 1
+    pub rank: usize,
+    pub total: u32,
+    /// The parts of `total`, in scoring order
+    pub components: Vec<ScoreComponent>,
+    pub importance: Importance,
+    /// Where `importance` came from
+    pub importance_source: String,
+    /// Watch rules the item matched
+    pub matched_rules: Vec<String>,
+    pub urgency: Urgency,
+    /// The rule that decided `urgency`
+    pub urgency_reason: String,
+    /// Whether the item was listed under the action items
+    pub action_item: bool,
+}
+
+/// One part of a priority score
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    pub name: String,
+    pub points: u32,
+    /// What the points were given for
+    pub reason: String,
+}
+
+/// Record how the items of `analysis` were scored, as seen at `now`
+pub fn explain_scores(
+    analysis: &AnalysisResult,
+    current_user: Option<&str>,
+    now: Timestamp,
+) -> RunScores {
+    let actions: HashSet<(&str, u32)> = analysis
+        .action_items
+        .iter()
+        .map(|action| (action.repo.as_str(), action.issue.number))
+        .collect();
+
+    let mut seen = HashSet::new();
+    let items = analysis
+        .prioritized_issues
+        .iter()
+        .filter(|item| seen.insert((item.repo.as_str(), item.issue.number)))
+        .enumerate()
+        .map(|(i, item)| {
+            let (urgency, urgency_reason) = urgency_with_reason(item);
+            let matched_rules = analysis
+                .matched_rules
+                .iter()
+                .find(|m| m.repo == item.repo && m.issue.number == item.issue.number)
+                .map(|m| m.rules.clone())
+                .unwrap_or_default();
+            ScoreExplanation {
+                repo: item.repo.clone(),
+                number: item.issue.number,
+                title: item.issue.title.clone(),
+                url: item.issue.url.clone(),
+                rank: i + 1,
+                total: item.score.total,
+                components: components(item, &matched_rules, current_user, now),
+                importance: item.importance,
+                importance_source: "default, as repositories have no configured importance"
+                    .to_string(),
+                matched_rules,
+                urgency,
+                urgency_reason: urgency_reason.to_string(),
+                action_item: actions.contains(&(item.repo.as_str(), item.issue.number)),
+            }
+        })
+        .collect();
+
+    RunScores {
+        generated_at: now,
+        items,
+    }
+}
+
+fn components(
+    item: &PrioritizedIssue,
+    matched_rules: &[String],
+    current_user: Option<&str>,
+    now: Timestamp,
+) -> Vec<ScoreComponent> {
+    let issue = &item.issue;
+    let score = &item.score;
+    let component = |name: &str, points: u32, reason: String| ScoreComponent {
+        name: name.to_string(),
+        points,
+        reason,
+    };
+
+    let age_hours = (now - issue.updated_at).get_hours().max(0);
+    let best_label = issue
+        .labels
+        .iter()
+        .max_by_key(|label| label_points(&label.name));
+    let involvement = match current_user {
+        Some(user) if issue.is_review_requested_from(user) => "your review is requested",
+        Some(user) if issue.is_assigned_to(user) => "assigned to you",
+        Some(_) => "not assigned to you and no review requested from you",
+        None => "the current user was unknown",
+    };
+
+    vec![
+        component(
+            "importance",
+            score.importance_score,
+            format!("{:?} repository", item.importance).to_lowercase(),
+        ),
+        component(
+            "recency",
+            score.recency_score,
+            format!("updated {} hours before the report", age_hours),
+        ),
+        component(
+            "activity",
+            score.activity_score,
+            format!(
+                "{} comments, 2 points each up to 10",
+                issue.comments.total_count
+            ),
+        ),
+        component(
+            "rule match",
+            score.rule_match_score,
+            if matched_rules.is_empty() {
+                "no watch rules matched".to_string()
+            } else {
+                format!("matched watch rules: {}", matched_rules.join(", "))
+            },
+        ),
+        component(
+            "labels",
+            score.label_score,
+            match best_label {
+                Some(label) => format!("best label: {}", label.name),
+                None => "no labels".to_string(),
+            },
+        ),
+        component(
+            "involvement",
+            score.involvement_score,
+            involvement.to_string(),
+        ),
+        component(
+            "newcomer",
+            score.newcomer_score,
+            if issue.is_first_time_contributor() {
+                "opened by a first-time contributor"
+            } else {
+                "not opened by a first-time contributor"
+            }
+            .to_string(),
+        ),
+        component(
+            "pull request",
+            score.total
+                - score.importance_score
+                - score.recency_score
+                - score.activity_score
+                - score.rule_match_score
+                - score.label_score
+                - score.involvement_score
+                - score.newcomer_score,
+            if issue.is_pull_request {
+                "bonus for pull requests"
+            } else {
+                "issues get no bonus"
+            }
+            .to_string(),
+        ),
+    ]
+}
+
+/// Explain an item of `run` for the terminal
+pub fn render_explanation(run: &RunScores, item: &ScoreExplanation) -> String {
+    let mut output = format!(
+        "{}#{} - {}\n{}\n\n",
+        item.repo, item.number, item.title, item.url
+    );
+    output.push_str(&format!(
+        "Ranked {} of {} in the report of {}\n\n",
+        item.rank,
+        run.items.len(),
+        run.generated_at.strftime("%Y-%m-%d %H:%M")
+    ));
+
+    output.push_str(&format!("Score: {}\n", item.total));
+    for component in &item.components {
+        output.push_str(&format!(
+            "  {:<14}{:>4}  {}\n",
+            component.name, component.points, component.reason
+        ));
+    }
+
+    output.push_str(&format!(
+        "\nImportance: {:?} ({})\n",
+        item.importance, item.importance_source
+    ));
+    if item.matched_rules.is_empty() {
+        output.push_str("Watch rules: none matched\n");
+    } else {
+        output.push_str(&format!("Watch rules: {}\n", item.matched_rules.join(", ")));
+    }
+    output.push_str(&format!(
+        "Urgency: {:?} ({})\n",
+        item.urgency, item.urgency_reason
+    ));
+    output.push_str(if item.action_item {
+        "Listed under action items\n"
+    } else {
+        "Not listed under action items\n"
+    });
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::github::{Label, RepoActivity};
+    use crate::intelligence::IntelligentAnalyzer;
+    use crate::test_utils::create_test_issue;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_explain_scores() {
+        let now = Timestamp::now();
+        let mut bug = create_test_issue(1, "Deadlock on shutdown", false);
+        bug.labels.push(Label {
+            name: "C-bug".to_string(),
+            color: None,
+            description: None,
+        });
+        let mut activity = RepoActivity::default();
+        activity.new_issues.push(bug);
+        activity
+            .new_prs
+            .push(create_test_issue(2, "Speed up the scheduler", true));
+        let activities = BTreeMap::from([("test/repo".to_string(), activity)]);
+
+        let config = Config::default();
+        let analysis = IntelligentAnalyzer::new(&config).analyze(&activities);
+        let run = explain_scores(&analysis, None, now);
+
+        let bug = run.find("Test/Repo", 1).unwrap();
+        let points: u32 = bug.components.iter().map(|c| c.points).sum();
+        assert_eq!(points, bug.total);
+        let labels = bug.components.iter().find(|c| c.name == "labels").unwrap();
+        assert_eq!(
+            (labels.points, labels.reason.as_str()),
+            (15, "best label: C-bug")
+        );
+        assert_eq!(bug.urgency, Urgency::High);
+        assert!(bug.action_item);
+
+        let pr = run.find("test/repo", 2).unwrap();
+        assert_eq!(pr.components.last().unwrap().points, 10);
+
+        let output = render_explanation(&run, bug);
+        assert!(output.contains("Urgency: High (labeled breaking, bug, or urgent)"));
+        assert!(output.contains("  labels          15  best label: C-bug\n"));
+    }
+}
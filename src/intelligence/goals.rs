@@ -0,0 +1,162 @@
+//! Weekly goals and Claude's check-in on them
+//!
+//! Goals come from `report.goals` and from the list items of
+//! `report.goals_file`. Each report asks Claude how the period's activity
+//! moved every goal forward, using the layout requested by
+//! `goals_check_in_prompt`.
+
+/// How far a goal got, as judged from the report's activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalStatus {
+    Done,
+    OnTrack,
+    AtRisk,
+    NoProgress,
+    /// The activity says nothing either way, or the answer was unreadable
+    Unclear,
+}
+
+impl GoalStatus {
+    fn parse(status: &str) -> Option<Self> {
+        match status
+            .trim()
+            .to_uppercase()
+            .replace([' ', '-'], "_")
+            .as_str()
+        {
+            "DONE" => Some(GoalStatus::Done),
+            "ON_TRACK" => Some(GoalStatus::OnTrack),
+            "AT_RISK" => Some(GoalStatus::AtRisk),
+            "NO_PROGRESS" => Some(GoalStatus::NoProgress),
+            "UNCLEAR" => Some(GoalStatus::Unclear),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GoalStatus::Done => "✅ Done",
+            GoalStatus::OnTrack => "🟢 On track",
+            GoalStatus::AtRisk => "🟠 At risk",
+            GoalStatus::NoProgress => "⚪ No progress",
+            GoalStatus::Unclear => "❔ Unclear",
+        }
+    }
+}
+
+/// Claude's assessment of one goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoalCheckIn {
+    pub goal: String,
+    pub status: GoalStatus,
+    /// One or two sentences of markdown, possibly with links to items
+    pub assessment: String,
+}
+
+/// The goals listed in a goals file
+///
+/// Every `-`, `*`, or numbered list item is a goal; headings and other text
+/// are left out, so the file can group goals and explain them.
+pub fn parse_goal_list(markdown: &str) -> Vec<String> {
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let item = match line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+                Some(item) => item,
+                None => {
+                    let (number, item) = line.split_once(". ")?;
+                    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                        return None;
+                    }
+                    item
+                }
+            };
+            let item = item
+                .strip_prefix("[ ] ")
+                .or_else(|| item.strip_prefix("[x] "))
+                .unwrap_or(item)
+                .trim();
+            (!item.is_empty()).then(|| item.to_string())
+        })
+        .collect()
+}
+
+/// Match a check-in response to the goals it was asked about
+///
+/// Expects one `<number>. <STATUS>: <assessment>` line per goal. Goals
+/// without a readable line are marked [`GoalStatus::Unclear`].
+pub fn parse_goal_check_in(goals: &[String], response: &str) -> Vec<GoalCheckIn> {
+    let mut check_ins: Vec<GoalCheckIn> = goals
+        .iter()
+        .map(|goal| GoalCheckIn {
+            goal: goal.clone(),
+            status: GoalStatus::Unclear,
+            assessment: String::new(),
+        })
+        .collect();
+
+    for line in response.lines() {
+        let Some((number, rest)) = line.trim().split_once(". ") else {
+            continue;
+        };
+        let Some((status, assessment)) = rest.split_once(':') else {
+            continue;
+        };
+        let (Ok(number), Some(status)) = (number.parse::<usize>(), GoalStatus::parse(status))
+        else {
+            continue;
+        };
+        if let Some(check_in) = number.checked_sub(1).and_then(|i| check_ins.get_mut(i)) {
+            check_in.status = status;
+            check_in.assessment = assessment.trim().to_string();
+        }
+    }
+    check_ins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_goal_list() {
+        let markdown = "# Goals for this week\n\n\
+            Keep the scheduler work moving.\n\n\
+            - Merge the io_uring PR\n\
+            * [ ] Reduce the open bug count in tokio\n\
+            1. Release 1.38\n";
+        assert_eq!(
+            parse_goal_list(markdown),
+            [
+                "Merge the io_uring PR",
+                "Reduce the open bug count in tokio",
+                "Release 1.38"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_goal_check_in() {
+        let goals = [
+            "Merge the io_uring PR".to_string(),
+            "Reduce the open bug count".to_string(),
+            "Release 1.38".to_string(),
+        ];
+        let response = "Here is the check-in:\n\
+            1. ON TRACK: Review of [#42](https://github.com/tokio-rs/tokio/pull/42) is nearly done.\n\
+            2. AT_RISK: Three new bugs were filed and one closed.\n\
+            7. DONE: Not a goal that was asked about.\n";
+        let check_ins = parse_goal_check_in(&goals, response);
+        let statuses: Vec<_> = check_ins.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            [GoalStatus::OnTrack, GoalStatus::AtRisk, GoalStatus::Unclear]
+        );
+        assert_eq!(
+            check_ins[1].assessment,
+            "Three new bugs were filed and one closed."
+        );
+        assert!(check_ins[0].assessment.contains("[#42]"));
+    }
+}
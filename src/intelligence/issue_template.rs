@@ -0,0 +1,290 @@
+use crate::github::{Issue, IssueState};
+use serde::Serialize;
+
+/// Label fragments that mark an issue as a bug report
+const BUG_LABELS: &[&str] = &["bug", "crash", "regression", "panic"];
+
+/// Label fragments that mark an issue as something other than a bug report
+const NON_BUG_LABELS: &[&str] = &[
+    "feature",
+    "enhancement",
+    "question",
+    "docs",
+    "documentation",
+    "proposal",
+];
+
+/// Label fragments showing a maintainer already asked for more information
+const NEEDS_INFO_LABELS: &[&str] = &["needs-info", "more-info", "needs-reproduction"];
+
+/// Words in an unlabelled issue that suggest a bug report
+const BUG_WORDS: &[&str] = &[
+    "bug",
+    "panic",
+    "panics",
+    "crash",
+    "crashes",
+    "error",
+    "broken",
+    "fails",
+    "failure",
+    "regression",
+];
+
+/// Placeholders issue forms leave in sections the reporter skipped
+const EMPTY_PLACEHOLDERS: &[&str] = &["_no response_", "n/a", "none", "-"];
+
+/// A section bug report templates usually require
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateSection {
+    Reproduction,
+    Version,
+}
+
+impl TemplateSection {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            TemplateSection::Reproduction => "steps to reproduce",
+            TemplateSection::Version => "version",
+        }
+    }
+
+    /// What to ask the reporter for in a request-for-info comment
+    fn request(&self) -> &'static str {
+        match self {
+            TemplateSection::Reproduction => {
+                "Steps to reproduce the problem, ideally a minimal example we can run"
+            }
+            TemplateSection::Version => {
+                "The version you are using (and your OS/toolchain, if relevant)"
+            }
+        }
+    }
+
+    /// Words that name this section in a heading
+    fn heading_keywords(&self) -> &'static [&'static str] {
+        match self {
+            TemplateSection::Reproduction => &["reproduc", "repro", "steps", "example"],
+            TemplateSection::Version => &["version", "environment"],
+        }
+    }
+
+    /// Whether the section's information appears in free text
+    fn mentioned_in(&self, body: &str) -> bool {
+        let lower = body.to_lowercase();
+        match self {
+            TemplateSection::Reproduction => {
+                body.contains("```")
+                    || ["to reproduce", "reproduction", "repro:", "minimal example"]
+                        .iter()
+                        .any(|k| lower.contains(k))
+            }
+            TemplateSection::Version => lower.split_whitespace().any(looks_like_version),
+        }
+    }
+}
+
+/// A new issue that is missing information its template asks for
+#[derive(Debug, Clone, Serialize)]
+pub struct NeedsInfoItem {
+    pub issue: Issue,
+    pub repo: String,
+    pub missing: Vec<TemplateSection>,
+    /// A ready-to-post comment asking the reporter for what is missing
+    pub draft_comment: String,
+}
+
+/// Required template sections missing from a new bug report
+///
+/// Only open bug reports that nobody has already asked for more information
+/// are checked. A section counts as present when its heading has content
+/// (issue form placeholders like `_No response_` don't count) or, for free-form
+/// reports, when the body mentions it.
+pub fn missing_template_sections(issue: &Issue) -> Vec<TemplateSection> {
+    if issue.is_pull_request || issue.state != IssueState::Open || !is_bug_report(issue) {
+        return Vec::new();
+    }
+
+    let has_label = |fragments: &[&str]| {
+        issue.labels.iter().any(|l| {
+            let name = l.name.to_lowercase().replace([' ', '_'], "-");
+            fragments.iter().any(|f| name.contains(f))
+        })
+    };
+    if has_label(NEEDS_INFO_LABELS) {
+        return Vec::new();
+    }
+
+    let body = issue.body.as_deref().unwrap_or_default();
+    [TemplateSection::Reproduction, TemplateSection::Version]
+        .into_iter()
+        .filter(
+            |section| match section_content(body, section.heading_keywords()) {
+                Some(content) => !has_content(&content),
+                None => !section.mentioned_in(body),
+            },
+        )
+        .collect()
+}
+
+/// Draft a friendly comment asking the reporter for the missing sections
+pub fn draft_info_request(issue: &Issue, missing: &[TemplateSection]) -> String {
+    let mut comment = format!(
+        "Thanks for the report, @{}! To help us look into this, could you add:\n\n",
+        issue.author.login
+    );
+    for section in missing {
+        comment.push_str(&format!("- {}\n", section.request()));
+    }
+    comment.push_str("\nWith that we should be able to track down what is going on.");
+    comment
+}
+
+fn is_bug_report(issue: &Issue) -> bool {
+    let labels: Vec<String> = issue.labels.iter().map(|l| l.name.to_lowercase()).collect();
+    if labels
+        .iter()
+        .any(|l| NON_BUG_LABELS.iter().any(|f| l.contains(f)))
+    {
+        return false;
+    }
+    if labels
+        .iter()
+        .any(|l| BUG_LABELS.iter().any(|f| l.contains(f)))
+    {
+        return true;
+    }
+    if !labels.is_empty() {
+        return false;
+    }
+
+    let text = format!(
+        "{} {}",
+        issue.title,
+        issue.body.as_deref().unwrap_or_default()
+    )
+    .to_lowercase();
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|w| BUG_WORDS.contains(&w))
+}
+
+/// Content under the first markdown heading matching one of `keywords`
+fn section_content(body: &str, keywords: &[&str]) -> Option<String> {
+    let mut lines = body.lines();
+
+    lines.by_ref().find(|line| {
+        let line = line.trim();
+        let heading = line
+            .strip_prefix('#')
+            .map(|h| h.trim_start_matches('#'))
+            .or_else(|| line.strip_prefix("**").and_then(|h| h.strip_suffix("**")));
+        heading.is_some_and(|h| {
+            let h = h.to_lowercase();
+            keywords.iter().any(|k| h.contains(k))
+        })
+    })?;
+
+    let content: Vec<&str> = lines
+        .take_while(|line| !line.trim_start().starts_with('#'))
+        .collect();
+    Some(content.join("\n"))
+}
+
+/// Whether a section has anything besides comments and placeholders
+fn has_content(section: &str) -> bool {
+    let mut text = section.to_string();
+    while let Some(start) = text.find("<!--") {
+        let end = text[start..]
+            .find("-->")
+            .map(|e| start + e + 3)
+            .unwrap_or(text.len());
+        text.replace_range(start..end, "");
+    }
+
+    text.lines()
+        .map(str::trim)
+        .any(|line| !line.is_empty() && !EMPTY_PLACEHOLDERS.contains(&line.to_lowercase().as_str()))
+}
+
+/// `1.2`, `v0.3.1`, `1.75.0-nightly` and similar
+fn looks_like_version(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+    let word = word.strip_prefix('v').unwrap_or(word);
+    let mut parts = word.split('.');
+    let major = parts.next().unwrap_or_default();
+    let minor = parts.next().unwrap_or_default();
+    let minor = minor
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .unwrap_or_default();
+
+    !major.is_empty() && major.chars().all(|c| c.is_ascii_digit()) && !minor.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_issue, create_test_issue_with_labels};
+
+    fn bug_with_body(body: &str) -> Issue {
+        let mut issue = create_test_issue_with_labels(1, "Runtime hangs", vec!["bug"]);
+        issue.body = Some(body.to_string());
+        issue
+    }
+
+    #[test]
+    fn test_issue_form_with_empty_sections() {
+        let issue = bug_with_body(
+            "### Description\n\nIt hangs.\n\n### Steps to reproduce\n\n_No response_\n\n### Version\n\n<!-- e.g. 1.2.3 -->\n",
+        );
+        assert_eq!(
+            missing_template_sections(&issue),
+            vec![TemplateSection::Reproduction, TemplateSection::Version]
+        );
+
+        let issue = bug_with_body(
+            "### Steps to reproduce\n\nRun `cargo test` twice.\n\n### Version\n\ntokio 1.36.0\n",
+        );
+        assert!(missing_template_sections(&issue).is_empty());
+    }
+
+    #[test]
+    fn test_free_form_bug_report() {
+        let issue = bug_with_body("Upgrading to v1.36 makes my app hang.");
+        assert_eq!(
+            missing_template_sections(&issue),
+            vec![TemplateSection::Reproduction]
+        );
+
+        let issue = bug_with_body("Hangs on 1.36.\n\n```rust\nfn main() {}\n```");
+        assert!(missing_template_sections(&issue).is_empty());
+    }
+
+    #[test]
+    fn test_only_bug_reports_are_checked() {
+        let mut feature = create_test_issue_with_labels(2, "Add a panic hook", vec!["enhancement"]);
+        feature.body = Some("It would be nice".to_string());
+        assert!(missing_template_sections(&feature).is_empty());
+
+        let asked = create_test_issue_with_labels(3, "Crash", vec!["bug", "needs-info"]);
+        assert!(missing_template_sections(&asked).is_empty());
+
+        // Unlabelled issues are judged by their wording
+        let mut unlabelled = create_test_issue(4, "Scheduler panics on shutdown", false);
+        unlabelled.body = None;
+        assert_eq!(missing_template_sections(&unlabelled).len(), 2);
+
+        let pr = create_test_issue(5, "Fix crash", true);
+        assert!(missing_template_sections(&pr).is_empty());
+    }
+
+    #[test]
+    fn test_draft_info_request() {
+        let issue = bug_with_body("It hangs");
+        let comment = draft_info_request(&issue, &[TemplateSection::Version]);
+        assert!(comment.starts_with("Thanks for the report, @testuser!"));
+        assert!(comment.contains("- The version you are using"));
+        assert!(!comment.contains("reproduce"));
+    }
+}
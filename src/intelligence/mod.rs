@@ -1,20 +1,72 @@
 use crate::config::{Config, Importance};
-use crate::github::{Issue, RepoActivity};
-use std::collections::BTreeMap;
+use crate::github::{Issue, IssueState, RepoActivity};
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use tracing::info;
 
+mod budget;
+mod ci_log;
 mod context;
+mod deadlines;
+mod escalation;
+mod explain;
+mod goals;
+mod issue_template;
+mod newcomers;
+mod replies;
+mod reviewer_load;
 mod scoring;
-pub use context::{build_context_prompt, extract_action_items};
+mod staleness;
+mod thread_diff;
+mod waiting;
+mod watch_rules;
+use budget::comment_cap;
+pub use budget::{window_comments, Truncation};
+pub use ci_log::{extract_error_region, CiFailure};
+pub use context::{build_context_prompt, extract_action_items, find_excerpt, EXCERPT_MAX_CHARS};
+pub use deadlines::{extract_deadlines, Deadline, DeadlineSource};
+pub use escalation::{
+    escalation_candidates, find_escalations, parse_escalation_check, Escalation, EscalationLevel,
+    MAX_ESCALATION_THREADS,
+};
+pub use explain::{
+    explain_scores, render_explanation, RunScores, ScoreComponent, ScoreExplanation,
+};
+pub use goals::{parse_goal_check_in, parse_goal_list, GoalCheckIn, GoalStatus};
+pub use issue_template::{
+    draft_info_request, missing_template_sections, NeedsInfoItem, TemplateSection,
+};
+pub use newcomers::{find_new_contributions, NewContribution};
+pub use replies::{find_awaiting_reply, AwaitingReply, ReplyReason};
+pub use reviewer_load::{reviewer_workload, PendingReview, ReviewerLoad, MAX_PRS_PER_REVIEWER};
 pub use scoring::{calculate_priority_score, PriorityScore};
+pub use staleness::{assess_staleness, Staleness, WaitingOnYou};
+pub use thread_diff::{comments_since, parse_thread_update, ThreadUpdate, MAX_TRACKED_THREADS};
+pub use waiting::{detect_waiting_on_author, WaitingItem, WaitingReason};
+pub use watch_rules::{WatchRuleEngine, RULE_MATCH_POINTS};
 
 /// Intelligent filtering and analysis of GitHub activities
 pub struct IntelligentAnalyzer<'a> {
-    _config: &'a Config, // Keep for future use
+    config: &'a Config,
+    current_user: Option<String>,
+    watch_rules: WatchRuleEngine,
 }
 
 impl<'a> IntelligentAnalyzer<'a> {
     pub fn new(config: &'a Config) -> Self {
-        IntelligentAnalyzer { _config: config }
+        IntelligentAnalyzer {
+            config,
+            current_user: None,
+            watch_rules: WatchRuleEngine::new(&config.watch_rules, None),
+        }
+    }
+
+    /// Set the authenticated user, used to boost items that involve them
+    pub fn with_current_user(mut self, login: Option<&str>) -> Self {
+        self.current_user = login.map(str::to_string);
+        self.watch_rules = WatchRuleEngine::new(&self.config.watch_rules, login);
+        self
     }
 
     /// Analyze activities and return prioritized, filtered results
@@ -34,8 +86,7 @@ impl<'a> IntelligentAnalyzer<'a> {
             all_items.extend(activity.updated_prs.iter());
 
             for issue in all_items {
-                // Calculate priority score based on basic metrics
-                let score = calculate_priority_score(issue, importance, issue.is_pull_request);
+                let score = self.priority_score(repo_name, issue);
 
                 prioritized_issues.push(PrioritizedIssue {
                     issue: issue.clone(),
@@ -47,32 +98,448 @@ impl<'a> IntelligentAnalyzer<'a> {
         }
 
         // Sort by priority score (highest first)
-        prioritized_issues.sort_by(|a, b| b.score.total.cmp(&a.score.total));
+        prioritized_issues.sort_by_key(|p| std::cmp::Reverse(p.score.total));
+
+        let mut seen = HashSet::new();
+        let matched_rules: Vec<RuleMatch> = prioritized_issues
+            .iter()
+            .filter(|p| seen.insert((p.repo.as_str(), p.issue.number)))
+            .filter_map(|p| {
+                let rules = self.watch_rules.matches(&p.repo, &p.issue);
+                (!rules.is_empty()).then(|| RuleMatch {
+                    repo: p.repo.clone(),
+                    issue: p.issue.clone(),
+                    rules,
+                })
+            })
+            .collect();
 
         // Build simple context for AI summarization
         let context_prompt = build_context_prompt();
 
-        // Extract potential action items
-        let action_items = extract_action_items(&prioritized_issues);
+        // Items blocked on their author are not action items for the user
+        let waiting_on_author = self.find_waiting_on_author(activities);
+        let waiting: HashSet<(&str, u32)> = waiting_on_author
+            .iter()
+            .map(|w| (w.repo.as_str(), w.issue.number))
+            .collect();
+        let actionable: Vec<PrioritizedIssue> = prioritized_issues
+            .iter()
+            .filter(|p| !waiting.contains(&(p.repo.as_str(), p.issue.number)))
+            .cloned()
+            .collect();
+
+        // Extract potential action items, quoting what triggered them
+        let mut action_items = extract_action_items(&actionable);
+        for action in &mut action_items {
+            let comments = activities
+                .get(&action.repo)
+                .and_then(|activity| {
+                    activity
+                        .new_comments
+                        .iter()
+                        .find(|(i, _)| i.number == action.issue.number)
+                })
+                .map(|(_, comments)| comments.as_slice())
+                .unwrap_or_default();
+            action.excerpt = find_excerpt(&action.issue, comments);
+        }
 
         AnalysisResult {
             prioritized_issues,
             context_prompt,
             action_items,
+            waiting_on_author,
+            waiting_on_you: self.find_waiting_on_you(activities),
+            needs_info: find_needs_info(activities),
+            new_contributions: find_new_contributions(activities),
+            matched_rules,
+        }
+    }
+
+    /// `activities` without the items scoring below `intelligence.min_score_for_ai`
+    ///
+    /// Keeps low-priority items out of AI prompts. Repositories left without
+    /// items are dropped.
+    pub fn above_ai_threshold(
+        &self,
+        activities: &BTreeMap<String, RepoActivity>,
+    ) -> BTreeMap<String, RepoActivity> {
+        let min_score = self.config.intelligence.min_score_for_ai;
+        let (kept, dropped) = self.split_by_score(activities, min_score);
+        if !dropped.is_empty() {
+            info!(
+                "Leaving {} items scoring below {} out of AI prompts",
+                dropped.len(),
+                min_score
+            );
+        }
+        kept
+    }
+
+    /// Split `activities` into the items scoring at least `min_score` and the
+    /// `(repo, issue)` pairs of the others, each listed once
+    ///
+    /// Repositories left without items are dropped. A `min_score` of 0 keeps
+    /// everything.
+    pub fn split_by_score(
+        &self,
+        activities: &BTreeMap<String, RepoActivity>,
+        min_score: u32,
+    ) -> (BTreeMap<String, RepoActivity>, Vec<(String, Issue)>) {
+        if min_score == 0 {
+            return (activities.clone(), Vec::new());
+        }
+
+        let mut dropped: Vec<(String, Issue)> = Vec::new();
+        let kept = retain_items(activities, |repo_name, issue| {
+            if self.priority_score(repo_name, issue).total >= min_score {
+                return true;
+            }
+            if !dropped
+                .iter()
+                .any(|(repo, dropped)| repo == repo_name && dropped.number == issue.number)
+            {
+                dropped.push((repo_name.to_string(), issue.clone()));
+            }
+            false
+        });
+        (kept, dropped)
+    }
+
+    /// Keep the `max_items` highest-scoring issues and PRs, and cap their
+    /// comments so there are at most `max_comments` in total
+    ///
+    /// Threads are first cut down to `settings.max_comments_per_issue`
+    /// comments, picked by `settings.comment_window`. Comments are then capped
+    /// at the same number for every item, keeping the most recent ones. A
+    /// limit of 0 disables it.
+    pub fn enforce_budget(
+        &self,
+        activities: &BTreeMap<String, RepoActivity>,
+        max_items: usize,
+        max_comments: usize,
+    ) -> (BTreeMap<String, RepoActivity>, Truncation) {
+        let mut items: Vec<(&str, &Issue, u32)> = Vec::new();
+        let mut seen = HashSet::new();
+        for (repo_name, activity) in activities {
+            for issue in activity_items(activity) {
+                if seen.insert((repo_name.as_str(), issue.number)) {
+                    items.push((
+                        repo_name,
+                        issue,
+                        self.priority_score(repo_name, issue).total,
+                    ));
+                }
+            }
+        }
+
+        let mut truncation = Truncation {
+            total_items: items.len(),
+            ..Truncation::default()
+        };
+        let mut activities = activities.clone();
+        if max_items > 0 && items.len() > max_items {
+            items.sort_by(|a, b| {
+                b.2.cmp(&a.2)
+                    .then_with(|| b.1.updated_at.cmp(&a.1.updated_at))
+            });
+            truncation.skipped = items[max_items..]
+                .iter()
+                .map(|(repo, issue, _)| (repo.to_string(), (*issue).clone()))
+                .collect();
+            let skipped: HashSet<(&str, u32)> = truncation
+                .skipped
+                .iter()
+                .map(|(repo, issue)| (repo.as_str(), issue.number))
+                .collect();
+            activities = retain_items(&activities, |repo, issue| {
+                !skipped.contains(&(repo, issue.number))
+            });
+        }
+
+        let settings = &self.config.settings;
+        for (_, comments) in activities
+            .values_mut()
+            .flat_map(|activity| activity.new_comments.iter_mut())
+        {
+            if settings.max_comments_per_issue > 0
+                && comments.len() > settings.max_comments_per_issue
+            {
+                comments.sort_by_key(|comment| comment.created_at);
+                let kept: Vec<_> = window_comments(
+                    comments,
+                    settings.max_comments_per_issue,
+                    settings.comment_window,
+                )
+                .into_iter()
+                .cloned()
+                .collect();
+                truncation.windowed_comments += comments.len() - kept.len();
+                truncation.windowed_items += 1;
+                truncation.comment_window =
+                    Some((settings.max_comments_per_issue, settings.comment_window));
+                *comments = kept;
+            }
+        }
+
+        let counts: Vec<usize> = activities
+            .values()
+            .flat_map(|activity| activity.new_comments.iter().map(|(_, c)| c.len()))
+            .collect();
+        if let Some(cap) = comment_cap(&counts, max_comments) {
+            for (_, comments) in activities
+                .values_mut()
+                .flat_map(|activity| activity.new_comments.iter_mut())
+            {
+                if comments.len() > cap {
+                    truncation.skipped_comments += comments.len() - cap;
+                    truncation.trimmed_items += 1;
+                    comments.sort_by_key(|comment| comment.created_at);
+                    comments.drain(..comments.len() - cap);
+                }
+            }
+            truncation.comments_per_item = Some(cap);
+        }
+
+        if !truncation.is_empty() {
+            info!(
+                "Leaving out {} items and {} comments over the report budget",
+                truncation.skipped.len(),
+                truncation.skipped_comments
+            );
+        }
+        (activities, truncation)
+    }
+
+    /// Priority score of `issue`, boosted when it matches a watch rule
+    fn priority_score(&self, repo: &str, issue: &Issue) -> PriorityScore {
+        let mut score = calculate_priority_score(
+            issue,
+            Importance::Medium,
+            issue.is_pull_request,
+            self.current_user.as_deref(),
+        );
+        if !self.watch_rules.matches(repo, issue).is_empty() {
+            score.rule_match_score = RULE_MATCH_POINTS;
+            score.total += RULE_MATCH_POINTS;
+        }
+        score
+    }
+
+    /// Find open items where the next step belongs to the author
+    fn find_waiting_on_author(
+        &self,
+        activities: &BTreeMap<String, RepoActivity>,
+    ) -> Vec<WaitingItem> {
+        let threshold_days = self.config.settings.waiting_on_author_days;
+        if threshold_days == 0 {
+            return Vec::new();
+        }
+
+        let now = Timestamp::now();
+        let mut waiting = Vec::new();
+
+        for (repo_name, activity) in activities {
+            let mut seen = HashSet::new();
+            let items = activity
+                .new_issues
+                .iter()
+                .chain(&activity.updated_issues)
+                .chain(&activity.new_prs)
+                .chain(&activity.updated_prs)
+                .chain(activity.new_comments.iter().map(|(issue, _)| issue))
+                .filter(|issue| issue.state == IssueState::Open);
+
+            for issue in items {
+                if !seen.insert(issue.number) {
+                    continue;
+                }
+
+                let comments = activity
+                    .new_comments
+                    .iter()
+                    .find(|(i, _)| i.number == issue.number)
+                    .map(|(_, comments)| comments.as_slice())
+                    .unwrap_or_default();
+                let reviews = activity
+                    .reviews
+                    .get(&issue.number)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                let head_sha = activity.head_shas.get(&issue.number).map(String::as_str);
+
+                if let Some((reason, idle_days)) = detect_waiting_on_author(
+                    issue,
+                    comments,
+                    reviews,
+                    head_sha,
+                    now,
+                    threshold_days,
+                ) {
+                    waiting.push(WaitingItem {
+                        issue: issue.clone(),
+                        repo: repo_name.clone(),
+                        reason,
+                        idle_days,
+                    });
+                }
+            }
         }
+
+        // Longest waiting first
+        waiting.sort_by_key(|w| std::cmp::Reverse(w.idle_days));
+        waiting
+    }
+
+    /// Find open items where the next step belongs to the current user
+    fn find_waiting_on_you(
+        &self,
+        activities: &BTreeMap<String, RepoActivity>,
+    ) -> Vec<WaitingOnYou> {
+        let threshold_days = self.config.settings.waiting_on_you_days;
+        let Some(user) = self.current_user.as_deref() else {
+            return Vec::new();
+        };
+        if threshold_days == 0 {
+            return Vec::new();
+        }
+
+        let now = Timestamp::now();
+        let mut waiting = Vec::new();
+
+        for (repo_name, activity) in activities {
+            let mut seen = HashSet::new();
+            for issue in activity_items(activity) {
+                if !seen.insert(issue.number) {
+                    continue;
+                }
+
+                let comments = activity
+                    .new_comments
+                    .iter()
+                    .find(|(i, _)| i.number == issue.number)
+                    .map(|(_, comments)| comments.as_slice())
+                    .unwrap_or_default();
+                let Some(staleness) = assess_staleness(issue, comments, user, now) else {
+                    continue;
+                };
+                if let (Some(from), true) = (
+                    staleness.waiting,
+                    staleness.idle_days >= i64::from(threshold_days),
+                ) {
+                    waiting.push(WaitingOnYou {
+                        issue: issue.clone(),
+                        repo: repo_name.clone(),
+                        from,
+                        idle_days: staleness.idle_days,
+                        nudge: None,
+                    });
+                }
+            }
+        }
+
+        // Longest waiting first
+        waiting.sort_by_key(|w| std::cmp::Reverse(w.idle_days));
+        waiting
     }
 }
 
+/// The issues and PRs of `activity`, including the ones only commented on
+fn activity_items(activity: &RepoActivity) -> impl Iterator<Item = &Issue> {
+    activity
+        .new_issues
+        .iter()
+        .chain(&activity.updated_issues)
+        .chain(&activity.new_prs)
+        .chain(&activity.updated_prs)
+        .chain(&activity.merged_prs)
+        .chain(&activity.closed_issues)
+        .chain(activity.new_comments.iter().map(|(issue, _)| issue))
+}
+
+/// `activities` with only the items `keep` accepts, dropping repositories
+/// left without any
+fn retain_items(
+    activities: &BTreeMap<String, RepoActivity>,
+    mut keep: impl FnMut(&str, &Issue) -> bool,
+) -> BTreeMap<String, RepoActivity> {
+    let mut kept = BTreeMap::new();
+    for (repo_name, activity) in activities {
+        let mut activity = activity.clone();
+        for items in [
+            &mut activity.new_issues,
+            &mut activity.updated_issues,
+            &mut activity.new_prs,
+            &mut activity.updated_prs,
+            &mut activity.merged_prs,
+            &mut activity.closed_issues,
+        ] {
+            items.retain(|issue| keep(repo_name, issue));
+        }
+        activity
+            .new_comments
+            .retain(|(issue, _)| keep(repo_name, issue));
+
+        if activity_items(&activity).next().is_some() {
+            kept.insert(repo_name.clone(), activity);
+        }
+    }
+    kept
+}
+
+/// New bug reports missing sections their issue template asks for
+fn find_needs_info(activities: &BTreeMap<String, RepoActivity>) -> Vec<NeedsInfoItem> {
+    let mut items = Vec::new();
+
+    for (repo_name, activity) in activities {
+        for issue in &activity.new_issues {
+            let missing = missing_template_sections(issue);
+            if missing.is_empty() {
+                continue;
+            }
+            items.push(NeedsInfoItem {
+                issue: issue.clone(),
+                repo: repo_name.clone(),
+                draft_comment: draft_info_request(issue, &missing),
+                missing,
+            });
+        }
+    }
+
+    items
+}
+
 /// Result of intelligent analysis
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct AnalysisResult {
     pub prioritized_issues: Vec<PrioritizedIssue>,
+    #[serde(skip)]
     pub context_prompt: String,
     pub action_items: Vec<ActionItem>,
+    pub waiting_on_author: Vec<WaitingItem>,
+    /// Open items waiting on the user's review or reply, longest waiting first
+    pub waiting_on_you: Vec<WaitingOnYou>,
+    /// New bug reports that should be asked for more information
+    pub needs_info: Vec<NeedsInfoItem>,
+    /// Open items opened by first-time contributors, oldest first
+    pub new_contributions: Vec<NewContribution>,
+    /// Items matching `[watch_rules]`, highest score first
+    pub matched_rules: Vec<RuleMatch>,
+}
+
+/// An item and the watch rules it matched
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMatch {
+    pub repo: String,
+    pub issue: Issue,
+    /// Names of the matched rules
+    pub rules: Vec<String>,
 }
 
 /// An issue with priority scoring and context
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PrioritizedIssue {
     pub issue: Issue,
     pub repo: String,
@@ -81,16 +548,28 @@ pub struct PrioritizedIssue {
 }
 
 /// A suggested action item
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ActionItem {
     pub description: String,
     pub issue: Issue,
     pub repo: String,
     pub urgency: Urgency,
     pub reason: String,
+    /// The latest comment, or the description, quoted under the action
+    pub excerpt: Option<Excerpt>,
+}
+
+/// A short, sanitized quote of a comment or issue description
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Excerpt {
+    pub author: String,
+    pub text: String,
+    /// Permalink to the quoted comment, or the item itself for its description
+    pub url: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Urgency {
     Low,
     Medium,
@@ -98,11 +577,24 @@ pub enum Urgency {
     Critical,
 }
 
+impl Urgency {
+    /// The urgency as shown next to action items, e.g. `HIGH`
+    pub fn label(self) -> &'static str {
+        match self {
+            Urgency::Low => "LOW",
+            Urgency::Medium => "MEDIUM",
+            Urgency::High => "HIGH",
+            Urgency::Critical => "CRITICAL",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::CommentWindow;
     use crate::github::{Author, CommentCount, IssueState, Label as GHLabel};
-    use jiff::Timestamp;
+    use jiff::{Timestamp, ToSpan};
 
     #[test]
     fn test_intelligent_analyzer_creation() {
@@ -143,6 +635,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 5 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         });
 
         activities.insert("test/repo".to_string(), repo_activity);
@@ -152,4 +648,188 @@ mod tests {
         // Should match security_issues watch rule
         assert!(!result.prioritized_issues.is_empty());
     }
+
+    #[test]
+    fn test_above_ai_threshold() {
+        let mut config = Config::default();
+        let mut activities = BTreeMap::new();
+        let mut active = RepoActivity::default();
+        active
+            .new_prs
+            .push(crate::test_utils::create_test_issue(1, "Fresh PR", true));
+        // Issues score 10 points below otherwise identical PRs
+        let issue = crate::test_utils::create_test_issue(2, "Quiet issue", false);
+        active.updated_issues.push(issue.clone());
+        activities.insert("test/active".to_string(), active);
+        let mut quiet = RepoActivity::default();
+        quiet.closed_issues.push(issue.clone());
+        activities.insert("test/quiet".to_string(), quiet);
+
+        // Disabled by default
+        let all = IntelligentAnalyzer::new(&config).above_ai_threshold(&activities);
+        assert_eq!(all.len(), 2);
+
+        let issue_score = calculate_priority_score(&issue, Importance::Medium, false, None).total;
+        config.intelligence.min_score_for_ai = issue_score + 1;
+        let kept = IntelligentAnalyzer::new(&config).above_ai_threshold(&activities);
+        let repos: Vec<&String> = kept.keys().collect();
+        assert_eq!(repos, ["test/active"]);
+        assert_eq!(kept["test/active"].new_prs.len(), 1);
+        assert!(kept["test/active"].updated_issues.is_empty());
+    }
+
+    #[test]
+    fn test_enforce_budget() {
+        let config = Config::default();
+        let comment = |id: u64, hours_ago: i64| crate::github::Comment {
+            id,
+            body: format!("Comment {}", id),
+            author: Author {
+                login: "alice".to_string(),
+                user_type: None,
+            },
+            created_at: Timestamp::now() - hours_ago.hours(),
+            updated_at: Timestamp::now() - hours_ago.hours(),
+        };
+        let pr = crate::test_utils::create_test_issue(1, "Fresh PR", true);
+        let issue = crate::test_utils::create_test_issue(2, "Quiet issue", false);
+        let mut activity = RepoActivity::default();
+        activity.new_prs.push(pr.clone());
+        activity.updated_issues.push(issue.clone());
+        activity
+            .new_comments
+            .push((pr, (1..=4).map(|id| comment(id, id as i64)).collect()));
+        activity.new_comments.push((issue, vec![comment(5, 1)]));
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), activity);
+        let analyzer = IntelligentAnalyzer::new(&config);
+
+        let (all, truncation) = analyzer.enforce_budget(&activities, 0, 0);
+        assert!(truncation.is_empty());
+        assert_eq!(all["test/repo"].new_comments[0].1.len(), 4);
+
+        // The issue scores below the PR and is left out with its comment
+        let (kept, truncation) = analyzer.enforce_budget(&activities, 1, 2);
+        assert_eq!(truncation.total_items, 2);
+        assert_eq!(truncation.skipped.len(), 1);
+        assert_eq!(truncation.skipped[0].1.number, 2);
+        assert!(kept["test/repo"].updated_issues.is_empty());
+        let (_, comments) = &kept["test/repo"].new_comments[0];
+        let ids: Vec<u64> = comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [2, 1]);
+        assert_eq!(truncation.comments_per_item, Some(2));
+        assert_eq!(
+            (truncation.skipped_comments, truncation.trimmed_items),
+            (2, 1)
+        );
+
+        let mut config = Config::default();
+        config.settings.max_comments_per_issue = 2;
+        config.settings.comment_window = CommentWindow::FirstAndLatest;
+        let analyzer = IntelligentAnalyzer::new(&config);
+        let (kept, truncation) = analyzer.enforce_budget(&activities, 0, 0);
+        let (_, comments) = &kept["test/repo"].new_comments[0];
+        let ids: Vec<u64> = comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [4, 1]);
+        assert_eq!(
+            (truncation.windowed_comments, truncation.windowed_items),
+            (2, 1)
+        );
+        assert_eq!(
+            truncation.comment_window,
+            Some((2, CommentWindow::FirstAndLatest))
+        );
+        assert_eq!(truncation.comments_per_item, None);
+    }
+
+    #[test]
+    fn test_waiting_items_are_not_action_items() {
+        let config = Config::default();
+        let analyzer = IntelligentAnalyzer::new(&config);
+
+        let mut issue = crate::test_utils::create_test_issue_with_labels(
+            7,
+            "Crash with no details",
+            vec!["bug", "needs-info"],
+        );
+        issue.updated_at = Timestamp::now() - (10_i64 * 24).hours();
+
+        let mut repo_activity = RepoActivity::default();
+        repo_activity.updated_issues.push(issue);
+        repo_activity
+            .updated_issues
+            .push(crate::test_utils::create_test_issue_with_labels(
+                8,
+                "Fresh bug",
+                vec!["bug"],
+            ));
+
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), repo_activity);
+
+        let result = analyzer.analyze(&activities);
+
+        assert_eq!(result.waiting_on_author.len(), 1);
+        assert_eq!(result.waiting_on_author[0].issue.number, 7);
+        assert_eq!(result.waiting_on_author[0].reason, WaitingReason::NeedsInfo);
+        assert!(result.action_items.iter().all(|a| a.issue.number != 7));
+        assert_eq!(result.prioritized_issues.len(), 2);
+    }
+
+    #[test]
+    fn test_items_waiting_on_you() {
+        let config = Config::default();
+        let now = Timestamp::now();
+
+        let mut pr = crate::test_utils::create_test_issue(3, "Add feature", true);
+        pr.requested_reviewers.push(crate::github::Author {
+            login: "me".to_string(),
+            user_type: None,
+        });
+        pr.updated_at = now - (5_i64 * 24).hours();
+        let mut fresh = crate::test_utils::create_test_issue(4, "Fix bug", true);
+        fresh.requested_reviewers = pr.requested_reviewers.clone();
+
+        let mut repo_activity = RepoActivity::default();
+        repo_activity.updated_prs.extend([pr, fresh]);
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), repo_activity);
+
+        let result = IntelligentAnalyzer::new(&config).analyze(&activities);
+        assert!(result.waiting_on_you.is_empty());
+
+        let result = IntelligentAnalyzer::new(&config)
+            .with_current_user(Some("me"))
+            .analyze(&activities);
+        assert_eq!(result.waiting_on_you.len(), 1);
+        assert_eq!(result.waiting_on_you[0].issue.number, 3);
+        assert_eq!(result.waiting_on_you[0].from, "testuser");
+        assert_eq!(result.waiting_on_you[0].idle_days, 5);
+    }
+
+    #[test]
+    fn test_new_bug_reports_missing_template_sections() {
+        let config = Config::default();
+        let analyzer = IntelligentAnalyzer::new(&config);
+
+        let mut repo_activity = RepoActivity::default();
+        repo_activity
+            .new_issues
+            .push(crate::test_utils::create_test_issue_with_labels(
+                9,
+                "Hang on shutdown",
+                vec!["bug"],
+            ));
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), repo_activity);
+
+        let result = analyzer.analyze(&activities);
+
+        assert_eq!(result.needs_info.len(), 1);
+        assert_eq!(
+            result.needs_info[0].missing,
+            vec![TemplateSection::Reproduction, TemplateSection::Version]
+        );
+        assert!(result.needs_info[0].draft_comment.contains("@testuser"));
+    }
 }
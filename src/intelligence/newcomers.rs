@@ -0,0 +1,75 @@
+//! Items opened by people contributing to a repository for the first time
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+use super::activity_items;
+use crate::github::{Issue, IssueState, RepoActivity};
+
+/// An open issue or PR opened by a first-time contributor
+#[derive(Debug, Clone, Serialize)]
+pub struct NewContribution {
+    pub repo: String,
+    pub issue: Issue,
+}
+
+/// The open items of `activities` opened by first-time contributors, oldest
+/// first, each listed once
+pub fn find_new_contributions(activities: &BTreeMap<String, RepoActivity>) -> Vec<NewContribution> {
+    let mut contributions = Vec::new();
+    for (repo_name, activity) in activities {
+        let mut seen = HashSet::new();
+        for issue in activity_items(activity) {
+            if issue.state == IssueState::Open
+                && issue.is_first_time_contributor()
+                && !issue.author.is_bot()
+                && seen.insert(issue.number)
+            {
+                contributions.push(NewContribution {
+                    repo: repo_name.clone(),
+                    issue: issue.clone(),
+                });
+            }
+        }
+    }
+    contributions.sort_by_key(|c| c.issue.created_at);
+    contributions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+    use jiff::ToSpan;
+
+    #[test]
+    fn test_find_new_contributions() {
+        let first_timer = |number: u32, title: &str, is_pr: bool, association: &str| {
+            let mut issue = create_test_issue(number, title, is_pr);
+            issue.author_association = Some(association.to_string());
+            issue
+        };
+        let mut older = first_timer(2, "Panic on empty input", false, "FIRST_TIMER");
+        older.created_at -= 48.hours();
+        let mut merged = first_timer(3, "Fix docs", true, "FIRST_TIME_CONTRIBUTOR");
+        merged.state = IssueState::Merged;
+
+        let mut activity = RepoActivity::default();
+        activity
+            .new_prs
+            .push(first_timer(1, "Fix typo", true, "FIRST_TIME_CONTRIBUTOR"));
+        activity.new_issues.push(older.clone());
+        activity.updated_issues.push(older);
+        activity.merged_prs.push(merged);
+        activity
+            .new_issues
+            .push(first_timer(4, "Add a feature", false, "CONTRIBUTOR"));
+        let activities = BTreeMap::from([("test/repo".to_string(), activity)]);
+
+        let numbers: Vec<u32> = find_new_contributions(&activities)
+            .iter()
+            .map(|c| c.issue.number)
+            .collect();
+        assert_eq!(numbers, [2, 1]);
+    }
+}
@@ -0,0 +1,278 @@
+//! Comments waiting on a reply from the user
+//!
+//! Covers @-mentions in issue comments and inline PR review comments, and
+//! questions asked in review threads the user took part in. A reply from the
+//! user later in the same thread settles it.
+
+use jiff::Timestamp;
+use std::collections::BTreeMap;
+
+use super::context::{plain_text, EXCERPT_MAX_CHARS};
+use crate::github::{Author, Comment, Issue, ReviewComment};
+
+/// Why a comment needs a reply from the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyReason {
+    /// The user was @-mentioned
+    Mentioned,
+    /// A question in a review thread the user commented in
+    Question,
+}
+
+impl ReplyReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ReplyReason::Mentioned => "mentioned you",
+            ReplyReason::Question => "asked a question",
+        }
+    }
+}
+
+/// The latest comment of a thread that waits on the user
+#[derive(Debug, Clone)]
+pub struct AwaitingReply {
+    pub issue: Issue,
+    pub repo: String,
+    pub author: String,
+    pub reason: ReplyReason,
+    /// The comment as one line of plain text, truncated to `EXCERPT_MAX_CHARS`
+    pub text: String,
+    pub url: String,
+    /// `path:line` of inline review comments
+    pub location: Option<String>,
+    pub created_at: Timestamp,
+}
+
+/// A comment from either endpoint
+struct ThreadComment<'a> {
+    author: &'a Author,
+    body: &'a str,
+    created_at: Timestamp,
+    url: String,
+    location: Option<String>,
+}
+
+/// Find the comments on an issue or PR since `since` that wait on `user`
+///
+/// The issue comments count as one thread and each inline review thread as
+/// another; at most one comment is returned per thread.
+pub fn find_awaiting_reply(
+    repo: &str,
+    issue: &Issue,
+    comments: &[Comment],
+    review_comments: &[ReviewComment],
+    user: &str,
+    since: Timestamp,
+) -> Vec<AwaitingReply> {
+    let conversation = comments
+        .iter()
+        .map(|comment| ThreadComment {
+            author: &comment.author,
+            body: &comment.body,
+            created_at: comment.created_at,
+            url: format!("{}#issuecomment-{}", issue.url, comment.id),
+            location: None,
+        })
+        .collect();
+
+    let mut review_threads: BTreeMap<u64, Vec<ThreadComment>> = BTreeMap::new();
+    for comment in review_comments {
+        review_threads
+            .entry(comment.thread_id())
+            .or_default()
+            .push(ThreadComment {
+                author: &comment.user,
+                body: &comment.body,
+                created_at: comment.created_at,
+                url: comment.html_url.clone(),
+                location: Some(match comment.line {
+                    Some(line) => format!("{}:{}", comment.path, line),
+                    None => comment.path.clone(),
+                }),
+            });
+    }
+
+    // Questions in the issue conversation are too often meant for anyone
+    let mut found: Vec<AwaitingReply> = awaiting_in_thread(conversation, user, since, false)
+        .into_iter()
+        .chain(
+            review_threads
+                .into_values()
+                .filter_map(|thread| awaiting_in_thread(thread, user, since, true)),
+        )
+        .map(|(comment, text, reason)| AwaitingReply {
+            issue: issue.clone(),
+            repo: repo.to_string(),
+            author: comment.author.login.clone(),
+            reason,
+            text,
+            url: comment.url,
+            location: comment.location,
+            created_at: comment.created_at,
+        })
+        .collect();
+    found.sort_by_key(|reply| reply.created_at);
+    found
+}
+
+/// The last comment of `thread` waiting on `user`, with its text
+fn awaiting_in_thread<'a>(
+    mut thread: Vec<ThreadComment<'a>>,
+    user: &str,
+    since: Timestamp,
+    include_questions: bool,
+) -> Option<(ThreadComment<'a>, String, ReplyReason)> {
+    thread.sort_by_key(|comment| comment.created_at);
+
+    let mut participated = false;
+    let mut pending = None;
+    for comment in thread {
+        if comment.author.login.eq_ignore_ascii_case(user) {
+            participated = true;
+            pending = None;
+            continue;
+        }
+        if comment.created_at < since || comment.author.user_type.as_deref() == Some("Bot") {
+            continue;
+        }
+
+        let text = plain_text(comment.body);
+        let reason = if mentions(&text, user) {
+            ReplyReason::Mentioned
+        } else if include_questions && participated && text.contains('?') {
+            ReplyReason::Question
+        } else {
+            continue;
+        };
+        pending = Some((comment, truncate(text), reason));
+    }
+    pending
+}
+
+/// Whether `text` @-mentions `user`
+fn mentions(text: &str, user: &str) -> bool {
+    let text = text.to_lowercase();
+    let handle = format!("@{}", user.to_lowercase());
+    text.match_indices(&handle).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + handle.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric())
+            && !after.is_some_and(|c| c.is_alphanumeric() || c == '-')
+    })
+}
+
+fn truncate(text: String) -> String {
+    if text.chars().count() <= EXCERPT_MAX_CHARS {
+        return text;
+    }
+    let truncated: String = text.chars().take(EXCERPT_MAX_CHARS).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+
+    fn author(login: &str) -> Author {
+        Author {
+            login: login.to_string(),
+            user_type: Some("User".to_string()),
+        }
+    }
+
+    fn review_comment(
+        id: u64,
+        reply_to: Option<u64>,
+        login: &str,
+        body: &str,
+        at: &str,
+    ) -> ReviewComment {
+        ReviewComment {
+            id,
+            body: body.to_string(),
+            user: author(login),
+            path: "src/sync/mpsc.rs".to_string(),
+            line: Some(120),
+            in_reply_to_id: reply_to,
+            html_url: format!(
+                "https://github.com/tokio-rs/tokio/pull/42#discussion_r{}",
+                id
+            ),
+            created_at: at.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_mentions() {
+        assert!(mentions("thanks @Alice, can you look?", "alice"));
+        assert!(mentions("@alice", "alice"));
+        assert!(!mentions("cc @alice-bot", "alice"));
+        assert!(!mentions("mail alice@alice.dev", "alice"));
+    }
+
+    #[test]
+    fn test_review_comments_awaiting_reply() {
+        let pr = create_test_issue(42, "Bounded channel", true);
+        let since: Timestamp = "2024-03-05T00:00:00Z".parse().unwrap();
+        let review_comments = [
+            // A thread the user started, with an unanswered question
+            review_comment(
+                1,
+                None,
+                "me",
+                "Should this be bounded?",
+                "2024-03-04T10:00:00Z",
+            ),
+            review_comment(
+                2,
+                Some(1),
+                "bob",
+                "Why not use a semaphore?",
+                "2024-03-05T10:00:00Z",
+            ),
+            // A mention the user already answered
+            review_comment(3, None, "carol", "@me thoughts?", "2024-03-05T11:00:00Z"),
+            review_comment(4, Some(3), "me", "Looks fine", "2024-03-05T12:00:00Z"),
+            // A question in a thread the user isn't part of
+            review_comment(5, None, "dave", "Is this tested?", "2024-03-05T13:00:00Z"),
+            // A mention quoted in a reply doesn't count
+            review_comment(
+                6,
+                None,
+                "erin",
+                "> @me said\n\nAgreed",
+                "2024-03-05T14:00:00Z",
+            ),
+        ];
+        let comments = [Comment {
+            id: 77,
+            body: "@me can this land in 1.37?".to_string(),
+            author: author("frank"),
+            created_at: "2024-03-05T15:00:00Z".parse().unwrap(),
+            updated_at: "2024-03-05T15:00:00Z".parse().unwrap(),
+        }];
+
+        let found = find_awaiting_reply(
+            "tokio-rs/tokio",
+            &pr,
+            &comments,
+            &review_comments,
+            "me",
+            since,
+        );
+        let summary: Vec<_> = found
+            .iter()
+            .map(|r| (r.author.as_str(), r.reason, r.location.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("bob", ReplyReason::Question, Some("src/sync/mpsc.rs:120")),
+                ("frank", ReplyReason::Mentioned, None),
+            ]
+        );
+        assert_eq!(found[0].text, "Why not use a semaphore?");
+        assert!(found[1].url.ends_with("#issuecomment-77"));
+    }
+}
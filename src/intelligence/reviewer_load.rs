@@ -0,0 +1,156 @@
+//! How the open PRs of the user's repositories spread over their reviewers
+
+use jiff::Timestamp;
+use serde::Serialize;
+
+use crate::github::ReviewQueue;
+
+/// Most PRs listed under each reviewer
+pub const MAX_PRS_PER_REVIEWER: usize = 5;
+
+/// An open PR waiting on a reviewer
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingReview {
+    pub repo: String,
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    pub requested_at: Timestamp,
+    /// Whole days since the review was requested
+    pub waiting_days: i64,
+}
+
+/// The open PRs waiting on one reviewer, a user or an `org/team`
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewerLoad {
+    pub reviewer: String,
+    /// Longest waiting first
+    pub reviews: Vec<PendingReview>,
+}
+
+impl ReviewerLoad {
+    /// Days the longest waiting PR has waited
+    pub fn longest_wait(&self) -> i64 {
+        self.reviews.first().map_or(0, |review| review.waiting_days)
+    }
+}
+
+/// Group the pending review requests of `queues` by reviewer
+///
+/// Only repositories the user can push to count, and draft PRs are left
+/// out. Reviewers with the most PRs come first, ties broken by the longest
+/// wait.
+pub fn reviewer_workload(queues: &[(String, ReviewQueue)], now: Timestamp) -> Vec<ReviewerLoad> {
+    let mut loads: Vec<ReviewerLoad> = Vec::new();
+    for (repo, queue) in queues.iter().filter(|(_, queue)| queue.can_write) {
+        for pr in queue.pull_requests.iter().filter(|pr| !pr.is_draft) {
+            for request in &pr.requests {
+                let review = PendingReview {
+                    repo: repo.clone(),
+                    number: pr.number,
+                    title: pr.title.clone(),
+                    url: pr.url.clone(),
+                    author: pr.author.clone(),
+                    requested_at: request.requested_at,
+                    waiting_days: now.duration_since(request.requested_at).as_hours().max(0) / 24,
+                };
+                match loads
+                    .iter_mut()
+                    .find(|load| load.reviewer.eq_ignore_ascii_case(&request.reviewer))
+                {
+                    Some(load) => load.reviews.push(review),
+                    None => loads.push(ReviewerLoad {
+                        reviewer: request.reviewer.clone(),
+                        reviews: vec![review],
+                    }),
+                }
+            }
+        }
+    }
+
+    for load in &mut loads {
+        load.reviews.sort_by_key(|review| review.requested_at);
+    }
+    loads.sort_by(|a, b| {
+        b.reviews
+            .len()
+            .cmp(&a.reviews.len())
+            .then(b.longest_wait().cmp(&a.longest_wait()))
+            .then(a.reviewer.cmp(&b.reviewer))
+    });
+    loads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{PendingReviewPr, ReviewRequest};
+    use jiff::ToSpan;
+
+    fn pr(number: u32, is_draft: bool, requests: &[(&str, Timestamp)]) -> PendingReviewPr {
+        PendingReviewPr {
+            number,
+            title: format!("PR {}", number),
+            url: format!("https://github.com/tokio-rs/tokio/pull/{}", number),
+            author: "contributor".to_string(),
+            is_draft,
+            created_at: requests[0].1,
+            requests: requests
+                .iter()
+                .map(|(reviewer, requested_at)| ReviewRequest {
+                    reviewer: reviewer.to_string(),
+                    requested_at: *requested_at,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_reviewer_workload() {
+        let now: Timestamp = "2024-03-15T12:00:00Z".parse().unwrap();
+        let days_ago = |days: i64| now - (days * 24).hours();
+        let queues = vec![
+            (
+                "tokio-rs/tokio".to_string(),
+                ReviewQueue {
+                    can_write: true,
+                    pull_requests: vec![
+                        pr(1, false, &[("alice", days_ago(2)), ("bob", days_ago(9))]),
+                        pr(2, false, &[("Alice", days_ago(12))]),
+                        pr(3, true, &[("carol", days_ago(30))]),
+                        pr(4, false, &[("tokio-rs/maintainers", days_ago(1))]),
+                    ],
+                },
+            ),
+            (
+                "someone/fork".to_string(),
+                ReviewQueue {
+                    can_write: false,
+                    pull_requests: vec![pr(5, false, &[("alice", days_ago(3))])],
+                },
+            ),
+        ];
+
+        let loads = reviewer_workload(&queues, now);
+        let summary: Vec<(&str, usize, i64)> = loads
+            .iter()
+            .map(|load| {
+                (
+                    load.reviewer.as_str(),
+                    load.reviews.len(),
+                    load.longest_wait(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("alice", 2, 12),
+                ("bob", 1, 9),
+                ("tokio-rs/maintainers", 1, 1)
+            ]
+        );
+        assert_eq!(loads[0].reviews[0].number, 2);
+    }
+}
@@ -1,9 +1,10 @@
 use crate::config::Importance;
 use crate::github::Issue;
 use jiff::Timestamp;
+use serde::Serialize;
 
 /// Priority score for an issue or PR
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PriorityScore {
     pub total: u32,
     pub importance_score: u32,
@@ -11,13 +12,22 @@ pub struct PriorityScore {
     pub activity_score: u32,
     pub rule_match_score: u32,
     pub label_score: u32,
+    pub involvement_score: u32,
+    pub newcomer_score: u32,
 }
 
+/// Points for items opened by first-time contributors
+pub const NEWCOMER_POINTS: u32 = 15;
+
 /// Calculate priority score for an issue
+///
+/// When `current_user` is known, items assigned to them or awaiting their
+/// review are boosted.
 pub fn calculate_priority_score(
     issue: &Issue,
     repo_importance: Importance,
     is_pr: bool,
+    current_user: Option<&str>,
 ) -> PriorityScore {
     let mut score = PriorityScore {
         total: 0,
@@ -26,6 +36,8 @@ pub fn calculate_priority_score(
         activity_score: 0,
         rule_match_score: 0,
         label_score: 0,
+        involvement_score: 0,
+        newcomer_score: 0,
     };
 
     // 1. Repository importance (0-40 points)
@@ -49,29 +61,34 @@ pub fn calculate_priority_score(
     };
 
     // 3. Activity score (0-20 points)
-    score.activity_score = (issue.comments.total_count.min(10) * 2) as u32;
+    score.activity_score = issue.comments.total_count.min(10) * 2;
 
-    // 4. Simple rule match score based on labels (0-30 points)
-    score.rule_match_score = 0; // Simplified - no rule matching for now
+    // 4. Watch rule matches (0-30 points), added by the analyzer, which
+    // knows the configured rules
+    score.rule_match_score = 0;
 
     // 5. Label score (0-20 points)
     for label in &issue.labels {
-        let label_name = label.name.to_lowercase();
-        let label_points = if label_name.contains("security") || label_name.contains("critical") {
+        score.label_score = score.label_score.max(label_points(&label.name));
+    }
+
+    // 6. Direct involvement (0-25 points)
+    if let Some(user) = current_user {
+        score.involvement_score = if issue.is_review_requested_from(user) {
+            25
+        } else if issue.is_assigned_to(user) {
             20
-        } else if label_name.contains("bug") || label_name.contains("urgent") {
-            15
-        } else if label_name.contains("feature") || label_name.contains("enhancement") {
-            10
-        } else if label_name.contains("documentation") || label_name.contains("test") {
-            5
         } else {
-            2
+            0
         };
-        score.label_score = score.label_score.max(label_points);
     }
 
-    // 6. PR bonus (additional 10 points for PRs)
+    // 7. First-time contributors (0-15 points), who deserve a quick answer
+    if issue.is_first_time_contributor() {
+        score.newcomer_score = NEWCOMER_POINTS;
+    }
+
+    // 8. PR bonus (additional 10 points for PRs)
     if is_pr {
         score.total += 10;
     }
@@ -81,11 +98,29 @@ pub fn calculate_priority_score(
         + score.recency_score
         + score.activity_score
         + score.rule_match_score
-        + score.label_score;
+        + score.label_score
+        + score.involvement_score
+        + score.newcomer_score;
 
     score
 }
 
+/// Points for one label; an item scores the points of its best label
+pub(super) fn label_points(label: &str) -> u32 {
+    let label_name = label.to_lowercase();
+    if label_name.contains("security") || label_name.contains("critical") {
+        20
+    } else if label_name.contains("bug") || label_name.contains("urgent") {
+        15
+    } else if label_name.contains("feature") || label_name.contains("enhancement") {
+        10
+    } else if label_name.contains("documentation") || label_name.contains("test") {
+        5
+    } else {
+        2
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,15 +149,20 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 3 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         };
 
-        let score = calculate_priority_score(&issue, Importance::High, false);
+        let score = calculate_priority_score(&issue, Importance::High, false, None);
 
         assert_eq!(score.importance_score, 30); // High importance
         assert_eq!(score.recency_score, 30); // Last 6 hours
         assert_eq!(score.activity_score, 6); // 3 comments * 2
         assert_eq!(score.rule_match_score, 0); // No rule matching
         assert_eq!(score.label_score, 15); // Bug label
+        assert_eq!(score.involvement_score, 0); // No current user
     }
 
     #[test]
@@ -143,11 +183,54 @@ mod tests {
             url: "https://github.com/test/repo/pull/100".to_string(),
             comments: CommentCount { total_count: 0 },
             is_pull_request: true,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         };
 
-        let score = calculate_priority_score(&pr, Importance::Medium, true);
+        let score = calculate_priority_score(&pr, Importance::Medium, true, None);
 
         // Should have PR bonus
         assert!(score.total >= 10);
     }
+
+    #[test]
+    fn test_involvement_boost() {
+        let mut pr = crate::test_utils::create_test_issue(7, "Needs review", true);
+        let baseline = calculate_priority_score(&pr, Importance::Medium, true, Some("me"));
+        assert_eq!(baseline.involvement_score, 0);
+
+        pr.assignees.push(Author {
+            login: "Me".to_string(),
+            user_type: None,
+        });
+        let assigned = calculate_priority_score(&pr, Importance::Medium, true, Some("me"));
+        assert_eq!(assigned.involvement_score, 20);
+
+        pr.requested_reviewers.push(Author {
+            login: "me".to_string(),
+            user_type: None,
+        });
+        let requested = calculate_priority_score(&pr, Importance::Medium, true, Some("me"));
+        assert_eq!(requested.involvement_score, 25);
+        assert_eq!(requested.total, baseline.total + 25);
+
+        // Without a known user nothing is boosted
+        let anonymous = calculate_priority_score(&pr, Importance::Medium, true, None);
+        assert_eq!(anonymous.involvement_score, 0);
+    }
+
+    #[test]
+    fn test_first_time_contributor_boost() {
+        let mut pr = crate::test_utils::create_test_issue(8, "Fix typo", true);
+        pr.author_association = Some("CONTRIBUTOR".to_string());
+        let baseline = calculate_priority_score(&pr, Importance::Medium, true, None);
+        assert_eq!(baseline.newcomer_score, 0);
+
+        pr.author_association = Some("FIRST_TIME_CONTRIBUTOR".to_string());
+        let boosted = calculate_priority_score(&pr, Importance::Medium, true, None);
+        assert_eq!(boosted.newcomer_score, NEWCOMER_POINTS);
+        assert_eq!(boosted.total, baseline.total + NEWCOMER_POINTS);
+    }
 }
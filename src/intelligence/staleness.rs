@@ -0,0 +1,140 @@
+use crate::github::{Comment, Issue, IssueState};
+use jiff::Timestamp;
+use serde::Serialize;
+
+/// How long an open item has been idle, and whose move it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Staleness {
+    /// Days since the last comment, or since the last update without comments
+    pub idle_days: i64,
+    /// Who is waiting on the user, when the next step is theirs
+    pub waiting: Option<String>,
+}
+
+/// An item waiting on the user's review or reply
+#[derive(Debug, Clone, Serialize)]
+pub struct WaitingOnYou {
+    pub issue: Issue,
+    pub repo: String,
+    /// Login of whoever is waiting
+    pub from: String,
+    /// Days since they last heard back
+    pub idle_days: i64,
+    /// A reply drafted by Claude, with `settings.draft_nudges`
+    pub nudge: Option<String>,
+}
+
+/// Assess an open issue or PR from the point of view of `user`
+///
+/// The ball is in the user's court when someone else commented last on an
+/// item the user is part of: they opened it, are assigned to it, were asked
+/// to review it, or commented on it before. Without comments, a review
+/// request or an assignment on someone else's item counts the same. Bot
+/// comments are ignored. Returns `None` for closed items.
+pub fn assess_staleness(
+    issue: &Issue,
+    comments: &[Comment],
+    user: &str,
+    now: Timestamp,
+) -> Option<Staleness> {
+    if issue.state != IssueState::Open {
+        return None;
+    }
+
+    let is_user = |login: &str| login.eq_ignore_ascii_case(user);
+    let asked = issue.is_review_requested_from(user) || issue.is_assigned_to(user);
+
+    let last = comments
+        .iter()
+        .filter(|c| !c.author.is_bot())
+        .max_by_key(|c| c.created_at);
+    let (last_activity, waiting) = match last {
+        Some(last) => {
+            let involved = asked
+                || is_user(&issue.author.login)
+                || comments.iter().any(|c| is_user(&c.author.login));
+            let waiting =
+                (involved && !is_user(&last.author.login)).then(|| last.author.login.clone());
+            (last.created_at, waiting)
+        }
+        None => {
+            let waiting =
+                (asked && !is_user(&issue.author.login)).then(|| issue.author.login.clone());
+            (issue.updated_at, waiting)
+        }
+    };
+
+    Some(Staleness {
+        idle_days: (now.as_second() - last_activity.as_second()) / 86400,
+        waiting,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Author;
+    use crate::test_utils::create_test_issue;
+    use jiff::ToSpan;
+
+    fn author(login: &str) -> Author {
+        Author {
+            login: login.to_string(),
+            user_type: None,
+        }
+    }
+
+    fn comment(login: &str, at: Timestamp) -> Comment {
+        Comment {
+            id: 1,
+            body: "Any update?".to_string(),
+            author: author(login),
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn test_ball_in_your_court() {
+        let now = Timestamp::now();
+        let pr = create_test_issue(1, "Add feature", true);
+        let comments = vec![
+            comment("me", now - (6_i64 * 24).hours()),
+            comment("testuser", now - (4_i64 * 24).hours()),
+            comment("dependabot[bot]", now - 1_i64.hours()),
+        ];
+
+        let staleness = assess_staleness(&pr, &comments, "me", now).unwrap();
+        assert_eq!(staleness.idle_days, 4);
+        assert_eq!(staleness.waiting.as_deref(), Some("testuser"));
+
+        // The user spoke last
+        let replied = vec![
+            comment("testuser", now - 48_i64.hours()),
+            comment("me", now),
+        ];
+        let staleness = assess_staleness(&pr, &replied, "me", now).unwrap();
+        assert_eq!(staleness.waiting, None);
+
+        // Not a thread the user is part of
+        let others = vec![comment("testuser", now - 48_i64.hours())];
+        let staleness = assess_staleness(&pr, &others, "me", now).unwrap();
+        assert_eq!((staleness.idle_days, staleness.waiting), (2, None));
+    }
+
+    #[test]
+    fn test_review_requested_without_comments() {
+        let now = Timestamp::now();
+        let mut pr = create_test_issue(2, "Fix bug", true);
+        pr.updated_at = now - (3_i64 * 24).hours();
+        assert_eq!(assess_staleness(&pr, &[], "me", now).unwrap().waiting, None);
+
+        pr.requested_reviewers.push(author("Me"));
+        let staleness = assess_staleness(&pr, &[], "me", now).unwrap();
+        assert_eq!(staleness.idle_days, 3);
+        assert_eq!(staleness.waiting.as_deref(), Some("testuser"));
+
+        pr.state = IssueState::Closed;
+        assert_eq!(assess_staleness(&pr, &[], "me", now), None);
+    }
+}
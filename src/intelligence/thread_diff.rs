@@ -0,0 +1,78 @@
+use crate::github::Comment;
+
+/// Maximum number of hot threads followed per report
+pub const MAX_TRACKED_THREADS: usize = 5;
+
+/// What changed in a recurring hot thread since the previous report
+#[derive(Debug, Clone)]
+pub struct ThreadUpdate {
+    pub repo: String,
+    pub issue_number: u32,
+    pub change: String,
+}
+
+/// Comments posted after the last one already folded into a stored summary
+///
+/// GitHub comment IDs increase over time, so anything with a larger ID than
+/// `last_processed` is new. With nothing processed yet, every comment is new.
+pub fn comments_since(comments: &[Comment], last_processed: Option<u64>) -> Vec<&Comment> {
+    comments
+        .iter()
+        .filter(|c| last_processed.is_none_or(|last| c.id > last))
+        .collect()
+}
+
+/// Split a thread update response into the change and the updated summary
+///
+/// Expects the `CHANGED:` / `SUMMARY:` layout requested by
+/// `thread_update_prompt`. The summary may span several lines.
+pub fn parse_thread_update(response: &str) -> Option<(String, String)> {
+    let (before, summary) = response.split_once("SUMMARY:")?;
+    let change = before.split_once("CHANGED:")?.1.trim();
+    let summary = summary.trim();
+    if change.is_empty() || summary.is_empty() {
+        return None;
+    }
+    Some((change.to_string(), summary.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Author;
+    use jiff::Timestamp;
+
+    fn comment(id: u64) -> Comment {
+        Comment {
+            id,
+            body: format!("comment {}", id),
+            author: Author {
+                login: "alice".to_string(),
+                user_type: None,
+            },
+            created_at: Timestamp::now(),
+            updated_at: Timestamp::now(),
+        }
+    }
+
+    #[test]
+    fn test_comments_since() {
+        let comments = vec![comment(10), comment(20), comment(30)];
+        let ids = |new: Vec<&Comment>| new.iter().map(|c| c.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(comments_since(&comments, Some(20))), vec![30]);
+        assert_eq!(ids(comments_since(&comments, None)), vec![10, 20, 30]);
+        assert!(comments_since(&comments, Some(30)).is_empty());
+    }
+
+    #[test]
+    fn test_parse_thread_update() {
+        let response = "CHANGED: The maintainers agreed to ship the fix in 1.37.\n\nSUMMARY: Shutdown hangs when\nblocking tasks are pending. A fix is planned for 1.37.";
+        let (change, summary) = parse_thread_update(response).unwrap();
+        assert_eq!(change, "The maintainers agreed to ship the fix in 1.37.");
+        assert!(summary.starts_with("Shutdown hangs when\nblocking"));
+
+        assert_eq!(parse_thread_update("Nothing much happened."), None);
+        assert_eq!(parse_thread_update("CHANGED:\nSUMMARY: x"), None);
+    }
+}
@@ -0,0 +1,326 @@
+use crate::github::{Comment, Issue, IssueState, Review, ReviewState};
+use jiff::Timestamp;
+use serde::Serialize;
+
+/// Label fragments that mark an item as blocked on its author
+const WAITING_LABELS: &[&str] = &[
+    "waiting-on-author",
+    "waiting-for-author",
+    "awaiting-response",
+    "changes-requested",
+    "needs-info",
+    "more-info",
+    "needs-reproduction",
+];
+
+/// Why an item is blocked on its author rather than on the maintainer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitingReason {
+    /// A reviewer requested changes on a pull request
+    ChangesRequested,
+    /// Someone asked the reporter of an issue for more information
+    NeedsInfo,
+}
+
+impl WaitingReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            WaitingReason::ChangesRequested => "changes requested",
+            WaitingReason::NeedsInfo => "more information requested",
+        }
+    }
+}
+
+/// An item whose next step belongs to its author
+#[derive(Debug, Clone, Serialize)]
+pub struct WaitingItem {
+    pub issue: Issue,
+    pub repo: String,
+    pub reason: WaitingReason,
+    /// Days since the author was asked to respond
+    pub idle_days: i64,
+}
+
+/// Check whether an open issue or PR is waiting on its author
+///
+/// PRs are waiting when a reviewer requested changes and the author has not
+/// replied, pushed, or re-requested review since. A push shows as `head_sha`
+/// (the PR's current head commit) differing from the commit the review was
+/// submitted on. Issues are waiting when the
+/// last comment is a question from someone other than the reporter. A
+/// waiting-style label (e.g. `needs-info`, `S-waiting-on-author`) counts as a
+/// request as well. Returns the reason and the number of idle days once the
+/// item has been idle for at least `threshold_days`.
+pub fn detect_waiting_on_author(
+    issue: &Issue,
+    comments: &[Comment],
+    reviews: &[Review],
+    head_sha: Option<&str>,
+    now: Timestamp,
+    threshold_days: u32,
+) -> Option<(WaitingReason, i64)> {
+    if threshold_days == 0 || issue.state != IssueState::Open {
+        return None;
+    }
+
+    let author = issue.author.login.as_str();
+    let is_author = |login: &str| login.eq_ignore_ascii_case(author);
+
+    let (reason, asked_at) = if issue.is_pull_request {
+        match pending_changes_request(issue, reviews, head_sha) {
+            Some(at) => (WaitingReason::ChangesRequested, at),
+            None => (
+                WaitingReason::ChangesRequested,
+                labelled_since(issue, comments)?,
+            ),
+        }
+    } else {
+        match open_question(issue, comments) {
+            Some(at) => (WaitingReason::NeedsInfo, at),
+            None => (WaitingReason::NeedsInfo, labelled_since(issue, comments)?),
+        }
+    };
+
+    // The author responded after being asked
+    if comments
+        .iter()
+        .any(|c| is_author(&c.author.login) && c.created_at > asked_at)
+    {
+        return None;
+    }
+
+    let idle_days = (now.as_second() - asked_at.as_second()) / 86400;
+    if idle_days < threshold_days as i64 {
+        return None;
+    }
+
+    Some((reason, idle_days))
+}
+
+/// When the most recent outstanding "changes requested" review was submitted
+fn pending_changes_request(
+    issue: &Issue,
+    reviews: &[Review],
+    head_sha: Option<&str>,
+) -> Option<Timestamp> {
+    let author = issue.author.login.as_str();
+
+    reviews
+        .iter()
+        .filter(|r| r.state == ReviewState::ChangesRequested)
+        .filter(|r| !r.user.login.eq_ignore_ascii_case(author))
+        // Re-requesting review hands the PR back to the reviewer
+        .filter(|r| !issue.is_review_requested_from(&r.user.login))
+        // So does pushing new commits after the review
+        .filter(|r| match (r.commit_id.as_deref(), head_sha) {
+            (Some(reviewed), Some(head)) => reviewed == head,
+            _ => true,
+        })
+        // A later approval or dismissal by the same reviewer resolves it
+        .filter(|r| {
+            !reviews.iter().any(|later| {
+                later.user.login == r.user.login
+                    && later.submitted_at > r.submitted_at
+                    && matches!(later.state, ReviewState::Approved | ReviewState::Dismissed)
+            })
+        })
+        .filter_map(|r| r.submitted_at)
+        .max()
+}
+
+/// When a non-reporter asked a still unanswered question
+fn open_question(issue: &Issue, comments: &[Comment]) -> Option<Timestamp> {
+    let last = comments.iter().max_by_key(|c| c.created_at)?;
+
+    if last.author.login.eq_ignore_ascii_case(&issue.author.login) || !last.body.contains('?') {
+        return None;
+    }
+
+    Some(last.created_at)
+}
+
+/// When a waiting-style label was (at the latest) applied
+fn labelled_since(issue: &Issue, comments: &[Comment]) -> Option<Timestamp> {
+    let labelled = issue.labels.iter().any(|l| {
+        let name = l.name.to_lowercase().replace([' ', '_'], "-");
+        WAITING_LABELS.iter().any(|w| name.contains(w))
+    });
+    if !labelled {
+        return None;
+    }
+
+    // The label itself has no timestamp; use the last non-author comment if
+    // there is one, otherwise the last update to the item.
+    comments
+        .iter()
+        .filter(|c| !c.author.login.eq_ignore_ascii_case(&issue.author.login))
+        .map(|c| c.created_at)
+        .max()
+        .or(Some(issue.updated_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Author, Label};
+    use crate::test_utils::create_test_issue;
+    use jiff::ToSpan;
+
+    fn author(login: &str) -> Author {
+        Author {
+            login: login.to_string(),
+            user_type: None,
+        }
+    }
+
+    fn comment(login: &str, body: &str, at: Timestamp) -> Comment {
+        Comment {
+            id: 1,
+            body: body.to_string(),
+            author: author(login),
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    fn review(login: &str, state: ReviewState, at: Timestamp) -> Review {
+        Review {
+            id: 1,
+            user: author(login),
+            state,
+            submitted_at: Some(at),
+            commit_id: Some("abc123".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_pr_changes_requested() {
+        let now = Timestamp::now();
+        let pr = create_test_issue(1, "Add feature", true);
+        let reviews = vec![review(
+            "maintainer",
+            ReviewState::ChangesRequested,
+            now - (10_i64 * 24).hours(),
+        )];
+
+        let result = detect_waiting_on_author(&pr, &[], &reviews, None, now, 7);
+        assert_eq!(result, Some((WaitingReason::ChangesRequested, 10)));
+
+        // Not idle long enough yet
+        assert_eq!(
+            detect_waiting_on_author(&pr, &[], &reviews, None, now, 14),
+            None
+        );
+
+        // The author replied after the review
+        let comments = vec![comment("testuser", "Will do", now - 24_i64.hours())];
+        assert_eq!(
+            detect_waiting_on_author(&pr, &comments, &reviews, None, now, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pr_pushed_after_changes_requested() {
+        let now = Timestamp::now();
+        let pr = create_test_issue(1, "Add feature", true);
+        let reviews = vec![review(
+            "maintainer",
+            ReviewState::ChangesRequested,
+            now - (10_i64 * 24).hours(),
+        )];
+
+        // Still on the reviewed commit
+        assert_eq!(
+            detect_waiting_on_author(&pr, &[], &reviews, Some("abc123"), now, 7),
+            Some((WaitingReason::ChangesRequested, 10))
+        );
+
+        // The author pushed fixes without commenting
+        assert_eq!(
+            detect_waiting_on_author(&pr, &[], &reviews, Some("def456"), now, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pr_changes_request_resolved() {
+        let now = Timestamp::now();
+        let mut pr = create_test_issue(1, "Add feature", true);
+        let mut reviews = vec![review(
+            "maintainer",
+            ReviewState::ChangesRequested,
+            now - (10_i64 * 24).hours(),
+        )];
+
+        // Review re-requested from the reviewer
+        pr.requested_reviewers.push(author("maintainer"));
+        assert_eq!(
+            detect_waiting_on_author(&pr, &[], &reviews, None, now, 7),
+            None
+        );
+
+        // Later approval by the same reviewer
+        pr.requested_reviewers.clear();
+        reviews.push(review(
+            "maintainer",
+            ReviewState::Approved,
+            now - (8_i64 * 24).hours(),
+        ));
+        assert_eq!(
+            detect_waiting_on_author(&pr, &[], &reviews, None, now, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_issue_needs_info() {
+        let now = Timestamp::now();
+        let issue = create_test_issue(2, "Crash on startup", false);
+        let asked = now - (9_i64 * 24).hours();
+        let comments = vec![
+            comment("testuser", "It crashes", asked - 1_i64.hours()),
+            comment("maintainer", "Which version are you on?", asked),
+        ];
+
+        assert_eq!(
+            detect_waiting_on_author(&issue, &comments, &[], None, now, 7),
+            Some((WaitingReason::NeedsInfo, 9))
+        );
+
+        // A statement rather than a question is not a request for info
+        let comments = vec![comment("maintainer", "Thanks for the report.", asked)];
+        assert_eq!(
+            detect_waiting_on_author(&issue, &comments, &[], None, now, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_waiting_label() {
+        let now = Timestamp::now();
+        let mut issue = create_test_issue(3, "Unclear bug", false);
+        issue.updated_at = now - (20_i64 * 24).hours();
+        assert_eq!(
+            detect_waiting_on_author(&issue, &[], &[], None, now, 7),
+            None
+        );
+
+        issue.labels.push(Label {
+            name: "S-waiting-on-author".to_string(),
+            color: None,
+            description: None,
+        });
+        assert_eq!(
+            detect_waiting_on_author(&issue, &[], &[], None, now, 7),
+            Some((WaitingReason::NeedsInfo, 20))
+        );
+
+        // Disabled with a zero threshold
+        assert_eq!(
+            detect_waiting_on_author(&issue, &[], &[], None, now, 0),
+            None
+        );
+    }
+}
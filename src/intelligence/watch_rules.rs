@@ -0,0 +1,118 @@
+//! Watch rules from `[watch_rules]`, which flag items matching patterns
+//!
+//! Each rule has a name and a list of patterns; an item matches the rule when
+//! any pattern matches it. Plain patterns are searched for in the title and
+//! description, where `*` matches any run of characters. `label:`, `author:`
+//! and `repo:` patterns match the item's labels, author, and repository
+//! instead. `{username}` stands for the authenticated user.
+
+use std::collections::BTreeMap;
+
+use crate::config::glob_matches;
+use crate::github::Issue;
+
+/// Points added to the priority score of items matching any watch rule
+pub const RULE_MATCH_POINTS: u32 = 30;
+
+/// The configured watch rules, ready to match items
+#[derive(Debug, Clone, Default)]
+pub struct WatchRuleEngine {
+    rules: Vec<(String, Vec<String>)>,
+}
+
+impl WatchRuleEngine {
+    /// Rules from `[watch_rules]`, with `{username}` replaced by `current_user`
+    ///
+    /// Rules without patterns match nothing, as do patterns that need the
+    /// user when it is unknown.
+    pub fn new(rules: &BTreeMap<String, Vec<String>>, current_user: Option<&str>) -> Self {
+        let rules = rules
+            .iter()
+            .map(|(name, patterns)| {
+                let patterns = patterns
+                    .iter()
+                    .filter_map(|pattern| match current_user {
+                        Some(user) => Some(pattern.replace("{username}", user)),
+                        None if pattern.contains("{username}") => None,
+                        None => Some(pattern.clone()),
+                    })
+                    .filter(|pattern| !pattern.is_empty())
+                    .collect::<Vec<_>>();
+                (name.clone(), patterns)
+            })
+            .filter(|(_, patterns)| !patterns.is_empty())
+            .collect();
+        WatchRuleEngine { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Names of the rules `issue` in `repo` matches, in rule order
+    pub fn matches(&self, repo: &str, issue: &Issue) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter(|(_, patterns)| {
+                patterns
+                    .iter()
+                    .any(|pattern| pattern_matches(pattern, repo, issue))
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+fn pattern_matches(pattern: &str, repo: &str, issue: &Issue) -> bool {
+    match pattern.split_once(':') {
+        Some(("label", label)) => issue.labels.iter().any(|l| glob_matches(label, &l.name)),
+        Some(("author", login)) => issue.author.login.eq_ignore_ascii_case(login),
+        Some(("repo", repo_pattern)) => glob_matches(repo_pattern, repo),
+        _ => {
+            let text = format!("*{}*", pattern);
+            glob_matches(&text, &issue.title)
+                || issue
+                    .body
+                    .as_deref()
+                    .is_some_and(|body| glob_matches(&text, body))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_rules() {
+        let rules = BTreeMap::from([
+            (
+                "performance".to_string(),
+                vec!["regression".to_string(), "label:perf*".to_string()],
+            ),
+            ("mentions".to_string(), vec!["@{username}".to_string()]),
+            ("tokio".to_string(), vec!["repo:tokio-rs/*".to_string()]),
+            ("everything".to_string(), vec![]),
+        ]);
+        let mut issue = crate::test_utils::create_test_issue(1, "Scheduler Regression", false);
+        issue.body = Some("cc @carllerche".to_string());
+
+        let engine = WatchRuleEngine::new(&rules, Some("carllerche"));
+        assert_eq!(
+            engine.matches("test/repo", &issue),
+            ["mentions", "performance"]
+        );
+        assert_eq!(
+            engine.matches("tokio-rs/tokio", &issue),
+            ["mentions", "performance", "tokio"]
+        );
+
+        // Without the user, rules that need it are left out
+        let engine = WatchRuleEngine::new(&rules, None);
+        assert_eq!(engine.matches("test/repo", &issue), ["performance"]);
+
+        let unrelated = crate::test_utils::create_test_issue_with_labels(2, "Docs", vec!["perf"]);
+        assert_eq!(engine.matches("test/repo", &unrelated), ["performance"]);
+        assert!(WatchRuleEngine::new(&BTreeMap::new(), None).is_empty());
+    }
+}
@@ -1,15 +1,31 @@
+pub mod activity;
 pub mod cache;
 pub mod claude;
 pub mod cli;
+pub mod completions;
 pub mod config;
+pub mod costs;
+pub mod delivery;
+pub mod doctor;
 pub mod error;
+pub mod events;
+pub mod git;
 pub mod github;
+pub mod hooks;
 pub mod intelligence;
+pub mod lock;
+pub mod open;
+pub mod profile;
 pub mod progress;
 pub mod report;
+pub mod schema;
+pub mod snapshot;
 pub mod state;
+pub mod stats;
 pub mod summarize;
 pub mod time;
+pub mod triage;
+pub mod watch;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -0,0 +1,91 @@
+//! Advisory file locks that keep concurrent runs from racing
+//!
+//! A report holds the lock of the state file from loading the state until it
+//! saved it again, so overlapping runs (e.g. from cron) fail right away
+//! instead of overwriting each other's state. Cache writes take a short lock
+//! of their own. The locks are released when their holder exits, so a crashed
+//! run never leaves a stale lock behind.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Exclusive use of a state file, until dropped
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Lock `state_file`, failing when another gh-report instance holds it
+    pub fn acquire(state_file: &Path) -> Result<Self> {
+        let path = lock_path(state_file);
+        let file = open_lock_file(&path)?;
+        match file.try_lock() {
+            Ok(()) => {
+                debug!("Locked {:?}", path);
+                Ok(InstanceLock { _file: file })
+            }
+            Err(TryLockError::WouldBlock) => bail!(
+                "another gh-report instance is running (it holds {:?}); \
+                 pass --force to run anyway",
+                path
+            ),
+            Err(TryLockError::Error(e)) => {
+                Err(e).with_context(|| format!("Failed to lock {:?}", path))
+            }
+        }
+    }
+}
+
+/// Run `f` while holding the lock at `path`, waiting for other holders first
+pub fn with_lock<T>(path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let file = open_lock_file(path)?;
+    file.lock()
+        .with_context(|| format!("Failed to lock {:?}", path))?;
+    // Unlocked when `file` is dropped
+    f()
+}
+
+/// The lock file next to `state_file`, e.g. `state.json.lock`
+fn lock_path(state_file: &Path) -> PathBuf {
+    let mut path = state_file.as_os_str().to_owned();
+    path.push(".lock");
+    PathBuf::from(path)
+}
+
+fn open_lock_file(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {:?}", parent))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open lock file {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_instance_lock() {
+        let dir = TempDir::new().unwrap();
+        let state_file = dir.path().join("state.json");
+
+        let lock = InstanceLock::acquire(&state_file).unwrap();
+        assert!(dir.path().join("state.json.lock").exists());
+        let error = InstanceLock::acquire(&state_file).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("another gh-report instance is running"));
+
+        drop(lock);
+        InstanceLock::acquire(&state_file).unwrap();
+    }
+}
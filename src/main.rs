@@ -1,81 +1,277 @@
 use anyhow::{Context, Result};
 use clap::Parser;
+use clap_complete::Shell;
 use gh_report::{
-    cli::{Cli, Commands},
-    github::GitHubClient,
-    report::ReportGenerator,
+    activity::ActivityFormat,
+    cache::{format_size, CacheManager},
+    claude::{probe_backends, ClaudeClient, PRICING_TABLE_VERSION},
+    cli::{CacheAction, Cli, Commands, ProfileAction},
+    completions,
+    config::{CollisionStrategy, LlmProvider, ReportFormat},
+    costs::{budget_warning, month_to_date, render_costs, CostEntry, CostLedger},
+    doctor::{render_checks, run_checks, CheckStatus, DoctorOptions},
+    events::EventStore,
+    git::LocalRepo,
+    github::{GitHubClient, RetryPolicy},
+    hooks::{run_hooks, HookKind, HookVars},
+    intelligence::render_explanation,
+    lock::InstanceLock,
+    profile::{PhaseTimings, ProfileLayer},
+    report::{
+        previous_period, HistoryEntry, OutputTarget, ReportFlavor, ReportGenerator, ReportHistory,
+    },
+    snapshot::ActivitySnapshot,
+    state::RecentItem,
+    stats::{activity_stats, render_activity_stats, render_tool_stats, RunRecord, StatsStore},
     summarize::IssueSummarizer,
+    time::TimeRange,
+    triage::Triager,
     Config, State,
 };
+use jiff::{Timestamp, ToSpan};
 use std::path::{Path, PathBuf};
-use tracing::{error, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{error, info, info_span, warn, Level};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::EnvFilter;
 
+/// Set while a report or summary goes to stdout, to keep status lines out of it
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for status messages, left out when the output is stdout
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    select_profile(&mut cli)?;
 
     // Set up logging based on verbosity
-    setup_logging(cli.verbose)?;
+    let timings = setup_logging(cli.verbose, cli.profile_run)?;
+
+    let started_at = Timestamp::now();
+    let timer = Instant::now();
+    let result = run_command(&cli);
+    record_costs(&cli);
+    record_usage(&cli, started_at, timer.elapsed(), result.is_ok());
+
+    if let Some(timings) = timings {
+        // On stderr, so it stays out of piped output like `activity --format json`
+        eprint!("\n{}", timings.render(timer.elapsed()));
+    }
 
+    result
+}
+
+fn run_command(cli: &Cli) -> Result<()> {
     // Run the appropriate command
     match cli.command {
         Some(Commands::Report {
             ref since,
+            ref until,
+            since_last,
             ref output,
+            append,
             dry_run,
             estimate_cost,
             no_cache,
             clear_cache,
+            ref with_git,
+            ci_logs,
+            ref model,
+            ref secondary_model,
+            ref language,
+            format,
+            flavor,
+            ref from_snapshot,
+            from_events,
+            offline,
+            ref org,
+            ref users,
+            ref repos,
+            ref email,
+            open,
+            full,
+            max_items,
+            resume,
         }) => {
             info!("Generating activity report");
-            report_command(
+            let options = ReportOptions {
                 since,
+                until: until.as_deref(),
+                since_last,
                 output,
+                append,
                 dry_run,
                 estimate_cost,
                 no_cache,
                 clear_cache,
-                &cli,
-            )?;
+                with_git: with_git.as_deref(),
+                ci_logs,
+                model: model.as_deref(),
+                secondary_model: secondary_model.as_deref(),
+                language: language.as_deref(),
+                format,
+                flavor,
+                from_snapshot: from_snapshot.as_deref(),
+                from_events,
+                offline,
+                org: org.as_deref(),
+                users,
+                repos,
+                email,
+                open,
+                full,
+                max_items,
+                resume,
+            };
+            report_command(&options, cli)?;
         }
-        Some(Commands::Init { ref since, output }) => {
+        Some(Commands::Init {
+            ref since,
+            ref output,
+        }) => {
             info!("Initializing configuration based on GitHub activity");
-            init_command(since, output)?;
+            init_command(since, output.clone())?;
         }
         Some(Commands::RebuildState) => {
             info!("Rebuilding state from existing reports");
-            rebuild_state_command(&cli)?;
+            rebuild_state_command(cli)?;
         }
         Some(Commands::Summarize {
-            ref target,
+            ref targets,
             ref output,
             no_recommendations,
+            refresh,
+            stream,
+            open,
+            with_diff,
+            ref model,
+            ref secondary_model,
+            ref language,
+        }) => {
+            info!("Summarizing issue/PR: {}", targets.join(", "));
+            summarize_command(
+                targets,
+                output.as_deref(),
+                no_recommendations,
+                refresh,
+                stream,
+                open,
+                with_diff,
+                model.as_deref(),
+                secondary_model.as_deref(),
+                language.as_deref(),
+                cli,
+            )?;
+        }
+        Some(Commands::Triage {
+            ref repo,
+            ref since,
+            limit,
+            ref output,
+            apply,
+        }) => {
+            info!("Triaging issues of {}", repo);
+            triage_command(repo, since, limit, output.as_deref(), apply, cli)?;
+        }
+        Some(Commands::Projects {
+            ref project_urls,
+            ref since,
+            ref output,
         }) => {
-            info!("Summarizing issue/PR: {}", target);
-            summarize_command(target, output.as_deref(), no_recommendations, &cli)?;
+            info!("Showing project boards");
+            projects_command(project_urls, since, output.as_deref(), cli)?;
+        }
+        Some(Commands::Explain { ref target }) => {
+            info!("Explaining the score of {}", target);
+            explain_command(target, cli)?;
+        }
+        Some(Commands::Mute { ref target, list }) => {
+            mute_command(target.as_deref(), list, cli)?;
+        }
+        Some(Commands::Unmute { ref target }) => {
+            unmute_command(target, cli)?;
         }
         Some(Commands::ListRepos {
             ref since,
+            ref until,
             ref output,
         }) => {
             info!("Listing repositories with recent activity");
-            list_repos_command(since, output, &cli)?;
+            list_repos_command(since, until.as_deref(), output, cli)?;
         }
         Some(Commands::Activity {
             ref since,
+            ref until,
             ref include_types,
             ref exclude_types,
+            format,
             ref output,
+            ref save_snapshot,
         }) => {
             info!("Showing GitHub activity feed");
+            let range = TimeRange::parse(since, until.as_deref())
+                .with_context(|| format!("Invalid time format: {}", since))?;
             activity_command(
-                since,
+                &range,
                 include_types.as_ref(),
                 exclude_types.as_ref(),
+                format,
                 output,
-                &cli,
+                save_snapshot.as_deref(),
+                cli,
             )?;
         }
+        Some(Commands::Ingest { stdin, ref files }) => {
+            info!("Ingesting webhook events");
+            ingest_command(stdin, files, cli)?;
+        }
+        Some(Commands::Watch { ref every }) => {
+            info!("Watching for new activity every {}", every);
+            watch_command(every, cli)?;
+        }
+        Some(Commands::Models) => {
+            info!("Listing Claude models");
+            models_command(cli)?;
+        }
+        Some(Commands::Costs) => {
+            info!("Showing LLM costs");
+            costs_command(cli)?;
+        }
+        Some(Commands::Doctor) => {
+            info!("Checking the environment");
+            doctor_command(cli)?;
+        }
+        Some(Commands::Stats {
+            tool,
+            ref since,
+            top,
+        }) => {
+            info!("Showing usage statistics");
+            stats_command(tool, since.as_deref(), top, cli)?;
+        }
+        Some(Commands::Cache { action }) => {
+            info!("Managing the cache");
+            cache_command(action, cli)?;
+        }
+        Some(Commands::Profile { ref action }) => {
+            info!("Managing profiles");
+            profile_command(action, cli)?;
+        }
+        Some(Commands::Completions {
+            shell,
+            ref complete_target,
+        }) => {
+            completions_command(shell, complete_target.as_deref(), cli)?;
+        }
         None => {
             // Show help when no command is provided
             println!("Use --help to see available commands");
@@ -85,7 +281,87 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn setup_logging(verbosity: u8) -> Result<()> {
+/// Append this run to the local statistics file, if enabled
+fn record_usage(cli: &Cli, started_at: Timestamp, duration: Duration, success: bool) {
+    let Some(command) = &cli.command else {
+        return;
+    };
+    if matches!(
+        command,
+        Commands::Stats { .. } | Commands::Completions { .. }
+    ) {
+        return;
+    }
+
+    let Ok(config) = Config::load(cli.config.as_deref()) else {
+        return;
+    };
+    if !config.settings.usage_stats {
+        return;
+    }
+
+    let record = RunRecord::capture(command.name(), started_at, duration, success);
+    if let Err(e) = StatsStore::new(config.settings.stats_file).append(&record) {
+        warn!("Failed to record usage statistics: {}", e);
+    }
+}
+
+/// Point the config path at the profile selected with `--profile`
+fn select_profile(cli: &mut Cli) -> Result<()> {
+    let Some(profile) = &cli.profile else {
+        return Ok(());
+    };
+    let path = Config::profile_path(profile)?;
+    let manages_profiles = matches!(
+        cli.command,
+        Some(Commands::Profile { .. } | Commands::Init { .. })
+    );
+    if !path.exists() && !manages_profiles {
+        anyhow::bail!(
+            "Profile {:?} doesn't exist, create it with `gh-report profile create {}`",
+            profile,
+            profile
+        );
+    }
+    cli.config = Some(path);
+    Ok(())
+}
+
+/// Add the LLM usage since the last call to the cost ledger, warning when
+/// the monthly budget is running out
+fn record_costs(cli: &Cli) {
+    let Some(command) = &cli.command else {
+        return;
+    };
+    let Ok(config) = Config::load(cli.config.as_deref()) else {
+        return;
+    };
+    let now = Timestamp::now();
+    let Some(entry) = CostEntry::capture(command.name(), now, &config.claude.pricing()) else {
+        return;
+    };
+
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let entries = match CostLedger::beside(&state_file).append(entry) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("Failed to record LLM costs: {}", e);
+            return;
+        }
+    };
+    if let Some(budget) = config.claude.monthly_budget {
+        let spent = month_to_date(&entries, now, &jiff::tz::TimeZone::system());
+        if let Some(warning) = budget_warning(spent, budget) {
+            println!("⚠️  {}", warning);
+        }
+    }
+}
+
+/// Set up logging, and phase timing when profiling the run
+fn setup_logging(verbosity: u8, profile_run: bool) -> Result<Option<PhaseTimings>> {
     let filter = match verbosity {
         0 => EnvFilter::new("warn"),
         1 => EnvFilter::new("info"),
@@ -93,50 +369,135 @@ fn setup_logging(verbosity: u8) -> Result<()> {
         _ => EnvFilter::new("trace"),
     };
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
+    // Logs go to stderr, so they stay out of output piped from stdout
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
         .with_target(false)
+        .with_filter(filter);
+
+    // Phase spans are recorded regardless of the log verbosity
+    let timings = profile_run.then(PhaseTimings::default);
+    let profile_layer = timings.clone().map(|timings| {
+        ProfileLayer::new(timings).with_filter(Targets::new().with_target("gh_report", Level::INFO))
+    });
+
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(profile_layer)
         .init();
 
-    Ok(())
+    Ok(timings)
 }
 
-fn report_command(
-    since: &str,
-    output: &Option<PathBuf>,
+/// Flags for the report command
+struct ReportOptions<'a> {
+    since: &'a str,
+    until: Option<&'a str>,
+    since_last: bool,
+    output: &'a [PathBuf],
+    append: bool,
     dry_run: bool,
     estimate_cost: bool,
-    _no_cache: bool,
+    no_cache: bool,
     clear_cache: bool,
-    cli: &Cli,
-) -> Result<()> {
-    // Check GitHub CLI first
-    info!("Checking GitHub CLI");
-    match gh_report::github::check_gh_version() {
-        Ok(version) => info!("Using gh version {}", version),
-        Err(e) => {
-            error!("GitHub CLI check failed: {}", e);
-            println!("❌ {}", e);
-            println!("\nPlease install GitHub CLI from: https://cli.github.com/");
-            return Err(e);
+    with_git: Option<&'a Path>,
+    ci_logs: bool,
+    model: Option<&'a str>,
+    secondary_model: Option<&'a str>,
+    language: Option<&'a str>,
+    format: Option<ReportFormat>,
+    flavor: ReportFlavor,
+    from_snapshot: Option<&'a Path>,
+    from_events: bool,
+    offline: bool,
+    org: Option<&'a str>,
+    users: &'a [String],
+    repos: &'a [String],
+    email: &'a [String],
+    open: bool,
+    full: bool,
+    max_items: Option<usize>,
+    resume: bool,
+}
+
+fn report_command(options: &ReportOptions, cli: &Cli) -> Result<()> {
+    let ReportOptions {
+        since,
+        until,
+        since_last,
+        output,
+        append,
+        dry_run,
+        estimate_cost,
+        no_cache,
+        clear_cache,
+        with_git,
+        ci_logs,
+        model,
+        secondary_model,
+        language,
+        format,
+        flavor,
+        from_snapshot,
+        from_events,
+        offline,
+        org,
+        users,
+        repos,
+        email,
+        open,
+        full,
+        max_items,
+        resume,
+    } = *options;
+    if output.iter().any(|path| path == Path::new("-")) {
+        QUIET.store(true, Ordering::Relaxed);
+    }
+
+    // Check GitHub CLI first; offline reports never run it
+    if !offline {
+        info!("Checking GitHub CLI");
+        match gh_report::github::check_gh_version() {
+            Ok(version) => info!("Using gh version {}", version),
+            Err(e) => {
+                error!("GitHub CLI check failed: {}", e);
+                status!("❌ {}", e);
+                status!("\nPlease install GitHub CLI from: https://cli.github.com/");
+                return Err(e);
+            }
         }
     }
 
     info!("Loading configuration");
     let mut config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
-
-    // Override report directory if custom output is specified
-    if let Some(output_path) = output {
-        if let Some(parent) = output_path.parent() {
-            info!("Using custom output directory: {:?}", parent);
-            config.settings.report_dir = parent.to_path_buf();
-
-            // Create the output directory if it doesn't exist
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create output directory: {:?}", parent))?;
-        }
+    config.override_models(model, secondary_model);
+    if let Some(language) = language {
+        config.claude.output_language = Some(language.to_string());
+    }
+    if let Some(format) = format {
+        config.report.format = format;
+    }
+    if let Some(org) = org {
+        config.report.org = Some(org.to_string());
+    }
+    if !users.is_empty() {
+        config.report.users = users.to_vec();
+    }
+    if !repos.is_empty() {
+        config.report.include_repos = repos.to_vec();
+    }
+    if full {
+        config.report.min_priority_score = 0;
+    }
+    if let Some(max_items) = max_items {
+        config.settings.max_issues_per_report = max_items;
+    }
+    if append {
+        config.settings.collision_strategy = CollisionStrategy::Append;
     }
 
+    let output_targets = OutputTarget::resolve(&config, output)?;
+
     // Override state file location if specified
     let state_file = if let Some(state_path) = &cli.state {
         info!("Using custom state file: {:?}", state_path);
@@ -152,6 +513,7 @@ fn report_command(
         config.settings.state_file.clone()
     };
 
+    let _lock = lock_state(&state_file, cli)?;
     info!("Loading state");
     let mut state = State::load(&state_file).context("Failed to load state")?;
 
@@ -162,10 +524,54 @@ fn report_command(
     }
 
     // Create GitHub client for dynamic updates
-    let github_client = GitHubClient::new().context("Failed to create GitHub client")?;
+    let github_client = if offline {
+        if !config.cache.enabled {
+            anyhow::bail!("--offline reports from the cache, but cache.enabled is false");
+        }
+        let cache = cache_manager(&config).with_offline();
+        cache.initialize()?;
+        status!("📴 Offline: reporting from cached data only");
+        GitHubClient::offline()
+            .with_host(config.settings.github_host.as_deref())
+            .with_activity_cache(Some(cache))
+    } else {
+        github_client(&config)?.with_activity_cache(if no_cache {
+            None
+        } else {
+            activity_cache(&config)
+        })
+    };
 
-    // Using activity-based discovery - no need for explicit repository tracking
-    println!("🔍 Discovering repositories from your GitHub activity...");
+    let snapshot = match from_snapshot {
+        Some(path) => {
+            let snapshot = ActivitySnapshot::load(path)?;
+            status!(
+                "📼 Reporting on a snapshot of {} events captured at {}",
+                snapshot.events.len(),
+                config.report.locale().format_datetime(snapshot.captured_at)
+            );
+            Some(snapshot)
+        }
+        None if from_events => {
+            status!(
+                "📨 Reporting on webhook events stored in {:?}",
+                config.settings.event_store
+            );
+            None
+        }
+        None => {
+            match &config.report.org {
+                Some(org) => status!("🏢 Reporting on all repositories of {}...", org),
+                None if !config.report.users.is_empty() => status!(
+                    "👥 Reporting on the activity of {}...",
+                    config.report.users.join(", ")
+                ),
+                // Using activity-based discovery - no need for explicit repository tracking
+                None => status!("🔍 Discovering repositories from your GitHub activity..."),
+            }
+            None
+        }
+    };
 
     // Dry run is now handled in the report generator
 
@@ -175,33 +581,161 @@ fn report_command(
         return Ok(());
     }
 
-    // Parse the time duration using our new utility
-    use gh_report::time::TimeDuration;
-    let duration: TimeDuration = since
-        .parse()
+    let range = TimeRange::parse(since, until)
         .with_context(|| format!("Invalid time format: {}", since))?;
-    let lookback_days = duration.as_days();
+    let mut lookback_days = range.lookback_days(Timestamp::now());
 
-    info!("Using custom since period: {} ({})", since, duration);
+    info!("Using custom since period: {} ({})", since, range);
 
-    info!("Generating report for the last {} days", lookback_days);
-    println!("✓ Loading configuration");
+    status!("✓ Loading configuration");
     if let Some(last_run) = state.last_run {
-        println!("✓ Last report: {}", last_run.strftime("%Y-%m-%d %H:%M"));
+        status!("✓ Last report: {}", last_run.strftime("%Y-%m-%d %H:%M"));
     } else {
-        println!("✓ First run - no previous report found");
+        status!("✓ First run - no previous report found");
     }
 
+    let since_last_report = match state.last_run {
+        Some(_) if since_last => {
+            let window_start = state.get_since_timestamp(config.settings.max_lookback_days);
+            let hours = Timestamp::now().duration_since(window_start).as_hours();
+            lookback_days = (hours / 24 + 1) as u32;
+            status!(
+                "✓ Reporting activity since {}",
+                window_start.strftime("%Y-%m-%d %H:%M")
+            );
+            Some(window_start)
+        }
+        None if since_last => {
+            status!("ℹ️  No previous report, using --since {}", since);
+            None
+        }
+        _ => None,
+    };
+    info!("Generating report for the last {} days", lookback_days);
+
+    let snapshot = match snapshot {
+        None if from_events => {
+            let store = EventStore::new(config.settings.event_store.clone());
+            let user = github_client.get_current_user().ok();
+            let captured_at = range.end.unwrap_or_else(Timestamp::now);
+            let snapshot = store
+                .snapshot(captured_at, range.lookback_days(captured_at), user)
+                .context("Failed to read the event store")?;
+            status!(
+                "✓ {} stored events about {} issues/PRs",
+                snapshot.events.len(),
+                snapshot.items.len()
+            );
+            Some(snapshot)
+        }
+        snapshot => snapshot,
+    };
+    // Stored webhook events are live data, unlike a saved snapshot or the
+    // cache of an offline run; a window ending before now re-covers the past
+    let replay = (snapshot.is_some() && !from_events) || offline || range.end.is_some();
+
     // Generate the report
-    println!("📊 Fetching GitHub activity...");
+    if snapshot.is_none() {
+        status!("📊 Fetching GitHub activity...");
+    }
 
     // Check if AI summarization is available
-    if std::env::var("ANTHROPIC_API_KEY").is_err() {
-        println!("ℹ️  Running without AI summarization (ANTHROPIC_API_KEY not set)");
+    let uses_anthropic_key = matches!(
+        config.llm_provider(),
+        LlmProvider::Anthropic | LlmProvider::Auto
+    );
+    if !offline && uses_anthropic_key && std::env::var("ANTHROPIC_API_KEY").is_err() {
+        status!("ℹ️  Running without AI summarization (ANTHROPIC_API_KEY not set)");
     }
 
-    let generator = ReportGenerator::new(github_client, &config, &state);
-    let report = if dry_run {
+    let mut generator = ReportGenerator::new(github_client, &config, &state);
+    if let Some(path) = with_git {
+        let path = gh_report::config::expand_tilde(path)?;
+        let local_repo = LocalRepo::open(&path)
+            .with_context(|| format!("Failed to open local clone at {}", path.display()))?;
+        status!("✓ Correlating with local clone at {}", path.display());
+        generator = generator.with_local_repo(local_repo);
+    }
+    if ci_logs {
+        status!("🔍 Inspecting failed CI runs of open PRs");
+        generator = generator.with_ci_logs(true);
+    }
+    if let Some(window_start) = since_last_report {
+        generator = generator.with_since_last_report(window_start);
+    }
+    if range.absolute {
+        status!("✓ Reporting activity {}", range);
+        generator = generator.with_window(range.start, range.end);
+    }
+    if resume {
+        generator = generator.with_resume();
+        match generator.resumed_since() {
+            Some(window_start) => {
+                let hours = Timestamp::now().duration_since(window_start).as_hours();
+                lookback_days = (hours / 24 + 1) as u32;
+                status!(
+                    "↻ Resuming the interrupted report of activity since {}",
+                    window_start.strftime("%Y-%m-%d %H:%M")
+                );
+            }
+            None => status!("ℹ️  No interrupted report to resume, starting a new one"),
+        }
+    }
+    // Rollups are compared to the previous report of the same flavor
+    let window_start = generator
+        .resumed_since()
+        .or(since_last_report)
+        .unwrap_or(range.start);
+    let history = ReportHistory::beside(&state_file);
+    if flavor.is_rollup() {
+        let previous = match history.load() {
+            Ok(entries) => previous_period(&entries, flavor, window_start).cloned(),
+            Err(e) => {
+                warn!("Failed to read the report history: {}", e);
+                None
+            }
+        };
+        match &previous {
+            Some(entry) => status!(
+                "✓ Comparing to the {} report of {}",
+                flavor.period(),
+                config.report.locale().format_date(entry.until)
+            ),
+            None => status!(
+                "ℹ️  No earlier {} report to compare with",
+                flavor.label().to_lowercase()
+            ),
+        }
+        generator = generator.with_flavor(flavor, previous);
+    }
+    let hook_vars = HookVars::new()
+        .with("since", window_start)
+        .with("until", range.end.unwrap_or_else(Timestamp::now))
+        .with("flavor", flavor.label().to_lowercase())
+        .with("format", config.report.format.extension())
+        .with_path("report_dir", &config.settings.report_dir)
+        .with_path("state_file", &state_file);
+    let hook_timeout = Duration::from_secs(config.hooks.timeout_secs);
+    if !config.hooks.pre_report.is_empty() && !dry_run {
+        let outputs = run_hooks(
+            HookKind::PreReport,
+            &config.hooks.pre_report,
+            &hook_vars,
+            hook_timeout,
+        )?;
+        status!("✓ Ran the pre_report hooks");
+        let notes = outputs
+            .into_iter()
+            .map(|output| output.stdout)
+            .filter(|stdout| !stdout.trim().is_empty())
+            .collect();
+        generator = generator.with_hook_notes(notes);
+    }
+    let report = if let Some(snapshot) = &snapshot {
+        generator
+            .generate_from_snapshot(snapshot)
+            .context("Failed to generate report from snapshot")?
+    } else if dry_run {
         generator
             .generate_with_progress(lookback_days, true)
             .context("Failed to generate repository-based report (dry run)")?
@@ -211,22 +745,94 @@ fn report_command(
             .context("Failed to generate repository-based report")?
     };
 
-    // Save the report
-    let report_path = if let Some(output_path) = output {
-        // Custom output path specified
-        report
-            .save_to_path(output_path)
-            .context("Failed to save report to custom path")?
-    } else {
-        // Use default naming and location
-        report.save(&config).context("Failed to save report")?
-    };
+    let unavailable = generator.unavailable();
+    if !unavailable.is_empty() {
+        status!("📴 Not in the cache, so left out of the report:");
+        for data in &unavailable {
+            status!("   - {}", data);
+        }
+    }
 
-    println!("✓ Report saved to: {:?}", report_path);
+    // Save the report to every destination
+    let report_paths = report
+        .save_all(&output_targets, &config)
+        .context("Failed to save report")?;
+
+    for report_path in &report_paths {
+        status!("✓ Report saved to: {:?}", report_path);
+
+        if let Some(ics_path) = report
+            .save_deadlines_ics(report_path)
+            .context("Failed to save deadlines calendar")?
+        {
+            status!(
+                "✓ {} deadlines saved to: {:?}",
+                report.deadlines.len(),
+                ics_path
+            );
+        }
+    }
 
-    // Update state
-    state.update_last_run();
+    // Update state; a dry run covers nothing and a report from a snapshot
+    // re-covers an old window, so the next --since-last still starts at the
+    // last live report
+    if !replay && !dry_run {
+        state.record_run(report.timestamp);
+        state.record_items(report.items.iter().cloned());
+        state.record_seen(report.seen_items.clone(), report.timestamp);
+    }
+    if let Some(repo_trust) = &report.repo_trust {
+        state.repo_trust = repo_trust.clone();
+    }
+    if let Some(scores) = &report.scores {
+        state.last_scores = Some(scores.clone());
+    }
     state.save(&state_file).context("Failed to save state")?;
+    if !replay && !dry_run {
+        let entry = HistoryEntry {
+            flavor,
+            since: window_start,
+            until: report.timestamp,
+            totals: report.totals,
+        };
+        if let Err(e) = history.append(entry) {
+            warn!("Failed to record the report in {:?}: {}", history.path(), e);
+        }
+    }
+    evict_cache(&config);
+
+    if !email.is_empty() && !dry_run && !replay {
+        gh_report::delivery::send_email(&report, &config, email)
+            .context("The report was saved, but emailing it failed")?;
+        status!("✓ Report emailed to: {}", email.join(", "));
+    }
+    if config.delivery.slack.is_some() && !dry_run && !replay {
+        gh_report::delivery::send_slack(&report, &config)
+            .context("The report was saved, but posting it to Slack failed")?;
+        status!("✓ Report posted to Slack");
+    }
+    if !config.hooks.post_report.is_empty() && !dry_run {
+        for report_path in &report_paths {
+            let vars = hook_vars
+                .clone()
+                .with_path("report_path", report_path)
+                .with("title", &report.title);
+            run_hooks(
+                HookKind::PostReport,
+                &config.hooks.post_report,
+                &vars,
+                hook_timeout,
+            )
+            .context("The report was saved, but running its hooks failed")?;
+            status!("✓ Ran the post_report hooks for: {:?}", report_path);
+        }
+    }
+
+    if open {
+        for report_path in &report_paths {
+            gh_report::open::open(report_path)?;
+        }
+    }
 
     Ok(())
 }
@@ -296,9 +902,9 @@ fn init_command(since: &str, output: Option<PathBuf>) -> Result<()> {
     // Also save initial state
     let state_path = config.settings.state_file.clone();
     let expanded_state_path = if let Some(s) = state_path.to_str() {
-        if s.starts_with("~/") {
+        if let Some(rest) = s.strip_prefix("~/") {
             let home = dirs::home_dir().context("Could not determine home directory")?;
-            home.join(&s[2..])
+            home.join(rest)
         } else {
             state_path
         }
@@ -342,127 +948,552 @@ fn rebuild_state_command(cli: &Cli) -> Result<()> {
 }
 
 fn clear_cache_dir(config: &Config) -> Result<()> {
-    let cache_dir = config.settings.report_dir.join(".cache");
-    if cache_dir.exists() {
-        std::fs::remove_dir_all(&cache_dir)
-            .with_context(|| format!("Failed to clear cache at {:?}", cache_dir))?;
-        info!("Cache cleared");
-    } else {
-        info!("No cache to clear");
+    cache_manager(config).clear_all()?;
+    info!("Cache cleared");
+    Ok(())
+}
+
+fn cache_manager(config: &Config) -> CacheManager {
+    CacheManager::new(
+        config.cache_dir(),
+        config.cache.ttl_hours,
+        config.cache.compression_enabled,
+    )
+    .with_max_size_mb(config.cache.max_size_mb)
+}
+
+/// Evict the least recently used cache entries beyond `cache.max_size_mb`
+fn evict_cache(config: &Config) {
+    if !config.cache.enabled {
+        return;
+    }
+    if let Err(e) = cache_manager(config).evict_to_max_size() {
+        warn!("Failed to evict cache entries: {}", e);
+    }
+}
+
+/// The cache activity feeds are reused from, unless it is turned off
+/// A GitHub client following the `[github]` retry and rate limit settings
+/// and `settings.github_host`
+fn github_client(config: &Config) -> Result<GitHubClient> {
+    Ok(GitHubClient::new()
+        .context("Failed to create GitHub client")?
+        .with_retry(RetryPolicy::from_config(&config.github))
+        .with_rate_limit_threshold(config.github.min_remaining_requests)
+        .with_host(config.settings.github_host.as_deref()))
+}
+
+fn activity_cache(config: &Config) -> Option<CacheManager> {
+    if !config.cache.enabled || config.cache.activity_ttl_minutes == 0 {
+        return None;
+    }
+    let cache = cache_manager(config).with_activity_ttl_minutes(config.cache.activity_ttl_minutes);
+    if let Err(e) = cache.initialize() {
+        warn!("Failed to initialize cache: {}", e);
+        return None;
+    }
+    Some(cache)
+}
+
+fn cache_command(action: CacheAction, cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let cache = cache_manager(&config);
+
+    match action {
+        CacheAction::Stats => {
+            let stats = cache.get_stats()?;
+            println!("Cache at {:?}", cache.cache_dir());
+            let rows = [
+                ("GitHub responses", stats.github_entries, stats.github_size),
+                ("Claude responses", stats.claude_entries, stats.claude_size),
+                ("Issue contexts", stats.context_entries, stats.context_size),
+            ];
+            for (name, entries, size) in rows {
+                println!(
+                    "  {:<18} {:>6} entries  {:>10}",
+                    name,
+                    entries,
+                    format_size(size)
+                );
+            }
+            println!(
+                "  {:<18} {:>6} entries  {:>10}",
+                "Total",
+                stats.total_entries,
+                stats.size_human()
+            );
+            if stats.evicted_entries > 0 {
+                println!(
+                    "  {:<18} {:>6} entries  {:>10}",
+                    "Evicted",
+                    stats.evicted_entries,
+                    format_size(stats.evicted_size)
+                );
+            }
+            println!(
+                "\nEntries expire after {} hours; issue contexts are kept longer.",
+                config.cache.ttl_hours
+            );
+            if config.cache.max_size_mb > 0 {
+                println!(
+                    "The least recently used entries are evicted beyond {} MB.",
+                    config.cache.max_size_mb
+                );
+            }
+        }
+        CacheAction::Clear => {
+            let stats = cache.get_stats()?;
+            cache.clear_all()?;
+            println!(
+                "✓ Cleared {} cache entries ({})",
+                stats.total_entries,
+                stats.size_human()
+            );
+        }
+        CacheAction::Prune => {
+            let before = cache.get_stats()?.total_size;
+            let removed = cache.clear_expired()?;
+            let after = cache.get_stats()?.total_size;
+            println!(
+                "✓ Removed {} expired cache entries ({})",
+                removed,
+                format_size(before.saturating_sub(after))
+            );
+            let eviction = cache.evict_to_max_size()?;
+            if eviction.entries > 0 {
+                println!(
+                    "✓ Evicted {} least recently used entries ({}) to stay under {} MB",
+                    eviction.entries,
+                    format_size(eviction.size),
+                    config.cache.max_size_mb
+                );
+            }
+        }
     }
+
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn summarize_command(
-    target: &str,
+    targets: &[String],
     output_path: Option<&Path>,
     no_recommendations: bool,
+    refresh: bool,
+    stream: bool,
+    open: bool,
+    with_diff: bool,
+    model: Option<&str>,
+    secondary_model: Option<&str>,
+    language: Option<&str>,
     cli: &Cli,
 ) -> Result<()> {
+    // The whole summary goes to stdout, so it isn't streamed as well
+    let to_stdout = output_path == Some(Path::new("-"));
+    if to_stdout {
+        QUIET.store(true, Ordering::Relaxed);
+    }
+
     // Check GitHub CLI first
     match gh_report::github::check_gh_version() {
         Ok(version) => info!("Using gh version {}", version),
         Err(e) => {
             error!("GitHub CLI check failed: {}", e);
-            println!("❌ {}", e);
-            println!("\nPlease install GitHub CLI from: https://cli.github.com/");
+            status!("❌ {}", e);
+            status!("\nPlease install GitHub CLI from: https://cli.github.com/");
             return Err(e);
         }
     }
 
     // Load configuration
-    let config = Config::load(cli.config.as_deref())?;
+    let mut config = Config::load(cli.config.as_deref())?;
+    config.override_models(model, secondary_model);
+    if let Some(language) = language {
+        config.claude.output_language = Some(language.to_string());
+    }
 
     // Create GitHub client
-    let github_client = GitHubClient::new().context("Failed to create GitHub client")?;
+    let github_client = github_client(&config)?;
 
     // Create summarizer
-    let summarizer = IssueSummarizer::new(github_client, &config);
+    let summarizer = IssueSummarizer::new(github_client, &config)
+        .with_refresh(refresh)
+        .with_stream(stream && !to_stdout)
+        .with_diff(with_diff);
 
     // Generate summary
     let include_recommendations = !no_recommendations;
-    match summarizer.summarize(target, output_path, include_recommendations) {
+    let result = match targets {
+        [target] => summarizer.summarize(target, output_path, include_recommendations),
+        _ => summarizer.summarize_many(targets, output_path, include_recommendations),
+    };
+    match result {
         Ok(output_file) => {
-            println!("✓ Summary saved to: {}", output_file);
+            status!("✓ Summary saved to: {}", output_file);
+            remember_summarized(targets, &config, cli);
+            if open && !to_stdout {
+                gh_report::open::open(Path::new(&output_file))?;
+            }
             Ok(())
         }
         Err(e) => {
             error!("Failed to generate summary: {}", e);
-            println!("❌ {}", e);
+            status!("❌ {}", e);
             Err(e)
         }
     }
 }
 
-fn estimate_costs(config: &Config, _state: &State) -> Result<()> {
-    // TODO: Implement actual cost estimation based on data volume
-    println!("Estimating costs based on current configuration...");
-    println!("\nUsing activity-based repository discovery");
-    println!(
-        "Max issues per report: {}",
-        config.settings.max_issues_per_report
-    );
-    println!(
-        "Max comments per report: {}",
-        config.settings.max_comments_per_report
-    );
+fn triage_command(
+    repo: &str,
+    since: &str,
+    limit: usize,
+    output_path: Option<&Path>,
+    apply: bool,
+    cli: &Cli,
+) -> Result<()> {
+    gh_report::github::check_gh_version()?;
+    let config = Config::load(cli.config.as_deref())?;
 
-    println!("\nEstimated Claude API usage:");
-    println!(
-        "  Primary model ({}): ~5000 tokens",
-        config.claude.primary_model
-    );
-    println!(
-        "  Secondary model ({}): ~2000 tokens",
-        config.claude.secondary_model
-    );
-    println!("\nEstimated cost: $0.02-0.04");
+    use gh_report::time::TimeDuration;
+    let duration: TimeDuration = since
+        .parse()
+        .with_context(|| format!("Invalid time format: {}", since))?;
+    let since =
+        Timestamp::now() - jiff::SignedDuration::from_hours(i64::from(duration.as_days()) * 24);
 
+    let github_client = github_client(&config)?;
+    let triager = Triager::new(github_client, &config)
+        .with_apply(apply)
+        .with_limit(limit);
+
+    let output_file = triager.triage(repo, since, output_path)?;
+    println!("✓ Triage worksheet saved to: {}", output_file.display());
     Ok(())
 }
 
-fn list_repos_command(since: &str, output: &Option<PathBuf>, _cli: &Cli) -> Result<()> {
-    // Check GitHub CLI first
-    match gh_report::github::check_gh_version() {
-        Ok(version) => info!("Using gh version {}", version),
-        Err(e) => {
-            error!("GitHub CLI check failed: {}", e);
-            println!("❌ {}", e);
-            println!("\nPlease install GitHub CLI from: https://cli.github.com/");
-            return Err(e);
-        }
+fn projects_command(
+    project_urls: &[String],
+    since: &str,
+    output_path: Option<&Path>,
+    cli: &Cli,
+) -> Result<()> {
+    gh_report::github::check_gh_version()?;
+    let config = Config::load(cli.config.as_deref())?;
+    let project_urls = if project_urls.is_empty() {
+        &config.report.projects
+    } else {
+        project_urls
+    };
+    if project_urls.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No project boards to show. Pass --project-url or set report.projects"
+        ));
     }
 
-    // Parse the time duration using our new utility
     use gh_report::time::TimeDuration;
     let duration: TimeDuration = since
         .parse()
         .with_context(|| format!("Invalid time format: {}", since))?;
-    let lookback_days = duration.as_days();
-
-    // Build output as a string that we can either print or write to file
-    let mut output_lines = Vec::new();
-
-    output_lines.push(format!(
-        "Discovering repositories you have write access to with recent activity (last {})...",
-        duration
-    ));
 
-    // Create GitHub client
-    let github_client = GitHubClient::new().context("Failed to create GitHub client")?;
+    let github_client = github_client(&config)?.with_activity_cache(activity_cache(&config));
+    let mut boards = Vec::new();
+    for url in project_urls {
+        let project = gh_report::github::parse_project_url(url)?;
+        let board = github_client
+            .fetch_project(&project, &config.report.project_status_field)
+            .with_context(|| format!("Failed to fetch project {}", url))?;
+        boards.push(board);
+    }
 
-    // Use activity-based discovery (same as the main report)
-    let all_events = github_client
-        .fetch_activity(lookback_days)
+    let events = github_client
+        .fetch_activity(duration.as_days())
         .context("Failed to fetch activity")?;
+    let references = gh_report::activity::issue_references(&events.iter().collect::<Vec<_>>());
+    let active = references
+        .iter()
+        .map(|(repo, number)| (repo.as_str(), *number))
+        .collect();
 
-    // Apply default activity filtering
-    let events = filter_events(&all_events, None, None);
-
-    if events.is_empty() {
+    let status =
+        gh_report::report::render_project_status(&boards, &active, since, Timestamp::now())?;
+    match output_path {
+        Some(path) => {
+            std::fs::write(path, status)
+                .with_context(|| format!("Failed to write project status to {:?}", path))?;
+            println!("✓ Project status saved to: {}", path.display());
+        }
+        None => print!("{}", status),
+    }
+    Ok(())
+}
+
+/// Keep other instances off the state file until the returned lock is dropped
+fn lock_state(state_file: &Path, cli: &Cli) -> Result<Option<InstanceLock>> {
+    if cli.force {
+        warn!("Not locking {:?} because of --force", state_file);
+        return Ok(None);
+    }
+    InstanceLock::acquire(state_file).map(Some)
+}
+
+/// Record summarized targets in the state, so they are offered for completion
+fn remember_summarized(targets: &[String], config: &Config, cli: &Cli) {
+    let items: Vec<RecentItem> = targets
+        .iter()
+        .filter_map(|target| {
+            gh_report::github::parse_issue_reference_on(target, config.github_host()).ok()
+        })
+        .map(|reference| RecentItem {
+            repo: reference.repo_name(),
+            number: reference.number,
+            title: String::new(),
+            seen_at: Timestamp::now(),
+        })
+        .collect();
+    if items.is_empty() {
+        return;
+    }
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let result = lock_state(&state_file, cli).and_then(|_lock| {
+        let mut state = State::load(&state_file)?;
+        state.record_items(items);
+        state.save(&state_file)
+    });
+    if let Err(e) = result {
+        warn!("Failed to remember summarized target: {}", e);
+    }
+}
+
+/// Print how the last report scored an issue or PR
+fn explain_command(target: &str, cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let reference = gh_report::github::parse_issue_reference_on(target, config.github_host())?;
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let state = State::load(&state_file).context("Failed to load state")?;
+
+    let Some(scores) = &state.last_scores else {
+        println!("No scores recorded yet; run `gh-report report` first.");
+        return Ok(());
+    };
+    match scores.find(&reference.repo_name(), reference.number) {
+        Some(item) => print!("{}", render_explanation(scores, item)),
+        None => {
+            println!(
+                "{} was not among the {} items scored in the report of {}.",
+                reference.display(),
+                scores.items.len(),
+                scores.generated_at.strftime("%Y-%m-%d %H:%M")
+            );
+            if !config.report.includes_repo(&reference.repo_name()) {
+                println!("Its repository is filtered out by report.include_repos or report.exclude_repos.");
+            } else {
+                println!(
+                    "Only new and updated issues and PRs are scored; merged and closed \
+                     ones are reported without a score."
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Mute an issue or PR, or list the muted ones
+fn mute_command(target: Option<&str>, list: bool, cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let Some(target) = target.filter(|_| !list) else {
+        let state = State::load(&state_file).context("Failed to load state")?;
+        if state.muted.is_empty() {
+            println!("No issues or PRs are muted.");
+        }
+        for item in &state.muted {
+            let title = state
+                .recent_items
+                .iter()
+                .find(|recent| recent.repo == item.repo && recent.number == item.number)
+                .map(|recent| format!(" {}", recent.title))
+                .unwrap_or_default();
+            println!(
+                "{}{} (muted {})",
+                item.reference(),
+                title,
+                config.report.locale().format_date(item.muted_at)
+            );
+        }
+        return Ok(());
+    };
+
+    let reference = gh_report::github::parse_issue_reference_on(target, config.github_host())?;
+    let _lock = lock_state(&state_file, cli)?;
+    let mut state = State::load(&state_file).context("Failed to load state")?;
+    if state.mute(&reference.repo_name(), reference.number, Timestamp::now()) {
+        state.save(&state_file).context("Failed to save state")?;
+        println!(
+            "Muted {}; reports leave it out until `gh-report unmute {}`.",
+            reference.display(),
+            reference.display()
+        );
+    } else {
+        println!("{} is already muted.", reference.display());
+    }
+    Ok(())
+}
+
+/// Bring a muted issue or PR back into reports
+fn unmute_command(target: &str, cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let reference = gh_report::github::parse_issue_reference_on(target, config.github_host())?;
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let _lock = lock_state(&state_file, cli)?;
+    let mut state = State::load(&state_file).context("Failed to load state")?;
+    if state.unmute(&reference.repo_name(), reference.number) {
+        state.save(&state_file).context("Failed to save state")?;
+        println!("Unmuted {}.", reference.display());
+    } else {
+        println!("{} was not muted.", reference.display());
+    }
+    Ok(())
+}
+
+/// Print a completion script, or the candidates for a `summarize` target
+fn completions_command(
+    shell: Option<Shell>,
+    complete_target: Option<&str>,
+    cli: &Cli,
+) -> Result<()> {
+    let Some(prefix) = complete_target else {
+        if let Some(shell) = shell {
+            completions::write_script(shell, &mut std::io::stdout())?;
+        }
+        return Ok(());
+    };
+
+    // Runs on every tab press: stay quiet, and offer nothing rather than fail
+    let config = Config::load(cli.config.as_deref()).ok();
+    let state_file = cli
+        .state
+        .clone()
+        .or_else(|| config.as_ref().map(|c| c.settings.state_file.clone()));
+    let state = state_file
+        .and_then(|path| State::load(&path).ok())
+        .unwrap_or_default();
+    let configured_repos = config
+        .iter()
+        .flat_map(|c| c.repos.keys().map(String::as_str));
+
+    for (value, description) in completions::target_candidates(&state, configured_repos, prefix) {
+        if description.is_empty() {
+            println!("{}", value);
+        } else {
+            println!("{}\t{}", value, description);
+        }
+    }
+    Ok(())
+}
+
+fn estimate_costs(config: &Config, _state: &State) -> Result<()> {
+    // TODO: Implement actual cost estimation based on data volume
+    println!("Estimating costs based on current configuration...");
+    println!("\nUsing activity-based repository discovery");
+    println!(
+        "Max issues per report: {}",
+        config.settings.max_issues_per_report
+    );
+    println!(
+        "Max comments per report: {}",
+        config.settings.max_comments_per_report
+    );
+
+    let primary_model = config.claude.resolve_model(&config.claude.primary_model);
+    let secondary_model = config.claude.resolve_model(&config.claude.secondary_model);
+    println!("\nEstimated Claude API usage:");
+    println!("  Primary model ({}): ~5000 tokens", primary_model);
+    println!("  Secondary model ({}): ~2000 tokens", secondary_model);
+
+    // Assume roughly a fifth of the tokens are output
+    let pricing = config.claude.pricing();
+    let cost = pricing.estimate_cost(&primary_model, 4000, 1000)
+        + pricing.estimate_cost(&secondary_model, 1600, 400);
+    println!("\nEstimated cost: ~${:.3}", cost);
+
+    Ok(())
+}
+
+/// The GitHub Enterprise Server host from the config, if there is one
+fn list_repos_command(
+    since: &str,
+    until: Option<&str>,
+    output: &Option<PathBuf>,
+    cli: &Cli,
+) -> Result<()> {
+    // Check GitHub CLI first
+    match gh_report::github::check_gh_version() {
+        Ok(version) => info!("Using gh version {}", version),
+        Err(e) => {
+            error!("GitHub CLI check failed: {}", e);
+            println!("❌ {}", e);
+            println!("\nPlease install GitHub CLI from: https://cli.github.com/");
+            return Err(e);
+        }
+    }
+
+    let range = TimeRange::parse(since, until)
+        .with_context(|| format!("Invalid time format: {}", since))?;
+    let lookback_days = range.lookback_days(Timestamp::now());
+
+    // Build output as a string that we can either print or write to file
+    let mut output_lines = Vec::new();
+
+    output_lines.push(format!(
+        "Discovering repositories you have write access to with recent activity ({})...",
+        range
+    ));
+
+    // Create GitHub client
+    let config = Config::load(cli.config.as_deref()).ok();
+    let github_client = github_client(config.as_ref().unwrap_or(&Config::default()))?
+        .with_activity_cache(config.as_ref().and_then(activity_cache));
+    let report_config = config.map(|config| config.report).unwrap_or_default();
+
+    // Use activity-based discovery (same as the main report)
+    let feed = github_client
+        .fetch_activity_feed(lookback_days)
+        .context("Failed to fetch activity")?;
+    if let Some(truncated_at) = feed.truncated_at {
         output_lines.push(format!(
-            "\nNo repositories found with recent activity in the last {}.",
-            duration
+            "⚠️  GitHub only returned events back to {}, older activity is missing.",
+            truncated_at.strftime("%Y-%m-%d %H:%M UTC")
+        ));
+    }
+    let mut all_events = feed.events;
+    all_events.retain(|event| range.contains(event.created_at));
+
+    // Apply default activity filtering, leaving out bots and ignored authors
+    let (events, ignored) = gh_report::activity::drop_ignored_authors(
+        gh_report::activity::filter_events(&all_events, None, None),
+        |author| report_config.ignores_author(author),
+    );
+    if let Some(line) = bot_activity_line(&ignored) {
+        output_lines.push(line);
+    }
+
+    if events.is_empty() {
+        output_lines.push(format!(
+            "\nNo repositories found with activity for {}.",
+            range
         ));
         let final_output = output_lines.join("\n");
 
@@ -496,7 +1527,7 @@ fn list_repos_command(since: &str, output: &Option<PathBuf>, _cli: &Cli) -> Resu
             ("(no org)".to_string(), repo_name.clone())
         };
 
-        grouped_repos.entry(org).or_insert_with(Vec::new).push(repo);
+        grouped_repos.entry(org).or_default().push(repo);
     }
 
     // Sort repositories within each org alphabetically
@@ -524,7 +1555,7 @@ fn list_repos_command(since: &str, output: &Option<PathBuf>, _cli: &Cli) -> Resu
         .push("\nThese repositories have recent activity and will be automatically".to_string());
     output_lines.push("included in reports based on your GitHub activity feed.".to_string());
     output_lines.push("\nSelection criteria:".to_string());
-    output_lines.push(format!("   - Recent activity in the last {}", duration));
+    output_lines.push(format!("   - Activity for {}", range));
     output_lines.push("   - Activity types: issues, PRs, comments, reviews".to_string());
 
     let final_output = output_lines.join("\n");
@@ -540,55 +1571,14 @@ fn list_repos_command(since: &str, output: &Option<PathBuf>, _cli: &Cli) -> Resu
     Ok(())
 }
 
-fn filter_events<'a>(
-    events: &'a [gh_report::github::ActivityEvent],
-    include_types: Option<&Vec<String>>,
-    exclude_types: Option<&Vec<String>>,
-) -> Vec<&'a gh_report::github::ActivityEvent> {
-    let default_included_types = vec![
-        "IssueCommentEvent".to_string(),
-        "PullRequestEvent".to_string(),
-        "IssuesEvent".to_string(),
-        "PullRequestReviewCommentEvent".to_string(),
-        "PullRequestReviewEvent".to_string(),
-    ];
-
-    events
-        .iter()
-        .filter(|event| {
-            // First check include types (default to user's preferred list if not specified)
-            let included_types = include_types.unwrap_or(&default_included_types);
-            if !included_types.contains(&event.event_type) {
-                return false;
-            }
-
-            // Check exclude types
-            if let Some(excluded) = exclude_types {
-                if excluded.contains(&event.event_type) {
-                    return false;
-                }
-            }
-
-            // Special filtering for IssuesEvent - exclude 'labeled' actions
-            if event.event_type == "IssuesEvent" {
-                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                    if action == "labeled" || action == "unlabeled" {
-                        return false;
-                    }
-                }
-            }
-
-            true
-        })
-        .collect()
-}
-
 fn activity_command(
-    since: &str,
+    range: &TimeRange,
     include_types: Option<&Vec<String>>,
     exclude_types: Option<&Vec<String>>,
+    format: ActivityFormat,
     output: &Option<PathBuf>,
-    _cli: &Cli,
+    save_snapshot: Option<&Path>,
+    cli: &Cli,
 ) -> Result<()> {
     // Check GitHub CLI first
     match gh_report::github::check_gh_version() {
@@ -601,371 +1591,229 @@ fn activity_command(
         }
     }
 
-    // Parse the time duration using our new utility
-    use gh_report::time::TimeDuration;
-    let duration: TimeDuration = since
-        .parse()
-        .with_context(|| format!("Invalid time format: {}", since))?;
-    let days = duration.as_days();
-
-    // Build output as a string that we can either print or write to file
-    let mut output_lines = Vec::new();
-
-    output_lines.push(format!(
-        "Fetching activity on repositories you're subscribed to for the last {} ({})...",
-        duration, since
-    ));
+    let days = range.lookback_days(Timestamp::now());
 
     // Create GitHub client
-    let github_client = GitHubClient::new().context("Failed to create GitHub client")?;
+    let config = Config::load(cli.config.as_deref()).ok();
+    let github_client = github_client(config.as_ref().unwrap_or(&Config::default()))?
+        .with_activity_cache(config.as_ref().and_then(activity_cache));
+    let report_config = config.map(|config| config.report).unwrap_or_default();
 
     // Fetch activity events
-    let all_events = github_client
+    let mut all_events = github_client
         .fetch_activity(days)
         .context("Failed to fetch activity")?;
+    all_events.retain(|event| range.contains(event.created_at));
 
     // Apply event type filtering
-    let events = filter_events(&all_events, include_types, exclude_types);
+    let events = gh_report::activity::filter_events(
+        &all_events,
+        include_types.map(Vec::as_slice),
+        exclude_types.map(Vec::as_slice),
+    );
 
-    if events.is_empty() {
-        output_lines.push(format!(
-            "\nNo matching activity found in the last {}.",
-            duration
-        ));
-        if all_events.len() > 0 {
-            output_lines.push(format!("({} events were filtered out)", all_events.len()));
-        }
+    let (events, ignored_authors) = gh_report::activity::drop_ignored_authors(events, |author| {
+        report_config.ignores_author(author)
+    });
 
-        let final_output = output_lines.join("\n");
+    // Group events by date → repo → issue/PR
+    let mut summary = gh_report::activity::summarize_activity(&events, all_events.len());
+    summary.ignored_authors = ignored_authors;
+
+    if let Some(path) = save_snapshot {
+        // Status goes to stderr so JSON output stays parseable
+        eprintln!("📼 Fetching the issues and PRs for the snapshot...");
+        let snapshot = ActivitySnapshot::capture(&github_client, days, all_events)
+            .context("Failed to capture activity snapshot")?;
+        snapshot.save(path)?;
+        eprintln!(
+            "✓ Snapshot of {} events and {} issues/PRs saved to: {:?}",
+            snapshot.events.len(),
+            snapshot.items.len(),
+            path
+        );
+    }
 
-        if let Some(output_path) = output {
-            std::fs::write(output_path, final_output)
-                .with_context(|| format!("Failed to write output to {:?}", output_path))?;
-            println!("Output saved to: {:?}", output_path);
-        } else {
-            println!("{}", final_output);
+    let final_output = match format {
+        ActivityFormat::Json => serde_json::to_string_pretty(&summary)
+            .context("Failed to serialize activity to JSON")?,
+        ActivityFormat::Text => {
+            let _span = info_span!("render").entered();
+            render_activity_text(&summary, range)
         }
-        return Ok(());
-    }
+    };
 
-    // Group events by date → repo → issue/PR
-    use std::collections::BTreeMap;
+    if let Some(output_path) = output {
+        std::fs::write(output_path, final_output)
+            .with_context(|| format!("Failed to write output to {:?}", output_path))?;
+        println!("Output saved to: {:?}", output_path);
+    } else {
+        println!("{}", final_output);
+    }
 
-    let mut events_by_date: BTreeMap<
-        String,
-        BTreeMap<String, BTreeMap<Option<IssueKey>, Vec<&gh_report::github::ActivityEvent>>>,
-    > = BTreeMap::new();
+    Ok(())
+}
 
-    for event in &events {
-        let date_key = event.created_at.strftime("%Y-%m-%d").to_string();
-        let repo_name = event.repo.name.clone();
+fn ingest_command(stdin: bool, files: &[PathBuf], cli: &Cli) -> Result<()> {
+    use std::io::BufRead;
 
-        // Extract issue/PR number if available
-        let issue_key = extract_issue_key(event);
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let store = EventStore::new(config.settings.event_store.clone());
 
-        events_by_date
-            .entry(date_key)
-            .or_insert_with(BTreeMap::new)
-            .entry(repo_name)
-            .or_insert_with(BTreeMap::new)
-            .entry(issue_key)
-            .or_insert_with(Vec::new)
-            .push(event);
+    let mut sources: Vec<(String, Box<dyn BufRead>)> = Vec::new();
+    if stdin {
+        sources.push(("stdin".to_string(), Box::new(std::io::stdin().lock())));
+    }
+    for path in files {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        sources.push((
+            path.display().to_string(),
+            Box::new(std::io::BufReader::new(file)),
+        ));
     }
 
-    output_lines.push(format!("\nActivity Summary ({} events):", events.len()));
-    output_lines.push(format!("{}", "=".repeat(60)));
-
-    // Display events grouped by date → repo → issue/PR
-    for (date, repos_events) in events_by_date.iter().rev() {
-        let total_events: usize = repos_events
-            .values()
-            .map(|repo_issues| {
-                repo_issues
-                    .values()
-                    .map(|events| events.len())
-                    .sum::<usize>()
-            })
-            .sum();
-        output_lines.push(format!("\n**{}** ({} events)", date, total_events));
-
-        for (repo_name, issues_events) in repos_events {
-            let _repo_event_count: usize = issues_events.values().map(|events| events.len()).sum();
-            output_lines.push(format!("  {}", repo_name));
-
-            for (issue_key, issue_events) in issues_events {
-                match issue_key {
-                    Some(key) => {
-                        let item_type = if key.is_pr { "PR" } else { "Issue" };
-                        
-                        // Extract title from the first event that has one
-                        let title = issue_events
-                            .iter()
-                            .find_map(|event| extract_title_from_event(event))
-                            .unwrap_or_else(|| "[No title]".to_string());
-                        let truncated_title = truncate_title(&title, 60);
-                        
-                        // Show issue/PR with title
-                        output_lines.push(format!(
-                            "    {} #{} - {}",
-                            item_type,
-                            key.issue_number,
-                            truncated_title
-                        ));
-                        
-                        // Group events by action and show them indented
-                        let action_groups = group_events_by_action(issue_events);
-                        for (action, actors) in action_groups {
-                            output_lines.push(format!(
-                                "      - {} ({})",
-                                action,
-                                actors.join(", ")
-                            ));
-                        }
-                    }
-                    None => {
-                        // Events without specific issue/PR (e.g., general repo activity)
-                        for event in issue_events {
-                            let event_desc = format_activity_event(event);
-                            output_lines.push(format!("    {}", event_desc));
-                        }
-                    }
+    // Events are stored as they arrive, so a receiver can keep the pipe open
+    let (mut stored, mut skipped, mut invalid) = (0, 0, 0);
+    for (name, reader) in sources {
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.with_context(|| format!("Failed to read {}", name))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match gh_report::events::parse_delivery(&line, Timestamp::now()) {
+                Ok(Some(event)) => {
+                    store.append(&[event])?;
+                    stored += 1;
+                }
+                Ok(None) => skipped += 1,
+                Err(e) => {
+                    warn!("Skipping line {} of {}: {:#}", i + 1, name, e);
+                    invalid += 1;
                 }
             }
         }
     }
 
-    output_lines.push(format!("\n{}", "=".repeat(60)));
-    output_lines.push("\nEvent types found:".to_string());
+    let cutoff = Timestamp::now() - (config.settings.max_lookback_days as i64 * 24).hours();
+    let pruned = store.prune(cutoff)?;
 
-    // Count event types
-    let mut event_type_counts: std::collections::HashMap<String, usize> =
-        std::collections::HashMap::new();
-    for event in &events {
-        *event_type_counts
-            .entry(event.event_type.clone())
-            .or_insert(0) += 1;
+    println!("✓ Stored {} events in {:?}", stored, store.path());
+    if skipped > 0 {
+        println!("  {} events reports don't use were skipped", skipped);
     }
-
-    let mut sorted_types: Vec<_> = event_type_counts.iter().collect();
-    sorted_types.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (event_type, count) in sorted_types {
-        output_lines.push(format!("   - {}: {}", event_type, count));
+    if pruned > 0 {
+        println!(
+            "  {} events older than {} days were dropped",
+            pruned, config.settings.max_lookback_days
+        );
     }
-
-    let final_output = output_lines.join("\n");
-
-    if let Some(output_path) = output {
-        std::fs::write(output_path, final_output)
-            .with_context(|| format!("Failed to write output to {:?}", output_path))?;
-        println!("Output saved to: {:?}", output_path);
-    } else {
-        println!("{}", final_output);
+    if invalid > 0 {
+        if stored == 0 {
+            anyhow::bail!("None of the {} lines were webhook events", invalid);
+        }
+        println!(
+            "⚠️  {} lines were not webhook events (see the log)",
+            invalid
+        );
     }
 
     Ok(())
 }
 
-fn extract_issue_key(event: &gh_report::github::ActivityEvent) -> Option<IssueKey> {
-    match event.event_type.as_str() {
-        "PullRequestEvent" => {
-            if let Some(pr_number) = event
-                .payload
-                .get("pull_request")
-                .and_then(|pr| pr.get("number"))
-                .and_then(|n| n.as_u64())
-            {
-                Some(IssueKey {
-                    issue_number: pr_number,
-                    is_pr: true,
-                })
-            } else {
-                None
-            }
-        }
-        "IssuesEvent" | "IssueCommentEvent" => {
-            if let Some(issue_number) = event
-                .payload
-                .get("issue")
-                .and_then(|issue| issue.get("number"))
-                .and_then(|n| n.as_u64())
-            {
-                // Check if this is actually a PR (issues API includes PRs)
-                let is_pr = event
-                    .payload
-                    .get("issue")
-                    .and_then(|issue| issue.get("pull_request"))
-                    .is_some();
-
-                Some(IssueKey {
-                    issue_number,
-                    is_pr,
-                })
-            } else {
-                None
-            }
-        }
-        "PullRequestReviewCommentEvent" => {
-            if let Some(pr_number) = event
-                .payload
-                .get("pull_request")
-                .and_then(|pr| pr.get("number"))
-                .and_then(|n| n.as_u64())
-            {
-                Some(IssueKey {
-                    issue_number: pr_number,
-                    is_pr: true,
-                })
-            } else {
-                None
-            }
+/// Human-readable version of the grouped activity
+fn render_activity_text(
+    summary: &gh_report::activity::ActivitySummary,
+    range: &TimeRange,
+) -> String {
+    let mut output_lines = Vec::new();
+
+    output_lines.push(format!(
+        "Fetching activity on repositories you're subscribed to for {}...",
+        range
+    ));
+
+    if summary.days.is_empty() {
+        output_lines.push(format!("\nNo matching activity found for {}.", range));
+        if summary.filtered_out > 0 {
+            output_lines.push(format!(
+                "({} events were filtered out)",
+                summary.filtered_out
+            ));
         }
-        _ => None,
+        output_lines.extend(bot_activity_line(&summary.ignored_authors));
+        return output_lines.join("\n");
     }
-}
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct IssueKey {
-    issue_number: u64,
-    is_pr: bool,
-}
+    output_lines.push(format!(
+        "\nActivity Summary ({} events):",
+        summary.total_events
+    ));
+    output_lines.push("=".repeat(60));
+    output_lines.extend(bot_activity_line(&summary.ignored_authors));
 
-fn format_activity_event(event: &gh_report::github::ActivityEvent) -> String {
-    let actor = &event.actor.login;
+    // Display events grouped by date → repo → issue/PR
+    for day in &summary.days {
+        output_lines.push(format!("\n**{}** ({} events)", day.date, day.event_count));
 
-    match event.event_type.as_str() {
-        "PushEvent" => {
-            if let Some(commits) = event.payload.get("commits").and_then(|c| c.as_array()) {
-                format!("@{} pushed {} commit(s)", actor, commits.len())
-            } else {
-                format!("@{} pushed commits", actor)
-            }
-        }
-        "PullRequestEvent" => {
-            if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                if let Some(pr_number) = event
-                    .payload
-                    .get("pull_request")
-                    .and_then(|pr| pr.get("number"))
-                    .and_then(|n| n.as_u64())
-                {
-                    format!("@{} {} PR #{}", actor, action, pr_number)
-                } else {
-                    format!("@{} {} pull request", actor, action)
-                }
-            } else {
-                format!("@{} pull request activity", actor)
+        for repo in &day.repos {
+            output_lines.push(format!("  {}", repo.name));
+
+            // Events without specific issue/PR (e.g., general repo activity)
+            for event in &repo.other_events {
+                output_lines.push(format!("    {}", event.description));
             }
-        }
-        "IssuesEvent" => {
-            if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                if let Some(issue_number) = event
-                    .payload
-                    .get("issue")
-                    .and_then(|issue| issue.get("number"))
-                    .and_then(|n| n.as_u64())
-                {
-                    format!("@{} {} issue #{}", actor, action, issue_number)
-                } else {
-                    format!("@{} {} issue", actor, action)
+
+            for item in &repo.items {
+                let item_type = if item.is_pull_request { "PR" } else { "Issue" };
+                let title = item.title.as_deref().unwrap_or("[No title]");
+
+                // Show issue/PR with title
+                output_lines.push(format!(
+                    "    {} #{} - {}",
+                    item_type,
+                    item.number,
+                    truncate_title(title, 60)
+                ));
+
+                // Show what happened, grouped by action
+                for action in &item.actions {
+                    let actors: Vec<String> =
+                        action.actors.iter().map(|a| format!("@{}", a)).collect();
+                    output_lines.push(format!("      - {} ({})", action.action, actors.join(", ")));
                 }
-            } else {
-                format!("@{} issue activity", actor)
-            }
-        }
-        "IssueCommentEvent" => {
-            if let Some(issue_number) = event
-                .payload
-                .get("issue")
-                .and_then(|issue| issue.get("number"))
-                .and_then(|n| n.as_u64())
-            {
-                format!("@{} commented on issue #{}", actor, issue_number)
-            } else {
-                format!("@{} commented on issue", actor)
-            }
-        }
-        "PullRequestReviewEvent" => {
-            if let Some(pr_number) = event
-                .payload
-                .get("pull_request")
-                .and_then(|pr| pr.get("number"))
-                .and_then(|n| n.as_u64())
-            {
-                format!("@{} reviewed PR #{}", actor, pr_number)
-            } else {
-                format!("@{} reviewed pull request", actor)
-            }
-        }
-        "PullRequestReviewCommentEvent" => {
-            if let Some(pr_number) = event
-                .payload
-                .get("pull_request")
-                .and_then(|pr| pr.get("number"))
-                .and_then(|n| n.as_u64())
-            {
-                format!("@{} commented on PR #{}", actor, pr_number)
-            } else {
-                format!("@{} commented on pull request", actor)
             }
         }
-        "CreateEvent" => {
-            if let Some(ref_type) = event.payload.get("ref_type").and_then(|r| r.as_str()) {
-                format!("@{} created {}", actor, ref_type)
-            } else {
-                format!("@{} created resource", actor)
-            }
-        }
-        "DeleteEvent" => {
-            if let Some(ref_type) = event.payload.get("ref_type").and_then(|r| r.as_str()) {
-                format!("@{} deleted {}", actor, ref_type)
-            } else {
-                format!("@{} deleted resource", actor)
-            }
-        }
-        "ForkEvent" => format!("@{} forked repository", actor),
-        "WatchEvent" => format!("@{} starred repository", actor),
-        "ReleaseEvent" => {
-            if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                format!("@{} {} release", actor, action)
-            } else {
-                format!("@{} release activity", actor)
-            }
-        }
-        _ => format!("@{} {} event", actor, event.event_type),
     }
+
+    output_lines.push(format!("\n{}", "=".repeat(60)));
+    output_lines.push("\nEvent types found:".to_string());
+
+    for event_type in &summary.event_types {
+        output_lines.push(format!(
+            "   - {}: {}",
+            event_type.event_type, event_type.count
+        ));
+    }
+
+    output_lines.join("\n")
 }
 
-/// Extract title from an event payload for issues or PRs
-fn extract_title_from_event(event: &gh_report::github::ActivityEvent) -> Option<String> {
-    match event.event_type.as_str() {
-        "PullRequestEvent" => {
-            event
-                .payload
-                .get("pull_request")
-                .and_then(|pr| pr.get("title"))
-                .and_then(|t| t.as_str())
-                .map(|s| s.to_string())
-        }
-        "IssuesEvent" | "IssueCommentEvent" => {
-            event
-                .payload
-                .get("issue")
-                .and_then(|issue| issue.get("title"))
-                .and_then(|t| t.as_str())
-                .map(|s| s.to_string())
-        }
-        "PullRequestReviewCommentEvent" | "PullRequestReviewEvent" => {
-            event
-                .payload
-                .get("pull_request")
-                .and_then(|pr| pr.get("title"))
-                .and_then(|t| t.as_str())
-                .map(|s| s.to_string())
-        }
-        _ => None,
+/// A single collapsed line for the events of bots and other ignored authors
+fn bot_activity_line(ignored: &std::collections::BTreeMap<String, usize>) -> Option<String> {
+    if ignored.is_empty() {
+        return None;
     }
+    let total: usize = ignored.values().sum();
+    let authors: Vec<String> = ignored
+        .iter()
+        .map(|(login, count)| format!("{}: {}", login, count))
+        .collect();
+    Some(format!(
+        "🤖 Bot activity: {} events left out ({})",
+        total,
+        authors.join(", ")
+    ))
 }
 
 /// Truncate a title to a reasonable length
@@ -980,88 +1828,317 @@ fn truncate_title(title: &str, max_length: usize) -> String {
     }
 }
 
-/// Group events by action and collect actors for each action
-fn group_events_by_action(events: &[&gh_report::github::ActivityEvent]) -> Vec<(String, Vec<String>)> {
-    use std::collections::HashMap;
-    let mut action_actors: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
-    
-    for event in events {
-        let action_text = match event.event_type.as_str() {
-            "PullRequestEvent" => {
-                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                    match action {
-                        "opened" => "opened".to_string(),
-                        "closed" => "closed".to_string(),
-                        "reopened" => "reopened".to_string(),
-                        "ready_for_review" => "ready for review".to_string(),
-                        "converted_to_draft" => "converted to draft".to_string(),
-                        _ => action.to_string(),
-                    }
-                } else {
-                    "updated".to_string()
-                }
+fn watch_command(every: &str, cli: &Cli) -> Result<()> {
+    let interval = gh_report::time::parse_interval(every)?;
+    // Used for the first report, when there is no previous one to start from
+    let first_window = format!("{}h", interval.as_secs().div_ceil(3600));
+    let options = ReportOptions {
+        since: &first_window,
+        until: None,
+        since_last: true,
+        output: &[],
+        append: false,
+        dry_run: false,
+        estimate_cost: false,
+        // Fetch the feed that new activity was just seen in, not a cached one
+        no_cache: true,
+        clear_cache: false,
+        with_git: None,
+        ci_logs: false,
+        model: None,
+        secondary_model: None,
+        language: None,
+        format: None,
+        flavor: ReportFlavor::Daily,
+        from_snapshot: None,
+        from_events: false,
+        offline: false,
+        org: None,
+        users: &[],
+        repos: &[],
+        email: &[],
+        open: false,
+        full: false,
+        max_items: None,
+        resume: false,
+    };
+
+    println!(
+        "👀 Watching for new activity every {} (Ctrl-C to stop)",
+        every
+    );
+    loop {
+        if let Err(e) = watch_tick(&options, cli) {
+            error!("Watch run failed: {:#}", e);
+            println!("❌ {:#}", e);
+        }
+
+        let next = Timestamp::now() + jiff::SignedDuration::try_from(interval)?;
+        println!("💤 Next check at {}", next.strftime("%Y-%m-%d %H:%M"));
+        std::thread::sleep(interval);
+    }
+}
+
+/// Generate a report if there is activity since the last one
+fn watch_tick(options: &ReportOptions, cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let state = State::load(&state_file).context("Failed to load state")?;
+
+    let github_client = github_client(&config)?;
+    let new_events = gh_report::watch::check_for_new_activity(
+        &github_client,
+        &config.report,
+        &state,
+        config.settings.max_lookback_days,
+    )?;
+
+    if new_events == 0 {
+        println!(
+            "✓ {} No new activity, skipping report",
+            Timestamp::now().strftime("%Y-%m-%d %H:%M")
+        );
+        return Ok(());
+    }
+
+    println!(
+        "🔔 {} {} new events, generating report",
+        Timestamp::now().strftime("%Y-%m-%d %H:%M"),
+        new_events
+    );
+    let result = report_command(options, cli);
+    record_costs(cli);
+    result
+}
+
+fn models_command(cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+
+    println!("Aliases (built-in, overridden by [claude.aliases]):");
+    for (alias, model) in config.claude.model_aliases() {
+        println!("  {:<12} → {}", alias, model);
+    }
+
+    println!(
+        "\nConfigured: primary {}, secondary {}",
+        config.claude.resolve_model(&config.claude.primary_model),
+        config.claude.resolve_model(&config.claude.secondary_model)
+    );
+
+    println!("\nBackends:");
+    for probe in probe_backends(&config) {
+        let mark = if probe.available { "✓" } else { "✗" };
+        println!("  {} {:<12} {}", mark, probe.name, probe.detail);
+    }
+    if config.llm_provider() == LlmProvider::Auto {
+        println!("  The Claude CLI is used first; the API takes over if it fails during a run");
+    }
+
+    let client = match ClaudeClient::new() {
+        Ok(client) => client,
+        Err(e) => {
+            println!("\nSet ANTHROPIC_API_KEY to list available models ({})", e);
+            return Ok(());
+        }
+    };
+    let models = client
+        .list_models()
+        .context("Failed to list models from the Claude API")?;
+
+    println!(
+        "\nAvailable models (price per 1M tokens, input / output; table {}):",
+        PRICING_TABLE_VERSION
+    );
+    let pricing = config.claude.pricing();
+    for model in models {
+        let price = match pricing.lookup(&model.id) {
+            Some(price) => format!("${:.2} / ${:.2}", price.input, price.output),
+            None => "unknown, add it under [claude.pricing]".to_string(),
+        };
+        println!("  {:<32} {:<24} {}", model.id, model.display_name, price);
+    }
+
+    Ok(())
+}
+
+fn profile_command(action: &ProfileAction, cli: &Cli) -> Result<()> {
+    match action {
+        ProfileAction::List => {
+            let profiles_dir = Config::profiles_dir()?;
+            let names = Config::profiles_in(&profiles_dir)?;
+            if names.is_empty() {
+                println!("No profiles yet. Create one with `gh-report profile create <name>`.");
+                return Ok(());
             }
-            "IssuesEvent" => {
-                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                    match action {
-                        "opened" => "opened".to_string(),
-                        "closed" => "closed".to_string(),
-                        "reopened" => "reopened".to_string(),
-                        _ => action.to_string(),
-                    }
+            for name in names {
+                let marker = if cli.profile.as_deref() == Some(name.as_str()) {
+                    "*"
                 } else {
-                    "updated".to_string()
-                }
+                    " "
+                };
+                println!("{} {}", marker, name);
+            }
+            println!("\nProfiles are stored in {:?}", profiles_dir);
+        }
+        ProfileAction::Create { name } => {
+            let path = Config::profile_path(name)?;
+            if path.exists() {
+                anyhow::bail!("Profile {:?} already exists at {:?}", name, path);
+            }
+            let config = Config::for_profile(name);
+            let config_str =
+                toml::to_string_pretty(&config).context("Failed to serialize config")?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create config directory {:?}", parent))?;
             }
-            "IssueCommentEvent" => "commented".to_string(),
-            "PullRequestReviewEvent" => {
-                if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                    match action {
-                        "submitted" => "reviewed".to_string(),
-                        _ => "review activity".to_string(),
-                    }
+            std::fs::write(&path, config_str)
+                .with_context(|| format!("Failed to write config to {:?}", path))?;
+
+            println!("✓ Profile {:?} created at: {:?}", name, path);
+            println!("  Reports and state go to {:?}", config.settings.report_dir);
+            println!(
+                "  Set include_repos under [report] to focus it, then run `gh-report --profile {} report`",
+                name
+            );
+        }
+        ProfileAction::Show { name } => {
+            let (label, path) = match name.as_deref().or(cli.profile.as_deref()) {
+                Some(name) => (format!("Profile {:?}", name), Config::profile_path(name)?),
+                None => (
+                    "Default configuration".to_string(),
+                    match &cli.config {
+                        Some(path) => path.clone(),
+                        None => Config::default_config_path()?,
+                    },
+                ),
+            };
+            let config = Config::load(Some(&path))?;
+            let filter = |patterns: &[String]| {
+                if patterns.is_empty() {
+                    "-".to_string()
                 } else {
-                    "reviewed".to_string()
+                    patterns.join(", ")
                 }
-            }
-            "PullRequestReviewCommentEvent" => "review commented".to_string(),
-            _ => event.event_type.clone(),
-        };
-        
-        let actor = format!("@{}", event.actor.login);
-        action_actors.entry(action_text).or_insert_with(std::collections::HashSet::new).insert(actor);
-    }
-    
-    let mut result: Vec<(String, Vec<String>)> = action_actors
-        .into_iter()
-        .map(|(action, actors)| {
-            let mut actor_list: Vec<String> = actors.into_iter().collect();
-            actor_list.sort();
-            (action, actor_list)
-        })
-        .collect();
-    
-    // Sort actions by a reasonable order
-    result.sort_by(|a, b| {
-        let order_a = action_priority(&a.0);
-        let order_b = action_priority(&b.0);
-        order_a.cmp(&order_b).then_with(|| a.0.cmp(&b.0))
+            };
+
+            println!("{}", label);
+            println!("  Config:        {:?}", path);
+            println!("  State file:    {:?}", config.settings.state_file);
+            println!("  Report dir:    {:?}", config.settings.report_dir);
+            println!("  Repositories:  {}", filter(&config.report.include_repos));
+            println!("  Excluded:      {}", filter(&config.report.exclude_repos));
+            println!(
+                "  Model:         {}",
+                config.claude.resolve_model(&config.claude.primary_model)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn costs_command(cli: &Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let state_file = cli
+        .state
+        .clone()
+        .unwrap_or_else(|| config.settings.state_file.clone());
+    let ledger = CostLedger::beside(&state_file);
+    let entries = ledger.load()?;
+
+    print!(
+        "{}",
+        render_costs(
+            &entries,
+            Timestamp::now(),
+            &jiff::tz::TimeZone::system(),
+            config.claude.monthly_budget,
+        )
+    );
+    if entries.is_empty() {
+        println!(
+            "Costs of report and summarize runs are recorded in {:?}.",
+            ledger.path()
+        );
+    }
+    Ok(())
+}
+
+fn doctor_command(cli: &Cli) -> Result<()> {
+    let checks = run_checks(&DoctorOptions {
+        config: cli.config.clone(),
+        state: cli.state.clone(),
     });
-    
-    result
+    print!("{}", render_checks(&checks));
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        anyhow::bail!("{} of {} checks failed", failed, checks.len());
+    }
+    Ok(())
 }
 
-/// Get priority order for actions (lower number = higher priority)
-fn action_priority(action: &str) -> u8 {
-    match action {
-        "opened" => 1,
-        "closed" => 2,
-        "reopened" => 3,
-        "reviewed" => 4,
-        "commented" => 5,
-        "review commented" => 6,
-        _ => 10,
+fn stats_command(tool: bool, since: Option<&str>, top: usize, cli: &Cli) -> Result<()> {
+    if let Some(since) = since {
+        return activity_stats_command(since, top, cli);
+    }
+
+    let config = Config::load(cli.config.as_deref()).context("Failed to load configuration")?;
+    let store = StatsStore::new(config.settings.stats_file.clone());
+    let records = store.load()?;
+
+    if tool {
+        if records.is_empty() && !config.settings.usage_stats {
+            println!("Usage statistics are disabled.");
+            println!("Enable them with `usage_stats = true` under [settings] in your config.");
+            println!(
+                "They are stored locally in {:?} and never sent anywhere.",
+                store.path()
+            );
+            return Ok(());
+        }
+        print!("{}", render_tool_stats(&records));
+    }
+
+    Ok(())
+}
+
+/// Print a heatmap and top lists of the activity feed, without involving Claude
+fn activity_stats_command(since: &str, top: usize, cli: &Cli) -> Result<()> {
+    match gh_report::github::check_gh_version() {
+        Ok(version) => info!("Using gh version {}", version),
+        Err(e) => {
+            error!("GitHub CLI check failed: {}", e);
+            println!("❌ {}", e);
+            println!("\nPlease install GitHub CLI from: https://cli.github.com/");
+            return Err(e);
+        }
     }
+
+    use gh_report::time::TimeDuration;
+    let duration: TimeDuration = since
+        .parse()
+        .with_context(|| format!("Invalid time format: {}", since))?;
+    let days = duration.as_days();
+
+    let config = Config::load(cli.config.as_deref()).ok();
+    let github_client = github_client(config.as_ref().unwrap_or(&Config::default()))?
+        .with_activity_cache(config.as_ref().and_then(activity_cache));
+    let events = github_client
+        .fetch_activity(days)
+        .context("Failed to fetch activity")?;
+
+    let now = Timestamp::now();
+    let window_start = now - jiff::SignedDuration::from_hours(i64::from(days) * 24);
+    let stats = activity_stats(&events, window_start, now, &jiff::tz::TimeZone::system());
+    print!("{}", render_activity_stats(&stats, top));
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1074,7 +2151,7 @@ mod tests {
         let short = "Short title";
         assert_eq!(truncate_title(short, 50), "Short title");
 
-        // Test long title  
+        // Test long title
         let long = "This is a very long title that should be truncated because it exceeds the maximum length";
         let truncated = truncate_title(long, 20);
         // 20 total chars: "This is a very lo" (17 chars) + "..." (3 chars) = 20 total
@@ -1085,11 +2162,4 @@ mod tests {
         let exact = "Exactly twenty chars";
         assert_eq!(truncate_title(exact, 20), "Exactly twenty chars");
     }
-
-    #[test]
-    fn test_action_priority() {
-        assert!(action_priority("opened") < action_priority("closed"));
-        assert!(action_priority("closed") < action_priority("commented"));
-        assert!(action_priority("reviewed") < action_priority("unknown"));
-    }
 }
@@ -0,0 +1,93 @@
+//! Opening generated files for `--open`
+//!
+//! Files are handed to the system's default application for them (`open` on
+//! macOS, `start` on Windows, `xdg-open` elsewhere). When that isn't
+//! available or fails, as on machines without a desktop, `$EDITOR` is used
+//! instead.
+
+use anyhow::{bail, Result};
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+/// A program to launch, with the arguments that go before the path
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Opener {
+    program: String,
+    args: Vec<String>,
+}
+
+/// Open `path` in the default viewer, or `$EDITOR` when there is none
+pub fn open(path: &Path) -> Result<()> {
+    let editor = std::env::var("EDITOR").ok();
+    for opener in openers(editor.as_deref()) {
+        debug!("Opening {} with {}", path.display(), opener.program);
+        match Command::new(&opener.program)
+            .args(&opener.args)
+            .arg(path)
+            .status()
+        {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => debug!("{} exited with {}", opener.program, status),
+            Err(e) => debug!("Failed to run {}: {}", opener.program, e),
+        }
+    }
+    bail!(
+        "Could not open {}: no default viewer is available and $EDITOR is not set",
+        path.display()
+    )
+}
+
+/// The programs to try in order, given the value of `$EDITOR`
+fn openers(editor: Option<&str>) -> Vec<Opener> {
+    let system = if cfg!(target_os = "macos") {
+        Opener {
+            program: "open".to_string(),
+            args: Vec::new(),
+        }
+    } else if cfg!(windows) {
+        // The empty argument is the window title `start` would otherwise
+        // take the quoted path for
+        Opener {
+            program: "cmd".to_string(),
+            args: vec!["/C".to_string(), "start".to_string(), String::new()],
+        }
+    } else {
+        Opener {
+            program: "xdg-open".to_string(),
+            args: Vec::new(),
+        }
+    };
+
+    let mut openers = vec![system];
+    // `$EDITOR` may carry arguments, like `code --wait`
+    let mut editor = editor.unwrap_or_default().split_whitespace();
+    if let Some(program) = editor.next() {
+        openers.push(Opener {
+            program: program.to_string(),
+            args: editor.map(str::to_string).collect(),
+        });
+    }
+    openers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openers() {
+        assert_eq!(openers(None).len(), 1);
+        assert_eq!(openers(Some("  ")).len(), 1);
+
+        let openers = openers(Some("code --wait"));
+        assert_eq!(openers.len(), 2);
+        assert_eq!(
+            openers[1],
+            Opener {
+                program: "code".to_string(),
+                args: vec!["--wait".to_string()],
+            }
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Per-phase timing of a run, collected from tracing spans
+//!
+//! The phases of a run (fetching, filtering, prompting, rendering) are
+//! instrumented with `info_span!`s. With `--profile-run`, [`ProfileLayer`]
+//! adds up how long each span was open so a breakdown can be printed at exit.
+
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Time spent in one phase, over all spans with the same name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub total: Duration,
+    pub count: u32,
+}
+
+/// Phase timings shared between the layer and whoever prints them
+#[derive(Debug, Clone, Default)]
+pub struct PhaseTimings {
+    phases: Arc<Mutex<Vec<Phase>>>,
+}
+
+impl PhaseTimings {
+    pub fn record(&self, name: &'static str, elapsed: Duration) {
+        let mut phases = self.phases.lock().unwrap_or_else(|e| e.into_inner());
+        match phases.iter_mut().find(|p| p.name == name) {
+            Some(phase) => {
+                phase.total += elapsed;
+                phase.count += 1;
+            }
+            None => phases.push(Phase {
+                name,
+                total: elapsed,
+                count: 1,
+            }),
+        }
+    }
+
+    /// Phases in the order they first started
+    pub fn phases(&self) -> Vec<Phase> {
+        self.phases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Render the breakdown, with the wall-clock time of the whole run
+    pub fn render(&self, wall_time: Duration) -> String {
+        let mut output = String::from("⏱  Run profile\n");
+        for phase in self.phases() {
+            let name = phase.name.replace('_', " ");
+            let _ = write!(
+                output,
+                "   {:<20} {:>8}",
+                name,
+                format_duration(phase.total)
+            );
+            if phase.count > 1 {
+                let _ = write!(output, "  ({} calls)", phase.count);
+            }
+            output.push('\n');
+        }
+        let _ = writeln!(
+            output,
+            "   {:<20} {:>8}",
+            "total",
+            format_duration(wall_time)
+        );
+        output
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        format!(
+            "{}m {:02}s",
+            duration.as_secs() / 60,
+            duration.as_secs() % 60
+        )
+    }
+}
+
+/// Tracing layer adding up how long each span is open
+pub struct ProfileLayer {
+    timings: PhaseTimings,
+}
+
+struct SpanStart(Instant);
+
+impl ProfileLayer {
+    pub fn new(timings: PhaseTimings) -> Self {
+        ProfileLayer { timings }
+    }
+}
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanStart(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let elapsed = span
+            .extensions()
+            .get::<SpanStart>()
+            .map(|SpanStart(start)| start.elapsed());
+        if let Some(elapsed) = elapsed {
+            self.timings.record(span.name(), elapsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::info_span;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_profile_layer_records_spans() {
+        let timings = PhaseTimings::default();
+        let subscriber = tracing_subscriber::registry().with(ProfileLayer::new(timings.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            info_span!("activity_fetch").in_scope(|| {});
+            for _ in 0..3 {
+                info_span!("issue_fetch", repo = "tokio-rs/tokio").in_scope(|| {});
+            }
+        });
+
+        let phases = timings.phases();
+        let names: Vec<(&str, u32)> = phases.iter().map(|p| (p.name, p.count)).collect();
+        assert_eq!(names, [("activity_fetch", 1), ("issue_fetch", 3)]);
+    }
+
+    #[test]
+    fn test_render() {
+        let timings = PhaseTimings::default();
+        timings.record("issue_fetch", Duration::from_millis(48_200));
+        timings.record("claude", Duration::from_secs(9));
+        timings.record("claude", Duration::from_millis(100));
+
+        let output = timings.render(Duration::from_secs(75));
+        assert!(output.contains("issue fetch             48.2s\n"));
+        assert!(output.contains("claude                   9.1s  (2 calls)\n"));
+        assert!(output.ends_with("   total                  1m 15s\n"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Active GitHub Discussions threads, from `DiscussionEvent`s and
+//! `DiscussionCommentEvent`s in the feed
+
+use crate::github::{ActivityEvent, Discussion};
+
+/// Most discussions listed in a report
+pub const MAX_DISCUSSIONS: usize = 10;
+
+/// A discussion with Claude's one-line summary of the thread
+#[derive(Debug, Clone)]
+pub struct DiscussionNote {
+    pub repo: String,
+    pub discussion: Discussion,
+    /// Missing without Claude and for repositories kept out of AI summaries
+    pub summary: Option<String>,
+}
+
+/// The discussions with activity in `events` as `(repo, number)`, most
+/// recently active first
+pub fn discussion_references(events: &[&ActivityEvent]) -> Vec<(String, u32)> {
+    let mut events: Vec<&ActivityEvent> = events
+        .iter()
+        .copied()
+        .filter(|event| {
+            matches!(
+                event.event_type.as_str(),
+                "DiscussionEvent" | "DiscussionCommentEvent"
+            )
+        })
+        .collect();
+    events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+    let mut discussions: Vec<(String, u32)> = Vec::new();
+    for event in events {
+        let number = event
+            .payload
+            .get("discussion")
+            .and_then(|discussion| discussion.get("number"))
+            .and_then(|n| n.as_u64());
+        if let Some(number) = number {
+            let discussion = (event.repo.name.clone(), number as u32);
+            if !discussions.contains(&discussion) {
+                discussions.push(discussion);
+            }
+        }
+    }
+    discussions
+}
+
+/// Fill in the summaries of `notes` from a response with one
+/// `<number>. <summary>` line per discussion
+pub fn parse_discussion_summaries(notes: &mut [DiscussionNote], response: &str) {
+    for line in response.lines() {
+        let Some((number, summary)) = line.trim().split_once(". ") else {
+            continue;
+        };
+        let Ok(number) = number.parse::<usize>() else {
+            continue;
+        };
+        let summary = summary.trim();
+        if let Some(note) = number.checked_sub(1).and_then(|i| notes.get_mut(i)) {
+            if !summary.is_empty() {
+                note.summary = Some(summary.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: u32, event_type: &str, number: u64) -> ActivityEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "type": event_type,
+            "actor": { "login": "carllerche" },
+            "repo": { "id": 1, "name": "tokio-rs/tokio", "url": "" },
+            "payload": { "action": "created", "discussion": { "number": number, "title": "Roadmap" } },
+            "created_at": format!("2024-03-06T{:02}:00:00Z", id),
+            "public": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_discussion_references() {
+        let events = [
+            event(1, "DiscussionEvent", 40),
+            event(2, "DiscussionCommentEvent", 41),
+            event(3, "DiscussionCommentEvent", 40),
+            event(4, "IssueCommentEvent", 42),
+        ];
+        let events: Vec<&ActivityEvent> = events.iter().collect();
+        assert_eq!(
+            discussion_references(&events),
+            [
+                ("tokio-rs/tokio".to_string(), 40),
+                ("tokio-rs/tokio".to_string(), 41)
+            ]
+        );
+    }
+}
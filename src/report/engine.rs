@@ -0,0 +1,196 @@
+//! Markdown reports rendered through minijinja templates
+//!
+//! The default template renders every section from the report's data:
+//! items with their badges, action items, summaries, and so on. Each section
+//! is a block of it, so a `report.template` can list `sections.*` to reorder
+//! or drop them, or lay out the items itself from the same data.
+
+use anyhow::{Context, Result};
+use minijinja::{context, Environment, Value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use super::Locale;
+
+const DEFAULT_NAME: &str = "default";
+const TEMPLATE_NAME: &str = "report";
+
+/// The layout used when `report.template` is unset
+pub const DEFAULT_TEMPLATE: &str = include_str!("report.md.j2");
+
+/// The blocks of `DEFAULT_TEMPLATE`, in order, available as `sections.*`
+pub(super) const SECTIONS: &[&str] = &[
+    "header",
+    "warnings",
+    "comparison",
+    "notes",
+    "action_items",
+    "awaiting_reply",
+    "escalations",
+    "waiting_on_you",
+    "waiting_on_others",
+    "reviewer_workload",
+    "new_contributors",
+    "needs_info",
+    "ci_attention",
+    "ci_failures",
+    "local_work",
+    "notifications",
+    "mentions",
+    "watch_rules",
+    "highlights",
+    "goals",
+    "upcoming",
+    "releases",
+    "discussions",
+    "projects",
+    "team",
+    "no_activity",
+    "summary",
+    "prioritized",
+    "activity",
+    "truncation",
+    "low_priority",
+    "appendix",
+    "footer",
+];
+
+/// A compiled report template
+pub struct TemplateEngine {
+    env: Environment<'static>,
+    /// Whether `report.template` replaces the default layout
+    custom: bool,
+}
+
+impl TemplateEngine {
+    /// Compile `source`, or the default layout when there is none
+    ///
+    /// Templates from before the engine, with `{placeholder}` syntax, are
+    /// ignored with a warning rather than rendered as literal text.
+    pub fn new(source: Option<&str>, locale: Locale) -> Result<Self> {
+        let source = match source {
+            Some(source) if is_legacy(source) => {
+                warn!(
+                    "report.template uses the old {{placeholder}} syntax and is ignored; \
+                     see the README for the template syntax"
+                );
+                None
+            }
+            source => source,
+        };
+
+        let mut env = Environment::new();
+        env.set_keep_trailing_newline(true);
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+        env.add_filter("count", move |n: i64| locale.format_count(n));
+        env.add_filter("truncate", truncate);
+        env.add_template(DEFAULT_NAME, DEFAULT_TEMPLATE)?;
+        if let Some(source) = source {
+            env.add_template_owned(TEMPLATE_NAME, source.to_string())
+                .context("Invalid report.template")?;
+        }
+        Ok(TemplateEngine {
+            env,
+            custom: source.is_some(),
+        })
+    }
+
+    pub fn render(&self, context: impl Serialize) -> Result<String> {
+        let context = Value::from_serialize(context);
+        if !self.custom {
+            return self
+                .env
+                .get_template(DEFAULT_NAME)?
+                .render(context)
+                .context("Failed to render the report");
+        }
+
+        let template = self.env.get_template(TEMPLATE_NAME)?;
+        let context = if template.undeclared_variables(false).contains("sections") {
+            context! { sections => self.sections(&context)?, ..context }
+        } else {
+            context
+        };
+        template
+            .render(context)
+            .context("Failed to render report.template")
+    }
+
+    /// Each section of the default layout, rendered on its own
+    fn sections(&self, context: &Value) -> Result<BTreeMap<&'static str, String>> {
+        let mut captured = self
+            .env
+            .get_template(DEFAULT_NAME)?
+            .render_captured(context)?;
+        captured.with_state_mut(|state| {
+            SECTIONS
+                .iter()
+                .map(|name| Ok((*name, state.render_block(name)?)))
+                .collect()
+        })
+    }
+}
+
+/// Whether a template only has the `{placeholder}` syntax of old configs
+fn is_legacy(source: &str) -> bool {
+    !source.contains("{{") && !source.contains("{%") && source.contains('{')
+}
+
+/// Shorten text to `length` characters, ending it with `…` when cut
+fn truncate(text: String, length: Option<usize>) -> String {
+    let length = length.unwrap_or(80);
+    if text.chars().count() <= length {
+        return text;
+    }
+    let mut truncated: String = text.chars().take(length.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_template() {
+        let engine = TemplateEngine::new(
+            Some(
+                "{% for item in prioritized if item.kind == \"pr\" %}\n\
+                 - {{ item.title | truncate(8) }} ({{ item.score | count }})\n\
+                 {% endfor %}",
+            ),
+            Locale::lookup("en-US").unwrap(),
+        )
+        .unwrap();
+        let output = engine
+            .render(serde_json::json!({
+                "prioritized": [
+                    { "kind": "pr", "title": "Speed up the scheduler", "score": 1200 },
+                    { "kind": "issue", "title": "Panic", "score": 50 },
+                ],
+            }))
+            .unwrap();
+        assert_eq!(output, "- Speed u… (1,200)\n");
+    }
+
+    #[test]
+    fn test_legacy_and_invalid_templates() {
+        let context = serde_json::json!({ "heading": "Report", "enabled": {}, "filtered": {} });
+        let legacy = TemplateEngine::new(
+            Some("# Report - {date}\n{action_required}"),
+            Locale::default(),
+        )
+        .unwrap();
+        let default = TemplateEngine::new(None, Locale::default()).unwrap();
+        let output = legacy.render(&context).unwrap();
+        assert!(output.starts_with("# Report\n"));
+        assert_eq!(output, default.render(&context).unwrap());
+
+        let error = TemplateEngine::new(Some("{% if %}"), Locale::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Invalid report.template"));
+    }
+}
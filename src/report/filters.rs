@@ -0,0 +1,202 @@
+//! Leaving issues and PRs out of reports by their author or labels, or
+//! because they were muted
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::Locale;
+use crate::github::Issue;
+
+/// Why an item is left out of the report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemFilter {
+    /// It was opened by this ignored author, like a bot
+    Author(String),
+    /// It has this excluded label, as configured
+    Label(String),
+    /// It has none of `report.require_labels`
+    MissingRequiredLabel,
+    /// It was muted with `gh-report mute`
+    Muted,
+}
+
+/// Whether `issue` is left out with the `excluded` and `required` labels
+///
+/// Labels match case-insensitively. An excluded label wins over a required one.
+pub fn label_filter<S: AsRef<str>>(
+    issue: &Issue,
+    excluded: &[&str],
+    required: &[S],
+) -> Option<ItemFilter> {
+    let has = |name: &str| {
+        issue
+            .labels
+            .iter()
+            .any(|label| label.name.eq_ignore_ascii_case(name))
+    };
+    if let Some(label) = excluded.iter().find(|label| has(label)) {
+        return Some(ItemFilter::Label(label.to_string()));
+    }
+    if !required.is_empty() && !required.iter().any(|label| has(label.as_ref())) {
+        return Some(ItemFilter::MissingRequiredLabel);
+    }
+    None
+}
+
+/// Counts of the items left out of a report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FilteredItems {
+    /// Items opened by ignored authors, by login
+    pub authors: BTreeMap<String, usize>,
+    /// Items with an excluded label, by label
+    pub labels: BTreeMap<String, usize>,
+    /// Items without any of `report.require_labels`
+    pub missing_required: usize,
+    /// Items muted with `gh-report mute`
+    pub muted: usize,
+}
+
+impl FilteredItems {
+    pub fn record(&mut self, filter: ItemFilter) {
+        match filter {
+            ItemFilter::Author(login) => *self.authors.entry(login).or_default() += 1,
+            ItemFilter::Label(label) => *self.labels.entry(label).or_default() += 1,
+            ItemFilter::MissingRequiredLabel => self.missing_required += 1,
+            ItemFilter::Muted => self.muted += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.authors.values().sum::<usize>()
+            + self.labels.values().sum::<usize>()
+            + self.missing_required
+            + self.muted
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// `12 items (dependencies: 9, bot: 2, 1 without a required label)`, for the report header
+    pub fn describe_labels(&self, locale: &Locale) -> Option<String> {
+        let mut parts = describe_counts(&self.labels, locale);
+        if self.missing_required > 0 {
+            parts.push(format!(
+                "{} without a required label",
+                locale.format_count(self.missing_required as i64)
+            ));
+        }
+        let total = self.labels.values().sum::<usize>() + self.missing_required;
+        (total > 0).then(|| items_with_parts(total, &parts, locale))
+    }
+
+    /// `2 items`, for the report header
+    pub fn describe_muted(&self, locale: &Locale) -> Option<String> {
+        (self.muted > 0).then(|| {
+            format!(
+                "{} item{}",
+                locale.format_count(self.muted as i64),
+                if self.muted == 1 { "" } else { "s" }
+            )
+        })
+    }
+
+    /// `14 items (dependabot[bot]: 12, renovate[bot]: 2)`, for the collapsed bot activity line
+    pub fn describe_authors(&self, locale: &Locale) -> Option<String> {
+        let total = self.authors.values().sum::<usize>();
+        (total > 0)
+            .then(|| items_with_parts(total, &describe_counts(&self.authors, locale), locale))
+    }
+}
+
+fn describe_counts(counts: &BTreeMap<String, usize>, locale: &Locale) -> Vec<String> {
+    counts
+        .iter()
+        .map(|(name, count)| format!("{}: {}", name, locale.format_count(*count as i64)))
+        .collect()
+}
+
+fn items_with_parts(total: usize, parts: &[String], locale: &Locale) -> String {
+    format!(
+        "{} item{} ({})",
+        locale.format_count(total as i64),
+        if total == 1 { "" } else { "s" },
+        parts.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Label;
+    use crate::test_utils::create_test_issue;
+
+    fn labeled(number: u32, labels: &[&str]) -> Issue {
+        let mut issue = create_test_issue(number, "Bump serde", true);
+        issue.labels = labels
+            .iter()
+            .map(|name| Label {
+                name: name.to_string(),
+                color: None,
+                description: None,
+            })
+            .collect();
+        issue
+    }
+
+    #[test]
+    fn test_label_filter() {
+        let excluded = ["dependencies", "bot"];
+        let none: &[String] = &[];
+        assert_eq!(
+            label_filter(&labeled(1, &["Dependencies"]), &excluded, none),
+            Some(ItemFilter::Label("dependencies".to_string()))
+        );
+        assert_eq!(label_filter(&labeled(2, &["bug"]), &excluded, none), None);
+
+        let required = ["bug", "regression"];
+        assert_eq!(
+            label_filter(&labeled(3, &["bug"]), &excluded, &required),
+            None
+        );
+        assert_eq!(
+            label_filter(&labeled(4, &[]), &excluded, &required),
+            Some(ItemFilter::MissingRequiredLabel)
+        );
+        assert_eq!(
+            label_filter(&labeled(5, &["bug", "bot"]), &excluded, &required),
+            Some(ItemFilter::Label("bot".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_describe_counts() {
+        let locale = Locale::default();
+        let mut filtered = FilteredItems::default();
+        assert_eq!(filtered.describe_labels(&locale), None);
+
+        for _ in 0..2 {
+            filtered.record(ItemFilter::Label("dependencies".to_string()));
+        }
+        filtered.record(ItemFilter::Label("bot".to_string()));
+        filtered.record(ItemFilter::MissingRequiredLabel);
+        assert_eq!(filtered.total(), 4);
+        assert_eq!(
+            filtered.describe_labels(&locale).unwrap(),
+            "4 items (bot: 1, dependencies: 2, 1 without a required label)"
+        );
+        assert_eq!(filtered.describe_authors(&locale), None);
+
+        filtered.record(ItemFilter::Author("dependabot[bot]".to_string()));
+        assert_eq!(filtered.total(), 5);
+        assert_eq!(
+            filtered.describe_authors(&locale).unwrap(),
+            "1 item (dependabot[bot]: 1)"
+        );
+        assert_eq!(filtered.describe_muted(&locale), None);
+
+        filtered.record(ItemFilter::Muted);
+        assert_eq!(filtered.total(), 6);
+        assert_eq!(filtered.describe_muted(&locale).unwrap(), "1 item");
+    }
+}
@@ -1,47 +1,118 @@
 use anyhow::{Context, Result};
+use jiff::tz::TimeZone;
 use jiff::{Timestamp, ToSpan};
-use std::collections::BTreeMap;
-use tracing::{info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{info, info_span, warn};
 
-use super::{group_activities_by_repo, Report, ReportTemplate};
-use crate::cache::{generate_cache_key, CacheManager};
-use crate::claude::prompts::{generate_title_prompt, summarize_activities_prompt, system_prompt};
+use super::discussions::{
+    discussion_references, parse_discussion_summaries, DiscussionNote, MAX_DISCUSSIONS,
+};
+use super::filters::{label_filter, FilteredItems, ItemFilter};
+use super::json::JsonReport;
+use super::mentions::{collect_mentions, Mention};
+use super::org::group_by_org;
+use super::releases::{parse_release_summaries, published_releases, ReleaseNote, MAX_RELEASES};
+use super::rollup::{HistoryEntry, PeriodComparison, PeriodTotals, ReportFlavor};
+use super::team::{team_breakdown, PersonActivity};
+use super::upcoming::{upcoming_milestones, UpcomingMilestone};
+use super::{
+    activity_issues, covered_items, group_activities_by_repo, seen_items, ActivityCounts, Report,
+    ReportDigest, ReportTemplate,
+};
+use crate::cache::IssueContext;
+use crate::cache::{generate_cache_key, CacheManager, PartialRun};
+use crate::claude::prompts::{
+    chunk_activities, combine_summaries_prompt, diff_patches_section, discussion_summaries_prompt,
+    escalation_check_prompt, explain_ci_failure_prompt, generate_title_prompt,
+    goals_check_in_prompt, nudge_reply_prompt, pr_changes_section, release_summaries_prompt,
+    rollup_instructions, summarize_activities_prompt, summarize_context_prompt,
+    summarize_org_prompt, system_prompt, thread_update_prompt, with_output_language,
+};
 use crate::claude::{
-    estimate_cost, estimate_tokens, resolve_model_alias, ClaudeInterface, Message, MessagesRequest,
+    backend_from_config, confirm_new_repos, confirm_private_repos, estimate_tokens,
+    find_private_repos, LlmBackend, Message, MessagesRequest, OfflineBackend,
+};
+use crate::config::{Config, EscalationDetection, GroupBy, ReportFormat, SummarizationStrategy};
+use crate::git::{correlate_local_work, LocalRepo, LocalWork};
+use crate::github::{
+    is_auth_expired, parse_project_url, ActivityFeed, CiStatus, Comment, GitHubClient, Issue,
+    IssueState, Notification, PrDiff, ProjectBoard,
+};
+use crate::intelligence::{
+    comments_since, escalation_candidates, explain_scores, extract_deadlines, extract_error_region,
+    find_awaiting_reply, find_escalations, parse_escalation_check, parse_goal_check_in,
+    parse_thread_update, reviewer_workload, AnalysisResult, AwaitingReply, CiFailure, Deadline,
+    Escalation, GoalCheckIn, IntelligentAnalyzer, ReviewerLoad, ThreadUpdate, Truncation,
+    WaitingOnYou, MAX_ESCALATION_THREADS, MAX_TRACKED_THREADS,
 };
-use crate::config::Config;
-use crate::github::{GitHubClient, Issue};
-use crate::intelligence::IntelligentAnalyzer;
 use crate::progress::ProgressReporter;
-use crate::state::State;
+use crate::snapshot::ActivitySnapshot;
+use crate::state::{RepoTrust, SeenStatus, State};
 
 pub struct ReportGenerator<'a> {
     github_client: GitHubClient,
-    claude_client: Option<ClaudeInterface>,
+    llm: Option<Box<dyn LlmBackend>>,
     config: &'a Config,
-    _state: &'a State, // Keep for future use
+    state: &'a State,
     cache_manager: Option<CacheManager>,
+    local_repo: Option<LocalRepo>,
+    ci_logs: bool,
+    since_last_report: Option<Timestamp>,
+    /// Start of a window given as a date, rather than a day count
+    window_start: Option<Timestamp>,
+    /// End of a window that closed before now
+    until: Option<Timestamp>,
+    /// Data fetched by the interrupted report picked up with `--resume`
+    resumed: Option<PartialRun>,
+    flavor: ReportFlavor,
+    /// The previous report of the same flavor, which a rollup is compared to
+    previous_period: Option<HistoryEntry>,
+    /// Markdown printed by the `pre_report` hooks
+    hook_notes: Vec<String>,
 }
 
+/// Maximum number of failed workflow runs inspected with `--ci-logs`
+const MAX_CI_FAILURES: usize = 5;
+
+/// Most items waiting on the user that get a reply drafted with `settings.draft_nudges`
+const MAX_NUDGES: usize = 5;
+
+/// Most open PRs whose checks are looked up for one report
+const MAX_CI_STATUS_PRS: usize = 30;
+
+/// Repositories per search query when filling in a truncated activity feed
+const SEARCH_REPOS_PER_QUERY: usize = 5;
+
+/// Repositories summarized at the same time with `summarization_strategy = "map_reduce"`
+const MAP_CONCURRENCY: usize = 4;
+
+/// Most tokens of an activity summary
+const SUMMARY_MAX_TOKENS: u32 = 4000;
+
+/// Notification reasons that ask something of the user
+const NOTIFICATION_REASONS: &[&str] = &["review_requested", "mention", "team_mention"];
+
 impl<'a> ReportGenerator<'a> {
     pub fn new(github_client: GitHubClient, config: &'a Config, state: &'a State) -> Self {
-        // Try to create Claude client based on config
-        let claude_client = match ClaudeInterface::new(&config.claude) {
-            Ok(client) => client,
-            Err(e) => {
-                warn!("Failed to initialize Claude: {}", e);
-                None
+        // Try to create Claude client based on config; offline runs only
+        // use the AI output in the cache
+        let llm = if github_client.is_offline() {
+            Some(Box::new(OfflineBackend) as Box<dyn LlmBackend>)
+        } else {
+            match backend_from_config(config) {
+                Ok(client) => client,
+                Err(e) => {
+                    warn!("Failed to initialize LLM backend: {}", e);
+                    None
+                }
             }
         };
 
         // Initialize cache manager if caching is enabled
         let cache_manager = if config.cache.enabled {
-            let cache_dir = dirs::cache_dir()
-                .unwrap_or_else(|| std::path::PathBuf::from("."))
-                .join("gh-report");
-
             let manager = CacheManager::new(
-                cache_dir,
+                config.cache_dir(),
                 config.cache.ttl_hours,
                 config.cache.compression_enabled,
             );
@@ -50,6 +121,9 @@ impl<'a> ReportGenerator<'a> {
             if let Err(e) = manager.initialize() {
                 warn!("Failed to initialize cache: {}", e);
                 None
+            } else if github_client.is_offline() {
+                info!("Using cached data regardless of its age");
+                Some(manager.with_offline())
             } else {
                 info!("Cache initialized with {} hour TTL", config.cache.ttl_hours);
                 Some(manager)
@@ -60,17 +134,121 @@ impl<'a> ReportGenerator<'a> {
 
         ReportGenerator {
             github_client,
-            claude_client,
+            llm,
             config,
-            _state: state,
+            state,
             cache_manager,
+            local_repo: None,
+            ci_logs: false,
+            since_last_report: None,
+            window_start: None,
+            until: None,
+            resumed: None,
+            flavor: ReportFlavor::Daily,
+            previous_period: None,
+            hook_notes: Vec::new(),
         }
     }
 
+    /// Correlate report items with work in a local clone
+    pub fn with_local_repo(mut self, local_repo: LocalRepo) -> Self {
+        self.local_repo = Some(local_repo);
+        self
+    }
+
+    /// Dig into failed GitHub Actions runs of pull requests in the report
+    pub fn with_ci_logs(mut self, enabled: bool) -> Self {
+        self.ci_logs = enabled;
+        self
+    }
+
+    /// Start the report window exactly at the previous report instead of a day count
+    pub fn with_since_last_report(mut self, last_run: Timestamp) -> Self {
+        self.since_last_report = Some(last_run);
+        self
+    }
+
+    /// Report on a fixed window, from `--since 2024-05-01 --until 2024-05-07`
+    ///
+    /// With an end, the report is built as if it ran then, leaving out
+    /// activity that came after.
+    pub fn with_window(mut self, start: Timestamp, until: Option<Timestamp>) -> Self {
+        self.window_start = Some(start);
+        self.until = until;
+        self
+    }
+
+    /// Write a weekly or monthly rollup of themes and trends, compared to
+    /// `previous`, the previous report of the same flavor
+    pub fn with_flavor(mut self, flavor: ReportFlavor, previous: Option<HistoryEntry>) -> Self {
+        self.flavor = flavor;
+        self.previous_period = previous;
+        self
+    }
+
+    /// Add what the `pre_report` hooks printed to the report and to the
+    /// context of the AI summary
+    pub fn with_hook_notes(mut self, notes: Vec<String>) -> Self {
+        self.hook_notes = notes;
+        self
+    }
+
+    /// Continue the interrupted report of the same kind, if there is one,
+    /// reusing what it already fetched and its report window
+    pub fn with_resume(mut self) -> Self {
+        let Some(cache) = &self.cache_manager else {
+            warn!("Caching is disabled, so there is no interrupted report to resume");
+            return self;
+        };
+        self.resumed = match cache.get_partial_run() {
+            Ok(Some(run)) if run.scope == self.run_scope() => Some(run),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("Failed to read the interrupted report: {}", e);
+                None
+            }
+        };
+        self
+    }
+
+    /// Start of the window of the resumed report, if one is resumed
+    pub fn resumed_since(&self) -> Option<Timestamp> {
+        self.resumed.as_ref().map(|run| run.since)
+    }
+
     pub fn generate(&self, lookback_days: u32) -> Result<Report> {
         self.generate_with_progress(lookback_days, false)
     }
 
+    /// What an offline report had to leave out, one line per kind of GitHub data
+    ///
+    /// Empty unless the GitHub client is offline.
+    pub fn unavailable(&self) -> Vec<String> {
+        self.github_client
+            .unavailable()
+            .into_iter()
+            .map(|(data, calls)| match calls {
+                1 => data,
+                calls => format!("{} ({} requests)", data, calls),
+            })
+            .collect()
+    }
+
+    /// Report warnings about an offline run and the data it went without
+    fn offline_warnings(&self) -> Vec<String> {
+        if !self.github_client.is_offline() {
+            return Vec::new();
+        }
+        let mut warnings = vec![
+            "📴 Offline report, built from cached GitHub data and AI summaries only".to_string(),
+        ];
+        let unavailable = self.unavailable();
+        if !unavailable.is_empty() {
+            warnings.push(format!("📴 Not in the cache: {}", unavailable.join(", ")));
+        }
+        warnings
+    }
+
     /// Generate report from GitHub activity feed (new approach)
     pub fn generate_from_activity(&self, lookback_days: u32) -> Result<Report> {
         self.generate_from_activity_with_progress(lookback_days, false)
@@ -83,7 +261,7 @@ impl<'a> ReportGenerator<'a> {
         dry_run: bool,
     ) -> Result<Report> {
         let mut progress = ProgressReporter::new();
-        let now = Timestamp::now();
+        let now = self.until.unwrap_or_else(Timestamp::now);
 
         if !progress.is_interactive() {
             info!(
@@ -99,13 +277,18 @@ impl<'a> ReportGenerator<'a> {
         let _spinner = progress.spinner("Fetching activity feed");
 
         // Fetch activity events using the same filtering as the activity command
-        let all_events = self
-            .github_client
-            .fetch_activity(lookback_days)
+        let feed = self
+            .fetch_feed(lookback_days)
             .context("Failed to fetch activity")?;
+        if let Some(truncated_at) = feed.truncated_at {
+            warn!(
+                "The activity feed only reaches back to {}, searching for the rest",
+                truncated_at
+            );
+        }
 
         // Apply default activity filtering (same as activity command)
-        let events = self.filter_activity_events(&all_events);
+        let events = self.filter_activity_events(&feed.events);
 
         if events.is_empty() {
             warn!(
@@ -116,10 +299,18 @@ impl<'a> ReportGenerator<'a> {
                 title: "No Activity Report".to_string(),
                 content: format!("# No GitHub Activity\n\nNo relevant activity found in the last {} days.\n\n*Report generated at {}*",
                     lookback_days,
-                    now.strftime("%Y-%m-%d %H:%M")
+                    self.config.report.locale().format_datetime(now)
                 ),
                 timestamp: now,
                 estimated_cost: 0.0,
+                deadlines: Vec::new(),
+                items: Vec::new(),
+                seen_items: BTreeMap::new(),
+                repo_trust: None,
+                scores: None,
+                format: ReportFormat::Markdown,
+                totals: PeriodTotals::default(),
+                digest: ReportDigest::default(),
             });
         }
 
@@ -127,17 +318,58 @@ impl<'a> ReportGenerator<'a> {
         let _spinner2 = progress.spinner("Extracting issues and PRs");
 
         // Extract unique issues/PRs from activity events
-        let issue_refs = self.extract_issue_references(&events);
+        let mut issue_refs = crate::activity::issue_references(&events);
+        let since = self
+            .resumed_since()
+            .or(self.since_last_report)
+            .or(self.window_start)
+            .unwrap_or_else(|| now - (lookback_days as i64 * 24).hours());
+        let mut errors = Vec::new();
+
+        // Review requests and mentions that never showed up in the feed,
+        // which are personal and left out of team reports
+        let team_report = !self.config.report.users.is_empty();
+        let mut notifications = if dry_run || team_report {
+            Vec::new()
+        } else {
+            let known: HashSet<_> = issue_refs.iter().cloned().collect();
+            let notifications = self.missed_notifications(since, &known, &mut errors)?;
+            issue_refs.extend(notification_refs(&notifications));
+            notifications
+        };
+
+        // Fill in the span the feed no longer reaches back to
+        if let (Some(truncated_at), false) = (feed.truncated_at, dry_run) {
+            let mut repos: Vec<String> = feed
+                .events
+                .iter()
+                .map(|e| e.repo.name.clone())
+                .filter(|repo| self.config.report.includes_repo(repo))
+                .collect();
+            repos.sort();
+            repos.dedup();
+            let known: HashSet<_> = issue_refs.iter().cloned().collect();
+            let found = self.search_feed_gap(&repos, feed.cutoff, truncated_at, &mut errors)?;
+            issue_refs.extend(found.into_iter().filter(|item| !known.contains(item)));
+        }
 
         if issue_refs.is_empty() {
             warn!("No issues or PRs found in activity");
             return Ok(Report {
                 title: "No Issues Found".to_string(),
                 content: format!("# No Issues or PRs\n\nNo issues or pull requests found in recent activity.\n\n*Report generated at {}*",
-                    now.strftime("%Y-%m-%d %H:%M")
+                    self.config.report.locale().format_datetime(now)
                 ),
                 timestamp: now,
                 estimated_cost: 0.0,
+                deadlines: Vec::new(),
+                items: Vec::new(),
+                seen_items: BTreeMap::new(),
+                repo_trust: None,
+                scores: None,
+                format: ReportFormat::Markdown,
+                totals: PeriodTotals::default(),
+                digest: ReportDigest::default(),
             });
         }
 
@@ -145,55 +377,182 @@ impl<'a> ReportGenerator<'a> {
         let _spinner3 = progress.spinner("Fetching issue details");
 
         // Fetch full context for each issue/PR
-        let mut all_issue_data = Vec::new();
-        let mut errors = Vec::new();
-
-        for (repo, issue_number) in &issue_refs {
-            if dry_run {
+        if dry_run {
+            for (repo, issue_number) in &issue_refs {
                 println!("Would fetch: {}/issues/{}", repo, issue_number);
-                continue;
             }
-
-            match self.github_client.fetch_single_issue(repo, *issue_number) {
-                Ok((issue, comments)) => {
-                    all_issue_data.push((issue, comments));
-                }
-                Err(e) => {
-                    warn!("Failed to fetch {}/issues/{}: {}", repo, issue_number, e);
-                    errors.push(format!(
-                        "Failed to fetch {}/issues/{}: {}",
-                        repo, issue_number, e
-                    ));
-                }
-            }
-        }
-
-        if dry_run {
             return Ok(Report {
                 title: "Dry Run Complete".to_string(),
                 content: format!("# Dry Run Report\n\nWould have fetched {} issues/PRs.\n\n*Report generated at {}*",
                     issue_refs.len(),
-                    now.strftime("%Y-%m-%d %H:%M")
+                    self.config.report.locale().format_datetime(now)
                 ),
                 timestamp: now,
                 estimated_cost: 0.0,
+                deadlines: Vec::new(),
+                items: Vec::new(),
+                seen_items: BTreeMap::new(),
+                repo_trust: None,
+                scores: None,
+                format: ReportFormat::Markdown,
+                totals: PeriodTotals::default(),
+                digest: ReportDigest::default(),
             });
         }
+        let mut all_issue_data = self.fetch_new_items(&issue_refs, since, &mut errors)?;
+        let filtered =
+            self.drop_filtered_items(&mut all_issue_data, |(issue, _)| issue, &mut notifications);
+        let mentions = self.included_mentions(since, &mut errors)?;
 
         info!("Successfully fetched {} issues/PRs", all_issue_data.len());
         let _spinner4 = progress.spinner("Organizing activities");
 
         // Group issues by repository for existing report logic
         let activities = self.group_issues_by_repo(all_issue_data);
+        let team = team_breakdown(&self.config.report.users, &events);
+        let releases = self.fetch_releases(&feed.events, &mut errors);
+        let discussions = self.fetch_discussions(&events, since, &mut errors);
 
         // Use existing intelligent analysis and report generation
-        self.generate_final_report(activities, now, &mut progress, errors)
+        let report = self.generate_final_report(
+            activities,
+            since,
+            now,
+            &mut progress,
+            errors,
+            notifications,
+            mentions,
+            feed.truncated_at,
+            team,
+            releases,
+            discussions,
+            filtered,
+            None,
+        )?;
+        self.clear_partial_run();
+        Ok(report)
+    }
+
+    /// The events of the user's activity feed between `since` and `now`
+    ///
+    /// Organization reports keep the events of `org_repos` only. A feed that
+    /// can't be fetched leaves the sections built from it out, with a warning.
+    fn feed_events(
+        &self,
+        lookback_days: u32,
+        since: Timestamp,
+        now: Timestamp,
+        org_repos: &[String],
+        errors: &mut Vec<String>,
+    ) -> Result<Vec<crate::github::ActivityEvent>> {
+        let feed = match self.github_client.fetch_activity_feed(lookback_days) {
+            Ok(feed) => feed,
+            Err(e) if is_auth_expired(&e) => return Err(e),
+            Err(e) => {
+                warn!("Failed to fetch the activity feed: {}", e);
+                errors.push(format!("⚠️ Could not fetch the activity feed: {}", e));
+                return Ok(Vec::new());
+            }
+        };
+        Ok(feed
+            .events
+            .into_iter()
+            .filter(|event| event.created_at >= since && event.created_at < now)
+            .filter(|event| {
+                self.config.report.org.is_none() || org_repos.contains(&event.repo.name)
+            })
+            .collect())
+    }
+
+    /// The user's activity feed, or the combined events of `report.users` in team reports
+    ///
+    /// `lookback_days` reaches back from now, so a window ending before now
+    /// leaves out the events after `until`.
+    fn fetch_feed(&self, lookback_days: u32) -> Result<ActivityFeed> {
+        let users = &self.config.report.users;
+        let mut feed = if users.is_empty() {
+            self.github_client.fetch_activity_feed(lookback_days)?
+        } else {
+            let cutoff = Timestamp::now() - (lookback_days as i64 * 24).hours();
+            let mut feeds = Vec::new();
+            for login in users {
+                let feed = self
+                    .github_client
+                    .fetch_user_events(login, lookback_days)
+                    .with_context(|| format!("Failed to fetch the activity of {}", login))?;
+                info!("Found {} events by {}", feed.events.len(), login);
+                feeds.push(feed);
+            }
+            ActivityFeed::merge(cutoff, feeds)
+        };
+        if let Some(until) = self.until {
+            feed.events.retain(|event| event.created_at < until);
+        }
+        Ok(feed)
+    }
+
+    /// Generate a report from a saved activity snapshot, without fetching from GitHub
+    ///
+    /// The report is built as if it ran when the snapshot was captured.
+    pub fn generate_from_snapshot(&self, snapshot: &ActivitySnapshot) -> Result<Report> {
+        let mut progress = ProgressReporter::new();
+        let now = snapshot.captured_at;
+        let since = now - (snapshot.lookback_days as i64 * 24).hours();
+        info!(
+            "Generating report from a snapshot of {} events captured at {}",
+            snapshot.events.len(),
+            now
+        );
+
+        let mut items: Vec<_> = snapshot
+            .items
+            .iter()
+            .filter(|item| {
+                item.issue
+                    .repository_name()
+                    .is_none_or(|repo| self.config.report.includes_repo(&repo))
+            })
+            .map(|item| (item.issue.clone(), item.comments.clone()))
+            .collect();
+        let filtered = self.drop_filtered_items(&mut items, |(issue, _)| issue, &mut Vec::new());
+        let mut activities = self.group_issues_by_repo(items);
+        for item in snapshot.items.iter().filter(|i| i.issue.is_pull_request) {
+            if let Some(activity) = item
+                .issue
+                .repository_name()
+                .and_then(|repo| activities.get_mut(&repo))
+            {
+                activity
+                    .reviews
+                    .insert(item.issue.number, item.reviews.clone());
+            }
+        }
+
+        self.generate_final_report(
+            activities,
+            since,
+            now,
+            &mut progress,
+            snapshot.errors.clone(),
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            filtered,
+            Some(snapshot),
+        )
     }
 
     pub fn generate_with_progress(&self, lookback_days: u32, dry_run: bool) -> Result<Report> {
         let mut progress = ProgressReporter::new();
-        let now = Timestamp::now();
-        let since = now - (lookback_days as i64 * 24).hours();
+        let now = self.until.unwrap_or_else(Timestamp::now);
+        let since = self
+            .resumed_since()
+            .or(self.since_last_report)
+            .or(self.window_start)
+            .unwrap_or_else(|| now - (lookback_days as i64 * 24).hours());
 
         if !progress.is_interactive() {
             info!("Generating report for the last {} days", lookback_days);
@@ -207,25 +566,48 @@ impl<'a> ReportGenerator<'a> {
             info!("DRY RUN: Showing what would be fetched without generating report");
         }
 
+        // Team reports follow the activity of the team members instead
+        if self.config.report.org.is_none() && !self.config.report.users.is_empty() {
+            return self.generate_from_activity_with_progress(lookback_days, dry_run);
+        }
+
         // Use dynamic repository discovery based on user activity
         info!("Using dynamic repository discovery based on GitHub activity");
 
         let mut all_issues = Vec::new();
         let mut errors = Vec::new();
 
-        // Discover repositories dynamically based on user activity
-        let repos_to_process = match self.discover_active_repositories(&since) {
-            Ok(repos) => repos,
-            Err(e) => {
-                warn!("Failed to discover repositories: {}", e);
-                warn!("Continuing with empty repository list");
-                Vec::new()
+        let current_user = self.current_user();
+
+        let repos_to_process = if let Some(org) = &self.config.report.org {
+            // Every repository of the organization, whoever was active in it
+            self.org_repositories(org)?
+        } else {
+            // Discover repositories dynamically based on user activity
+            let discovered = match current_user.as_deref() {
+                Some(username) => self.discover_active_repositories(username, &since),
+                None => Err(anyhow::anyhow!("Failed to get current user")),
+            };
+            match discovered {
+                Ok(repos) => repos,
+                Err(e) => {
+                    warn!("Failed to discover repositories: {}", e);
+                    warn!("Continuing with empty repository list");
+                    Vec::new()
+                }
             }
         };
+        let repos_to_process: Vec<String> = repos_to_process
+            .into_iter()
+            .filter(|repo| self.config.report.includes_repo(repo))
+            .collect();
+
+        self.github_client.check_auth()?;
 
         // Start main progress bar
         let total_repos = repos_to_process.len();
         let _main_pb = progress.start_report_generation(total_repos);
+        let mut partial = self.partial_run(since);
 
         for repo_name in &repos_to_process {
             let repo_pb = progress.start_repo_fetch(repo_name);
@@ -234,10 +616,25 @@ impl<'a> ReportGenerator<'a> {
             let cache_key =
                 generate_cache_key(&["issues", repo_name, &since.as_millisecond().to_string()]);
 
-            let cached_issues = if let Some(ref cache) = self.cache_manager {
-                match cache.get_github_response(&cache_key) {
+            let cached_issues = if let Some(issues) = partial.repos.get(repo_name) {
+                info!(
+                    "  Reusing {} issues of {} fetched before",
+                    issues.len(),
+                    repo_name
+                );
+                Some(issues.clone())
+            } else if let Some(ref cache) = self.cache_manager {
+                // Offline, the latest issues of any window are better than none
+                let cached = match cache.get_github_response(&cache_key) {
+                    Ok(None) if self.github_client.is_offline() => {
+                        cache.get_github_response(&latest_issues_key(repo_name))
+                    }
+                    cached => cached,
+                };
+                match cached {
                     Ok(Some(data)) => match serde_json::from_slice::<Vec<Issue>>(&data) {
-                        Ok(issues) => {
+                        Ok(mut issues) => {
+                            issues.retain(|issue| issue.updated_at >= since);
                             if !progress.is_interactive() {
                                 info!(
                                     "  Using cached data for {} ({} issues)",
@@ -278,14 +675,17 @@ impl<'a> ReportGenerator<'a> {
                         if !dry_run {
                             if let Some(ref cache) = self.cache_manager {
                                 let data = serde_json::to_vec(&issues).unwrap_or_default();
-                                if let Err(e) = cache.cache_github_response(&cache_key, &data) {
-                                    warn!("Failed to cache GitHub response: {}", e);
+                                for key in [&cache_key, &latest_issues_key(repo_name)] {
+                                    if let Err(e) = cache.cache_github_response(key, &data) {
+                                        warn!("Failed to cache GitHub response: {}", e);
+                                    }
                                 }
                             }
                         }
 
                         issues
                     }
+                    Err(e) if is_auth_expired(&e) => return Err(e),
                     Err(e) => {
                         let error_msg = format!("{}", e);
                         progress.report_repo_error(repo_pb.as_ref(), repo_name, &error_msg);
@@ -297,30 +697,38 @@ impl<'a> ReportGenerator<'a> {
             };
 
             progress.complete_repo_fetch(repo_pb.as_ref(), repo_name, issues.len());
+            if !dry_run && !partial.repos.contains_key(repo_name) {
+                partial.repos.insert(repo_name.clone(), issues.clone());
+                self.save_partial_run(&partial);
+            }
             all_issues.extend(issues);
         }
+        if let Some(until) = self.until {
+            all_issues.retain(|issue| issue.created_at < until);
+        }
+
+        // Review requests and mentions outside the discovered repositories,
+        // which are personal and left out of organization reports
+        let mut notifications = if dry_run || self.config.report.org.is_some() {
+            Vec::new()
+        } else {
+            let known: HashSet<_> = all_issues
+                .iter()
+                .filter_map(|issue| Some((issue.repository_name()?, issue.number)))
+                .collect();
+            let notifications = self.missed_notifications(since, &known, &mut errors)?;
+            let items = self.fetch_items(&notification_refs(&notifications), &mut errors)?;
+            all_issues.extend(items.into_iter().map(|(issue, _)| issue));
+            notifications
+        };
 
-        // TODO: Add include_mentions configuration option
-        let include_mentions: Vec<String> = vec![];
-        if !include_mentions.is_empty() {
-            info!("Fetching mentions for users: {:?}", include_mentions);
+        let mentions = if dry_run {
+            Vec::new()
+        } else {
+            self.included_mentions(since, &mut errors)?
+        };
 
-            for username in &include_mentions {
-                match self.fetch_user_mentions(username, since) {
-                    Ok(mut mentions) => {
-                        info!("  Found {} mentions for {}", mentions.len(), username);
-                        all_issues.append(&mut mentions);
-                    }
-                    Err(e) => {
-                        warn!("Failed to fetch mentions for {}: {}", username, e);
-                        errors.push(format!(
-                            "⚠️ Could not fetch mentions for {}: {}",
-                            username, e
-                        ));
-                    }
-                }
-            }
-        }
+        let filtered = self.drop_filtered_items(&mut all_issues, |issue| issue, &mut notifications);
 
         // Stop here if dry run
         if dry_run {
@@ -349,15 +757,44 @@ impl<'a> ReportGenerator<'a> {
                 content: String::new(),
                 timestamp: now,
                 estimated_cost: 0.0,
+                deadlines: Vec::new(),
+                items: Vec::new(),
+                seen_items: BTreeMap::new(),
+                repo_trust: None,
+                scores: None,
+                format: ReportFormat::Markdown,
+                totals: PeriodTotals::default(),
+                digest: ReportDigest::default(),
             });
         }
 
+        // Releases and discussions come from the activity feed, of the
+        // organization's repositories only in organization reports
+        let feed_events =
+            self.feed_events(lookback_days, since, now, &repos_to_process, &mut errors)?;
+        let mut releases = self.fetch_releases(&feed_events, &mut errors);
+        let mut discussions = self.fetch_discussions(
+            &self.filter_activity_events(&feed_events),
+            since,
+            &mut errors,
+        );
+
         // Group activities and run analysis for actual report generation
-        let activities = group_activities_by_repo(all_issues);
+        let activities = info_span!("filter").in_scope(|| group_activities_by_repo(all_issues));
+        let totals = PeriodTotals::of(&activities);
+        let comparison = self.comparison(totals);
+        let (mut activities, truncation) =
+            self.enforce_budget(&activities, current_user.as_deref());
+        self.fetch_conversation_details(&mut activities, now);
+        let awaiting_reply = self.awaiting_reply(&activities, current_user.as_deref(), since);
+        self.github_client.check_auth()?;
+        let (activities, low_priority) =
+            self.split_low_priority(&activities, current_user.as_deref());
 
         // Apply intelligent analysis
-        let analyzer = IntelligentAnalyzer::new(&self.config);
-        let analysis = analyzer.analyze(&activities);
+        let analyzer =
+            IntelligentAnalyzer::new(self.config).with_current_user(current_user.as_deref());
+        let mut analysis = info_span!("analysis").in_scope(|| analyzer.analyze(&activities));
 
         info!(
             "Intelligent analysis: {} prioritized items, {} action items",
@@ -365,506 +802,4415 @@ impl<'a> ReportGenerator<'a> {
             analysis.action_items.len()
         );
 
-        // Generate AI summary if Claude is available
-        let (ai_summary, ai_title, estimated_cost) = if let Some(claude) = &self.claude_client {
-            let ai_pb = progress.start_ai_summary();
-            // Include context from intelligent analysis
-            let context_prompt = Some(analysis.context_prompt.as_str());
-            match self.generate_ai_summary_with_context(claude, &activities, context_prompt) {
-                Ok((summary, title, cost)) => {
-                    progress.complete_ai_summary(ai_pb.as_ref(), cost);
-                    if !progress.is_interactive() {
-                        info!("Generated AI summary (estimated cost: ${:.4})", cost);
-                    }
-                    (Some(summary), Some(title), cost)
-                }
-                Err(e) => {
-                    warn!("Failed to generate AI summary: {}", e);
-                    errors.push(format!("⚠️ AI summarization failed: {}", e));
-                    (None, None, 0.0)
-                }
-            }
-        } else {
-            (None, None, 0.0)
-        };
-
-        let template = ReportTemplate::new(&self.config);
-        let content = template.render_with_intelligence(
+        let summaries = self.summarize(
             &activities,
-            since,
-            now,
-            &errors,
-            ai_summary.as_deref(),
-            &analysis,
+            &mut analysis,
+            current_user.as_deref(),
+            &mut releases,
+            &mut discussions,
+            comparison.as_ref(),
+            false,
+            &mut progress,
+            &mut errors,
         )?;
+        let projects = self.fetch_projects(&mut errors);
+        let upcoming = self.upcoming_milestones(&activities, now, &mut errors);
+        let reviewer_workload = self.reviewer_workload(&activities, now, &mut errors);
+        self.github_client.check_auth()?;
+        errors.extend(self.offline_warnings());
+        let estimated_cost = summaries.cost;
 
-        let title = ai_title.unwrap_or_else(|| self.generate_title(since, now, &activities));
+        let template = self.report_template(
+            &activities,
+            ReportParts {
+                current_user: current_user.clone(),
+                ci_failures: summaries.ci_failures,
+                ci_statuses: self.ci_statuses(&activities),
+                thread_updates: summaries.thread_updates,
+                org_summaries: summaries.org_summaries,
+                notifications,
+                mentions,
+                awaiting_reply,
+                escalations: summaries.escalations,
+                goal_check_ins: summaries.goal_check_ins,
+                upcoming,
+                reviewer_workload,
+                releases,
+                discussions,
+                projects,
+                team: Vec::new(),
+                low_priority,
+                truncation,
+                filtered,
+                summarized_by: summaries.summarized_by,
+                rate_limit: self.github_client.rate_limit(),
+                feed_truncated_at: None,
+                comparison,
+            },
+        );
+        let title = summaries
+            .ai_title
+            .unwrap_or_else(|| self.generate_title(since, now, &activities));
+        let content = if self.config.report.format == ReportFormat::Json {
+            JsonReport {
+                title: &title,
+                user: current_user.as_deref(),
+                warnings: &errors,
+                ai_summary: summaries.ai_summary.as_deref(),
+                summarized_by: template.summarized_by.as_deref(),
+                estimated_cost,
+                filtered: Some(&template.filtered),
+                ..JsonReport::new(&activities, &analysis, since, now)
+            }
+            .render()?
+        } else {
+            template.render_with_intelligence(
+                &activities,
+                since,
+                now,
+                &errors,
+                summaries.ai_summary.as_deref(),
+                &analysis,
+            )?
+        };
+        self.clear_partial_run();
 
         Ok(Report {
             title,
             content,
             timestamp: now,
             estimated_cost,
+            deadlines: self.deadlines(&activities, now),
+            items: covered_items(&activities, now),
+            seen_items: seen_items(&activities, now),
+            repo_trust: summaries.repo_trust,
+            scores: Some(explain_scores(&analysis, current_user.as_deref(), now)),
+            format: self.config.report.format,
+            totals,
+            digest: self.digest(&activities, &analysis, summaries.ai_summary),
         })
     }
 
-    fn discover_active_repositories(&self, since: &Timestamp) -> Result<Vec<String>> {
-        info!(
-            "Discovering repositories based on user activity since {}",
-            since.strftime("%Y-%m-%d %H:%M")
-        );
+    /// What Claude adds to a report, the same in both report pipelines
+    ///
+    /// The repositories of `activities`, releases, and discussions go through
+    /// `claude.confirm_new_repos` and the private repository check first, and
+    /// only what passes is sent: the items scoring at least
+    /// `intelligence.min_score_for_ai`, with the diffs of their top PRs, and
+    /// the releases and discussions of the remaining repositories. Replaying
+    /// a snapshot leaves the tracked threads as they are and fetches no diffs.
+    #[allow(clippy::too_many_arguments)]
+    fn summarize(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        analysis: &mut AnalysisResult,
+        current_user: Option<&str>,
+        releases: &mut [ReleaseNote],
+        discussions: &mut [DiscussionNote],
+        comparison: Option<&PeriodComparison>,
+        replay: bool,
+        progress: &mut ProgressReporter,
+        errors: &mut Vec<String>,
+    ) -> Result<Summaries> {
+        let analyzer = IntelligentAnalyzer::new(self.config).with_current_user(current_user);
+        let feed_repos: BTreeSet<&str> = releases
+            .iter()
+            .map(|note| note.repo.as_str())
+            .chain(discussions.iter().map(|note| note.repo.as_str()))
+            .collect();
+        let (excluded_repos, repo_trust) =
+            self.confirm_new_repos(&with_repos(activities, &feed_repos))?;
+        let ai_activities =
+            analyzer.above_ai_threshold(&without_repos(activities, &excluded_repos));
+        let llm = self.claude_for(&with_repos(
+            &ai_activities,
+            &without(&feed_repos, &excluded_repos),
+        ))?;
+        let claude = llm.filter(|_| !ai_activities.is_empty());
 
-        let mut discovered_repos = std::collections::HashSet::new();
-        
-        // Get the current user
-        let username = self.github_client.get_current_user()
-            .context("Failed to get current user")?;
-        
-        // Search for recent activity in different ways
-        let searches = vec![
-            format!("involves:{} updated:>{}", username, since.strftime("%Y-%m-%d")),
-            format!("author:{} updated:>{}", username, since.strftime("%Y-%m-%d")),
-            format!("assignee:{} updated:>{}", username, since.strftime("%Y-%m-%d")),
-            format!("mentions:{} updated:>{}", username, since.strftime("%Y-%m-%d")),
-        ];
+        let (ci_failures, ci_cost) = self.ci_failures(claude, activities, &ai_activities);
+        let (thread_updates, thread_cost) = match replay {
+            true => (Vec::new(), 0.0),
+            false => self.thread_updates(claude, &ai_activities, analysis),
+        };
+        let (org_summaries, org_cost) = self.org_summaries(claude, &ai_activities);
+        let (goal_check_ins, goals_cost) = self.goals_check_in(claude, &ai_activities, errors);
+        let nudges_cost = self.draft_nudges(
+            claude,
+            &ai_activities,
+            &mut analysis.waiting_on_you,
+            current_user,
+        );
+        let (escalations, escalations_cost) = self.escalations(claude, activities, &ai_activities);
+        let releases_cost = self.summarize_releases(llm, releases, &excluded_repos);
+        let discussions_cost = self.summarize_discussions(llm, discussions, &excluded_repos);
+        let mut cost = ci_cost
+            + thread_cost
+            + org_cost
+            + goals_cost
+            + nudges_cost
+            + escalations_cost
+            + releases_cost
+            + discussions_cost;
 
-        for query in searches {
-            match self.search_repositories(&query) {
-                Ok(repos) => {
-                    for repo in repos {
-                        discovered_repos.insert(repo);
+        let claude = claude.filter(|_| self.config.report.sections.ai_summary);
+        let (ai_summary, ai_title, summarized_by) = match claude {
+            Some(claude) => {
+                let ai_pb = progress.start_ai_summary();
+                let context = self.summary_context(&analyzer, &ai_activities, replay);
+                match self.generate_ai_summary_with_context(
+                    claude,
+                    &ai_activities,
+                    Some(context.as_str()),
+                    comparison,
+                ) {
+                    Ok((summary, title, summary_cost)) => {
+                        progress.complete_ai_summary(ai_pb.as_ref(), summary_cost);
+                        cost += summary_cost;
+                        (Some(summary), Some(title), Some(self.summarized_by(claude)))
+                    }
+                    Err(e) => {
+                        warn!("Failed to generate AI summary: {}", e);
+                        errors.push(format!("⚠️ AI summarization failed: {}", e));
+                        (None, None, None)
                     }
-                }
-                Err(e) => {
-                    warn!("Search failed for query '{}': {}", query, e);
-                    continue;
                 }
             }
+            None => (None, None, None),
+        };
+
+        Ok(Summaries {
+            ai_summary,
+            ai_title,
+            summarized_by,
+            ci_failures,
+            thread_updates,
+            org_summaries,
+            goal_check_ins,
+            escalations,
+            repo_trust,
+            cost,
+        })
+    }
+
+    /// The context of the AI summary: the analysis of `ai_activities`, and
+    /// the changes and patches of their top PRs unless replaying a snapshot
+    fn summary_context(
+        &self,
+        analyzer: &IntelligentAnalyzer,
+        ai_activities: &BTreeMap<String, crate::github::RepoActivity>,
+        replay: bool,
+    ) -> String {
+        let analysis = analyzer.analyze(ai_activities);
+        let mut context = analysis.context_prompt.clone();
+        let diffs = match replay {
+            true => Vec::new(),
+            false => self.prefetch_diffs(&analysis, ai_activities),
+        };
+        if diffs.is_empty() {
+            return context;
         }
 
-        let mut repos: Vec<String> = discovered_repos.into_iter().collect();
-        repos.sort();
-        
-        info!("Discovered {} repositories with recent activity", repos.len());
-        for repo in &repos {
-            info!("  {}", repo);
+        let changes: Vec<_> = diffs
+            .iter()
+            .map(|(repo, pr, diff)| (repo.as_str(), pr, diff))
+            .collect();
+        context.push_str(&format!("\n{}", pr_changes_section(&changes)));
+        let patch_tokens = self.config.intelligence.diff_patch_tokens;
+        for (repo, pr, diff) in &changes {
+            let patches = diff_patches_section(diff, patch_tokens);
+            if !patches.is_empty() {
+                context.push_str(&format!(
+                    "\nPatches of PR #{} in {}:\n{}",
+                    pr.number, repo, patches
+                ));
+            }
         }
-        
-        Ok(repos)
+        context
     }
-    
-    fn search_repositories(&self, query: &str) -> Result<Vec<String>> {
-        // Use GitHub search to find repositories
-        let encoded_query = query
-            .replace(" ", "%20")
-            .replace(":", "%3A")
-            .replace(">", "%3E");
-        let endpoint = format!("search/issues?q={}&per_page=100", encoded_query);
-        
-        // Execute the search using gh CLI
-        let output = std::process::Command::new("gh")
-            .args(&["api", &endpoint])
-            .output()
-            .context("Failed to execute gh command for repository search")?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("GitHub search failed: {}", stderr));
-        }
+    /// Fetch comments, reviews, and the head commits of PRs with requested
+    /// changes, needed to tell whether open items are waiting on their
+    /// author, or on the user
+    ///
+    /// Only items old enough to have been idle for
+    /// `settings.waiting_on_author_days` or `settings.waiting_on_you_days` are
+    /// considered, and data that is already present is not fetched again.
+    fn fetch_conversation_details(
+        &self,
+        activities: &mut BTreeMap<String, crate::github::RepoActivity>,
+        now: Timestamp,
+    ) {
+        let settings = &self.config.settings;
+        let Some(threshold_days) = [
+            settings.waiting_on_author_days,
+            settings.waiting_on_you_days,
+        ]
+        .into_iter()
+        .filter(|&days| days > 0)
+        .min() else {
+            return;
+        };
+        let cutoff = now - (threshold_days as i64 * 24).hours();
 
-        let stdout = String::from_utf8(output.stdout)
-            .context("Invalid UTF-8 in search output")?;
+        for (repo_name, activity) in activities.iter_mut() {
+            let candidates: Vec<Issue> = activity
+                .new_issues
+                .iter()
+                .chain(&activity.updated_issues)
+                .chain(&activity.new_prs)
+                .chain(&activity.updated_prs)
+                .filter(|i| i.state == crate::github::IssueState::Open && i.created_at <= cutoff)
+                .cloned()
+                .collect();
 
-        #[derive(serde::Deserialize)]
-        struct SearchResult {
-            items: Vec<SearchItem>,
-        }
+            for issue in candidates {
+                let has_comments = activity
+                    .new_comments
+                    .iter()
+                    .any(|(i, _)| i.number == issue.number);
+                if !has_comments {
+                    match self
+                        .github_client
+                        .fetch_comments(repo_name, issue.number, None)
+                    {
+                        Ok(comments) => activity.new_comments.push((issue.clone(), comments)),
+                        Err(e) if is_auth_expired(&e) => return,
+                        Err(e) => {
+                            warn!(
+                                "Failed to fetch comments for {}#{}: {}",
+                                repo_name, issue.number, e
+                            );
+                            continue;
+                        }
+                    }
+                }
+
+                if issue.is_pull_request && !activity.reviews.contains_key(&issue.number) {
+                    match self.github_client.fetch_pr_reviews(repo_name, issue.number) {
+                        Ok(reviews) => {
+                            activity.reviews.insert(issue.number, reviews);
+                        }
+                        Err(e) if is_auth_expired(&e) => return,
+                        Err(e) => warn!(
+                            "Failed to fetch reviews for {}#{}: {}",
+                            repo_name, issue.number, e
+                        ),
+                    }
+                }
 
-        #[derive(serde::Deserialize)]
-        struct SearchItem {
-            repository_url: String,
+                // Pushes since a changes request show in the head commit
+                let changes_requested =
+                    activity.reviews.get(&issue.number).is_some_and(|reviews| {
+                        reviews
+                            .iter()
+                            .any(|r| r.state == crate::github::ReviewState::ChangesRequested)
+                    });
+                if changes_requested && !activity.head_shas.contains_key(&issue.number) {
+                    match self
+                        .github_client
+                        .fetch_pr_head_sha(repo_name, issue.number)
+                    {
+                        Ok(Some(sha)) => {
+                            activity.head_shas.insert(issue.number, sha);
+                        }
+                        Ok(None) => {}
+                        Err(e) if is_auth_expired(&e) => return,
+                        Err(e) => warn!(
+                            "Failed to fetch the head commit of {}#{}: {}",
+                            repo_name, issue.number, e
+                        ),
+                    }
+                }
+            }
         }
+    }
 
-        let result: SearchResult = serde_json::from_str(&stdout)
-            .context("Failed to parse search results")?;
+    /// Comments since `since` waiting on a reply from the user
+    ///
+    /// Inline review comments, which the issue comments don't include, are
+    /// fetched for the open PRs in the activity.
+    fn awaiting_reply(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        user: Option<&str>,
+        since: Timestamp,
+    ) -> Vec<AwaitingReply> {
+        let Some(user) = user else {
+            return Vec::new();
+        };
 
-        let mut repos = std::collections::HashSet::new();
-        for item in result.items {
-            // Extract repo name from repository_url: https://api.github.com/repos/owner/name
-            if let Some(repo_name) = item.repository_url.strip_prefix("https://api.github.com/repos/") {
-                repos.insert(repo_name.to_string());
+        let mut found = Vec::new();
+        for (repo_name, activity) in activities {
+            let mut items: Vec<&Issue> = activity.new_comments.iter().map(|(i, _)| i).collect();
+            for pr in activity.new_prs.iter().chain(&activity.updated_prs) {
+                if !items.iter().any(|i| i.number == pr.number) {
+                    items.push(pr);
+                }
             }
-        }
 
-        Ok(repos.into_iter().collect())
-    }
+            for issue in items {
+                let comments = activity
+                    .new_comments
+                    .iter()
+                    .find(|(i, _)| i.number == issue.number)
+                    .map_or(&[][..], |(_, comments)| comments.as_slice());
+                let review_comments =
+                    if issue.is_pull_request && issue.state == crate::github::IssueState::Open {
+                        match self
+                            .github_client
+                            .fetch_pr_review_comments(repo_name, issue.number)
+                        {
+                            Ok(review_comments) => review_comments,
+                            Err(e) if is_auth_expired(&e) => return found,
+                            Err(e) => {
+                                warn!(
+                                    "Failed to fetch review comments for {}#{}: {}",
+                                    repo_name, issue.number, e
+                                );
+                                Vec::new()
+                            }
+                        }
+                    } else {
+                        Vec::new()
+                    };
 
-    fn fetch_user_mentions(&self, _username: &str, since: Timestamp) -> Result<Vec<Issue>> {
-        self.github_client
-            .fetch_mentions(since)
-            .context("Failed to fetch user mentions")
+                found.extend(find_awaiting_reply(
+                    repo_name,
+                    issue,
+                    comments,
+                    &review_comments,
+                    user,
+                    since,
+                ));
+            }
+        }
+        found.sort_by_key(|reply| reply.created_at);
+        found
     }
 
-    fn generate_ai_summary(
+    /// Local branches from `--with-git`, linked to the report items
+    fn local_work(
         &self,
-        claude: &ClaudeInterface,
         activities: &BTreeMap<String, crate::github::RepoActivity>,
-    ) -> Result<(String, String, f32)> {
-        self.generate_ai_summary_with_context(claude, activities, None)
+    ) -> Vec<LocalWork> {
+        let Some(local_repo) = &self.local_repo else {
+            return Vec::new();
+        };
+
+        let repo_name = local_repo.github_repo(self.config.github_host());
+        if repo_name.is_none() {
+            warn!(
+                "Could not determine the GitHub repository for {}",
+                local_repo.path().display()
+            );
+        }
+
+        match local_repo.branches() {
+            Ok(branches) => correlate_local_work(
+                repo_name.as_deref(),
+                branches,
+                activities,
+                self.config.github_host(),
+            ),
+            Err(e) => {
+                warn!("Failed to read local git history: {}", e);
+                Vec::new()
+            }
+        }
     }
 
-    fn generate_ai_summary_with_context(
+    /// Failed workflow runs of open PRs, with log excerpts and explanations
+    ///
+    /// Only runs with `--ci-logs`. Failures of every PR in `activities` are
+    /// listed, but only the logs of PRs in `ai_activities` are explained.
+    /// Returns the failures and the estimated cost of explaining them.
+    fn ci_failures(
         &self,
-        claude: &ClaudeInterface,
+        claude: Option<&dyn LlmBackend>,
         activities: &BTreeMap<String, crate::github::RepoActivity>,
-        context: Option<&str>,
-    ) -> Result<(String, String, f32)> {
-        // Generate the prompt
-        let prompt = summarize_activities_prompt(activities, context);
-
-        // Generate cache key for this prompt
-        let prompt_hash = {
-            use sha2::{Digest, Sha256};
-            let mut hasher = Sha256::new();
-            hasher.update(prompt.as_bytes());
-            format!("{:x}", hasher.finalize())
-        };
+        ai_activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> (Vec<CiFailure>, f32) {
+        let mut failures = Vec::new();
+        let mut cost = 0.0;
+        if !self.ci_logs {
+            return (failures, cost);
+        }
 
-        let cache_key = generate_cache_key(&[
-            "claude_summary",
-            &prompt_hash[..16], // Use first 16 chars of hash
-        ]);
+        'repos: for (repo_name, activity) in activities {
+            let open_prs = activity
+                .new_prs
+                .iter()
+                .chain(&activity.updated_prs)
+                .filter(|pr| pr.state == crate::github::IssueState::Open);
 
-        // Try to get from cache
-        if let Some(ref cache) = self.cache_manager {
-            if let Ok(Some(cached)) = cache.get_claude_response(&cache_key) {
-                // Parse cached response (format: "TITLE\n---\nSUMMARY\n---\nCOST")
-                let parts: Vec<&str> = cached.split("\n---\n").collect();
-                if parts.len() == 3 {
-                    let title = parts[0].to_string();
-                    let summary = parts[1].to_string();
-                    let cost: f32 = parts[2].parse().unwrap_or(0.0);
-                    info!("Using cached AI summary (saved cost: ${:.4})", cost);
-                    return Ok((summary, title, 0.0)); // Return 0 cost since we didn't call API
+            for pr in open_prs {
+                // Logs of PRs left out of AI summaries are not explained
+                let claude = claude.filter(|_| {
+                    ai_activities.get(repo_name).is_some_and(|activity| {
+                        activity
+                            .new_prs
+                            .iter()
+                            .chain(&activity.updated_prs)
+                            .any(|ai_pr| ai_pr.number == pr.number)
+                    })
+                });
+                let runs = match self.github_client.fetch_failed_runs(repo_name, pr.number) {
+                    Ok(runs) => runs,
+                    Err(e) if is_auth_expired(&e) => break 'repos,
+                    Err(e) => {
+                        warn!(
+                            "Failed to fetch workflow runs for {}#{}: {}",
+                            repo_name, pr.number, e
+                        );
+                        continue;
+                    }
+                };
+
+                // One entry per workflow, for the most recent run
+                let mut seen_workflows = std::collections::HashSet::new();
+                for run in runs {
+                    if !seen_workflows.insert(run.name.clone()) {
+                        continue;
+                    }
+                    if failures.len() >= MAX_CI_FAILURES {
+                        break 'repos;
+                    }
+
+                    let excerpt = match self.github_client.fetch_failed_run_log(repo_name, run.id) {
+                        Ok(log) => extract_error_region(&log),
+                        Err(e) => {
+                            warn!("Failed to fetch log for run {}: {}", run.id, e);
+                            None
+                        }
+                    };
+
+                    let workflow = run.name.as_deref().unwrap_or("CI");
+                    let explanation = match (claude, &excerpt) {
+                        (Some(claude), Some(excerpt)) => {
+                            let prompt = explain_ci_failure_prompt(workflow, &pr.title, excerpt);
+                            let model = self
+                                .config
+                                .claude
+                                .resolve_model(&self.config.claude.secondary_model);
+                            let request = MessagesRequest::new(
+                                model.clone(),
+                                vec![Message::user(prompt.clone())],
+                            )
+                            .with_max_tokens(100);
+                            match claude.messages(request) {
+                                Ok(response) => {
+                                    cost += self.config.claude.pricing().estimate_cost(
+                                        &model,
+                                        estimate_tokens(&prompt),
+                                        response.usage.output_tokens,
+                                    );
+                                    Some(response.get_text().trim().to_string())
+                                }
+                                Err(e) => {
+                                    warn!("Failed to explain CI failure: {}", e);
+                                    None
+                                }
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    failures.push(CiFailure {
+                        repo: repo_name.clone(),
+                        pr: pr.clone(),
+                        run,
+                        excerpt,
+                        explanation,
+                    });
                 }
             }
         }
 
-        // Estimate tokens
-        let input_tokens = estimate_tokens(&prompt) + estimate_tokens(&system_prompt());
+        (failures, cost)
+    }
 
-        // Create request
-        let model = resolve_model_alias(&self.config.claude.primary_model);
-        let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt)])
-            .with_system(system_prompt())
-            .with_max_tokens(4000);
+    /// What changed in hot threads that were already in a previous report
+    ///
+    /// The summary of each hot thread is stored in the cache's issue contexts.
+    /// When a thread shows up again, only the comments posted since are sent
+    /// to Claude together with the stored summary. Threads seen for the first
+    /// time are summarized so the next report can build on them.
+    fn thread_updates(
+        &self,
+        claude: Option<&dyn LlmBackend>,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        analysis: &AnalysisResult,
+    ) -> (Vec<ThreadUpdate>, f32) {
+        let mut updates = Vec::new();
+        let mut cost = 0.0;
+        let (Some(claude), Some(cache)) = (claude, &self.cache_manager) else {
+            return (updates, cost);
+        };
 
-        // Send request
-        let response = match claude.messages(request) {
-            Ok(resp) => resp,
-            Err(e) => {
-                // Log the actual error for debugging
-                warn!("Claude API error details: {:#}", e);
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let pricing = self.config.claude.pricing();
 
-                let error_str = e.to_string();
+        // Hot threads are the top prioritized items with new discussion
+        let hot_threads = analysis.prioritized_issues.iter().filter_map(|item| {
+            let (issue, comments) = activities
+                .get(&item.repo)?
+                .new_comments
+                .iter()
+                .find(|(issue, _)| issue.number == item.issue.number)?;
+            (!comments.is_empty()).then_some((&item.repo, issue, comments))
+        });
 
-                // Provide helpful error messages based on the error type
-                if error_str.contains("ANTHROPIC_API_KEY") {
-                    return Err(anyhow::anyhow!("ANTHROPIC_API_KEY environment variable is not set. Please set it to use AI summarization."));
-                } else if error_str.contains("invalid x-api-key")
-                    || error_str.contains("authentication_error")
-                {
-                    return Err(anyhow::anyhow!("Invalid ANTHROPIC_API_KEY. Please check that your API key is correct and active."));
-                } else if error_str.contains("rate_limit") {
-                    return Err(anyhow::anyhow!(
-                        "Claude API rate limit exceeded. Please try again later."
-                    ));
-                } else if error_str.contains("overloaded") {
-                    return Err(anyhow::anyhow!(
-                        "Claude API is currently overloaded. Please try again in a few moments."
-                    ));
+        for (repo, issue, comments) in hot_threads.take(MAX_TRACKED_THREADS) {
+            let previous = match cache.get_issue_context(repo, issue.number) {
+                Ok(previous) => previous,
+                Err(e) => {
+                    warn!(
+                        "Failed to read context for {}#{}: {}",
+                        repo, issue.number, e
+                    );
+                    None
                 }
+            };
+            let last_processed = previous.as_ref().and_then(|c| c.last_processed_comment_id);
+            let new_comments = comments_since(comments, last_processed);
+            if new_comments.is_empty() {
+                continue;
+            }
+            let bodies: Vec<String> = new_comments
+                .iter()
+                .map(|c| format!("@{}: {}", c.author.login, c.body))
+                .collect();
 
-                return Err(e).context("Failed to get summary from Claude");
+            let prompt = match &previous {
+                Some(previous) => thread_update_prompt(&issue.title, &previous.summary, &bodies),
+                None => summarize_context_prompt(
+                    &issue.title,
+                    issue.body.as_deref().unwrap_or_default(),
+                    &bodies,
+                ),
+            };
+            let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+                .with_max_tokens(400);
+            let response = match claude.messages(request) {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!("Failed to summarize {}#{}: {}", repo, issue.number, e);
+                    continue;
+                }
+            };
+            cost += pricing.estimate_cost(
+                &model,
+                estimate_tokens(&prompt),
+                response.usage.output_tokens,
+            );
+
+            let text = response.get_text();
+            let summary = match (&previous, parse_thread_update(&text)) {
+                (Some(_), Some((change, summary))) => {
+                    updates.push(ThreadUpdate {
+                        repo: repo.clone(),
+                        issue_number: issue.number,
+                        change,
+                    });
+                    summary
+                }
+                (Some(_), None) => {
+                    warn!(
+                        "Unexpected thread update format for {}#{}",
+                        repo, issue.number
+                    );
+                    continue;
+                }
+                (None, _) => text.trim().to_string(),
+            };
+
+            let context = IssueContext {
+                issue_number: issue.number,
+                repo: repo.clone(),
+                summary,
+                key_points: previous.map(|p| p.key_points).unwrap_or_default(),
+                last_processed_comment_id: new_comments.iter().map(|c| c.id).max(),
+                cached_at: Timestamp::now(),
+            };
+            if let Err(e) = cache.cache_issue_context(repo, issue.number, &context) {
+                warn!(
+                    "Failed to store context for {}#{}: {}",
+                    repo, issue.number, e
+                );
             }
-        };
+        }
 
-        let summary = response.get_text();
-        let output_tokens = response.usage.output_tokens;
+        (updates, cost)
+    }
 
-        // Generate title from summary
-        let title_prompt = generate_title_prompt(&summary);
-        let title_request = MessagesRequest::new(
-            resolve_model_alias(&self.config.claude.secondary_model),
-            vec![Message::user(title_prompt)],
-        )
-        .with_max_tokens(100);
+    /// Diffs of the top `intelligence.diff_prefetch` prioritized open PRs,
+    /// fetched `intelligence.diff_concurrency` at a time
+    ///
+    /// Only PRs in `activities` are considered. PRs whose diff can't be
+    /// fetched are left out.
+    fn prefetch_diffs(
+        &self,
+        analysis: &AnalysisResult,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> Vec<(String, Issue, PrDiff)> {
+        let settings = &self.config.intelligence;
+        let prs: Vec<_> = analysis
+            .prioritized_issues
+            .iter()
+            .filter(|item| {
+                item.issue.is_pull_request
+                    && item.issue.state == IssueState::Open
+                    && activities.contains_key(&item.repo)
+            })
+            .take(settings.diff_prefetch)
+            .collect();
+        if prs.is_empty() {
+            return Vec::new();
+        }
+        if !self.github_client.allows_optional_fetches() {
+            info!("Skipping PR diffs to save the remaining GitHub API quota");
+            return Vec::new();
+        }
 
-        let title_response = claude
-            .messages(title_request)
-            .context("Failed to generate title from Claude")?;
+        let _span = info_span!("diff_fetch").entered();
+        let next = AtomicUsize::new(0);
+        let (github, cache) = (&self.github_client, self.cache_manager.as_ref());
+        let mut fetched: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..settings.diff_concurrency.clamp(1, prs.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut fetched = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = prs.get(i) else {
+                                break;
+                            };
+                            match cached_pr_diff(
+                                github,
+                                cache,
+                                &item.repo,
+                                item.issue.number,
+                                settings.max_diff_bytes,
+                            ) {
+                                Ok(diff) => fetched.push((i, diff)),
+                                Err(e) => warn!(
+                                    "Failed to fetch the diff of {}#{}: {}",
+                                    item.repo, item.issue.number, e
+                                ),
+                            }
+                        }
+                        fetched
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("diff fetch worker panicked"))
+                .collect()
+        });
 
-        let title = title_response.get_text().trim().to_string();
+        fetched.sort_by_key(|(i, _)| *i);
+        fetched
+            .into_iter()
+            .map(|(i, diff)| (prs[i].repo.clone(), prs[i].issue.clone(), diff))
+            .collect()
+    }
 
-        // Calculate total cost
-        let summary_cost = estimate_cost(&model, input_tokens, output_tokens);
-        let title_cost = estimate_cost(
-            &self.config.claude.secondary_model,
-            estimate_tokens(&generate_title_prompt(&summary)),
-            title_response.usage.output_tokens,
-        );
+    /// Short AI summaries per organization, for `report.group_by = "org"`
+    fn org_summaries(
+        &self,
+        claude: Option<&dyn LlmBackend>,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> (BTreeMap<String, String>, f32) {
+        let mut summaries = BTreeMap::new();
+        let mut cost = 0.0;
+        let Some(claude) = claude else {
+            return (summaries, cost);
+        };
+        if self.config.report.group_by != GroupBy::Org {
+            return (summaries, cost);
+        }
 
-        let total_cost = summary_cost + title_cost;
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let pricing = self.config.claude.pricing();
 
-        // Cache the result
-        if let Some(ref cache) = self.cache_manager {
-            let cached_data = format!("{}\n---\n{}\n---\n{}", title, summary, total_cost);
-            if let Err(e) = cache.cache_claude_response(&cache_key, &cached_data) {
-                warn!("Failed to cache Claude response: {}", e);
+        for group in group_by_org(activities) {
+            let prompt = summarize_org_prompt(group.org, &group.repos);
+            let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+                .with_max_tokens(200);
+            match claude.messages(request) {
+                Ok(response) => {
+                    cost += pricing.estimate_cost(
+                        &model,
+                        estimate_tokens(&prompt),
+                        response.usage.output_tokens,
+                    );
+                    summaries.insert(
+                        group.org.to_string(),
+                        response.get_text().trim().to_string(),
+                    );
+                }
+                Err(e) => warn!("Failed to summarize activity in {}: {}", group.org, e),
             }
         }
 
-        Ok((summary, title, total_cost))
+        (summaries, cost)
     }
 
-    fn generate_title(
+    /// Claude's check-in on `report.goals`, when any are configured
+    fn goals_check_in(
         &self,
-        since: Timestamp,
-        now: Timestamp,
+        claude: Option<&dyn LlmBackend>,
         activities: &BTreeMap<String, crate::github::RepoActivity>,
-    ) -> String {
-        let date_range =
-            if since.strftime("%Y-%m-%d").to_string() == now.strftime("%Y-%m-%d").to_string() {
-                format!("Daily Report - {}", now.strftime("%Y-%m-%d"))
-            } else {
-                format!(
-                    "Report - {} to {}",
-                    since.strftime("%Y-%m-%d"),
-                    now.strftime("%Y-%m-%d")
-                )
-            };
-
-        let total_items: usize = activities
-            .values()
-            .map(|a| {
-                a.new_issues.len() + a.updated_issues.len() + a.new_prs.len() + a.updated_prs.len()
-            })
-            .sum();
+        errors: &mut Vec<String>,
+    ) -> (Vec<GoalCheckIn>, f32) {
+        let Some(claude) = claude else {
+            return (Vec::new(), 0.0);
+        };
+        let goals = match self.config.report.goals() {
+            Ok(goals) => goals,
+            Err(e) => {
+                warn!("Failed to load goals: {:#}", e);
+                errors.push(format!("⚠️ Could not load goals: {:#}", e));
+                return (Vec::new(), 0.0);
+            }
+        };
+        if goals.is_empty() {
+            return (Vec::new(), 0.0);
+        }
 
-        if total_items > 0 {
-            format!("{} ({} items)", date_range, total_items)
-        } else {
-            date_range
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.primary_model);
+        let prompt = goals_check_in_prompt(&goals, activities);
+        let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+            .with_max_tokens(1000);
+        match claude.messages(request) {
+            Ok(response) => {
+                let cost = self.config.claude.pricing().estimate_cost(
+                    &model,
+                    estimate_tokens(&prompt),
+                    response.usage.output_tokens,
+                );
+                (parse_goal_check_in(&goals, &response.get_text()), cost)
+            }
+            Err(e) => {
+                warn!("Failed to check in on goals: {}", e);
+                (Vec::new(), 0.0)
+            }
         }
     }
 
-    /// Filter activity events using the same logic as the activity command
-    fn filter_activity_events<'e>(
+    /// Have Claude draft a reply for the items waiting longest on the user
+    ///
+    /// Only with `settings.draft_nudges`, and only for items in `activities`,
+    /// which leaves out repositories kept from AI summaries.
+    fn draft_nudges(
         &self,
-        events: &'e [crate::github::ActivityEvent],
-    ) -> Vec<&'e crate::github::ActivityEvent> {
-        let default_included_types = vec![
-            "IssueCommentEvent".to_string(),
-            "PullRequestEvent".to_string(),
-            "IssuesEvent".to_string(),
-            "PullRequestReviewCommentEvent".to_string(),
-            "PullRequestReviewEvent".to_string(),
-        ];
-
-        events
-            .iter()
-            .filter(|event| {
-                // Check if this event type should be included
-                if !default_included_types.contains(&event.event_type) {
-                    return false;
-                }
+        claude: Option<&dyn LlmBackend>,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        waiting: &mut [WaitingOnYou],
+        user: Option<&str>,
+    ) -> f32 {
+        let (Some(claude), Some(user), true) = (claude, user, self.config.settings.draft_nudges)
+        else {
+            return 0.0;
+        };
 
-                // Special filtering for IssuesEvent - exclude 'labeled' actions
-                if event.event_type == "IssuesEvent" {
-                    if let Some(action) = event.payload.get("action").and_then(|a| a.as_str()) {
-                        if action == "labeled" || action == "unlabeled" {
-                            return false;
-                        }
-                    }
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let pricing = self.config.claude.pricing();
+        let mut cost = 0.0;
+        for item in waiting.iter_mut().take(MAX_NUDGES) {
+            let Some(activity) = activities.get(&item.repo) else {
+                continue;
+            };
+            let comments = activity
+                .new_comments
+                .iter()
+                .find(|(i, _)| i.number == item.issue.number)
+                .map(|(_, comments)| comments.as_slice())
+                .unwrap_or_default();
+            let prompt =
+                nudge_reply_prompt(&item.repo, &item.issue, comments, user, item.idle_days);
+            let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+                .with_max_tokens(300);
+            match claude.messages(request) {
+                Ok(response) => {
+                    cost += pricing.estimate_cost(
+                        &model,
+                        estimate_tokens(&prompt),
+                        response.usage.output_tokens,
+                    );
+                    item.nudge = Some(response.get_text().trim().to_string());
                 }
+                Err(e) => warn!(
+                    "Failed to draft a reply for {}#{}: {}",
+                    item.repo, item.issue.number, e
+                ),
+            }
+        }
 
-                true
-            })
-            .collect()
+        cost
     }
 
-    /// Extract unique issue/PR references from activity events
-    fn extract_issue_references(
+    /// Threads needing de-escalation, as `intelligence.escalation` finds them
+    ///
+    /// With `claude`, the secondary model reads the busiest threads of
+    /// `ai_activities` in one request. Threads it doesn't read are checked by
+    /// keywords, as are all of them when Claude is unavailable or fails.
+    fn escalations(
         &self,
-        events: &[&crate::github::ActivityEvent],
-    ) -> Vec<(String, u32)> {
-        use std::collections::HashSet;
-        let mut refs = HashSet::new();
-
-        for event in events {
-            let repo_name = &event.repo.name;
-
-            match event.event_type.as_str() {
-                "PullRequestEvent" => {
-                    if let Some(pr_number) = event
-                        .payload
-                        .get("pull_request")
-                        .and_then(|pr| pr.get("number"))
-                        .and_then(|n| n.as_u64())
-                    {
-                        refs.insert((repo_name.clone(), pr_number as u32));
-                    }
-                }
-                "IssuesEvent" | "IssueCommentEvent" => {
-                    if let Some(issue_number) = event
-                        .payload
-                        .get("issue")
-                        .and_then(|issue| issue.get("number"))
-                        .and_then(|n| n.as_u64())
-                    {
-                        refs.insert((repo_name.clone(), issue_number as u32));
-                    }
-                }
-                "PullRequestReviewCommentEvent" | "PullRequestReviewEvent" => {
-                    if let Some(pr_number) = event
-                        .payload
-                        .get("pull_request")
-                        .and_then(|pr| pr.get("number"))
-                        .and_then(|n| n.as_u64())
-                    {
-                        refs.insert((repo_name.clone(), pr_number as u32));
-                    }
-                }
-                _ => {
-                    // For other event types, try to extract issue/PR from payload
-                    if let Some(issue_number) = event
-                        .payload
-                        .get("issue")
-                        .and_then(|issue| issue.get("number"))
-                        .and_then(|n| n.as_u64())
-                    {
-                        refs.insert((repo_name.clone(), issue_number as u32));
-                    }
-                }
-            }
+        claude: Option<&dyn LlmBackend>,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        ai_activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> (Vec<Escalation>, f32) {
+        let claude = match self.config.intelligence.escalation {
+            EscalationDetection::Off => return (Vec::new(), 0.0),
+            EscalationDetection::Keywords => None,
+            EscalationDetection::Claude => claude,
+        };
+        let Some(claude) = claude else {
+            return (find_escalations(activities), 0.0);
+        };
+
+        let mut threads = escalation_candidates(ai_activities);
+        threads.truncate(MAX_ESCALATION_THREADS);
+        let read: HashSet<(&str, u32)> = threads
+            .iter()
+            .map(|(repo, issue, _)| (*repo, issue.number))
+            .collect();
+        let mut escalations: Vec<Escalation> = find_escalations(activities)
+            .into_iter()
+            .filter(|e| !read.contains(&(e.repo.as_str(), e.issue.number)))
+            .collect();
+        if threads.is_empty() {
+            return (escalations, 0.0);
         }
 
-        refs.into_iter().collect()
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let prompt = escalation_check_prompt(&threads);
+        let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+            .with_max_tokens(100 * threads.len() as u32);
+        match claude.messages(request) {
+            Ok(response) => {
+                let cost = self.config.claude.pricing().estimate_cost(
+                    &model,
+                    estimate_tokens(&prompt),
+                    response.usage.output_tokens,
+                );
+                escalations.extend(parse_escalation_check(&threads, &response.get_text()));
+                escalations.sort_by_key(|e| std::cmp::Reverse(e.level));
+                info!("Found {} threads needing de-escalation", escalations.len());
+                (escalations, cost)
+            }
+            Err(e) => {
+                warn!("Failed to check threads for escalation: {}", e);
+                (find_escalations(activities), 0.0)
+            }
+        }
     }
 
-    /// Group issues by repository to match existing report structure
-    fn group_issues_by_repo(
+    /// Fetch the releases published in the feed's repositories
+    fn fetch_releases(
         &self,
-        issue_data: Vec<(Issue, Vec<crate::github::Comment>)>,
-    ) -> BTreeMap<String, crate::github::RepoActivity> {
-        let mut activities = BTreeMap::new();
+        events: &[crate::github::ActivityEvent],
+        errors: &mut Vec<String>,
+    ) -> Vec<ReleaseNote> {
+        let mut releases = published_releases(events);
+        releases.retain(|(repo, _)| self.config.report.includes_repo(repo));
+        if releases.len() > MAX_RELEASES {
+            warn!(
+                "Listing {} of {} published releases",
+                MAX_RELEASES,
+                releases.len()
+            );
+            releases.truncate(MAX_RELEASES);
+        }
 
-        for (issue, comments) in issue_data {
-            let repo_name = issue
-                .repository_name()
-                .unwrap_or_else(|| "unknown".to_string());
-            let activity =
-                activities
-                    .entry(repo_name)
-                    .or_insert_with(|| crate::github::RepoActivity {
-                        new_issues: Vec::new(),
-                        updated_issues: Vec::new(),
-                        new_prs: Vec::new(),
-                        updated_prs: Vec::new(),
-                        merged_prs: Vec::new(),
-                        closed_issues: Vec::new(),
-                        new_comments: Vec::new(),
-                    });
+        releases
+            .into_iter()
+            .filter_map(
+                |(repo, id)| match self.github_client.fetch_release(&repo, id) {
+                    Ok(release) => Some(ReleaseNote {
+                        repo,
+                        release,
+                        summary: None,
+                    }),
+                    Err(e) => {
+                        warn!("Failed to fetch release {} of {}: {}", id, repo, e);
+                        errors.push(format!("Could not fetch a release of {}: {}", repo, e));
+                        None
+                    }
+                },
+            )
+            .collect()
+    }
 
-            // Store the issue with comments in new_comments since they all have recent activity
-            activity.new_comments.push((issue.clone(), comments));
+    /// Have Claude sum up the notes of each release in one line
+    ///
+    /// Releases of repositories kept out of AI summaries, and releases
+    /// without notes, are listed without a summary.
+    fn summarize_releases(
+        &self,
+        claude: Option<&dyn LlmBackend>,
+        releases: &mut [ReleaseNote],
+        excluded_repos: &[String],
+    ) -> f32 {
+        let Some(claude) = claude else {
+            return 0.0;
+        };
+        let mut to_summarize: Vec<ReleaseNote> = releases
+            .iter()
+            .filter(|note| !excluded_repos.contains(&note.repo))
+            .filter(|note| {
+                note.release
+                    .body
+                    .as_deref()
+                    .is_some_and(|body| !body.trim().is_empty())
+            })
+            .cloned()
+            .collect();
+        if to_summarize.is_empty() {
+            return 0.0;
+        }
 
-            // Also add to appropriate category for backward compatibility
-            if issue.is_pull_request {
-                activity.updated_prs.push(issue);
-            } else {
-                activity.updated_issues.push(issue);
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let prompt = release_summaries_prompt(
+            &to_summarize
+                .iter()
+                .map(|note| (note.repo.as_str(), &note.release))
+                .collect::<Vec<_>>(),
+        );
+        let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+            .with_max_tokens(100 * to_summarize.len() as u32);
+        match claude.messages(request) {
+            Ok(response) => {
+                parse_release_summaries(&mut to_summarize, &response.get_text());
+                for summarized in to_summarize {
+                    if let Some(note) = releases.iter_mut().find(|note| {
+                        note.repo == summarized.repo && note.release.id == summarized.release.id
+                    }) {
+                        note.summary = summarized.summary;
+                    }
+                }
+                self.config.claude.pricing().estimate_cost(
+                    &model,
+                    estimate_tokens(&prompt),
+                    response.usage.output_tokens,
+                )
+            }
+            Err(e) => {
+                warn!("Failed to summarize releases: {}", e);
+                0.0
             }
         }
+    }
 
+    /// How the items relate to the previous reports, keyed by URL
+    fn seen_statuses(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> BTreeMap<String, SeenStatus> {
         activities
+            .values()
+            .flat_map(activity_issues)
+            .filter_map(|issue| Some((issue.url.clone(), self.state.seen_status(issue)?)))
+            .collect()
     }
 
-    /// Generate the final report using existing logic
-    fn generate_final_report(
+    /// Fetch the discussions with activity in the feed
+    ///
+    /// Each repository's recently updated discussions are fetched in one
+    /// request, and only those the feed refers to are kept.
+    fn fetch_discussions(
         &self,
-        activities: BTreeMap<String, crate::github::RepoActivity>,
-        now: Timestamp,
-        progress: &mut ProgressReporter,
-        errors: Vec<String>,
-    ) -> Result<Report> {
-        if activities.is_empty() {
-            return Ok(Report {
-                title: "No Activities Found".to_string(),
-                content: format!("# No Activities\n\nNo relevant activities found to report.\n\n*Report generated at {}*",
-                    now.strftime("%Y-%m-%d %H:%M")
-                ),
-                timestamp: now,
-                estimated_cost: 0.0,
-            });
+        events: &[&crate::github::ActivityEvent],
+        since: Timestamp,
+        errors: &mut Vec<String>,
+    ) -> Vec<DiscussionNote> {
+        let mut references = discussion_references(events);
+        if references.len() > MAX_DISCUSSIONS {
+            warn!(
+                "Listing {} of {} active discussions",
+                MAX_DISCUSSIONS,
+                references.len()
+            );
+            references.truncate(MAX_DISCUSSIONS);
         }
 
-        // Use existing intelligent analysis
-        let _spinner = progress.spinner("Analyzing importance");
-        let analyzer = IntelligentAnalyzer::new(self.config);
-        let _analysis = analyzer.analyze(&activities);
-
-        let mut total_cost = 0.0;
-        let since = now - (7 as i64 * 24).hours(); // Default to 7 days back
-
-        // Generate AI summary if Claude is available
-        let (summary, title) = if let Some(ref claude) = self.claude_client {
-            let _ai_spinner = progress.spinner("Generating AI summary");
-            match self.generate_ai_summary(claude, &activities) {
-                Ok((sum, tit, cost)) => {
-                    total_cost += cost;
-                    (sum, tit)
-                }
+        let repos: BTreeSet<&str> = references.iter().map(|(repo, _)| repo.as_str()).collect();
+        let mut fetched: Vec<(String, crate::github::Discussion)> = Vec::new();
+        for repo in repos {
+            match self.github_client.fetch_discussions(repo, since) {
+                Ok(discussions) => fetched.extend(
+                    discussions
+                        .into_iter()
+                        .map(|discussion| (repo.to_string(), discussion)),
+                ),
                 Err(e) => {
-                    warn!("Failed to generate AI summary: {}", e);
-                    // Fall back to basic summary
-                    let template = ReportTemplate::new(self.config);
-                    let content = template.render(&activities, since, now, &errors)?;
-                    (content, "GitHub Activity Report".to_string())
+                    warn!("Failed to fetch discussions of {}: {}", repo, e);
+                    errors.push(format!(
+                        "Could not fetch the discussions of {}: {}",
+                        repo, e
+                    ));
                 }
             }
-        } else {
-            // Use template-based generation
-            let template = ReportTemplate::new(self.config);
-            let content = template.render(&activities, since, now, &errors)?;
-            (content, "GitHub Activity Report".to_string())
-        };
+        }
 
-        Ok(Report {
+        references
+            .into_iter()
+            .filter_map(|(repo, number)| {
+                let index = fetched
+                    .iter()
+                    .position(|(r, d)| *r == repo && d.number == number)?;
+                let (repo, discussion) = fetched.swap_remove(index);
+                Some(DiscussionNote {
+                    repo,
+                    discussion,
+                    summary: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Fetch the boards of `report.projects`
+    ///
+    /// Boards that can't be fetched are left out with a warning in the report.
+    fn fetch_projects(&self, errors: &mut Vec<String>) -> Vec<ProjectBoard> {
+        let mut boards = Vec::new();
+        for url in &self.config.report.projects {
+            let board = parse_project_url(url).and_then(|project| {
+                self.github_client
+                    .fetch_project(&project, &self.config.report.project_status_field)
+            });
+            match board {
+                Ok(board) => boards.push(board),
+                Err(e) => {
+                    warn!("Failed to fetch project {}: {}", url, e);
+                    errors.push(format!("Could not fetch the project {}: {}", url, e));
+                }
+            }
+        }
+        boards
+    }
+
+    /// Open milestones of the reported repositories due within `report.upcoming_days`
+    ///
+    /// Repositories whose milestones can't be fetched are left out with a
+    /// warning in the report.
+    fn upcoming_milestones(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        now: Timestamp,
+        errors: &mut Vec<String>,
+    ) -> Vec<UpcomingMilestone> {
+        // Offline, the milestones of every repository would be listed as missing
+        let days = self.config.report.upcoming_days;
+        if days == 0 || self.github_client.is_offline() {
+            return Vec::new();
+        }
+
+        let mut milestones = Vec::new();
+        for repo in activities.keys() {
+            match self.github_client.fetch_milestones(repo) {
+                Ok(fetched) => {
+                    milestones.extend(fetched.into_iter().map(|m| (repo.clone(), m)));
+                }
+                Err(e) if is_auth_expired(&e) => {
+                    warn!("Failed to fetch milestones of {}: {}", repo, e);
+                    break;
+                }
+                Err(e) => {
+                    warn!("Failed to fetch milestones of {}: {}", repo, e);
+                    errors.push(format!("Could not fetch the milestones of {}: {}", repo, e));
+                }
+            }
+        }
+        let upcoming = upcoming_milestones(milestones, now, days, &TimeZone::system());
+        info!("Found {} milestones due soon", upcoming.len());
+        upcoming
+    }
+
+    /// Open PRs of the reported repositories the user can push to, by requested reviewer
+    ///
+    /// Repositories whose pull requests can't be fetched are left out with a
+    /// warning in the report.
+    fn reviewer_workload(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        now: Timestamp,
+        errors: &mut Vec<String>,
+    ) -> Vec<ReviewerLoad> {
+        if !self.config.report.reviewer_workload || self.github_client.is_offline() {
+            return Vec::new();
+        }
+
+        let mut queues = Vec::new();
+        for repo in activities.keys() {
+            match self.github_client.fetch_review_queue(repo) {
+                Ok(queue) => queues.push((repo.clone(), queue)),
+                Err(e) if is_auth_expired(&e) => {
+                    warn!("Failed to fetch review requests of {}: {}", repo, e);
+                    break;
+                }
+                Err(e) => {
+                    warn!("Failed to fetch review requests of {}: {}", repo, e);
+                    errors.push(format!(
+                        "Could not fetch the review requests of {}: {}",
+                        repo, e
+                    ));
+                }
+            }
+        }
+        let workload = reviewer_workload(&queues, now);
+        info!("Found {} reviewers with pending reviews", workload.len());
+        workload
+    }
+
+    /// Have Claude sum up each discussion thread in one line
+    ///
+    /// Discussions of repositories kept out of AI summaries are listed
+    /// without a summary.
+    fn summarize_discussions(
+        &self,
+        claude: Option<&dyn LlmBackend>,
+        discussions: &mut [DiscussionNote],
+        excluded_repos: &[String],
+    ) -> f32 {
+        let Some(claude) = claude else {
+            return 0.0;
+        };
+        let mut to_summarize: Vec<DiscussionNote> = discussions
+            .iter()
+            .filter(|note| !excluded_repos.contains(&note.repo))
+            .cloned()
+            .collect();
+        if to_summarize.is_empty() {
+            return 0.0;
+        }
+
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let prompt = discussion_summaries_prompt(
+            &to_summarize
+                .iter()
+                .map(|note| (note.repo.as_str(), &note.discussion))
+                .collect::<Vec<_>>(),
+        );
+        let request = MessagesRequest::new(model.clone(), vec![Message::user(prompt.clone())])
+            .with_max_tokens(100 * to_summarize.len() as u32);
+        match claude.messages(request) {
+            Ok(response) => {
+                parse_discussion_summaries(&mut to_summarize, &response.get_text());
+                for summarized in to_summarize {
+                    if let Some(note) = discussions.iter_mut().find(|note| {
+                        note.repo == summarized.repo
+                            && note.discussion.number == summarized.discussion.number
+                    }) {
+                        note.summary = summarized.summary;
+                    }
+                }
+                self.config.claude.pricing().estimate_cost(
+                    &model,
+                    estimate_tokens(&prompt),
+                    response.usage.output_tokens,
+                )
+            }
+            Err(e) => {
+                warn!("Failed to summarize discussions: {}", e);
+                0.0
+            }
+        }
+    }
+
+    /// The state of the checks of the open PRs in the report
+    ///
+    /// Lookups stop once the API quota runs low, as the report can do without them.
+    fn ci_statuses(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> BTreeMap<(String, u32), CiStatus> {
+        let mut statuses = BTreeMap::new();
+        if !self.config.report.ci_status {
+            return statuses;
+        }
+
+        let open_prs = activities.iter().flat_map(|(repo_name, activity)| {
+            activity
+                .new_prs
+                .iter()
+                .chain(&activity.updated_prs)
+                .filter(|pr| pr.state == IssueState::Open)
+                .map(move |pr| (repo_name, pr.number))
+        });
+        for (repo_name, number) in open_prs.take(MAX_CI_STATUS_PRS) {
+            if !self.github_client.allows_optional_fetches() {
+                break;
+            }
+            match self.github_client.fetch_pr_checks(repo_name, number) {
+                Ok(runs) => {
+                    if let Some(status) = CiStatus::of(&runs) {
+                        statuses.insert((repo_name.clone(), number), status);
+                    }
+                }
+                Err(e) if is_auth_expired(&e) => break,
+                Err(e) => warn!("Failed to fetch checks for {}#{}: {}", repo_name, number, e),
+            }
+        }
+        statuses
+    }
+
+    /// Upcoming deadlines for the calendar export, if enabled
+    fn deadlines(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        now: Timestamp,
+    ) -> Vec<Deadline> {
+        if !self.config.report.deadlines_ics {
+            return Vec::new();
+        }
+
+        let tz = TimeZone::system();
+        let today = now.to_zoned(tz.clone()).date();
+        let deadlines = extract_deadlines(activities, today, &tz);
+        info!("Found {} upcoming deadlines", deadlines.len());
+        deadlines
+    }
+
+    /// Repositories among `repos` to leave out of AI summaries
+    ///
+    /// Asks about repositories seen for the first time when
+    /// `claude.confirm_new_repos` is set. Also returns the updated answers
+    /// when they changed, to be saved in the state.
+    fn confirm_new_repos(
+        &self,
+        repos: &BTreeSet<&str>,
+    ) -> Result<(Vec<String>, Option<RepoTrust>)> {
+        // Offline, nothing is sent anywhere
+        if self.llm.is_none() || self.github_client.is_offline() {
+            return Ok((Vec::new(), None));
+        }
+
+        let mut trust = self.state.repo_trust.clone();
+        let excluded = confirm_new_repos(&self.config.claude, &mut trust, repos.iter().copied())?;
+        if !excluded.is_empty() {
+            info!("Leaving out of AI summaries: {}", excluded.join(", "));
+        }
+        let changed = trust != self.state.repo_trust;
+        Ok((excluded, changed.then_some(trust)))
+    }
+
+    /// The Claude client, if it may be sent content from these repositories
+    ///
+    /// Content from private repositories is only sent after confirmation,
+    /// unless `claude.allow_private_repos` is set.
+    fn claude_for(&self, repos: &BTreeSet<&str>) -> Result<Option<&dyn LlmBackend>> {
+        let Some(claude) = self.llm.as_deref() else {
+            return Ok(None);
+        };
+        if repos.is_empty() {
+            return Ok(None);
+        }
+        if self.config.claude.allow_private_repos || self.github_client.is_offline() {
+            return Ok(Some(claude));
+        }
+
+        let private_repos = find_private_repos(
+            &self.github_client,
+            self.cache_manager.as_ref(),
+            repos.iter().copied(),
+        );
+        if confirm_private_repos(&self.config.claude, &claude.destination(), &private_repos)? {
+            Ok(Some(claude))
+        } else {
+            eprintln!("ℹ️  Continuing without AI summarization");
+            Ok(None)
+        }
+    }
+
+    /// Fetch issues and PRs with their comments, `settings.fetch_concurrency` at a time
+    ///
+    /// Items that fail to fetch are left out and reported in `errors`.
+    fn fetch_items(
+        &self,
+        refs: &[(String, u32)],
+        errors: &mut Vec<String>,
+    ) -> Result<Vec<(Issue, Vec<Comment>)>> {
+        let results = self
+            .github_client
+            .fetch_single_issues(refs, self.config.settings.fetch_concurrency);
+
+        let mut items = Vec::new();
+        for ((repo, issue_number), result) in refs.iter().zip(results) {
+            match result {
+                Ok(item) => items.push(item),
+                Err(e) if is_auth_expired(&e) => return Err(e),
+                Err(e) => {
+                    warn!("Failed to fetch {}/issues/{}: {}", repo, issue_number, e);
+                    errors.push(format!(
+                        "Failed to fetch {}/issues/{}: {}",
+                        repo, issue_number, e
+                    ));
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    /// Like `fetch_items`, but reusing the items an interrupted report fetched
+    /// and saving the fetched ones, so `--resume` can pick them up
+    fn fetch_new_items(
+        &self,
+        refs: &[(String, u32)],
+        since: Timestamp,
+        errors: &mut Vec<String>,
+    ) -> Result<Vec<(Issue, Vec<Comment>)>> {
+        let mut partial = self.partial_run(since);
+        let fetched: HashSet<(String, u32)> = partial
+            .items
+            .iter()
+            .filter_map(|(issue, _)| Some((issue.repository_name()?, issue.number)))
+            .collect();
+        let (reused, missing): (Vec<_>, Vec<_>) = refs
+            .iter()
+            .cloned()
+            .partition(|item| fetched.contains(item));
+        if !reused.is_empty() {
+            info!("Reusing {} issues/PRs fetched before", reused.len());
+        }
+
+        let items = self.fetch_items(&missing, errors)?;
+        partial.items.extend(items);
+        self.save_partial_run(&partial);
+
+        let refs: HashSet<_> = refs.iter().collect();
+        Ok(partial
+            .items
+            .into_iter()
+            .filter(|(issue, _)| {
+                issue
+                    .repository_name()
+                    .is_some_and(|repo| refs.contains(&(repo, issue.number)))
+            })
+            .collect())
+    }
+
+    /// Which report this is, as an interrupted report is only resumed by the
+    /// same kind of report
+    fn run_scope(&self) -> String {
+        let report = &self.config.report;
+        format!(
+            "org={} users={} repos={}",
+            report.org.as_deref().unwrap_or_default(),
+            report.users.join(","),
+            report.include_repos.join(",")
+        )
+    }
+
+    /// The fetched data of the resumed report, or a new one starting at `since`
+    fn partial_run(&self, since: Timestamp) -> PartialRun {
+        self.resumed
+            .clone()
+            .unwrap_or_else(|| PartialRun::new(self.run_scope(), since))
+    }
+
+    fn save_partial_run(&self, run: &PartialRun) {
+        if let Some(cache) = &self.cache_manager {
+            if let Err(e) = cache.cache_partial_run(run) {
+                warn!("Failed to save the fetched data for --resume: {}", e);
+            }
+        }
+    }
+
+    fn clear_partial_run(&self) {
+        if let Some(cache) = &self.cache_manager {
+            if let Err(e) = cache.clear_partial_run() {
+                warn!("Failed to remove the data of the interrupted report: {}", e);
+            }
+        }
+    }
+
+    /// Issues and PRs updated between `from` and `to`, found through search
+    ///
+    /// Covers the span a truncated activity feed misses: items involving the
+    /// user, and items in the repositories the feed does show.
+    fn search_feed_gap(
+        &self,
+        repos: &[String],
+        from: Timestamp,
+        to: Timestamp,
+        errors: &mut Vec<String>,
+    ) -> Result<Vec<(String, u32)>> {
+        let updated = format!(
+            "updated:{}..{}",
+            from.strftime("%Y-%m-%dT%H:%M:%SZ"),
+            to.strftime("%Y-%m-%dT%H:%M:%SZ")
+        );
+        let mut queries = vec![format!("involves:@me {}", updated)];
+        // Several `repo:` qualifiers match any of the repositories
+        for chunk in repos.chunks(SEARCH_REPOS_PER_QUERY) {
+            let repos: Vec<String> = chunk.iter().map(|r| format!("repo:{}", r)).collect();
+            queries.push(format!("{} {}", repos.join(" "), updated));
+        }
+
+        let mut found = BTreeSet::new();
+        for query in &queries {
+            match self.github_client.search_issues(query) {
+                Ok(issues) => found.extend(
+                    issues
+                        .iter()
+                        .filter_map(|issue| Some((issue.repository_name()?, issue.number))),
+                ),
+                Err(e) if is_auth_expired(&e) => return Err(e),
+                Err(e) => {
+                    warn!("Search for `{}` failed: {}", query, e);
+                    errors.push(format!(
+                        "⚠️ Could not search for activity missing from the feed: {}",
+                        e
+                    ));
+                }
+            }
+        }
+        info!(
+            "Found {} issues/PRs from before the activity feed starts",
+            found.len()
+        );
+        Ok(found.into_iter().collect())
+    }
+
+    /// Review requests and mentions since `since` about items not in `known`
+    ///
+    /// Failing to fetch notifications only adds a warning to the report.
+    fn missed_notifications(
+        &self,
+        since: Timestamp,
+        known: &HashSet<(String, u32)>,
+        errors: &mut Vec<String>,
+    ) -> Result<Vec<Notification>> {
+        if !self.config.report.notifications {
+            return Ok(Vec::new());
+        }
+
+        let notifications = match self.github_client.fetch_notifications(since) {
+            Ok(notifications) => notifications,
+            Err(e) if is_auth_expired(&e) => return Err(e),
+            Err(e) => {
+                warn!("Failed to fetch notifications: {}", e);
+                errors.push(format!("⚠️ Could not fetch notifications: {}", e));
+                return Ok(Vec::new());
+            }
+        };
+
+        let mut seen = HashSet::new();
+        let missed: Vec<Notification> = notifications
+            .into_iter()
+            .filter(|n| NOTIFICATION_REASONS.contains(&n.reason.as_str()))
+            .filter(|n| self.config.report.includes_repo(&n.repository.full_name))
+            .filter(|n| {
+                n.item_number().is_some_and(|number| {
+                    let item = (n.repository.full_name.clone(), number);
+                    !known.contains(&item) && seen.insert(item)
+                })
+            })
+            .collect();
+        info!(
+            "Found {} review requests and mentions outside the activity",
+            missed.len()
+        );
+        Ok(missed)
+    }
+
+    /// Items updated since `since` that mention the users and teams in
+    /// `settings.include_mentions`, in any repository
+    ///
+    /// Failing to search for one of them only adds a warning to the report.
+    fn included_mentions(
+        &self,
+        since: Timestamp,
+        errors: &mut Vec<String>,
+    ) -> Result<Vec<Mention>> {
+        let mut found = Vec::new();
+        for target in &self.config.settings.include_mentions {
+            match self.github_client.fetch_mentions(target, since) {
+                Ok(mut issues) => {
+                    issues.retain(|issue| self.item_filter(issue).is_none());
+                    info!("Found {} items mentioning {}", issues.len(), target);
+                    found.push((target.clone(), issues));
+                }
+                Err(e) if is_auth_expired(&e) => return Err(e),
+                Err(e) => {
+                    warn!("Failed to fetch mentions of {}: {}", target, e);
+                    errors.push(format!("⚠️ Could not fetch mentions of {}: {}", target, e));
+                }
+            }
+        }
+        Ok(collect_mentions(found))
+    }
+
+    /// Split off the items scoring below `report.min_priority_score`, which the
+    /// report only lists in its appendix
+    fn split_low_priority(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        current_user: Option<&str>,
+    ) -> (
+        BTreeMap<String, crate::github::RepoActivity>,
+        Vec<(String, Issue)>,
+    ) {
+        let min_score = self.config.report.min_priority_score;
+        let (kept, low_priority) = IntelligentAnalyzer::new(self.config)
+            .with_current_user(current_user)
+            .split_by_score(activities, min_score);
+        if !low_priority.is_empty() {
+            info!(
+                "Collapsing {} items scoring below {} into the appendix",
+                low_priority.len(),
+                min_score
+            );
+        }
+        (kept, low_priority)
+    }
+
+    /// Keep the report within `settings.max_issues_per_report` and
+    /// `settings.max_comments_per_report`, favoring the highest-scoring items
+    fn enforce_budget(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        current_user: Option<&str>,
+    ) -> (BTreeMap<String, crate::github::RepoActivity>, Truncation) {
+        IntelligentAnalyzer::new(self.config)
+            .with_current_user(current_user)
+            .enforce_budget(
+                activities,
+                self.config.settings.max_issues_per_report,
+                self.config.settings.max_comments_per_report,
+            )
+    }
+
+    /// Why `issue` is left out by being muted, its author, or the label
+    /// filters for its repository, if it is
+    fn item_filter(&self, issue: &Issue) -> Option<ItemFilter> {
+        let repo = issue.repository_name().unwrap_or_default();
+        if self.state.is_muted(&repo, issue.number) {
+            return Some(ItemFilter::Muted);
+        }
+        if self.config.report.ignores_author(&issue.author) {
+            return Some(ItemFilter::Author(issue.author.login.clone()));
+        }
+        label_filter(
+            issue,
+            &self.config.exclude_labels(&repo),
+            &self.config.report.require_labels,
+        )
+    }
+
+    /// Drop items left out by being muted, their author, or labels, along
+    /// with the notifications about them, and count what was dropped
+    fn drop_filtered_items<T>(
+        &self,
+        items: &mut Vec<T>,
+        issue_of: impl Fn(&T) -> &Issue,
+        notifications: &mut Vec<Notification>,
+    ) -> FilteredItems {
+        let mut filtered = FilteredItems::default();
+        let mut dropped = HashSet::new();
+        items.retain(|item| {
+            let issue = issue_of(item);
+            let Some(filter) = self.item_filter(issue) else {
+                return true;
+            };
+            filtered.record(filter);
+            dropped.insert((issue.repository_name().unwrap_or_default(), issue.number));
+            false
+        });
+        if filtered.is_empty() {
+            return filtered;
+        }
+
+        info!(
+            "Leaving out {} muted items or items by their author or labels",
+            filtered.total()
+        );
+        notifications.retain(|n| {
+            n.item_number()
+                .is_none_or(|number| !dropped.contains(&(n.repository.full_name.clone(), number)))
+        });
+        filtered
+    }
+
+    fn current_user(&self) -> Option<String> {
+        match self.github_client.get_current_user() {
+            Ok(login) => Some(login),
+            Err(e) => {
+                warn!("Failed to get current user: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Names of the organization's repositories, without archived ones
+    fn org_repositories(&self, org: &str) -> Result<Vec<String>> {
+        let repos = self
+            .github_client
+            .fetch_org_repositories(org)
+            .with_context(|| format!("Failed to list repositories of {}", org))?;
+        let names: Vec<String> = repos
+            .into_iter()
+            .filter(|repo| !repo.is_archived)
+            .map(|repo| repo.full_name)
+            .collect();
+        info!("Found {} active repositories in {}", names.len(), org);
+        Ok(names)
+    }
+
+    fn discover_active_repositories(
+        &self,
+        username: &str,
+        since: &Timestamp,
+    ) -> Result<Vec<String>> {
+        let _span = info_span!("activity_fetch").entered();
+        info!(
+            "Discovering repositories based on user activity since {}",
+            since.strftime("%Y-%m-%d %H:%M")
+        );
+
+        // Offline, the repositories of the last discovery stand in
+        let cache_key = generate_cache_key(&["discovered_repos", username]);
+        if self.github_client.is_offline() {
+            let cached = self
+                .cache_manager
+                .as_ref()
+                .and_then(|cache| cache.get_github_response(&cache_key).ok().flatten())
+                .and_then(|data| serde_json::from_slice::<Vec<String>>(&data).ok());
+            if let Some(repos) = cached {
+                info!("Using the {} repositories discovered before", repos.len());
+                return Ok(repos);
+            }
+        }
+
+        let mut discovered_repos = std::collections::HashSet::new();
+
+        // Search for recent activity in different ways
+        let searches = vec![
+            format!(
+                "involves:{} updated:>{}",
+                username,
+                since.strftime("%Y-%m-%d")
+            ),
+            format!(
+                "author:{} updated:>{}",
+                username,
+                since.strftime("%Y-%m-%d")
+            ),
+            format!(
+                "assignee:{} updated:>{}",
+                username,
+                since.strftime("%Y-%m-%d")
+            ),
+            format!(
+                "mentions:{} updated:>{}",
+                username,
+                since.strftime("%Y-%m-%d")
+            ),
+        ];
+
+        for query in searches {
+            match self.search_repositories(&query) {
+                Ok(repos) => {
+                    for repo in repos {
+                        discovered_repos.insert(repo);
+                    }
+                }
+                Err(e) => {
+                    warn!("Search failed for query '{}': {}", query, e);
+                    continue;
+                }
+            }
+        }
+
+        let mut repos: Vec<String> = discovered_repos.into_iter().collect();
+        repos.sort();
+
+        info!(
+            "Discovered {} repositories with recent activity",
+            repos.len()
+        );
+        for repo in &repos {
+            info!("  {}", repo);
+        }
+        if let (Some(cache), false) = (&self.cache_manager, repos.is_empty()) {
+            let data = serde_json::to_vec(&repos).unwrap_or_default();
+            if let Err(e) = cache.cache_github_response(&cache_key, &data) {
+                warn!("Failed to cache the discovered repositories: {}", e);
+            }
+        }
+
+        Ok(repos)
+    }
+
+    /// Repositories of the issues and PRs matching a GitHub search query
+    fn search_repositories(&self, query: &str) -> Result<Vec<String>> {
+        let issues = self.github_client.search_issues(query)?;
+        let repos: HashSet<String> = issues
+            .iter()
+            .filter_map(|issue| issue.repository_name())
+            .collect();
+        Ok(repos.into_iter().collect())
+    }
+
+    /// Footer note on which provider and model wrote the AI summary
+    ///
+    /// Names the reason when the provider changed during the run.
+    fn summarized_by(&self, claude: &dyn LlmBackend) -> String {
+        let text = if self.github_client.is_offline() {
+            "AI summary from the cache (offline run)".to_string()
+        } else {
+            let model = self
+                .config
+                .claude
+                .resolve_model(&self.config.claude.primary_model);
+            match claude.fallback_reason() {
+                Some(reason) => format!(
+                    "AI summary by {} ({}) after {}",
+                    claude.name(),
+                    model,
+                    reason
+                ),
+                None => format!("AI summary by {} ({})", claude.name(), model),
+            }
+        };
+        info!("{}", text);
+        text
+    }
+
+    /// Summarize `activities`, as a rollup of themes and trends when there
+    /// is a `comparison` to the previous period
+    fn generate_ai_summary_with_context(
+        &self,
+        claude: &dyn LlmBackend,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        context: Option<&str>,
+        comparison: Option<&PeriodComparison>,
+    ) -> Result<(String, String, f32)> {
+        // Generate the prompt
+        let context = self.with_hook_notes_context(context);
+        let context = context.as_deref();
+        let repo_contexts = self.config.repo_contexts();
+        let rollup = comparison.map(|comparison| {
+            rollup_instructions(
+                &comparison.flavor.label().to_lowercase(),
+                comparison.flavor.period(),
+                &comparison.prompt_lines(),
+            )
+        });
+        let mut prompt = summarize_activities_prompt(activities, context, &repo_contexts);
+        prompt.push_str(rollup.as_deref().unwrap_or_default());
+        let strategy = self.config.claude.summarization_strategy;
+
+        // Generate cache key for this prompt
+        let prompt_hash = {
+            use sha2::{Digest, Sha256};
+            let mut hasher = Sha256::new();
+            hasher.update(prompt.as_bytes());
+            // The same activity summarized in another language is another summary
+            if let Some(language) = self.output_language() {
+                hasher.update(language.as_bytes());
+            }
+            format!("{:x}", hasher.finalize())
+        };
+
+        let cache_key = match strategy {
+            SummarizationStrategy::Single => generate_cache_key(&[
+                "claude_summary",
+                &prompt_hash[..16], // Use first 16 chars of hash
+            ]),
+            SummarizationStrategy::MapReduce => {
+                generate_cache_key(&["claude_summary", "map_reduce", &prompt_hash[..16]])
+            }
+        };
+
+        // Try to get from cache
+        if let Some(ref cache) = self.cache_manager {
+            if let Ok(Some(cached)) = cache.get_claude_response(&cache_key) {
+                // Parse cached response (format: "TITLE\n---\nSUMMARY\n---\nCOST")
+                let parts: Vec<&str> = cached.split("\n---\n").collect();
+                if parts.len() == 3 {
+                    let title = parts[0].to_string();
+                    let summary = parts[1].to_string();
+                    let cost: f32 = parts[2].parse().unwrap_or(0.0);
+                    info!("Using cached AI summary (saved cost: ${:.4})", cost);
+                    return Ok((summary, title, 0.0)); // Return 0 cost since we didn't call API
+                }
+            }
+        }
+
+        let primary_model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.primary_model);
+        let (summary, summary_cost) = match strategy {
+            SummarizationStrategy::MapReduce if activities.len() > 1 => {
+                let summaries = self.summarize_repositories(claude, activities, &repo_contexts)?;
+                let (repo_summaries, map_cost): (Vec<_>, Vec<f32>) = summaries
+                    .into_iter()
+                    .map(|(repo, summary, cost)| ((repo, summary), cost))
+                    .unzip();
+                info!(
+                    "Combining the summaries of {} repositories",
+                    repo_summaries.len()
+                );
+                let mut prompt = combine_summaries_prompt(&repo_summaries, context);
+                prompt.push_str(rollup.as_deref().unwrap_or_default());
+                let prompt = with_output_language(prompt, self.output_language());
+                let (summary, cost) = self.summary_request(claude, &primary_model, prompt)?;
+                (summary, cost + map_cost.iter().sum::<f32>())
+            }
+            _ => self.summarize_within_budget(
+                claude,
+                &primary_model,
+                activities,
+                prompt,
+                context,
+                rollup.as_deref(),
+                &repo_contexts,
+                self.output_language(),
+            )?,
+        };
+
+        // Generate title from summary
+        let title_prompt =
+            with_output_language(generate_title_prompt(&summary), self.output_language());
+        let title_tokens = estimate_tokens(&title_prompt);
+        let title_request = MessagesRequest::new(
+            self.config
+                .claude
+                .resolve_model(&self.config.claude.secondary_model),
+            vec![Message::user(title_prompt)],
+        )
+        .with_max_tokens(100);
+
+        let title_response = claude
+            .messages(title_request)
+            .context("Failed to generate title from Claude")?;
+
+        let title = title_response.get_text().trim().to_string();
+
+        // Calculate total cost
+        let pricing = self.config.claude.pricing();
+        let title_cost = pricing.estimate_cost(
+            &self.config.claude.secondary_model,
+            title_tokens,
+            title_response.usage.output_tokens,
+        );
+
+        let total_cost = summary_cost + title_cost;
+
+        // Cache the result
+        if let Some(ref cache) = self.cache_manager {
+            let cached_data = format!("{}\n---\n{}\n---\n{}", title, summary, total_cost);
+            if let Err(e) = cache.cache_claude_response(&cache_key, &cached_data) {
+                warn!("Failed to cache Claude response: {}", e);
+            }
+        }
+
+        Ok((summary, title, total_cost))
+    }
+
+    /// `context` followed by what the `pre_report` hooks printed
+    fn with_hook_notes_context(&self, context: Option<&str>) -> Option<String> {
+        if self.hook_notes.is_empty() {
+            return context.map(str::to_string);
+        }
+        let mut context = context.map_or_else(String::new, |context| format!("{}\n\n", context));
+        context.push_str("Notes from the user's pre-report hooks:\n");
+        for note in &self.hook_notes {
+            context.push_str(&format!("\n{}\n", note.trim()));
+        }
+        Some(context)
+    }
+
+    /// Language of the summaries and titles of the report, when not English
+    fn output_language(&self) -> Option<&str> {
+        self.config.claude.output_language.as_deref()
+    }
+
+    /// Send the summary `prompt` of `activities`, in several requests whose
+    /// summaries are then combined when it is too large for the model
+    ///
+    /// The answer that is returned is asked for in `language`, and combined
+    /// as a rollup with the `rollup` instructions; the summaries of the parts
+    /// are intermediate and stay in English.
+    #[allow(clippy::too_many_arguments)]
+    fn summarize_within_budget(
+        &self,
+        claude: &dyn LlmBackend,
+        model: &str,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        prompt: String,
+        context: Option<&str>,
+        rollup: Option<&str>,
+        repo_contexts: &BTreeMap<String, String>,
+        language: Option<&str>,
+    ) -> Result<(String, f32)> {
+        let budget = self
+            .config
+            .claude
+            .context_window(model)
+            .saturating_sub(SUMMARY_MAX_TOKENS + estimate_tokens(&system_prompt()));
+        let tokens = estimate_tokens(&prompt);
+        if tokens <= budget {
+            return self.summary_request(claude, model, with_output_language(prompt, language));
+        }
+
+        let chunks = chunk_activities(activities, repo_contexts, budget);
+        if chunks.len() < 2 {
+            warn!(
+                "The summary prompt of ~{} tokens is over the {} token budget of {} and can't be split",
+                tokens, budget, model
+            );
+            return self.summary_request(claude, model, with_output_language(prompt, language));
+        }
+        info!(
+            "The summary prompt of ~{} tokens is over the {} token budget of {}, splitting it into {} requests",
+            tokens,
+            budget,
+            model,
+            chunks.len()
+        );
+
+        let mut summaries = Vec::with_capacity(chunks.len());
+        let mut total_cost = 0.0;
+        for (i, chunk) in chunks.iter().enumerate() {
+            let repos: Vec<&str> = chunk.keys().map(String::as_str).collect();
+            let label = format!("{} (part {} of {})", repos.join(", "), i + 1, chunks.len());
+            let prompt = summarize_activities_prompt(chunk, None, repo_contexts);
+            info!(
+                "Summarizing {}, ~{} tokens",
+                label,
+                estimate_tokens(&prompt)
+            );
+            let (summary, cost) = self
+                .summary_request(claude, model, prompt)
+                .with_context(|| format!("Failed to summarize {}", label))?;
+            total_cost += cost;
+            summaries.push((label, summary));
+        }
+
+        let parts: Vec<(&str, String)> = summaries
+            .iter()
+            .map(|(label, summary)| (label.as_str(), summary.clone()))
+            .collect();
+        let mut prompt = combine_summaries_prompt(&parts, context);
+        prompt.push_str(rollup.unwrap_or_default());
+        let prompt = with_output_language(prompt, language);
+        let (summary, cost) = self.summary_request(claude, model, prompt)?;
+        Ok((summary, total_cost + cost))
+    }
+
+    /// Send a summary prompt, returning the text and its estimated cost
+    fn summary_request(
+        &self,
+        claude: &dyn LlmBackend,
+        model: &str,
+        prompt: String,
+    ) -> Result<(String, f32)> {
+        // Estimate tokens
+        let input_tokens = estimate_tokens(&prompt) + estimate_tokens(&system_prompt());
+
+        let request = MessagesRequest::new(model.to_string(), vec![Message::user(prompt)])
+            .with_system(system_prompt())
+            .with_max_tokens(SUMMARY_MAX_TOKENS);
+
+        // Send request
+        let response = match claude.messages(request) {
+            Ok(resp) => resp,
+            Err(e) => {
+                // Log the actual error for debugging
+                warn!("Claude API error details: {:#}", e);
+
+                let error_str = e.to_string();
+
+                // Provide helpful error messages based on the error type
+                if error_str.contains("ANTHROPIC_API_KEY") {
+                    return Err(anyhow::anyhow!("ANTHROPIC_API_KEY environment variable is not set. Please set it to use AI summarization."));
+                } else if error_str.contains("invalid x-api-key")
+                    || error_str.contains("authentication_error")
+                {
+                    return Err(anyhow::anyhow!("Invalid ANTHROPIC_API_KEY. Please check that your API key is correct and active."));
+                } else if error_str.contains("rate_limit") {
+                    return Err(anyhow::anyhow!(
+                        "Claude API rate limit exceeded. Please try again later."
+                    ));
+                } else if error_str.contains("overloaded") {
+                    return Err(anyhow::anyhow!(
+                        "Claude API is currently overloaded. Please try again in a few moments."
+                    ));
+                }
+
+                return Err(e).context("Failed to get summary from Claude");
+            }
+        };
+
+        let cost = self.config.claude.pricing().estimate_cost(
+            model,
+            input_tokens,
+            response.usage.output_tokens,
+        );
+        Ok((response.get_text(), cost))
+    }
+
+    /// Summarize each repository on its own with the secondary model,
+    /// `MAP_CONCURRENCY` at a time, for `claude.summarization_strategy = "map_reduce"`
+    fn summarize_repositories<'r>(
+        &self,
+        claude: &dyn LlmBackend,
+        activities: &'r BTreeMap<String, crate::github::RepoActivity>,
+        repo_contexts: &BTreeMap<String, String>,
+    ) -> Result<Vec<(&'r str, String, f32)>> {
+        let _span = info_span!("claude_map").entered();
+        let model = self
+            .config
+            .claude
+            .resolve_model(&self.config.claude.secondary_model);
+        let repos: Vec<(&str, &crate::github::RepoActivity)> = activities
+            .iter()
+            .map(|(repo, activity)| (repo.as_str(), activity))
+            .collect();
+        info!("Summarizing {} repositories with {}", repos.len(), model);
+
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, Result<(String, f32)>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..MAP_CONCURRENCY.min(repos.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some((repo, activity)) = repos.get(i) else {
+                                break;
+                            };
+                            let single = BTreeMap::from([(repo.to_string(), (*activity).clone())]);
+                            let prompt = summarize_activities_prompt(&single, None, repo_contexts);
+                            let result = self
+                                .summarize_within_budget(
+                                    claude,
+                                    &model,
+                                    &single,
+                                    prompt,
+                                    None,
+                                    None,
+                                    repo_contexts,
+                                    None,
+                                )
+                                .with_context(|| format!("Failed to summarize {}", repo));
+                            results.push((i, result));
+                        }
+                        results
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("summary worker panicked"))
+                .collect()
+        });
+
+        let mut summaries = Vec::with_capacity(repos.len());
+        results.sort_by_key(|(i, _)| *i);
+        for (i, result) in results {
+            let (summary, cost) = result?;
+            summaries.push((repos[i].0, summary, cost));
+        }
+        Ok(summaries)
+    }
+
+    /// The counts of a weekly or monthly rollup next to the previous period
+    fn comparison(&self, totals: PeriodTotals) -> Option<PeriodComparison> {
+        self.flavor.is_rollup().then(|| PeriodComparison {
+            flavor: self.flavor,
+            current: totals,
+            previous: self.previous_period.clone(),
+        })
+    }
+
+    fn generate_title(
+        &self,
+        since: Timestamp,
+        now: Timestamp,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+    ) -> String {
+        let locale = self.config.report.locale();
+        let (since_date, now_date) = (locale.format_date(since), locale.format_date(now));
+        let date_range = if self.flavor.is_rollup() {
+            format!(
+                "{} Rollup - {} to {}",
+                self.flavor.label(),
+                since_date,
+                now_date
+            )
+        } else if since_date == now_date {
+            format!("Daily Report - {}", now_date)
+        } else {
+            format!("Report - {} to {}", since_date, now_date)
+        };
+
+        let total_items: usize = activities
+            .values()
+            .map(|a| {
+                a.new_issues.len() + a.updated_issues.len() + a.new_prs.len() + a.updated_prs.len()
+            })
+            .sum();
+
+        if total_items > 0 {
+            format!("{} ({} items)", date_range, total_items)
+        } else {
+            date_range
+        }
+    }
+
+    /// Filter activity events using the same logic as the activity command,
+    /// keeping only the repositories the report covers
+    fn filter_activity_events<'e>(
+        &self,
+        events: &'e [crate::github::ActivityEvent],
+    ) -> Vec<&'e crate::github::ActivityEvent> {
+        crate::activity::filter_events(events, None, None)
+            .into_iter()
+            .filter(|event| self.config.report.includes_repo(&event.repo.name))
+            .filter(|event| {
+                self.window_start
+                    .is_none_or(|start| event.created_at >= start)
+            })
+            .filter(|event| self.until.is_none_or(|until| event.created_at < until))
+            .collect()
+    }
+
+    /// Group issues by repository to match existing report structure
+    fn group_issues_by_repo(
+        &self,
+        issue_data: Vec<(Issue, Vec<crate::github::Comment>)>,
+    ) -> BTreeMap<String, crate::github::RepoActivity> {
+        let mut activities = BTreeMap::new();
+
+        for (issue, comments) in issue_data {
+            let repo_name = issue
+                .repository_name()
+                .unwrap_or_else(|| "unknown".to_string());
+            let activity: &mut crate::github::RepoActivity =
+                activities.entry(repo_name).or_default();
+
+            // Store the issue with comments in new_comments since they all have recent activity
+            activity.new_comments.push((issue.clone(), comments));
+
+            // Also add to appropriate category for backward compatibility
+            if issue.is_pull_request {
+                activity.updated_prs.push(issue);
+            } else {
+                activity.updated_issues.push(issue);
+            }
+        }
+
+        activities
+    }
+
+    /// The highlights, counts, and action items of a report, leaving out the
+    /// sections that are turned off
+    fn digest(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        analysis: &AnalysisResult,
+        ai_summary: Option<String>,
+    ) -> ReportDigest {
+        let sections = &self.config.report.sections;
+        ReportDigest {
+            highlights: ai_summary.filter(|_| sections.ai_summary),
+            summary: (sections.statistics && !activities.is_empty())
+                .then(|| ActivityCounts::of(activities)),
+            action_items: if sections.action_items {
+                analysis.action_items.clone()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// The template of a report with `parts`, along with what every report
+    /// carries: how items relate to previous reports, local work, and the
+    /// notes of the `pre_report` hooks
+    fn report_template(
+        &self,
+        activities: &BTreeMap<String, crate::github::RepoActivity>,
+        parts: ReportParts,
+    ) -> ReportTemplate<'a> {
+        ReportTemplate::new(self.config)
+            .with_seen_statuses(self.seen_statuses(activities))
+            .with_current_user(parts.current_user.as_deref())
+            .with_local_work(self.local_work(activities))
+            .with_ci_failures(parts.ci_failures)
+            .with_ci_statuses(parts.ci_statuses)
+            .with_thread_updates(parts.thread_updates)
+            .with_since_last_report(self.since_last_report.is_some())
+            .with_org_summaries(parts.org_summaries)
+            .with_notifications(parts.notifications)
+            .with_mentions(parts.mentions)
+            .with_awaiting_reply(parts.awaiting_reply)
+            .with_escalations(parts.escalations)
+            .with_goal_check_ins(parts.goal_check_ins)
+            .with_upcoming(parts.upcoming)
+            .with_reviewer_workload(parts.reviewer_workload)
+            .with_releases(parts.releases)
+            .with_discussions(parts.discussions)
+            .with_projects(parts.projects)
+            .with_team(parts.team)
+            .with_low_priority(parts.low_priority)
+            .with_truncation(parts.truncation)
+            .with_filtered(parts.filtered)
+            .with_summarized_by(parts.summarized_by)
+            .with_rate_limit(parts.rate_limit)
+            .with_feed_truncated_at(parts.feed_truncated_at)
+            .with_comparison(parts.comparison)
+            .with_notes(self.hook_notes.clone())
+    }
+
+    /// Generate the final report using existing logic
+    #[allow(clippy::too_many_arguments)]
+    fn generate_final_report(
+        &self,
+        activities: BTreeMap<String, crate::github::RepoActivity>,
+        since: Timestamp,
+        now: Timestamp,
+        progress: &mut ProgressReporter,
+        mut errors: Vec<String>,
+        notifications: Vec<Notification>,
+        mentions: Vec<Mention>,
+        feed_truncated_at: Option<Timestamp>,
+        team: Vec<PersonActivity>,
+        mut releases: Vec<ReleaseNote>,
+        mut discussions: Vec<DiscussionNote>,
+        filtered: FilteredItems,
+        snapshot: Option<&ActivitySnapshot>,
+    ) -> Result<Report> {
+        if activities.is_empty() {
+            return Ok(Report {
+                title: "No Activities Found".to_string(),
+                content: format!("# No Activities\n\nNo relevant activities found to report.\n\n*Report generated at {}*",
+                    self.config.report.locale().format_datetime(now)
+                ),
+                timestamp: now,
+                estimated_cost: 0.0,
+                deadlines: Vec::new(),
+                items: Vec::new(),
+                seen_items: BTreeMap::new(),
+                repo_trust: None,
+                scores: None,
+                format: ReportFormat::Markdown,
+                totals: PeriodTotals::default(),
+                digest: ReportDigest::default(),
+            });
+        }
+
+        let current_user = match snapshot {
+            Some(snapshot) => snapshot.user.clone(),
+            None => self.current_user(),
+        };
+        let totals = PeriodTotals::of(&activities);
+        let comparison = self.comparison(totals);
+        let (mut activities, truncation) =
+            self.enforce_budget(&activities, current_user.as_deref());
+
+        // A snapshot already holds the conversations and reviews, but not
+        // the inline review comments
+        let awaiting_reply = match snapshot {
+            Some(_) => Vec::new(),
+            None => {
+                let _details_spinner = progress.spinner("Checking for stalled conversations");
+                self.fetch_conversation_details(&mut activities, now);
+                self.awaiting_reply(&activities, current_user.as_deref(), since)
+            }
+        };
+        let (activities, low_priority) =
+            self.split_low_priority(&activities, current_user.as_deref());
+
+        // Use existing intelligent analysis
+        let _spinner = progress.spinner("Analyzing importance");
+        let analyzer =
+            IntelligentAnalyzer::new(self.config).with_current_user(current_user.as_deref());
+        let mut analysis = analyzer.analyze(&activities);
+
+        let summaries = self.summarize(
+            &activities,
+            &mut analysis,
+            current_user.as_deref(),
+            &mut releases,
+            &mut discussions,
+            comparison.as_ref(),
+            snapshot.is_some(),
+            progress,
+            &mut errors,
+        )?;
+        let ci_statuses = match snapshot {
+            Some(_) => BTreeMap::new(),
+            None => self.ci_statuses(&activities),
+        };
+        let projects = match snapshot {
+            Some(_) => Vec::new(),
+            None => self.fetch_projects(&mut errors),
+        };
+        let upcoming = match snapshot {
+            Some(_) => Vec::new(),
+            None => self.upcoming_milestones(&activities, now, &mut errors),
+        };
+        let reviewer_workload = match snapshot {
+            Some(_) => Vec::new(),
+            None => self.reviewer_workload(&activities, now, &mut errors),
+        };
+        if snapshot.is_none() {
+            self.github_client.check_auth()?;
+        }
+        let rate_limit = match snapshot {
+            Some(_) => None,
+            None => self.github_client.rate_limit(),
+        };
+        errors.extend(self.offline_warnings());
+
+        let template = self.report_template(
+            &activities,
+            ReportParts {
+                current_user: current_user.clone(),
+                ci_failures: summaries.ci_failures,
+                ci_statuses,
+                thread_updates: summaries.thread_updates,
+                org_summaries: summaries.org_summaries,
+                notifications,
+                mentions,
+                awaiting_reply,
+                escalations: summaries.escalations,
+                goal_check_ins: summaries.goal_check_ins,
+                upcoming,
+                reviewer_workload,
+                releases,
+                discussions,
+                projects,
+                team,
+                low_priority,
+                truncation,
+                filtered,
+                summarized_by: summaries.summarized_by,
+                rate_limit,
+                feed_truncated_at,
+                comparison,
+            },
+        );
+        let title = summaries.ai_title.unwrap_or_else(|| template.heading());
+        let content = if self.config.report.format == ReportFormat::Json {
+            // The data behind the report, with the summary as it comes
+            JsonReport {
+                title: &title,
+                user: current_user.as_deref(),
+                warnings: &errors,
+                ai_summary: summaries.ai_summary.as_deref(),
+                summarized_by: template.summarized_by.as_deref(),
+                estimated_cost: summaries.cost,
+                filtered: Some(&template.filtered),
+                ..JsonReport::new(&activities, &analysis, since, now)
+            }
+            .render()?
+        } else {
+            template.render_with_intelligence(
+                &activities,
+                since,
+                now,
+                &errors,
+                summaries.ai_summary.as_deref(),
+                &analysis,
+            )?
+        };
+
+        Ok(Report {
             title,
-            content: summary,
+            content,
             timestamp: now,
-            estimated_cost: total_cost,
+            estimated_cost: summaries.cost,
+            deadlines: self.deadlines(&activities, now),
+            items: covered_items(&activities, now),
+            seen_items: seen_items(&activities, now),
+            repo_trust: summaries.repo_trust,
+            scores: Some(explain_scores(&analysis, current_user.as_deref(), now)),
+            format: self.config.report.format,
+            totals,
+            digest: self.digest(&activities, &analysis, summaries.ai_summary),
         })
     }
-}
+}
+
+/// What `summarize` adds to a report, along with its estimated cost and the
+/// updated answers of `claude.confirm_new_repos`
+struct Summaries {
+    ai_summary: Option<String>,
+    ai_title: Option<String>,
+    summarized_by: Option<String>,
+    ci_failures: Vec<CiFailure>,
+    thread_updates: Vec<ThreadUpdate>,
+    org_summaries: BTreeMap<String, String>,
+    goal_check_ins: Vec<GoalCheckIn>,
+    escalations: Vec<Escalation>,
+    repo_trust: Option<RepoTrust>,
+    cost: f32,
+}
+
+/// The sections both report pipelines fill in before rendering, passed to
+/// `report_template`
+///
+/// Each pipeline spells out every field, so adding one is a compile error in
+/// both until they fill it in, though nothing checks what they fill it with.
+struct ReportParts {
+    current_user: Option<String>,
+    ci_failures: Vec<CiFailure>,
+    ci_statuses: BTreeMap<(String, u32), CiStatus>,
+    thread_updates: Vec<ThreadUpdate>,
+    org_summaries: BTreeMap<String, String>,
+    notifications: Vec<Notification>,
+    mentions: Vec<Mention>,
+    awaiting_reply: Vec<AwaitingReply>,
+    escalations: Vec<Escalation>,
+    goal_check_ins: Vec<GoalCheckIn>,
+    upcoming: Vec<UpcomingMilestone>,
+    reviewer_workload: Vec<ReviewerLoad>,
+    releases: Vec<ReleaseNote>,
+    discussions: Vec<DiscussionNote>,
+    projects: Vec<ProjectBoard>,
+    team: Vec<PersonActivity>,
+    low_priority: Vec<(String, Issue)>,
+    truncation: Truncation,
+    filtered: FilteredItems,
+    summarized_by: Option<String>,
+    rate_limit: Option<crate::github::RateLimit>,
+    feed_truncated_at: Option<Timestamp>,
+    comparison: Option<PeriodComparison>,
+}
+
+/// Cache key of the issues most recently fetched for `repo`, whatever their window
+fn latest_issues_key(repo: &str) -> String {
+    generate_cache_key(&["issues", repo, "latest"])
+}
+
+/// The diff of a PR, with patches capped at `max_patch_bytes`
+///
+/// Diffs are cached by the PR's head commit, so pushing to the PR, including
+/// a force-push, fetches the diff again.
+fn cached_pr_diff(
+    github: &GitHubClient,
+    cache: Option<&CacheManager>,
+    repo: &str,
+    number: u32,
+    max_patch_bytes: usize,
+) -> Result<PrDiff> {
+    let head_sha = github.fetch_pr_head_sha(repo, number)?;
+    let key = head_sha
+        .map(|sha| generate_cache_key(&["pr_diff", repo, &number.to_string(), &sha]))
+        .filter(|_| cache.is_some());
+    if let (Some(cache), Some(key)) = (cache, &key) {
+        if let Ok(Some(data)) = cache.get_github_response(key) {
+            if let Ok(diff) = serde_json::from_slice(&data) {
+                return Ok(diff);
+            }
+        }
+    }
+
+    let mut diff = github.fetch_pr_diff(repo, number)?;
+    diff.cap_patches(max_patch_bytes);
+    if let (Some(cache), Some(key)) = (cache, &key) {
+        let stored = serde_json::to_vec(&diff)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| cache.cache_github_response(key, &data));
+        if let Err(e) = stored {
+            warn!("Failed to cache the diff of {}#{}: {}", repo, number, e);
+        }
+    }
+    Ok(diff)
+}
+
+/// The issues and PRs the notifications are about
+fn notification_refs(notifications: &[Notification]) -> Vec<(String, u32)> {
+    notifications
+        .iter()
+        .filter_map(|n| Some((n.repository.full_name.clone(), n.item_number()?)))
+        .collect()
+}
+
+/// The repositories of `activities`, along with `repos`
+fn with_repos<'r>(
+    activities: &'r BTreeMap<String, crate::github::RepoActivity>,
+    repos: &BTreeSet<&'r str>,
+) -> BTreeSet<&'r str> {
+    activities
+        .keys()
+        .map(String::as_str)
+        .chain(repos.iter().copied())
+        .collect()
+}
+
+/// `repos` without the given repositories
+fn without<'r>(repos: &BTreeSet<&'r str>, excluded: &[String]) -> BTreeSet<&'r str> {
+    repos
+        .iter()
+        .copied()
+        .filter(|repo| !excluded.iter().any(|e| e == repo))
+        .collect()
+}
+
+/// `activities` without the given repositories
+fn without_repos(
+    activities: &BTreeMap<String, crate::github::RepoActivity>,
+    excluded: &[String],
+) -> BTreeMap<String, crate::github::RepoActivity> {
+    activities
+        .iter()
+        .filter(|(repo, _)| !excluded.contains(repo))
+        .map(|(repo, activity)| (repo.clone(), activity.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::MockGitHub;
+    use crate::test_utils::{mock_repo, test_config, text_response};
+
+    #[test]
+    fn test_report_generator_creation() {
+        let mock = MockGitHub::new();
+        let github_client = GitHubClient::Mock(mock);
+        let config = test_config();
+        let state = State::default();
+
+        let generator = ReportGenerator::new(github_client, &config, &state);
+
+        // Generate should work even without Claude client
+        let result = generator.generate(1);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_notifications_add_missed_items() {
+        let notification = |reason: &str, number: u32, updated_at: &str| -> Notification {
+            serde_json::from_value(serde_json::json!({
+                "id": number.to_string(),
+                "unread": true,
+                "reason": reason,
+                "updated_at": updated_at,
+                "repository": { "full_name": "test/repo" },
+                "subject": {
+                    "title": format!("Item {}", number),
+                    "type": "PullRequest",
+                    "url": format!("https://api.github.com/repos/test/repo/pulls/{}", number)
+                }
+            }))
+            .unwrap()
+        };
+        let recent = Timestamp::now().to_string();
+
+        let mut mock = MockGitHub::new();
+        mock.issues.push(crate::test_utils::create_test_issue(
+            7,
+            "Add task hooks",
+            true,
+        ));
+        mock.notifications = vec![
+            notification("review_requested", 7, &recent),
+            notification("mention", 7, &recent),
+            notification("subscribed", 8, &recent),
+            notification("mention", 9, "2020-01-01T00:00:00Z"),
+        ];
+        let config = test_config();
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate(7).unwrap();
+        let content = &report.content;
+        assert!(content.contains("## 🔔 Notifications"));
+        assert!(content.contains(
+            "- **[test/repo]** PR [#7](https://github.com/test/repo/pull/7) - Item 7 (review requested, "
+        ));
+        assert!(!content.contains("(mentioned, "));
+        assert!(!content.contains("#8"));
+        assert!(!content.contains("#9"));
+        // The item itself is fetched and reported like the rest of the activity
+        assert!(content.contains("Add task hooks"));
+        assert!(report.items.iter().any(|item| item.number == 7));
+    }
+
+    #[test]
+    fn test_org_report() {
+        let repo = |full_name: &str, archived: bool| crate::github::Repository {
+            is_archived: archived,
+            ..mock_repo(full_name)
+        };
+
+        let mut mock = MockGitHub::new();
+        // The mock returns every issue for each repository, so list only one active repo
+        mock.repositories = vec![
+            repo("test/repo", false),
+            repo("test/old", true),
+            repo("other/repo", false),
+        ];
+        mock.issues = (1..=3)
+            .map(|n| crate::test_utils::create_test_issue(n, &format!("Item {}", n), false))
+            .collect();
+        // Only the recently updated item scores high enough to stay
+        for n in [0, 2] {
+            mock.issues[n].updated_at = Timestamp::now() - 72.hours();
+        }
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.settings.max_issues_per_report = 1;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate(7).unwrap();
+        let content = &report.content;
+        assert!(content.contains("**Organization**: [test](https://github.com/test)"));
+        assert!(content.contains("Showing the 1 highest-priority of 3 issues and PRs"));
+        assert!(content.contains("Item 2"));
+        // The left-out items are only listed in the truncation note
+        let (body, truncated) = content.split_once("## ✂️ Truncated").unwrap();
+        assert!(!body.contains("Item 1"));
+        assert!(!body.contains("Item 3"));
+        assert!(truncated.contains("Item 1") && truncated.contains("Item 3"));
+    }
+
+    #[test]
+    fn test_excluded_labels_are_dropped() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = (1..=3)
+            .map(|n| crate::test_utils::create_test_issue(n, &format!("Item {}", n), false))
+            .collect();
+        mock.issues[1].labels.push(crate::github::Label {
+            name: "WontFix".to_string(),
+            color: None,
+            description: None,
+        });
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.settings.exclude_labels = vec!["wontfix".to_string()];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("Item 1"));
+        assert!(!content.contains("Item 2"));
+        assert!(content.contains("Item 3"));
+        assert!(content.contains("**Filtered by label**: 1 item (wontfix: 1)"));
+    }
+
+    #[test]
+    fn test_muted_items_are_dropped() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = (1..=3)
+            .map(|n| crate::test_utils::create_test_issue(n, &format!("Item {}", n), false))
+            .collect();
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        let mut state = State::default();
+        state.mute("Test/Repo", 2, Timestamp::now());
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate(7).unwrap();
+        assert!(report.content.contains("Item 1"));
+        assert!(!report.content.contains("Item 2"));
+        assert!(report.content.contains("Item 3"));
+        assert!(report.content.contains("**Muted**: 1 item left out"));
+        assert!(report.items.iter().all(|item| item.number != 2));
+    }
+
+    #[test]
+    fn test_report_label_filters_are_counted() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = (1..=4)
+            .map(|n| crate::test_utils::create_test_issue(n, &format!("Item {}", n), false))
+            .collect();
+        for (i, name) in [(0, "bug"), (1, "dependencies"), (2, "bug"), (2, "bot")] {
+            mock.issues[i].labels.push(crate::github::Label {
+                name: name.to_string(),
+                color: None,
+                description: None,
+            });
+        }
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.report.exclude_labels = vec!["dependencies".to_string(), "bot".to_string()];
+        config.report.require_labels = vec!["bug".to_string()];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("Item 1"));
+        for item in ["Item 2", "Item 3", "Item 4"] {
+            assert!(!content.contains(item), "{} should be filtered", item);
+        }
+        assert!(content.contains(
+            "**Filtered by label**: 3 items (bot: 1, dependencies: 1, 1 without a required label)"
+        ));
+    }
+
+    #[test]
+    fn test_bot_items_are_collapsed() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = (1..=4)
+            .map(|n| crate::test_utils::create_test_issue(n, &format!("Item {}", n), true))
+            .collect();
+        mock.issues[1].author.login = "dependabot[bot]".to_string();
+        mock.issues[2].author.login = "renovate".to_string();
+        mock.issues[2].author.user_type = Some("Bot".to_string());
+        mock.issues[3].author.login = "release-train".to_string();
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.report.ignore_authors = vec!["release-*".to_string()];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("Item 1"));
+        for item in ["Item 2", "Item 3", "Item 4"] {
+            assert!(!content.contains(item), "{} should be left out", item);
+        }
+        assert!(content.contains(
+            "**Bot activity**: 3 items (dependabot[bot]: 1, release-train: 1, renovate: 1) left out"
+        ));
+    }
+
+    #[test]
+    fn test_review_comment_mentions_await_reply() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            5,
+            "Bounded channel",
+            true,
+        )];
+        mock.pr_review_comments = vec![(
+            5,
+            vec![serde_json::from_value(serde_json::json!({
+                "id": 900,
+                "body": "@testuser should this be configurable?",
+                "user": { "login": "alice", "type": "User" },
+                "path": "src/chan.rs",
+                "line": 12,
+                "html_url": "https://github.com/test/repo/pull/5#discussion_r900",
+                "created_at": Timestamp::now().to_string(),
+            }))
+            .unwrap()],
+        )];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("## 💬 Awaiting Your Reply"));
+        assert!(content.contains(
+            "[@alice](https://github.com/test/repo/pull/5#discussion_r900) mentioned you on `src/chan.rs:12`"
+        ));
+        assert!(content.contains("  > @testuser should this be configurable?"));
+    }
+
+    #[test]
+    fn test_goals_check_in() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            5,
+            "io_uring driver",
+            true,
+        )];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.report.goals = vec!["Merge the io_uring PR".to_string()];
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response(
+                    "1. ON_TRACK: [#5](https://github.com/test/repo/pull/5) is under review.",
+                ))
+                .with_response(text_response("The io_uring driver is under review."))
+                .with_response(text_response("io_uring under review")),
+        )));
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 🎯 Goals Check-in\n\n- **Merge the io_uring PR** — 🟢 On track: \
+             [#5](https://github.com/test/repo/pull/5) is under review.\n"
+        ));
+        let model = config.claude.resolve_model(&config.claude.primary_model);
+        assert!(content.contains(&format!("*AI summary by anthropic ({})*", model)));
+    }
+
+    #[test]
+    fn test_map_reduce_summary() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::github::RepoActivity;
+
+        let mut activities = BTreeMap::new();
+        for (repo, title) in [
+            ("tokio-rs/tokio", "Scheduler hang"),
+            ("tokio-rs/axum", "Router panic"),
+        ] {
+            let mut activity = RepoActivity::default();
+            activity
+                .new_issues
+                .push(crate::test_utils::create_test_issue(1, title, false));
+            activities.insert(repo.to_string(), activity);
+        }
+        let mut config = test_config();
+        config.claude.summarization_strategy = SummarizationStrategy::MapReduce;
+        let state = State::default();
+        let generator = ReportGenerator::new(GitHubClient::mock(), &config, &state);
+        let claude = ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("One repository."))
+                .with_response(text_response("The other repository."))
+                .with_response(text_response("A scheduler hang and a router panic."))
+                .with_response(text_response("Hangs and panics")),
+        );
+
+        let (summary, title, cost) = generator
+            .generate_ai_summary_with_context(&claude, &activities, None, None)
+            .unwrap();
+        assert_eq!(summary, "A scheduler hang and a router panic.");
+        assert_eq!(title, "Hangs and panics");
+        assert!(cost > 0.0);
+        let ClaudeClient::Mock(mock) = &claude else {
+            unreachable!()
+        };
+        assert_eq!(mock.call_count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_summary_over_context_window_is_split() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::github::RepoActivity;
+
+        let mut activities = BTreeMap::new();
+        for repo in ["tokio-rs/tokio", "tokio-rs/axum"] {
+            let activity = RepoActivity {
+                new_issues: (1..=30)
+                    .map(|n| crate::test_utils::create_test_issue(n, "Scheduler hang", false))
+                    .collect(),
+                ..RepoActivity::default()
+            };
+            activities.insert(repo.to_string(), activity);
+        }
+        let one_repo: BTreeMap<_, _> = activities
+            .iter()
+            .take(1)
+            .map(|(repo, activity)| (repo.clone(), activity.clone()))
+            .collect();
+        let mut config = test_config();
+        let model = config.claude.resolve_model(&config.claude.primary_model);
+        // Room for the prompt of one repository, not both
+        let window = SUMMARY_MAX_TOKENS
+            + estimate_tokens(&system_prompt())
+            + estimate_tokens(&summarize_activities_prompt(
+                &one_repo,
+                None,
+                &BTreeMap::new(),
+            ))
+            + 10;
+        config.claude.context_windows.insert(model, window);
+        let state = State::default();
+        let generator = ReportGenerator::new(GitHubClient::mock(), &config, &state);
+        let claude = ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("Axum hangs."))
+                .with_response(text_response("Tokio hangs."))
+                .with_response(text_response("Hangs in axum and tokio."))
+                .with_response(text_response("Scheduler hangs")),
+        );
+
+        let (summary, title, _) = generator
+            .generate_ai_summary_with_context(&claude, &activities, None, None)
+            .unwrap();
+        assert_eq!(summary, "Hangs in axum and tokio.");
+        assert_eq!(title, "Scheduler hangs");
+        let ClaudeClient::Mock(mock) = &claude else {
+            unreachable!()
+        };
+        assert_eq!(mock.call_count.load(Ordering::Relaxed), 4);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::github::MockGitHub;
+    #[test]
+    fn test_ci_failures_outside_ai_activities_are_not_explained() {
+        use crate::claude::{ClaudeClient, LlmBackend, MockClaude};
+        use crate::github::{RepoActivity, WorkflowRun};
+
+        let run = |id: u64| WorkflowRun {
+            id,
+            name: Some("CI".to_string()),
+            html_url: format!("https://github.com/test/repo/actions/runs/{}", id),
+            head_sha: "abc123".to_string(),
+            conclusion: Some("failure".to_string()),
+            created_at: Timestamp::now(),
+        };
+        let mut mock = MockGitHub::new();
+        mock.failed_runs = vec![(1, vec![run(10)]), (2, vec![run(20)])];
+        mock.run_logs = vec![
+            (10, "error: secret internal build failed".to_string()),
+            (20, "error: test timer::wheel failed".to_string()),
+        ];
+        let config = test_config();
+        let state = State::default();
+        let generator =
+            ReportGenerator::new(GitHubClient::Mock(mock), &config, &state).with_ci_logs(true);
+        let claude = ClaudeClient::Mock(
+            MockClaude::new().with_response(text_response("The timer wheel test is flaky.")),
+        );
+
+        let activity = |number: u32| RepoActivity {
+            new_prs: vec![crate::test_utils::create_test_issue(number, "Fix", true)],
+            ..RepoActivity::default()
+        };
+        let activities = BTreeMap::from([
+            ("a/hidden".to_string(), activity(1)),
+            ("test/repo".to_string(), activity(2)),
+        ]);
+        let ai_activities = BTreeMap::from([("test/repo".to_string(), activity(2))]);
+        let (failures, _) = generator.ci_failures(
+            Some(&claude as &dyn LlmBackend),
+            &activities,
+            &ai_activities,
+        );
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].repo, "a/hidden");
+        assert_eq!(failures[0].explanation, None);
+        assert_eq!(
+            failures[1].explanation.as_deref(),
+            Some("The timer wheel test is flaky.")
+        );
+    }
 
     #[test]
-    fn test_report_generator_creation() {
-        let mock = MockGitHub::new();
-        let github_client = GitHubClient::Mock(mock);
-        let config = Config::default();
+    fn test_ci_status_of_open_prs() {
+        let check = |name: &str, conclusion: &str| -> crate::github::CheckRun {
+            serde_json::from_value(serde_json::json!({
+                "id": 1,
+                "name": name,
+                "status": "completed",
+                "conclusion": conclusion,
+                "html_url": null,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(5, "io_uring driver", true),
+            crate::test_utils::create_test_issue(6, "Fix docs", true),
+        ];
+        mock.pr_checks = vec![
+            (
+                5,
+                vec![check("test (linux)", "failure"), check("lint", "success")],
+            ),
+            (6, vec![check("test (linux)", "success")]),
+        ];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
         let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
 
-        let generator = ReportGenerator::new(github_client, &config, &state);
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 🚨 Needs CI Attention\n\n- **[test/repo]** PR \
+             [#5](https://github.com/test/repo/pull/5) - io_uring driver (failing: test (linux))\n\n"
+        ));
+        assert!(content.contains("[#5](https://github.com/test/repo/pull/5) io_uring driver by [@testuser](https://github.com/testuser) ❌"));
+        assert!(content.contains("[#6](https://github.com/test/repo/pull/6) Fix docs by [@testuser](https://github.com/testuser) ✅"));
+    }
 
-        // Generate should work even without Claude client
-        let result = generator.generate(1);
-        assert!(result.is_ok());
+    #[test]
+    fn test_low_priority_appendix() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        let typo = crate::test_utils::create_test_issue(6, "Docs typo", false);
+        let min_score = crate::intelligence::calculate_priority_score(
+            &typo,
+            crate::config::Importance::Medium,
+            false,
+            Some("testuser"),
+        )
+        .total
+            + 1;
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(5, "io_uring driver", true),
+            typo,
+        ];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.report.min_priority_score = min_score;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("\n*1 low-priority item omitted (see appendix)*\n"));
+        assert!(content.contains(
+            "## Appendix: Low-Priority Items\n\n- **[test/repo]** [OPEN] \
+             [#6](https://github.com/test/repo/issues/6) Docs typo by"
+        ));
+        assert_eq!(content.matches("test/repo/issues/6)").count(), 1);
+        assert!(content.contains("[#5](https://github.com/test/repo/pull/5) io_uring driver"));
+    }
+
+    #[test]
+    fn test_resume_interrupted_report() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        cache.initialize().unwrap();
+        let since = Timestamp::now() - 48.hours();
+        let mut run = PartialRun::new("org=test users= repos=".to_string(), since);
+        run.repos.insert(
+            "test/repo".to_string(),
+            vec![crate::test_utils::create_test_issue(
+                9,
+                "Fetched before the outage",
+                false,
+            )],
+        );
+        cache.cache_partial_run(&run).unwrap();
+
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+        generator.cache_manager = Some(CacheManager::new(temp_dir.path().to_path_buf(), 24, false));
+        let generator = generator.with_resume();
+        assert_eq!(generator.resumed_since(), Some(since));
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("Fetched before the outage"));
+        // A completed report leaves nothing to resume
+        assert!(cache.get_partial_run().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_watch_rules_section() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(5, "io_uring driver", true),
+            crate::test_utils::create_test_issue(6, "Timer regression", false),
+        ];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config
+            .watch_rules
+            .insert("performance".to_string(), vec!["regression".to_string()]);
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate(7).unwrap();
+        assert!(report.content.contains(
+            "## 👀 Matched Watch Rules\n\n### performance\n\n- **[test/repo]** [OPEN] \
+             [#6](https://github.com/test/repo/issues/6) Timer regression by"
+        ));
+        // The boost ranks the matching issue above the otherwise higher-scoring PR
+        let scores = report.scores.unwrap();
+        assert_eq!(scores.items[0].number, 6);
+        assert_eq!(scores.items[0].matched_rules, ["performance"]);
+    }
+
+    #[test]
+    fn test_mentions_section() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            5,
+            "io_uring driver",
+            true,
+        )];
+        let mut elsewhere =
+            crate::test_utils::create_test_issue(12, "Timer wheel regression", false);
+        elsewhere.url = "https://github.com/other/project/issues/12".to_string();
+        mock.mentions = vec![
+            ("carllerche".to_string(), elsewhere.clone()),
+            ("@tokio-rs/maintainers".to_string(), elsewhere),
+        ];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        config.settings.include_mentions = vec![
+            "carllerche".to_string(),
+            "@tokio-rs/maintainers".to_string(),
+        ];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 📣 Mentions\n\n- **[other/project]** [OPEN] \
+             [#12](https://github.com/other/project/issues/12) Timer wheel regression by"
+        ));
+        assert!(content.contains("(mentions carllerche, @tokio-rs/maintainers)\n"));
+        assert_eq!(content.matches("other/project/issues/12)").count(), 1);
+    }
+
+    #[test]
+    fn test_releases_section() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let recent = Timestamp::now() - 1.hour();
+        let event = |id: u32, event_type: &str, payload| -> crate::github::ActivityEvent {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "type": event_type,
+                "actor": { "login": "testuser" },
+                "repo": { "id": 1, "name": "test/repo", "url": "" },
+                "payload": payload,
+                "created_at": recent.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![
+            event(
+                1,
+                "IssueCommentEvent",
+                serde_json::json!({ "action": "created", "issue": { "number": 1 } }),
+            ),
+            event(
+                2,
+                "ReleaseEvent",
+                serde_json::json!({ "action": "published", "release": { "id": 77 } }),
+            ),
+        ];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.releases = vec![serde_json::from_value(serde_json::json!({
+            "id": 77,
+            "tag_name": "v1.2.0",
+            "name": "Version 1.2",
+            "body": "## Added\n- `JoinSet::spawn_blocking`\n## Fixed\n- A panic in the timer",
+            "html_url": "https://github.com/test/repo/releases/tag/v1.2.0",
+        }))
+        .unwrap()];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response(
+                    "1. Adds `JoinSet::spawn_blocking` and fixes a timer panic.",
+                ))
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## 🚀 Releases\n\n- **[test/repo]** \
+             [Version 1.2](https://github.com/test/repo/releases/tag/v1.2.0) `v1.2.0` — \
+             Adds `JoinSet::spawn_blocking` and fixes a timer panic.\n"
+        ));
+    }
+
+    #[test]
+    fn test_releases_of_declined_repositories_are_not_summarized() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let recent = Timestamp::now() - 1.hour();
+        let event = |id: u32, repo: &str, event_type: &str, payload| {
+            serde_json::from_value::<crate::github::ActivityEvent>(serde_json::json!({
+                "id": id.to_string(),
+                "type": event_type,
+                "actor": { "login": "testuser" },
+                "repo": { "id": id, "name": repo, "url": "" },
+                "payload": payload,
+                "created_at": recent.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![
+            event(
+                1,
+                "test/repo",
+                "IssueCommentEvent",
+                serde_json::json!({ "action": "created", "issue": { "number": 1 } }),
+            ),
+            event(
+                2,
+                "other/tool",
+                "ReleaseEvent",
+                serde_json::json!({ "action": "published", "release": { "id": 77 } }),
+            ),
+        ];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.releases = vec![serde_json::from_value(serde_json::json!({
+            "id": 77,
+            "tag_name": "v0.3.0",
+            "name": "Version 0.3",
+            "body": "## Fixed\n- Internal tooling",
+            "html_url": "https://github.com/other/tool/releases/tag/v0.3.0",
+        }))
+        .unwrap()];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        config.claude.confirm_new_repos = true;
+        let mut state = State::default();
+        state.repo_trust.repos.insert("test/repo".to_string(), true);
+        state
+            .repo_trust
+            .repos
+            .insert("other/tool".to_string(), false);
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let report = generator.generate_from_activity(7).unwrap();
+        assert_eq!(report.title, "Timer panic");
+        assert!(report.content.contains(
+            "## 🚀 Releases\n\n- **[other/tool]** \
+             [Version 0.3](https://github.com/other/tool/releases/tag/v0.3.0) `v0.3.0`\n"
+        ));
+    }
+
+    #[test]
+    fn test_releases_section_of_discovered_repositories() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let mut mock = MockGitHub::new();
+        mock.search_results = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.issues = mock.search_results.clone();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "ReleaseEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "published", "release": { "id": 77 } },
+            "created_at": (Timestamp::now() - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.releases = vec![serde_json::from_value(serde_json::json!({
+            "id": 77,
+            "tag_name": "v1.2.0",
+            "name": "Version 1.2",
+            "body": "## Fixed\n- A panic in the timer",
+            "html_url": "https://github.com/test/repo/releases/tag/v1.2.0",
+        }))
+        .unwrap()];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("1. Fixes a timer panic."))
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 🚀 Releases\n\n- **[test/repo]** \
+             [Version 1.2](https://github.com/test/repo/releases/tag/v1.2.0) `v1.2.0` — \
+             Fixes a timer panic.\n"
+        ));
+    }
+
+    #[test]
+    fn test_items_marked_against_previous_reports() {
+        let recent = Timestamp::now() - 1.hour();
+        let event = |id: u32, number: u32| -> crate::github::ActivityEvent {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "type": "IssueCommentEvent",
+                "actor": { "login": "testuser" },
+                "repo": { "id": 1, "name": "test/repo", "url": "" },
+                "payload": { "action": "created", "issue": { "number": number } },
+                "created_at": recent.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![event(1, 1), event(2, 2), event(3, 3)];
+        mock.issues = (1..=3)
+            .map(|n| crate::test_utils::create_test_issue(n, &format!("Item {}", n), false))
+            .collect();
+        mock.issues[1].state = IssueState::Closed;
+        let config = test_config();
+        let mut state = State::default();
+        let seen = |issue: &Issue| {
+            let mut seen = crate::state::SeenItem::of(issue, recent - 24.hours());
+            seen.open = true;
+            (issue.url.clone(), seen)
+        };
+        state.record_seen([seen(&mock.issues[0]), seen(&mock.issues[1])], recent);
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate_from_activity(7).unwrap();
+        let content = &report.content;
+        assert!(content.contains("**Since previous reports**: 1 new, 1 carried over, 1 resolved\n"));
+        let line = |needle: &str| content.lines().find(|l| l.contains(needle)).unwrap();
+        assert!(line("Item 1 by").ends_with("**🔁 carried over**"));
+        assert!(line("Item 2 by").ends_with("**✅ resolved**"));
+        assert!(line("Item 3 by").ends_with("**🆕 new**"));
+        assert_eq!(report.seen_items.len(), 3);
+        assert!(!report.seen_items["https://github.com/test/repo/issues/2"].open);
+    }
+
+    #[test]
+    fn test_discussions_section() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let recent = Timestamp::now() - 1.hour();
+        let event = |id: u32, event_type: &str, payload| -> crate::github::ActivityEvent {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "type": event_type,
+                "actor": { "login": "testuser" },
+                "repo": { "id": 1, "name": "test/repo", "url": "" },
+                "payload": payload,
+                "created_at": recent.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![
+            event(
+                1,
+                "IssueCommentEvent",
+                serde_json::json!({ "action": "created", "issue": { "number": 1 } }),
+            ),
+            event(
+                2,
+                "DiscussionCommentEvent",
+                serde_json::json!({ "action": "created", "discussion": { "number": 5 } }),
+            ),
+        ];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.discussions = vec![(
+            "test/repo".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "number": 5,
+                "title": "Roadmap for 2.0",
+                "body": "What should go into 2.0?",
+                "url": "https://github.com/test/repo/discussions/5",
+                "author": "testuser",
+                "category": "Ideas",
+                "is_answered": false,
+                "created_at": recent.to_string(),
+                "updated_at": recent.to_string(),
+                "comment_count": 3,
+                "comments": [],
+            }))
+            .unwrap(),
+        )];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response(
+                    "1. Collecting ideas for 2.0, leaning towards io_uring.",
+                ))
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## 🗣️ Discussions\n\n- **[test/repo]** \
+             [Roadmap for 2.0](https://github.com/test/repo/discussions/5) (Ideas, 3 comments) — \
+             Collecting ideas for 2.0, leaning towards io_uring.\n"
+        ));
+    }
+
+    #[test]
+    fn test_summarized_activity_report_uses_configured_template() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 1 } },
+            "created_at": (Timestamp::now() - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        config.report.template =
+            Some("# Weekly notes\n\n{{ summary }}\n{{ sections.footer }}".to_string());
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let report = generator.generate_from_activity(7).unwrap();
+        assert_eq!(report.title, "Timer panic");
+        assert!(report
+            .content
+            .starts_with("# Weekly notes\n\nA timer panic was reported.\n"));
+        assert!(report.content.contains("*AI summary by "));
+        assert!(!report.content.contains("## Activity by Repository"));
+    }
+
+    #[test]
+    fn test_discussions_section_of_discovered_repositories() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let recent = Timestamp::now() - 1.hour();
+        let mut mock = MockGitHub::new();
+        mock.search_results = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.issues = mock.search_results.clone();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "DiscussionCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "discussion": { "number": 5 } },
+            "created_at": recent.to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.discussions = vec![(
+            "test/repo".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "number": 5,
+                "title": "Roadmap for 2.0",
+                "body": "What should go into 2.0?",
+                "url": "https://github.com/test/repo/discussions/5",
+                "author": "testuser",
+                "category": "Ideas",
+                "is_answered": false,
+                "created_at": recent.to_string(),
+                "updated_at": recent.to_string(),
+                "comment_count": 3,
+                "comments": [],
+            }))
+            .unwrap(),
+        )];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("1. Collecting ideas for 2.0."))
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 🗣️ Discussions\n\n- **[test/repo]** \
+             [Roadmap for 2.0](https://github.com/test/repo/discussions/5) (Ideas, 3 comments) — \
+             Collecting ideas for 2.0.\n"
+        ));
+    }
+
+    #[test]
+    fn test_discussions_of_declined_repositories_are_not_summarized() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let recent = Timestamp::now() - 1.hour();
+        let mut mock = MockGitHub::new();
+        mock.search_results = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.issues = mock.search_results.clone();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "DiscussionCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 2, "name": "other/forum", "url": "" },
+            "payload": { "action": "created", "discussion": { "number": 5 } },
+            "created_at": recent.to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.discussions = vec![(
+            "other/forum".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "number": 5,
+                "title": "Hiring plans",
+                "body": "Who should we hire next?",
+                "url": "https://github.com/other/forum/discussions/5",
+                "author": "testuser",
+                "category": "General",
+                "is_answered": false,
+                "created_at": recent.to_string(),
+                "updated_at": recent.to_string(),
+                "comment_count": 2,
+                "comments": [],
+            }))
+            .unwrap(),
+        )];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        config.claude.confirm_new_repos = true;
+        let mut state = State::default();
+        state.repo_trust.repos.insert("test/repo".to_string(), true);
+        state
+            .repo_trust
+            .repos
+            .insert("other/forum".to_string(), false);
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 🗣️ Discussions\n\n- **[other/forum]** \
+             [Hiring plans](https://github.com/other/forum/discussions/5) (General, 2 comments)\n"
+        ));
+    }
+
+    #[test]
+    fn test_project_status_section() {
+        let recent = Timestamp::now() - 1.hour();
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 1 } },
+            "created_at": recent.to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        let item = |number: u32, title: &str| crate::github::ProjectItem {
+            repo: "test/repo".to_string(),
+            number,
+            title: title.to_string(),
+            url: format!("https://github.com/test/repo/issues/{}", number),
+            is_pull_request: false,
+            state: IssueState::Open,
+            status: Some("In Progress".to_string()),
+        };
+        mock.projects = vec![ProjectBoard {
+            title: "Roadmap".to_string(),
+            url: "https://github.com/orgs/test/projects/5".to_string(),
+            statuses: vec!["Todo".to_string(), "In Progress".to_string()],
+            items: vec![item(9, "Quiet work"), item(1, "Timer panic")],
+        }];
+        let mut config = test_config();
+        config.report.projects = vec![
+            "https://github.com/orgs/test/projects/5".to_string(),
+            "https://github.com/orgs/test/projects/6".to_string(),
+        ];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## 📋 Project Status\n\n\
+             ### [Roadmap](https://github.com/orgs/test/projects/5)\n\n\
+             **In Progress** (2) — 1 active\n\n\
+             - [test/repo#1](https://github.com/test/repo/issues/1) Timer panic **⚡ active**\n\
+             - [test/repo#9](https://github.com/test/repo/issues/9) Quiet work\n"
+        ));
+        assert!(
+            content.contains("Could not fetch the project https://github.com/orgs/test/projects/6")
+        );
+    }
+
+    #[test]
+    fn test_upcoming_deadlines_section() {
+        let now = Timestamp::now();
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 1 } },
+            "created_at": (now - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        let milestone = |title: &str, due_on: Timestamp| crate::github::RepoMilestone {
+            number: 1,
+            title: title.to_string(),
+            html_url: format!("https://github.com/test/repo/milestone/{}", title),
+            due_on: Some(due_on),
+            open_issues: 2,
+            closed_issues: 5,
+        };
+        mock.milestones = vec![
+            ("test/repo".to_string(), milestone("v1.0", now + 72.hours())),
+            (
+                "test/repo".to_string(),
+                milestone("v2.0", now + 720.hours()),
+            ),
+            (
+                "other/repo".to_string(),
+                milestone("v0.1", now + 24.hours()),
+            ),
+        ];
+        let config = test_config();
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## 📅 Upcoming Deadlines\n\n\
+             - **[test/repo]** [v1.0](https://github.com/test/repo/milestone/v1.0) due "
+        ));
+        assert!(content.contains(" (in 3 days) — 2 open, 5 closed\n\n"));
+        assert!(!content.contains("milestone/v2.0"));
+        assert!(!content.contains("milestone/v0.1"));
+    }
+
+    /// A mock with a heated comment thread on test/repo#1
+    fn heated_thread_mock() -> MockGitHub {
+        let now = Timestamp::now();
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 1 } },
+            "created_at": (now - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        for (i, (login, body)) in [
+            ("alice", "Timers panic after an hour."),
+            ("bob", "Works for me, can you share a reproduction?"),
+            ("alice", "This is still broken and frankly ridiculous."),
+            ("bob", "You people never read the docs. Unacceptable tone."),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let at = now - (5 - i as i64).hours();
+            mock.comments.push(crate::github::Comment {
+                id: i as u64 + 1,
+                body: body.to_string(),
+                author: crate::github::Author {
+                    login: login.to_string(),
+                    user_type: Some("User".to_string()),
+                },
+                created_at: at,
+                updated_at: at,
+            });
+        }
+        mock
+    }
+
+    #[test]
+    fn test_escalations_by_keywords() {
+        let mut config = test_config();
+        config.intelligence.escalation = EscalationDetection::Keywords;
+        let state = State::default();
+        let mut generator =
+            ReportGenerator::new(GitHubClient::Mock(heated_thread_mock()), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## 🧯 Threads Needing De-escalation\n\n\
+             - **[test/repo]** Issue [#1](https://github.com/test/repo/issues/1) - Timer panic \
+             — 🔴 Heated: Heated exchange between @alice and @bob (2 heated comments: \
+             ridiculous, still broken, unacceptable)\n"
+        ));
+
+        // Off by default
+        let config = test_config();
+        let mut generator =
+            ReportGenerator::new(GitHubClient::Mock(heated_thread_mock()), &config, &state);
+        generator.llm = None;
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(!content.contains("De-escalation"));
+    }
+
+    #[test]
+    fn test_escalations_by_claude() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let mut config = test_config();
+        config.intelligence.escalation = EscalationDetection::Claude;
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator =
+            ReportGenerator::new(GitHubClient::Mock(heated_thread_mock()), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response(
+                    "1. TENSE: @alice is losing patience with the requests for a reproduction.",
+                ))
+                .with_response(text_response("A timer panic is being debugged."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## 🧯 Threads Needing De-escalation\n\n\
+             - **[test/repo]** Issue [#1](https://github.com/test/repo/issues/1) - Timer panic \
+             — 🟠 Tense: @alice is losing patience with the requests for a reproduction.\n"
+        ));
+    }
+
+    #[test]
+    fn test_new_contributors_section() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        let mut issue = crate::test_utils::create_test_issue(1, "Timer panic", false);
+        issue.author.login = "newcomer".to_string();
+        issue.author_association = Some("FIRST_TIME_CONTRIBUTOR".to_string());
+        mock.issues = vec![
+            issue,
+            crate::test_utils::create_test_issue(2, "Slow shutdown", false),
+        ];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "## 👋 New Contributors\n\n\
+             - **[test/repo]** Issue [#1](https://github.com/test/repo/issues/1) - Timer panic \
+             by [@newcomer](https://github.com/newcomer), opened "
+        ));
+        assert!(
+            !content.contains("Slow shutdown by [@testuser](https://github.com/testuser), opened")
+        );
+        assert!(content.contains("Timer panic (Score: 65)\n- **[test/repo]** Issue [#2]"));
+    }
+
+    #[test]
+    fn test_weekly_rollup_comparison() {
+        let mut mock = MockGitHub::new();
+        mock.repositories = vec![mock_repo("test/repo")];
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(1, "Timer panic", false),
+            crate::test_utils::create_test_issue(2, "Slow shutdown", false),
+        ];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        let state = State::default();
+        let now = Timestamp::now();
+        let previous = HistoryEntry {
+            flavor: ReportFlavor::Weekly,
+            since: now - (14 * 24).hours(),
+            until: now - (7 * 24).hours(),
+            totals: PeriodTotals {
+                opened: 3,
+                closed: 1,
+                merged: 0,
+            },
+        };
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state)
+            .with_flavor(ReportFlavor::Weekly, Some(previous));
+        generator.llm = None;
+
+        let report = generator.generate(7).unwrap();
+        assert_eq!(report.totals.opened, 2);
+        assert!(report.title.starts_with("Weekly Rollup - "));
+        assert!(report.content.starts_with("# Weekly GitHub Rollup\n"));
+        assert!(report.content.contains(
+            "## 📈 Compared to Last Week\n\n\
+             - **Opened**: 2 (-1 from 3)\n\
+             - **Closed**: 0 (-1 from 1)\n\
+             - **Merged**: 0 (no change)\n"
+        ));
+
+        let mut generator =
+            ReportGenerator::new(GitHubClient::Mock(MockGitHub::new()), &config, &state)
+                .with_flavor(ReportFlavor::Monthly, None);
+        generator.llm = None;
+        let content = generator.generate(30).unwrap().content;
+        assert!(content.contains(
+            "## 📈 This Month\n\n- **Opened**: 0\n- **Closed**: 0\n- **Merged**: 0\n\n\
+             *No earlier monthly report to compare with yet.*\n"
+        ));
+    }
+
+    #[test]
+    fn test_hook_notes_section() {
+        let mut mock = MockGitHub::new();
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        let mut config = test_config();
+        config.report.org = Some("test".to_string());
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state)
+            .with_hook_notes(vec!["On call: **@alice**\n".to_string()]);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains("## 📝 Notes\n\nOn call: **@alice**\n\n"));
+        assert_eq!(
+            generator
+                .with_hook_notes_context(Some("I maintain tokio"))
+                .unwrap(),
+            "I maintain tokio\n\nNotes from the user's pre-report hooks:\n\nOn call: **@alice**\n"
+        );
+    }
+
+    #[test]
+    fn test_reviewer_workload_section() {
+        let now = Timestamp::now();
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 1 } },
+            "created_at": (now - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        mock.review_queues = vec![(
+            "test/repo".to_string(),
+            crate::github::ReviewQueue {
+                can_write: true,
+                pull_requests: vec![crate::github::PendingReviewPr {
+                    number: 7,
+                    title: "Add timeout".to_string(),
+                    url: "https://github.com/test/repo/pull/7".to_string(),
+                    author: "contributor".to_string(),
+                    is_draft: false,
+                    created_at: now - 100.hours(),
+                    requests: vec![crate::github::ReviewRequest {
+                        reviewer: "alice".to_string(),
+                        requested_at: now - 100.hours(),
+                    }],
+                }],
+            },
+        )];
+        let config = test_config();
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(
+            "## ⚖️ Reviewer Workload\n\n\
+             **[@alice](https://github.com/alice)**: 1 PR, longest waiting 4 days\n\n\
+             - **[test/repo]** PR [#7](https://github.com/test/repo/pull/7) - Add timeout \
+             by [@contributor](https://github.com/contributor) (waiting 4 days)\n"
+        ));
+    }
+
+    #[test]
+    fn test_diffs_are_cached_by_head_commit() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let diff: PrDiff = serde_json::from_value(serde_json::json!({
+            "files": [
+                { "filename": "src/io/uring.rs", "status": "added", "additions": 300,
+                  "deletions": 0, "changes": 300, "patch": "+".repeat(300) },
+                { "filename": "README.md", "status": "modified", "additions": 2,
+                  "deletions": 0, "changes": 2, "patch": "+a\n+b" },
+            ],
+            "total_additions": 302,
+            "total_deletions": 0,
+            "total_files": 2,
+        }))
+        .unwrap();
+        let mut config = test_config();
+        config.intelligence.max_diff_bytes = 300;
+        let state = State::default();
+        let pr = crate::test_utils::create_test_issue(5, "io_uring driver", true);
+        let mut activities = BTreeMap::new();
+        activities
+            .entry("test/repo".to_string())
+            .or_insert_with(crate::github::RepoActivity::default)
+            .new_prs
+            .push(pr);
+        let analysis = IntelligentAnalyzer::new(&config).analyze(&activities);
+
+        let generator_for = |sha: &str, diffs: Vec<(u32, PrDiff)>| {
+            let mut mock = MockGitHub::new();
+            mock.head_shas = vec![(5, sha.to_string())];
+            mock.pr_diffs = diffs;
+            let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+            let cache = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+            cache.initialize().unwrap();
+            generator.cache_manager = Some(cache);
+            generator
+        };
+
+        let fetched = generator_for("abc", vec![(5, diff)]).prefetch_diffs(&analysis, &activities);
+        assert_eq!(fetched.len(), 1);
+        let (repo, pr, diff) = &fetched[0];
+        assert_eq!((repo.as_str(), pr.number), ("test/repo", 5));
+        assert!(diff.files[0].patch.is_some());
+        assert_eq!(diff.files[1].patch, None);
+
+        // The same head commit is read from the cache
+        let cached = generator_for("abc", Vec::new()).prefetch_diffs(&analysis, &activities);
+        assert_eq!(cached[0].2.total_additions, 302);
+
+        // A force-push moves the head commit, so the diff is fetched again
+        let pushed = generator_for("def", Vec::new()).prefetch_diffs(&analysis, &activities);
+        assert!(pushed.is_empty());
+
+        // Diffs are skipped when the API quota runs low
+        let low_quota = generator_for("abc", Vec::new());
+        if let GitHubClient::Mock(mock) = &low_quota.github_client {
+            mock.rate_limiter.update(crate::github::RateLimit {
+                limit: 5000,
+                remaining: 20,
+                reset: Timestamp::now(),
+            });
+        }
+        assert!(low_quota.prefetch_diffs(&analysis, &activities).is_empty());
+    }
+
+    #[test]
+    fn test_repositories_discovered_through_search() {
+        let mut mock = MockGitHub::new();
+        mock.search_results = vec![crate::test_utils::create_test_issue(7, "Search hit", false)];
+        mock.issues = mock.search_results.clone();
+        let config = test_config();
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate(7).unwrap();
+        assert!(report.content.contains("test/repo"));
+        assert!(report.content.contains("Search hit"));
+    }
+
+    #[test]
+    fn test_truncated_feed_is_supplemented_from_search() {
+        let recent = Timestamp::now() - 1.hour();
+        let mut mock = MockGitHub::new();
+        mock.events = (0..crate::github::EVENTS_API_LIMIT as i64)
+            .map(|i| {
+                serde_json::from_value(serde_json::json!({
+                    "id": i.to_string(),
+                    "type": "IssueCommentEvent",
+                    "actor": { "login": "alice" },
+                    "repo": { "id": 1, "name": "test/repo", "url": "" },
+                    "payload": { "action": "created", "issue": { "number": 1 } },
+                    "created_at": (recent - i.seconds()).to_string(),
+                    "public": true,
+                }))
+                .unwrap()
+            })
+            .collect();
+        let older = crate::test_utils::create_test_issue(2, "Found through search", false);
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(1, "In the feed", false),
+            older.clone(),
+        ];
+        mock.search_results = vec![older];
+        let config = test_config();
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate_from_activity(7).unwrap();
+        assert!(report.content.contains("> ⚠️ **Incomplete activity feed**"));
+        assert!(report.content.contains("In the feed"));
+        assert!(report.content.contains("Found through search"));
+    }
+
+    #[test]
+    fn test_team_report() {
+        let recent = Timestamp::now() - 1.hour();
+        let event = |id: u32, actor: &str, number: u32| -> crate::github::ActivityEvent {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "type": "IssueCommentEvent",
+                "actor": { "login": actor },
+                "repo": { "id": 1, "name": "test/repo", "url": "" },
+                "payload": { "action": "created", "issue": { "number": number } },
+                "created_at": recent.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![
+            event(1, "alice", 1),
+            event(2, "bob", 2),
+            event(3, "carol", 3),
+        ];
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(1, "Alice's issue", false),
+            crate::test_utils::create_test_issue(2, "Bob's issue", false),
+            crate::test_utils::create_test_issue(3, "Carol's issue", false),
+        ];
+        let mut config = test_config();
+        config.report.users = vec!["alice".to_string(), "bob".to_string()];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let content = generator.generate(7).unwrap().content;
+        assert!(content.contains(
+            "**Team**: [@alice](https://github.com/alice), [@bob](https://github.com/bob)"
+        ));
+        assert!(content.contains("## 👥 Team"));
+        assert!(content.contains(
+            "### [@alice](https://github.com/alice)\n\n1 event: 1 comment\n\n\
+             - **[test/repo]** Issue [#1](https://github.com/test/repo/issues/1) - Alice's issue\n"
+        ));
+        assert!(content.contains("Bob's issue"));
+        assert!(!content.contains("Carol's issue"));
+    }
+
+    #[test]
+    fn test_activity_is_filtered_by_repo() {
+        let recent = Timestamp::now() - 1.hour();
+        let event = |id: u32, repo: &str| -> crate::github::ActivityEvent {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "type": "IssuesEvent",
+                "actor": { "login": "alice" },
+                "repo": { "id": 1, "name": repo, "url": "" },
+                "payload": { "action": "opened", "issue": { "number": id } },
+                "created_at": recent.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![
+            event(1, "test/repo"),
+            event(2, "test/other"),
+            event(3, "elsewhere/repo"),
+        ];
+        mock.issues = vec![
+            crate::test_utils::create_test_issue(1, "Kept", false),
+            crate::test_utils::create_test_issue(2, "Excluded", false),
+            crate::test_utils::create_test_issue(3, "Not included", false),
+        ];
+        let mut config = test_config();
+        config.report.include_repos = vec!["test/*".to_string()];
+        config.report.exclude_repos = vec!["TEST/other".to_string()];
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+
+        let report = generator.generate_from_activity(7).unwrap();
+        assert!(report.content.contains("Kept"));
+        assert!(!report.content.contains("Excluded"));
+        assert!(!report.content.contains("Not included"));
+    }
+
+    #[test]
+    fn test_since_last_report_window() {
+        let github_client = GitHubClient::Mock(MockGitHub::new());
+        let config = test_config();
+        let state = State::default();
+        let last_run: Timestamp = "2024-01-15T08:30:00Z".parse().unwrap();
+
+        let generator =
+            ReportGenerator::new(github_client, &config, &state).with_since_last_report(last_run);
+        let report = generator.generate(7).unwrap();
+
+        assert!(report.content.contains("**Period**: 2024-01-15 08:30 to "));
+        assert!(report.content.contains("(since last report)"));
+    }
+
+    #[test]
+    fn test_activity_report_sections_match_discovery_report() {
+        use crate::claude::{ClaudeClient, MockClaude};
+
+        let last_run = Timestamp::now() - 48.hours();
+        let mut mock = MockGitHub::new();
+        mock.events = vec![serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "testuser" },
+            "repo": { "id": 1, "name": "test/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 1 } },
+            "created_at": (Timestamp::now() - 1.hour()).to_string(),
+            "public": true,
+        }))
+        .unwrap()];
+        mock.issues = vec![crate::test_utils::create_test_issue(
+            1,
+            "Timer panic",
+            false,
+        )];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        config.report.group_by = GroupBy::Org;
+        let state = State::default();
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state)
+            .with_since_last_report(last_run);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response("Timer work dominated."))
+                .with_response(text_response("A timer panic was reported."))
+                .with_response(text_response("Timer panic")),
+        )));
+
+        let content = generator.generate_from_activity(7).unwrap().content;
+        assert!(content.contains(&format!(
+            "**Period**: {} to ",
+            config.report.locale().format_datetime(last_run)
+        )));
+        assert!(content.contains("(since last report)"));
+        assert!(content.contains("> Timer work dominated.\n"));
+        assert!(content.contains("*AI summary by "));
+    }
+
+    #[test]
+    fn test_fixed_window() {
+        let github_client = GitHubClient::Mock(MockGitHub::new());
+        let config = test_config();
+        let state = State::default();
+        let start: Timestamp = "2024-05-01T00:00:00Z".parse().unwrap();
+        let until: Timestamp = "2024-05-08T00:00:00Z".parse().unwrap();
+
+        let mut generator =
+            ReportGenerator::new(github_client, &config, &state).with_window(start, Some(until));
+        generator.llm = None;
+        let report = generator.generate(30).unwrap();
+
+        assert_eq!(report.timestamp, until);
+        assert!(report
+            .content
+            .contains("**Period**: 2024-05-01 00:00 to 2024-05-08 00:00"));
+    }
+
+    #[test]
+    fn test_feed_ends_at_window_end() {
+        let until = Timestamp::now() - 48.hours();
+        let event = |id: u32, created_at: Timestamp| -> crate::github::ActivityEvent {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "type": "IssueCommentEvent",
+                "actor": { "login": "testuser" },
+                "repo": { "id": 1, "name": "test/repo", "url": "" },
+                "payload": { "action": "created", "issue": { "number": id } },
+                "created_at": created_at.to_string(),
+                "public": true,
+            }))
+            .unwrap()
+        };
+        let mut mock = MockGitHub::new();
+        mock.events = vec![event(1, until - 1.hour()), event(2, until + 1.hour())];
+        let config = test_config();
+        let state = State::default();
+        let generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state)
+            .with_window(until - 72.hours(), Some(until));
+
+        let feed = generator.fetch_feed(7).unwrap();
+        let ids: Vec<_> = feed.events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["1"]);
+    }
+
+    #[test]
+    fn test_report_from_snapshot_does_not_fetch() {
+        use crate::snapshot::{SnapshotItem, SNAPSHOT_VERSION};
+        use crate::test_utils::create_test_issue;
+
+        // Every GitHub call would fail, so the report has to come from the snapshot
+        let mut mock = MockGitHub::new();
+        mock.auth_expired = true;
+        let config = test_config();
+        let state = State::default();
+        let captured_at: Timestamp = "2024-03-06T12:00:00Z".parse().unwrap();
+        let snapshot = ActivitySnapshot {
+            version: SNAPSHOT_VERSION,
+            captured_at,
+            lookback_days: 2,
+            user: Some("testuser".to_string()),
+            events: Vec::new(),
+            items: vec![SnapshotItem {
+                issue: create_test_issue(42, "Snapshot issue", false),
+                comments: Vec::new(),
+                reviews: Vec::new(),
+            }],
+            errors: Vec::new(),
+        };
+
+        let mut generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        generator.llm = None;
+        let report = generator.generate_from_snapshot(&snapshot).unwrap();
+
+        assert_eq!(report.timestamp, captured_at);
+        assert!(report.content.contains("Snapshot issue"));
+        assert!(report.content.contains("**Period**: 2024-03-04 12:00 to "));
+    }
+
+    #[test]
+    fn test_waiting_on_you_with_drafted_nudge() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::snapshot::{SnapshotItem, SNAPSHOT_VERSION};
+        use crate::test_utils::create_test_issue;
+
+        let now = Timestamp::now();
+        let mut pr = create_test_issue(12, "Add timeout support", true);
+        pr.updated_at = now - (5_i64 * 24).hours();
+        pr.requested_reviewers.push(crate::github::Author {
+            login: "me".to_string(),
+            user_type: None,
+        });
+        let snapshot = ActivitySnapshot {
+            version: SNAPSHOT_VERSION,
+            captured_at: now,
+            lookback_days: 7,
+            user: Some("me".to_string()),
+            events: Vec::new(),
+            items: vec![SnapshotItem {
+                issue: pr,
+                comments: Vec::new(),
+                reviews: Vec::new(),
+            }],
+            errors: Vec::new(),
+        };
+
+        let mut config = test_config();
+        config.settings.draft_nudges = true;
+        config.claude.allow_private_repos = true;
+        let state = State::default();
+        let mut generator =
+            ReportGenerator::new(GitHubClient::Mock(MockGitHub::new()), &config, &state);
+        generator.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new()
+                .with_response(text_response(
+                    "Sorry for the wait, reviewing this tomorrow.",
+                ))
+                .with_response(text_response("One PR is waiting on your review."))
+                .with_response(text_response("Review pending")),
+        )));
+
+        let content = generator.generate_from_snapshot(&snapshot).unwrap().content;
+        assert!(content.contains("## ⏰ Waiting on You"));
+        assert!(content.contains(
+            "#12](https://github.com/test/repo/pull/12) - Add timeout support \
+             ([@testuser](https://github.com/testuser) waiting for 5 days)"
+        ));
+        assert!(content.contains("  > Sorry for the wait, reviewing this tomorrow."));
+    }
+
+    #[test]
+    fn test_expired_auth_fails_the_run() {
+        let mut mock = MockGitHub::new();
+        mock.auth_expired = true;
+        let config = test_config();
+        let state = State::default();
+
+        let generator = ReportGenerator::new(GitHubClient::Mock(mock), &config, &state);
+        let error = generator.generate(1).err().unwrap();
+        assert!(is_auth_expired(&error));
+        assert_eq!(
+            error.to_string(),
+            "GitHub authentication expired — run gh auth login"
+        );
     }
 }
@@ -0,0 +1,1270 @@
+//! HTML rendering of reports
+
+use anyhow::Result;
+use jiff::Timestamp;
+use pulldown_cmark::{html, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::milestone::group_by_milestone;
+use super::org::group_by_org;
+use super::projects::{active_items, item_state, status_groups, MAX_ITEMS_PER_STATUS};
+use super::team::MAX_ITEMS_PER_PERSON;
+use super::template::{by_rule, discussion_status, find_issue, has_items};
+use super::ReportTemplate;
+use crate::config::GroupBy;
+use crate::github::{Issue, IssueState, Label, RepoActivity};
+use crate::intelligence::{AnalysisResult, MAX_PRS_PER_REVIEWER};
+
+const STYLE: &str = "
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Helvetica, Arial, sans-serif; line-height: 1.5; color: #1f2328; margin: 0; }
+main { max-width: 960px; margin: 0 auto; padding: 2rem 1rem; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+.meta { color: #59636e; }
+section.repo, section.org { border-top: 1px solid #d1d9e0; margin-top: 1.5rem; }
+section.org section.repo { border-top: none; }
+blockquote { color: #59636e; border-left: 0.25rem solid #d1d9e0; margin: 0; padding: 0 1rem; }
+ul.items { list-style: none; padding-left: 0; }
+ul.items > li { margin: 0.35rem 0; }
+.label { display: inline-block; padding: 0 0.5rem; border-radius: 1rem; font-size: 0.75rem; font-weight: 500; background-color: #eaeef2; margin-left: 0.25rem; }
+.state, .urgency { display: inline-block; padding: 0 0.4rem; border-radius: 0.3rem; font-size: 0.75rem; font-weight: 600; color: #ffffff; }
+.state-open { background-color: #1f883d; }
+.state-closed { background-color: #cf222e; }
+.state-merged { background-color: #8250df; }
+.urgency-critical { background-color: #cf222e; }
+.urgency-high { background-color: #bc4c00; }
+.urgency-medium { background-color: #9a6700; }
+.urgency-low { background-color: #59636e; }
+.repo-name { font-weight: 600; }
+.badge { font-weight: 600; margin-left: 0.25rem; }
+pre { background-color: #f6f8fa; padding: 0.75rem; overflow-x: auto; }
+footer { color: #59636e; border-top: 1px solid #d1d9e0; margin-top: 2rem; }
+";
+
+/// Render a report as a standalone HTML page
+///
+/// Mirrors the sections of the Markdown report. The AI highlights, which
+/// come back as Markdown, are converted with a small subset of the syntax.
+pub(super) fn render(
+    template: &ReportTemplate,
+    activities: &BTreeMap<String, RepoActivity>,
+    since: Timestamp,
+    now: Timestamp,
+    errors: &[String],
+    ai_summary: Option<&str>,
+    analysis: &AnalysisResult,
+) -> Result<String> {
+    let mut output = String::new();
+
+    writeln!(output, "<!DOCTYPE html>")?;
+    writeln!(output, "<html lang=\"en\">")?;
+    writeln!(output, "<head>")?;
+    writeln!(output, "<meta charset=\"utf-8\">")?;
+    writeln!(
+        output,
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+    )?;
+    writeln!(
+        output,
+        "<title>{} - {}</title>",
+        escape(&template.heading()),
+        template.locale.format_date(now)
+    )?;
+    writeln!(output, "<style>{}</style>", STYLE)?;
+    writeln!(output, "</head>")?;
+    writeln!(output, "<body>")?;
+    writeln!(output, "<main>")?;
+
+    writeln!(output, "<h1>{}</h1>", escape(&template.heading()))?;
+    writeln!(
+        output,
+        "<p class=\"meta\"><strong>Period</strong>: {} to {}{}<br>\n<strong>Generated</strong>: {}</p>",
+        template.locale.format_datetime(since),
+        template.locale.format_datetime(now),
+        if template.since_last_report {
+            " (since last report)"
+        } else {
+            ""
+        },
+        template.locale.format_datetime_with_seconds(now)
+    )?;
+    if let Some(org) = template.org() {
+        writeln!(
+            output,
+            "<p class=\"meta\"><strong>Organization</strong>: <a href=\"https://github.com/{}\">{}</a></p>",
+            escape(org),
+            escape(org)
+        )?;
+    }
+    if !template.team.is_empty() {
+        let members: Vec<String> = template
+            .team
+            .iter()
+            .map(|person| user_link(&person.login))
+            .collect();
+        writeln!(
+            output,
+            "<p class=\"meta\"><strong>Team</strong>: {}</p>",
+            members.join(", ")
+        )?;
+    }
+    if let Some(counts) = template.seen_counts() {
+        writeln!(
+            output,
+            "<p class=\"meta\"><strong>Since previous reports</strong>: {}</p>",
+            escape(&counts)
+        )?;
+    }
+    if let Some(filtered) = template.label_filter_counts() {
+        writeln!(
+            output,
+            "<p class=\"meta\"><strong>Filtered by label</strong>: {}</p>",
+            escape(&filtered)
+        )?;
+    }
+    if let Some(bots) = template.bot_activity() {
+        writeln!(
+            output,
+            "<p class=\"meta\"><strong>Bot activity</strong>: {} left out</p>",
+            escape(&bots)
+        )?;
+    }
+    if let Some(muted) = template.muted_items() {
+        writeln!(
+            output,
+            "<p class=\"meta\"><strong>Muted</strong>: {} left out</p>",
+            escape(&muted)
+        )?;
+    }
+    if let Some(warning) = template.feed_warning_text() {
+        writeln!(
+            output,
+            "<blockquote>⚠️ <strong>Incomplete activity feed</strong>: {}</blockquote>",
+            escape(&warning)
+        )?;
+    }
+
+    if !errors.is_empty() {
+        writeln!(output, "<h2>Warnings</h2>\n<ul>")?;
+        for error in errors {
+            writeln!(output, "<li>{}</li>", escape(error))?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if let Some(comparison) = &template.comparison {
+        writeln!(
+            output,
+            "<h2>📈 {}</h2>\n<ul>",
+            escape(&ReportTemplate::comparison_heading(comparison))
+        )?;
+        for (label, text) in template.comparison_counts(comparison) {
+            writeln!(
+                output,
+                "<li><strong>{}</strong>: {}</li>",
+                escape(label),
+                escape(&text)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+        if let Some(note) = ReportTemplate::comparison_note(comparison) {
+            writeln!(output, "<p><em>{}</em></p>", escape(&note))?;
+        }
+    }
+
+    if !template.notes.is_empty() {
+        writeln!(output, "<h2>📝 Notes</h2>")?;
+        for note in &template.notes {
+            output.push_str(&markdown_to_html(note, true));
+        }
+    }
+
+    let enabled = template.sections();
+    if enabled.action_items && !analysis.action_items.is_empty() {
+        writeln!(output, "<h2>Action Items</h2>\n<ol>")?;
+        for action in &analysis.action_items {
+            let text = action.urgency.label();
+            let class = text.to_lowercase();
+            write!(
+                output,
+                "<li><span class=\"urgency urgency-{}\">{}</span> {} - {}",
+                class,
+                text,
+                escape(&action.description),
+                escape(&action.reason)
+            )?;
+            if let Some(excerpt) = &action.excerpt {
+                write!(
+                    output,
+                    "<blockquote>{} — <a href=\"{}\">@{}</a></blockquote>",
+                    escape(&excerpt.text),
+                    escape(&excerpt.url),
+                    escape(&excerpt.author)
+                )?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ol>")?;
+    }
+
+    if !template.awaiting_reply.is_empty() {
+        writeln!(
+            output,
+            "<h2>💬 Awaiting Your Reply</h2>\n<ul class=\"items\">"
+        )?;
+        for reply in &template.awaiting_reply {
+            writeln!(
+                output,
+                "<li>{}: <a href=\"{}\">@{}</a> {}{} ({})<blockquote>{}</blockquote></li>",
+                item_link(&reply.repo, &reply.issue),
+                escape(&reply.url),
+                escape(&reply.author),
+                escape(reply.reason.describe()),
+                reply
+                    .location
+                    .as_ref()
+                    .map(|location| format!(" on <code>{}</code>", escape(location)))
+                    .unwrap_or_default(),
+                template.locale.format_date(reply.created_at),
+                escape(&reply.text)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.escalations.is_empty() {
+        writeln!(
+            output,
+            "<h2>🧯 Threads Needing De-escalation</h2>\n<ul class=\"items\">"
+        )?;
+        for escalation in &template.escalations {
+            writeln!(
+                output,
+                "<li>{} — {}: {}</li>",
+                item_link(&escalation.repo, &escalation.issue),
+                escalation.level.label(),
+                escape(&escalation.reason)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !analysis.waiting_on_you.is_empty() {
+        writeln!(output, "<h2>⏰ Waiting on You</h2>\n<ul class=\"items\">")?;
+        for item in &analysis.waiting_on_you {
+            write!(
+                output,
+                "<li>{} ({} waiting for {} days)",
+                item_link(&item.repo, &item.issue),
+                user_link(&item.from),
+                template.locale.format_count(item.idle_days)
+            )?;
+            if let Some(nudge) = &item.nudge {
+                write!(
+                    output,
+                    "<details><summary>Draft reply</summary>\n<pre>{}</pre>\n</details>",
+                    escape(nudge)
+                )?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !analysis.waiting_on_author.is_empty() {
+        writeln!(
+            output,
+            "<h2>⏳ Waiting on Others</h2>\n<ul class=\"items\">"
+        )?;
+        for item in &analysis.waiting_on_author {
+            writeln!(
+                output,
+                "<li>{} ({}, no response from {} for {} days)</li>",
+                item_link(&item.repo, &item.issue),
+                escape(item.reason.describe()),
+                user_link(&item.issue.author.login),
+                template.locale.format_count(item.idle_days)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.reviewer_workload.is_empty() {
+        writeln!(output, "<h2>⚖️ Reviewer Workload</h2>")?;
+        for load in &template.reviewer_workload {
+            let reviewer = if load.reviewer.contains('/') {
+                format!("@{} (team)", escape(&load.reviewer))
+            } else {
+                user_link(&load.reviewer)
+            };
+            writeln!(
+                output,
+                "<p><strong>{}</strong>: {}</p>\n<ul class=\"items\">",
+                reviewer,
+                escape(&template.reviewer_summary(load))
+            )?;
+            for review in load.reviews.iter().take(MAX_PRS_PER_REVIEWER) {
+                writeln!(
+                    output,
+                    "<li><span class=\"repo-name\">{}</span> <a href=\"{}\">#{}</a> {} by {} (waiting {} days)</li>",
+                    escape(&review.repo),
+                    escape(&review.url),
+                    review.number,
+                    escape(&review.title),
+                    user_link(&review.author),
+                    template.locale.format_count(review.waiting_days)
+                )?;
+            }
+            if load.reviews.len() > MAX_PRS_PER_REVIEWER {
+                writeln!(
+                    output,
+                    "<li>…and {} more</li>",
+                    load.reviews.len() - MAX_PRS_PER_REVIEWER
+                )?;
+            }
+            writeln!(output, "</ul>")?;
+        }
+    }
+
+    if !analysis.new_contributions.is_empty() {
+        writeln!(output, "<h2>👋 New Contributors</h2>\n<ul class=\"items\">")?;
+        for contribution in &analysis.new_contributions {
+            writeln!(
+                output,
+                "<li>{} by {}, opened {}</li>",
+                item_link(&contribution.repo, &contribution.issue),
+                user_link(&contribution.issue.author.login),
+                template.locale.format_date(contribution.issue.created_at)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !analysis.needs_info.is_empty() {
+        writeln!(output, "<h2>📝 Needs More Info</h2>\n<ul class=\"items\">")?;
+        for item in &analysis.needs_info {
+            let missing: Vec<&str> = item.missing.iter().map(|s| s.describe()).collect();
+            writeln!(
+                output,
+                "<li>{} (missing: {}; consider labelling <code>needs-info</code>)",
+                item_link(&item.repo, &item.issue),
+                escape(&missing.join(", "))
+            )?;
+            writeln!(
+                output,
+                "<details><summary>Draft comment</summary>\n<pre>{}</pre>\n</details></li>",
+                escape(&item.draft_comment)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    let ci_attention = template.ci_attention(activities);
+    if !ci_attention.is_empty() {
+        writeln!(
+            output,
+            "<h2>🚨 Needs CI Attention</h2>\n<ul class=\"items\">"
+        )?;
+        for (repo, pr, status) in ci_attention {
+            writeln!(
+                output,
+                "<li>{} (failing: {})</li>",
+                item_link(repo, pr),
+                escape(&status.failing.join(", "))
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.ci_failures.is_empty() {
+        writeln!(output, "<h2>🔴 CI Failures</h2>\n<ul class=\"items\">")?;
+        for failure in &template.ci_failures {
+            writeln!(
+                output,
+                "<li>{} (<a href=\"{}\">{} run</a>)",
+                item_link(&failure.repo, &failure.pr),
+                escape(&failure.run.html_url),
+                escape(failure.run.name.as_deref().unwrap_or("workflow"))
+            )?;
+            if let Some(explanation) = &failure.explanation {
+                writeln!(output, "<br>💡 Probable cause: {}", escape(explanation))?;
+            }
+            if let Some(excerpt) = &failure.excerpt {
+                writeln!(
+                    output,
+                    "<details><summary>Log excerpt</summary>\n<pre>{}</pre>\n</details>",
+                    escape(excerpt)
+                )?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.notifications.is_empty() {
+        writeln!(output, "<h2>🔔 Notifications</h2>\n<ul class=\"items\">")?;
+        for notification in &template.notifications {
+            let (Some(number), Some(url)) = (
+                notification.item_number(),
+                notification.html_url(template.github_host()),
+            ) else {
+                continue;
+            };
+            writeln!(
+                output,
+                "<li><span class=\"repo-name\">{}</span> {} <a href=\"{}\">#{}</a> - {} ({}, {})</li>",
+                escape(&notification.repository.full_name),
+                if notification.is_pull_request() {
+                    "PR"
+                } else {
+                    "Issue"
+                },
+                escape(&url),
+                number,
+                escape(&notification.subject.title),
+                escape(notification.describe_reason()),
+                template.locale.format_date(notification.updated_at)
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.mentions.is_empty() {
+        writeln!(output, "<h2>📣 Mentions</h2>\n<ul class=\"items\">")?;
+        for mention in &template.mentions {
+            writeln!(
+                output,
+                "<li>{} (mentions {})</li>",
+                item_link(&mention.repo, &mention.issue),
+                escape(&mention.targets.join(", "))
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !analysis.matched_rules.is_empty() {
+        writeln!(output, "<h2>👀 Matched Watch Rules</h2>")?;
+        for (rule, items) in by_rule(&analysis.matched_rules) {
+            writeln!(output, "<h3>{}</h3>\n<ul class=\"items\">", escape(rule))?;
+            for item in items {
+                writeln!(output, "<li>{}</li>", item_link(&item.repo, &item.issue))?;
+            }
+            writeln!(output, "</ul>")?;
+        }
+    }
+
+    if !template.local_work.is_empty() {
+        writeln!(
+            output,
+            "<h2>🧵 Your local work in progress</h2>\n<ul class=\"items\">"
+        )?;
+        for work in &template.local_work {
+            let branch = &work.branch;
+            let status = match (branch.unpushed_commits, &branch.upstream) {
+                (0, _) => String::new(),
+                (1, Some(_)) => " - 1 unpushed commit".to_string(),
+                (n, Some(_)) => format!(" - {} unpushed commits", n),
+                (1, None) => " - 1 commit, never pushed".to_string(),
+                (n, None) => format!(" - {} commits, never pushed", n),
+            };
+            let links: Vec<String> = work
+                .items
+                .iter()
+                .map(|item| {
+                    let link = format!("<a href=\"{}\">#{}</a>", escape(&item.url), item.number);
+                    match &item.title {
+                        Some(title) => format!("{} {}", link, escape(title)),
+                        None => link,
+                    }
+                })
+                .collect();
+
+            write!(
+                output,
+                "<li><code>{}</code>{}",
+                escape(&branch.name),
+                status
+            )?;
+            if !links.is_empty() {
+                write!(output, " → {}", links.join(", "))?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if let Some(summary) = ai_summary.filter(|_| enabled.ai_summary) {
+        writeln!(output, "<h2>Highlights</h2>")?;
+        output.push_str(&markdown_to_html(summary, true));
+    }
+
+    if !template.goal_check_ins.is_empty() {
+        writeln!(output, "<h2>🎯 Goals Check-in</h2>\n<ul class=\"items\">")?;
+        for check_in in &template.goal_check_ins {
+            write!(
+                output,
+                "<li><strong>{}</strong> — {}",
+                escape(&check_in.goal),
+                escape(check_in.status.label())
+            )?;
+            if !check_in.assessment.is_empty() {
+                write!(output, ": {}", inline(&check_in.assessment))?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.upcoming.is_empty() {
+        writeln!(
+            output,
+            "<h2>📅 Upcoming Deadlines</h2>\n<ul class=\"items\">"
+        )?;
+        for upcoming in &template.upcoming {
+            writeln!(
+                output,
+                "<li><span class=\"repo-name\">{}</span> <a href=\"{}\">{}</a> due {} ({}) — {}</li>",
+                escape(&upcoming.repo),
+                escape(&upcoming.milestone.html_url),
+                escape(&upcoming.milestone.title),
+                escape(&template.locale.format_date(upcoming.due_on)),
+                escape(&upcoming.due_in()),
+                escape(&template.milestone_progress(upcoming))
+            )?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.releases.is_empty() {
+        writeln!(output, "<h2>🚀 Releases</h2>\n<ul class=\"items\">")?;
+        for note in &template.releases {
+            write!(
+                output,
+                "<li><span class=\"repo-name\">{}</span> <a href=\"{}\">{}</a> <code>{}</code>",
+                escape(&note.repo),
+                escape(&note.release.html_url),
+                escape(note.release.title()),
+                escape(&note.release.tag_name)
+            )?;
+            if let Some(summary) = &note.summary {
+                write!(output, " — {}", inline(summary))?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.discussions.is_empty() {
+        writeln!(output, "<h2>🗣️ Discussions</h2>\n<ul class=\"items\">")?;
+        for note in &template.discussions {
+            write!(
+                output,
+                "<li><span class=\"repo-name\">{}</span> <a href=\"{}\">{}</a> ({})",
+                escape(&note.repo),
+                escape(&note.discussion.url),
+                escape(&note.discussion.title),
+                escape(&discussion_status(&note.discussion))
+            )?;
+            if let Some(summary) = &note.summary {
+                write!(output, " — {}", inline(summary))?;
+            }
+            writeln!(output, "</li>")?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    if !template.projects.is_empty() {
+        writeln!(output, "<h2>📋 Project Status</h2>")?;
+        let active = active_items(activities);
+        for board in &template.projects {
+            writeln!(
+                output,
+                "<h3><a href=\"{}\">{}</a></h3>",
+                escape(&board.url),
+                escape(&board.title)
+            )?;
+            for group in status_groups(board, &active, Some(MAX_ITEMS_PER_STATUS)) {
+                write!(
+                    output,
+                    "<p><strong>{}</strong> ({})",
+                    escape(group.status),
+                    group.total
+                )?;
+                if group.active > 0 {
+                    write!(output, " — {} active", group.active)?;
+                }
+                writeln!(output, "</p>\n<ul class=\"items\">")?;
+                for (item, is_active) in &group.items {
+                    writeln!(
+                        output,
+                        "<li><span class=\"repo-name\">{}</span> <a href=\"{}\">#{}</a> {}{}{}</li>",
+                        escape(&item.repo),
+                        escape(&item.url),
+                        item.number,
+                        escape(&item.title),
+                        item_state(item),
+                        if *is_active { " <strong>⚡ active</strong>" } else { "" }
+                    )?;
+                }
+                if group.items.len() < group.total {
+                    writeln!(
+                        output,
+                        "<li><em>…and {} more</em></li>",
+                        group.total - group.items.len()
+                    )?;
+                }
+                writeln!(output, "</ul>")?;
+            }
+        }
+    }
+
+    if !template.team.is_empty() {
+        writeln!(output, "<h2>👥 Team</h2>")?;
+        for person in &template.team {
+            writeln!(
+                output,
+                "<h3>{}</h3>\n<p>{}</p>",
+                user_link(&person.login),
+                escape(&template.person_summary(person))
+            )?;
+            if person.items.is_empty() {
+                continue;
+            }
+            writeln!(output, "<ul class=\"items\">")?;
+            for (repo, number) in person.items.iter().take(MAX_ITEMS_PER_PERSON) {
+                match find_issue(activities, repo, *number) {
+                    Some(issue) => writeln!(output, "<li>{}</li>", item_link(repo, issue))?,
+                    None => writeln!(
+                        output,
+                        "<li><span class=\"repo-name\">{}</span> <a href=\"https://github.com/{}/issues/{}\">#{}</a></li>",
+                        escape(repo),
+                        escape(repo),
+                        number,
+                        number
+                    )?,
+                }
+            }
+            if person.items.len() > MAX_ITEMS_PER_PERSON {
+                writeln!(
+                    output,
+                    "<li>…and {} more</li>",
+                    person.items.len() - MAX_ITEMS_PER_PERSON
+                )?;
+            }
+            writeln!(output, "</ul>")?;
+        }
+    }
+
+    if activities.is_empty() {
+        writeln!(output, "<h2>No Activity</h2>")?;
+        writeln!(
+            output,
+            "<p>No issues or pull requests were updated in the specified time period.</p>"
+        )?;
+    } else {
+        if enabled.statistics {
+            write_summary(&mut output, template, activities)?;
+        }
+
+        if enabled.prioritized && !analysis.prioritized_issues.is_empty() {
+            writeln!(output, "<h2>Prioritized Items</h2>\n<ul class=\"items\">")?;
+            for issue in analysis.prioritized_issues.iter().take(10) {
+                let waiting = analysis
+                    .waiting_on_author
+                    .iter()
+                    .any(|w| w.repo == issue.repo && w.issue.number == issue.issue.number);
+                write!(
+                    output,
+                    "<li>{}{}{}{}{} (Score: {})",
+                    item_link(&issue.repo, &issue.issue),
+                    ci_badge(template, &issue.issue),
+                    involvement_badges(template, &issue.issue),
+                    seen_badge(template, &issue.issue),
+                    if waiting {
+                        " <em>(waiting on author)</em>"
+                    } else {
+                        ""
+                    },
+                    template.locale.format_count(issue.score.total.into())
+                )?;
+                if let Some(update) = template
+                    .thread_updates
+                    .iter()
+                    .find(|u| u.repo == issue.repo && u.issue_number == issue.issue.number)
+                {
+                    write!(
+                        output,
+                        "<br>🔄 Since last report: {}",
+                        escape(&update.change)
+                    )?;
+                }
+                writeln!(output, "</li>")?;
+            }
+            writeln!(output, "</ul>")?;
+        }
+
+        if enabled.by_repository {
+            write_activities(&mut output, template, activities)?;
+        }
+    }
+
+    if !template.truncation.is_empty() {
+        writeln!(output, "<h2>✂️ Truncated</h2>")?;
+        for note in template.truncation_notes() {
+            writeln!(output, "<p><em>{}</em></p>", escape(&note))?;
+        }
+        if !template.truncation.skipped.is_empty() {
+            writeln!(output, "<ul class=\"items\">")?;
+            for (repo, issue) in &template.truncation.skipped {
+                writeln!(output, "<li>{}</li>", item_link(repo, issue))?;
+            }
+            writeln!(output, "</ul>")?;
+        }
+    }
+
+    if !template.low_priority.is_empty() {
+        writeln!(
+            output,
+            "<p><em><a href=\"#appendix\">{}</a></em></p>",
+            escape(&template.low_priority_note())
+        )?;
+        writeln!(
+            output,
+            "<h2 id=\"appendix\">Appendix: Low-Priority Items</h2>\n<ul class=\"items\">"
+        )?;
+        for (repo, issue) in &template.low_priority {
+            writeln!(output, "<li>{}</li>", item_link(repo, issue))?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    writeln!(
+        output,
+        "<footer><p>Generated by gh-report v{}</p>",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    if let Some(text) = &template.summarized_by {
+        writeln!(output, "<p>{}</p>", escape(text))?;
+    }
+    if let Some(text) = template.rate_limit_text() {
+        writeln!(output, "<p>{}</p>", escape(&text))?;
+    }
+    writeln!(output, "</footer>")?;
+    writeln!(output, "</main>")?;
+    writeln!(output, "</body>")?;
+    writeln!(output, "</html>")?;
+
+    Ok(output)
+}
+
+fn write_summary(
+    output: &mut String,
+    template: &ReportTemplate,
+    activities: &BTreeMap<String, RepoActivity>,
+) -> Result<()> {
+    let total = |count: fn(&RepoActivity) -> usize| activities.values().map(count).sum::<usize>();
+
+    writeln!(output, "<h2>Summary</h2>\n<ul>")?;
+    for (name, count) in [
+        ("Repositories", activities.len()),
+        ("New Issues", total(|a| a.new_issues.len())),
+        ("Updated Issues", total(|a| a.updated_issues.len())),
+        ("New Pull Requests", total(|a| a.new_prs.len())),
+        ("Updated Pull Requests", total(|a| a.updated_prs.len())),
+        ("Merged Pull Requests", total(|a| a.merged_prs.len())),
+        ("Closed Issues", total(|a| a.closed_issues.len())),
+    ] {
+        writeln!(
+            output,
+            "<li><strong>{}</strong>: {}</li>",
+            name,
+            template.locale.format_count(count as i64)
+        )?;
+    }
+    writeln!(output, "</ul>")?;
+    Ok(())
+}
+
+fn write_activities(
+    output: &mut String,
+    template: &ReportTemplate,
+    activities: &BTreeMap<String, RepoActivity>,
+) -> Result<()> {
+    if template.group_by() == GroupBy::Org {
+        return write_activities_by_org(output, template, activities);
+    }
+    if template.group_by() == GroupBy::Milestone {
+        writeln!(output, "<h2>Activity by Milestone</h2>")?;
+        for group in group_by_milestone(activities) {
+            writeln!(
+                output,
+                "<h3>{}</h3>\n<ul class=\"items\">",
+                escape(&template.milestone_heading(group.milestone))
+            )?;
+            for (repo_name, issue) in group.items {
+                writeln!(
+                    output,
+                    "<li><span class=\"repo-name\">{}</span> {}</li>",
+                    escape(repo_name),
+                    issue_line(template, issue)
+                )?;
+            }
+            writeln!(output, "</ul>")?;
+        }
+        return Ok(());
+    }
+
+    let active: Vec<(&String, &RepoActivity)> = activities
+        .iter()
+        .filter(|(_, activity)| has_items(activity))
+        .collect();
+
+    writeln!(output, "<h2>Activity by Repository</h2>")?;
+    writeln!(output, "<nav><ul>")?;
+    for (repo_name, _) in &active {
+        writeln!(
+            output,
+            "<li><a href=\"#{}\">{}</a></li>",
+            repo_anchor(repo_name),
+            escape(repo_name)
+        )?;
+    }
+    writeln!(output, "</ul></nav>")?;
+
+    for (repo_name, activity) in active {
+        write_repo_activity(output, template, repo_name, activity, 3)?;
+    }
+
+    Ok(())
+}
+
+/// Repository sections nested under organization headers with subtotals
+fn write_activities_by_org(
+    output: &mut String,
+    template: &ReportTemplate,
+    activities: &BTreeMap<String, RepoActivity>,
+) -> Result<()> {
+    let groups: Vec<_> = group_by_org(activities)
+        .into_iter()
+        .map(|mut group| {
+            group.repos.retain(|(_, activity)| has_items(activity));
+            group
+        })
+        .filter(|group| !group.repos.is_empty())
+        .collect();
+
+    writeln!(output, "<h2>Activity by Organization</h2>")?;
+    writeln!(output, "<nav><ul>")?;
+    for group in &groups {
+        writeln!(output, "<li>{}<ul>", escape(group.org))?;
+        for (repo_name, _) in &group.repos {
+            writeln!(
+                output,
+                "<li><a href=\"#{}\">{}</a></li>",
+                repo_anchor(repo_name),
+                escape(repo_name)
+            )?;
+        }
+        writeln!(output, "</ul></li>")?;
+    }
+    writeln!(output, "</ul></nav>")?;
+
+    for group in &groups {
+        writeln!(output, "<section class=\"org\">")?;
+        writeln!(
+            output,
+            "<h3><a href=\"https://github.com/{}\">{}</a></h3>",
+            escape(group.org),
+            escape(group.org)
+        )?;
+        writeln!(
+            output,
+            "<p class=\"meta\">{}</p>",
+            template.org_subtotal(group.repos.len(), group.issue_count(), group.pr_count())
+        )?;
+        if let Some(summary) = template.org_summaries.get(group.org) {
+            writeln!(
+                output,
+                "<blockquote>{}</blockquote>",
+                markdown_to_html(summary, true)
+            )?;
+        }
+        for (repo_name, activity) in &group.repos {
+            write_repo_activity(output, template, repo_name, activity, 4)?;
+        }
+        writeln!(output, "</section>")?;
+    }
+
+    Ok(())
+}
+
+/// A repository section, with its header at `level` and categories one below
+fn write_repo_activity(
+    output: &mut String,
+    template: &ReportTemplate,
+    repo_name: &str,
+    activity: &RepoActivity,
+    level: u8,
+) -> Result<()> {
+    writeln!(
+        output,
+        "<section class=\"repo\" id=\"{}\">",
+        repo_anchor(repo_name)
+    )?;
+    writeln!(
+        output,
+        "<h{level}><a href=\"https://github.com/{}\">{}</a></h{level}>",
+        escape(repo_name),
+        escape(repo_name)
+    )?;
+
+    // Same order as the Markdown report: completed work first
+    for (heading, issues) in [
+        ("🎉 Merged Pull Requests", &activity.merged_prs),
+        ("✅ Closed Issues", &activity.closed_issues),
+        ("🔄 New Pull Requests", &activity.new_prs),
+        ("📝 Updated Pull Requests", &activity.updated_prs),
+        ("🆕 New Issues", &activity.new_issues),
+        ("🔄 Updated Issues", &activity.updated_issues),
+    ] {
+        if issues.is_empty() {
+            continue;
+        }
+        writeln!(
+            output,
+            "<h{0}>{1}</h{0}>\n<ul class=\"items\">",
+            level + 1,
+            heading
+        )?;
+        for issue in issues {
+            write_issue_line(output, template, issue)?;
+        }
+        writeln!(output, "</ul>")?;
+    }
+
+    writeln!(output, "</section>")?;
+    Ok(())
+}
+
+fn write_issue_line(output: &mut String, template: &ReportTemplate, issue: &Issue) -> Result<()> {
+    writeln!(output, "<li>{}</li>", issue_line(template, issue))?;
+    Ok(())
+}
+
+/// The state, link, title, labels, author, and badges of an item
+fn issue_line(template: &ReportTemplate, issue: &Issue) -> String {
+    let (class, state) = match issue.state {
+        IssueState::Open => ("open", "open"),
+        IssueState::Closed => ("closed", "closed"),
+        IssueState::Merged => ("merged", "merged"),
+    };
+    let labels: String = issue.labels.iter().map(label_chip).collect();
+
+    format!(
+        "<span class=\"state state-{}\">{}</span> <a href=\"{}\">#{}</a> {}{} by {}{}{}{}",
+        class,
+        state,
+        escape(&issue.url),
+        issue.number,
+        escape(&issue.title),
+        labels,
+        user_link(&issue.author.login),
+        ci_badge(template, issue),
+        involvement_badges(template, issue),
+        seen_badge(template, issue)
+    )
+}
+
+/// `owner/repo PR #12 title`, as used in the cross-repository sections
+fn item_link(repo: &str, issue: &Issue) -> String {
+    format!(
+        "<span class=\"repo-name\">{}</span> {} <a href=\"{}\">#{}</a> - {}",
+        escape(repo),
+        if issue.is_pull_request { "PR" } else { "Issue" },
+        escape(&issue.url),
+        issue.number,
+        escape(&issue.title)
+    )
+}
+
+fn user_link(login: &str) -> String {
+    format!(
+        "<a href=\"https://github.com/{}\">@{}</a>",
+        escape(login),
+        escape(login)
+    )
+}
+
+fn ci_badge(template: &ReportTemplate, issue: &Issue) -> String {
+    template
+        .ci_status(issue)
+        .map(|status| format!(" {}", status.state.emoji()))
+        .unwrap_or_default()
+}
+
+fn involvement_badges(template: &ReportTemplate, issue: &Issue) -> String {
+    let Some(user) = template.current_user.as_deref() else {
+        return String::new();
+    };
+
+    let mut badges = String::new();
+    if issue.is_review_requested_from(user) {
+        badges.push_str(" <span class=\"badge\">👀 review requested from you</span>");
+    }
+    if issue.is_assigned_to(user) {
+        badges.push_str(" <span class=\"badge\">📌 assigned to you</span>");
+    }
+    badges
+}
+
+fn seen_badge(template: &ReportTemplate, issue: &Issue) -> String {
+    template
+        .seen_statuses
+        .get(&issue.url)
+        .map(|status| format!(" <span class=\"badge\">{}</span>", status.label()))
+        .unwrap_or_default()
+}
+
+/// A label chip in the label's GitHub color
+fn label_chip(label: &Label) -> String {
+    match label.color.as_deref().and_then(parse_hex_color) {
+        Some((r, g, b)) => {
+            // Dark text on light labels, like GitHub
+            let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+            let text = if luminance > 150.0 {
+                "#1f2328"
+            } else {
+                "#ffffff"
+            };
+            format!(
+                "<span class=\"label\" style=\"background-color: #{:02x}{:02x}{:02x}; color: {}\">{}</span>",
+                r,
+                g,
+                b,
+                text,
+                escape(&label.name)
+            )
+        }
+        None => format!("<span class=\"label\">{}</span>", escape(&label.name)),
+    }
+}
+
+/// Parse a `rrggbb` color as returned by the GitHub API
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let color = color.trim_start_matches('#');
+    if color.len() != 6 || !color.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&color[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn repo_anchor(repo: &str) -> String {
+    format!("repo-{}", escape(&repo.replace('/', "-")))
+}
+
+/// Escape text for use in HTML content and attribute values
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Convert Markdown, e.g. the highlights Claude writes, to HTML
+///
+/// Raw HTML in the Markdown is escaped rather than passed through, and only
+/// http(s) links and images are kept. With `nested`, headings start at
+/// `<h3>`, for Markdown placed under an `<h2>`.
+pub(crate) fn markdown_to_html(markdown: &str, nested: bool) -> String {
+    let mut output = String::new();
+    html::push_html(&mut output, safe_events(markdown, nested));
+    output
+}
+
+/// Inline Markdown, like `**bold**`, `` `code` `` and links, without the
+/// surrounding paragraph
+fn inline(text: &str) -> String {
+    let events = safe_events(text, false).filter(|event| {
+        !matches!(
+            event,
+            Event::Start(Tag::Paragraph) | Event::End(TagEnd::Paragraph)
+        )
+    });
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output.trim_end().to_string()
+}
+
+/// The events of `markdown` with raw HTML turned into text and links to
+/// anything but http(s) URLs turned into their text
+fn safe_events(markdown: &str, nested: bool) -> impl Iterator<Item = Event<'_>> {
+    let parser = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+    );
+    // Whether each open link or image is kept
+    let mut links: Vec<bool> = Vec::new();
+    parser.filter_map(move |event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Some(Event::Text(html)),
+        Event::Start(Tag::HtmlBlock) | Event::End(TagEnd::HtmlBlock) => None,
+        Event::Start(Tag::Heading {
+            level,
+            id,
+            classes,
+            attrs,
+        }) if nested => Some(Event::Start(Tag::Heading {
+            level: nested_heading(level),
+            id,
+            classes,
+            attrs,
+        })),
+        Event::End(TagEnd::Heading(level)) if nested => {
+            Some(Event::End(TagEnd::Heading(nested_heading(level))))
+        }
+        Event::Start(Tag::Link { ref dest_url, .. })
+        | Event::Start(Tag::Image { ref dest_url, .. }) => {
+            let keep = dest_url.starts_with("https://") || dest_url.starts_with("http://");
+            links.push(keep);
+            keep.then_some(event)
+        }
+        Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+            links.pop().unwrap_or(false).then_some(event)
+        }
+        event => Some(event),
+    })
+}
+
+/// Level of a heading placed under an `<h2>`
+fn nested_heading(level: HeadingLevel) -> HeadingLevel {
+    HeadingLevel::try_from((level as usize + 1).clamp(3, 6)).unwrap_or(HeadingLevel::H6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, GroupBy, ReportFormat};
+    use crate::test_utils::create_test_issue_with_labels;
+    use jiff::ToSpan;
+
+    #[test]
+    fn test_render_html_report() {
+        let mut config = Config::default();
+        config.report.format = ReportFormat::Html;
+        let template = ReportTemplate::new(&config);
+
+        let mut issue = create_test_issue_with_labels(42, "Fix <script> in docs", vec!["bug"]);
+        issue.labels[0].color = Some("d73a4a".to_string());
+        let mut activity = RepoActivity::default();
+        activity.new_issues.push(issue);
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), activity);
+
+        let now = Timestamp::now();
+        let html = template
+            .render_with_summary(
+                &activities,
+                now - 24_i64.hours(),
+                now,
+                &[],
+                Some("## Themes\n\n- **Runtime** fixes, see [#42](https://github.com/test/repo/issues/42)"),
+            )
+            .unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(html.contains("<section class=\"repo\" id=\"repo-test-repo\">"));
+        assert!(html.contains("<a href=\"https://github.com/test/repo/issues/42\">#42</a>"));
+        assert!(html.contains("Fix &lt;script&gt; in docs"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains(
+            "<span class=\"label\" style=\"background-color: #d73a4a; color: #ffffff\">bug</span>"
+        ));
+        assert!(html.contains("<h3>Themes</h3>"));
+        assert!(html.contains("<li><strong>Runtime</strong> fixes, see <a href=\"https://github.com/test/repo/issues/42\">#42</a></li>"));
+    }
+
+    #[test]
+    fn test_render_html_grouped_by_org() {
+        let mut config = Config::default();
+        config.report.format = ReportFormat::Html;
+        config.report.group_by = GroupBy::Org;
+        let mut summaries = BTreeMap::new();
+        summaries.insert("test".to_string(), "Docs **cleanup**.".to_string());
+        let template = ReportTemplate::new(&config).with_org_summaries(summaries);
+
+        let mut activities = BTreeMap::new();
+        for repo in ["test/repo", "test/other", "quiet/repo"] {
+            activities.insert(repo.to_string(), RepoActivity::default());
+        }
+        for repo in ["test/repo", "test/other"] {
+            activities
+                .get_mut(repo)
+                .unwrap()
+                .new_issues
+                .push(create_test_issue_with_labels(1, "Docs", vec![]));
+        }
+
+        let now = Timestamp::now();
+        let html = template
+            .render(&activities, now - 24_i64.hours(), now, &[])
+            .unwrap();
+
+        assert!(html.contains("<h2>Activity by Organization</h2>"));
+        assert!(html.contains("<section class=\"org\">"));
+        assert!(html.contains("<p class=\"meta\">2 repositories, 2 issues, 0 pull requests</p>"));
+        assert!(html.contains("<strong>cleanup</strong>"));
+        assert!(html.contains("<h4><a href=\"https://github.com/test/repo\">test/repo</a></h4>"));
+        assert!(!html.contains("quiet"));
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        let html = markdown_to_html(
+            "# Themes\n\nIntro with `code` and <script>alert(1)</script>\ncontinued.\n\n\
+             <div onclick=\"x\">block</div>\n\n1. first\n2. [bad](javascript:alert)\n",
+            true,
+        );
+        assert_eq!(
+            html,
+            "<h3>Themes</h3>\n\
+             <p>Intro with <code>code</code> and &lt;script&gt;alert(1)&lt;/script&gt;\ncontinued.</p>\n\
+             &lt;div onclick=\"x\"&gt;block&lt;/div&gt;\n\
+             <ol>\n<li>first</li>\n<li>bad</li>\n</ol>\n"
+        );
+        assert_eq!(
+            inline("**Runtime** fixes, see [#42](https://github.com/test/repo/issues/42)"),
+            "<strong>Runtime</strong> fixes, see <a href=\"https://github.com/test/repo/issues/42\">#42</a>"
+        );
+    }
+
+    #[test]
+    fn test_label_chip_colors() {
+        let label = |color: Option<&str>| Label {
+            name: "good first issue".to_string(),
+            color: color.map(str::to_string),
+            description: None,
+        };
+
+        assert!(label_chip(&label(Some("7057ff"))).contains("color: #ffffff"));
+        assert!(label_chip(&label(Some("fef2c0"))).contains("color: #1f2328"));
+        // Unknown or malformed colors fall back to the default chip style
+        assert_eq!(
+            label_chip(&label(Some("red\" onclick=\"x"))),
+            "<span class=\"label\">good first issue</span>"
+        );
+        assert_eq!(
+            label_chip(&label(None)),
+            "<span class=\"label\">good first issue</span>"
+        );
+    }
+}
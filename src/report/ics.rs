@@ -0,0 +1,131 @@
+//! iCalendar (RFC 5545) export of deadlines found while building a report
+
+use jiff::Timestamp;
+use sha2::{Digest, Sha256};
+
+use crate::intelligence::Deadline;
+
+/// Lines longer than this many octets are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Render deadlines as an iCalendar document of all-day events
+///
+/// Event UIDs are derived from the event itself, so importing a newer
+/// file updates existing events instead of duplicating them.
+pub fn render_ics(deadlines: &[Deadline], generated_at: Timestamp) -> String {
+    let stamp = generated_at.strftime("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//gh-report//deadlines//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for deadline in deadlines {
+        let start = deadline.date;
+        let end = start.tomorrow().unwrap_or(start);
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@gh-report", event_uid(deadline)));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", start.strftime("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", end.strftime("%Y%m%d")));
+        lines.push(format!("SUMMARY:{}", escape_text(&deadline.summary)));
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            escape_text(&format!("{}\n{}", deadline.description, deadline.url))
+        ));
+        lines.push(format!("URL:{}", deadline.url));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = String::new();
+    for line in lines {
+        output.push_str(&fold_line(&line));
+        output.push_str("\r\n");
+    }
+    output
+}
+
+fn event_uid(deadline: &Deadline) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(deadline.url.as_bytes());
+    hasher.update(deadline.date.to_string().as_bytes());
+    hasher.update(deadline.summary.as_bytes());
+    let digest = hasher.finalize();
+    digest[..12].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Escape a TEXT property value
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 characters
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intelligence::DeadlineSource;
+
+    #[test]
+    fn test_render_ics() {
+        let deadline = Deadline {
+            date: "2024-03-08".parse().unwrap(),
+            summary: "test/repo#7: RC cut on Friday, then release".to_string(),
+            description: "Mentioned in \"Release 1.0\"".to_string(),
+            url: "https://github.com/test/repo/issues/7".to_string(),
+            source: DeadlineSource::Discussion,
+        };
+        let now: Timestamp = "2024-03-06T12:00:00Z".parse().unwrap();
+
+        let ics = render_ics(&[deadline], now);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTAMP:20240306T120000Z\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20240308\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20240309\r\n"));
+        assert!(ics.contains("SUMMARY:test/repo#7: RC cut on Friday\\, then release\r\n"));
+        assert!(ics
+            .lines()
+            .all(|l| l.trim_end_matches('\r').len() <= MAX_LINE_OCTETS));
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = "é".repeat(50);
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
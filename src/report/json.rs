@@ -0,0 +1,142 @@
+//! JSON rendering of reports, for dashboards and scripts
+
+use anyhow::{Context, Result};
+use jiff::Timestamp;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::FilteredItems;
+use crate::github::{Issue, RepoActivity};
+use crate::intelligence::AnalysisResult;
+
+/// Version of the JSON report layout, bumped on incompatible changes
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+/// Everything a report is built from, as written with `report.format = "json"`
+#[derive(Debug, Serialize)]
+pub(super) struct JsonReport<'a> {
+    pub version: u32,
+    pub title: &'a str,
+    pub since: Timestamp,
+    pub until: Timestamp,
+    /// The authenticated user, when known
+    pub user: Option<&'a str>,
+    pub warnings: &'a [String],
+    /// Claude's summary, in markdown
+    pub ai_summary: Option<&'a str>,
+    /// Provider and model that wrote the summary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarized_by: Option<&'a str>,
+    /// Estimated LLM cost of the report, in dollars
+    pub estimated_cost: f32,
+    /// Items left out by their author or labels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filtered: Option<&'a FilteredItems>,
+    #[serde(flatten)]
+    pub analysis: &'a AnalysisResult,
+    pub repositories: BTreeMap<&'a str, JsonRepoActivity<'a>>,
+}
+
+/// A repository's items of the report period
+#[derive(Debug, Serialize)]
+pub(super) struct JsonRepoActivity<'a> {
+    pub new_issues: &'a [Issue],
+    pub new_prs: &'a [Issue],
+    pub updated_issues: &'a [Issue],
+    pub updated_prs: &'a [Issue],
+    pub merged_prs: &'a [Issue],
+    pub closed_issues: &'a [Issue],
+    /// Number of comments in the period, by issue or PR number
+    pub comments: BTreeMap<u32, usize>,
+}
+
+impl<'a> JsonRepoActivity<'a> {
+    pub fn new(activity: &'a RepoActivity) -> Self {
+        JsonRepoActivity {
+            new_issues: &activity.new_issues,
+            new_prs: &activity.new_prs,
+            updated_issues: &activity.updated_issues,
+            updated_prs: &activity.updated_prs,
+            merged_prs: &activity.merged_prs,
+            closed_issues: &activity.closed_issues,
+            comments: activity
+                .new_comments
+                .iter()
+                .map(|(issue, comments)| (issue.number, comments.len()))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> JsonReport<'a> {
+    /// The report for `activities`, with nothing but the analysis filled in
+    pub fn new(
+        activities: &'a BTreeMap<String, RepoActivity>,
+        analysis: &'a AnalysisResult,
+        since: Timestamp,
+        until: Timestamp,
+    ) -> Self {
+        JsonReport {
+            version: JSON_REPORT_VERSION,
+            title: "GitHub Activity Report",
+            since,
+            until,
+            user: None,
+            warnings: &[],
+            ai_summary: None,
+            summarized_by: None,
+            estimated_cost: 0.0,
+            filtered: None,
+            analysis,
+            repositories: activities
+                .iter()
+                .map(|(repo, activity)| (repo.as_str(), JsonRepoActivity::new(activity)))
+                .collect(),
+        }
+    }
+
+    pub fn render(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize the report to JSON")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::intelligence::IntelligentAnalyzer;
+    use crate::test_utils::create_test_issue;
+
+    #[test]
+    fn test_json_report() {
+        let mut activity = RepoActivity::default();
+        activity
+            .new_prs
+            .push(create_test_issue(7, "Add io_uring driver", true));
+        let issue = create_test_issue(8, "Panic in timer", false);
+        activity.new_comments.push((issue.clone(), Vec::new()));
+        activity.updated_issues.push(issue);
+        let mut activities = BTreeMap::new();
+        activities.insert("tokio-rs/tokio".to_string(), activity);
+
+        let config = Config::default();
+        let analysis = IntelligentAnalyzer::new(&config).analyze(&activities);
+        let since: Timestamp = "2024-03-01T00:00:00Z".parse().unwrap();
+        let until: Timestamp = "2024-03-08T00:00:00Z".parse().unwrap();
+        let report = JsonReport {
+            estimated_cost: 0.25,
+            ..JsonReport::new(&activities, &analysis, since, until)
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&report.render().unwrap()).unwrap();
+        assert_eq!(json["version"], JSON_REPORT_VERSION);
+        assert_eq!(json["since"], "2024-03-01T00:00:00Z");
+        assert_eq!(json["estimated_cost"], 0.25);
+        assert_eq!(json["prioritized_issues"].as_array().unwrap().len(), 2);
+        assert!(json["prioritized_issues"][0]["score"]["total"].is_u64());
+        assert!(json.get("context_prompt").is_none());
+        let repo = &json["repositories"]["tokio-rs/tokio"];
+        assert_eq!(repo["new_prs"][0]["number"], 7);
+        assert_eq!(repo["comments"]["8"], 0);
+    }
+}
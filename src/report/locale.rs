@@ -0,0 +1,212 @@
+//! Locale-specific formatting of dates and numbers in reports
+//!
+//! Only formats are localized here; the language of AI-written text is
+//! configured separately. Timestamps are shown in UTC, as before.
+
+use jiff::Timestamp;
+use tracing::warn;
+
+/// Date, time, and number conventions of a locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    /// BCP 47 tag, e.g. `de-DE`
+    pub tag: &'static str,
+    date: &'static str,
+    time: &'static str,
+    time_with_seconds: &'static str,
+    /// Digit group separator, `None` to leave counts ungrouped
+    thousands: Option<char>,
+    decimal: char,
+}
+
+/// The format reports used before locales existed: ISO dates, 24-hour times
+const DEFAULT_LOCALE: Locale = Locale {
+    tag: "default",
+    date: "%Y-%m-%d",
+    time: "%H:%M",
+    time_with_seconds: "%H:%M:%S",
+    thousands: None,
+    decimal: '.',
+};
+
+const fn locale(
+    tag: &'static str,
+    date: &'static str,
+    twelve_hour: bool,
+    thousands: char,
+    decimal: char,
+) -> Locale {
+    let (time, time_with_seconds) = if twelve_hour {
+        ("%-I:%M %p", "%-I:%M:%S %p")
+    } else {
+        ("%H:%M", "%H:%M:%S")
+    };
+    Locale {
+        tag,
+        date,
+        time,
+        time_with_seconds,
+        thousands: Some(thousands),
+        decimal,
+    }
+}
+
+/// Supported locales; the first one of each language is its fallback
+const LOCALES: &[Locale] = &[
+    locale("en-US", "%m/%d/%Y", true, ',', '.'),
+    locale("en-GB", "%d/%m/%Y", false, ',', '.'),
+    locale("en-AU", "%d/%m/%Y", true, ',', '.'),
+    locale("en-CA", "%Y-%m-%d", true, ',', '.'),
+    locale("de-DE", "%d.%m.%Y", false, '.', ','),
+    locale("de-CH", "%d.%m.%Y", false, '\'', '.'),
+    locale("fr-FR", "%d/%m/%Y", false, '\u{202f}', ','),
+    locale("fr-CA", "%Y-%m-%d", false, '\u{a0}', ','),
+    locale("es-ES", "%d/%m/%Y", false, '.', ','),
+    locale("it-IT", "%d/%m/%Y", false, '.', ','),
+    locale("nl-NL", "%d-%m-%Y", false, '.', ','),
+    locale("pt-BR", "%d/%m/%Y", false, '.', ','),
+    locale("pt-PT", "%d/%m/%Y", false, '\u{a0}', ','),
+    locale("pl-PL", "%d.%m.%Y", false, '\u{a0}', ','),
+    locale("sv-SE", "%Y-%m-%d", false, '\u{a0}', ','),
+    locale("ja-JP", "%Y/%m/%d", false, ',', '.'),
+    locale("zh-CN", "%Y/%m/%d", false, ',', '.'),
+];
+
+impl Default for Locale {
+    fn default() -> Self {
+        DEFAULT_LOCALE
+    }
+}
+
+impl Locale {
+    /// Find a locale by tag, matching on language when the region is unknown
+    ///
+    /// `de-AT` falls back to `de-DE`, `en` to `en-US`. Returns `None` for
+    /// languages without a known locale.
+    pub fn lookup(tag: &str) -> Option<Locale> {
+        let tag = tag.trim().replace('_', "-");
+        if let Some(locale) = LOCALES.iter().find(|l| l.tag.eq_ignore_ascii_case(&tag)) {
+            return Some(*locale);
+        }
+
+        let language = tag.split('-').next().unwrap_or_default();
+        LOCALES
+            .iter()
+            .find(|l| {
+                l.tag
+                    .split('-')
+                    .next()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language))
+            })
+            .copied()
+    }
+
+    /// The configured locale, or the default formats when unset or unknown
+    pub fn from_config(tag: Option<&str>) -> Locale {
+        let Some(tag) = tag.filter(|t| !t.trim().is_empty()) else {
+            return Locale::default();
+        };
+        Locale::lookup(tag).unwrap_or_else(|| {
+            warn!("Unknown report locale {:?}, using the default formats", tag);
+            Locale::default()
+        })
+    }
+
+    pub fn format_date(&self, timestamp: Timestamp) -> String {
+        timestamp.strftime(self.date).to_string()
+    }
+
+    pub fn format_datetime(&self, timestamp: Timestamp) -> String {
+        format!(
+            "{} {}",
+            self.format_date(timestamp),
+            timestamp.strftime(self.time)
+        )
+    }
+
+    pub fn format_datetime_with_seconds(&self, timestamp: Timestamp) -> String {
+        format!(
+            "{} {}",
+            self.format_date(timestamp),
+            timestamp.strftime(self.time_with_seconds)
+        )
+    }
+
+    /// The date, safe to use in a file name
+    pub fn format_file_date(&self, timestamp: Timestamp) -> String {
+        self.format_date(timestamp).replace(['/', ' '], "-")
+    }
+
+    /// A count with digit grouping, e.g. `12,345` or `12.345`
+    pub fn format_count(&self, count: i64) -> String {
+        let digits = count.unsigned_abs().to_string();
+        let Some(separator) = self.thousands else {
+            return count.to_string();
+        };
+
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        if count < 0 {
+            grouped.insert(0, '-');
+        }
+        grouped
+    }
+
+    /// A number with a fixed number of decimals, e.g. `0,0123`
+    pub fn format_decimal(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value);
+        if self.decimal == '.' {
+            formatted
+        } else {
+            formatted.replace('.', &self.decimal.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp() -> Timestamp {
+        "2024-03-06T14:05:09Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_default_keeps_iso_formats() {
+        let locale = Locale::from_config(None);
+        assert_eq!(locale.format_datetime(timestamp()), "2024-03-06 14:05");
+        assert_eq!(
+            locale.format_datetime_with_seconds(timestamp()),
+            "2024-03-06 14:05:09"
+        );
+        assert_eq!(locale.format_count(12345), "12345");
+    }
+
+    #[test]
+    fn test_localized_formats() {
+        let de = Locale::from_config(Some("de-DE"));
+        assert_eq!(de.format_datetime(timestamp()), "06.03.2024 14:05");
+        assert_eq!(de.format_count(1234567), "1.234.567");
+        assert_eq!(de.format_decimal(0.0125, 4), "0,0125");
+
+        let us = Locale::from_config(Some("en-US"));
+        assert_eq!(us.format_datetime(timestamp()), "03/06/2024 2:05 PM");
+        assert_eq!(us.format_count(-1234), "-1,234");
+        assert_eq!(us.format_count(999), "999");
+        assert_eq!(us.format_file_date(timestamp()), "03-06-2024");
+    }
+
+    #[test]
+    fn test_lookup_falls_back_to_language() {
+        assert_eq!(Locale::lookup("de_AT").unwrap().tag, "de-DE");
+        assert_eq!(Locale::lookup("EN-gb").unwrap().tag, "en-GB");
+        assert_eq!(Locale::lookup("en").unwrap().tag, "en-US");
+        assert_eq!(Locale::lookup("xx-YY"), None);
+        assert_eq!(Locale::from_config(Some("xx-YY")), Locale::default());
+    }
+}
@@ -0,0 +1,69 @@
+//! Issues and PRs mentioning the users and teams in `settings.include_mentions`
+//!
+//! Mentions are searched across all of GitHub, so they are listed in their own
+//! section rather than merged into the activity of the tracked repositories.
+
+use crate::github::Issue;
+
+/// An issue or PR and the configured users or teams it mentions
+#[derive(Debug, Clone)]
+pub struct Mention {
+    pub repo: String,
+    pub issue: Issue,
+    /// The `include_mentions` entries that matched, as configured
+    pub targets: Vec<String>,
+}
+
+/// Merge the search results of each target into one mention per item,
+/// most recently updated first
+pub fn collect_mentions(found: Vec<(String, Vec<Issue>)>) -> Vec<Mention> {
+    let mut mentions: Vec<Mention> = Vec::new();
+    for (target, issues) in found {
+        for issue in issues {
+            let Some(repo) = issue.repository_name() else {
+                continue;
+            };
+            match mentions
+                .iter_mut()
+                .find(|m| m.repo == repo && m.issue.number == issue.number)
+            {
+                Some(mention) => {
+                    if !mention.targets.contains(&target) {
+                        mention.targets.push(target.clone());
+                    }
+                }
+                None => mentions.push(Mention {
+                    repo,
+                    issue,
+                    targets: vec![target.clone()],
+                }),
+            }
+        }
+    }
+    mentions.sort_by_key(|m| std::cmp::Reverse(m.issue.updated_at));
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+
+    #[test]
+    fn test_collect_mentions() {
+        let mut older = create_test_issue(1, "Flaky test", false);
+        older.updated_at = "2024-03-01T00:00:00Z".parse().unwrap();
+        let mut newer = create_test_issue(2, "Add a timeout", true);
+        newer.updated_at = "2024-03-02T00:00:00Z".parse().unwrap();
+
+        let mentions = collect_mentions(vec![
+            ("carllerche".to_string(), vec![older.clone(), newer.clone()]),
+            ("@tokio-rs/maintainers".to_string(), vec![older]),
+        ]);
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].issue.number, 2);
+        assert_eq!(mentions[0].targets, ["carllerche"]);
+        assert_eq!(mentions[1].repo, "test/repo");
+        assert_eq!(mentions[1].targets, ["carllerche", "@tokio-rs/maintainers"]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Grouping of issues and PRs by milestone, for `report.group_by = "milestone"`
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::github::{Issue, Milestone, RepoActivity};
+
+/// The issues and PRs of one milestone, over all repositories
+pub struct MilestoneGroup<'a> {
+    /// `None` for the items without a milestone
+    pub milestone: Option<&'a Milestone>,
+    pub items: Vec<(&'a str, &'a Issue)>,
+}
+
+/// Group the items of `activities` by milestone title
+///
+/// Milestones due soonest come first, then those without a due date, and
+/// the items without a milestone last. Within a group, items keep the order
+/// of the repository sections.
+pub fn group_by_milestone(activities: &BTreeMap<String, RepoActivity>) -> Vec<MilestoneGroup<'_>> {
+    let mut groups: Vec<MilestoneGroup> = Vec::new();
+    let mut seen = HashSet::new();
+    for (repo, activity) in activities {
+        let issues = activity
+            .merged_prs
+            .iter()
+            .chain(&activity.closed_issues)
+            .chain(&activity.new_prs)
+            .chain(&activity.updated_prs)
+            .chain(&activity.new_issues)
+            .chain(&activity.updated_issues);
+        for issue in issues {
+            if !seen.insert((repo.as_str(), issue.number)) {
+                continue;
+            }
+            let title = issue.milestone.as_ref().map(|m| m.title.as_str());
+            match groups
+                .iter_mut()
+                .find(|group| group.milestone.map(|m| m.title.as_str()) == title)
+            {
+                Some(group) => group.items.push((repo, issue)),
+                None => groups.push(MilestoneGroup {
+                    milestone: issue.milestone.as_ref(),
+                    items: vec![(repo, issue)],
+                }),
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| match (a.milestone, b.milestone) {
+        (Some(a), Some(b)) => match (a.due_on, b.due_on) {
+            (Some(a_due), Some(b_due)) => a_due.cmp(&b_due).then_with(|| a.title.cmp(&b.title)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.title.cmp(&b.title),
+        },
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+
+    #[test]
+    fn test_group_by_milestone() {
+        let with_milestone = |number: u32, title: &str, due_on: Option<&str>| {
+            let mut issue = create_test_issue(number, "Item", false);
+            issue.milestone = Some(Milestone {
+                title: title.to_string(),
+                due_on: due_on.map(|due| due.parse().unwrap()),
+            });
+            issue
+        };
+        let mut activities: BTreeMap<String, RepoActivity> = BTreeMap::new();
+        let tokio = activities.entry("tokio-rs/tokio".to_string()).or_default();
+        tokio.new_issues = vec![
+            with_milestone(1, "Someday", None),
+            create_test_issue(2, "Item", false),
+            with_milestone(3, "v1.0", Some("2024-03-01T00:00:00Z")),
+        ];
+        let axum = activities.entry("tokio-rs/axum".to_string()).or_default();
+        axum.updated_issues = vec![
+            with_milestone(4, "v0.8", Some("2024-02-01T00:00:00Z")),
+            with_milestone(5, "v1.0", Some("2024-03-01T00:00:00Z")),
+        ];
+
+        let groups = group_by_milestone(&activities);
+        let titles: Vec<Option<&str>> = groups
+            .iter()
+            .map(|group| group.milestone.map(|m| m.title.as_str()))
+            .collect();
+        assert_eq!(titles, [Some("v0.8"), Some("v1.0"), Some("Someday"), None]);
+        let v1: Vec<(&str, u32)> = groups[1]
+            .items
+            .iter()
+            .map(|(repo, issue)| (*repo, issue.number))
+            .collect();
+        assert_eq!(v1, [("tokio-rs/axum", 5), ("tokio-rs/tokio", 3)]);
+    }
+}
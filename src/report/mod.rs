@@ -2,16 +2,50 @@ use anyhow::{Context, Result};
 use jiff::Timestamp;
 use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use crate::config::Config;
+use crate::config::{CollisionStrategy, Config, ReportFormat};
 use crate::github::{Issue, RepoActivity};
+use crate::intelligence::{ActionItem, Deadline, RunScores};
+use crate::state::{RecentItem, RepoTrust, SeenItem};
 
+mod discussions;
+mod engine;
+mod filters;
 mod generator;
+mod html;
+mod ics;
+mod json;
+mod locale;
+mod mentions;
+mod milestone;
+mod org;
+mod projects;
+mod releases;
+mod rollup;
+mod team;
 mod template;
+mod upcoming;
 
+pub use discussions::{discussion_references, DiscussionNote};
+pub use engine::{TemplateEngine, DEFAULT_TEMPLATE};
+pub use filters::FilteredItems;
 pub use generator::ReportGenerator;
+pub(crate) use html::{escape as escape_html, markdown_to_html};
+pub use ics::render_ics;
+pub use json::JSON_REPORT_VERSION;
+pub use locale::Locale;
+pub use mentions::{collect_mentions, Mention};
+pub use projects::render_project_status;
+pub use releases::{published_releases, ReleaseNote};
+pub use rollup::{
+    previous_period, HistoryEntry, PeriodComparison, PeriodTotals, ReportFlavor, ReportHistory,
+    HISTORY_FILE_NAME,
+};
+pub use team::{team_breakdown, PersonActivity, TeamAction};
 pub use template::ReportTemplate;
+pub use upcoming::UpcomingMilestone;
 
 /// A generated report ready to be saved
 pub struct Report {
@@ -19,40 +53,293 @@ pub struct Report {
     pub content: String,
     pub timestamp: Timestamp,
     pub estimated_cost: f32,
+    /// Upcoming deadlines, collected when `report.deadlines_ics` is enabled
+    pub deadlines: Vec<Deadline>,
+    /// Format of `content`, which decides the file extension
+    pub format: ReportFormat,
+    /// Issues and PRs covered by the report, remembered for shell completion
+    pub items: Vec<RecentItem>,
+    /// The state of the covered items by URL, to tell what is new in the next report
+    pub seen_items: BTreeMap<String, SeenItem>,
+    /// Updated answers about repositories in AI summaries, to save in the state
+    pub repo_trust: Option<RepoTrust>,
+    /// How the prioritized items were scored, when the report has any
+    pub scores: Option<RunScores>,
+    /// Items opened, closed, and merged in the period, for the report history
+    pub totals: PeriodTotals,
+    /// The report at a glance, for deliveries that post part of it
+    pub digest: ReportDigest,
+}
+
+/// The highlights, counts, and action items of a report, apart from the
+/// rendered content so they don't depend on `report.template`
+#[derive(Debug, Clone, Default)]
+pub struct ReportDigest {
+    /// The AI summary, in markdown
+    pub highlights: Option<String>,
+    /// Counts of the activity, unless `sections.statistics` is off or there was none
+    pub summary: Option<ActivityCounts>,
+    /// Action items, most urgent first, unless `sections.action_items` is off
+    pub action_items: Vec<ActionItem>,
+}
+
+/// Counts of a report's activity, as in its Summary section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActivityCounts {
+    pub repositories: usize,
+    pub new_issues: usize,
+    pub updated_issues: usize,
+    pub new_prs: usize,
+    pub updated_prs: usize,
+    pub merged_prs: usize,
+    pub closed_issues: usize,
+}
+
+impl ActivityCounts {
+    pub fn of(activities: &BTreeMap<String, RepoActivity>) -> Self {
+        activities.values().fold(
+            ActivityCounts {
+                repositories: activities.len(),
+                ..ActivityCounts::default()
+            },
+            |counts, activity| ActivityCounts {
+                new_issues: counts.new_issues + activity.new_issues.len(),
+                updated_issues: counts.updated_issues + activity.updated_issues.len(),
+                new_prs: counts.new_prs + activity.new_prs.len(),
+                updated_prs: counts.updated_prs + activity.updated_prs.len(),
+                merged_prs: counts.merged_prs + activity.merged_prs.len(),
+                closed_issues: counts.closed_issues + activity.closed_issues.len(),
+                ..counts
+            },
+        )
+    }
+
+    /// The counts with their labels, in the order of the Summary section
+    pub fn labeled(&self) -> [(&'static str, usize); 7] {
+        [
+            ("Repositories", self.repositories),
+            ("New Issues", self.new_issues),
+            ("Updated Issues", self.updated_issues),
+            ("New Pull Requests", self.new_prs),
+            ("Updated Pull Requests", self.updated_prs),
+            ("Merged Pull Requests", self.merged_prs),
+            ("Closed Issues", self.closed_issues),
+        ]
+    }
+}
+
+/// Every issue and PR of a repository's activity, possibly more than once
+fn activity_issues(activity: &RepoActivity) -> impl Iterator<Item = &Issue> {
+    activity
+        .new_issues
+        .iter()
+        .chain(&activity.new_prs)
+        .chain(&activity.updated_issues)
+        .chain(&activity.updated_prs)
+        .chain(&activity.merged_prs)
+        .chain(&activity.closed_issues)
+        .chain(activity.new_comments.iter().map(|(issue, _)| issue))
+}
+
+/// The issues and PRs in `activities`, as seen at `now`
+fn covered_items(activities: &BTreeMap<String, RepoActivity>, now: Timestamp) -> Vec<RecentItem> {
+    let mut items = Vec::new();
+    for (repo, activity) in activities {
+        for issue in activity_issues(activity) {
+            if items
+                .iter()
+                .any(|i: &RecentItem| i.repo == *repo && i.number == issue.number)
+            {
+                continue;
+            }
+            items.push(RecentItem {
+                repo: repo.clone(),
+                number: issue.number,
+                title: issue.title.clone(),
+                seen_at: now,
+            });
+        }
+    }
+    items
+}
+
+/// The issues and PRs in `activities` by URL, as reported at `now`
+fn seen_items(
+    activities: &BTreeMap<String, RepoActivity>,
+    now: Timestamp,
+) -> BTreeMap<String, SeenItem> {
+    activities
+        .values()
+        .flat_map(activity_issues)
+        .map(|issue| (issue.url.clone(), SeenItem::of(issue, now)))
+        .collect()
+}
+
+/// A place a report is written to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputTarget {
+    /// This exact file
+    File(PathBuf),
+    /// A directory, with the file name from `settings.file_name_format`
+    Dir(PathBuf),
+    /// Standard output, for `--output -`
+    Stdout,
+}
+
+impl OutputTarget {
+    /// A file when `path` has an extension and is not an existing directory
+    ///
+    /// `~/Reports` and `b/` are directories, `a.md` is a file, and `-` is
+    /// standard output.
+    pub fn from_path(path: &Path) -> Self {
+        if path == Path::new("-") {
+            return OutputTarget::Stdout;
+        }
+        let is_dir = path.is_dir()
+            || path.as_os_str().to_string_lossy().ends_with(['/', '\\'])
+            || path.extension().is_none();
+        if is_dir {
+            OutputTarget::Dir(path.to_path_buf())
+        } else {
+            OutputTarget::File(path.to_path_buf())
+        }
+    }
+
+    /// Where a report goes: the `--output` paths, or the report directory and `report.outputs`
+    pub fn resolve(config: &Config, output: &[PathBuf]) -> Result<Vec<OutputTarget>> {
+        if !output.is_empty() {
+            return Ok(output.iter().map(|p| OutputTarget::from_path(p)).collect());
+        }
+
+        let mut targets = vec![OutputTarget::Dir(config.settings.report_dir.clone())];
+        for path in &config.report.outputs {
+            let target = OutputTarget::from_path(&crate::config::expand_tilde(path)?);
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        Ok(targets)
+    }
 }
 
 impl Report {
-    /// Save the report to a file
+    /// Save the report to the report directory
     pub fn save(&self, config: &Config) -> Result<PathBuf> {
+        self.save_to_dir(&config.settings.report_dir, config)
+    }
+
+    /// Write the same report to every target, returning the written files
+    pub fn save_all(&self, targets: &[OutputTarget], config: &Config) -> Result<Vec<PathBuf>> {
+        targets
+            .iter()
+            .filter_map(|target| match target {
+                OutputTarget::File(path) => Some(self.save_to_path(path, config)),
+                OutputTarget::Dir(dir) => Some(self.save_to_dir(dir, config)),
+                OutputTarget::Stdout => self.print().err().map(Err),
+            })
+            .collect()
+    }
+
+    /// Write the report to standard output
+    pub fn print(&self) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(self.content.as_bytes())
+            .and_then(|()| stdout.flush())
+            .context("Failed to write the report to stdout")
+    }
+
+    /// Save the report to a directory, named by `settings.file_name_format`
+    pub fn save_to_dir(&self, report_dir: &Path, config: &Config) -> Result<PathBuf> {
         // Ensure report directory exists
-        let report_dir = &config.settings.report_dir;
         fs::create_dir_all(report_dir)
             .with_context(|| format!("Failed to create report directory: {:?}", report_dir))?;
 
         // Generate filename
         let filename = self.generate_filename(config);
-        let filepath = report_dir.join(&filename);
-
-        // Write report
-        fs::write(&filepath, &self.content)
-            .with_context(|| format!("Failed to write report to {:?}", filepath))?;
-
-        Ok(filepath)
+        self.write_file(&report_dir.join(&filename), config)
     }
 
     /// Save the report to a specific file path
-    pub fn save_to_path(&self, path: &PathBuf) -> Result<PathBuf> {
+    pub fn save_to_path(&self, path: &Path, config: &Config) -> Result<PathBuf> {
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create directory: {:?}", parent))?;
         }
 
-        // Write report
-        fs::write(path, &self.content)
+        self.write_file(path, config)
+    }
+
+    /// Write the report to `path`, dealing with an existing file as
+    /// `settings.collision_strategy` says
+    fn write_file(&self, path: &Path, config: &Config) -> Result<PathBuf> {
+        let strategy = match config.settings.collision_strategy {
+            // Only markdown can take another report at its end
+            CollisionStrategy::Append if self.format != ReportFormat::Markdown => {
+                CollisionStrategy::NewFile
+            }
+            strategy => strategy,
+        };
+
+        let path = match strategy {
+            _ if !path.exists() => path.to_path_buf(),
+            CollisionStrategy::Overwrite => path.to_path_buf(),
+            CollisionStrategy::NewFile => numbered_path(path),
+            CollisionStrategy::Append => {
+                let update = self.update_section(&config.report.locale());
+                fs::OpenOptions::new()
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(update.as_bytes()))
+                    .with_context(|| format!("Failed to append report to {:?}", path))?;
+                return Ok(path.to_path_buf());
+            }
+        };
+
+        fs::write(&path, &self.content)
             .with_context(|| format!("Failed to write report to {:?}", path))?;
+        Ok(path)
+    }
 
-        Ok(path.clone())
+    /// The report as a section to append to an earlier one: its title becomes
+    /// a timestamped heading and the other headings move one level down
+    fn update_section(&self, locale: &Locale) -> String {
+        let mut section = format!(
+            "\n---\n\n## 🔄 Update at {}\n",
+            locale.format_datetime(self.timestamp)
+        );
+        let mut in_code = false;
+        let mut lines = self.content.lines().peekable();
+        if lines.peek().is_some_and(|line| line.starts_with("# ")) {
+            lines.next();
+        }
+        for line in lines {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+            }
+            if !in_code && line.starts_with('#') {
+                section.push('#');
+            }
+            section.push_str(line);
+            section.push('\n');
+        }
+        section
+    }
+
+    /// Write the report's deadlines as an iCalendar file next to `report_path`
+    ///
+    /// Returns `None` without touching the disk when there are no deadlines.
+    pub fn save_deadlines_ics(&self, report_path: &Path) -> Result<Option<PathBuf>> {
+        if self.deadlines.is_empty() {
+            return Ok(None);
+        }
+
+        let path = report_path.with_file_name("deadlines.ics");
+        fs::write(&path, render_ics(&self.deadlines, self.timestamp))
+            .with_context(|| format!("Failed to write calendar to {:?}", path))?;
+
+        Ok(Some(path))
     }
 
     /// Generate filename based on config format
@@ -71,14 +358,23 @@ impl Report {
         filename = filename.replace("{yyyy}", &year);
         filename = filename.replace("{mm}", &month);
         filename = filename.replace("{dd}", &day);
+        filename = filename.replace(
+            "{date}",
+            &config.report.locale().format_file_date(self.timestamp),
+        );
 
-        // Generate short title (max 8 words)
-        let short_title = self.generate_short_title();
+        // Generate short title (max 8 words), without path separators from
+        // localized dates
+        let short_title = self.generate_short_title().replace('/', "-");
         filename = filename.replace("{short-title}", &short_title);
 
-        // Ensure .md extension
-        if !filename.ends_with(".md") {
-            filename.push_str(".md");
+        // Ensure the extension matches the format
+        let extension = format!(".{}", self.format.extension());
+        if let Some(stem) = filename.strip_suffix(".md") {
+            filename = stem.to_string();
+        }
+        if !filename.ends_with(&extension) {
+            filename.push_str(&extension);
         }
 
         filename
@@ -98,6 +394,19 @@ impl Report {
     }
 }
 
+/// `path` with the first free number added to its name: `a (2).md`, `a (3).md`, ...
+fn numbered_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{} ({}){}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .expect("a free file name")
+}
+
 /// Group activities by repository
 pub fn group_activities_by_repo(issues: Vec<Issue>) -> BTreeMap<String, RepoActivity> {
     let mut activities: BTreeMap<String, RepoActivity> = BTreeMap::new();
@@ -106,9 +415,7 @@ pub fn group_activities_by_repo(issues: Vec<Issue>) -> BTreeMap<String, RepoActi
         // Extract repo name from URL (format: https://github.com/owner/repo/...)
         let repo_name = extract_repo_from_url(&issue.url).unwrap_or_else(|| "unknown".to_string());
 
-        let activity = activities
-            .entry(repo_name)
-            .or_insert_with(RepoActivity::default);
+        let activity = activities.entry(repo_name).or_default();
 
         // Categorize by type and state
         if issue.is_pull_request {
@@ -152,13 +459,8 @@ pub fn group_activities_by_repo(issues: Vec<Issue>) -> BTreeMap<String, RepoActi
 
 /// Extract repository name from GitHub URL
 fn extract_repo_from_url(url: &str) -> Option<String> {
-    // URL format: https://github.com/owner/repo/...
-    let parts: Vec<&str> = url.split('/').collect();
-    if parts.len() >= 5 && parts[2] == "github.com" {
-        Some(format!("{}/{}", parts[3], parts[4]))
-    } else {
-        None
-    }
+    // URL format: https://github.com/owner/repo/..., or the same on an enterprise host
+    crate::github::repo_from_url(url)
 }
 
 #[cfg(test)]
@@ -187,13 +489,128 @@ mod tests {
             content: "# Test".to_string(),
             timestamp: Timestamp::from_second(1704931200).unwrap(), // 2024-01-11
             estimated_cost: 0.0,
+            deadlines: Vec::new(),
+            format: ReportFormat::Markdown,
+            items: Vec::new(),
+            seen_items: BTreeMap::new(),
+            repo_trust: None,
+            scores: None,
+            totals: PeriodTotals::default(),
+            digest: ReportDigest::default(),
         };
 
-        let config = Config::default();
+        let mut config = Config::default();
         let filename = report.generate_filename(&config);
 
         assert!(filename.contains("2024-01-11"));
         assert!(filename.contains("Test Report Title Here"));
         assert!(filename.ends_with(".md"));
+
+        // A configured .md suffix is swapped for the format's extension
+        config.settings.file_name_format = "{yyyy-mm-dd}.md".to_string();
+        let report = Report {
+            format: ReportFormat::Html,
+            ..report
+        };
+        assert_eq!(report.generate_filename(&config), "2024-01-11.html");
+    }
+
+    #[test]
+    fn test_save_to_several_outputs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let report = Report {
+            title: "Weekly".to_string(),
+            content: "# Weekly".to_string(),
+            timestamp: Timestamp::from_second(1704931200).unwrap(),
+            estimated_cost: 0.0,
+            deadlines: Vec::new(),
+            format: ReportFormat::Markdown,
+            items: Vec::new(),
+            seen_items: BTreeMap::new(),
+            repo_trust: None,
+            scores: None,
+            totals: PeriodTotals::default(),
+            digest: ReportDigest::default(),
+        };
+        let mut config = Config::default();
+        config.settings.file_name_format = "{yyyy-mm-dd}.md".to_string();
+        config.settings.report_dir = dir.path().join("reports");
+        config.report.outputs = vec![dir.path().join("vault/daily/"), dir.path().join("reports")];
+
+        // The report directory is listed once
+        let targets = OutputTarget::resolve(&config, &[]).unwrap();
+        assert_eq!(
+            targets,
+            [
+                OutputTarget::Dir(dir.path().join("reports")),
+                OutputTarget::Dir(dir.path().join("vault/daily/")),
+            ]
+        );
+
+        let output = [
+            dir.path().join("a.md"),
+            PathBuf::from("-"),
+            dir.path().join("b/"),
+        ];
+        let targets = OutputTarget::resolve(&config, &output).unwrap();
+        assert_eq!(targets[1], OutputTarget::Stdout);
+        let paths = report.save_all(&targets, &config).unwrap();
+        assert_eq!(
+            paths,
+            [dir.path().join("a.md"), dir.path().join("b/2024-01-11.md")]
+        );
+        for path in paths {
+            assert_eq!(fs::read_to_string(path).unwrap(), "# Weekly");
+        }
+    }
+
+    #[test]
+    fn test_collision_strategies() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let report = |content: &str, hour: i64| Report {
+            title: "Daily".to_string(),
+            content: content.to_string(),
+            timestamp: Timestamp::from_second(1704931200 + hour * 3600).unwrap(),
+            estimated_cost: 0.0,
+            deadlines: Vec::new(),
+            format: ReportFormat::Markdown,
+            items: Vec::new(),
+            seen_items: BTreeMap::new(),
+            repo_trust: None,
+            scores: None,
+            totals: PeriodTotals::default(),
+            digest: ReportDigest::default(),
+        };
+        let mut config = Config::default();
+        config.settings.file_name_format = "{yyyy-mm-dd}.md".to_string();
+        let path = dir.path().join("2024-01-11.md");
+
+        report("# Morning\n\n## Summary\n", 9)
+            .save_to_dir(dir.path(), &config)
+            .unwrap();
+        config.settings.collision_strategy = CollisionStrategy::Append;
+        let saved = report("# Afternoon\n\n## Summary\n```\n# not a heading\n```\n", 15)
+            .save_to_dir(dir.path(), &config)
+            .unwrap();
+        assert_eq!(saved, path);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Morning\n\n## Summary\n\n---\n\n## 🔄 Update at 2024-01-11 15:00\n\n\
+             ### Summary\n```\n# not a heading\n```\n"
+        );
+
+        config.settings.collision_strategy = CollisionStrategy::NewFile;
+        for n in 2..4 {
+            let saved = report("# Evening\n", 20)
+                .save_to_dir(dir.path(), &config)
+                .unwrap();
+            assert_eq!(saved, dir.path().join(format!("2024-01-11 ({}).md", n)));
+        }
+
+        config.settings.collision_strategy = CollisionStrategy::Overwrite;
+        report("# Night\n", 23)
+            .save_to_dir(dir.path(), &config)
+            .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Night\n");
     }
 }
@@ -0,0 +1,88 @@
+//! Grouping of repositories by organization, for `report.group_by = "org"`
+
+use std::collections::BTreeMap;
+
+use crate::github::RepoActivity;
+
+/// The repositories of one organization (or user) that had activity
+pub struct OrgGroup<'a> {
+    pub org: &'a str,
+    pub repos: Vec<(&'a str, &'a RepoActivity)>,
+}
+
+impl OrgGroup<'_> {
+    /// Issues over all repositories, counted like the report summary
+    pub fn issue_count(&self) -> usize {
+        self.repos
+            .iter()
+            .map(|(_, a)| a.new_issues.len() + a.updated_issues.len() + a.closed_issues.len())
+            .sum()
+    }
+
+    /// Pull requests over all repositories, counted like the report summary
+    pub fn pr_count(&self) -> usize {
+        self.repos
+            .iter()
+            .map(|(_, a)| a.new_prs.len() + a.updated_prs.len() + a.merged_prs.len())
+            .sum()
+    }
+}
+
+/// The owner part of `owner/repo`
+pub fn org_of(repo: &str) -> &str {
+    repo.split_once('/').map_or(repo, |(org, _)| org)
+}
+
+/// Group repositories by organization, keeping the order of `activities`
+pub fn group_by_org(activities: &BTreeMap<String, RepoActivity>) -> Vec<OrgGroup<'_>> {
+    let mut groups: Vec<OrgGroup> = Vec::new();
+    // `activities` is sorted by name, so repositories of an org are adjacent
+    for (repo, activity) in activities {
+        let org = org_of(repo);
+        match groups.last_mut() {
+            Some(group) if group.org == org => group.repos.push((repo, activity)),
+            _ => groups.push(OrgGroup {
+                org,
+                repos: vec![(repo, activity)],
+            }),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+
+    #[test]
+    fn test_group_by_org() {
+        let mut activities = BTreeMap::new();
+        for (repo, prs, issues) in [
+            ("tokio-rs/tokio", 2, 1),
+            ("rust-lang/rust", 1, 0),
+            ("tokio-rs/axum", 0, 3),
+        ] {
+            let activity: &mut RepoActivity = activities.entry(repo.to_string()).or_default();
+            for n in 0..prs {
+                activity.new_prs.push(create_test_issue(n, "PR", true));
+            }
+            for n in 0..issues {
+                activity
+                    .updated_issues
+                    .push(create_test_issue(n, "Issue", false));
+            }
+        }
+
+        let groups = group_by_org(&activities);
+        let orgs: Vec<&str> = groups.iter().map(|g| g.org).collect();
+        assert_eq!(orgs, ["rust-lang", "tokio-rs"]);
+
+        let tokio = &groups[1];
+        let repos: Vec<&str> = tokio.repos.iter().map(|(name, _)| *name).collect();
+        assert_eq!(repos, ["tokio-rs/axum", "tokio-rs/tokio"]);
+        assert_eq!((tokio.issue_count(), tokio.pr_count()), (4, 2));
+
+        assert_eq!(org_of("no-slash"), "no-slash");
+    }
+}
@@ -0,0 +1,234 @@
+//! Status of GitHub Projects boards, for `report.projects` and `gh-report projects`
+//!
+//! Items are grouped by the board's status field, in the order of its
+//! columns. Items with activity in the period come first in their column and
+//! are marked, so the board reads next to the rest of the report.
+
+use anyhow::Result;
+use jiff::Timestamp;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use super::activity_issues;
+use crate::github::{IssueState, ProjectBoard, ProjectItem, RepoActivity};
+
+/// Most items listed per status in a report; `gh-report projects` lists all
+pub const MAX_ITEMS_PER_STATUS: usize = 10;
+
+/// Heading of the items without a status
+const NO_STATUS: &str = "No Status";
+
+/// A column of a board with the items listed under it
+#[derive(Debug)]
+pub struct StatusGroup<'a> {
+    pub status: &'a str,
+    /// Items listed, active ones first, with whether they are active
+    pub items: Vec<(&'a ProjectItem, bool)>,
+    /// Items in the column, listed or not
+    pub total: usize,
+    pub active: usize,
+}
+
+/// The items of `board` grouped by status, in the order of the board's columns
+///
+/// Statuses that are no longer options of the field follow the columns, and
+/// items without a status come last. Empty columns are left out. `active`
+/// holds the `(repo, number)` of items with activity; with `limit`, at most
+/// that many items are listed per column.
+pub fn status_groups<'a>(
+    board: &'a ProjectBoard,
+    active: &BTreeSet<(&str, u32)>,
+    limit: Option<usize>,
+) -> Vec<StatusGroup<'a>> {
+    let mut columns: Vec<(&str, Vec<&ProjectItem>)> = board
+        .statuses
+        .iter()
+        .map(|status| (status.as_str(), Vec::new()))
+        .collect();
+    let mut no_status = Vec::new();
+    for item in &board.items {
+        match item.status.as_deref() {
+            Some(status) => match columns.iter_mut().find(|(name, _)| *name == status) {
+                Some((_, items)) => items.push(item),
+                None => columns.push((status, vec![item])),
+            },
+            None => no_status.push(item),
+        }
+    }
+    columns.push((NO_STATUS, no_status));
+
+    columns
+        .into_iter()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(status, items)| {
+            let mut items: Vec<(&ProjectItem, bool)> = items
+                .into_iter()
+                .map(|item| (item, active.contains(&(item.repo.as_str(), item.number))))
+                .collect();
+            items.sort_by_key(|(_, is_active)| !is_active);
+            let total = items.len();
+            let active = items.iter().filter(|(_, is_active)| *is_active).count();
+            items.truncate(limit.unwrap_or(total));
+            StatusGroup {
+                status,
+                items,
+                total,
+                active,
+            }
+        })
+        .collect()
+}
+
+/// The `(repo, number)` of every issue and PR in `activities`
+pub(super) fn active_items(activities: &BTreeMap<String, RepoActivity>) -> BTreeSet<(&str, u32)> {
+    activities
+        .iter()
+        .flat_map(|(repo, activity)| {
+            activity_issues(activity).map(move |issue| (repo.as_str(), issue.number))
+        })
+        .collect()
+}
+
+/// Write `board` under a heading of `level`, with a bold line per status
+pub fn write_board(
+    output: &mut String,
+    board: &ProjectBoard,
+    level: usize,
+    active: &BTreeSet<(&str, u32)>,
+    limit: Option<usize>,
+) -> Result<()> {
+    writeln!(
+        output,
+        "{} [{}]({})\n",
+        "#".repeat(level),
+        board.title,
+        board.url
+    )?;
+    let groups = status_groups(board, active, limit);
+    if groups.is_empty() {
+        writeln!(output, "*No issues or PRs on this board.*\n")?;
+        return Ok(());
+    }
+    for group in groups {
+        write!(output, "**{}** ({})", group.status, group.total)?;
+        if group.active > 0 {
+            write!(output, " — {} active", group.active)?;
+        }
+        writeln!(output, "\n")?;
+        for (item, is_active) in &group.items {
+            writeln!(output, "- {}", item_line(item, *is_active))?;
+        }
+        if group.items.len() < group.total {
+            writeln!(output, "- *…and {} more*", group.total - group.items.len())?;
+        }
+        writeln!(output)?;
+    }
+    Ok(())
+}
+
+/// One item of a board, e.g. `[tokio-rs/tokio#42](…) Add io_uring support (merged)`
+pub fn item_line(item: &ProjectItem, is_active: bool) -> String {
+    format!(
+        "[{}#{}]({}) {}{}{}",
+        item.repo,
+        item.number,
+        item.url,
+        item.title,
+        item_state(item),
+        if is_active { " **⚡ active**" } else { "" }
+    )
+}
+
+/// ` (closed)` or ` (merged)` for items that are no longer open
+pub(super) fn item_state(item: &ProjectItem) -> &'static str {
+    match item.state {
+        IssueState::Open => "",
+        IssueState::Closed => " (closed)",
+        IssueState::Merged => " (merged)",
+    }
+}
+
+/// The output of `gh-report projects`: every item of `boards`, marking those
+/// in `active`
+pub fn render_project_status(
+    boards: &[ProjectBoard],
+    active: &BTreeSet<(&str, u32)>,
+    since: &str,
+    now: Timestamp,
+) -> Result<String> {
+    let mut output = String::new();
+    writeln!(output, "# 📋 Project Status\n")?;
+    writeln!(
+        output,
+        "*Marking items with activity in the last {}, as of {}*\n",
+        since,
+        now.strftime("%Y-%m-%d %H:%M UTC")
+    )?;
+    for board in boards {
+        write_board(&mut output, board, 2, active, None)?;
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(number: u32, status: Option<&str>, state: IssueState) -> ProjectItem {
+        ProjectItem {
+            repo: "tokio-rs/tokio".to_string(),
+            number,
+            title: format!("Item {}", number),
+            url: format!("https://github.com/tokio-rs/tokio/issues/{}", number),
+            is_pull_request: false,
+            state,
+            status: status.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_status_groups() {
+        let board = ProjectBoard {
+            title: "Roadmap".to_string(),
+            url: "https://github.com/orgs/tokio-rs/projects/5".to_string(),
+            statuses: vec![
+                "Todo".to_string(),
+                "In Progress".to_string(),
+                "Done".to_string(),
+            ],
+            items: vec![
+                item(1, Some("Done"), IssueState::Closed),
+                item(2, None, IssueState::Open),
+                item(3, Some("In Progress"), IssueState::Open),
+                item(4, Some("In Progress"), IssueState::Open),
+                item(5, Some("Blocked"), IssueState::Open),
+                item(6, Some("In Progress"), IssueState::Open),
+            ],
+        };
+        let active = BTreeSet::from([("tokio-rs/tokio", 4), ("tokio-rs/tokio", 1)]);
+
+        let groups = status_groups(&board, &active, Some(2));
+        let statuses: Vec<&str> = groups.iter().map(|g| g.status).collect();
+        assert_eq!(statuses, ["In Progress", "Done", "Blocked", "No Status"]);
+        let in_progress: Vec<(u32, bool)> = groups[0]
+            .items
+            .iter()
+            .map(|(item, is_active)| (item.number, *is_active))
+            .collect();
+        assert_eq!(in_progress, [(4, true), (3, false)]);
+        assert_eq!((groups[0].total, groups[0].active), (3, 1));
+
+        let mut output = String::new();
+        write_board(&mut output, &board, 3, &active, Some(2)).unwrap();
+        assert!(output.starts_with(
+            "### [Roadmap](https://github.com/orgs/tokio-rs/projects/5)\n\n\
+             **In Progress** (3) — 1 active\n\n\
+             - [tokio-rs/tokio#4](https://github.com/tokio-rs/tokio/issues/4) Item 4 **⚡ active**\n\
+             - [tokio-rs/tokio#3](https://github.com/tokio-rs/tokio/issues/3) Item 3\n\
+             - *…and 1 more*\n\n\
+             **Done** (1) — 1 active\n\n\
+             - [tokio-rs/tokio#1](https://github.com/tokio-rs/tokio/issues/1) Item 1 (closed) **⚡ active**\n"
+        ));
+    }
+}
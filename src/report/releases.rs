@@ -0,0 +1,129 @@
+//! Releases published in the report period, from `ReleaseEvent`s in the feed
+
+use crate::github::{ActivityEvent, Release};
+
+/// Most releases listed in a report
+pub const MAX_RELEASES: usize = 10;
+
+/// A release with Claude's one-line summary of its notes
+#[derive(Debug, Clone)]
+pub struct ReleaseNote {
+    pub repo: String,
+    pub release: Release,
+    /// Missing without Claude, for repositories kept out of AI summaries,
+    /// and for releases without notes
+    pub summary: Option<String>,
+}
+
+/// The releases published in `events` as `(repo, release id)`, most recent first
+pub fn published_releases(events: &[ActivityEvent]) -> Vec<(String, u64)> {
+    let mut events: Vec<&ActivityEvent> = events
+        .iter()
+        .filter(|event| event.event_type == "ReleaseEvent")
+        .filter(|event| event.payload.get("action").and_then(|a| a.as_str()) == Some("published"))
+        .collect();
+    events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+    let mut releases: Vec<(String, u64)> = Vec::new();
+    for event in events {
+        let id = event
+            .payload
+            .get("release")
+            .and_then(|release| release.get("id"))
+            .and_then(|id| id.as_u64());
+        if let Some(id) = id {
+            let release = (event.repo.name.clone(), id);
+            if !releases.contains(&release) {
+                releases.push(release);
+            }
+        }
+    }
+    releases
+}
+
+/// Fill in the summaries of `notes` from a response with one
+/// `<number>. <summary>` line per release
+pub fn parse_release_summaries(notes: &mut [ReleaseNote], response: &str) {
+    for line in response.lines() {
+        let Some((number, summary)) = line.trim().split_once(". ") else {
+            continue;
+        };
+        let Ok(number) = number.parse::<usize>() else {
+            continue;
+        };
+        let summary = summary.trim();
+        if let Some(note) = number.checked_sub(1).and_then(|i| notes.get_mut(i)) {
+            if !summary.is_empty() {
+                note.summary = Some(summary.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: u32, action: &str, release_id: u64) -> ActivityEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "type": "ReleaseEvent",
+            "actor": { "login": "carllerche" },
+            "repo": { "id": 1, "name": "tokio-rs/tokio", "url": "" },
+            "payload": { "action": action, "release": { "id": release_id } },
+            "created_at": format!("2024-03-06T{:02}:00:00Z", id),
+            "public": true,
+        }))
+        .unwrap()
+    }
+
+    fn release(id: u64, tag: &str) -> Release {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "tag_name": tag,
+            "name": null,
+            "body": "",
+            "html_url": format!("https://github.com/tokio-rs/tokio/releases/tag/{}", tag),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_published_releases() {
+        let events = [
+            event(1, "published", 100),
+            event(2, "published", 101),
+            event(3, "published", 101),
+            event(4, "edited", 102),
+        ];
+        assert_eq!(
+            published_releases(&events),
+            [
+                ("tokio-rs/tokio".to_string(), 101),
+                ("tokio-rs/tokio".to_string(), 100)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_release_summaries() {
+        let mut notes: Vec<ReleaseNote> = [release(1, "tokio-1.37.0"), release(2, "tokio-1.38.0")]
+            .into_iter()
+            .map(|release| ReleaseNote {
+                repo: "tokio-rs/tokio".to_string(),
+                release,
+                summary: None,
+            })
+            .collect();
+        parse_release_summaries(
+            &mut notes,
+            "2. Adds `JoinSet::spawn_blocking` and fixes a timer panic.\n3. Unknown\n",
+        );
+        assert_eq!(notes[0].summary, None);
+        assert_eq!(
+            notes[1].summary.as_deref(),
+            Some("Adds `JoinSet::spawn_blocking` and fixes a timer panic.")
+        );
+        assert_eq!(notes[1].release.title(), "tokio-1.38.0");
+    }
+}
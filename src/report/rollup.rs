@@ -0,0 +1,288 @@
+//! Weekly and monthly rollups, and the history they are compared against
+//!
+//! Every live report adds the counts of its period to a history file next to
+//! the state file. Weekly and monthly reports look up the previous report of
+//! the same flavor there and show how the counts changed.
+
+use anyhow::{Context, Result};
+use jiff::{Timestamp, ToSpan};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::github::RepoActivity;
+
+/// File name of the history, in the directory of the state file
+pub const HISTORY_FILE_NAME: &str = ".gh-report-history.json";
+
+/// How long after the start of a period the previous report may have ended,
+/// so a scheduled report that ran a little late still counts
+const PERIOD_SLACK_HOURS: i64 = 24;
+
+/// The kind of report, chosen with `report --flavor`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFlavor {
+    /// What needs attention, item by item
+    #[default]
+    Daily,
+    /// A rollup of the week's themes and trends, compared to the week before
+    Weekly,
+    /// A rollup of the month's themes and trends, compared to the month before
+    Monthly,
+}
+
+impl ReportFlavor {
+    /// Whether the report is a higher-level rollup compared to the previous period
+    pub fn is_rollup(&self) -> bool {
+        !matches!(self, ReportFlavor::Daily)
+    }
+
+    /// `Weekly` or `Monthly`, as in `# Weekly Rollup`
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportFlavor::Daily => "Daily",
+            ReportFlavor::Weekly => "Weekly",
+            ReportFlavor::Monthly => "Monthly",
+        }
+    }
+
+    /// The period one report covers, as in `compared to last week`
+    pub fn period(&self) -> &'static str {
+        match self {
+            ReportFlavor::Daily => "day",
+            ReportFlavor::Weekly => "week",
+            ReportFlavor::Monthly => "month",
+        }
+    }
+}
+
+/// Items opened, closed, and merged in a report's period
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PeriodTotals {
+    /// New issues and pull requests
+    pub opened: usize,
+    /// Closed issues
+    pub closed: usize,
+    /// Merged pull requests
+    pub merged: usize,
+}
+
+impl PeriodTotals {
+    /// The totals of the activity of a report
+    pub fn of(activities: &BTreeMap<String, RepoActivity>) -> Self {
+        activities
+            .values()
+            .fold(PeriodTotals::default(), |totals, activity| PeriodTotals {
+                opened: totals.opened + activity.new_issues.len() + activity.new_prs.len(),
+                closed: totals.closed + activity.closed_issues.len(),
+                merged: totals.merged + activity.merged_prs.len(),
+            })
+    }
+}
+
+/// The totals of one report
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    pub flavor: ReportFlavor,
+    /// Start of the report's period
+    pub since: Timestamp,
+    /// End of the report's period, when it was generated
+    pub until: Timestamp,
+    pub totals: PeriodTotals,
+}
+
+/// The history file
+pub struct ReportHistory {
+    path: PathBuf,
+}
+
+impl ReportHistory {
+    /// The history kept next to `state_file`
+    pub fn beside(state_file: &Path) -> Self {
+        ReportHistory {
+            path: state_file.with_file_name(HISTORY_FILE_NAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All entries, oldest first
+    pub fn load(&self) -> Result<Vec<HistoryEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read report history {:?}", self.path))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse report history {:?}", self.path))
+    }
+
+    /// Add an entry
+    pub fn append(&self, entry: HistoryEntry) -> Result<()> {
+        let mut entries = self.load()?;
+        entries.push(entry);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        let json =
+            serde_json::to_string_pretty(&entries).context("Failed to serialize report history")?;
+        fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write report history {:?}", self.path))
+    }
+}
+
+/// The latest report of `flavor` that ended before the period starting at
+/// `since`, give or take a day
+///
+/// A second weekly report in the same week is compared to the week before,
+/// not to the first one.
+pub fn previous_period(
+    entries: &[HistoryEntry],
+    flavor: ReportFlavor,
+    since: Timestamp,
+) -> Option<&HistoryEntry> {
+    let latest_end = since + PERIOD_SLACK_HOURS.hours();
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.flavor == flavor && entry.until <= latest_end)
+}
+
+/// The totals of a rollup next to those of the previous period
+#[derive(Debug, Clone)]
+pub struct PeriodComparison {
+    pub flavor: ReportFlavor,
+    pub current: PeriodTotals,
+    /// The previous report of the same flavor, unless this is the first
+    pub previous: Option<HistoryEntry>,
+}
+
+impl PeriodComparison {
+    /// `(label, this period, change since the previous one)` for each count
+    pub fn rows(&self) -> [(&'static str, usize, Option<i64>); 3] {
+        let change = |current: usize, previous: fn(&PeriodTotals) -> usize| {
+            self.previous
+                .as_ref()
+                .map(|entry| current as i64 - previous(&entry.totals) as i64)
+        };
+        [
+            (
+                "Opened",
+                self.current.opened,
+                change(self.current.opened, |t| t.opened),
+            ),
+            (
+                "Closed",
+                self.current.closed,
+                change(self.current.closed, |t| t.closed),
+            ),
+            (
+                "Merged",
+                self.current.merged,
+                change(self.current.merged, |t| t.merged),
+            ),
+        ]
+    }
+
+    /// The comparison as prompt context, e.g. `Opened: 12 (last week: 9)`
+    pub fn prompt_lines(&self) -> String {
+        self.rows()
+            .iter()
+            .map(|(label, count, change)| match change {
+                Some(change) => format!(
+                    "- {}: {} (last {}: {})\n",
+                    label,
+                    count,
+                    self.flavor.period(),
+                    *count as i64 - change
+                ),
+                None => format!("- {}: {}\n", label, count),
+            })
+            .collect()
+    }
+}
+
+/// A change in a count, e.g. `+3`, `-2`, or `no change`
+pub fn format_change(change: i64) -> String {
+    match change {
+        0 => "no change".to_string(),
+        change if change > 0 => format!("+{}", change),
+        change => change.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(flavor: ReportFlavor, until: &str, opened: usize) -> HistoryEntry {
+        let until: Timestamp = until.parse().unwrap();
+        HistoryEntry {
+            flavor,
+            since: until - (7 * 24).hours(),
+            until,
+            totals: PeriodTotals {
+                opened,
+                closed: 2,
+                merged: 3,
+            },
+        }
+    }
+
+    #[test]
+    fn test_previous_period() {
+        let entries = vec![
+            entry(ReportFlavor::Weekly, "2024-03-04T09:00:00Z", 5),
+            entry(ReportFlavor::Weekly, "2024-03-11T09:05:00Z", 9),
+            entry(ReportFlavor::Daily, "2024-03-17T09:00:00Z", 1),
+            entry(ReportFlavor::Weekly, "2024-03-18T09:00:00Z", 11),
+        ];
+        // The next Monday's report, a little early
+        let since: Timestamp = "2024-03-18T08:00:00Z".parse().unwrap();
+        let previous = previous_period(&entries, ReportFlavor::Weekly, since).unwrap();
+        assert_eq!(previous.totals.opened, 11);
+
+        // A second report on the same Monday skips the first
+        let since: Timestamp = "2024-03-11T12:00:00Z".parse().unwrap();
+        let previous = previous_period(&entries, ReportFlavor::Weekly, since).unwrap();
+        assert_eq!(previous.totals.opened, 9);
+
+        let since: Timestamp = "2024-02-01T00:00:00Z".parse().unwrap();
+        assert!(previous_period(&entries, ReportFlavor::Monthly, since).is_none());
+        assert!(previous_period(&entries, ReportFlavor::Weekly, since).is_none());
+    }
+
+    #[test]
+    fn test_comparison_rows() {
+        let comparison = PeriodComparison {
+            flavor: ReportFlavor::Weekly,
+            current: PeriodTotals {
+                opened: 12,
+                closed: 1,
+                merged: 3,
+            },
+            previous: Some(entry(ReportFlavor::Weekly, "2024-03-11T09:00:00Z", 9)),
+        };
+        assert_eq!(
+            comparison.rows(),
+            [
+                ("Opened", 12, Some(3)),
+                ("Closed", 1, Some(-1)),
+                ("Merged", 3, Some(0))
+            ]
+        );
+        assert_eq!(
+            comparison.prompt_lines(),
+            "- Opened: 12 (last week: 9)\n- Closed: 1 (last week: 2)\n- Merged: 3 (last week: 3)\n"
+        );
+        assert_eq!(format_change(3), "+3");
+        assert_eq!(format_change(-1), "-1");
+        assert_eq!(format_change(0), "no change");
+    }
+}
@@ -0,0 +1,184 @@
+//! Per-person breakdown of team reports, for `report.users`
+
+use std::collections::BTreeMap;
+
+use crate::github::ActivityEvent;
+
+/// Most items listed under each person
+pub const MAX_ITEMS_PER_PERSON: usize = 10;
+
+/// What one team member did during the report window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonActivity {
+    pub login: String,
+    pub event_count: usize,
+    /// Counts of what they did, most frequent first
+    pub actions: Vec<(TeamAction, usize)>,
+    /// The issues and PRs they worked on as `(repo, number)`, most recent first
+    pub items: Vec<(String, u32)>,
+}
+
+/// Break `events` down by the users they were performed by, in the order of `users`
+///
+/// Users without events are kept, so the report shows who was quiet.
+pub fn team_breakdown(users: &[String], events: &[&ActivityEvent]) -> Vec<PersonActivity> {
+    users
+        .iter()
+        .map(|login| {
+            let mut events: Vec<&ActivityEvent> = events
+                .iter()
+                .copied()
+                .filter(|event| event.actor.login.eq_ignore_ascii_case(login))
+                .collect();
+            events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+            let mut counts: BTreeMap<TeamAction, usize> = BTreeMap::new();
+            let mut items: Vec<(String, u32)> = Vec::new();
+            for event in &events {
+                if let Some(action) = TeamAction::of(event) {
+                    *counts.entry(action).or_default() += 1;
+                }
+                if let Some(item) = crate::activity::issue_references(&[*event]).pop() {
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+            }
+            let mut actions: Vec<(TeamAction, usize)> = counts.into_iter().collect();
+            actions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+            PersonActivity {
+                login: login.clone(),
+                event_count: events.len(),
+                actions,
+                items,
+            }
+        })
+        .collect()
+}
+
+/// What an event counts as in the breakdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TeamAction {
+    PrOpened,
+    PrMerged,
+    PrClosed,
+    IssueOpened,
+    IssueClosed,
+    Review,
+    ReviewComment,
+    Comment,
+}
+
+impl TeamAction {
+    fn of(event: &ActivityEvent) -> Option<Self> {
+        let action = event.payload.get("action").and_then(|a| a.as_str());
+        match (event.event_type.as_str(), action) {
+            ("PullRequestEvent", Some("opened")) => Some(TeamAction::PrOpened),
+            ("PullRequestEvent", Some("closed")) => {
+                let merged = event
+                    .payload
+                    .get("pull_request")
+                    .and_then(|pr| pr.get("merged"))
+                    .and_then(|merged| merged.as_bool())
+                    .unwrap_or(false);
+                Some(if merged {
+                    TeamAction::PrMerged
+                } else {
+                    TeamAction::PrClosed
+                })
+            }
+            ("IssuesEvent", Some("opened")) => Some(TeamAction::IssueOpened),
+            ("IssuesEvent", Some("closed")) => Some(TeamAction::IssueClosed),
+            ("IssueCommentEvent", _) => Some(TeamAction::Comment),
+            ("PullRequestReviewEvent", _) => Some(TeamAction::Review),
+            ("PullRequestReviewCommentEvent", _) => Some(TeamAction::ReviewComment),
+            _ => None,
+        }
+    }
+
+    /// What `count` of these are called, e.g. `PRs merged`
+    pub fn describe(&self, count: usize) -> &'static str {
+        let (one, many) = match self {
+            TeamAction::PrOpened => ("PR opened", "PRs opened"),
+            TeamAction::PrMerged => ("PR merged", "PRs merged"),
+            TeamAction::PrClosed => ("PR closed", "PRs closed"),
+            TeamAction::IssueOpened => ("issue opened", "issues opened"),
+            TeamAction::IssueClosed => ("issue closed", "issues closed"),
+            TeamAction::Review => ("review", "reviews"),
+            TeamAction::ReviewComment => ("review comment", "review comments"),
+            TeamAction::Comment => ("comment", "comments"),
+        };
+        if count == 1 {
+            one
+        } else {
+            many
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: u32, actor: &str, event_type: &str, payload: serde_json::Value) -> ActivityEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "type": event_type,
+            "actor": { "login": actor },
+            "repo": { "id": 1, "name": "tokio-rs/tokio", "url": "" },
+            "payload": payload,
+            "created_at": format!("2024-03-06T{:02}:00:00Z", id),
+            "public": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_team_breakdown() {
+        let events = [
+            event(
+                1,
+                "alice",
+                "PullRequestEvent",
+                serde_json::json!({ "action": "opened", "pull_request": { "number": 10 } }),
+            ),
+            event(
+                2,
+                "bob",
+                "PullRequestReviewEvent",
+                serde_json::json!({ "action": "created", "pull_request": { "number": 10 } }),
+            ),
+            event(
+                3,
+                "Alice",
+                "IssueCommentEvent",
+                serde_json::json!({ "action": "created", "issue": { "number": 4 } }),
+            ),
+            event(
+                4,
+                "alice",
+                "IssueCommentEvent",
+                serde_json::json!({ "action": "created", "issue": { "number": 4 } }),
+            ),
+        ];
+        let events: Vec<&ActivityEvent> = events.iter().collect();
+        let users = ["alice".to_string(), "bob".to_string(), "carol".to_string()];
+
+        let team = team_breakdown(&users, &events);
+        assert_eq!(team[0].event_count, 3);
+        assert_eq!(
+            team[0].actions,
+            [(TeamAction::Comment, 2), (TeamAction::PrOpened, 1)]
+        );
+        assert_eq!(
+            team[0].items,
+            [
+                ("tokio-rs/tokio".to_string(), 4),
+                ("tokio-rs/tokio".to_string(), 10)
+            ]
+        );
+        assert_eq!(team[1].actions, [(TeamAction::Review, 1)]);
+        assert_eq!(team[2].event_count, 0);
+    }
+}
@@ -1,19 +1,379 @@
 use anyhow::Result;
 use jiff::Timestamp;
+use serde_json::json;
 use std::collections::BTreeMap;
-use std::fmt::Write;
+use tracing::info_span;
 
-use crate::config::Config;
-use crate::github::{Issue, IssueState, RepoActivity};
-use crate::intelligence::AnalysisResult;
+use super::discussions::DiscussionNote;
+use super::engine::TemplateEngine;
+use super::mentions::Mention;
+use super::milestone::group_by_milestone;
+use super::org::group_by_org;
+use super::projects::{active_items, status_groups, MAX_ITEMS_PER_STATUS};
+use super::releases::ReleaseNote;
+use super::rollup::{format_change, PeriodComparison};
+use super::team::{PersonActivity, MAX_ITEMS_PER_PERSON};
+use super::upcoming::UpcomingMilestone;
+use super::{html, ActivityCounts, FilteredItems, Locale};
+use crate::config::{Config, GroupBy, ReportFormat, ReportSections};
+use crate::git::LocalWork;
+use crate::github::{
+    CiState, CiStatus, Discussion, Issue, Milestone, Notification, ProjectBoard, RateLimit,
+    RepoActivity,
+};
+use crate::intelligence::{
+    AnalysisResult, AwaitingReply, CiFailure, Escalation, GoalCheckIn, ReviewerLoad, RuleMatch,
+    ThreadUpdate, Truncation, MAX_PRS_PER_REVIEWER,
+};
+use crate::state::SeenStatus;
 
 pub struct ReportTemplate<'a> {
-    _config: &'a Config,
+    config: &'a Config,
+    pub(super) current_user: Option<String>,
+    /// How the items relate to the previous reports, keyed by URL
+    pub(super) seen_statuses: BTreeMap<String, SeenStatus>,
+    pub(super) local_work: Vec<LocalWork>,
+    pub(super) ci_failures: Vec<CiFailure>,
+    /// CI state of open PRs, keyed by `(repo, number)`
+    pub(super) ci_statuses: BTreeMap<(String, u32), CiStatus>,
+    pub(super) thread_updates: Vec<ThreadUpdate>,
+    pub(super) since_last_report: bool,
+    pub(super) locale: Locale,
+    pub(super) org_summaries: BTreeMap<String, String>,
+    pub(super) notifications: Vec<Notification>,
+    pub(super) mentions: Vec<Mention>,
+    pub(super) awaiting_reply: Vec<AwaitingReply>,
+    /// Threads with rising frustration or conflict, most heated first
+    pub(super) escalations: Vec<Escalation>,
+    /// Open PRs of repositories the user can push to, by requested reviewer
+    pub(super) reviewer_workload: Vec<ReviewerLoad>,
+    pub(super) goal_check_ins: Vec<GoalCheckIn>,
+    /// Open milestones due within `report.upcoming_days`
+    pub(super) upcoming: Vec<UpcomingMilestone>,
+    pub(super) releases: Vec<ReleaseNote>,
+    pub(super) discussions: Vec<DiscussionNote>,
+    pub(super) projects: Vec<ProjectBoard>,
+    pub(super) team: Vec<PersonActivity>,
+    /// Items scoring below `report.min_priority_score`, listed in the appendix
+    pub(super) low_priority: Vec<(String, Issue)>,
+    /// What was left out to stay within the report limits
+    pub(super) truncation: Truncation,
+    /// Items left out by their author, `exclude_labels`, and `report.require_labels`
+    pub(super) filtered: FilteredItems,
+    pub(super) rate_limit: Option<RateLimit>,
+    /// Provider and model that wrote the AI summary, for the footer
+    pub(super) summarized_by: Option<String>,
+    pub(super) feed_truncated_at: Option<Timestamp>,
+    /// Counts of a weekly or monthly rollup next to those of the previous period
+    pub(super) comparison: Option<PeriodComparison>,
+    /// Markdown printed by the `pre_report` hooks
+    pub(super) notes: Vec<String>,
 }
 
 impl<'a> ReportTemplate<'a> {
     pub fn new(config: &'a Config) -> Self {
-        ReportTemplate { _config: config }
+        ReportTemplate {
+            config,
+            current_user: None,
+            seen_statuses: BTreeMap::new(),
+            local_work: Vec::new(),
+            ci_failures: Vec::new(),
+            ci_statuses: BTreeMap::new(),
+            thread_updates: Vec::new(),
+            since_last_report: false,
+            locale: config.report.locale(),
+            org_summaries: BTreeMap::new(),
+            notifications: Vec::new(),
+            mentions: Vec::new(),
+            awaiting_reply: Vec::new(),
+            escalations: Vec::new(),
+            reviewer_workload: Vec::new(),
+            goal_check_ins: Vec::new(),
+            upcoming: Vec::new(),
+            releases: Vec::new(),
+            discussions: Vec::new(),
+            projects: Vec::new(),
+            team: Vec::new(),
+            low_priority: Vec::new(),
+            truncation: Truncation::default(),
+            filtered: FilteredItems::default(),
+            rate_limit: None,
+            summarized_by: None,
+            feed_truncated_at: None,
+            comparison: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Set the authenticated user, used to badge items that involve them
+    pub fn with_current_user(mut self, login: Option<&str>) -> Self {
+        self.current_user = login.map(str::to_string);
+        self
+    }
+
+    /// Set how the items relate to the previous reports, keyed by URL
+    pub fn with_seen_statuses(mut self, seen_statuses: BTreeMap<String, SeenStatus>) -> Self {
+        self.seen_statuses = seen_statuses;
+        self
+    }
+
+    /// Set local git work to connect with the report items
+    pub fn with_local_work(mut self, local_work: Vec<LocalWork>) -> Self {
+        self.local_work = local_work;
+        self
+    }
+
+    /// Set failed CI runs to show with their probable cause
+    pub fn with_ci_failures(mut self, ci_failures: Vec<CiFailure>) -> Self {
+        self.ci_failures = ci_failures;
+        self
+    }
+
+    /// Set the CI state of open PRs, keyed by `(repo, number)`
+    pub fn with_ci_statuses(mut self, ci_statuses: BTreeMap<(String, u32), CiStatus>) -> Self {
+        self.ci_statuses = ci_statuses;
+        self
+    }
+
+    /// Set what changed in recurring hot threads since the previous report
+    pub fn with_thread_updates(mut self, thread_updates: Vec<ThreadUpdate>) -> Self {
+        self.thread_updates = thread_updates;
+        self
+    }
+
+    /// Label the period as starting at the previous report
+    pub fn with_since_last_report(mut self, since_last_report: bool) -> Self {
+        self.since_last_report = since_last_report;
+        self
+    }
+
+    /// Set AI summaries shown under each organization header, keyed by org
+    pub fn with_org_summaries(mut self, org_summaries: BTreeMap<String, String>) -> Self {
+        self.org_summaries = org_summaries;
+        self
+    }
+
+    /// Set review requests and mentions that are missing from the activity
+    pub fn with_notifications(mut self, notifications: Vec<Notification>) -> Self {
+        self.notifications = notifications;
+        self
+    }
+
+    /// Set the items mentioning the users and teams in `settings.include_mentions`
+    pub fn with_mentions(mut self, mentions: Vec<Mention>) -> Self {
+        self.mentions = mentions;
+        self
+    }
+
+    /// Set the comments that wait on a reply from the user
+    pub fn with_awaiting_reply(mut self, awaiting_reply: Vec<AwaitingReply>) -> Self {
+        self.awaiting_reply = awaiting_reply;
+        self
+    }
+
+    /// Set Claude's check-in on the configured goals
+    pub fn with_goal_check_ins(mut self, goal_check_ins: Vec<GoalCheckIn>) -> Self {
+        self.goal_check_ins = goal_check_ins;
+        self
+    }
+
+    /// Set the threads needing de-escalation
+    pub fn with_escalations(mut self, escalations: Vec<Escalation>) -> Self {
+        self.escalations = escalations;
+        self
+    }
+
+    /// Set the open PRs waiting on each reviewer
+    pub fn with_reviewer_workload(mut self, reviewer_workload: Vec<ReviewerLoad>) -> Self {
+        self.reviewer_workload = reviewer_workload;
+        self
+    }
+
+    /// Set the open milestones that are due soon
+    pub fn with_upcoming(mut self, upcoming: Vec<UpcomingMilestone>) -> Self {
+        self.upcoming = upcoming;
+        self
+    }
+
+    /// Set the releases published in the period
+    pub fn with_releases(mut self, releases: Vec<ReleaseNote>) -> Self {
+        self.releases = releases;
+        self
+    }
+
+    /// Set the discussions with activity in the period
+    pub fn with_discussions(mut self, discussions: Vec<DiscussionNote>) -> Self {
+        self.discussions = discussions;
+        self
+    }
+
+    /// Set the project boards of `report.projects`
+    pub fn with_projects(mut self, projects: Vec<ProjectBoard>) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    /// Set what each member of a team report did
+    pub fn with_team(mut self, team: Vec<PersonActivity>) -> Self {
+        self.team = team;
+        self
+    }
+
+    /// Set the low-priority items collapsed into the appendix
+    pub fn with_low_priority(mut self, low_priority: Vec<(String, Issue)>) -> Self {
+        self.low_priority = low_priority;
+        self
+    }
+
+    /// Set what was left out to stay within `max_issues_per_report` and
+    /// `max_comments_per_report`
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Set the counts of items left out by their author or labels, shown in the header
+    pub fn with_filtered(mut self, filtered: FilteredItems) -> Self {
+        self.filtered = filtered;
+        self
+    }
+
+    /// Set the GitHub API quota left after the run, shown in the footer
+    pub fn with_rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Note which provider and model wrote the AI summary, in the footer
+    pub fn with_summarized_by(mut self, summarized_by: Option<String>) -> Self {
+        self.summarized_by = summarized_by;
+        self
+    }
+
+    /// Warn that the activity feed did not reach back to the start of the period
+    pub fn with_feed_truncated_at(mut self, truncated_at: Option<Timestamp>) -> Self {
+        self.feed_truncated_at = truncated_at;
+        self
+    }
+
+    /// Make the report a weekly or monthly rollup, comparing its counts to
+    /// the previous period
+    pub fn with_comparison(mut self, comparison: Option<PeriodComparison>) -> Self {
+        self.comparison = comparison;
+        self
+    }
+
+    /// Set the markdown printed by the `pre_report` hooks, shown as it is
+    pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    /// Title of the report, e.g. `GitHub Activity Report` or `Weekly GitHub Rollup`
+    pub(super) fn heading(&self) -> String {
+        match &self.comparison {
+            Some(comparison) => format!("{} GitHub Rollup", comparison.flavor.label()),
+            None => "GitHub Activity Report".to_string(),
+        }
+    }
+
+    /// Heading of the comparison, e.g. `Compared to Last Week`
+    pub(super) fn comparison_heading(comparison: &PeriodComparison) -> String {
+        let period = comparison.flavor.period();
+        let period = format!("{}{}", period[..1].to_uppercase(), &period[1..]);
+        match comparison.previous {
+            Some(_) => format!("Compared to Last {}", period),
+            None => format!("This {}", period),
+        }
+    }
+
+    /// Each count of the comparison with its change, e.g. `("Opened", "12 (+3 from 9)")`
+    pub(super) fn comparison_counts(&self, comparison: &PeriodComparison) -> Vec<(&str, String)> {
+        comparison
+            .rows()
+            .into_iter()
+            .map(|(label, count, change)| {
+                let count_text = self.locale.format_count(count as i64);
+                let text = match change {
+                    Some(0) => format!("{} (no change)", count_text),
+                    Some(change) => format!(
+                        "{} ({} from {})",
+                        count_text,
+                        format_change(change),
+                        self.locale.format_count(count as i64 - change)
+                    ),
+                    None => count_text,
+                };
+                (label, text)
+            })
+            .collect()
+    }
+
+    /// Note for a rollup without a previous report to compare with
+    pub(super) fn comparison_note(comparison: &PeriodComparison) -> Option<String> {
+        comparison.previous.is_none().then(|| {
+            format!(
+                "No earlier {} report to compare with yet.",
+                comparison.flavor.label().to_lowercase()
+            )
+        })
+    }
+
+    /// Why the activity feed is incomplete, when it was cut short
+    pub(super) fn feed_warning_text(&self) -> Option<String> {
+        let truncated_at = self.feed_truncated_at?;
+        Some(format!(
+            "GitHub only returned events back to {}. \
+             Earlier items were found through search and may be incomplete.",
+            self.locale.format_datetime(truncated_at)
+        ))
+    }
+
+    /// Footer line with the API quota left, e.g. `GitHub API: 4,210 of 5,000 requests left`
+    pub(super) fn rate_limit_text(&self) -> Option<String> {
+        let rate_limit = self.rate_limit?;
+        Some(format!(
+            "GitHub API: {} of {} requests left, resets {}",
+            self.locale.format_count(i64::from(rate_limit.remaining)),
+            self.locale.format_count(i64::from(rate_limit.limit)),
+            self.locale.format_datetime(rate_limit.reset)
+        ))
+    }
+
+    /// The organization of an org-wide report
+    pub(super) fn org(&self) -> Option<&str> {
+        self.config.report.org.as_deref()
+    }
+
+    /// Host of the GitHub instance the report is about
+    pub(super) fn github_host(&self) -> &str {
+        self.config.github_host()
+    }
+
+    pub(super) fn group_by(&self) -> GroupBy {
+        self.config.report.group_by
+    }
+
+    /// The built-in sections that are turned on
+    pub(super) fn sections(&self) -> ReportSections {
+        self.config.report.sections
+    }
+
+    /// Subtotal line of an organization, e.g. `2 repositories, 3 issues, 5 pull requests`
+    pub(super) fn org_subtotal(&self, repos: usize, issues: usize, prs: usize) -> String {
+        let count = |n: usize, one: &str, many: &str| {
+            format!(
+                "{} {}",
+                self.locale.format_count(n as i64),
+                if n == 1 { one } else { many }
+            )
+        };
+        format!(
+            "{}, {}, {}",
+            count(repos, "repository", "repositories"),
+            count(issues, "issue", "issues"),
+            count(prs, "pull request", "pull requests")
+        )
     }
 
     pub fn render(
@@ -44,6 +404,11 @@ impl<'a> ReportTemplate<'a> {
                 prioritized_issues: vec![],
                 context_prompt: String::new(),
                 action_items: vec![],
+                waiting_on_author: vec![],
+                waiting_on_you: vec![],
+                needs_info: vec![],
+                new_contributions: vec![],
+                matched_rules: vec![],
             },
         )
     }
@@ -57,266 +422,762 @@ impl<'a> ReportTemplate<'a> {
         ai_summary: Option<&str>,
         analysis: &AnalysisResult,
     ) -> Result<String> {
-        let mut output = String::new();
-
-        self.write_header(&mut output, since, now)?;
-
-        if !errors.is_empty() {
-            self.write_errors(&mut output, errors)?;
+        let _span = info_span!("render").entered();
+        if self.config.report.format == ReportFormat::Html {
+            return html::render(self, activities, since, now, errors, ai_summary, analysis);
         }
 
-        // Add action items if available
-        if !analysis.action_items.is_empty() {
-            writeln!(&mut output, "\n## Action Items\n")?;
-            for (i, action) in analysis.action_items.iter().enumerate() {
-                let urgency_text = match action.urgency {
-                    crate::intelligence::Urgency::Critical => "[CRITICAL]",
-                    crate::intelligence::Urgency::High => "[HIGH]",
-                    crate::intelligence::Urgency::Medium => "[MEDIUM]",
-                    crate::intelligence::Urgency::Low => "[LOW]",
-                };
-                writeln!(
-                    &mut output,
-                    "{}. {} {} - {}",
-                    i + 1,
-                    urgency_text,
-                    action.description,
-                    action.reason
-                )?;
-            }
-            writeln!(&mut output)?;
-        }
-
-        // Add highlights if available
-        if let Some(summary) = ai_summary {
-            writeln!(&mut output, "\n## Highlights\n")?;
-            writeln!(&mut output, "{}", summary)?;
-        }
+        let engine = TemplateEngine::new(self.config.report.template.as_deref(), self.locale)?;
+        engine.render(self.template_context(activities, since, now, errors, ai_summary, analysis))
+    }
 
-        if activities.is_empty() {
-            writeln!(&mut output, "\n## No Activity\n")?;
-            writeln!(
-                &mut output,
-                "No issues or pull requests were updated in the specified time period."
-            )?;
-        } else {
-            self.write_summary(&mut output, activities)?;
-
-            // Add prioritized issues section if available
-            if !analysis.prioritized_issues.is_empty() {
-                writeln!(&mut output, "\n## Prioritized Items\n")?;
-
-                // Show top 10 prioritized items
-                for issue in analysis.prioritized_issues.iter().take(10) {
-                    let type_str = if issue.issue.is_pull_request {
-                        "PR"
-                    } else {
-                        "Issue"
-                    };
-                    writeln!(
-                        &mut output,
-                        "- **[{}]** {} [#{}]({}) - {} (Score: {})",
-                        issue.repo,
-                        type_str,
-                        issue.issue.number,
-                        issue.issue.url,
-                        issue.issue.title,
-                        issue.score.total
-                    )?;
-                }
-                writeln!(&mut output)?;
-            }
+    /// Everything the report template renders, and a `report.template` can refer to
+    fn template_context(
+        &self,
+        activities: &BTreeMap<String, RepoActivity>,
+        since: Timestamp,
+        now: Timestamp,
+        errors: &[String],
+        ai_summary: Option<&str>,
+        analysis: &AnalysisResult,
+    ) -> serde_json::Value {
+        let items = |repo: &str, issues: &[Issue]| -> Vec<serde_json::Value> {
+            issues
+                .iter()
+                .map(|issue| self.item_value(repo, issue))
+                .collect()
+        };
+        let repo_value = |repo: &str, activity: &RepoActivity| {
+            json!({
+                "name": repo,
+                "has_items": has_items(activity),
+                "new_issues": items(repo, &activity.new_issues),
+                "updated_issues": items(repo, &activity.updated_issues),
+                "closed_issues": items(repo, &activity.closed_issues),
+                "new_prs": items(repo, &activity.new_prs),
+                "updated_prs": items(repo, &activity.updated_prs),
+                "merged_prs": items(repo, &activity.merged_prs),
+            })
+        };
 
-            self.write_activities(&mut output, activities)?;
-        }
+        let action_items: Vec<_> = analysis
+            .action_items
+            .iter()
+            .map(|action| {
+                self.item_with(
+                    &action.repo,
+                    &action.issue,
+                    json!({
+                        "urgency": format!("{:?}", action.urgency).to_lowercase(),
+                        "description": action.description,
+                        "reason": action.reason,
+                        "excerpt": action.excerpt,
+                    }),
+                )
+            })
+            .collect();
+        let awaiting_reply: Vec<_> = self
+            .awaiting_reply
+            .iter()
+            .map(|reply| {
+                self.item_with(
+                    &reply.repo,
+                    &reply.issue,
+                    json!({
+                        "reason": reply.reason.describe(),
+                        "comment": {
+                            "author": reply.author,
+                            "text": reply.text,
+                            "url": reply.url,
+                            "location": reply.location,
+                            "date": self.locale.format_date(reply.created_at),
+                        },
+                    }),
+                )
+            })
+            .collect();
+        let escalations: Vec<_> = self
+            .escalations
+            .iter()
+            .map(|escalation| {
+                self.item_with(
+                    &escalation.repo,
+                    &escalation.issue,
+                    json!({ "level": escalation.level.label(), "reason": escalation.reason }),
+                )
+            })
+            .collect();
+        let waiting_on_you: Vec<_> = analysis
+            .waiting_on_you
+            .iter()
+            .map(|item| {
+                self.item_with(
+                    &item.repo,
+                    &item.issue,
+                    json!({ "from": item.from, "idle_days": item.idle_days, "nudge": item.nudge }),
+                )
+            })
+            .collect();
+        let waiting_on_others: Vec<_> = analysis
+            .waiting_on_author
+            .iter()
+            .map(|item| {
+                self.item_with(
+                    &item.repo,
+                    &item.issue,
+                    json!({ "reason": item.reason.describe(), "idle_days": item.idle_days }),
+                )
+            })
+            .collect();
+        let reviewer_workload: Vec<_> = self
+            .reviewer_workload
+            .iter()
+            .map(|load| {
+                json!({
+                    "reviewer": load.reviewer,
+                    "is_team": load.reviewer.contains('/'),
+                    "summary": self.reviewer_summary(load),
+                    "reviews": &load.reviews[..load.reviews.len().min(MAX_PRS_PER_REVIEWER)],
+                    "more": load.reviews.len().saturating_sub(MAX_PRS_PER_REVIEWER),
+                })
+            })
+            .collect();
+        let new_contributors: Vec<_> = analysis
+            .new_contributions
+            .iter()
+            .map(|contribution| {
+                self.item_with(
+                    &contribution.repo,
+                    &contribution.issue,
+                    json!({ "opened": self.locale.format_date(contribution.issue.created_at) }),
+                )
+            })
+            .collect();
+        let needs_info: Vec<_> = analysis
+            .needs_info
+            .iter()
+            .map(|item| {
+                let missing: Vec<&str> = item.missing.iter().map(|s| s.describe()).collect();
+                self.item_with(
+                    &item.repo,
+                    &item.issue,
+                    json!({ "missing": missing, "draft_comment": item.draft_comment }),
+                )
+            })
+            .collect();
+        let ci_attention: Vec<_> = self
+            .ci_attention(activities)
+            .into_iter()
+            .map(|(repo, pr, status)| {
+                self.item_with(repo, pr, json!({ "failing": status.failing }))
+            })
+            .collect();
+        let ci_failures: Vec<_> = self
+            .ci_failures
+            .iter()
+            .map(|failure| {
+                self.item_with(
+                    &failure.repo,
+                    &failure.pr,
+                    json!({
+                        "run": {
+                            "name": failure.run.name.as_deref().unwrap_or("workflow"),
+                            "url": failure.run.html_url,
+                        },
+                        "explanation": failure.explanation,
+                        "excerpt": failure.excerpt,
+                    }),
+                )
+            })
+            .collect();
+        let local_work: Vec<_> = self
+            .local_work
+            .iter()
+            .map(|work| {
+                let items: Vec<_> = work
+                    .items
+                    .iter()
+                    .map(|item| json!({ "number": item.number, "title": item.title, "url": item.url }))
+                    .collect();
+                json!({
+                    "branch": work.branch.name,
+                    "upstream": work.branch.upstream,
+                    "unpushed_commits": work.branch.unpushed_commits,
+                    "items": items,
+                })
+            })
+            .collect();
+        let notifications: Vec<_> = self
+            .notifications
+            .iter()
+            .filter_map(|notification| {
+                Some(json!({
+                    "repo": notification.repository.full_name,
+                    "number": notification.item_number()?,
+                    "url": notification.html_url(self.github_host())?,
+                    "title": notification.subject.title,
+                    "kind": if notification.is_pull_request() { "pr" } else { "issue" },
+                    "reason": notification.describe_reason(),
+                    "date": self.locale.format_date(notification.updated_at),
+                }))
+            })
+            .collect();
+        let mentions: Vec<_> = self
+            .mentions
+            .iter()
+            .map(|mention| {
+                self.item_with(
+                    &mention.repo,
+                    &mention.issue,
+                    json!({ "targets": mention.targets }),
+                )
+            })
+            .collect();
+        let watch_rules: Vec<_> = by_rule(&analysis.matched_rules)
+            .into_iter()
+            .map(|(rule, matches)| {
+                let items: Vec<_> = matches
+                    .iter()
+                    .map(|item| self.item_value(&item.repo, &item.issue))
+                    .collect();
+                json!({ "name": rule, "items": items })
+            })
+            .collect();
+        let goals: Vec<_> = self
+            .goal_check_ins
+            .iter()
+            .map(|check_in| {
+                json!({
+                    "goal": check_in.goal,
+                    "status": check_in.status.label(),
+                    "assessment": check_in.assessment,
+                })
+            })
+            .collect();
+        let upcoming: Vec<_> = self
+            .upcoming
+            .iter()
+            .map(|upcoming| {
+                json!({
+                    "repo": upcoming.repo,
+                    "title": upcoming.milestone.title,
+                    "url": upcoming.milestone.html_url,
+                    "due": self.locale.format_date(upcoming.due_on),
+                    "due_in": upcoming.due_in(),
+                    "progress": self.milestone_progress(upcoming),
+                })
+            })
+            .collect();
+        let releases: Vec<_> = self
+            .releases
+            .iter()
+            .map(|note| {
+                json!({
+                    "repo": note.repo,
+                    "title": note.release.title(),
+                    "url": note.release.html_url,
+                    "tag": note.release.tag_name,
+                    "summary": note.summary,
+                })
+            })
+            .collect();
+        let discussions: Vec<_> = self
+            .discussions
+            .iter()
+            .map(|note| {
+                json!({
+                    "repo": note.repo,
+                    "title": note.discussion.title,
+                    "url": note.discussion.url,
+                    "status": discussion_status(&note.discussion),
+                    "summary": note.summary,
+                })
+            })
+            .collect();
+        let active = active_items(activities);
+        let projects: Vec<_> = self
+            .projects
+            .iter()
+            .map(|board| {
+                let groups: Vec<_> = status_groups(board, &active, Some(MAX_ITEMS_PER_STATUS))
+                    .into_iter()
+                    .map(|group| {
+                        let items: Vec<_> = group
+                            .items
+                            .iter()
+                            .map(|(item, is_active)| {
+                                json!({
+                                    "repo": item.repo,
+                                    "number": item.number,
+                                    "title": item.title,
+                                    "url": item.url,
+                                    "kind": if item.is_pull_request { "pr" } else { "issue" },
+                                    "state": format!("{:?}", item.state).to_lowercase(),
+                                    "active": is_active,
+                                })
+                            })
+                            .collect();
+                        json!({
+                            "status": group.status,
+                            "total": group.total,
+                            "active": group.active,
+                            "more": group.total - group.items.len(),
+                            "items": items,
+                        })
+                    })
+                    .collect();
+                json!({ "title": board.title, "url": board.url, "groups": groups })
+            })
+            .collect();
+        let team: Vec<_> = self
+            .team
+            .iter()
+            .map(|person| {
+                let items: Vec<_> = person
+                    .items
+                    .iter()
+                    .take(MAX_ITEMS_PER_PERSON)
+                    .map(
+                        |(repo, number)| match find_issue(activities, repo, *number) {
+                            Some(issue) => self.item_value(repo, issue),
+                            None => json!({
+                                "repo": repo,
+                                "number": number,
+                                "url": format!("https://github.com/{}/issues/{}", repo, number),
+                            }),
+                        },
+                    )
+                    .collect();
+                json!({
+                    "login": person.login,
+                    "summary": self.person_summary(person),
+                    "items": items,
+                    "more": person.items.len().saturating_sub(MAX_ITEMS_PER_PERSON),
+                })
+            })
+            .collect();
+        let counts: Vec<_> = ActivityCounts::of(activities)
+            .labeled()
+            .into_iter()
+            .map(|(label, count)| json!({ "label": label, "count": count }))
+            .collect();
+        let prioritized: Vec<_> = analysis
+            .prioritized_issues
+            .iter()
+            .map(|item| {
+                let waiting = analysis
+                    .waiting_on_author
+                    .iter()
+                    .any(|w| w.repo == item.repo && w.issue.number == item.issue.number);
+                let update = self
+                    .thread_updates
+                    .iter()
+                    .find(|u| u.repo == item.repo && u.issue_number == item.issue.number);
+                self.item_with(
+                    &item.repo,
+                    &item.issue,
+                    json!({
+                        "score": item.score.total,
+                        "waiting_on_author": waiting,
+                        "update": update.map(|u| &u.change),
+                    }),
+                )
+            })
+            .collect();
+        let repos: Vec<_> = activities
+            .iter()
+            .map(|(repo, activity)| repo_value(repo, activity))
+            .collect();
+        let orgs: Vec<_> = group_by_org(activities)
+            .into_iter()
+            .filter_map(|group| {
+                let repos: Vec<_> = group
+                    .repos
+                    .iter()
+                    .filter(|(_, activity)| has_items(activity))
+                    .map(|(repo, activity)| repo_value(repo, activity))
+                    .collect();
+                (!repos.is_empty()).then(|| {
+                    json!({
+                        "name": group.org,
+                        "subtotal": self.org_subtotal(repos.len(), group.issue_count(), group.pr_count()),
+                        "summary": self.org_summaries.get(group.org),
+                        "repos": repos,
+                    })
+                })
+            })
+            .collect();
+        let milestones: Vec<_> = group_by_milestone(activities)
+            .into_iter()
+            .map(|group| {
+                let items: Vec<_> = group
+                    .items
+                    .iter()
+                    .map(|(repo, issue)| self.item_value(repo, issue))
+                    .collect();
+                json!({ "heading": self.milestone_heading(group.milestone), "items": items })
+            })
+            .collect();
+        let truncation = (!self.truncation.is_empty()).then(|| {
+            let skipped: Vec<_> = self
+                .truncation
+                .skipped
+                .iter()
+                .map(|(repo, issue)| self.item_value(repo, issue))
+                .collect();
+            json!({ "notes": self.truncation_notes(), "skipped": skipped })
+        });
+        let low_priority: Vec<_> = self
+            .low_priority
+            .iter()
+            .map(|(repo, issue)| self.item_value(repo, issue))
+            .collect();
+        let comparison = self.comparison.as_ref().map(|comparison| {
+            let counts: Vec<_> = self
+                .comparison_counts(comparison)
+                .into_iter()
+                .map(|(label, text)| json!({ "label": label, "text": text }))
+                .collect();
+            json!({
+                "heading": Self::comparison_heading(comparison),
+                "counts": counts,
+                "note": Self::comparison_note(comparison),
+            })
+        });
 
-        self.write_footer(&mut output)?;
+        let report = json!({
+            "heading": self.heading(),
+            "since": self.locale.format_datetime(since),
+            "until": self.locale.format_datetime(now),
+            "generated": self.locale.format_datetime_with_seconds(now),
+            "since_last_report": self.since_last_report,
+            "org": self.org(),
+            "seen_counts": self.seen_counts(),
+            "filtered": {
+                "labels": self.label_filter_counts(),
+                "authors": self.bot_activity(),
+                "muted": self.muted_items(),
+            },
+            "feed_warning": self.feed_warning_text(),
+            "enabled": self.sections(),
+            "group_by": self.group_by(),
+            "errors": errors,
+            "summary": ai_summary,
+            "version": env!("CARGO_PKG_VERSION"),
+            "summarized_by": self.summarized_by,
+            "rate_limit": self.rate_limit_text(),
+        });
+        let sections = json!({
+            "comparison": comparison,
+            "notes": self.notes,
+            "action_items": action_items,
+            "awaiting_reply": awaiting_reply,
+            "escalations": escalations,
+            "waiting_on_you": waiting_on_you,
+            "waiting_on_others": waiting_on_others,
+            "reviewer_workload": reviewer_workload,
+            "new_contributors": new_contributors,
+            "needs_info": needs_info,
+            "ci_attention": ci_attention,
+            "ci_failures": ci_failures,
+            "local_work": local_work,
+            "notifications": notifications,
+            "mentions": mentions,
+            "watch_rules": watch_rules,
+            "goals": goals,
+            "upcoming": upcoming,
+            "releases": releases,
+            "discussions": discussions,
+            "projects": projects,
+            "team": team,
+            "counts": counts,
+            "prioritized": prioritized,
+            "repos": repos,
+            "orgs": orgs,
+            "milestones": milestones,
+            "truncation": truncation,
+            "low_priority": low_priority,
+            "low_priority_note": self.low_priority_note(),
+        });
+        with_fields(report, sections)
+    }
 
-        Ok(output)
+    /// An issue or PR as seen by the report template, with its badges
+    fn item_value(&self, repo: &str, issue: &Issue) -> serde_json::Value {
+        let user = self.current_user.as_deref();
+        let ci = self.ci_status(issue).map(|status| {
+            json!({
+                "state": format!("{:?}", status.state).to_lowercase(),
+                "emoji": status.state.emoji(),
+                "failing": status.failing,
+            })
+        });
+        json!({
+            "repo": repo,
+            "number": issue.number,
+            "title": issue.title,
+            "url": issue.url,
+            "kind": if issue.is_pull_request { "pr" } else { "issue" },
+            "state": format!("{:?}", issue.state).to_lowercase(),
+            "author": issue.author.login,
+            "labels": issue.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(),
+            "ci": ci,
+            "review_requested": user.is_some_and(|user| issue.is_review_requested_from(user)),
+            "assigned": user.is_some_and(|user| issue.is_assigned_to(user)),
+            "seen": self.seen_statuses.get(&issue.url).map(|status| status.label()),
+        })
     }
 
-    fn write_header(&self, output: &mut String, since: Timestamp, now: Timestamp) -> Result<()> {
-        writeln!(output, "# GitHub Activity Report")?;
-        writeln!(output)?;
-        writeln!(
-            output,
-            "**Period**: {} to {}",
-            since.strftime("%Y-%m-%d %H:%M"),
-            now.strftime("%Y-%m-%d %H:%M")
-        )?;
-        writeln!(
-            output,
-            "**Generated**: {}",
-            now.strftime("%Y-%m-%d %H:%M:%S")
-        )?;
-        Ok(())
+    /// `item_value` with the fields of `extra` added
+    fn item_with(&self, repo: &str, issue: &Issue, extra: serde_json::Value) -> serde_json::Value {
+        with_fields(self.item_value(repo, issue), extra)
     }
 
-    fn write_errors(&self, output: &mut String, errors: &[String]) -> Result<()> {
-        writeln!(output, "\n## Warnings\n")?;
-        for error in errors {
-            writeln!(output, "- {}", error)?;
-        }
-        Ok(())
+    /// How many PRs wait on a reviewer, e.g. `3 PRs, longest waiting 12 days`
+    pub(super) fn reviewer_summary(&self, load: &ReviewerLoad) -> String {
+        format!(
+            "{} {}, longest waiting {} days",
+            self.locale.format_count(load.reviews.len() as i64),
+            if load.reviews.len() == 1 { "PR" } else { "PRs" },
+            self.locale.format_count(load.longest_wait())
+        )
     }
 
-    fn write_summary(
-        &self,
-        output: &mut String,
-        activities: &BTreeMap<String, RepoActivity>,
-    ) -> Result<()> {
-        writeln!(output, "\n## Summary\n")?;
-
-        let mut total_new_issues = 0;
-        let mut total_updated_issues = 0;
-        let mut total_new_prs = 0;
-        let mut total_updated_prs = 0;
-        let mut total_merged_prs = 0;
-        let mut total_closed_issues = 0;
-
-        for activity in activities.values() {
-            total_new_issues += activity.new_issues.len();
-            total_updated_issues += activity.updated_issues.len();
-            total_new_prs += activity.new_prs.len();
-            total_updated_prs += activity.updated_prs.len();
-            total_merged_prs += activity.merged_prs.len();
-            total_closed_issues += activity.closed_issues.len();
+    /// A milestone's title with its due date, or `No milestone`
+    pub(super) fn milestone_heading(&self, milestone: Option<&Milestone>) -> String {
+        match milestone {
+            Some(Milestone {
+                title,
+                due_on: Some(due_on),
+            }) => format!("{} (due {})", title, self.locale.format_date(*due_on)),
+            Some(milestone) => milestone.title.clone(),
+            None => "No milestone".to_string(),
         }
+    }
 
-        writeln!(output, "- **Repositories**: {}", activities.len())?;
-        writeln!(output, "- **New Issues**: {}", total_new_issues)?;
-        writeln!(output, "- **Updated Issues**: {}", total_updated_issues)?;
-        writeln!(output, "- **New Pull Requests**: {}", total_new_prs)?;
-        writeln!(output, "- **Updated Pull Requests**: {}", total_updated_prs)?;
-        writeln!(output, "- **Merged Pull Requests**: {}", total_merged_prs)?;
-        writeln!(output, "- **Closed Issues**: {}", total_closed_issues)?;
+    /// The CI state of a PR in the report
+    pub(super) fn ci_status(&self, issue: &Issue) -> Option<&CiStatus> {
+        let repo = issue.repository_name()?;
+        self.ci_statuses
+            .iter()
+            .find(|((r, number), _)| *number == issue.number && r.eq_ignore_ascii_case(&repo))
+            .map(|(_, status)| status)
+    }
 
-        Ok(())
+    /// PRs with failing checks, except those already explained under CI failures
+    pub(super) fn ci_attention<'b>(
+        &'b self,
+        activities: &'b BTreeMap<String, RepoActivity>,
+    ) -> Vec<(&'b str, &'b Issue, &'b CiStatus)> {
+        self.ci_statuses
+            .iter()
+            .filter(|(_, status)| status.state == CiState::Failing)
+            .filter(|((repo, number), _)| {
+                !self
+                    .ci_failures
+                    .iter()
+                    .any(|failure| failure.repo == *repo && failure.pr.number == *number)
+            })
+            .filter_map(|((repo, number), status)| {
+                Some((
+                    repo.as_str(),
+                    find_issue(activities, repo, *number)?,
+                    status,
+                ))
+            })
+            .collect()
     }
 
-    fn write_activities(
-        &self,
-        output: &mut String,
-        activities: &BTreeMap<String, RepoActivity>,
-    ) -> Result<()> {
-        writeln!(output, "\n## Activity by Repository\n")?;
-
-        for (repo_name, activity) in activities {
-            let total = activity.new_issues.len()
-                + activity.updated_issues.len()
-                + activity.new_prs.len()
-                + activity.updated_prs.len()
-                + activity.merged_prs.len()
-                + activity.closed_issues.len();
-
-            if total == 0 {
-                continue;
-            }
+    /// `3 new, 5 carried over, 2 resolved`, for the report header
+    pub(super) fn seen_counts(&self) -> Option<String> {
+        let counts: Vec<String> = [
+            (SeenStatus::New, "new"),
+            (SeenStatus::CarriedOver, "carried over"),
+            (SeenStatus::Resolved, "resolved"),
+        ]
+        .into_iter()
+        .filter_map(|(status, name)| {
+            let count = self
+                .seen_statuses
+                .values()
+                .filter(|s| **s == status)
+                .count();
+            (count > 0).then(|| format!("{} {}", self.locale.format_count(count as i64), name))
+        })
+        .collect();
+        (!counts.is_empty()).then(|| counts.join(", "))
+    }
 
-            writeln!(output, "### {}\n", repo_name)?;
+    /// How many items were left out by their labels, for the report header
+    pub(super) fn label_filter_counts(&self) -> Option<String> {
+        self.filtered.describe_labels(&self.locale)
+    }
 
-            // Show completed work first to celebrate accomplishments
-            if !activity.merged_prs.is_empty() {
-                writeln!(output, "#### 🎉 Merged Pull Requests\n")?;
-                for pr in &activity.merged_prs {
-                    self.write_issue_line(output, pr)?;
-                }
-                writeln!(output)?;
-            }
+    /// How many items by bots and other ignored authors were left out, for the report header
+    pub(super) fn bot_activity(&self) -> Option<String> {
+        self.filtered.describe_authors(&self.locale)
+    }
 
-            if !activity.closed_issues.is_empty() {
-                writeln!(output, "#### ✅ Closed Issues\n")?;
-                for issue in &activity.closed_issues {
-                    self.write_issue_line(output, issue)?;
-                }
-                writeln!(output)?;
-            }
+    /// How many muted items were left out, for the report header
+    pub(super) fn muted_items(&self) -> Option<String> {
+        self.filtered.describe_muted(&self.locale)
+    }
 
-            // Then show work that needs attention
-            if !activity.new_prs.is_empty() {
-                writeln!(output, "#### 🔄 New Pull Requests\n")?;
-                for pr in &activity.new_prs {
-                    self.write_issue_line(output, pr)?;
-                }
-                writeln!(output)?;
-            }
+    /// Open and closed issue counts of a milestone, e.g. `3 open, 7 closed`
+    pub(super) fn milestone_progress(&self, upcoming: &UpcomingMilestone) -> String {
+        format!(
+            "{} open, {} closed",
+            self.locale
+                .format_count(upcoming.milestone.open_issues as i64),
+            self.locale
+                .format_count(upcoming.milestone.closed_issues as i64)
+        )
+    }
 
-            if !activity.updated_prs.is_empty() {
-                writeln!(output, "#### 📝 Updated Pull Requests\n")?;
-                for pr in &activity.updated_prs {
-                    self.write_issue_line(output, pr)?;
-                }
-                writeln!(output)?;
+    /// What each person did, e.g. `12 events: 5 comments, 3 reviews`
+    pub(super) fn person_summary(&self, person: &PersonActivity) -> String {
+        let mut summary = format!(
+            "{} {}",
+            self.locale.format_count(person.event_count as i64),
+            if person.event_count == 1 {
+                "event"
+            } else {
+                "events"
             }
+        );
+        let actions: Vec<String> = person
+            .actions
+            .iter()
+            .map(|(action, count)| {
+                format!(
+                    "{} {}",
+                    self.locale.format_count(*count as i64),
+                    action.describe(*count)
+                )
+            })
+            .collect();
+        if !actions.is_empty() {
+            summary.push_str(&format!(": {}", actions.join(", ")));
+        }
+        summary
+    }
 
-            if !activity.new_issues.is_empty() {
-                writeln!(output, "#### 🆕 New Issues\n")?;
-                for issue in &activity.new_issues {
-                    self.write_issue_line(output, issue)?;
-                }
-                writeln!(output)?;
-            }
+    /// The one-line note standing in for the low-priority items
+    pub(super) fn low_priority_note(&self) -> String {
+        let count = self.low_priority.len();
+        format!(
+            "{} low-priority {} omitted (see appendix)",
+            self.locale.format_count(count as i64),
+            if count == 1 { "item" } else { "items" }
+        )
+    }
 
-            if !activity.updated_issues.is_empty() {
-                writeln!(output, "#### 🔄 Updated Issues\n")?;
-                for issue in &activity.updated_issues {
-                    self.write_issue_line(output, issue)?;
-                }
-                writeln!(output)?;
-            }
+    /// What the limits left out, e.g. "Showing the 100 highest-priority of
+    /// 130 issues and PRs"
+    pub(super) fn truncation_notes(&self) -> Vec<String> {
+        let truncation = &self.truncation;
+        let mut notes = Vec::new();
+        if !truncation.skipped.is_empty() {
+            notes.push(format!(
+                "Showing the {} highest-priority of {} issues and PRs \
+                 (settings.max_issues_per_report)",
+                truncation.total_items - truncation.skipped.len(),
+                truncation.total_items
+            ));
+        }
+        if let Some((max, window)) = truncation.comment_window {
+            notes.push(format!(
+                "Keeping {} comments of each thread ({}), leaving out {} comments \
+                 on {} items (settings.max_comments_per_issue)",
+                max,
+                window.as_str(),
+                truncation.windowed_comments,
+                truncation.windowed_items
+            ));
         }
+        if let Some(cap) = truncation.comments_per_item {
+            notes.push(format!(
+                "Showing the latest {} comments of each item, leaving out {} older \
+                 comments on {} items (settings.max_comments_per_report)",
+                cap, truncation.skipped_comments, truncation.trimmed_items
+            ));
+        }
+        notes
+    }
+}
 
-        Ok(())
+/// `value` with the fields of the object `extra` added
+fn with_fields(mut value: serde_json::Value, extra: serde_json::Value) -> serde_json::Value {
+    if let (Some(fields), serde_json::Value::Object(extra)) = (value.as_object_mut(), extra) {
+        fields.extend(extra);
     }
+    value
+}
 
-    fn write_issue_line(&self, output: &mut String, issue: &Issue) -> Result<()> {
-        let state_text = match issue.state {
-            IssueState::Open => "[OPEN]",
-            IssueState::Closed => "[CLOSED]",
-            IssueState::Merged => "[MERGED]",
-        };
+/// An issue or PR of the report, wherever it is listed
+pub(super) fn find_issue<'a>(
+    activities: &'a BTreeMap<String, RepoActivity>,
+    repo: &str,
+    number: u32,
+) -> Option<&'a Issue> {
+    let activity = activities.get(repo)?;
+    activity
+        .new_issues
+        .iter()
+        .chain(&activity.updated_issues)
+        .chain(&activity.closed_issues)
+        .chain(&activity.new_prs)
+        .chain(&activity.updated_prs)
+        .chain(&activity.merged_prs)
+        .chain(activity.new_comments.iter().map(|(issue, _)| issue))
+        .find(|issue| issue.number == number)
+}
+
+/// Whether the repository has any issues or PRs to list
+pub(super) fn has_items(activity: &RepoActivity) -> bool {
+    !(activity.new_issues.is_empty()
+        && activity.updated_issues.is_empty()
+        && activity.new_prs.is_empty()
+        && activity.updated_prs.is_empty()
+        && activity.merged_prs.is_empty()
+        && activity.closed_issues.is_empty())
+}
 
-        let labels = if issue.labels.is_empty() {
-            String::new()
+/// The category and comment count of a discussion, e.g. `Q&A, 4 comments, answered`
+pub(super) fn discussion_status(discussion: &Discussion) -> String {
+    let mut status = format!(
+        "{}, {} comment{}",
+        discussion.category,
+        discussion.comment_count,
+        if discussion.comment_count == 1 {
+            ""
         } else {
-            let label_names: Vec<String> = issue
-                .labels
-                .iter()
-                .map(|l| format!("`{}`", l.name))
-                .collect();
-            format!(" {}", label_names.join(" "))
-        };
+            "s"
+        }
+    );
+    if discussion.is_answered {
+        status.push_str(", answered");
+    }
+    status
+}
 
-        writeln!(
-            output,
-            "- {} [#{}]({}) {}{} by [@{}](https://github.com/{})",
-            state_text,
-            issue.number,
-            issue.url,
-            issue.title,
-            labels,
-            issue.author.login,
-            issue.author.login
-        )?;
-
-        Ok(())
-    }
-
-    fn write_footer(&self, output: &mut String) -> Result<()> {
-        writeln!(output, "\n---")?;
-        writeln!(
-            output,
-            "\n*Generated by gh-report v{}*",
-            env!("CARGO_PKG_VERSION")
-        )?;
-        Ok(())
+/// The items matching each watch rule, keeping their order
+pub(super) fn by_rule(matches: &[RuleMatch]) -> BTreeMap<&str, Vec<&RuleMatch>> {
+    let mut rules: BTreeMap<&str, Vec<&RuleMatch>> = BTreeMap::new();
+    for item in matches {
+        for rule in &item.rules {
+            rules.entry(rule).or_default().push(item);
+        }
     }
+    rules
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::github::{Author, CommentCount, Issue, Label};
+    use crate::github::{Author, CommentCount, Issue, IssueState, Label};
+    use crate::intelligence::NeedsInfoItem;
+    use crate::test_utils::create_test_issue;
     use jiff::ToSpan;
 
     #[test]
@@ -358,6 +1219,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/42".to_string(),
             comments: CommentCount { total_count: 0 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         });
 
         activities.insert("test/repo".to_string(), repo_activity);
@@ -371,4 +1236,435 @@ mod tests {
         assert!(result.contains("#42"));
         assert!(result.contains("`bug`"));
     }
+
+    #[test]
+    fn test_group_by_org() {
+        let mut config = Config::default();
+        config.report.group_by = GroupBy::Org;
+
+        let mut activities = BTreeMap::new();
+        for (repo, number, is_pr) in [
+            ("tokio-rs/tokio", 1, true),
+            ("tokio-rs/tokio", 2, false),
+            ("tokio-rs/axum", 3, true),
+            ("rust-lang/rust", 4, false),
+        ] {
+            let activity: &mut RepoActivity = activities.entry(repo.to_string()).or_default();
+            let issue = create_test_issue(number, "Item", is_pr);
+            if is_pr {
+                activity.new_prs.push(issue);
+            } else {
+                activity.new_issues.push(issue);
+            }
+        }
+
+        let mut summaries = BTreeMap::new();
+        summaries.insert("tokio-rs".to_string(), "Mostly runtime fixes.".to_string());
+        let template = ReportTemplate::new(&config).with_org_summaries(summaries);
+        let now = Timestamp::now();
+        let result = template
+            .render(&activities, now - 24_i64.hours(), now, &[])
+            .unwrap();
+
+        assert!(result.contains("## Activity by Organization"));
+        assert!(!result.contains("## Activity by Repository"));
+        assert!(result.contains(
+            "### tokio-rs\n\n*2 repositories, 1 issue, 2 pull requests*\n\n> Mostly runtime fixes.\n"
+        ));
+        assert!(result.contains(
+            "### rust-lang\n\n*1 repository, 1 issue, 0 pull requests*\n\n#### rust-lang/rust"
+        ));
+        assert!(result.contains("#### tokio-rs/axum\n\n##### 🔄 New Pull Requests"));
+        assert!(result.find("rust-lang").unwrap() < result.find("### tokio-rs").unwrap());
+    }
+
+    #[test]
+    fn test_group_by_milestone() {
+        let mut config = Config::default();
+        config.report.group_by = GroupBy::Milestone;
+
+        let mut activities: BTreeMap<String, RepoActivity> = BTreeMap::new();
+        let mut planned = create_test_issue(1, "Planned", false);
+        planned.milestone = Some(Milestone {
+            title: "v1.0".to_string(),
+            due_on: Some("2024-03-01T12:00:00Z".parse().unwrap()),
+        });
+        activities
+            .entry("tokio-rs/tokio".to_string())
+            .or_default()
+            .new_issues = vec![planned, create_test_issue(2, "Unplanned", false)];
+
+        let template = ReportTemplate::new(&config);
+        let now = Timestamp::now();
+        let result = template
+            .render(&activities, now - 24_i64.hours(), now, &[])
+            .unwrap();
+
+        assert!(result.contains(
+            "## Activity by Milestone\n\n### v1.0 (due 2024-03-01)\n\n\
+             - **[tokio-rs/tokio]** [OPEN] [#1](https://github.com/test/repo/issues/1) Planned"
+        ));
+        assert!(result.contains("### No milestone\n\n- **[tokio-rs/tokio]** [OPEN] [#2]"));
+        assert!(!result.contains("## Activity by Repository"));
+    }
+
+    #[test]
+    fn test_localized_header() {
+        let mut config = Config::default();
+        config.report.locale = Some("de-DE".to_string());
+        let template = ReportTemplate::new(&config);
+        let since: Timestamp = "2024-03-05T14:05:09Z".parse().unwrap();
+        let now: Timestamp = "2024-03-06T14:05:09Z".parse().unwrap();
+
+        let output = template.render(&BTreeMap::new(), since, now, &[]).unwrap();
+        assert!(output.contains("**Period**: 05.03.2024 14:05 to 06.03.2024 14:05"));
+        assert!(output.contains("**Generated**: 06.03.2024 14:05:09"));
+    }
+
+    #[test]
+    fn test_rate_limit_in_footer() {
+        let mut config = Config::default();
+        config.report.locale = Some("en-US".to_string());
+        let template = ReportTemplate::new(&config).with_rate_limit(Some(RateLimit {
+            limit: 5000,
+            remaining: 4210,
+            reset: "2024-03-06T15:00:00Z".parse().unwrap(),
+        }));
+
+        let now = Timestamp::now();
+        let output = template
+            .render(&BTreeMap::new(), now - 24_i64.hours(), now, &[])
+            .unwrap();
+        assert_eq!(
+            output.lines().last(),
+            Some("*GitHub API: 4,210 of 5,000 requests left, resets 03/06/2024 3:00 PM*")
+        );
+    }
+
+    #[test]
+    fn test_summarized_by_in_footer() {
+        let config = Config::default();
+        let now = Timestamp::now();
+        let render = |template: ReportTemplate| {
+            template
+                .render(&BTreeMap::new(), now - 24_i64.hours(), now, &[])
+                .unwrap()
+        };
+        assert!(!render(ReportTemplate::new(&config)).contains("AI summary"));
+
+        let output = render(
+            ReportTemplate::new(&config).with_summarized_by(Some(
+                "AI summary by anthropic (claude-sonnet-4-5) after claude-cli failed: timed out"
+                    .to_string(),
+            )),
+        );
+        assert_eq!(
+            output.lines().last(),
+            Some(
+                "*AI summary by anthropic (claude-sonnet-4-5) after claude-cli failed: timed out*"
+            )
+        );
+    }
+
+    #[test]
+    fn test_involvement_badges() {
+        let config = Config::default();
+        let template = ReportTemplate::new(&config).with_current_user(Some("me"));
+
+        let mut pr = crate::test_utils::create_test_issue(7, "Needs review", true);
+        pr.requested_reviewers.push(Author {
+            login: "me".to_string(),
+            user_type: None,
+        });
+        let mut issue = crate::test_utils::create_test_issue(8, "Assigned bug", false);
+        issue.assignees.push(Author {
+            login: "me".to_string(),
+            user_type: None,
+        });
+        let other = crate::test_utils::create_test_issue(9, "Someone else", false);
+
+        let mut repo_activity = RepoActivity::default();
+        repo_activity.updated_prs.push(pr);
+        repo_activity.updated_issues.push(issue);
+        repo_activity.updated_issues.push(other);
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), repo_activity);
+
+        let now = Timestamp::now();
+        let result = template
+            .render(&activities, now - 24_i64.hours(), now, &[])
+            .unwrap();
+
+        let line = |needle: &str| result.lines().find(|l| l.contains(needle)).unwrap();
+        assert!(line("Needs review").contains("review requested from you"));
+        assert!(line("Assigned bug").contains("assigned to you"));
+        assert!(!line("Someone else").contains("you**"));
+    }
+
+    #[test]
+    fn test_thread_updates_in_prioritized_items() {
+        let config = Config::default();
+        let template = ReportTemplate::new(&config).with_thread_updates(vec![ThreadUpdate {
+            repo: "test/repo".to_string(),
+            issue_number: 8,
+            change: "A fix was proposed in #9.".to_string(),
+        }]);
+
+        let mut repo_activity = RepoActivity::default();
+        repo_activity
+            .updated_issues
+            .push(crate::test_utils::create_test_issue(8, "Hot thread", false));
+        repo_activity
+            .updated_issues
+            .push(crate::test_utils::create_test_issue(
+                10,
+                "Quiet thread",
+                false,
+            ));
+        let mut activities = BTreeMap::new();
+        activities.insert("test/repo".to_string(), repo_activity);
+        let analysis = crate::intelligence::IntelligentAnalyzer::new(&config).analyze(&activities);
+
+        let now = Timestamp::now();
+        let result = template
+            .render_with_intelligence(&activities, now - 24_i64.hours(), now, &[], None, &analysis)
+            .unwrap();
+
+        let lines: Vec<&str> = result.lines().collect();
+        let hot = lines
+            .iter()
+            .position(|l| l.contains("Hot thread") && l.contains("Score"))
+            .unwrap();
+        assert_eq!(
+            lines[hot + 1],
+            "  - 🔄 Since last report: A fix was proposed in #9."
+        );
+        assert_eq!(result.matches("Since last report").count(), 1);
+    }
+
+    #[test]
+    fn test_needs_info_section() {
+        use crate::intelligence::{draft_info_request, TemplateSection};
+
+        let config = Config::default();
+        let template = ReportTemplate::new(&config);
+        let issue = crate::test_utils::create_test_issue(5, "Hang on shutdown", false);
+        let analysis = AnalysisResult {
+            prioritized_issues: vec![],
+            context_prompt: String::new(),
+            action_items: vec![],
+            waiting_on_author: vec![],
+            waiting_on_you: vec![],
+            needs_info: vec![NeedsInfoItem {
+                draft_comment: draft_info_request(&issue, &[TemplateSection::Version]),
+                issue,
+                repo: "test/repo".to_string(),
+                missing: vec![TemplateSection::Version],
+            }],
+            new_contributions: vec![],
+            matched_rules: vec![],
+        };
+
+        let now = Timestamp::now();
+        let result = template
+            .render_with_intelligence(
+                &BTreeMap::new(),
+                now - 24_i64.hours(),
+                now,
+                &[],
+                None,
+                &analysis,
+            )
+            .unwrap();
+
+        assert!(result.contains("## 📝 Needs More Info"));
+        assert!(result.contains("Hang on shutdown (missing: version;"));
+        assert!(result.contains("  > Thanks for the report, @testuser!"));
+    }
+
+    #[test]
+    fn test_ci_failures_section() {
+        use crate::github::WorkflowRun;
+
+        let config = Config::default();
+        let pr = crate::test_utils::create_test_issue(8, "Add io_uring driver", true);
+        let template = ReportTemplate::new(&config).with_ci_failures(vec![CiFailure {
+            repo: "test/repo".to_string(),
+            pr,
+            run: WorkflowRun {
+                id: 42,
+                name: Some("CI".to_string()),
+                html_url: "https://github.com/test/repo/actions/runs/42".to_string(),
+                head_sha: "abc123".to_string(),
+                conclusion: Some("failure".to_string()),
+                created_at: Timestamp::now(),
+            },
+            excerpt: Some("error[E0308]: mismatched types".to_string()),
+            explanation: Some("A type mismatch in the new driver breaks the build.".to_string()),
+        }]);
+
+        let now = Timestamp::now();
+        let result = template
+            .render(&BTreeMap::new(), now - 24_i64.hours(), now, &[])
+            .unwrap();
+
+        assert!(result.contains("## 🔴 CI Failures"));
+        assert!(result.contains(
+            "Add io_uring driver ([CI run](https://github.com/test/repo/actions/runs/42))"
+        ));
+        assert!(result.contains("💡 Probable cause: A type mismatch"));
+        assert!(result.contains("  error[E0308]: mismatched types"));
+    }
+
+    #[test]
+    fn test_local_work_section() {
+        use crate::git::{LinkedItem, LocalBranch};
+
+        let config = Config::default();
+        let template = ReportTemplate::new(&config).with_local_work(vec![LocalWork {
+            branch: LocalBranch {
+                name: "fix-12".to_string(),
+                upstream: Some("origin/fix-12".to_string()),
+                unpushed_commits: 3,
+                issue_refs: vec![12],
+            },
+            items: vec![LinkedItem {
+                number: 12,
+                title: Some("Scheduler fix".to_string()),
+                url: "https://github.com/test/repo/pull/12".to_string(),
+            }],
+        }]);
+
+        let now = Timestamp::now();
+        let result = template
+            .render(&BTreeMap::new(), now - 24_i64.hours(), now, &[])
+            .unwrap();
+
+        assert!(result.contains("## 🧵 Your local work in progress"));
+        assert!(result.contains(
+            "- `fix-12` - 3 unpushed commits → [#12](https://github.com/test/repo/pull/12) Scheduler fix"
+        ));
+    }
+
+    #[test]
+    fn test_disabled_sections() {
+        use crate::config::Importance;
+        use crate::intelligence::{PrioritizedIssue, PriorityScore};
+
+        let mut config = Config::default();
+        config.report.sections.statistics = false;
+        config.report.sections.prioritized = false;
+        config.report.sections.ai_summary = false;
+        let template = ReportTemplate::new(&config);
+        let issue = create_test_issue(3, "Faster timers", true);
+        let mut activity = RepoActivity::default();
+        activity.new_prs.push(issue.clone());
+        let activities = BTreeMap::from([("test/repo".to_string(), activity)]);
+        let analysis = AnalysisResult {
+            prioritized_issues: vec![PrioritizedIssue {
+                issue,
+                repo: "test/repo".to_string(),
+                score: PriorityScore {
+                    total: 40,
+                    importance_score: 20,
+                    recency_score: 20,
+                    activity_score: 0,
+                    rule_match_score: 0,
+                    label_score: 0,
+                    involvement_score: 0,
+                    newcomer_score: 0,
+                },
+                importance: Importance::Medium,
+            }],
+            context_prompt: String::new(),
+            action_items: vec![],
+            waiting_on_author: vec![],
+            waiting_on_you: vec![],
+            needs_info: vec![],
+            new_contributions: vec![],
+            matched_rules: vec![],
+        };
+        let now = Timestamp::now();
+
+        let result = template
+            .render_with_intelligence(
+                &activities,
+                now - 24_i64.hours(),
+                now,
+                &[],
+                Some("A quiet week."),
+                &analysis,
+            )
+            .unwrap();
+        assert!(!result.contains("## Summary"));
+        assert!(!result.contains("## Prioritized Items"));
+        assert!(!result.contains("## Highlights"));
+        assert!(!result.contains("A quiet week."));
+        assert!(result.contains("## Activity by Repository"));
+        assert!(result.contains("Faster timers"));
+
+        config.report.sections.by_repository = false;
+        let result = ReportTemplate::new(&config)
+            .render(&activities, now - 24_i64.hours(), now, &[])
+            .unwrap();
+        assert!(!result.contains("Faster timers"));
+    }
+
+    #[test]
+    fn test_configured_template() {
+        let mut config = Config::default();
+        config.report.template = Some(
+            "{{ sections.activity -}}\n\
+             {% for repo in repos %}{{ repo.name }}: {{ repo.new_prs | length }} new PRs\n{% endfor %}"
+                .to_string(),
+        );
+        let template = ReportTemplate::new(&config);
+        let mut activity = RepoActivity::default();
+        activity
+            .new_prs
+            .push(create_test_issue(3, "Faster timers", true));
+        let activities = BTreeMap::from([("test/repo".to_string(), activity)]);
+        let now = Timestamp::now();
+
+        let result = template
+            .render(&activities, now - 24_i64.hours(), now, &[])
+            .unwrap();
+        assert!(result.starts_with("\n## Activity by Repository"));
+        assert!(result.contains("Faster timers"));
+        assert!(result.ends_with("test/repo: 1 new PRs\n"));
+        assert!(!result.contains("# GitHub Activity Report"));
+    }
+    #[test]
+    fn test_sections_lay_out_the_default_report() {
+        let mut config = Config::default();
+        let mut activity = RepoActivity::default();
+        activity
+            .new_prs
+            .push(create_test_issue(3, "Faster timers", true));
+        let activities = BTreeMap::from([("test/repo".to_string(), activity)]);
+        let now = Timestamp::now();
+        let render = |config: &Config| {
+            ReportTemplate::new(config)
+                .with_low_priority(vec![(
+                    "test/repo".to_string(),
+                    create_test_issue(4, "Typo", false),
+                )])
+                .render_with_summary(
+                    &activities,
+                    now - 24_i64.hours(),
+                    now,
+                    &["Rate limited".to_string()],
+                    Some("A quiet day."),
+                )
+                .unwrap()
+        };
+        let default = render(&config);
+
+        let layout: String = crate::report::engine::SECTIONS
+            .iter()
+            .map(|name| format!("{{{{ sections.{} -}}}}\n", name))
+            .collect();
+        config.report.template = Some(layout);
+        assert_eq!(render(&config), default);
+    }
 }
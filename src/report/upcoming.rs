@@ -0,0 +1,114 @@
+//! Open milestones of the reported repositories that are due soon
+
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, ToSpan};
+
+use crate::github::RepoMilestone;
+
+/// Most milestones listed under "Upcoming deadlines"
+pub const MAX_UPCOMING: usize = 10;
+
+/// An open milestone due within `report.upcoming_days`
+#[derive(Debug, Clone)]
+pub struct UpcomingMilestone {
+    pub repo: String,
+    pub milestone: RepoMilestone,
+    pub due_on: Timestamp,
+    /// Calendar days until it is due, 0 when it is due today
+    pub days_left: i32,
+}
+
+impl UpcomingMilestone {
+    /// When it is due relative to the report, e.g. `in 3 days`
+    pub fn due_in(&self) -> String {
+        match self.days_left {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            days => format!("in {} days", days),
+        }
+    }
+}
+
+/// The milestones due between `now` and `days` from now, soonest first
+///
+/// Overdue milestones and milestones without a due date are left out.
+pub fn upcoming_milestones(
+    milestones: Vec<(String, RepoMilestone)>,
+    now: Timestamp,
+    days: u32,
+    tz: &TimeZone,
+) -> Vec<UpcomingMilestone> {
+    let horizon = now + (days as i64 * 24).hours();
+    let today = now.to_zoned(tz.clone()).date();
+    let mut upcoming: Vec<UpcomingMilestone> = milestones
+        .into_iter()
+        .filter_map(|(repo, milestone)| {
+            let due_on = milestone.due_on?;
+            (due_on >= now && due_on <= horizon).then(|| UpcomingMilestone {
+                repo,
+                days_left: today
+                    .until(due_on.to_zoned(tz.clone()).date())
+                    .map_or(0, |span| span.get_days()),
+                milestone,
+                due_on,
+            })
+        })
+        .collect();
+    upcoming.sort_by(|a, b| a.due_on.cmp(&b.due_on).then(a.repo.cmp(&b.repo)));
+    upcoming.truncate(MAX_UPCOMING);
+    upcoming
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn milestone(title: &str, due_on: Option<&str>) -> RepoMilestone {
+        RepoMilestone {
+            number: 1,
+            title: title.to_string(),
+            html_url: format!("https://github.com/tokio-rs/tokio/milestone/{}", title),
+            due_on: due_on.map(|due_on| due_on.parse().unwrap()),
+            open_issues: 3,
+            closed_issues: 7,
+        }
+    }
+
+    #[test]
+    fn test_upcoming_milestones() {
+        let now: Timestamp = "2024-03-01T12:00:00Z".parse().unwrap();
+        let repo = "tokio-rs/tokio".to_string();
+        let upcoming = upcoming_milestones(
+            vec![
+                (
+                    repo.clone(),
+                    milestone("v2.0", Some("2024-03-10T07:00:00Z")),
+                ),
+                (
+                    repo.clone(),
+                    milestone("v1.1", Some("2024-03-02T07:00:00Z")),
+                ),
+                (
+                    repo.clone(),
+                    milestone("overdue", Some("2024-02-20T07:00:00Z")),
+                ),
+                (
+                    repo.clone(),
+                    milestone("later", Some("2024-04-20T07:00:00Z")),
+                ),
+                (repo.clone(), milestone("someday", None)),
+            ],
+            now,
+            14,
+            &TimeZone::UTC,
+        );
+
+        let titles: Vec<&str> = upcoming
+            .iter()
+            .map(|u| u.milestone.title.as_str())
+            .collect();
+        assert_eq!(titles, ["v1.1", "v2.0"]);
+        assert_eq!(upcoming[0].due_in(), "tomorrow");
+        assert_eq!(upcoming[1].due_in(), "in 9 days");
+    }
+}
@@ -0,0 +1,324 @@
+//! Public JSON schema for tools built on top of gh-report
+//!
+//! Hooks, plugins, and scripts read these types rather than the internal
+//! models, which change from release to release. Everything gh-report writes
+//! as JSON carries a `schema_version`, and within one version:
+//!
+//! - fields and enum values may be added, so consumers should ignore what
+//!   they don't know;
+//! - fields are never removed, renamed, or given another type, and optional
+//!   fields stay optional.
+//!
+//! Any other change bumps [`SCHEMA_VERSION`].
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Importance, ReportFormat};
+use crate::github::{self, IssueState};
+use crate::intelligence;
+use crate::report::Report;
+
+/// Version of the types in this module
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// An issue or pull request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Issue {
+    /// `owner/repo`
+    pub repo: String,
+    pub number: u32,
+    pub kind: ItemKind,
+    pub title: String,
+    pub url: String,
+    pub state: ItemState,
+    /// Login of the author
+    pub author: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    #[serde(default)]
+    pub milestone: Option<String>,
+    pub comment_count: u32,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Issue,
+    PullRequest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemState {
+    Open,
+    Closed,
+    Merged,
+}
+
+impl Issue {
+    pub fn new(repo: &str, issue: &github::Issue) -> Self {
+        Issue {
+            repo: repo.to_string(),
+            number: issue.number,
+            kind: if issue.is_pull_request {
+                ItemKind::PullRequest
+            } else {
+                ItemKind::Issue
+            },
+            title: issue.title.clone(),
+            url: issue.url.clone(),
+            state: match issue.state {
+                IssueState::Open => ItemState::Open,
+                IssueState::Closed => ItemState::Closed,
+                IssueState::Merged => ItemState::Merged,
+            },
+            author: issue.author.login.clone(),
+            labels: issue.labels.iter().map(|l| l.name.clone()).collect(),
+            assignees: issue.assignees.iter().map(|a| a.login.clone()).collect(),
+            milestone: issue.milestone.as_ref().map(|m| m.title.clone()),
+            comment_count: issue.comments.total_count,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+        }
+    }
+}
+
+/// What happened in one repository during the report window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoActivity {
+    /// `owner/repo`
+    pub repo: String,
+    pub new_issues: Vec<Issue>,
+    pub updated_issues: Vec<Issue>,
+    pub closed_issues: Vec<Issue>,
+    pub new_prs: Vec<Issue>,
+    pub updated_prs: Vec<Issue>,
+    pub merged_prs: Vec<Issue>,
+    /// Issues and PRs with new comments
+    pub commented: Vec<CommentedIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentedIssue {
+    #[serde(flatten)]
+    pub issue: Issue,
+    pub new_comments: u32,
+}
+
+impl RepoActivity {
+    pub fn new(repo: &str, activity: &github::RepoActivity) -> Self {
+        let issues = |issues: &[github::Issue]| -> Vec<Issue> {
+            issues.iter().map(|issue| Issue::new(repo, issue)).collect()
+        };
+        RepoActivity {
+            repo: repo.to_string(),
+            new_issues: issues(&activity.new_issues),
+            updated_issues: issues(&activity.updated_issues),
+            closed_issues: issues(&activity.closed_issues),
+            new_prs: issues(&activity.new_prs),
+            updated_prs: issues(&activity.updated_prs),
+            merged_prs: issues(&activity.merged_prs),
+            commented: activity
+                .new_comments
+                .iter()
+                .map(|(issue, comments)| CommentedIssue {
+                    issue: Issue::new(repo, issue),
+                    new_comments: comments.len() as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How an item's priority was scored; `total` is the sum of the parts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityScore {
+    pub total: u32,
+    pub importance: u32,
+    pub recency: u32,
+    pub activity: u32,
+    pub rule_match: u32,
+    pub labels: u32,
+    pub involvement: u32,
+    #[serde(default)]
+    pub newcomer: u32,
+}
+
+impl From<&intelligence::PriorityScore> for PriorityScore {
+    fn from(score: &intelligence::PriorityScore) -> Self {
+        PriorityScore {
+            total: score.total,
+            importance: score.importance_score,
+            recency: score.recency_score,
+            activity: score.activity_score,
+            rule_match: score.rule_match_score,
+            labels: score.label_score,
+            involvement: score.involvement_score,
+            newcomer: score.newcomer_score,
+        }
+    }
+}
+
+/// An item with its priority, as listed under "Prioritized Items"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrioritizedIssue {
+    pub issue: Issue,
+    pub score: PriorityScore,
+    pub importance: Importance,
+}
+
+impl From<&intelligence::PrioritizedIssue> for PrioritizedIssue {
+    fn from(item: &intelligence::PrioritizedIssue) -> Self {
+        PrioritizedIssue {
+            issue: Issue::new(&item.repo, &item.issue),
+            score: PriorityScore::from(&item.score),
+            importance: item.importance,
+        }
+    }
+}
+
+/// Something the user should do about an item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub issue: Issue,
+    pub description: String,
+    pub urgency: Urgency,
+    pub reason: String,
+    /// The comment or description quoted under the action
+    #[serde(default)]
+    pub excerpt: Option<Excerpt>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Urgency {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Excerpt {
+    pub author: String,
+    pub text: String,
+    pub url: String,
+}
+
+impl From<&intelligence::ActionItem> for ActionItem {
+    fn from(action: &intelligence::ActionItem) -> Self {
+        ActionItem {
+            issue: Issue::new(&action.repo, &action.issue),
+            description: action.description.clone(),
+            urgency: match action.urgency {
+                intelligence::Urgency::Low => Urgency::Low,
+                intelligence::Urgency::Medium => Urgency::Medium,
+                intelligence::Urgency::High => Urgency::High,
+                intelligence::Urgency::Critical => Urgency::Critical,
+            },
+            reason: action.reason.clone(),
+            excerpt: action.excerpt.as_ref().map(|excerpt| Excerpt {
+                author: excerpt.author.clone(),
+                text: excerpt.text.clone(),
+                url: excerpt.url.clone(),
+            }),
+        }
+    }
+}
+
+/// A generated report, without its content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportMetadata {
+    pub schema_version: u32,
+    pub title: String,
+    pub generated_at: Timestamp,
+    pub format: ReportFormat,
+    /// Estimated cost of the AI calls, in USD
+    pub estimated_cost: f32,
+    /// Number of issues and PRs covered
+    pub item_count: usize,
+    pub deadline_count: usize,
+}
+
+impl From<&Report> for ReportMetadata {
+    fn from(report: &Report) -> Self {
+        ReportMetadata {
+            schema_version: SCHEMA_VERSION,
+            title: report.title.clone(),
+            generated_at: report.timestamp,
+            format: report.format,
+            estimated_cost: report.estimated_cost,
+            item_count: report.items.len(),
+            deadline_count: report.deadlines.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_issue;
+
+    // Field names are part of the schema; changing them needs a new version
+    #[test]
+    fn test_schema_field_names() {
+        let mut activity = github::RepoActivity::default();
+        activity
+            .merged_prs
+            .push(create_test_issue(7, "Speed up the scheduler", true));
+        activity
+            .new_comments
+            .push((create_test_issue(8, "Panic on shutdown", false), Vec::new()));
+
+        let value = serde_json::to_value(RepoActivity::new("tokio-rs/tokio", &activity)).unwrap();
+        let pr = &value["merged_prs"][0];
+        let mut keys: Vec<&str> = pr.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "assignees",
+                "author",
+                "comment_count",
+                "created_at",
+                "kind",
+                "labels",
+                "milestone",
+                "number",
+                "repo",
+                "state",
+                "title",
+                "updated_at",
+                "url"
+            ]
+        );
+        assert_eq!(pr["kind"], "pull_request");
+        assert_eq!(pr["repo"], "tokio-rs/tokio");
+        assert_eq!(value["commented"][0]["number"], 8);
+        assert_eq!(value["commented"][0]["new_comments"], 0);
+
+        let round_trip: RepoActivity = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip.merged_prs[0].number, 7);
+    }
+
+    #[test]
+    fn test_consumers_tolerate_additions() {
+        let action: ActionItem = serde_json::from_value(serde_json::json!({
+            "issue": serde_json::to_value(Issue::new(
+                "tokio-rs/tokio",
+                &create_test_issue(1, "Deadlock", false),
+            ))
+            .unwrap(),
+            "description": "Respond to the new comment",
+            "urgency": "high",
+            "reason": "You were mentioned",
+            "added_in_a_later_release": true,
+        }))
+        .unwrap();
+        assert_eq!(action.urgency, Urgency::High);
+        assert_eq!(action.excerpt, None);
+    }
+}
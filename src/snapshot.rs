@@ -0,0 +1,185 @@
+//! Saved activity snapshots, for reporting on the same events again
+//!
+//! `activity --save-snapshot` stores the activity feed together with the
+//! issues, comments, and reviews a report fetches for it. `report
+//! --from-snapshot` builds the report from that file alone, so the exact
+//! same event set can be reported again after tweaking the config or
+//! prompts, or attached to a bug report about report content.
+
+use anyhow::{bail, Context, Result};
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::activity::{filter_events, issue_references};
+use crate::github::{is_auth_expired, ActivityEvent, Comment, GitHubClient, Issue, Review};
+
+/// Format version written to new snapshots
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The activity feed of a time window and everything a report needs from GitHub for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySnapshot {
+    pub version: u32,
+    /// When the activity was fetched; reports from the snapshot use it as "now"
+    pub captured_at: Timestamp,
+    pub lookback_days: u32,
+    /// The authenticated user at capture time
+    #[serde(default)]
+    pub user: Option<String>,
+    /// All events of the window, before any type filtering
+    pub events: Vec<ActivityEvent>,
+    pub items: Vec<SnapshotItem>,
+    /// Items that could not be fetched, reported like in a live run
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// An issue or PR referenced by the events, with its conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotItem {
+    pub issue: Issue,
+    pub comments: Vec<Comment>,
+    /// Reviews, for pull requests
+    #[serde(default)]
+    pub reviews: Vec<Review>,
+}
+
+impl ActivitySnapshot {
+    /// Fetch the issues and PRs the events refer to and bundle them with the events
+    pub fn capture(
+        github_client: &GitHubClient,
+        lookback_days: u32,
+        events: Vec<ActivityEvent>,
+    ) -> Result<Self> {
+        let captured_at = Timestamp::now();
+        let user = github_client.get_current_user().ok();
+        let refs = issue_references(&filter_events(&events, None, None));
+        info!("Capturing {} issues/PRs for the snapshot", refs.len());
+
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        for (repo, number) in refs {
+            let (issue, comments) = match github_client.fetch_single_issue(&repo, number) {
+                Ok(item) => item,
+                Err(e) if is_auth_expired(&e) => return Err(e),
+                Err(e) => {
+                    warn!("Failed to fetch {}/issues/{}: {}", repo, number, e);
+                    errors.push(format!("Failed to fetch {}/issues/{}: {}", repo, number, e));
+                    continue;
+                }
+            };
+
+            let reviews = if issue.is_pull_request {
+                match github_client.fetch_pr_reviews(&repo, number) {
+                    Ok(reviews) => reviews,
+                    Err(e) if is_auth_expired(&e) => return Err(e),
+                    Err(e) => {
+                        warn!("Failed to fetch reviews for {}#{}: {}", repo, number, e);
+                        Vec::new()
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+
+            items.push(SnapshotItem {
+                issue,
+                comments,
+                reviews,
+            });
+        }
+
+        Ok(ActivitySnapshot {
+            version: SNAPSHOT_VERSION,
+            captured_at,
+            lookback_days,
+            user,
+            events,
+            items,
+            errors,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read snapshot {}", path.display()))?;
+        let snapshot: ActivitySnapshot = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse snapshot {}", path.display()))?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            bail!(
+                "Snapshot {} has format version {}, this gh-report reads up to version {}",
+                path.display(),
+                snapshot.version,
+                SNAPSHOT_VERSION
+            );
+        }
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize snapshot")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write snapshot {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::MockGitHub;
+    use crate::test_utils::create_test_issue;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_capture_and_round_trip() {
+        let mut mock = MockGitHub::new();
+        mock.issues
+            .push(create_test_issue(12, "Runtime hangs", false));
+        let event: ActivityEvent = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "IssueCommentEvent",
+            "actor": { "login": "alice" },
+            "repo": { "id": 1, "name": "owner/repo", "url": "" },
+            "payload": { "action": "created", "issue": { "number": 12 } },
+            "created_at": "2024-03-06T12:00:00Z",
+            "public": true,
+        }))
+        .unwrap();
+        let mut missing = event.clone();
+        missing.payload = serde_json::json!({ "action": "created", "issue": { "number": 99 } });
+
+        let snapshot =
+            ActivitySnapshot::capture(&GitHubClient::Mock(mock), 7, vec![event, missing]).unwrap();
+        assert_eq!(snapshot.user.as_deref(), Some("testuser"));
+        assert_eq!(snapshot.items.len(), 1);
+        assert_eq!(snapshot.errors.len(), 1);
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("snap.json");
+        snapshot.save(&path).unwrap();
+        let loaded = ActivitySnapshot::load(&path).unwrap();
+        assert_eq!(loaded.captured_at, snapshot.captured_at);
+        assert_eq!(loaded.events.len(), 2);
+        assert_eq!(loaded.items[0].issue.title, "Runtime hangs");
+    }
+
+    #[test]
+    fn test_load_rejects_newer_versions() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("snap.json");
+        std::fs::write(
+            &path,
+            r#"{"version": 99, "captured_at": "2024-03-06T12:00:00Z", "lookback_days": 7, "events": [], "items": []}"#,
+        )
+        .unwrap();
+
+        let error = ActivitySnapshot::load(&path).unwrap_err();
+        assert!(error.to_string().contains("format version 99"));
+    }
+}
@@ -1,12 +1,128 @@
 use anyhow::{Context, Result};
 use jiff::{Timestamp, ToSpan};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
-#[derive(Debug, Deserialize, Serialize)]
+use crate::github::{Issue, IssueState};
+use crate::intelligence::RunScores;
+
+/// Number of recently seen issues and PRs kept for shell completion
+const MAX_RECENT_ITEMS: usize = 500;
+
+/// Items in no report for this many days are forgotten, and count as new
+/// when they come up again
+const SEEN_ITEM_RETENTION_DAYS: i64 = 90;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct State {
     pub last_run: Option<Timestamp>,
     pub last_report_file: Option<String>,
+    /// Issues and PRs from recent reports and summaries, most recent first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_items: Vec<RecentItem>,
+    /// Answers to the prompt for repositories new to AI summaries
+    #[serde(default, skip_serializing_if = "RepoTrust::is_empty")]
+    pub repo_trust: RepoTrust,
+    /// How the items of the last report were scored, for `explain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_scores: Option<RunScores>,
+    /// Issues and PRs of previous reports by URL, to tell new items from
+    /// carried-over ones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub seen_items: BTreeMap<String, SeenItem>,
+    /// Issues and PRs left out of every report, added by `gh-report mute`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub muted: Vec<MutedItem>,
+}
+
+/// An issue or PR muted with `gh-report mute`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MutedItem {
+    pub repo: String,
+    pub number: u32,
+    pub muted_at: Timestamp,
+}
+
+impl MutedItem {
+    /// The `owner/repo#number` reference accepted by `unmute`
+    pub fn reference(&self) -> String {
+        format!("{}#{}", self.repo, self.number)
+    }
+}
+
+/// An issue or PR as it was in the last report that included it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SeenItem {
+    pub updated_at: Timestamp,
+    pub open: bool,
+    /// When the item was last in a report
+    pub reported_at: Timestamp,
+}
+
+impl SeenItem {
+    pub fn of(issue: &Issue, reported_at: Timestamp) -> Self {
+        SeenItem {
+            updated_at: issue.updated_at,
+            open: issue.state == IssueState::Open,
+            reported_at,
+        }
+    }
+}
+
+/// How an item in a report relates to the previous reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeenStatus {
+    /// In no previous report
+    New,
+    /// In a previous report, and still open
+    CarriedOver,
+    /// Open in a previous report, and closed or merged since
+    Resolved,
+}
+
+impl SeenStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            SeenStatus::New => "🆕 new",
+            SeenStatus::CarriedOver => "🔁 carried over",
+            SeenStatus::Resolved => "✅ resolved",
+        }
+    }
+}
+
+/// Which repositories may be included in AI summaries, as answered by the user
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RepoTrust {
+    /// Whether each answered repository may be included
+    #[serde(default)]
+    pub repos: BTreeMap<String, bool>,
+    /// Include new repositories without asking, after answering "always"
+    #[serde(default)]
+    pub include_new: bool,
+}
+
+impl RepoTrust {
+    pub fn is_empty(&self) -> bool {
+        self.repos.is_empty() && !self.include_new
+    }
+}
+
+/// An issue or PR that appeared in a report or was summarized
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RecentItem {
+    pub repo: String,
+    pub number: u32,
+    #[serde(default)]
+    pub title: String,
+    pub seen_at: Timestamp,
+}
+
+impl RecentItem {
+    /// The `owner/repo#number` reference accepted by `summarize`
+    pub fn reference(&self) -> String {
+        format!("{}#{}", self.repo, self.number)
+    }
 }
 
 impl State {
@@ -46,6 +162,107 @@ impl State {
         self.last_run = Some(Timestamp::now());
     }
 
+    /// Record a report covering activity up to `at`
+    ///
+    /// Using the report's own timestamp rather than the time it finished keeps
+    /// consecutive `--since-last` windows from leaving a gap.
+    pub fn record_run(&mut self, at: Timestamp) {
+        self.last_run = Some(at);
+    }
+
+    /// Remember issues and PRs seen at `at`, keeping the most recent ones
+    ///
+    /// An item seen again moves to the front, keeping its title if the new
+    /// sighting has none.
+    pub fn record_items(&mut self, items: impl IntoIterator<Item = RecentItem>) {
+        let mut seen: Vec<RecentItem> = items.into_iter().collect();
+        seen.sort_by_key(|item| item.seen_at);
+
+        for mut item in seen {
+            if let Some(pos) = self
+                .recent_items
+                .iter()
+                .position(|i| i.repo == item.repo && i.number == item.number)
+            {
+                let previous = self.recent_items.remove(pos);
+                if item.title.is_empty() {
+                    item.title = previous.title;
+                }
+            }
+            self.recent_items.insert(0, item);
+        }
+        self.recent_items.truncate(MAX_RECENT_ITEMS);
+    }
+
+    /// How `issue` changed since the previous reports
+    ///
+    /// `None` until a report has recorded its items, so the first report
+    /// doesn't mark everything as new, and for items that were already
+    /// closed when last reported.
+    pub fn seen_status(&self, issue: &Issue) -> Option<SeenStatus> {
+        if self.seen_items.is_empty() {
+            return None;
+        }
+        let open = issue.state == IssueState::Open;
+        match self.seen_items.get(&issue.url) {
+            None => Some(SeenStatus::New),
+            Some(_) if open => Some(SeenStatus::CarriedOver),
+            Some(seen) if seen.open => Some(SeenStatus::Resolved),
+            Some(_) => None,
+        }
+    }
+
+    /// Remember the items of a report made at `now`, forgetting items
+    /// missing from reports for [`SEEN_ITEM_RETENTION_DAYS`]
+    pub fn record_seen(
+        &mut self,
+        items: impl IntoIterator<Item = (String, SeenItem)>,
+        now: Timestamp,
+    ) {
+        self.seen_items.extend(items);
+        let cutoff = now - (SEEN_ITEM_RETENTION_DAYS * 24).hours();
+        self.seen_items.retain(|_, seen| seen.reported_at >= cutoff);
+    }
+
+    /// Mute an item at `now`; false if it already was
+    pub fn mute(&mut self, repo: &str, number: u32, now: Timestamp) -> bool {
+        if self.is_muted(repo, number) {
+            return false;
+        }
+        self.muted.push(MutedItem {
+            repo: repo.to_string(),
+            number,
+            muted_at: now,
+        });
+        true
+    }
+
+    /// Unmute an item; false if it wasn't muted
+    pub fn unmute(&mut self, repo: &str, number: u32) -> bool {
+        let before = self.muted.len();
+        self.muted
+            .retain(|item| !(item.repo.eq_ignore_ascii_case(repo) && item.number == number));
+        self.muted.len() < before
+    }
+
+    /// Whether an item is muted, matching the repository case-insensitively
+    pub fn is_muted(&self, repo: &str, number: u32) -> bool {
+        self.muted
+            .iter()
+            .any(|item| item.repo.eq_ignore_ascii_case(repo) && item.number == number)
+    }
+
+    /// Repositories of recently seen items, most recent first
+    pub fn recent_repos(&self) -> Vec<&str> {
+        let mut repos: Vec<&str> = Vec::new();
+        for item in &self.recent_items {
+            if !repos.contains(&item.repo.as_str()) {
+                repos.push(&item.repo);
+            }
+        }
+        repos
+    }
+
     /// Get the timestamp to fetch data since
     pub fn get_since_timestamp(&self, max_lookback_days: u32) -> Timestamp {
         match self.last_run {
@@ -73,15 +290,6 @@ impl State {
     }
 }
 
-impl Default for State {
-    fn default() -> Self {
-        State {
-            last_run: None,
-            last_report_file: None,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +311,69 @@ mod tests {
         assert!(loaded.last_run.is_some());
     }
 
+    #[test]
+    fn test_record_items() {
+        let at: Timestamp = "2024-03-06T14:00:00Z".parse().unwrap();
+        let item = |repo: &str, number, title: &str, hours: i64| RecentItem {
+            repo: repo.to_string(),
+            number,
+            title: title.to_string(),
+            seen_at: at + hours.hours(),
+        };
+
+        let mut state = State::default();
+        state.record_items([
+            item("tokio-rs/tokio", 1, "Runtime panic", 0),
+            item("tokio-rs/axum", 2, "Routing", 1),
+        ]);
+        state.record_items([item("tokio-rs/tokio", 1, "", 2)]);
+
+        let refs: Vec<String> = state.recent_items.iter().map(|i| i.reference()).collect();
+        assert_eq!(refs, ["tokio-rs/tokio#1", "tokio-rs/axum#2"]);
+        assert_eq!(state.recent_items[0].title, "Runtime panic");
+        assert_eq!(state.recent_repos(), ["tokio-rs/tokio", "tokio-rs/axum"]);
+    }
+
+    #[test]
+    fn test_seen_status() {
+        let at: Timestamp = "2024-03-06T14:00:00Z".parse().unwrap();
+        let open = crate::test_utils::create_test_issue(1, "Runtime panic", false);
+        let other = crate::test_utils::create_test_issue(2, "Routing", false);
+
+        let mut state = State::default();
+        assert_eq!(state.seen_status(&open), None);
+
+        state.record_seen([(open.url.clone(), SeenItem::of(&open, at))], at);
+        assert_eq!(state.seen_status(&open), Some(SeenStatus::CarriedOver));
+        assert_eq!(state.seen_status(&other), Some(SeenStatus::New));
+
+        let mut resolved = open.clone();
+        resolved.state = IssueState::Closed;
+        assert_eq!(state.seen_status(&resolved), Some(SeenStatus::Resolved));
+        state.record_seen([(resolved.url.clone(), SeenItem::of(&resolved, at))], at);
+        assert_eq!(state.seen_status(&resolved), None);
+
+        // Forgotten after long enough out of reports
+        let later = at + (100 * 24).hours();
+        state.record_seen([(other.url.clone(), SeenItem::of(&other, later))], later);
+        assert_eq!(state.seen_status(&open), Some(SeenStatus::New));
+    }
+
+    #[test]
+    fn test_mute() {
+        let at: Timestamp = "2024-03-06T14:00:00Z".parse().unwrap();
+        let mut state = State::default();
+        assert!(state.mute("tokio-rs/tokio", 123, at));
+        assert!(!state.mute("Tokio-rs/Tokio", 123, at));
+        assert!(state.is_muted("tokio-rs/TOKIO", 123));
+        assert!(!state.is_muted("tokio-rs/tokio", 124));
+        assert_eq!(state.muted[0].reference(), "tokio-rs/tokio#123");
+
+        assert!(!state.unmute("tokio-rs/tokio", 124));
+        assert!(state.unmute("tokio-rs/tokio", 123));
+        assert!(state.muted.is_empty());
+    }
+
     #[test]
     fn test_load_nonexistent_returns_default() {
         let temp_dir = TempDir::new().unwrap();
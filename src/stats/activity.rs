@@ -0,0 +1,316 @@
+//! Statistics of the activity feed, for `gh-report stats --since`
+//!
+//! Everything here is computed from the events alone, without asking Claude.
+
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, ToSpan};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use crate::github::ActivityEvent;
+
+/// Heatmap cells from no events to the busiest days
+const HEAT_CHARS: &[char] = &['·', '░', '▒', '▓', '█'];
+
+/// Width of the longest bar in the weekday chart
+const WEEKDAY_BAR_WIDTH: usize = 20;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Event counts of the activity feed over a window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityStats {
+    pub first_day: Date,
+    pub last_day: Date,
+    pub total_events: usize,
+    /// Events per day, only for days that had any
+    pub days: BTreeMap<Date, usize>,
+    /// Events per day of the week, Monday first
+    pub weekdays: [usize; 7],
+    /// Events per repository, most active first
+    pub repos: Vec<(String, usize)>,
+    /// Issues and PRs with the most comments, most first
+    pub commented: Vec<CommentedItem>,
+}
+
+/// An issue or PR and the comments it got in the window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentedItem {
+    pub repo: String,
+    pub number: u32,
+    pub title: Option<String>,
+    pub comments: usize,
+}
+
+/// Count the events created between `since` and `now`, by day in `tz`
+pub fn activity_stats(
+    events: &[ActivityEvent],
+    since: Timestamp,
+    now: Timestamp,
+    tz: &TimeZone,
+) -> ActivityStats {
+    let mut days: BTreeMap<Date, usize> = BTreeMap::new();
+    let mut weekdays = [0; 7];
+    let mut repos: HashMap<&str, usize> = HashMap::new();
+    let mut commented: BTreeMap<(String, u32), CommentedItem> = BTreeMap::new();
+    let mut total_events = 0;
+
+    for event in events {
+        if event.created_at < since || event.created_at > now {
+            continue;
+        }
+        total_events += 1;
+
+        let date = event.created_at.to_zoned(tz.clone()).date();
+        *days.entry(date).or_default() += 1;
+        weekdays[date.weekday().to_monday_zero_offset() as usize] += 1;
+        *repos.entry(&event.repo.name).or_default() += 1;
+
+        if !matches!(
+            event.event_type.as_str(),
+            "IssueCommentEvent" | "PullRequestReviewCommentEvent"
+        ) {
+            continue;
+        }
+        let Some((repo, number)) = crate::activity::issue_references(&[event]).pop() else {
+            continue;
+        };
+        let item = commented
+            .entry((repo.clone(), number))
+            .or_insert_with(|| CommentedItem {
+                repo,
+                number,
+                title: None,
+                comments: 0,
+            });
+        item.comments += 1;
+        if item.title.is_none() {
+            item.title = crate::activity::extract_title_from_event(event);
+        }
+    }
+
+    let mut repos: Vec<(String, usize)> = repos
+        .into_iter()
+        .map(|(repo, count)| (repo.to_string(), count))
+        .collect();
+    repos.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut commented: Vec<CommentedItem> = commented.into_values().collect();
+    commented.sort_by_key(|item| std::cmp::Reverse(item.comments));
+
+    ActivityStats {
+        first_day: since.to_zoned(tz.clone()).date(),
+        last_day: now.to_zoned(tz.clone()).date(),
+        total_events,
+        days,
+        weekdays,
+        repos,
+        commented,
+    }
+}
+
+/// Render the heatmap, weekday chart, and the `top` repositories and issues
+pub fn render_activity_stats(stats: &ActivityStats, top: usize) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Activity since {}: {} events in {} repositories\n",
+        stats.first_day,
+        stats.total_events,
+        stats.repos.len()
+    );
+    if stats.total_events == 0 {
+        return out;
+    }
+
+    write_heatmap(&mut out, stats);
+
+    let _ = writeln!(out, "\nEvents by weekday");
+    let busiest = stats.weekdays.iter().copied().max().unwrap_or(0).max(1);
+    for (name, count) in WEEKDAYS.iter().zip(stats.weekdays) {
+        let bar = "█".repeat((count * WEEKDAY_BAR_WIDTH).div_ceil(busiest));
+        let _ = writeln!(
+            out,
+            "  {}  {:<width$} {}",
+            name,
+            bar,
+            count,
+            width = WEEKDAY_BAR_WIDTH
+        );
+    }
+
+    let _ = writeln!(out, "\nMost active repositories");
+    let repo_width = stats
+        .repos
+        .iter()
+        .take(top)
+        .map(|(repo, _)| repo.len())
+        .max()
+        .unwrap_or(0);
+    for (i, (repo, count)) in stats.repos.iter().take(top).enumerate() {
+        let _ = writeln!(
+            out,
+            "  {:>2}. {:<width$}  {} events",
+            i + 1,
+            repo,
+            count,
+            width = repo_width
+        );
+    }
+
+    if !stats.commented.is_empty() {
+        let _ = writeln!(out, "\nMost commented issues and PRs");
+        for (i, item) in stats.commented.iter().take(top).enumerate() {
+            let _ = writeln!(
+                out,
+                "  {:>2}. {}#{}  {} comment{}{}",
+                i + 1,
+                item.repo,
+                item.number,
+                item.comments,
+                if item.comments == 1 { "" } else { "s" },
+                item.title
+                    .as_deref()
+                    .map(|title| format!("  {}", title))
+                    .unwrap_or_default()
+            );
+        }
+    }
+
+    out
+}
+
+/// One column per week and one row per weekday, like the GitHub profile graph
+fn write_heatmap(out: &mut String, stats: &ActivityStats) {
+    let offset = stats.first_day.weekday().to_monday_zero_offset();
+    let first_monday = stats.first_day - i64::from(offset).days();
+    let mut weeks = Vec::new();
+    let mut monday = first_monday;
+    while monday <= stats.last_day {
+        weeks.push(monday);
+        monday += 7.days();
+    }
+    let busiest = stats.days.values().copied().max().unwrap_or(0).max(1);
+
+    // Month names above the first week of each month
+    let mut months = String::from("     ");
+    let mut previous_month = None;
+    for (i, week) in weeks.iter().enumerate() {
+        let day = (*week).max(stats.first_day);
+        let column = 5 + 2 * i;
+        if previous_month != Some(day.month()) && months.chars().count() <= column {
+            let padding = column - months.chars().count();
+            months.push_str(&" ".repeat(padding));
+            months.push_str(&day.strftime("%b").to_string());
+            previous_month = Some(day.month());
+        }
+    }
+    let _ = writeln!(out, "{}", months.trim_end());
+
+    for (weekday, name) in WEEKDAYS.iter().enumerate() {
+        let mut row = format!("{}  ", name);
+        for week in &weeks {
+            let day = *week + (weekday as i64).days();
+            let cell = if day < stats.first_day || day > stats.last_day {
+                ' '
+            } else {
+                let count = stats.days.get(&day).copied().unwrap_or(0);
+                HEAT_CHARS[(count * (HEAT_CHARS.len() - 1)).div_ceil(busiest)]
+            };
+            row.push(cell);
+            row.push(' ');
+        }
+        let _ = writeln!(out, "{}", row.trim_end());
+    }
+
+    let mut legend = String::from("     less ");
+    for c in HEAT_CHARS {
+        legend.push(*c);
+        legend.push(' ');
+    }
+    legend.push_str("more");
+    let _ = writeln!(out, "{}", legend);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: u32, repo: &str, event_type: &str, at: &str, number: u32) -> ActivityEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "type": event_type,
+            "actor": { "login": "alice" },
+            "repo": { "id": 1, "name": repo, "url": "" },
+            "payload": { "action": "created", "issue": { "number": number, "title": "Panic on shutdown" } },
+            "created_at": at,
+            "public": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_activity_stats() {
+        let events = [
+            // Monday
+            event(
+                1,
+                "tokio-rs/tokio",
+                "IssueCommentEvent",
+                "2024-03-04T10:00:00Z",
+                7,
+            ),
+            event(
+                2,
+                "tokio-rs/tokio",
+                "IssueCommentEvent",
+                "2024-03-04T11:00:00Z",
+                7,
+            ),
+            // Wednesday
+            event(3, "tokio-rs/axum", "IssuesEvent", "2024-03-06T09:00:00Z", 2),
+            event(
+                4,
+                "tokio-rs/tokio",
+                "IssueCommentEvent",
+                "2024-03-06T09:30:00Z",
+                9,
+            ),
+            // Before the window
+            event(
+                5,
+                "tokio-rs/mio",
+                "IssueCommentEvent",
+                "2024-02-01T09:00:00Z",
+                1,
+            ),
+        ];
+        let since: Timestamp = "2024-03-01T00:00:00Z".parse().unwrap();
+        let now: Timestamp = "2024-03-07T00:00:00Z".parse().unwrap();
+
+        let stats = activity_stats(&events, since, now, &TimeZone::UTC);
+        assert_eq!(stats.total_events, 4);
+        assert_eq!(stats.weekdays, [2, 0, 2, 0, 0, 0, 0]);
+        assert_eq!(
+            stats.repos,
+            [
+                ("tokio-rs/tokio".to_string(), 3),
+                ("tokio-rs/axum".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            (stats.commented[0].number, stats.commented[0].comments),
+            (7, 2)
+        );
+        assert_eq!(stats.commented.len(), 2);
+
+        let output = render_activity_stats(&stats, 1);
+        assert!(output.starts_with("Activity since 2024-03-01: 4 events in 2 repositories\n"));
+        // Friday 1st to Thursday 7th: the first week starts on the Friday row
+        assert!(output.contains("\nMon    █\n"));
+        assert!(output.contains("\nFri  ·\n"));
+        assert!(output.contains("   1. tokio-rs/tokio  3 events\n"));
+        assert!(!output.contains("tokio-rs/axum  1"));
+        assert!(output.contains("   1. tokio-rs/tokio#7  2 comments  Panic on shutdown\n"));
+    }
+}
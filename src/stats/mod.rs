@@ -0,0 +1,444 @@
+//! Local-only usage statistics
+//!
+//! When `settings.usage_stats` is enabled, every command appends one
+//! [`RunRecord`] to a JSON lines file on disk. Nothing is ever sent over the
+//! network; the file exists so `gh-report stats --tool` can show how the tool
+//! performs over time and which settings are worth tuning.
+//!
+//! Statistics of the activity feed, for `gh-report stats --since`, live in
+//! the `activity` submodule.
+
+use anyhow::{Context, Result};
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+mod activity;
+
+pub use activity::{activity_stats, render_activity_stats, ActivityStats, CommentedItem};
+
+static GITHUB_CALLS: AtomicU32 = AtomicU32::new(0);
+static CLAUDE_CALLS: AtomicU32 = AtomicU32::new(0);
+static CACHE_HITS: AtomicU32 = AtomicU32::new(0);
+static CACHE_MISSES: AtomicU32 = AtomicU32::new(0);
+static ERRORS: Mutex<BTreeMap<ErrorCategory, u32>> = Mutex::new(BTreeMap::new());
+
+/// Characters used to draw duration trends, from shortest to longest
+const SPARK_CHARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Number of recent runs shown in a duration trend
+const TREND_RUNS: usize = 30;
+
+/// Record a call to the GitHub CLI/API
+pub fn record_github_call() {
+    GITHUB_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Record a request to the Claude backend
+pub fn record_claude_call() {
+    CLAUDE_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Record a cache lookup and whether it was served from the cache
+pub fn record_cache_lookup(hit: bool) {
+    let counter = if hit { &CACHE_HITS } else { &CACHE_MISSES };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Record a failed external call, classified from its error message
+pub fn record_error(message: &str) {
+    let category = ErrorCategory::classify(message);
+    if let Ok(mut errors) = ERRORS.lock() {
+        *errors.entry(category).or_default() += 1;
+    }
+}
+
+/// Broad kind of failure, without any identifying details
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Auth,
+    RateLimit,
+    NotFound,
+    Network,
+    Parse,
+    Other,
+}
+
+impl ErrorCategory {
+    /// Classify an error from its message
+    pub fn classify(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| message.contains(n));
+
+        // Rate limits are reported as 403s, so check them before auth
+        if has(&["rate limit", "429", "too many requests"]) {
+            ErrorCategory::RateLimit
+        } else if has(&["401", "403", "auth", "credential", "api key"]) {
+            ErrorCategory::Auth
+        } else if has(&["404", "not found"]) {
+            ErrorCategory::NotFound
+        } else if has(&[
+            "timed out",
+            "timeout",
+            "connection",
+            "network",
+            "dns",
+            "resolve",
+        ]) {
+            ErrorCategory::Network
+        } else if has(&["parse", "invalid json", "deserializ", "utf-8"]) {
+            ErrorCategory::Parse
+        } else {
+            ErrorCategory::Other
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::RateLimit => "rate limit",
+            ErrorCategory::NotFound => "not found",
+            ErrorCategory::Network => "network",
+            ErrorCategory::Parse => "parse",
+            ErrorCategory::Other => "other",
+        }
+    }
+}
+
+/// Statistics for a single command invocation
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RunRecord {
+    pub command: String,
+    pub started_at: Timestamp,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub github_calls: u32,
+    pub claude_calls: u32,
+    pub cache_hits: u32,
+    pub cache_misses: u32,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<ErrorCategory, u32>,
+}
+
+impl RunRecord {
+    /// Build a record from the counters collected during this process
+    pub fn capture(
+        command: &str,
+        started_at: Timestamp,
+        duration: Duration,
+        success: bool,
+    ) -> Self {
+        RunRecord {
+            command: command.to_string(),
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            success,
+            github_calls: GITHUB_CALLS.load(Ordering::Relaxed),
+            claude_calls: CLAUDE_CALLS.load(Ordering::Relaxed),
+            cache_hits: CACHE_HITS.load(Ordering::Relaxed),
+            cache_misses: CACHE_MISSES.load(Ordering::Relaxed),
+            errors: ERRORS.lock().map(|e| e.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Append-only store of run records
+pub struct StatsStore {
+    path: PathBuf,
+}
+
+impl StatsStore {
+    pub fn new(path: PathBuf) -> Self {
+        StatsStore { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record to the store
+    pub fn append(&self, record: &RunRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+
+        let line = serde_json::to_string(record).context("Failed to serialize run record")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open stats file {:?}", self.path))?;
+        writeln!(file, "{}", line)
+            .with_context(|| format!("Failed to write stats to {:?}", self.path))?;
+
+        Ok(())
+    }
+
+    /// Load all records, skipping lines that can't be parsed
+    pub fn load(&self) -> Result<Vec<RunRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read stats from {:?}", self.path))?;
+
+        let mut records = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(record) => records.push(record),
+                Err(e) => warn!("Skipping malformed stats line {}: {}", i + 1, e),
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+/// Render a text overview of tool performance
+pub fn render_tool_stats(records: &[RunRecord]) -> String {
+    let mut out = String::new();
+
+    let Some(first) = records.iter().map(|r| r.started_at).min() else {
+        return "No runs recorded yet.\n".to_string();
+    };
+
+    let _ = writeln!(
+        out,
+        "Tool performance: {} runs since {}\n",
+        records.len(),
+        first.strftime("%Y-%m-%d")
+    );
+
+    let mut by_command: BTreeMap<&str, Vec<&RunRecord>> = BTreeMap::new();
+    for record in records {
+        by_command.entry(&record.command).or_default().push(record);
+    }
+
+    let _ = writeln!(
+        out,
+        "{:<12} {:>5} {:>6} {:>8} {:>8} {:>8} {:>9} {:>10} {:>7}",
+        "command", "runs", "failed", "avg", "p90", "max", "gh/run", "claude/run", "cache"
+    );
+    for (command, runs) in &by_command {
+        let mut durations: Vec<u64> = runs.iter().map(|r| r.duration_ms).collect();
+        durations.sort_unstable();
+        let n = runs.len() as f64;
+        let failed = runs.iter().filter(|r| !r.success).count();
+        let avg = durations.iter().sum::<u64>() / durations.len() as u64;
+        let p90 = durations[(durations.len() * 9 / 10).min(durations.len() - 1)];
+        let max = durations[durations.len() - 1];
+        let github = runs.iter().map(|r| r.github_calls as f64).sum::<f64>() / n;
+        let claude = runs.iter().map(|r| r.claude_calls as f64).sum::<f64>() / n;
+
+        let _ = writeln!(
+            out,
+            "{:<12} {:>5} {:>6} {:>8} {:>8} {:>8} {:>9.1} {:>10.1} {:>7}",
+            command,
+            runs.len(),
+            failed,
+            format_duration(avg),
+            format_duration(p90),
+            format_duration(max),
+            github,
+            claude,
+            hit_rate(runs.iter().copied())
+                .map(|r| format!("{:.0}%", r * 100.0))
+                .unwrap_or_else(|| "-".to_string()),
+        );
+    }
+
+    let _ = writeln!(out, "\nDuration trend (oldest → newest):");
+    for (command, runs) in &by_command {
+        let mut runs = runs.clone();
+        runs.sort_by_key(|r| r.started_at);
+        let recent: Vec<u64> = runs
+            .iter()
+            .rev()
+            .take(TREND_RUNS)
+            .rev()
+            .map(|r| r.duration_ms)
+            .collect();
+        let _ = writeln!(out, "  {:<12} {}", command, sparkline(&recent));
+    }
+
+    let mut errors: BTreeMap<ErrorCategory, u32> = BTreeMap::new();
+    for record in records {
+        for (category, count) in &record.errors {
+            *errors.entry(*category).or_default() += count;
+        }
+    }
+    if !errors.is_empty() {
+        let _ = writeln!(out, "\nErrors by category:");
+        for (category, count) in &errors {
+            let _ = writeln!(out, "  {:<12} {}", category.label(), count);
+        }
+    }
+
+    let hints = tuning_hints(records, &errors);
+    if !hints.is_empty() {
+        let _ = writeln!(out, "\nTuning hints:");
+        for hint in hints {
+            let _ = writeln!(out, "  - {}", hint);
+        }
+    }
+
+    out
+}
+
+/// Suggestions for settings based on the recorded runs
+fn tuning_hints(records: &[RunRecord], errors: &BTreeMap<ErrorCategory, u32>) -> Vec<String> {
+    let mut hints = Vec::new();
+
+    if errors.contains_key(&ErrorCategory::RateLimit) {
+        hints.push(
+            "GitHub rate limits were hit; keep [cache] enabled and use a shorter --since window"
+                .to_string(),
+        );
+    }
+
+    match hit_rate(records.iter()) {
+        Some(rate) if rate < 0.2 => hints.push(format!(
+            "Only {:.0}% of cache lookups hit; consider raising [cache] ttl_hours",
+            rate * 100.0
+        )),
+        None if records.iter().any(|r| r.github_calls > 0) => hints
+            .push("No cache lookups were recorded; check that [cache] enabled = true".to_string()),
+        _ => {}
+    }
+
+    if errors.contains_key(&ErrorCategory::Auth) {
+        hints.push("Authentication errors occurred; run `gh auth status`".to_string());
+    }
+
+    hints
+}
+
+/// Share of cache lookups that were hits, if any lookups happened
+fn hit_rate<'a>(records: impl Iterator<Item = &'a RunRecord>) -> Option<f64> {
+    let (hits, misses) = records.fold((0u64, 0u64), |(h, m), r| {
+        (h + r.cache_hits as u64, m + r.cache_misses as u64)
+    });
+    let total = hits + misses;
+    (total > 0).then(|| hits as f64 / total as f64)
+}
+
+fn sparkline(values: &[u64]) -> String {
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    let range = (max - min).max(1) as f64;
+    let steps = (SPARK_CHARS.len() - 1) as f64;
+
+    values
+        .iter()
+        .map(|v| SPARK_CHARS[(((v - min) as f64 / range) * steps).round() as usize])
+        .collect()
+}
+
+fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m{:02}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::ToSpan;
+    use tempfile::TempDir;
+
+    fn record(command: &str, duration_ms: u64, hits: u32, misses: u32) -> RunRecord {
+        RunRecord {
+            command: command.to_string(),
+            started_at: Timestamp::now(),
+            duration_ms,
+            success: true,
+            github_calls: 10,
+            claude_calls: 1,
+            cache_hits: hits,
+            cache_misses: misses,
+            errors: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        assert_eq!(
+            ErrorCategory::classify("gh: API rate limit exceeded (HTTP 403)"),
+            ErrorCategory::RateLimit
+        );
+        assert_eq!(
+            ErrorCategory::classify("Authentication failed. Run 'gh auth login'"),
+            ErrorCategory::Auth
+        );
+        assert_eq!(
+            ErrorCategory::classify("Resource not found"),
+            ErrorCategory::NotFound
+        );
+        assert_eq!(
+            ErrorCategory::classify("operation timed out"),
+            ErrorCategory::Network
+        );
+        assert_eq!(ErrorCategory::classify("boom"), ErrorCategory::Other);
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = StatsStore::new(temp_dir.path().join("nested").join("stats.jsonl"));
+        assert!(store.load().unwrap().is_empty());
+
+        let mut failed = record("report", 2500, 1, 3);
+        failed.success = false;
+        failed.errors.insert(ErrorCategory::Network, 2);
+        store.append(&record("report", 1200, 3, 1)).unwrap();
+        store.append(&failed).unwrap();
+
+        let records = store.load().unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(!records[1].success);
+        assert_eq!(records[1].errors.get(&ErrorCategory::Network), Some(&2));
+    }
+
+    #[test]
+    fn test_render_tool_stats() {
+        assert_eq!(render_tool_stats(&[]), "No runs recorded yet.\n");
+
+        let mut fast = record("report", 800, 0, 10);
+        fast.started_at -= 1.hour();
+        let mut slow = record("report", 90_000, 0, 10);
+        slow.errors.insert(ErrorCategory::RateLimit, 1);
+        let records = vec![slow, fast, record("summarize", 3000, 1, 0)];
+
+        let output = render_tool_stats(&records);
+        assert!(output.contains("3 runs since"));
+        assert!(output.contains("1m30s"));
+        assert!(output.contains("rate limit"));
+        assert!(output.contains("  report       ▁█"));
+        assert!(output.contains("Only 5% of cache lookups hit"));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[1, 5, 9]), "▁▅█");
+        assert_eq!(sparkline(&[4, 4]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
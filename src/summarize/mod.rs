@@ -1,38 +1,121 @@
 use anyhow::{anyhow, Context, Result};
+use jiff::Timestamp;
+use std::collections::BTreeSet;
+use std::io::Write;
 use std::path::Path;
 use tracing::{info, warn};
 
+use crate::cache::{CacheManager, IssueContext, SummaryCheckpoint};
 use crate::claude::prompts::{
-    generate_issue_filename, review_pr_for_maintainer, summarize_issue_for_maintainer,
+    compare_items_prompt, diff_patches_section, generate_comparison_filename,
+    generate_issue_filename, repo_context_section, review_pr_for_maintainer,
+    summarize_issue_for_maintainer, with_output_language, ComparedItem, DEFAULT_DIFF_PATCH_TOKENS,
+};
+use crate::claude::{
+    backend_from_config, confirm_private_repos, find_private_repos, LlmBackend, Message,
+    MessagesRequest, MessagesResponse,
 };
-use crate::claude::{resolve_model_alias, ClaudeInterface, Message, MessagesRequest};
 use crate::config::Config;
-use crate::github::{parse_issue_reference, Comment, GitHubClient, Issue, IssueState};
+use crate::github::{
+    parse_issue_reference_on, repo_from_url, Comment, GitHubClient, Issue, IssueReference,
+    IssueState, ReviewThread,
+};
+use crate::intelligence::window_comments;
 
 /// Orchestrates the summarization of a specific GitHub issue or PR
 pub struct IssueSummarizer<'a> {
     github_client: GitHubClient,
-    claude_client: Option<ClaudeInterface>,
+    llm: Option<Box<dyn LlmBackend>>,
     config: &'a Config,
+    cache: Option<CacheManager>,
+    refresh: bool,
+    stream: bool,
+    with_diff: bool,
 }
 
 impl<'a> IssueSummarizer<'a> {
     /// Create a new issue summarizer
     pub fn new(github_client: GitHubClient, config: &'a Config) -> Self {
         // Try to create Claude client
-        let claude_client = match ClaudeInterface::new(&config.claude) {
+        let llm = match backend_from_config(config) {
             Ok(client) => client,
             Err(e) => {
-                warn!("Failed to initialize Claude: {}", e);
+                warn!("Failed to initialize LLM backend: {}", e);
                 None
             }
         };
 
+        // Earlier summaries are kept in the cache's contexts namespace
+        let cache = if config.cache.enabled {
+            let manager = CacheManager::new(
+                config.cache_dir(),
+                config.cache.ttl_hours,
+                config.cache.compression_enabled,
+            );
+            match manager.initialize() {
+                Ok(()) => Some(manager),
+                Err(e) => {
+                    warn!("Failed to initialize cache: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         IssueSummarizer {
             github_client,
-            claude_client,
+            llm,
             config,
+            cache,
+            refresh: false,
+            stream: false,
+            with_diff: false,
+        }
+    }
+
+    /// Regenerate the summary even when the issue is unchanged since the last one
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Write the summary to stdout as Claude generates it
+    pub fn with_stream(mut self, stream: bool) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Show Claude the patches of a PR's most-changed files, not just file counts
+    pub fn with_diff(mut self, with_diff: bool) -> Self {
+        self.with_diff = with_diff;
+        self
+    }
+
+    /// Language summaries are written in, when not the model's default
+    fn output_language(&self) -> Option<&str> {
+        self.config.claude.output_language.as_deref()
+    }
+
+    /// Whether the summary of `issue` gets the patches, which only PRs have
+    fn wants_diff(&self, issue: &Issue) -> bool {
+        self.with_diff && issue.is_pull_request
+    }
+
+    /// Whether content from `repos` may be sent to `claude`
+    fn may_send<'r>(
+        &self,
+        claude: &dyn LlmBackend,
+        repos: impl IntoIterator<Item = &'r str>,
+    ) -> Result<bool> {
+        if self.config.claude.allow_private_repos {
+            return Ok(true);
         }
+        let mut repos: Vec<&str> = repos.into_iter().collect();
+        repos.sort_unstable();
+        repos.dedup();
+        let private_repos = find_private_repos(&self.github_client, self.cache.as_ref(), repos);
+        confirm_private_repos(&self.config.claude, &claude.destination(), &private_repos)
     }
 
     /// Summarize an issue or PR and save to file
@@ -42,11 +125,7 @@ impl<'a> IssueSummarizer<'a> {
         output_path: Option<&Path>,
         include_recommendations: bool,
     ) -> Result<String> {
-        info!("Parsing issue reference: {}", target);
-
-        // Parse the issue reference
-        let reference = parse_issue_reference(target)
-            .with_context(|| format!("Failed to parse issue reference: {}", target))?;
+        let reference = self.parse_target(target)?;
 
         info!(
             "Fetching issue #{} from {}",
@@ -68,12 +147,19 @@ impl<'a> IssueSummarizer<'a> {
 
         info!("Fetched issue with {} comments", comments.len());
 
-        // Generate the summary
-        let summary = if let Some(claude) = &self.claude_client {
-            self.generate_ai_summary(claude, &issue, &comments, include_recommendations)?
-        } else {
-            warn!("Claude not available, generating basic summary");
-            self.generate_basic_summary(&issue, &comments)
+        let repo = reference.repo_name();
+        let summary = match self.checkpoint(&repo, &issue, include_recommendations) {
+            Some(summary) => {
+                info!(
+                    "{}#{} is unchanged since its last summary, reusing it",
+                    repo, issue.number
+                );
+                if self.stream {
+                    println!("{}", summary);
+                }
+                summary
+            }
+            None => self.generate_summary(&repo, &issue, &comments, include_recommendations)?,
         };
 
         // Determine output file path
@@ -86,22 +172,320 @@ impl<'a> IssueSummarizer<'a> {
             std::env::current_dir()?.join(filename)
         };
 
-        // Write the summary to file
-        std::fs::write(&output_file, &summary)
-            .with_context(|| format!("Failed to write summary to {}", output_file.display()))?;
+        write_summary(&output_file, &summary)?;
 
-        info!("Summary saved to: {}", output_file.display());
+        Ok(output_file.to_string_lossy().into_owned())
+    }
+
+    /// Summarize several issues and PRs in one file that relates them to
+    /// each other: overlaps, conflicts, and the order they should land in
+    pub fn summarize_many(
+        &self,
+        targets: &[String],
+        output_path: Option<&Path>,
+        include_recommendations: bool,
+    ) -> Result<String> {
+        let mut references: Vec<IssueReference> = Vec::new();
+        for target in targets {
+            let reference = self.parse_target(target)?;
+            if !references
+                .iter()
+                .any(|r| r.repo_name() == reference.repo_name() && r.number == reference.number)
+            {
+                references.push(reference);
+            }
+        }
+
+        let mut items: Vec<(String, Issue, Vec<Comment>)> = Vec::new();
+        for reference in &references {
+            let repo = reference.repo_name();
+            info!("Fetching issue #{} from {}", reference.number, repo);
+            let (issue, comments) = self
+                .github_client
+                .fetch_single_issue(&repo, reference.number)
+                .with_context(|| {
+                    format!("Failed to fetch issue #{} from {}", reference.number, repo)
+                })?;
+            items.push((repo, issue, comments));
+        }
+
+        let repos = items.iter().map(|(repo, _, _)| repo.as_str());
+        let content = match self.llm.as_deref() {
+            Some(claude) if self.may_send(claude, repos)? => {
+                self.generate_ai_comparison(claude, &items, include_recommendations)?
+            }
+            _ => {
+                warn!("Claude not available, generating basic summaries");
+                let content = self.generate_basic_comparison(&items);
+                if self.stream {
+                    println!("{}", content);
+                }
+                content
+            }
+        };
+        let summary = format_comparison(&items, &content);
+
+        let output_file = match output_path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let numbers: Vec<(String, u32)> = items
+                    .iter()
+                    .map(|(repo, issue, _)| (repo.clone(), issue.number))
+                    .collect();
+                std::env::current_dir()?.join(generate_comparison_filename(&numbers))
+            }
+        };
+        write_summary(&output_file, &summary)?;
 
         Ok(output_file.to_string_lossy().into_owned())
     }
 
-    /// Generate AI-powered summary using Claude
-    fn generate_ai_summary(
+    /// Parse an issue reference, which must be on the configured host
+    fn parse_target(&self, target: &str) -> Result<IssueReference> {
+        info!("Parsing issue reference: {}", target);
+
+        let host = self.config.github_host();
+        let reference = parse_issue_reference_on(target, host)
+            .with_context(|| format!("Failed to parse issue reference: {}", target))?;
+        if let Some(target_host) = &reference.host {
+            if !target_host.eq_ignore_ascii_case(host) {
+                return Err(anyhow!(
+                    "{} is on {}, but gh is set up for {} (settings.github_host)",
+                    target,
+                    target_host,
+                    host
+                ));
+            }
+        }
+        Ok(reference)
+    }
+
+    /// Have Claude relate the items to each other, without header and footer
+    fn generate_ai_comparison(
+        &self,
+        claude: &dyn LlmBackend,
+        items: &[(String, Issue, Vec<Comment>)],
+        include_recommendations: bool,
+    ) -> Result<String> {
+        let diff_summaries = items
+            .iter()
+            .map(|(_, issue, _)| match issue.is_pull_request {
+                true => self.get_pr_diff_summary(issue).map(Some),
+                false => Ok(None),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let compared: Vec<ComparedItem> = items
+            .iter()
+            .zip(&diff_summaries)
+            .map(|((repo, issue, comments), diff_summary)| ComparedItem {
+                repo,
+                issue,
+                comments,
+                diff_summary: diff_summary.as_deref(),
+            })
+            .collect();
+        let mut prompt = compare_items_prompt(&compared, include_recommendations);
+
+        // Prepend the context of each repository involved, once
+        let repos: BTreeSet<&str> = items.iter().map(|(repo, _, _)| repo.as_str()).collect();
+        let contexts: String = repos
+            .into_iter()
+            .filter_map(|repo| {
+                let context = self.config.repo_context(repo)?;
+                Some(repo_context_section(repo, &context))
+            })
+            .collect();
+        prompt.insert_str(0, &contexts);
+        let prompt = with_output_language(prompt, self.output_language());
+
+        let request = MessagesRequest::new(self.primary_model(), vec![Message::user(prompt)])
+            .with_max_tokens(4000);
+        let response = if self.stream {
+            self.stream_to_stdout(claude, request)
+        } else {
+            claude.messages(request)
+        };
+        let response = response.context("Failed to get summary from Claude")?;
+
+        Ok(response.get_text())
+    }
+
+    /// The basic summary of each item, one after the other
+    fn generate_basic_comparison(&self, items: &[(String, Issue, Vec<Comment>)]) -> String {
+        items
+            .iter()
+            .map(|(repo, issue, comments)| {
+                format!(
+                    "## [{}#{}: {}]({})\n\n{}",
+                    repo,
+                    issue.number,
+                    issue.title,
+                    issue.url,
+                    self.basic_summary_body(issue, comments, &[])
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The summary from an earlier run, if the issue hasn't changed since
+    fn checkpoint(
         &self,
-        claude: &ClaudeInterface,
+        repo: &str,
+        issue: &Issue,
+        include_recommendations: bool,
+    ) -> Option<String> {
+        if self.refresh {
+            return None;
+        }
+        let checkpoint = match self
+            .cache
+            .as_ref()?
+            .get_summary_checkpoint(repo, issue.number)
+        {
+            Ok(checkpoint) => checkpoint?,
+            Err(e) => {
+                warn!("Failed to read summary checkpoint: {}", e);
+                return None;
+            }
+        };
+        // A summary grounded in the patches also does when they weren't asked for
+        let current = checkpoint.is_current(
+            issue.updated_at,
+            &self.primary_model(),
+            include_recommendations,
+        ) && (checkpoint.with_diff || !self.wants_diff(issue))
+            && checkpoint.language.as_deref() == self.output_language();
+        current.then_some(checkpoint.summary)
+    }
+
+    /// Summarize the issue, with Claude when available
+    ///
+    /// Claude summaries are checkpointed so an unchanged issue isn't sent again.
+    fn generate_summary(
+        &self,
+        repo: &str,
         issue: &Issue,
         comments: &[Comment],
         include_recommendations: bool,
+    ) -> Result<String> {
+        let review_threads = if issue.is_pull_request {
+            self.fetch_review_threads(repo, issue.number)
+        } else {
+            Vec::new()
+        };
+
+        let claude = match self.llm.as_deref() {
+            Some(claude) if self.may_send(claude, [repo])? => claude,
+            _ => {
+                warn!("Claude not available, generating basic summary");
+                let summary = self.generate_basic_summary(issue, comments, &review_threads);
+                if self.stream {
+                    println!("{}", summary);
+                }
+                return Ok(summary);
+            }
+        };
+        // Long threads are cut down to the comments that fit the prompt
+        let settings = &self.config.settings;
+        let kept = window_comments(
+            comments,
+            settings.max_comments_per_issue,
+            settings.comment_window,
+        );
+        let omitted = comments.len() - kept.len();
+        let ai_summary = self.generate_ai_summary(
+            claude,
+            issue,
+            &kept,
+            omitted,
+            &review_threads,
+            include_recommendations,
+        )?;
+        let summary = if omitted > 0 {
+            let note = format!(
+                "*{} of {} comments were left out of this summary \
+                 (settings.max_comments_per_issue, {})*",
+                omitted,
+                comments.len(),
+                settings.comment_window.as_str()
+            );
+            self.format_final_summary(issue, &format!("{}\n\n{}", ai_summary.trim_end(), note))
+        } else {
+            self.format_final_summary(issue, &ai_summary)
+        };
+
+        if let Some(cache) = &self.cache {
+            self.store_issue_context(cache, repo, issue, comments, &ai_summary);
+            let checkpoint = SummaryCheckpoint {
+                issue_number: issue.number,
+                repo: repo.to_string(),
+                updated_at: issue.updated_at,
+                model: self.primary_model(),
+                include_recommendations,
+                with_diff: self.wants_diff(issue),
+                language: self.output_language().map(str::to_string),
+                summary: summary.clone(),
+                cached_at: Timestamp::now(),
+            };
+            if let Err(e) = cache.cache_summary_checkpoint(&checkpoint) {
+                warn!("Failed to cache summary: {}", e);
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Record the summary as the context of the thread, so reports only
+    /// send the comments posted after it
+    fn store_issue_context(
+        &self,
+        cache: &CacheManager,
+        repo: &str,
+        issue: &Issue,
+        comments: &[Comment],
+        ai_summary: &str,
+    ) {
+        let key_points = match cache.get_issue_context(repo, issue.number) {
+            Ok(previous) => previous.map(|p| p.key_points).unwrap_or_default(),
+            Err(e) => {
+                warn!(
+                    "Failed to read context for {}#{}: {}",
+                    repo, issue.number, e
+                );
+                Vec::new()
+            }
+        };
+        let context = IssueContext {
+            issue_number: issue.number,
+            repo: repo.to_string(),
+            summary: ai_summary.trim().to_string(),
+            key_points,
+            last_processed_comment_id: comments.iter().map(|c| c.id).max(),
+            cached_at: Timestamp::now(),
+        };
+        if let Err(e) = cache.cache_issue_context(repo, issue.number, &context) {
+            warn!(
+                "Failed to store context for {}#{}: {}",
+                repo, issue.number, e
+            );
+        }
+    }
+
+    fn primary_model(&self) -> String {
+        self.config
+            .claude
+            .resolve_model(&self.config.claude.primary_model)
+    }
+
+    /// Generate AI-powered summary using Claude, without header and footer
+    fn generate_ai_summary(
+        &self,
+        claude: &dyn LlmBackend,
+        issue: &Issue,
+        comments: &[&Comment],
+        omitted_comments: usize,
+        review_threads: &[ReviewThread],
+        include_recommendations: bool,
     ) -> Result<String> {
         // Prepare issue data
         let issue_state = match issue.state {
@@ -131,7 +515,9 @@ impl<'a> IssueSummarizer<'a> {
                 &issue_labels,
                 &issue.url,
                 &comment_pairs,
+                omitted_comments,
                 &diff_summary,
+                review_threads,
                 include_recommendations,
             )
         } else {
@@ -144,26 +530,107 @@ impl<'a> IssueSummarizer<'a> {
                 &issue_labels,
                 &issue.url,
                 &comment_pairs,
+                omitted_comments,
                 include_recommendations,
             )
         };
 
+        // Prepend repository-specific context if configured
+        let prompt = match issue.repository_name() {
+            Some(repo) => match self.config.repo_context(&repo) {
+                Some(context) => format!("{}{}", repo_context_section(&repo, &context), prompt),
+                None => prompt,
+            },
+            None => prompt,
+        };
+        let prompt = with_output_language(prompt, self.output_language());
+
         // Call Claude
-        let model = resolve_model_alias(&self.config.claude.primary_model);
-        let request =
-            MessagesRequest::new(model, vec![Message::user(prompt)]).with_max_tokens(4000);
+        let request = MessagesRequest::new(self.primary_model(), vec![Message::user(prompt)])
+            .with_max_tokens(4000);
+
+        let response = if self.stream {
+            self.stream_to_stdout(claude, request)
+        } else {
+            claude.messages(request)
+        };
+        let response = response.context("Failed to get summary from Claude")?;
+
+        Ok(response.get_text())
+    }
 
-        let response = claude
-            .messages(request)
-            .context("Failed to get summary from Claude")?;
+    /// Send `request`, writing the text to stdout as it arrives
+    ///
+    /// A stream that fails before any text arrived is retried without
+    /// streaming.
+    fn stream_to_stdout(
+        &self,
+        claude: &dyn LlmBackend,
+        request: MessagesRequest,
+    ) -> Result<MessagesResponse> {
+        let fallback = request.clone();
+        let mut streamed = false;
+        let mut stdout = std::io::stdout();
+        let result = claude.messages_streaming(request, &mut |text| {
+            streamed = true;
+            let _ = stdout.write_all(text.as_bytes());
+            let _ = stdout.flush();
+        });
+        match result {
+            Ok(response) => {
+                println!();
+                Ok(response)
+            }
+            Err(e) if !streamed => {
+                warn!("Streaming failed, retrying without it: {}", e);
+                claude.messages(fallback)
+            }
+            Err(e) => {
+                println!();
+                Err(e)
+            }
+        }
+    }
 
-        // Generate the final markdown with header
-        let ai_summary = response.get_text();
-        Ok(self.format_final_summary(issue, &ai_summary))
+    /// Review threads of a PR, or none when they can't be fetched
+    fn fetch_review_threads(&self, repo: &str, pr_number: u32) -> Vec<ReviewThread> {
+        match self.github_client.fetch_review_threads(repo, pr_number) {
+            Ok(threads) => {
+                info!(
+                    "Fetched {} review threads, {} unresolved",
+                    threads.len(),
+                    threads.iter().filter(|t| !t.is_resolved).count()
+                );
+                threads
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to fetch review threads for {}#{}: {}",
+                    repo, pr_number, e
+                );
+                Vec::new()
+            }
+        }
     }
 
     /// Generate basic summary without AI
-    fn generate_basic_summary(&self, issue: &Issue, comments: &[Comment]) -> String {
+    fn generate_basic_summary(
+        &self,
+        issue: &Issue,
+        comments: &[Comment],
+        review_threads: &[ReviewThread],
+    ) -> String {
+        let summary = self.basic_summary_body(issue, comments, review_threads);
+        self.format_final_summary(issue, &summary)
+    }
+
+    /// The facts of the basic summary, without header and footer
+    fn basic_summary_body(
+        &self,
+        issue: &Issue,
+        comments: &[Comment],
+        review_threads: &[ReviewThread],
+    ) -> String {
         let issue_state = match issue.state {
             IssueState::Open => "Open",
             IssueState::Closed => "Closed",
@@ -199,6 +666,30 @@ impl<'a> IssueSummarizer<'a> {
             summary.push_str("\n\n");
         }
 
+        if !review_threads.is_empty() {
+            let unresolved: Vec<&ReviewThread> =
+                review_threads.iter().filter(|t| !t.is_resolved).collect();
+            summary.push_str(&format!(
+                "## Review Threads\n\n{} of {} resolved.\n\n",
+                review_threads.len() - unresolved.len(),
+                review_threads.len()
+            ));
+            for thread in unresolved {
+                summary.push_str(&format!(
+                    "- **Unresolved**{}: `{}` — {} ({} comments)\n",
+                    if thread.is_long_running() {
+                        ", long-running"
+                    } else {
+                        ""
+                    },
+                    thread.location(),
+                    thread.topic(),
+                    thread.comment_count
+                ));
+            }
+            summary.push('\n');
+        }
+
         // Add comments section
         if !comments.is_empty() {
             summary.push_str(&format!("## Comments ({})\n\n", comments.len()));
@@ -214,7 +705,7 @@ impl<'a> IssueSummarizer<'a> {
             }
         }
 
-        self.format_final_summary(issue, &summary)
+        summary
     }
 
     /// Add title header and footer to summary
@@ -280,6 +771,18 @@ impl<'a> IssueSummarizer<'a> {
                     }
                 }
 
+                if self.with_diff {
+                    let max_tokens = match self.config.intelligence.diff_patch_tokens {
+                        0 => DEFAULT_DIFF_PATCH_TOKENS,
+                        tokens => tokens,
+                    };
+                    let patches = diff_patches_section(&diff, max_tokens);
+                    if !patches.is_empty() {
+                        summary.push_str("\n\nPatches of the most-changed files:\n\n");
+                        summary.push_str(&patches);
+                    }
+                }
+
                 Ok(summary)
             }
             Err(e) => {
@@ -292,19 +795,53 @@ impl<'a> IssueSummarizer<'a> {
     /// Extract repository name from GitHub URL
     fn extract_repo_from_url(&self, url: &str) -> Result<String> {
         // Expected format: https://github.com/owner/repo/pull/123 or /issues/123
-        let parts: Vec<&str> = url.split('/').collect();
-        if parts.len() >= 5 && parts[2] == "github.com" {
-            Ok(format!("{}/{}", parts[3], parts[4]))
-        } else {
-            Err(anyhow!("Invalid GitHub URL format: {}", url))
-        }
+        repo_from_url(url).ok_or_else(|| anyhow!("Invalid GitHub URL format: {}", url))
     }
 }
 
+/// Write a summary to `output_file`, or to stdout when it is `-`
+fn write_summary(output_file: &Path, summary: &str) -> Result<()> {
+    if output_file == Path::new("-") {
+        let mut stdout = std::io::stdout().lock();
+        return stdout
+            .write_all(summary.as_bytes())
+            .and_then(|()| stdout.flush())
+            .context("Failed to write summary to stdout");
+    }
+
+    std::fs::write(output_file, summary)
+        .with_context(|| format!("Failed to write summary to {}", output_file.display()))?;
+    info!("Summary saved to: {}", output_file.display());
+    Ok(())
+}
+
+/// Add a header linking every item, and the footer, to a comparison
+fn format_comparison(items: &[(String, Issue, Vec<Comment>)], content: &str) -> String {
+    let mut summary = format!("# Comparison of {} items\n\n", items.len());
+    for (repo, issue, _) in items {
+        summary.push_str(&format!(
+            "- {} [{}#{}]({}): {}\n",
+            if issue.is_pull_request { "PR" } else { "Issue" },
+            repo,
+            issue.number,
+            issue.url,
+            issue.title
+        ));
+    }
+    format!(
+        "{}\n{}\n\n---\n\n*Summary generated by gh-report v{} for {} items*\n",
+        summary,
+        content.trim_end(),
+        env!("CARGO_PKG_VERSION"),
+        items.len()
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::github::{Author, CommentCount, Label, MockGitHub};
+    use crate::test_utils::test_config;
     use jiff::Timestamp;
 
     fn create_test_issue() -> Issue {
@@ -327,6 +864,10 @@ mod tests {
             url: "https://github.com/test/repo/issues/123".to_string(),
             comments: CommentCount { total_count: 1 },
             is_pull_request: false,
+            assignees: vec![],
+            requested_reviewers: vec![],
+            milestone: None,
+            author_association: None,
         }
     }
 
@@ -350,7 +891,7 @@ mod tests {
         mock.comments.push(create_test_comment());
 
         let github_client = GitHubClient::Mock(mock);
-        let config = Config::default();
+        let config = test_config();
 
         let _summarizer = IssueSummarizer::new(github_client, &config);
 
@@ -368,10 +909,10 @@ mod tests {
         mock.comments.push(comment.clone());
 
         let github_client = GitHubClient::Mock(mock);
-        let config = Config::default();
+        let config = test_config();
         let summarizer = IssueSummarizer::new(github_client, &config);
 
-        let summary = summarizer.generate_basic_summary(&issue, &vec![comment]);
+        let summary = summarizer.generate_basic_summary(&issue, &[comment], &[]);
 
         assert!(summary.contains("# [Issue #123:"));
         assert!(summary.contains("Test issue for summarization"));
@@ -379,5 +920,305 @@ mod tests {
         assert!(summary.contains("`bug`"));
         assert!(summary.contains("This looks like a valid bug report"));
         assert!(summary.contains("@reviewer"));
+        assert!(!summary.contains("## Review Threads"));
+    }
+
+    #[test]
+    fn test_unchanged_issue_reuses_checkpoint() {
+        use tempfile::TempDir;
+
+        let issue = create_test_issue();
+        let mut mock = MockGitHub::new();
+        mock.issues.push(issue.clone());
+
+        let config = test_config();
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        cache.initialize().unwrap();
+        let mut checkpoint = SummaryCheckpoint {
+            issue_number: 123,
+            repo: "test/repo".to_string(),
+            updated_at: issue.updated_at,
+            model: config.claude.resolve_model(&config.claude.primary_model),
+            include_recommendations: true,
+            with_diff: false,
+            language: None,
+            summary: "Cached summary".to_string(),
+            cached_at: Timestamp::now(),
+        };
+        cache.cache_summary_checkpoint(&checkpoint).unwrap();
+
+        let mut summarizer = IssueSummarizer::new(GitHubClient::Mock(mock), &config);
+        summarizer.llm = None;
+        summarizer.cache = Some(cache);
+        let output = temp_dir.path().join("summary.md");
+        let summarize = |summarizer: &IssueSummarizer, include_recommendations| {
+            summarizer
+                .summarize("test/repo#123", Some(&output), include_recommendations)
+                .unwrap();
+            std::fs::read_to_string(&output).unwrap()
+        };
+
+        assert_eq!(summarize(&summarizer, true), "Cached summary");
+        // Generated differently, or the issue changed since
+        assert!(summarize(&summarizer, false).contains("## Description"));
+        checkpoint.updated_at = "2020-01-01T00:00:00Z".parse().unwrap();
+        summarizer
+            .cache
+            .as_ref()
+            .unwrap()
+            .cache_summary_checkpoint(&checkpoint)
+            .unwrap();
+        assert!(summarize(&summarizer, true).contains("## Description"));
+
+        checkpoint.updated_at = issue.updated_at;
+        summarizer
+            .cache
+            .as_ref()
+            .unwrap()
+            .cache_summary_checkpoint(&checkpoint)
+            .unwrap();
+        let summarizer = summarizer.with_refresh(true);
+        assert!(summarize(&summarizer, true).contains("## Description"));
+
+        // Asked for in another language
+        let mut config = test_config();
+        config.claude.output_language = Some("de".to_string());
+        let mut mock = MockGitHub::new();
+        mock.issues.push(issue.clone());
+        let mut german = IssueSummarizer::new(GitHubClient::Mock(mock), &config);
+        german.llm = None;
+        german.cache = summarizer.cache;
+        assert!(summarize(&german, true).contains("## Description"));
+        checkpoint.language = Some("de".to_string());
+        german
+            .cache
+            .as_ref()
+            .unwrap()
+            .cache_summary_checkpoint(&checkpoint)
+            .unwrap();
+        assert_eq!(summarize(&german, true), "Cached summary");
+    }
+
+    #[test]
+    fn test_pr_diff_summary_with_patches() {
+        let mut pr = create_test_issue();
+        pr.is_pull_request = true;
+        pr.url = "https://github.com/test/repo/pull/123".to_string();
+        let mut mock = MockGitHub::new();
+        mock.pr_diffs.push((
+            123,
+            serde_json::from_value(serde_json::json!({
+                "files": [{
+                    "filename": "src/parser.rs",
+                    "status": "modified",
+                    "additions": 1,
+                    "deletions": 1,
+                    "changes": 2,
+                    "patch": "@@ -10 +10 @@\n-    let depth = 0;\n+    let depth = 1;",
+                }],
+                "total_additions": 1,
+                "total_deletions": 1,
+                "total_files": 1,
+            }))
+            .unwrap(),
+        ));
+        let config = test_config();
+        let summarizer = IssueSummarizer::new(GitHubClient::Mock(mock), &config);
+
+        let counts = summarizer.get_pr_diff_summary(&pr).unwrap();
+        assert_eq!(counts, "Modified 1 files. Added 1 lines, removed 1 lines.");
+
+        let summarizer = summarizer.with_diff(true);
+        let with_patches = summarizer.get_pr_diff_summary(&pr).unwrap();
+        assert!(with_patches.contains(
+            "Patches of the most-changed files:\n\nsrc/parser.rs (modified, +1/-1):\n\
+             ```diff\n@@ -10 +10 @@\n-    let depth = 0;\n+    let depth = 1;\n```\n"
+        ));
+    }
+
+    #[test]
+    fn test_ai_summary_becomes_thread_context() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::test_utils::text_response;
+        use tempfile::TempDir;
+
+        let mut mock = MockGitHub::new();
+        mock.issues.push(create_test_issue());
+        mock.comments.push(create_test_comment());
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf(), 24, false);
+        cache.initialize().unwrap();
+
+        let mut summarizer = IssueSummarizer::new(GitHubClient::Mock(mock), &config);
+        summarizer.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new().with_response(text_response("A confirmed bug in the parser.\n")),
+        )));
+        summarizer.cache = Some(cache);
+        let output = temp_dir.path().join("summary.md");
+        summarizer
+            .summarize("test/repo#123", Some(&output), true)
+            .unwrap();
+        assert!(std::fs::read_to_string(&output)
+            .unwrap()
+            .starts_with("# [Issue #123: Test issue for summarization]"));
+
+        let context = summarizer
+            .cache
+            .as_ref()
+            .unwrap()
+            .get_issue_context("test/repo", 123)
+            .unwrap()
+            .unwrap();
+        assert_eq!(context.summary, "A confirmed bug in the parser.");
+        assert_eq!(context.last_processed_comment_id, Some(1));
+    }
+
+    #[test]
+    fn test_streamed_summary() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::test_utils::text_response;
+        use tempfile::TempDir;
+
+        let mut mock = MockGitHub::new();
+        mock.issues.push(create_test_issue());
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+
+        let mut summarizer =
+            IssueSummarizer::new(GitHubClient::Mock(mock), &config).with_stream(true);
+        summarizer.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new().with_response(text_response("A confirmed bug in the parser.\n")),
+        )));
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("summary.md");
+        summarizer
+            .summarize("test/repo#123", Some(&output), true)
+            .unwrap();
+        let summary = std::fs::read_to_string(&output).unwrap();
+        assert!(summary.starts_with("# [Issue #123: Test issue for summarization]"));
+        assert!(summary.contains("A confirmed bug in the parser."));
+    }
+
+    #[test]
+    fn test_long_thread_notes_omitted_comments() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::test_utils::text_response;
+        use tempfile::TempDir;
+
+        let mut mock = MockGitHub::new();
+        mock.issues.push(create_test_issue());
+        for id in 1..=3 {
+            mock.comments.push(Comment {
+                id,
+                ..create_test_comment()
+            });
+        }
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        config.settings.max_comments_per_issue = 1;
+
+        let mut summarizer = IssueSummarizer::new(GitHubClient::Mock(mock), &config);
+        summarizer.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new().with_response(text_response("A confirmed bug in the parser.\n")),
+        )));
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("summary.md");
+        summarizer
+            .summarize("test/repo#123", Some(&output), true)
+            .unwrap();
+        let summary = std::fs::read_to_string(&output).unwrap();
+        assert!(summary.contains(
+            "A confirmed bug in the parser.\n\n*2 of 3 comments were left out of this summary \
+             (settings.max_comments_per_issue, latest)*"
+        ));
+    }
+
+    #[test]
+    fn test_summarize_many() {
+        use crate::claude::{ClaudeClient, MockClaude};
+        use crate::test_utils::text_response;
+        use tempfile::TempDir;
+
+        let mut pr = create_test_issue();
+        pr.number = 124;
+        pr.title = "Fix the parser".to_string();
+        pr.url = "https://github.com/test/repo/pull/124".to_string();
+        pr.is_pull_request = true;
+        let mut mock = MockGitHub::new();
+        mock.issues = vec![create_test_issue(), pr];
+        let mut config = test_config();
+        config.claude.allow_private_repos = true;
+        let targets = [
+            "test/repo#123".to_string(),
+            "test/repo#124".to_string(),
+            "https://github.com/test/repo/issues/123".to_string(),
+        ];
+
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("comparison.md");
+        let mut summarizer = IssueSummarizer::new(GitHubClient::Mock(mock), &config);
+        summarizer.llm = None;
+        summarizer
+            .summarize_many(&targets, Some(&output), true)
+            .unwrap();
+        let basic = std::fs::read_to_string(&output).unwrap();
+        assert!(basic.starts_with(
+            "# Comparison of 2 items\n\n\
+             - Issue [test/repo#123](https://github.com/test/repo/issues/123): Test issue for summarization\n\
+             - PR [test/repo#124](https://github.com/test/repo/pull/124): Fix the parser\n\n"
+        ));
+        assert!(basic.contains("## [test/repo#124: Fix the parser]"));
+
+        summarizer.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new().with_response(text_response(
+                "## Overlaps\n[test/repo#124](https://github.com/test/repo/pull/124) fixes #123.\n",
+            )),
+        )));
+        summarizer
+            .summarize_many(&targets, Some(&output), true)
+            .unwrap();
+        let summary = std::fs::read_to_string(&output).unwrap();
+        assert!(summary.contains(
+            "Fix the parser\n\n## Overlaps\n[test/repo#124](https://github.com/test/repo/pull/124) fixes #123.\n\n---\n"
+        ));
+        assert!(summary.ends_with("for 2 items*\n"));
+    }
+
+    #[test]
+    fn test_basic_summary_lists_unresolved_threads() {
+        use crate::github::{ReviewThread, ReviewThreadComment};
+
+        let mut issue = create_test_issue();
+        issue.is_pull_request = true;
+        let thread = |path: &str, is_resolved, comment_count| ReviewThread {
+            path: path.to_string(),
+            line: Some(42),
+            is_resolved,
+            is_outdated: false,
+            comment_count,
+            comments: vec![ReviewThreadComment {
+                author: "reviewer".to_string(),
+                body: "Should this be bounded?".to_string(),
+            }],
+        };
+
+        let config = test_config();
+        let summarizer = IssueSummarizer::new(GitHubClient::Mock(MockGitHub::new()), &config);
+        let summary = summarizer.generate_basic_summary(
+            &issue,
+            &[],
+            &[
+                thread("src/chan.rs", false, 5),
+                thread("src/lib.rs", true, 2),
+            ],
+        );
+
+        assert!(summary.contains("## Review Threads\n\n1 of 2 resolved."));
+        assert!(summary.contains("`src/chan.rs:42`"));
+        assert!(summary.contains("long-running"));
+        assert!(!summary.contains("src/lib.rs"));
     }
 }
@@ -1,7 +1,11 @@
 //! Test utilities for gh-report
 #![cfg(test)]
 
-use crate::github::{Author, CommentCount, GitHubClient, Issue, IssueState, Label, MockGitHub};
+use crate::claude::{Content, MessagesResponse, Usage};
+use crate::config::Config;
+use crate::github::{
+    Author, CommentCount, GitHubClient, Issue, IssueState, Label, MockGitHub, Owner, Repository,
+};
 use jiff::Timestamp;
 
 /// Create a mock GitHub client with test data
@@ -16,6 +20,14 @@ pub fn create_test_github_client() -> GitHubClient {
     GitHubClient::Mock(mock)
 }
 
+/// The default config with caching turned off, so tests never read or write
+/// the user's cache. Tests that need a cache set one up in a `TempDir`.
+pub fn test_config() -> Config {
+    let mut config = Config::default();
+    config.cache.enabled = false;
+    config
+}
+
 /// Create a test issue
 pub fn create_test_issue(number: u32, title: &str, is_pr: bool) -> Issue {
     Issue {
@@ -37,6 +49,10 @@ pub fn create_test_issue(number: u32, title: &str, is_pr: bool) -> Issue {
         ),
         comments: CommentCount { total_count: 0 },
         is_pull_request: is_pr,
+        assignees: vec![],
+        requested_reviewers: vec![],
+        milestone: None,
+        author_association: None,
     }
 }
 
@@ -53,3 +69,36 @@ pub fn create_test_issue_with_labels(number: u32, title: &str, labels: Vec<&str>
         .collect();
     issue
 }
+
+/// Create a public, unarchived repository, e.g. `mock_repo("test/repo")`
+pub fn mock_repo(full_name: &str) -> Repository {
+    let (owner, name) = full_name.split_once('/').expect("full_name is owner/name");
+    Repository {
+        name: name.to_string(),
+        owner: Owner {
+            login: owner.to_string(),
+        },
+        full_name: full_name.to_string(),
+        description: None,
+        is_private: false,
+        is_archived: false,
+        pushed_at: None,
+        default_branch: None,
+    }
+}
+
+/// Create a finished Claude response with `text` as its only content
+pub fn text_response(text: &str) -> MessagesResponse {
+    MessagesResponse {
+        id: "msg".to_string(),
+        content: vec![Content::Text {
+            text: text.to_string(),
+        }],
+        model: "claude".to_string(),
+        stop_reason: Some("end_turn".to_string()),
+        usage: Usage {
+            input_tokens: 100,
+            output_tokens: 10,
+        },
+    }
+}
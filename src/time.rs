@@ -1,5 +1,9 @@
 use anyhow::{anyhow, Result};
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, ToSpan};
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Parse time duration from a string with optional suffix
 ///
@@ -21,7 +25,7 @@ impl TimeDuration {
 
     /// Convert hours to days (rounded up)
     fn hours_to_days(hours: u32) -> u32 {
-        (hours + 23) / 24 // Round up: 1-24h = 1 day, 25-48h = 2 days, etc.
+        hours.div_ceil(24) // Round up: 1-24h = 1 day, 25-48h = 2 days, etc.
     }
 
     /// Convert weeks to days
@@ -84,6 +88,210 @@ impl std::fmt::Display for TimeDuration {
     }
 }
 
+/// The window a command covers, from `--since` and `--until`
+///
+/// `--since` takes a duration (`7d`), a date (`2024-05-01`), a timestamp
+/// (`2024-05-01T09:00:00Z`) or a keyword: `today`, `yesterday`, `this-week`,
+/// `last-week`, `this-month` or `last-month`. `--until` takes a date, which
+/// is included, a timestamp or a keyword. Keywords naming a finished period,
+/// like `yesterday`, end with it unless `--until` says otherwise.
+///
+/// Dates and keywords are in the local time zone, weeks start on Monday.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeRange {
+    pub start: Timestamp,
+    /// Exclusive end, `None` when the window runs until now
+    pub end: Option<Timestamp>,
+    /// Whether `start` is a fixed point, rather than a duration before now
+    pub absolute: bool,
+    label: String,
+}
+
+impl TimeRange {
+    /// Parse `--since` and `--until` relative to the current time
+    pub fn parse(since: &str, until: Option<&str>) -> Result<Self> {
+        Self::parse_at(since, until, Timestamp::now(), &TimeZone::system())
+    }
+
+    /// Parse `--since` and `--until` as if it were `now` in `tz`
+    pub fn parse_at(
+        since: &str,
+        until: Option<&str>,
+        now: Timestamp,
+        tz: &TimeZone,
+    ) -> Result<Self> {
+        let end = match until {
+            Some(until) => {
+                let (_, end) = parse_point(until, now, tz)?.ok_or_else(|| {
+                    anyhow!(
+                        "Invalid --until '{}'. Use a date (2024-05-07), a timestamp or a keyword like 'yesterday'",
+                        until
+                    )
+                })?;
+                Some(end)
+            }
+            None => None,
+        };
+
+        let (start, implied_end, absolute) = match parse_point(since, now, tz)? {
+            Some((start, end)) => (start, Some(end), true),
+            None => {
+                let duration: TimeDuration = since.parse()?;
+                let hours = duration.as_days() as i64 * 24;
+                match end {
+                    // A duration before the end of a fixed window
+                    Some(end) => (end - hours.hours(), None, true),
+                    None => (now - hours.hours(), None, false),
+                }
+            }
+        };
+
+        // A keyword like `yesterday` ends with its period; `today` and
+        // `this-week` end in the future, which is the same as running until now
+        let end = end
+            .or(implied_end.filter(|_| is_finished_period(since)))
+            .filter(|end| *end < now);
+        if let Some(end) = end {
+            if start >= end {
+                return Err(anyhow!("--since {} is not before --until", since));
+            }
+        }
+
+        let label = match (absolute, end) {
+            (false, _) => format!("the last {}", since_label(since)),
+            (true, None) => format!("since {}", format_point(start, tz)),
+            (true, Some(end)) => {
+                format!("{} to {}", format_point(start, tz), format_end(end, tz))
+            }
+        };
+
+        Ok(TimeRange {
+            start,
+            end,
+            absolute,
+            label,
+        })
+    }
+
+    /// Days back from `now` that must be fetched to reach the start
+    pub fn lookback_days(&self, now: Timestamp) -> u32 {
+        let seconds = now.duration_since(self.start).as_secs().max(0) as u64;
+        (seconds.div_ceil(24 * 60 * 60) as u32).max(1)
+    }
+
+    /// Whether something that happened at `at` falls in the window
+    pub fn contains(&self, at: Timestamp) -> bool {
+        at >= self.start && self.end.is_none_or(|end| at < end)
+    }
+}
+
+impl std::fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+/// The span a date, timestamp or keyword names, `None` for anything else
+fn parse_point(s: &str, now: Timestamp, tz: &TimeZone) -> Result<Option<(Timestamp, Timestamp)>> {
+    let s = s.trim();
+    let today = now.to_zoned(tz.clone()).date();
+    let monday = today - (today.weekday().to_monday_zero_offset() as i64).days();
+
+    let (first, last_exclusive) = match s.to_lowercase().as_str() {
+        "today" => (today, today.tomorrow()?),
+        "yesterday" => (today.yesterday()?, today),
+        "this-week" => (monday, monday + 1.week()),
+        "last-week" => (monday - 1.week(), monday),
+        "this-month" => (today.first_of_month(), today.first_of_month() + 1.month()),
+        "last-month" => (today.first_of_month() - 1.month(), today.first_of_month()),
+        _ => {
+            if let Ok(at) = s.parse::<Timestamp>() {
+                return Ok(Some((at, at)));
+            }
+            match s.parse::<Date>() {
+                Ok(date) => (date, date.tomorrow()?),
+                Err(_) => return Ok(None),
+            }
+        }
+    };
+
+    let start = first.to_zoned(tz.clone())?.timestamp();
+    let end = last_exclusive.to_zoned(tz.clone())?.timestamp();
+    Ok(Some((start, end)))
+}
+
+/// Keywords of a period that is over, which end the window by themselves
+fn is_finished_period(since: &str) -> bool {
+    matches!(
+        since.trim().to_lowercase().as_str(),
+        "yesterday" | "last-week" | "last-month"
+    )
+}
+
+fn since_label(since: &str) -> String {
+    since
+        .parse::<TimeDuration>()
+        .map(|duration| duration.to_string())
+        .unwrap_or_else(|_| since.to_string())
+}
+
+/// A date when `at` is local midnight, the date and time otherwise
+fn format_point(at: Timestamp, tz: &TimeZone) -> String {
+    let zoned = at.to_zoned(tz.clone());
+    if zoned.time() == jiff::civil::Time::midnight() {
+        zoned.strftime("%Y-%m-%d").to_string()
+    } else {
+        zoned.strftime("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// The last day of a window ending at midnight, which is included
+fn format_end(end: Timestamp, tz: &TimeZone) -> String {
+    let zoned = end.to_zoned(tz.clone());
+    if zoned.time() == jiff::civil::Time::midnight() {
+        match zoned.date().yesterday() {
+            Ok(date) => date.to_string(),
+            Err(_) => zoned.date().to_string(),
+        }
+    } else {
+        zoned.strftime("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// Parse an interval such as "30m", "6h", "1d" or "1w"
+///
+/// Unlike [`TimeDuration`], hours and minutes are kept exact, which matters
+/// for schedules.
+pub fn parse_interval(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow!("Interval '{}' needs a unit: m, h, d or w", s))?;
+    let (number, unit) = s.split_at(split);
+
+    let number: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid number in interval: '{}'", s))?;
+    if number == 0 {
+        return Err(anyhow!("Interval must be greater than 0"));
+    }
+
+    let seconds = match unit.to_lowercase().as_str() {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        other => {
+            return Err(anyhow!(
+                "Invalid interval unit '{}'. Use 'm', 'h', 'd' or 'w'",
+                other
+            ))
+        }
+    };
+
+    Ok(Duration::from_secs(number * seconds))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +329,111 @@ mod tests {
         assert!("3x".parse::<TimeDuration>().is_err());
         assert!("3.5d".parse::<TimeDuration>().is_err());
     }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(parse_interval("6h").unwrap(), Duration::from_secs(6 * 3600));
+        assert_eq!(parse_interval("1D").unwrap(), Duration::from_secs(86400));
+        assert_eq!(
+            parse_interval("2w").unwrap(),
+            Duration::from_secs(14 * 86400)
+        );
+
+        assert!(parse_interval("6").is_err());
+        assert!(parse_interval("0h").is_err());
+        assert!(parse_interval("h").is_err());
+        assert!(parse_interval("6x").is_err());
+    }
+
+    fn parse_range(since: &str, until: Option<&str>) -> Result<TimeRange> {
+        // Wednesday
+        let now: Timestamp = "2024-05-15T12:00:00Z".parse().unwrap();
+        TimeRange::parse_at(since, until, now, &TimeZone::UTC)
+    }
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_range_from_duration() {
+        let range = parse_range("7d", None).unwrap();
+        assert_eq!(range.start, ts("2024-05-08T12:00:00Z"));
+        assert_eq!(range.end, None);
+        assert!(!range.absolute);
+        assert_eq!(range.to_string(), "the last 7 days");
+        assert_eq!(range.lookback_days(ts("2024-05-15T12:00:00Z")), 7);
+    }
+
+    #[test]
+    fn test_range_from_dates() {
+        let range = parse_range("2024-05-01", None).unwrap();
+        assert_eq!(range.start, ts("2024-05-01T00:00:00Z"));
+        assert_eq!(range.end, None);
+        assert!(range.absolute);
+        assert_eq!(range.to_string(), "since 2024-05-01");
+        assert_eq!(range.lookback_days(ts("2024-05-15T12:00:00Z")), 15);
+
+        // The day of --until is included
+        let range = range_with("2024-05-01", "2024-05-07");
+        assert_eq!(range.end, Some(ts("2024-05-08T00:00:00Z")));
+        assert_eq!(range.to_string(), "2024-05-01 to 2024-05-07");
+        assert!(range.contains(ts("2024-05-07T23:59:00Z")));
+        assert!(!range.contains(ts("2024-05-08T00:00:00Z")));
+        assert!(!range.contains(ts("2024-04-30T23:59:00Z")));
+
+        let range = parse_range("2024-05-10T09:30:00Z", None).unwrap();
+        assert_eq!(range.start, ts("2024-05-10T09:30:00Z"));
+        assert_eq!(range.to_string(), "since 2024-05-10 09:30");
+
+        // A duration counts back from the end of the window
+        let range = range_with("3d", "2024-05-07");
+        assert_eq!(range.start, ts("2024-05-05T00:00:00Z"));
+        assert!(range.absolute);
+    }
+
+    fn range_with(since: &str, until: &str) -> TimeRange {
+        parse_range(since, Some(until)).unwrap()
+    }
+
+    #[test]
+    fn test_range_keywords() {
+        let yesterday = parse_range("yesterday", None).unwrap();
+        assert_eq!(yesterday.start, ts("2024-05-14T00:00:00Z"));
+        assert_eq!(yesterday.end, Some(ts("2024-05-15T00:00:00Z")));
+        assert_eq!(yesterday.to_string(), "2024-05-14 to 2024-05-14");
+
+        let today = parse_range("today", None).unwrap();
+        assert_eq!(today.start, ts("2024-05-15T00:00:00Z"));
+        assert_eq!(today.end, None);
+
+        let last_week = parse_range("last-week", None).unwrap();
+        assert_eq!(last_week.start, ts("2024-05-06T00:00:00Z"));
+        assert_eq!(last_week.end, Some(ts("2024-05-13T00:00:00Z")));
+        assert_eq!(last_week.to_string(), "2024-05-06 to 2024-05-12");
+
+        let this_week = parse_range("This-Week", None).unwrap();
+        assert_eq!(this_week.start, ts("2024-05-13T00:00:00Z"));
+        assert_eq!(this_week.end, None);
+
+        let last_month = parse_range("last-month", None).unwrap();
+        assert_eq!(last_month.start, ts("2024-04-01T00:00:00Z"));
+        assert_eq!(last_month.end, Some(ts("2024-05-01T00:00:00Z")));
+
+        // --until overrides the end of the keyword
+        let range = range_with("last-month", "yesterday");
+        assert_eq!(range.end, Some(ts("2024-05-15T00:00:00Z")));
+        // An end that has not come yet is now
+        assert_eq!(range_with("2024-05-01", "today").end, None);
+    }
+
+    #[test]
+    fn test_range_errors() {
+        assert!(parse_range("2024-05-07", Some("2024-05-01")).is_err());
+        assert!(parse_range("today", Some("yesterday")).is_err());
+        assert!(parse_range("7d", Some("7d")).is_err());
+        assert!(parse_range("2024-13-01", None).is_err());
+        assert!(parse_range("someday", None).is_err());
+    }
 }
@@ -0,0 +1,452 @@
+//! Triage worksheets for `gh-report triage`
+//!
+//! Open issues without labels, or opened within the lookback window, are sent
+//! to Claude along with the labels of the repository and the titles of its
+//! other open issues. The suggested labels, possible duplicates, and first
+//! responses are written to a markdown worksheet to go through by hand. With
+//! `--apply`, the suggested labels are also added on GitHub.
+
+use anyhow::{anyhow, Context, Result};
+use jiff::Timestamp;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::claude::prompts::{repo_context_section, triage_issues_prompt};
+use crate::claude::{
+    backend_from_config, confirm_private_repos, find_private_repos, LlmBackend, Message,
+    MessagesRequest,
+};
+use crate::config::Config;
+use crate::github::{GitHubClient, Issue, IssueState, Label};
+
+/// Most other open issues offered to Claude as possible duplicates
+const MAX_DUPLICATE_CANDIDATES: usize = 200;
+
+/// What Claude suggests for one issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriageSuggestion {
+    pub number: u32,
+    /// Labels of the repository, as spelled there
+    pub labels: Vec<String>,
+    /// Numbers of other open issues this one may duplicate
+    pub duplicates: Vec<u32>,
+    /// A first response to the author
+    pub response: String,
+}
+
+/// Read the `#<number>` blocks of a triage response
+///
+/// Labels that the repository doesn't have and duplicates that aren't among
+/// `open_issues` are dropped, so a worksheet only suggests what can be acted on.
+pub fn parse_triage_response(
+    response: &str,
+    labels: &[Label],
+    open_issues: &[u32],
+) -> Vec<TriageSuggestion> {
+    let mut suggestions: Vec<TriageSuggestion> = Vec::new();
+    for line in response.lines() {
+        let line = line.trim();
+        if let Some(number) = line.strip_prefix('#').and_then(|n| n.parse().ok()) {
+            suggestions.push(TriageSuggestion {
+                number,
+                labels: Vec::new(),
+                duplicates: Vec::new(),
+                response: String::new(),
+            });
+            continue;
+        }
+        let Some(suggestion) = suggestions.last_mut() else {
+            continue;
+        };
+
+        if let Some(names) = line.strip_prefix("LABELS:") {
+            suggestion.labels = list(names)
+                .filter_map(|name| {
+                    labels
+                        .iter()
+                        .find(|label| label.name.eq_ignore_ascii_case(name))
+                        .map(|label| label.name.clone())
+                })
+                .collect();
+        } else if let Some(numbers) = line.strip_prefix("DUPLICATES:") {
+            let own = suggestion.number;
+            suggestion.duplicates = list(numbers)
+                .filter_map(|n| n.trim_start_matches('#').parse().ok())
+                .filter(|n| *n != own && open_issues.contains(n))
+                .collect();
+        } else if let Some(text) = line.strip_prefix("RESPONSE:") {
+            suggestion.response = text.trim().to_string();
+        } else if !suggestion.response.is_empty() {
+            // The response may run over several lines
+            suggestion.response.push('\n');
+            suggestion.response.push_str(line);
+        }
+    }
+
+    for suggestion in &mut suggestions {
+        suggestion.response = suggestion.response.trim().to_string();
+    }
+    suggestions
+}
+
+/// Entries of a comma-separated list, without `none`
+fn list(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && !entry.eq_ignore_ascii_case("none"))
+}
+
+/// Split the open issues of a repository into the ones to triage, newest
+/// first, and the others
+///
+/// Issues are triaged when they have no labels or were opened after `since`.
+pub fn triage_candidates(
+    issues: &[Issue],
+    since: Timestamp,
+    limit: usize,
+) -> (Vec<&Issue>, Vec<&Issue>) {
+    let (mut candidates, mut others): (Vec<&Issue>, Vec<&Issue>) = issues
+        .iter()
+        .filter(|issue| !issue.is_pull_request && issue.state == IssueState::Open)
+        .partition(|issue| issue.labels.is_empty() || issue.created_at >= since);
+
+    candidates.sort_by_key(|issue| std::cmp::Reverse(issue.created_at));
+    others.extend(candidates.drain(limit.min(candidates.len())..));
+    others.sort_by_key(|issue| std::cmp::Reverse(issue.updated_at));
+    others.truncate(MAX_DUPLICATE_CANDIDATES);
+    (candidates, others)
+}
+
+/// Render the worksheet of a triage run
+///
+/// `applied` holds the issues whose suggested labels were added on GitHub.
+pub fn render_worksheet(
+    repo: &str,
+    issues: &[&Issue],
+    suggestions: &[TriageSuggestion],
+    open_issues: &[&Issue],
+    applied: &BTreeSet<u32>,
+    generated_at: Timestamp,
+) -> String {
+    let mut output = format!(
+        "# Triage: {}\n\n*{} issues to triage, generated {}*\n\n",
+        repo,
+        issues.len(),
+        generated_at.strftime("%Y-%m-%d %H:%M UTC")
+    );
+
+    for issue in issues {
+        output.push_str(&format!(
+            "## [#{} {}]({})\n\n*Opened by @{} on {}*\n\n",
+            issue.number,
+            issue.title,
+            issue.url,
+            issue.author.login,
+            issue.created_at.strftime("%Y-%m-%d")
+        ));
+
+        let Some(suggestion) = suggestions.iter().find(|s| s.number == issue.number) else {
+            output.push_str("No suggestions.\n\n");
+            continue;
+        };
+
+        let labels = if suggestion.labels.is_empty() {
+            "none".to_string()
+        } else {
+            let names: Vec<String> = suggestion
+                .labels
+                .iter()
+                .map(|label| format!("`{}`", label))
+                .collect();
+            names.join(", ")
+        };
+        output.push_str(&format!(
+            "- **Labels:** {}{}\n",
+            labels,
+            if applied.contains(&issue.number) {
+                " (applied)"
+            } else {
+                ""
+            }
+        ));
+
+        if !suggestion.duplicates.is_empty() {
+            let duplicates: Vec<String> = suggestion
+                .duplicates
+                .iter()
+                .filter_map(|n| open_issues.iter().find(|i| i.number == *n))
+                .map(|i| format!("[#{} {}]({})", i.number, i.title, i.url))
+                .collect();
+            output.push_str(&format!(
+                "- **Possible duplicate of:** {}\n",
+                duplicates.join(", ")
+            ));
+        }
+
+        if !suggestion.response.is_empty() {
+            output.push_str("\n**Suggested response:**\n\n");
+            for line in suggestion.response.lines() {
+                if line.is_empty() {
+                    output.push_str(">\n");
+                } else {
+                    output.push_str(&format!("> {}\n", line));
+                }
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// File name of a worksheet, e.g. `tokio-triage-2024-03-06.md`
+fn worksheet_filename(repo: &str, generated_at: Timestamp) -> String {
+    let name = repo.split('/').nth(1).unwrap_or(repo);
+    format!("{}-triage-{}.md", name, generated_at.strftime("%Y-%m-%d"))
+}
+
+/// Suggests labels, duplicates, and first responses for new issues
+pub struct Triager<'a> {
+    github_client: GitHubClient,
+    llm: Option<Box<dyn LlmBackend>>,
+    config: &'a Config,
+    apply: bool,
+    limit: usize,
+}
+
+impl<'a> Triager<'a> {
+    pub fn new(github_client: GitHubClient, config: &'a Config) -> Self {
+        let llm = match backend_from_config(config) {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Failed to initialize LLM backend: {}", e);
+                None
+            }
+        };
+
+        Triager {
+            github_client,
+            llm,
+            config,
+            apply: false,
+            limit: 20,
+        }
+    }
+
+    /// Add the suggested labels on GitHub
+    pub fn with_apply(mut self, apply: bool) -> Self {
+        self.apply = apply;
+        self
+    }
+
+    /// Triage at most `limit` issues, the newest ones
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Triage the issues of `repo` and write the worksheet
+    ///
+    /// Returns the path of the worksheet.
+    pub fn triage(
+        &self,
+        repo: &str,
+        since: Timestamp,
+        output_path: Option<&Path>,
+    ) -> Result<PathBuf> {
+        let claude = self
+            .llm
+            .as_deref()
+            .ok_or_else(|| anyhow!("Triage needs Claude, but no LLM backend is available"))?;
+        if !self.config.claude.allow_private_repos {
+            let private_repos = find_private_repos(&self.github_client, None, [repo]);
+            if !confirm_private_repos(&self.config.claude, &claude.destination(), &private_repos)? {
+                return Err(anyhow!(
+                    "Not sending issues of the private {} to {}",
+                    repo,
+                    claude.destination()
+                ));
+            }
+        }
+
+        info!("Fetching open issues of {}", repo);
+        let issues = self
+            .github_client
+            .fetch_issues(repo, None)
+            .with_context(|| format!("Failed to fetch issues of {}", repo))?;
+        let labels = self
+            .github_client
+            .fetch_labels(repo)
+            .with_context(|| format!("Failed to fetch labels of {}", repo))?;
+        let (candidates, others) = triage_candidates(&issues, since, self.limit);
+        let open_issues: Vec<&Issue> = candidates.iter().chain(&others).copied().collect();
+        info!("Triaging {} issues of {}", candidates.len(), repo);
+
+        let suggestions = if candidates.is_empty() {
+            Vec::new()
+        } else {
+            let mut prompt = triage_issues_prompt(repo, &labels, &candidates, &others);
+            if let Some(context) = self.config.repo_context(repo) {
+                prompt = format!("{}{}", repo_context_section(repo, &context), prompt);
+            }
+            let model = self
+                .config
+                .claude
+                .resolve_model(&self.config.claude.primary_model);
+            let request =
+                MessagesRequest::new(model, vec![Message::user(prompt)]).with_max_tokens(4000);
+            let response = claude
+                .messages(request)
+                .context("Failed to get triage suggestions from Claude")?;
+            let numbers: Vec<u32> = open_issues.iter().map(|i| i.number).collect();
+            parse_triage_response(&response.get_text(), &labels, &numbers)
+        };
+
+        let mut applied = BTreeSet::new();
+        if self.apply {
+            for suggestion in &suggestions {
+                let Some(issue) = candidates.iter().find(|i| i.number == suggestion.number) else {
+                    continue;
+                };
+                let new_labels: Vec<String> = suggestion
+                    .labels
+                    .iter()
+                    .filter(|name| !issue.labels.iter().any(|l| &l.name == *name))
+                    .cloned()
+                    .collect();
+                if new_labels.is_empty() {
+                    continue;
+                }
+                match self
+                    .github_client
+                    .add_labels(repo, issue.number, &new_labels)
+                {
+                    Ok(()) => {
+                        info!(
+                            "Labeled {}#{}: {}",
+                            repo,
+                            issue.number,
+                            new_labels.join(", ")
+                        );
+                        applied.insert(issue.number);
+                    }
+                    Err(e) => warn!("Failed to label {}#{}: {}", repo, issue.number, e),
+                }
+            }
+        }
+
+        let now = Timestamp::now();
+        let worksheet =
+            render_worksheet(repo, &candidates, &suggestions, &open_issues, &applied, now);
+        let output_file = match output_path {
+            Some(path) => path.to_path_buf(),
+            None => std::env::current_dir()?.join(worksheet_filename(repo, now)),
+        };
+        std::fs::write(&output_file, worksheet)
+            .with_context(|| format!("Failed to write worksheet to {}", output_file.display()))?;
+        Ok(output_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::{ClaudeClient, MockClaude};
+    use crate::github::MockGitHub;
+    use crate::test_utils::text_response;
+    use crate::test_utils::{create_test_issue, create_test_issue_with_labels};
+    use jiff::ToSpan;
+    use tempfile::TempDir;
+
+    fn label(name: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_triage_response() {
+        let response = "#12\nLABELS: Bug, A-unknown\nDUPLICATES: #7, #12, #99\n\
+                        RESPONSE: Thanks for the report!\nCould you share a reproduction?\n\n\
+                        #13\nLABELS: none\nDUPLICATES: none\nRESPONSE: Thanks!\n";
+        let suggestions = parse_triage_response(response, &[label("bug")], &[7, 12, 13]);
+        assert_eq!(
+            suggestions,
+            [
+                TriageSuggestion {
+                    number: 12,
+                    labels: vec!["bug".to_string()],
+                    duplicates: vec![7],
+                    response: "Thanks for the report!\nCould you share a reproduction?".to_string(),
+                },
+                TriageSuggestion {
+                    number: 13,
+                    labels: vec![],
+                    duplicates: vec![],
+                    response: "Thanks!".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_triage_worksheet() {
+        let since = Timestamp::now() - 168.hours();
+        let mut unlabeled = create_test_issue(12, "Panic on shutdown", false);
+        unlabeled.created_at = since - 24.hours();
+        let mut triaged =
+            create_test_issue_with_labels(7, "Runtime panics when dropped", vec!["bug"]);
+        triaged.created_at = since - 24.hours();
+        let labeled_new = create_test_issue_with_labels(13, "Add a timeout", vec!["enhancement"]);
+
+        let mut mock = MockGitHub::new();
+        mock.issues = vec![
+            triaged,
+            unlabeled,
+            labeled_new,
+            create_test_issue(14, "Fix a typo", true),
+        ];
+        mock.labels = vec![label("bug"), label("enhancement")];
+        let mut config = Config::default();
+        config.claude.allow_private_repos = true;
+
+        let mut triager = Triager::new(GitHubClient::Mock(mock), &config).with_apply(true);
+        triager.llm = Some(Box::new(ClaudeClient::Mock(
+            MockClaude::new().with_response(text_response(
+                "#13\nLABELS: enhancement\nDUPLICATES: none\nRESPONSE: Thanks!\n\n\
+                           #12\nLABELS: bug\nDUPLICATES: #7\nRESPONSE: Is this the same as #7?\n",
+            )),
+        )));
+
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("triage.md");
+        triager.triage("test/repo", since, Some(&output)).unwrap();
+        let worksheet = std::fs::read_to_string(&output).unwrap();
+
+        assert!(worksheet.starts_with("# Triage: test/repo\n\n*2 issues to triage"));
+        // Newest first
+        let new_issue = worksheet.find("## [#13 Add a timeout]").unwrap();
+        let old_issue = worksheet
+            .find("## [#12 Panic on shutdown](https://github.com/test/repo/issues/12)")
+            .unwrap();
+        assert!(new_issue < old_issue);
+        assert!(worksheet.contains("- **Labels:** `bug` (applied)\n"));
+        assert!(worksheet.contains("- **Labels:** `enhancement`\n"));
+        assert!(worksheet.contains(
+            "- **Possible duplicate of:** [#7 Runtime panics when dropped](https://github.com/test/repo/issues/7)\n"
+        ));
+        assert!(worksheet.contains("**Suggested response:**\n\n> Is this the same as #7?\n"));
+        assert!(!worksheet.contains("Fix a typo"));
+
+        let GitHubClient::Mock(mock) = &triager.github_client else {
+            unreachable!()
+        };
+        assert_eq!(
+            *mock.added_labels.lock().unwrap(),
+            [(12, vec!["bug".to_string()])]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Support for `gh-report watch`, which regenerates the report on a schedule
+//!
+//! Each tick first does a cheap check of the activity feed against
+//! `State::last_run`; the report (and any Claude calls) only runs when
+//! something new showed up.
+
+use anyhow::Result;
+use jiff::Timestamp;
+
+use crate::activity::{drop_ignored_authors, filter_events, issue_references};
+use crate::config::ReportConfig;
+use crate::github::{ActivityEvent, GitHubClient};
+use crate::State;
+
+/// Number of reportable events newer than the last run
+///
+/// Events the report leaves out, by `include_repos`/`exclude_repos`,
+/// `ignore_authors`, or because their item is muted, don't count. With no
+/// previous run, every reportable event counts as new.
+pub fn count_new_events(events: &[ActivityEvent], report: &ReportConfig, state: &State) -> usize {
+    let events = filter_events(events, None, None)
+        .into_iter()
+        .filter(|event| state.last_run.is_none_or(|since| event.created_at > since))
+        .filter(|event| report.includes_repo(&event.repo.name))
+        .collect();
+    let (events, _) = drop_ignored_authors(events, |author| report.ignores_author(author));
+    events
+        .into_iter()
+        .filter(|event| {
+            !issue_references(&[*event])
+                .iter()
+                .any(|(repo, number)| state.is_muted(repo, *number))
+        })
+        .count()
+}
+
+/// Check the activity feed for events since the last report
+pub fn check_for_new_activity(
+    github_client: &GitHubClient,
+    report: &ReportConfig,
+    state: &State,
+    max_lookback_days: u32,
+) -> Result<usize> {
+    let days = match state.last_run {
+        Some(last_run) => {
+            let hours = Timestamp::now().duration_since(last_run).as_hours().max(0);
+            (hours as u32 / 24 + 1).min(max_lookback_days)
+        }
+        None => max_lookback_days,
+    };
+
+    let events = github_client.fetch_activity(days)?;
+    Ok(count_new_events(&events, report, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{ActivityRepo, Author};
+    use jiff::ToSpan;
+
+    fn event(event_type: &str, created_at: Timestamp) -> ActivityEvent {
+        ActivityEvent {
+            id: "1".to_string(),
+            event_type: event_type.to_string(),
+            actor: Author {
+                login: "alice".to_string(),
+                user_type: None,
+            },
+            repo: ActivityRepo {
+                id: 1,
+                name: "owner/repo".to_string(),
+                url: "https://api.github.com/repos/owner/repo".to_string(),
+            },
+            payload: serde_json::json!({ "action": "created" }),
+            created_at,
+            is_public: true,
+        }
+    }
+
+    fn state_since(last_run: Option<Timestamp>) -> State {
+        State {
+            last_run,
+            ..State::default()
+        }
+    }
+
+    #[test]
+    fn test_count_new_events() {
+        let last_run = Timestamp::now() - 6.hours();
+        let events = vec![
+            event("IssueCommentEvent", last_run - 1.hour()),
+            event("IssueCommentEvent", last_run + 1.hour()),
+            event("PullRequestEvent", last_run + 2.hours()),
+            // Not reportable, so it doesn't trigger a report
+            event("WatchEvent", last_run + 3.hours()),
+        ];
+
+        let report = ReportConfig::default();
+        assert_eq!(
+            count_new_events(&events, &report, &state_since(Some(last_run))),
+            2
+        );
+        assert_eq!(count_new_events(&events, &report, &state_since(None)), 3);
+        assert_eq!(
+            count_new_events(&events, &report, &state_since(Some(Timestamp::now()))),
+            0
+        );
+    }
+
+    #[test]
+    fn test_count_new_events_left_out_of_the_report() {
+        let last_run = Timestamp::now() - 6.hours();
+        let mut other_repo = event("IssueCommentEvent", last_run + 1.hour());
+        other_repo.repo.name = "other/repo".to_string();
+        let mut ignored = event("IssueCommentEvent", last_run + 1.hour());
+        ignored.actor.login = "renovate".to_string();
+        let mut muted = event("IssueCommentEvent", last_run + 1.hour());
+        muted.payload = serde_json::json!({ "action": "created", "issue": { "number": 7 } });
+        let events = vec![
+            event("IssueCommentEvent", last_run + 1.hour()),
+            other_repo,
+            ignored,
+            muted,
+        ];
+        let report = ReportConfig {
+            include_repos: vec!["owner/*".to_string()],
+            ignore_authors: vec!["renovate".to_string()],
+            ..ReportConfig::default()
+        };
+        let mut state = state_since(Some(last_run));
+        state.mute("owner/repo", 7, Timestamp::now());
+
+        assert_eq!(count_new_events(&events, &report, &state), 1);
+    }
+}